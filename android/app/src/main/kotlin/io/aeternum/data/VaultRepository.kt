@@ -46,7 +46,7 @@ class VaultRepository {
      */
     suspend fun initializeVault(hardwareKeyBlob: ByteArray): Result<Unit> = withContext(Dispatchers.IO) {
         try {
-            engine.initializeVault(hardwareKeyBlob)
+            engine.initializeHardwareVault(hardwareKeyBlob)
             Result.success(Unit)
         } catch (e: PqrrException) {
             Result.failure(mapPqrrError(e))
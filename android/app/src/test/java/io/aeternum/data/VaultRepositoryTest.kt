@@ -59,14 +59,14 @@ class VaultRepositoryTest {
     fun `initializeVault 应该成功初始化 Vault`() = runTest {
         // Given
         val hardwareKeyBlob = byteArrayOf(0x01, 0x02, 0x03, 0x04)
-        every { mockEngine.initializeVault(any()) } just Runs
+        every { mockEngine.initializeHardwareVault(any()) } just Runs
 
         // When
         val result = repository.initializeVault(hardwareKeyBlob)
 
         // Then
         assertTrue(result.isSuccess)
-        verify { mockEngine.initializeVault(any()) }
+        verify { mockEngine.initializeHardwareVault(any()) }
     }
 
     @Test
@@ -76,7 +76,7 @@ class VaultRepositoryTest {
         val pqrrError = mockk<PqrrException.StorageException>(relaxed = true) {
             every { message } returns "存储空间不足"
         }
-        every { mockEngine.initializeVault(any()) } throws pqrrError
+        every { mockEngine.initializeHardwareVault(any()) } throws pqrrError
 
         // When
         val result = repository.initializeVault(hardwareKeyBlob)
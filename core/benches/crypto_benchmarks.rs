@@ -0,0 +1,180 @@
+//! Kyber + AEAD Hot Loop Benchmarks
+//!
+//! These benchmarks cover the rekey path (N devices × Kyber encapsulation +
+//! AEAD re-encryption of the vault) and the wire send path.
+//!
+//! Argon2id with the default config is covered by `kdf_benchmarks`.
+//!
+//! The `parallel_rekey` group compares the sequential and rayon paths; run
+//! it with `--features parallel` to measure the speedup.
+//!
+//! Run with: `cargo bench --bench crypto_benchmarks`
+
+use aeternum_core::crypto::aead::{stream, AeadCipher, XChaCha20Key, XChaCha20Nonce};
+use aeternum_core::crypto::kem::{wrap_dek_for_devices, KyberKEM};
+use aeternum_core::models::{DataEncryptionKey, DeviceId};
+use aeternum_core::sync::codec::PayloadType;
+use aeternum_core::sync::wire::WireProtocol;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Benchmark a single Kyber-1024 encapsulation and decapsulation
+fn bench_kyber_single(c: &mut Criterion) {
+    let keypair = KyberKEM::generate_keypair();
+    let (_ss, ciphertext) = KyberKEM::encapsulate(&keypair.public).unwrap();
+
+    let mut group = c.benchmark_group("kyber1024");
+
+    group.bench_function("encapsulate", |b| {
+        b.iter(|| KyberKEM::encapsulate(black_box(&keypair.public)))
+    });
+
+    group.bench_function("decapsulate", |b| {
+        b.iter(|| KyberKEM::decapsulate(black_box(&keypair.secret), black_box(&ciphertext)))
+    });
+
+    group.finish();
+}
+
+/// Benchmark a 10-device batch rekey
+///
+/// One Kyber encapsulation per device header plus AEAD re-encryption of a
+/// 1 MB vault under the new DEK.
+fn bench_batch_rekey(c: &mut Criterion) {
+    let devices: Vec<_> = (0..10).map(|_| KyberKEM::generate_keypair()).collect();
+    let vault = vec![0x5Au8; 1024 * 1024];
+
+    let mut group = c.benchmark_group("rekey");
+    group.sample_size(20);
+
+    group.bench_function("10_devices_1MB_vault", |b| {
+        b.iter(|| {
+            for device in &devices {
+                black_box(KyberKEM::encapsulate(&device.public).unwrap());
+            }
+
+            let new_dek = XChaCha20Key::generate();
+            let nonce = XChaCha20Nonce::random();
+            AeadCipher::new(&new_dek)
+                .encrypt(&nonce, black_box(&vault), None)
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+/// Benchmark per-device DEK wrapping and chunked vault encryption
+///
+/// Each `sequential` case runs on the calling thread; the `parallel` cases
+/// use the rayon pool when built with `--features parallel` and fall back
+/// to the same sequential loop otherwise.
+fn bench_parallel_rekey(c: &mut Criterion) {
+    let pubkeys: Vec<_> = (0..10)
+        .map(|_| (DeviceId::generate(), KyberKEM::generate_keypair().public))
+        .collect();
+    let dek = DataEncryptionKey::generate();
+
+    let cipher = AeadCipher::new(&XChaCha20Key::generate());
+    let nonce = XChaCha20Nonce::random();
+    let vault = vec![0x5Au8; 16 * 1024 * 1024];
+
+    let mut group = c.benchmark_group("parallel_rekey");
+    group.sample_size(20);
+
+    group.bench_function("wrap_10_devices_sequential", |b| {
+        b.iter(|| {
+            pubkeys
+                .iter()
+                .map(|(_, public_key)| dek.wrap_for_device(public_key))
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.bench_function("wrap_10_devices_parallel", |b| {
+        b.iter(|| wrap_dek_for_devices(black_box(&dek), black_box(&pubkeys)))
+    });
+
+    group.throughput(Throughput::Bytes(vault.len() as u64));
+
+    group.bench_function("stream_16MB_sequential", |b| {
+        b.iter(|| stream::seal_sequential(&cipher, &nonce, black_box(&vault)).unwrap())
+    });
+
+    group.bench_function("stream_16MB_parallel", |b| {
+        b.iter(|| stream::seal(&cipher, &nonce, black_box(&vault)).unwrap())
+    });
+
+    group.finish();
+}
+
+/// Benchmark AEAD encryption of large payloads
+fn bench_aead_payloads(c: &mut Criterion) {
+    let key = XChaCha20Key::generate();
+    let cipher = AeadCipher::new(&key);
+    let nonce = XChaCha20Nonce::random();
+
+    let mut group = c.benchmark_group("aead_encrypt");
+    group.sample_size(20);
+
+    for size_mb in [1usize, 16] {
+        let payload = vec![0xA5u8; size_mb * 1024 * 1024];
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}MB", size_mb)),
+            &payload,
+            |b, payload| b.iter(|| cipher.encrypt(&nonce, black_box(payload), None)),
+        );
+    }
+
+    group.finish();
+}
+
+/// Benchmark the wire send path
+///
+/// `send_message` measures the full frame path. The two `encrypt_*` cases
+/// isolate the cost of constructing the cipher for every frame versus
+/// reusing the per-session cipher held by `WireProtocol`.
+fn bench_wire_send(c: &mut Criterion) {
+    let key = XChaCha20Key::generate();
+    let payload = vec![0x42u8; 1024];
+
+    let mut group = c.benchmark_group("wire_send");
+
+    group.bench_function("send_message", |b| {
+        let mut protocol = WireProtocol::new(key.clone());
+        b.iter(|| {
+            protocol
+                .send_message(PayloadType::EpochSync, black_box(payload.clone()), 1)
+                .unwrap()
+        })
+    });
+
+    group.bench_function("encrypt_cached_cipher", |b| {
+        let cipher = AeadCipher::new(&key);
+        b.iter(|| {
+            let nonce = XChaCha20Nonce::random();
+            cipher.encrypt(&nonce, black_box(&payload), None).unwrap()
+        })
+    });
+
+    group.bench_function("encrypt_cipher_per_frame", |b| {
+        b.iter(|| {
+            let cipher = AeadCipher::new(black_box(&key));
+            let nonce = XChaCha20Nonce::random();
+            cipher.encrypt(&nonce, black_box(&payload), None).unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_kyber_single,
+    bench_batch_rekey,
+    bench_parallel_rekey,
+    bench_aead_payloads,
+    bench_wire_send
+);
+criterion_main!(benches);
@@ -0,0 +1,15 @@
+//! Fuzz `DeviceHeader` deserialization.
+//!
+//! Any accepted header must survive a serialize/deserialize round trip.
+
+#![no_main]
+
+use aeternum_core::models::DeviceHeader;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(header) = DeviceHeader::deserialize(data) {
+        let bytes = header.serialize();
+        assert_eq!(DeviceHeader::deserialize(&bytes).ok(), Some(header));
+    }
+});
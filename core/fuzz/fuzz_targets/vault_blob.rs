@@ -0,0 +1,19 @@
+//! Fuzz `VaultBlob` deserialization and `VaultHeader` parsing.
+//!
+//! Any accepted blob must survive a serialize/deserialize round trip.
+
+#![no_main]
+
+use aeternum_core::models::{VaultBlob, VaultHeader};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = VaultHeader::from_bytes(data);
+
+    if let Ok(blob) = VaultBlob::deserialize(data) {
+        let _ = blob.validate();
+        let bytes = blob.serialize().expect("parsed blob must re-serialize");
+        let reparsed = VaultBlob::deserialize(&bytes).expect("re-serialized blob must parse");
+        assert_eq!(reparsed.serialize().ok(), Some(bytes));
+    }
+});
@@ -0,0 +1,19 @@
+//! Fuzz `WireFrame` parsing under every supported frame profile.
+//!
+//! Any accepted frame must serialize back to the exact input bytes.
+
+#![no_main]
+
+use aeternum_core::sync::frame::WireFrame;
+use aeternum_core::sync::{PROFILE_BLE, PROFILE_DEFAULT};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    for profile in [PROFILE_DEFAULT, PROFILE_BLE] {
+        if let Ok(frame) = WireFrame::deserialize_with_profile(data, profile) {
+            let _ = frame.validate_with_profile(profile);
+            let bytes = frame.serialize().expect("parsed frame must re-serialize");
+            assert_eq!(bytes, data);
+        }
+    }
+});
@@ -6,22 +6,164 @@
 //!
 //! ```text
 //! Kotlin UI → AeternumEngine → Protocol/Storage Layers
+//!            ↓ initialize_vault() (first run)
+//!            ↓ InitReport
+//!            ↓ open_vault_with_mnemonic() (existing vault)
+//!            ↓ open_vault() (existing vault, this device's key)
 //!            ↓ unlock()
 //!            ↓ VaultSession (handle)
+//!            ↓ unlock_with_password()
+//!            ↓ VaultSessionHandle (session ID)
 //!            ↓ get_device_list()
 //!            ↓ Vec<DeviceInfo>
+//!            ↓ list_devices()
+//!            ↓ Vec<DeviceSummary>
+//!            ↓ put_item() / get_item() / delete_item() / list_items()
+//!            ↓ upgrade_epoch()
+//!            ↓ get_upgrade_progress() (polled from another thread)
 //! ```
+//!
+//! ## Degraded Mode
+//!
+//! While the state machine is `Degraded`, mutating operations fail with
+//! `PqrrError::ReadOnlyMode`; read and decrypt operations keep working.
+//! `recheck_integrity()` returns the engine to `Idle` once a fresh integrity
+//! token passes.
+//!
+//! ## First-Run Bootstrap
+//!
+//! `initialize_vault()` turns a fresh mnemonic into a complete vault
+//! directory:
+//!
+//! ```text
+//! vault.db          [VaultHeader:32][VaultBlob]   (epoch 1, empty payload)
+//! vault.db.headers  [Device_0 shadow anchor header]
+//! vault.db.anchor   [Nonce:24][XChaCha20-Poly1305(RecoveryKey, Device_0 secret key)]
+//! vault.db.attempts Recovery attempt history (empty)
+//! metadata.db       Local_Epoch = 1
+//! ```
+//!
+//! Device_0's key pair is derived from the RecoveryKey
+//! (`RecoveryKey::derive_anchor_keypair`), and its secret key is also sealed
+//! under the RecoveryKey. The mnemonic alone re-derives the RecoveryKey,
+//! which makes `cold_recover()` possible with nothing but the vault
+//! directory.
+//!
+//! Replacing an existing vault (`force_overwrite`) writes the new vault into
+//! a staging directory next to it (`vault.db.init/`) and only then renames
+//! its files over the old ones, so a failed write leaves the old vault
+//! intact.
+//!
+//! ## Password Unlock
+//!
+//! The vault key (VK) can be wrapped under a password-derived key:
+//!
+//! ```text
+//! KEK = Argon2id(password, salt)
+//! wrapped_vk = [Nonce:24][XChaCha20-Poly1305(KEK, VK, aad = VK_WRAP_AAD)]
+//! ```
+//!
+//! The wrapped VK is stored next to the vault as `vault.db.vkwrap`, so an
+//! enrolled password keeps working after the engine is recreated.
+//!
+//! ## Keystore Wrapping
+//!
+//! With a key wrapper set (`set_hardware_key_wrapper()`, backed by
+//! StrongBox on Android), key material stored on the device is also wrapped
+//! by the keystore:
+//!
+//! ```text
+//! vault.db.devkey   0x01 || KeyWrapper::wrap(this device's Kyber secret key)
+//! vault.db.vkwrap   0x01 || KeyWrapper::wrap(wrapped_vk)
+//! ```
+//!
+//! `initialize_vault()` then also registers this device with a fresh key
+//! pair and a header carrying the DEK, and `open_vault()` reopens the vault
+//! with the stored secret key instead of the mnemonic. A `vault.db.vkwrap`
+//! without the marker (exactly `wrapped_vk`) was written without a key
+//! wrapper; it is rewritten wrapped once one is set.
+//!
+//! Argon2id runs on every attempt, including when no password is enrolled,
+//! and the AEAD tag comparison is constant-time, so a wrong password is
+//! indistinguishable from any other failure by timing or error. The
+//! unwrapped VK goes straight into a `VaultSession` kept in the engine's
+//! session table; the UI only receives a `VaultSessionHandle`.
+//!
+//! ## Vault Items
+//!
+//! `put_item()`, `get_item()`, `delete_item()` and `list_items()` work on a
+//! `VaultContents`: every item is encrypted on its own under the current
+//! epoch's DEK, so a change re-encrypts only the touched item and the item
+//! index. The contents are the vault payload:
+//!
+//! ```text
+//! vault.db  [VaultHeader:32][VaultBlob(VK, VaultContents blob)][VK region]
+//! ```
+//!
+//! Every change is applied to the items on disk and written at the same
+//! epoch through the AUP shadow write and atomic commit (`aup_reseal`).
+//! `upgrade_epoch()` re-encrypts the items under the new DEK in the same
+//! AUP write. Each session keeps its own view of the items, loaded on first
+//! use and dropped when the session is locked.
 
+use crate::bridge::keystore::{ForeignKeyWrapper, HardwareKeyWrapper};
 use crate::bridge::session::VaultSession;
-use crate::bridge::types::DeviceInfo;
-use crate::models::device::{DeviceHeader, DeviceId};
-use crate::models::epoch::CryptoEpoch;
-use crate::protocol::device_mgmt::revoke_device;
+use crate::bridge::types::{
+    DeviceFilter, DeviceInfo, DeviceSummary, HandleRegistry, InitReport, VaultSessionHandle,
+    DEFAULT_MAX_SESSIONS,
+};
+use crate::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce};
+use crate::crypto::kdf::{Argon2idConfig, Argon2idKDF};
+use crate::crypto::kem::{unwrap_dek, KyberKEM, KyberSecretKeyBytes};
+use crate::crypto::wrap::KeyWrapper;
+use crate::models::device::{DeviceHeader, DeviceId, DeviceStatus, Role};
+use crate::models::epoch::{CryptoAlgorithm, CryptoEpoch};
+use crate::models::key_hierarchy::{DataEncryptionKey, MasterSeed, RecoveryKey, VaultKey};
+use crate::models::vault::items::{ItemId, VaultContents};
+use crate::models::vault::{VaultBlob, VaultFormatError};
+use crate::protocol::device_mgmt::{register_device_with_dek, revoke_device};
+use crate::protocol::epoch_upgrade::{EpochUpgradeCoordinator, UpgradeProgress};
 use crate::protocol::error::{PqrrError, Result};
+use crate::protocol::recovery::{RecoveredVault, RecoveryAttemptTracker, RecoveryRateLimitPolicy};
 use crate::protocol::PqrrStateMachine;
 use crate::protocol::ProtocolState;
+pub use crate::storage::aug::VAULT_FILE_NAME;
+use crate::storage::aug::{
+    aup_atomic_commit, aup_prepare, aup_reseal, aup_shadow_write, open_vault, read_vault_blob,
+    read_vault_key, LEGACY_VK_NONCE,
+};
+use crate::storage::export::{
+    device_headers_path, read_device_headers, sidecar_path, write_atomically, ANCHOR_SEAL_SUFFIX,
+    PASSWORD_WRAP_SUFFIX, RECOVERY_ATTEMPTS_SUFFIX,
+};
+use crate::storage::metadata::SqliteMetadataStore;
+use crate::storage::{device_secret_path, FileBackend, VaultFile, VaultLock, VaultStorage};
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use zeroize::Zeroizing;
+
+/// Associated data binding a wrapped vault key to its purpose
+const VK_WRAP_AAD: &[u8] = b"Aeternum_VaultKeyWrap_v1";
+
+/// Wrapped vault key nonce length in bytes
+const VK_WRAP_NONCE_LEN: usize = 24;
+
+/// Length of a password-wrapped vault key: `[Nonce:24][VK:32][Tag:16]`
+const VK_WRAP_LEN: usize = VK_WRAP_NONCE_LEN + 32 + 16;
+
+/// Marker of a password-wrapped vault key additionally wrapped by the
+/// engine's key wrapper
+const VK_WRAP_KEYSTORE: u8 = 0x01;
+
+pub use crate::storage::metadata::METADATA_FILE_NAME;
+
+/// Suffix of the directory a replacement vault is staged in
+const STAGING_SUFFIX: &str = ".init";
+
+/// Associated data binding a sealed Device_0 secret key to its purpose
+const ANCHOR_SEAL_AAD: &[u8] = b"Aeternum_ShadowAnchorSeal_v1";
 
 /// Mock recovery request ID generator
 fn generate_recovery_id() -> String {
@@ -33,6 +175,348 @@ fn generate_recovery_id() -> String {
     format!("rec_{}", timestamp)
 }
 
+/// Path of the sealed Device_0 secret key belonging to `vault_path`
+fn anchor_seal_path(vault_path: &Path) -> PathBuf {
+    sidecar_path(vault_path, ANCHOR_SEAL_SUFFIX)
+}
+
+/// Staging directory a replacement for the vault at `vault_path` is written to
+fn staging_dir(vault_path: &Path) -> PathBuf {
+    let mut path = vault_path.as_os_str().to_owned();
+    path.push(STAGING_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Path of the password-wrapped vault key belonging to `vault_path`
+fn password_wrap_path(vault_path: &Path) -> PathBuf {
+    sidecar_path(vault_path, PASSWORD_WRAP_SUFFIX)
+}
+
+/// Path of the recovery attempt history belonging to `vault_path`
+fn recovery_attempts_path(vault_path: &Path) -> PathBuf {
+    sidecar_path(vault_path, RECOVERY_ATTEMPTS_SUFFIX)
+}
+
+/// Load the recovery attempt history of the vault at `vault_path`
+///
+/// Vaults written before the history existed, imported from an export or
+/// written only through AUP have no history file; one is started empty and
+/// saved for them.
+///
+/// # Errors
+/// - `PqrrError::StorageError` - The file is malformed, or the new file
+///   cannot be written
+fn load_recovery_attempts(vault_path: &Path) -> Result<RecoveryAttemptTracker> {
+    let path = recovery_attempts_path(vault_path);
+    let policy = RecoveryRateLimitPolicy::default();
+    if path.exists() {
+        return RecoveryAttemptTracker::load(&FileBackend, &path, policy);
+    }
+
+    eprintln!(
+        "[Recovery] No attempt history for {}, starting an empty one",
+        vault_path.display()
+    );
+    let tracker = RecoveryAttemptTracker::new(policy);
+    let _lock = VaultLock::hold(vault_path).map_err(|e| PqrrError::storage_error(e.to_string()))?;
+    tracker.save(&FileBackend, &path)?;
+    Ok(tracker)
+}
+
+/// Every file belonging to the vault at `vault_path`
+///
+/// `initialize_vault` writes all but the password-wrapped vault key, which
+/// `initialize_vault_with_password` adds later, and the device secret, which
+/// it only writes when a key wrapper is set.
+fn vault_files(vault_path: &Path) -> [PathBuf; 7] {
+    [
+        vault_path.to_path_buf(),
+        device_headers_path(vault_path),
+        anchor_seal_path(vault_path),
+        recovery_attempts_path(vault_path),
+        device_secret_path(vault_path),
+        vault_path.with_file_name(METADATA_FILE_NAME),
+        password_wrap_path(vault_path),
+    ]
+}
+
+/// Replace the vault at `vault_path` with a fresh epoch-1 vault
+///
+/// The new vault is written to [`staging_dir`] first; the existing vault is
+/// only touched once that write has succeeded. Its files are then renamed
+/// into place, the vault file last, and stale files the new vault does not
+/// have are removed. The caller holds the vault lock.
+///
+/// # Errors
+/// - `PqrrError::StorageError` - Writing the new vault or a rename failed
+/// - `PqrrError::HeaderIncomplete` - The DEK could not be wrapped for this
+///   device
+fn replace_vault(
+    recovery_key: &RecoveryKey,
+    vault_path: &Path,
+    this_device: Option<(DeviceId, &dyn KeyWrapper)>,
+) -> Result<(PqrrStateMachine, DataEncryptionKey)> {
+    let staging = staging_dir(vault_path);
+    let io_error = |what: &str, path: &Path, e: std::io::Error| {
+        PqrrError::storage_error(format!("Failed to {} {}: {}", what, path.display(), e))
+    };
+
+    // Leftover of an interrupted replacement
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging).map_err(|e| io_error("remove", &staging, e))?;
+    }
+    std::fs::create_dir(&staging).map_err(|e| io_error("create", &staging, e))?;
+
+    let staged_vault = staging.join(VAULT_FILE_NAME);
+    let result =
+        write_initial_vault(recovery_key, &staged_vault, this_device).and_then(|initialized| {
+            let staged = vault_files(&staged_vault);
+            let targets = vault_files(vault_path);
+            for (from, to) in staged.iter().zip(&targets).rev() {
+                if from.exists() {
+                    std::fs::rename(from, to).map_err(|e| io_error("replace", to, e))?;
+                } else if to.exists() {
+                    std::fs::remove_file(to).map_err(|e| io_error("remove", to, e))?;
+                }
+            }
+            Ok(initialized)
+        });
+    let _ = std::fs::remove_dir_all(&staging);
+    result
+}
+
+/// Derive the RecoveryKey from a mnemonic
+///
+/// # Errors
+/// - `PqrrError::AuthenticationFailed` - Invalid mnemonic
+fn recovery_key_from_mnemonic(mnemonic: &str) -> Result<RecoveryKey> {
+    let seed = MasterSeed::from_mnemonic(mnemonic)
+        .map_err(|e| PqrrError::authentication_failed(format!("Invalid mnemonic: {}", e)))?;
+    Ok(seed.derive_recovery_key())
+}
+
+/// RecoveryKey as an AEAD cipher
+fn recovery_cipher(recovery_key: &RecoveryKey) -> Result<AeadCipher> {
+    XChaCha20Key::from_bytes(recovery_key.as_bytes())
+        .map(|key| AeadCipher::new(&key))
+        .map_err(|e| PqrrError::storage_error(format!("Invalid recovery key: {}", e)))
+}
+
+/// Write a fresh epoch-1 vault with Device_0 as its only device
+///
+/// With `this_device`, the device is registered as well: it gets a new key
+/// pair, a header carrying the DEK, and its secret key is stored next to
+/// the vault, wrapped by the given key wrapper.
+///
+/// The vault file is committed last, so until then no `vault.db` exists and
+/// a failure leaves only side files for the caller to remove. The caller
+/// holds the vault lock.
+///
+/// # Errors
+/// - `PqrrError::StorageError` - Key wrapping or any write failed
+/// - `PqrrError::HeaderIncomplete` - The DEK could not be wrapped for a
+///   device
+fn write_initial_vault(
+    recovery_key: &RecoveryKey,
+    vault_path: &Path,
+    this_device: Option<(DeviceId, &dyn KeyWrapper)>,
+) -> Result<(PqrrStateMachine, DataEncryptionKey)> {
+    let storage = |e: crate::storage::StorageError| PqrrError::storage_error(e.to_string());
+
+    // Device_0 key pair, derived from the RecoveryKey so the mnemonic alone
+    // reproduces it
+    let anchor = recovery_key.derive_anchor_keypair();
+    let vault_key = VaultKey::generate();
+
+    // AUP from the genesis epoch 0 yields epoch 1 with an empty payload. The
+    // genesis DEK only seals the VK for that first step; epoch 1's DEK is
+    // derived from the VK and is the one wrapped for Device_0.
+    let dek_key = XChaCha20Key::generate();
+    let encrypted_vk = AeadCipher::new(&dek_key)
+        .encrypt(
+            &XChaCha20Nonce::from_bytes(LEGACY_VK_NONCE),
+            vault_key.as_bytes(),
+            None,
+        )
+        .map_err(|e| PqrrError::storage_error(format!("Vault key wrapping failed: {}", e)))?;
+    let genesis = CryptoEpoch::new(0, CryptoAlgorithm::V1);
+    let preparation = aup_prepare(&genesis, &encrypted_vk, &dek_key, &[])
+        .map_err(|e| PqrrError::storage_error(format!("AUP prepare failed: {}", e)))?;
+    let epoch = preparation.new_epoch;
+    let dek = DataEncryptionKey::from_bytes(*preparation.new_dek.as_bytes());
+
+    let wrapped = dek.wrap_for_device(&anchor.public).map_err(|e| {
+        PqrrError::header_incomplete(
+            format!("{:?}", DeviceId::shadow_anchor()),
+            format!("DEK wrap failed: {}", e),
+        )
+    })?;
+    let mut header =
+        DeviceHeader::shadow_anchor(epoch, anchor.public.clone(), wrapped.kem_ciphertext.clone());
+    header.set_wrapped_dek(wrapped);
+
+    let nonce = XChaCha20Nonce::random();
+    let sealed = recovery_cipher(recovery_key)?
+        .encrypt(&nonce, anchor.secret.as_bytes(), Some(ANCHOR_SEAL_AAD))
+        .map_err(|e| PqrrError::storage_error(format!("Anchor key sealing failed: {}", e)))?;
+    let headers = HashMap::from([(header.device_id, header)]);
+    let mut state_machine = PqrrStateMachine::create(epoch, headers);
+
+    if let Some((device_id, wrapper)) = this_device {
+        let keypair = KyberKEM::generate_keypair();
+        register_device_with_dek(
+            &mut state_machine,
+            device_id,
+            keypair.public,
+            Role::Authorized,
+            &dek,
+        )?;
+        VaultFile::new(vault_path)
+            .store_device_secret(&keypair.secret, wrapper)
+            .map_err(storage)?;
+    }
+
+    write_atomically(&anchor_seal_path(vault_path), &[nonce.as_bytes(), &sealed])
+        .map_err(storage)?;
+    store_device_headers(vault_path, &state_machine.device_headers())?;
+    RecoveryAttemptTracker::new(RecoveryRateLimitPolicy::default())
+        .save(&FileBackend, &recovery_attempts_path(vault_path))?;
+    let mut metadata = SqliteMetadataStore::open(vault_path.with_file_name(METADATA_FILE_NAME))
+        .map_err(storage)?;
+    let shadow_file = aup_shadow_write(vault_path, &preparation).map_err(storage)?;
+    aup_atomic_commit(vault_path, shadow_file, &epoch, &mut metadata).map_err(storage)?;
+
+    Ok((state_machine, dek))
+}
+
+/// Persist `headers` next to the vault at `vault_path`
+///
+/// Headers are written in device ID order so the file only changes when the
+/// headers do. The caller holds the vault lock.
+///
+/// # Errors
+/// - `PqrrError::StorageError` - Encoding or the write failed
+fn store_device_headers(
+    vault_path: &Path,
+    headers: &HashMap<DeviceId, DeviceHeader>,
+) -> Result<()> {
+    let mut list: Vec<&DeviceHeader> = headers.values().collect();
+    list.sort_by_key(|header| header.device_id.0);
+    let bytes = bincode::serialize(&list)
+        .map_err(|e| PqrrError::storage_error(format!("Failed to encode headers: {}", e)))?;
+    write_atomically(&device_headers_path(vault_path), &[&bytes])
+        .map_err(|e| PqrrError::storage_error(e.to_string()))
+}
+
+/// The epoch DEK as an AEAD key
+fn dek_key(dek: &DataEncryptionKey) -> Result<XChaCha20Key> {
+    XChaCha20Key::from_bytes(dek.as_bytes())
+        .map_err(|e| PqrrError::storage_error(format!("Invalid epoch DEK: {}", e)))
+}
+
+/// Map an item-layer error
+fn item_error(e: VaultFormatError) -> PqrrError {
+    PqrrError::storage_error(format!("Vault items: {}", e))
+}
+
+/// Decode the vault items carried in a vault payload
+///
+/// An empty payload, as written by `initialize_vault`, holds no items.
+fn items_from_payload(payload: &[u8], dek: &DataEncryptionKey) -> Result<VaultContents> {
+    if payload.is_empty() {
+        return VaultContents::new(dek).map_err(item_error);
+    }
+    let blob = VaultBlob::deserialize(payload)
+        .map_err(|e| PqrrError::storage_error(format!("Invalid vault items: {}", e)))?;
+    VaultContents::from_blob(&blob, dek).map_err(item_error)
+}
+
+/// Read the vault items from the vault file, keyed with the epoch DEK
+///
+/// # Errors
+/// - `PqrrError::StorageError` - The vault cannot be read or opened with
+///   `dek`, or its items fail verification
+fn load_items(vault_path: &Path, dek: &DataEncryptionKey) -> Result<VaultContents> {
+    let storage = |e: crate::storage::StorageError| PqrrError::storage_error(e.to_string());
+    let blob = read_vault_blob(vault_path).map_err(storage)?;
+    let stored_vk = read_vault_key(vault_path)
+        .map_err(storage)?
+        .ok_or_else(|| {
+            PqrrError::storage_error(format!(
+                "vault {} has no vault key region",
+                vault_path.display()
+            ))
+        })?;
+    let payload = Zeroizing::new(open_vault(&blob, &stored_vk, &dek_key(dek)?).map_err(storage)?);
+    items_from_payload(&payload, dek)
+}
+
+/// Write `contents` as the vault payload at the current epoch
+///
+/// Goes through the AUP shadow write and atomic commit, so a failed write
+/// leaves the previous items in place. Takes the vault lock (reusing it
+/// if this thread already holds it).
+///
+/// # Errors
+/// - `PqrrError::StorageError` - Sealing, the shadow write or the commit
+///   failed, or another writer holds the vault lock
+fn store_items(vault_path: &Path, dek: &DataEncryptionKey, contents: &VaultContents) -> Result<()> {
+    let storage = |e: crate::storage::StorageError| PqrrError::storage_error(e.to_string());
+    let _lock = VaultLock::hold(vault_path).map_err(storage)?;
+
+    let blob = read_vault_blob(vault_path).map_err(storage)?;
+    let stored_vk = read_vault_key(vault_path)
+        .map_err(storage)?
+        .ok_or_else(|| {
+            PqrrError::storage_error(format!(
+                "vault {} has no vault key region",
+                vault_path.display()
+            ))
+        })?;
+    let payload = contents
+        .to_blob(blob.epoch)
+        .map_err(item_error)?
+        .serialize()
+        .map_err(|e| PqrrError::storage_error(format!("Failed to encode vault items: {}", e)))?;
+    let preparation = aup_reseal(
+        &blob.epoch,
+        &stored_vk.encrypted_vk,
+        &stored_vk.vk_nonce,
+        &dek_key(dek)?,
+        &payload,
+    )
+    .map_err(storage)?;
+
+    let mut metadata = SqliteMetadataStore::open(vault_path.with_file_name(METADATA_FILE_NAME))
+        .map_err(storage)?;
+    let shadow_file = aup_shadow_write(vault_path, &preparation).map_err(storage)?;
+    aup_atomic_commit(
+        vault_path,
+        shadow_file,
+        &preparation.new_epoch,
+        &mut metadata,
+    )
+    .map_err(storage)
+}
+
+/// Re-encrypt the vault items in `payload` for an epoch upgrade
+fn rekey_items(
+    payload: &[u8],
+    old_dek: &DataEncryptionKey,
+    new_dek: &DataEncryptionKey,
+    new_epoch: CryptoEpoch,
+) -> Result<Vec<u8>> {
+    if payload.is_empty() {
+        return Ok(Vec::new());
+    }
+    items_from_payload(payload, old_dek)?
+        .rekey(old_dek, new_dek)
+        .and_then(|contents| contents.to_blob(new_epoch))
+        .map_err(item_error)?
+        .serialize()
+        .map_err(|e| PqrrError::storage_error(format!("Failed to encode vault items: {}", e)))
+}
+
 /// Aeternum engine - Main entry point for UI layer
 ///
 /// Provides high-level operations for Android UI:
@@ -42,8 +526,8 @@ fn generate_recovery_id() -> String {
 /// - Vault integrity verification
 #[derive(uniffi::Object)]
 pub struct AeternumEngine {
-    /// Vault path
-    vault_path: String,
+    /// Vault path (replaced by `initialize_vault`)
+    vault_path: RwLock<String>,
 
     /// Protocol state machine
     state_machine: Arc<RwLock<PqrrStateMachine>>,
@@ -51,8 +535,33 @@ pub struct AeternumEngine {
     /// Device headers (cached from state machine)
     device_headers: Arc<RwLock<HashMap<DeviceId, DeviceHeader>>>,
 
-    /// Current device ID (this device)
-    this_device_id: DeviceId,
+    /// Current device ID (this device; replaced by `open_vault`)
+    this_device_id: RwLock<DeviceId>,
+
+    /// Keystore wrapping of this device's secret key and the vault key at
+    /// rest (`None` = stored under their own protection only)
+    key_wrapper: RwLock<Option<Arc<dyn KeyWrapper>>>,
+
+    /// Argon2id parameters for password unlock
+    kdf_config: Argon2idConfig,
+
+    /// Cached copy of the password-wrapped vault key file
+    /// (`None` = not loaded yet, or no password enrolled)
+    password_wrapped_vk: RwLock<Option<Vec<u8>>>,
+
+    /// Password-unlocked sessions by handle ID
+    sessions: parking_lot::Mutex<HandleRegistry<Arc<VaultSession>>>,
+
+    /// Latest progress of the running (or last) epoch upgrade
+    upgrade_progress: Arc<Mutex<Option<UpgradeProgress>>>,
+
+    /// DEK of the current epoch (`None` until the vault is initialized)
+    epoch_dek: RwLock<Option<DataEncryptionKey>>,
+
+    /// Bumped, under the `epoch_dek` write lock, whenever the vault or its
+    /// epoch DEK changes; session item views loaded under an older value
+    /// are reloaded
+    vault_generation: AtomicU64,
 }
 
 impl AeternumEngine {
@@ -70,10 +579,312 @@ impl AeternumEngine {
         let device_headers = state_machine.device_headers().clone();
 
         Self {
-            vault_path,
+            vault_path: RwLock::new(vault_path),
             state_machine: Arc::new(RwLock::new(state_machine)),
             device_headers: Arc::new(RwLock::new(device_headers)),
-            this_device_id,
+            this_device_id: RwLock::new(this_device_id),
+            key_wrapper: RwLock::new(None),
+            kdf_config: Argon2idConfig::default(),
+            password_wrapped_vk: RwLock::new(None),
+            sessions: parking_lot::Mutex::new(HandleRegistry::new(DEFAULT_MAX_SESSIONS)),
+            upgrade_progress: Arc::new(Mutex::new(None)),
+            epoch_dek: RwLock::new(None),
+            vault_generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Override the Argon2id parameters used for password unlock
+    ///
+    /// Must be called before a password is enrolled; a vault key wrapped
+    /// under other parameters can no longer be unwrapped.
+    pub fn with_kdf_config(mut self, config: Argon2idConfig) -> Self {
+        self.kdf_config = config;
+        self
+    }
+
+    /// Wrap this device's secret key and the vault key at rest with
+    /// `wrapper`
+    ///
+    /// Rust-side counterpart of
+    /// [`set_hardware_key_wrapper`](Self::set_hardware_key_wrapper), e.g.
+    /// with an [`AeadKeyWrapper`](crate::crypto::wrap::AeadKeyWrapper) on
+    /// platforms without a keystore.
+    pub fn with_key_wrapper(self, wrapper: Arc<dyn KeyWrapper>) -> Self {
+        *self.key_wrapper.write().unwrap() = Some(wrapper);
+        self
+    }
+
+    /// Override the number of sessions that may be open at once
+    ///
+    /// Must be called before a session is opened; open sessions are
+    /// dropped.
+    pub fn with_max_sessions(mut self, max_sessions: usize) -> Self {
+        self.sessions = parking_lot::Mutex::new(HandleRegistry::new(max_sessions));
+        self
+    }
+
+    /// Cold-recover vault access from the mnemonic
+    ///
+    /// Re-derives the RecoveryKey, unseals Device_0's secret key and unwraps
+    /// the DEK from the shadow anchor header stored next to the vault in
+    /// `vault_dir`. The result carries the RECOVERY role (Invariant #3).
+    ///
+    /// # Errors
+    /// - `PqrrError::AuthenticationFailed` - Invalid mnemonic, or the sealed
+    ///   key does not open with it
+    /// - `PqrrError::StorageError` - Vault files missing or malformed
+    /// - `PqrrError::HeaderIncomplete` - No shadow anchor header, or the DEK
+    ///   does not unwrap
+    pub fn cold_recover(mnemonic: &str, vault_dir: impl AsRef<Path>) -> Result<RecoveredVault> {
+        let vault_path = vault_dir.as_ref().join(VAULT_FILE_NAME);
+        let recovery_key = recovery_key_from_mnemonic(mnemonic)?;
+
+        let seal_path = anchor_seal_path(&vault_path);
+        let sealed = std::fs::read(&seal_path).map_err(|e| {
+            PqrrError::storage_error(format!("Failed to read {}: {}", seal_path.display(), e))
+        })?;
+        if sealed.len() <= VK_WRAP_NONCE_LEN {
+            return Err(PqrrError::storage_error(format!(
+                "Sealed anchor key {} is truncated",
+                seal_path.display()
+            )));
+        }
+        let (nonce, ciphertext) = sealed.split_at(VK_WRAP_NONCE_LEN);
+        let nonce = XChaCha20Nonce::try_from_slice(nonce)
+            .map_err(|e| PqrrError::storage_error(format!("Invalid anchor nonce: {}", e)))?;
+        let secret = recovery_cipher(&recovery_key)?
+            .decrypt(&nonce, ciphertext, Some(ANCHOR_SEAL_AAD))
+            .map_err(|_| {
+                PqrrError::authentication_failed(
+                    "Anchor key could not be unsealed with this mnemonic".to_string(),
+                )
+            })?;
+        let secret = KyberSecretKeyBytes::from_bytes(&secret)
+            .map_err(|e| PqrrError::storage_error(format!("Invalid anchor key: {}", e)))?;
+
+        let headers = read_device_headers(&vault_path)
+            .map_err(|e| PqrrError::storage_error(e.to_string()))?;
+        let anchor = headers
+            .iter()
+            .find(|h| h.device_id.is_shadow_anchor())
+            .ok_or_else(|| {
+                PqrrError::header_incomplete(
+                    format!("{:?}", DeviceId::shadow_anchor()),
+                    "no shadow anchor header".to_string(),
+                )
+            })?;
+
+        RecoveredVault::unwrap_from_header(anchor, &secret)
+    }
+
+    /// Derive the vault-key wrapping key from a password
+    ///
+    /// # Errors
+    /// - `PqrrError::StorageError` - Invalid KDF parameters or salt
+    fn derive_wrapping_key(&self, password: &str, salt: &[u8]) -> Result<XChaCha20Key> {
+        let kdf = Argon2idKDF::with_config(self.kdf_config)
+            .map_err(|e| PqrrError::storage_error(format!("Invalid KDF parameters: {}", e)))?;
+        let derived = kdf
+            .derive_key(password.as_bytes(), salt)
+            .map_err(|e| PqrrError::storage_error(format!("Key derivation failed: {}", e)))?;
+        XChaCha20Key::from_bytes(derived.as_bytes())
+            .map_err(|e| PqrrError::storage_error(format!("Invalid wrapping key: {}", e)))
+    }
+
+    /// Switch to the existing vault at `vault_path`, opened with `dek`
+    ///
+    /// Checks that `dek` opens the vault, then loads its epoch, device
+    /// headers and recovery attempt history.
+    ///
+    /// # Errors
+    /// - `PqrrError::StorageError` - Vault files missing or malformed, or
+    ///   `dek` does not open the vault
+    fn switch_to_vault(&self, vault_path: &Path, dek: DataEncryptionKey) -> Result<()> {
+        let storage = |e: crate::storage::StorageError| PqrrError::storage_error(e.to_string());
+        load_items(vault_path, &dek)?;
+
+        let epoch = read_vault_blob(vault_path).map_err(storage)?.epoch;
+        let headers = read_device_headers(vault_path)
+            .map_err(storage)?
+            .into_iter()
+            .map(|header| (header.device_id, header))
+            .collect();
+        let state_machine = PqrrStateMachine::create(epoch, headers);
+        state_machine.set_recovery_attempts(load_recovery_attempts(vault_path)?);
+
+        *self.device_headers.write().unwrap() = state_machine.device_headers().clone();
+        *self.state_machine.write().unwrap() = state_machine;
+        *self.vault_path.write().unwrap() = vault_path.display().to_string();
+        *self.password_wrapped_vk.write().unwrap() = None;
+        self.set_epoch_dek(dek);
+
+        Ok(())
+    }
+
+    /// Key wrapper set with `set_hardware_key_wrapper` or `with_key_wrapper`
+    fn key_wrapper(&self) -> Option<Arc<dyn KeyWrapper>> {
+        self.key_wrapper.read().unwrap().clone()
+    }
+
+    /// Password-wrapped vault key, loaded from disk on first use
+    ///
+    /// A key that is not keystore-wrapped yet is rewritten wrapped once a
+    /// key wrapper is set.
+    ///
+    /// # Errors
+    /// - `PqrrError::StorageError` - The file exists but cannot be read or
+    ///   unwrapped, or is keystore-wrapped and no key wrapper is set
+    fn password_wrapped_vk(&self) -> Result<Option<Vec<u8>>> {
+        if let Some(blob) = self.password_wrapped_vk.read().unwrap().clone() {
+            return Ok(Some(blob));
+        }
+
+        let vault_path = PathBuf::from(&*self.vault_path.read().unwrap());
+        let path = password_wrap_path(&vault_path);
+        let stored = match std::fs::read(&path) {
+            Ok(stored) => stored,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(PqrrError::storage_error(format!(
+                    "Failed to read {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+
+        let wrapper = self.key_wrapper();
+        let blob = if stored.len() == VK_WRAP_LEN {
+            if wrapper.is_some() {
+                // Migrate: replace the copy without keystore wrapping
+                let storage =
+                    |e: crate::storage::StorageError| PqrrError::storage_error(e.to_string());
+                let _lock = VaultLock::hold(&vault_path).map_err(storage)?;
+                write_atomically(&path, &[&self.encode_password_wrapped_vk(&stored)?])
+                    .map_err(storage)?;
+            }
+            stored
+        } else if let Some((&VK_WRAP_KEYSTORE, wrapped)) = stored.split_first() {
+            wrapper
+                .ok_or_else(|| {
+                    PqrrError::storage_error(format!(
+                        "{} is keystore-wrapped but no key wrapper is set",
+                        path.display()
+                    ))
+                })?
+                .unwrap(wrapped)
+                .map_err(|e| {
+                    PqrrError::storage_error(format!("Failed to unwrap {}: {}", path.display(), e))
+                })?
+        } else {
+            return Err(PqrrError::storage_error(format!(
+                "{} is not a wrapped vault key",
+                path.display()
+            )));
+        };
+
+        *self.password_wrapped_vk.write().unwrap() = Some(blob.clone());
+        Ok(Some(blob))
+    }
+
+    /// Password-wrapped vault key as stored on disk
+    ///
+    /// `blob` as is, or `0x01 || KeyWrapper::wrap(blob)` while a key
+    /// wrapper is set.
+    ///
+    /// # Errors
+    /// - `PqrrError::StorageError` - The key wrapper failed
+    fn encode_password_wrapped_vk(&self, blob: &[u8]) -> Result<Vec<u8>> {
+        let Some(wrapper) = self.key_wrapper() else {
+            return Ok(blob.to_vec());
+        };
+        let wrapped = wrapper.wrap(blob).map_err(|e| {
+            PqrrError::storage_error(format!("Vault key keystore wrapping failed: {}", e))
+        })?;
+
+        let mut stored = Vec::with_capacity(1 + wrapped.len());
+        stored.push(VK_WRAP_KEYSTORE);
+        stored.extend_from_slice(&wrapped);
+        Ok(stored)
+    }
+
+    /// Switch to `dek` as the current epoch DEK
+    ///
+    /// Session item views loaded under the previous DEK are reloaded on
+    /// their next use.
+    fn set_epoch_dek(&self, dek: DataEncryptionKey) {
+        let mut epoch_dek = self.epoch_dek.write().unwrap();
+        *epoch_dek = Some(dek);
+        self.vault_generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Run `f` on a session's item view with the vault path and epoch DEK
+    ///
+    /// Holds the `epoch_dek` read lock throughout, so an epoch upgrade
+    /// waits for the operation. The view is (re)loaded from the vault if
+    /// it is missing or was loaded under another DEK; `f` may replace it.
+    ///
+    /// # Errors
+    /// - `PqrrError::InvalidHandle` - Unknown, closed or stale handle
+    /// - `PqrrError::InsufficientPrivileges` - Locked session
+    /// - `PqrrError::SessionExpired` - Idle timeout elapsed (session now locked)
+    /// - `PqrrError::StorageError` - No vault is open, or loading it failed
+    fn with_session_items<T>(
+        &self,
+        handle: VaultSessionHandle,
+        operation: &str,
+        f: impl FnOnce(&mut VaultContents, &Path, &DataEncryptionKey) -> Result<T>,
+    ) -> Result<T> {
+        let session = self.session(handle)?;
+        let vault_path = PathBuf::from(&*self.vault_path.read().unwrap());
+        let epoch_dek = self.epoch_dek.read().unwrap();
+        let generation = self.vault_generation.load(Ordering::Acquire);
+
+        session.with_items(operation, |view| {
+            let dek = epoch_dek.as_ref().ok_or_else(|| {
+                PqrrError::storage_error("current epoch DEK not available".to_string())
+            })?;
+            if !matches!(view, Some((loaded, _)) if *loaded == generation) {
+                *view = Some((generation, load_items(&vault_path, dek)?));
+            }
+            match view {
+                Some((_, contents)) => f(contents, &vault_path, dek),
+                None => unreachable!("item view loaded above"),
+            }
+        })
+    }
+
+    /// Save the recovery attempt history of the engine's vault
+    ///
+    /// Called after every initiation and recorded outcome. Does nothing
+    /// while no vault exists at the engine's vault path.
+    ///
+    /// # Errors
+    /// - `PqrrError::StorageError` - The write failed
+    fn save_recovery_attempts(&self, state_machine: &PqrrStateMachine) -> Result<()> {
+        let vault_path = PathBuf::from(self.vault_path.read().unwrap().as_str());
+        if !vault_path.is_file() {
+            return Ok(());
+        }
+        state_machine
+            .recovery_attempts()
+            .save(&FileBackend, &recovery_attempts_path(&vault_path))
+    }
+
+    /// Reject mutating operations while the state machine is Degraded
+    ///
+    /// # Errors
+    /// - `PqrrError::ReadOnlyMode` - Device is degraded
+    fn ensure_writable(&self) -> Result<()> {
+        let state_machine = self.state_machine.read().unwrap();
+
+        match state_machine.state() {
+            ProtocolState::Degraded => Err(PqrrError::read_only_mode(
+                state_machine
+                    .degraded_reason()
+                    .unwrap_or_else(|| "integrity verification failed".to_string()),
+            )),
+            _ => Ok(()),
         }
     }
 }
@@ -87,11 +898,16 @@ impl AeternumEngine {
 impl AeternumEngine {
     /// Constructor - Create engine with vault path
     ///
+    /// If a vault exists at `vault_path`, its recovery attempt history is
+    /// loaded so the recovery rate limits survive restarts, and started
+    /// empty if the vault has none yet.
+    ///
     /// # Arguments
     /// - `vault_path`: Path to vault file
     ///
     /// # Errors
-    /// - `PqrrError::StorageError` - Failed to initialize vault
+    /// - `PqrrError::StorageError` - Failed to initialize vault, or the
+    ///   vault's recovery attempt history is malformed
     #[uniffi::constructor]
     pub fn new_with_path(vault_path: String) -> Result<Self> {
         // In production, this would:
@@ -103,12 +919,225 @@ impl AeternumEngine {
         let epoch = CryptoEpoch::initial();
         let headers = HashMap::new();
         let state_machine = PqrrStateMachine::create(epoch, headers);
+        if Path::new(&vault_path).is_file() {
+            state_machine.set_recovery_attempts(load_recovery_attempts(Path::new(&vault_path))?);
+        }
         let this_device_id = DeviceId::generate();
 
         Ok(Self::new(vault_path, state_machine, this_device_id))
     }
 
-    /// Initialize vault (for first-time setup)
+    /// Initialize a new vault from a mnemonic (first-run setup)
+    ///
+    /// Derives the MasterSeed and RecoveryKey, creates the Device_0 shadow
+    /// anchor, DEK and VK, encrypts an empty payload and writes the vault
+    /// directory through the AUP shadow-write path. On success the engine
+    /// switches to the new vault with its state machine at epoch 1.
+    ///
+    /// If a key wrapper is set (`set_hardware_key_wrapper`), this device is
+    /// registered too: its new Kyber secret key is stored next to the vault
+    /// wrapped by the keystore, so `open_vault` can reopen the vault later
+    /// without the mnemonic.
+    ///
+    /// With `force_overwrite`, an existing vault in `vault_dir` is replaced:
+    /// the new vault is staged next to it and renamed into place only once
+    /// it is fully written, so a failure leaves the existing vault as it
+    /// was. Otherwise, on failure every file written so far (and `vault_dir`,
+    /// if this call created it) is removed. The vault lock is held
+    /// throughout.
+    ///
+    /// # Arguments
+    /// - `mnemonic`: BIP-39 mnemonic phrase
+    /// - `vault_dir`: Directory to create the vault in
+    /// - `force_overwrite`: Replace an existing vault
+    ///
+    /// # Errors
+    /// - `PqrrError::InvalidStateTransition` - A vault already exists and
+    ///   `force_overwrite` is not set
+    /// - `PqrrError::AuthenticationFailed` - Invalid mnemonic
+    /// - `PqrrError::StorageError` - Failed to create vault, the key wrapper
+    ///   failed, or another writer holds the vault lock
+    /// - `PqrrError::HeaderIncomplete` - The DEK could not be wrapped for
+    ///   this device
+    /// - `PqrrError::ReadOnlyMode` - Device is degraded
+    #[uniffi::method(name = "initialize_vault_from_mnemonic")]
+    pub fn initialize_vault(
+        &self,
+        mnemonic: String,
+        vault_dir: String,
+        force_overwrite: bool,
+    ) -> Result<InitReport> {
+        self.ensure_writable()?;
+
+        let vault_dir = PathBuf::from(vault_dir);
+        let vault_path = vault_dir.join(VAULT_FILE_NAME);
+        let files = vault_files(&vault_path);
+
+        let replaced_existing = files.iter().any(|f| f.exists());
+        if replaced_existing && !force_overwrite {
+            return Err(PqrrError::invalid_transition(
+                "Initialized".to_string(),
+                "Initialized".to_string(),
+                format!("Vault already exists at {}", vault_dir.display()),
+            ));
+        }
+
+        // Reject a bad mnemonic before touching the disk
+        let recovery_key = recovery_key_from_mnemonic(&mnemonic)?;
+
+        let created_dir = !vault_dir.exists();
+        std::fs::create_dir_all(&vault_dir).map_err(|e| {
+            PqrrError::storage_error(format!("Failed to create {}: {}", vault_dir.display(), e))
+        })?;
+        let lock =
+            VaultLock::hold(&vault_path).map_err(|e| PqrrError::storage_error(e.to_string()))?;
+
+        // Another writer may have created a vault before we took the lock
+        let replaced_existing = files.iter().any(|f| f.exists());
+        if replaced_existing && !force_overwrite {
+            return Err(PqrrError::invalid_transition(
+                "Initialized".to_string(),
+                "Initialized".to_string(),
+                format!("Vault already exists at {}", vault_dir.display()),
+            ));
+        }
+
+        let wrapper = self.key_wrapper();
+        let this_device = wrapper
+            .as_deref()
+            .map(|wrapper| (*self.this_device_id.read().unwrap(), wrapper));
+        let initialized = if replaced_existing {
+            replace_vault(&recovery_key, &vault_path, this_device)
+        } else {
+            write_initial_vault(&recovery_key, &vault_path, this_device).inspect_err(|_| {
+                for file in &files {
+                    let _ = std::fs::remove_file(file);
+                }
+            })
+        };
+        let (state_machine, epoch_dek) = match initialized {
+            Ok(initialized) => initialized,
+            Err(e) => {
+                if created_dir {
+                    // Nothing else can know the directory we just created
+                    drop(lock);
+                    let _ = std::fs::remove_file(VaultLock::lock_path(&vault_path));
+                    let _ = std::fs::remove_dir(&vault_dir);
+                }
+                return Err(e);
+            }
+        };
+
+        let report = InitReport {
+            vault_path: vault_path.display().to_string(),
+            epoch: PqrrError::epoch_to_u32(state_machine.current_epoch().version)?,
+            replaced_existing,
+        };
+        *self.device_headers.write().unwrap() = state_machine.device_headers().clone();
+        *self.state_machine.write().unwrap() = state_machine;
+        *self.vault_path.write().unwrap() = report.vault_path.clone();
+        *self.password_wrapped_vk.write().unwrap() = None;
+        self.set_epoch_dek(epoch_dek);
+
+        Ok(report)
+    }
+
+    /// Open an existing vault with its mnemonic
+    ///
+    /// Unwraps the current epoch's DEK from the shadow anchor, as
+    /// `cold_recover` does, checks that it opens the vault and switches the
+    /// engine to the vault, its device headers and its epoch. Items stored
+    /// by an earlier engine are readable again afterwards.
+    ///
+    /// # Arguments
+    /// - `mnemonic`: BIP-39 mnemonic phrase the vault was initialized with
+    /// - `vault_dir`: Directory holding the vault
+    ///
+    /// # Errors
+    /// - `PqrrError::AuthenticationFailed` - Invalid mnemonic, or the sealed
+    ///   key does not open with it
+    /// - `PqrrError::HeaderIncomplete` - No shadow anchor header, or the DEK
+    ///   does not unwrap
+    /// - `PqrrError::StorageError` - Vault files missing or malformed
+    ///   (including a malformed recovery attempt history), or the anchor's
+    ///   DEK does not open the vault
+    pub fn open_vault_with_mnemonic(&self, mnemonic: String, vault_dir: String) -> Result<()> {
+        let vault_dir = PathBuf::from(vault_dir);
+        let vault_path = vault_dir.join(VAULT_FILE_NAME);
+
+        let recovered = Self::cold_recover(&mnemonic, &vault_dir)?;
+        let dek = DataEncryptionKey::from_bytes(*recovered.dek.as_bytes());
+        // An anchor header left at an older epoch does not open the vault
+        self.switch_to_vault(&vault_path, dek)
+    }
+
+    /// Open an existing vault with this device's key
+    ///
+    /// Loads this device's secret key, stored by `initialize_vault` while a
+    /// key wrapper was set, through the keystore and unwraps the current
+    /// epoch's DEK from this device's header. The engine then switches to
+    /// the vault as with `open_vault_with_mnemonic`, and takes on the
+    /// device's ID. No mnemonic is needed.
+    ///
+    /// # Arguments
+    /// - `vault_dir`: Directory holding the vault
+    ///
+    /// # Errors
+    /// - `PqrrError::AuthenticationFailed` - The keystore cannot unwrap the
+    ///   device secret
+    /// - `PqrrError::HeaderIncomplete` - No active device header unwraps
+    ///   with the device secret
+    /// - `PqrrError::StorageError` - No key wrapper is set, or vault files
+    ///   are missing or malformed
+    pub fn open_vault(&self, vault_dir: String) -> Result<()> {
+        let vault_path = PathBuf::from(vault_dir).join(VAULT_FILE_NAME);
+        let wrapper = self
+            .key_wrapper()
+            .ok_or_else(|| PqrrError::storage_error("No key wrapper is set".to_string()))?;
+
+        let secret = VaultFile::new(&vault_path)
+            .load_device_secret(wrapper.as_ref())
+            .map_err(|e| match e {
+                crate::storage::StorageError::AuthenticationFailed(msg) => {
+                    PqrrError::authentication_failed(msg)
+                }
+                e => PqrrError::storage_error(e.to_string()),
+            })?;
+        let (device_id, dek) = read_device_headers(&vault_path)
+            .map_err(|e| PqrrError::storage_error(e.to_string()))?
+            .iter()
+            .filter(|h| h.status == DeviceStatus::Active && !h.device_id.is_shadow_anchor())
+            .find_map(|h| {
+                let dek = unwrap_dek(&secret, &h.wrapped_dek()?).ok()?;
+                Some((h.device_id, dek))
+            })
+            .ok_or_else(|| {
+                PqrrError::header_incomplete(
+                    "this device".to_string(),
+                    "no active header unwraps with the device secret".to_string(),
+                )
+            })?;
+
+        self.switch_to_vault(&vault_path, dek)?;
+        *self.this_device_id.write().unwrap() = device_id;
+        Ok(())
+    }
+
+    /// Wrap this device's secret key and the vault key at rest with the
+    /// hardware keystore
+    ///
+    /// Call before `initialize_vault`, `open_vault` or
+    /// `initialize_vault_with_password`. A vault key enrolled without the
+    /// keystore is rewritten wrapped the next time it is loaded.
+    ///
+    /// # Arguments
+    /// - `wrapper`: Keystore callback implemented by the app
+    pub fn set_hardware_key_wrapper(&self, wrapper: Arc<dyn HardwareKeyWrapper>) {
+        *self.key_wrapper.write().unwrap() = Some(Arc::new(ForeignKeyWrapper::new(wrapper)));
+        *self.password_wrapped_vk.write().unwrap() = None;
+    }
+
+    /// Initialize vault with a hardware key (first-time setup)
     ///
     /// # Arguments
     /// - `hardware_key_blob`: Hardware key blob from StrongBox
@@ -116,7 +1145,10 @@ impl AeternumEngine {
     /// # Errors
     /// - `PqrrError::StorageError` - Failed to create vault
     /// - `PqrrError::InvalidStateTransition` - Vault already initialized
-    pub fn initialize_vault(&self, _hardware_key_blob: Vec<u8>) -> Result<()> {
+    /// - `PqrrError::ReadOnlyMode` - Device is degraded
+    pub fn initialize_hardware_vault(&self, _hardware_key_blob: Vec<u8>) -> Result<()> {
+        self.ensure_writable()?;
+
         // In production, this would:
         // 1. Generate new Vault Key (VK)
         // 2. Generate new DEK
@@ -128,6 +1160,20 @@ impl AeternumEngine {
         Ok(())
     }
 
+    /// Initialize vault with a hardware key (first-time setup)
+    ///
+    /// Former name of
+    /// [`initialize_hardware_vault`](Self::initialize_hardware_vault), still
+    /// exported to the bindings as `initialize_vault` so existing callers
+    /// keep working.
+    ///
+    /// # Errors
+    /// Same as [`initialize_hardware_vault`](Self::initialize_hardware_vault).
+    #[uniffi::method(name = "initialize_vault")]
+    pub fn initialize_vault_legacy(&self, hardware_key_blob: Vec<u8>) -> Result<()> {
+        self.initialize_hardware_vault(hardware_key_blob)
+    }
+
     /// Unlock vault - Returns session handle
     ///
     /// # Arguments
@@ -147,11 +1193,259 @@ impl AeternumEngine {
 
         // For demo, return a session with mock VK
         let vault_key = vec![0u8; 32]; // Mock 256-bit vault key
-        let epoch = self.state_machine.read().unwrap().current_epoch().version as u32;
+        let epoch =
+            PqrrError::epoch_to_u32(self.state_machine.read().unwrap().current_epoch().version)?;
 
         Ok(VaultSession::new(vault_key, epoch))
     }
 
+    /// Unlock vault with an idle timeout
+    ///
+    /// Same as `unlock`, but the returned session zeroizes its vault key
+    /// and rejects further use once it has been idle for longer than
+    /// `idle_timeout_ms`.
+    ///
+    /// # Arguments
+    /// - `hardware_key_blob`: Hardware key blob from StrongBox
+    /// - `idle_timeout_ms`: Maximum idle time in milliseconds
+    ///
+    /// # Errors
+    /// - `PqrrError::InsufficientPrivileges` - Hardware key invalid
+    /// - `PqrrError::HeaderIncomplete` - Vault data corrupted
+    pub fn unlock_with_timeout(
+        &self,
+        _hardware_key_blob: Vec<u8>,
+        idle_timeout_ms: u64,
+    ) -> Result<VaultSession> {
+        // For demo, return a session with mock VK
+        let vault_key = vec![0u8; 32]; // Mock 256-bit vault key
+        let epoch =
+            PqrrError::epoch_to_u32(self.state_machine.read().unwrap().current_epoch().version)?;
+
+        Ok(VaultSession::new_with_timeout(
+            vault_key,
+            epoch,
+            idle_timeout_ms,
+        ))
+    }
+
+    /// Check that a session is still usable
+    ///
+    /// Locks the session if its idle timeout has elapsed. Call this before
+    /// handing a cached session back to the UI.
+    ///
+    /// # Errors
+    /// - `PqrrError::InsufficientPrivileges` - Session already locked
+    /// - `PqrrError::SessionExpired` - Idle timeout elapsed (session now locked)
+    pub fn check_session(&self, session: Arc<VaultSession>) -> Result<()> {
+        session.ensure_active("check_session")
+    }
+
+    /// Enroll a password for vault unlock (first-time setup)
+    ///
+    /// Generates a fresh Vault Key and writes it, wrapped under
+    /// `Argon2id(password, salt)` and, if a key wrapper is set, by the
+    /// keystore, to `vault.db.vkwrap` next to the vault.
+    /// The salt must be stored by the caller and passed again to
+    /// `unlock_with_password`.
+    ///
+    /// # Arguments
+    /// - `password`: User password
+    /// - `salt`: Random salt (at least 16 bytes)
+    ///
+    /// # Errors
+    /// - `PqrrError::InvalidStateTransition` - A password is already enrolled
+    /// - `PqrrError::StorageError` - Key derivation, wrapping or the write
+    ///   failed
+    /// - `PqrrError::ReadOnlyMode` - Device is degraded
+    pub fn initialize_vault_with_password(&self, password: String, salt: Vec<u8>) -> Result<()> {
+        self.ensure_writable()?;
+
+        if self.password_wrapped_vk()?.is_some() {
+            return Err(PqrrError::invalid_transition(
+                "Initialized".to_string(),
+                "Initialized".to_string(),
+                "Vault password already enrolled".to_string(),
+            ));
+        }
+
+        let wrapping_key = self.derive_wrapping_key(&password, &salt)?;
+        let vault_key = VaultKey::generate();
+        let nonce = XChaCha20Nonce::random();
+        let ciphertext = AeadCipher::new(&wrapping_key)
+            .encrypt(&nonce, vault_key.as_bytes(), Some(VK_WRAP_AAD))
+            .map_err(|e| PqrrError::storage_error(format!("Vault key wrapping failed: {}", e)))?;
+
+        let mut blob = Vec::with_capacity(VK_WRAP_NONCE_LEN + ciphertext.len());
+        blob.extend_from_slice(nonce.as_bytes());
+        blob.extend_from_slice(&ciphertext);
+        let stored = self.encode_password_wrapped_vk(&blob)?;
+
+        let vault_path = PathBuf::from(&*self.vault_path.read().unwrap());
+        let storage = |e: crate::storage::StorageError| PqrrError::storage_error(e.to_string());
+        let _lock = VaultLock::hold(&vault_path).map_err(storage)?;
+        write_atomically(&password_wrap_path(&vault_path), &[&stored]).map_err(storage)?;
+        *self.password_wrapped_vk.write().unwrap() = Some(blob);
+
+        Ok(())
+    }
+
+    /// Unlock vault with a password - Returns a session handle
+    ///
+    /// Derives the wrapping key with Argon2id, unwraps the Vault Key and
+    /// opens a `VaultSession` held by the engine. Only the handle ID
+    /// crosses the FFI boundary.
+    ///
+    /// # Arguments
+    /// - `password`: User password
+    /// - `salt`: Salt used at enrollment
+    ///
+    /// # Errors
+    /// - `PqrrError::AuthenticationFailed` - Wrong password or salt, or no password enrolled
+    /// - `PqrrError::StorageError` - Invalid KDF parameters or salt, or the
+    ///   wrapped key file cannot be read
+    /// - `PqrrError::TooManySessions` - Session cap reached
+    pub fn unlock_with_password(
+        &self,
+        password: String,
+        salt: Vec<u8>,
+    ) -> Result<VaultSessionHandle> {
+        // Always pay for the KDF so the failure path costs the same
+        let wrapping_key = self.derive_wrapping_key(&password, &salt)?;
+
+        let vault_key = self
+            .password_wrapped_vk()?
+            .as_deref()
+            .filter(|blob| blob.len() > VK_WRAP_NONCE_LEN)
+            .and_then(|blob| {
+                let (nonce, ciphertext) = blob.split_at(VK_WRAP_NONCE_LEN);
+                let nonce = XChaCha20Nonce::try_from_slice(nonce).ok()?;
+                AeadCipher::new(&wrapping_key)
+                    .decrypt(&nonce, ciphertext, Some(VK_WRAP_AAD))
+                    .ok()
+            })
+            .ok_or_else(|| {
+                PqrrError::authentication_failed("Vault key could not be unwrapped".to_string())
+            })?;
+
+        let epoch =
+            PqrrError::epoch_to_u32(self.state_machine.read().unwrap().current_epoch().version)?;
+        let session = Arc::new(VaultSession::new(vault_key, epoch));
+
+        let id = self.sessions.lock().insert(session)?;
+        Ok(VaultSessionHandle { id })
+    }
+
+    /// Look up the session behind a handle
+    ///
+    /// # Errors
+    /// - `PqrrError::InvalidHandle` - Unknown, closed or stale handle
+    pub fn session(&self, handle: VaultSessionHandle) -> Result<Arc<VaultSession>> {
+        self.sessions.lock().get(handle.id).cloned()
+    }
+
+    /// Close a session: lock it (zeroizing the vault key) and drop the handle
+    ///
+    /// Closing an unknown handle is a no-op.
+    pub fn close_session(&self, handle: VaultSessionHandle) {
+        if let Ok(session) = self.sessions.lock().remove(handle.id) {
+            session.lock();
+        }
+    }
+
+    /// Store a vault item, replacing any previous value under `id`
+    ///
+    /// Encrypts `bytes` under the epoch DEK with a fresh nonce; only this
+    /// item and the item index are re-encrypted. The change is applied to
+    /// the items on disk, so items stored by other sessions are kept, and
+    /// written through the AUP shadow write and atomic commit.
+    ///
+    /// # Errors
+    /// - `PqrrError::InvalidHandle` - Unknown, closed or stale handle
+    /// - `PqrrError::InsufficientPrivileges` - Locked session
+    /// - `PqrrError::SessionExpired` - Idle timeout elapsed (session now locked)
+    /// - `PqrrError::StorageError` - No vault is open, encryption or the
+    ///   vault write failed, or another writer holds the vault lock
+    /// - `PqrrError::ReadOnlyMode` - Device is degraded
+    pub fn put_item(&self, session: VaultSessionHandle, id: String, bytes: Vec<u8>) -> Result<()> {
+        self.ensure_writable()?;
+
+        self.with_session_items(session, "put_item", |view, vault_path, dek| {
+            let _lock =
+                VaultLock::hold(vault_path).map_err(|e| PqrrError::storage_error(e.to_string()))?;
+            let mut contents = load_items(vault_path, dek)?;
+            contents
+                .put(dek, ItemId::new(id), &bytes)
+                .map_err(item_error)?;
+            store_items(vault_path, dek, &contents)?;
+            *view = contents;
+            Ok(())
+        })
+    }
+
+    /// Decrypt a vault item - Plaintext only crosses the FFI boundary here
+    ///
+    /// Reads from the session's view of the vault items, loaded from the
+    /// vault on first use.
+    ///
+    /// # Errors
+    /// - `PqrrError::InvalidHandle` - Unknown, closed or stale handle
+    /// - `PqrrError::InsufficientPrivileges` - Locked session
+    /// - `PqrrError::SessionExpired` - Idle timeout elapsed (session now locked)
+    /// - `PqrrError::ItemNotFound` - No item with this ID
+    /// - `PqrrError::StorageError` - No vault is open, or the item is
+    ///   tampered with or undecryptable
+    pub fn get_item(&self, session: VaultSessionHandle, id: String) -> Result<Vec<u8>> {
+        self.with_session_items(session, "get_item", |view, _, dek| {
+            let item_id = ItemId::new(id);
+            view.get(dek, &item_id)
+                .map_err(item_error)?
+                .map(|plaintext| plaintext.to_vec())
+                .ok_or_else(|| PqrrError::item_not_found(item_id.to_string()))
+        })
+    }
+
+    /// Delete a vault item, returning whether it existed
+    ///
+    /// Like `put_item`, applies the change to the items on disk.
+    ///
+    /// # Errors
+    /// - `PqrrError::InvalidHandle` - Unknown, closed or stale handle
+    /// - `PqrrError::InsufficientPrivileges` - Locked session
+    /// - `PqrrError::SessionExpired` - Idle timeout elapsed (session now locked)
+    /// - `PqrrError::StorageError` - No vault is open, resealing the item
+    ///   index or the vault write failed, or another writer holds the vault
+    ///   lock
+    /// - `PqrrError::ReadOnlyMode` - Device is degraded
+    pub fn delete_item(&self, session: VaultSessionHandle, id: String) -> Result<bool> {
+        self.ensure_writable()?;
+
+        self.with_session_items(session, "delete_item", |view, vault_path, dek| {
+            let _lock =
+                VaultLock::hold(vault_path).map_err(|e| PqrrError::storage_error(e.to_string()))?;
+            let mut contents = load_items(vault_path, dek)?;
+            let existed = contents.delete(dek, &ItemId::new(id)).map_err(item_error)?;
+            if existed {
+                store_items(vault_path, dek, &contents)?;
+            }
+            *view = contents;
+            Ok(existed)
+        })
+    }
+
+    /// List vault item IDs (sanitized - no item contents)
+    ///
+    /// # Errors
+    /// - `PqrrError::InvalidHandle` - Unknown, closed or stale handle
+    /// - `PqrrError::InsufficientPrivileges` - Locked session
+    /// - `PqrrError::SessionExpired` - Idle timeout elapsed (session now locked)
+    /// - `PqrrError::StorageError` - No vault is open, or loading it failed
+    pub fn list_items(&self, session: VaultSessionHandle) -> Result<Vec<String>> {
+        self.with_session_items(session, "list_items", |view, _, _| {
+            Ok(view.ids().map(ToString::to_string).collect())
+        })
+    }
+
     /// Get list of all devices (sanitized)
     ///
     /// Returns list of all registered devices with non-sensitive metadata.
@@ -164,15 +1458,16 @@ impl AeternumEngine {
 
         let mut devices = Vec::new();
 
+        let this_device_id = *self.this_device_id.read().unwrap();
         for (device_id, header) in headers.iter() {
             let state = state_machine.state(); // Would be per-device in production
 
             let info = DeviceInfo::new(
                 *device_id,
                 format!("Device {}", device_id.to_string()),
-                header.epoch.version as u32,
+                PqrrError::epoch_to_u32(header.epoch.version)?,
                 state,
-                *device_id == self.this_device_id,
+                *device_id == this_device_id,
             );
 
             devices.push(info);
@@ -181,6 +1476,46 @@ impl AeternumEngine {
         Ok(devices)
     }
 
+    /// List devices page by page
+    ///
+    /// Returns lightweight summaries sorted by creation time (oldest first),
+    /// so the UI can render large device sets without deserializing headers.
+    ///
+    /// # Arguments
+    /// - `filter`: Which device statuses to include
+    /// - `offset`: Number of matching devices to skip
+    /// - `limit`: Maximum number of devices to return
+    /// - `include_shadow_anchor`: Whether to include Device_0 (normally hidden in UI)
+    ///
+    /// # Errors
+    /// - `PqrrError::EpochOutOfRange` - A header epoch does not fit in a `u32`
+    pub fn list_devices(
+        &self,
+        filter: DeviceFilter,
+        offset: u32,
+        limit: u32,
+        include_shadow_anchor: bool,
+    ) -> Result<Vec<DeviceSummary>> {
+        let state_machine = self.state_machine.read().unwrap();
+
+        let device_headers = state_machine.device_headers();
+        let mut headers: Vec<&DeviceHeader> = device_headers
+            .values()
+            .filter(|h| filter.matches(h.status))
+            .filter(|h| include_shadow_anchor || !h.device_id.is_shadow_anchor())
+            .collect();
+
+        // Tie-break on device ID so pages are stable across calls
+        headers.sort_by_key(|h| (h.created_at, *h.device_id.as_bytes()));
+
+        headers
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .map(DeviceSummary::from_header)
+            .collect()
+    }
+
     /// Revoke a device
     ///
     /// # Arguments
@@ -189,7 +1524,10 @@ impl AeternumEngine {
     /// # Errors
     /// - `PqrrError::PermissionDenied` - Not authorized to revoke
     /// - `PqrrError::InsufficientPrivileges` - Cannot revoke this device
+    /// - `PqrrError::ReadOnlyMode` - Device is degraded
     pub fn revoke_device(&self, device_id_bytes: Vec<u8>) -> Result<()> {
+        self.ensure_writable()?;
+
         if device_id_bytes.len() != 16 {
             return Err(PqrrError::InsufficientPrivileges {
                 role: "UI".to_string(),
@@ -202,7 +1540,7 @@ impl AeternumEngine {
         let device_id = DeviceId::from_bytes(bytes);
 
         // Check if trying to revoke this device
-        if device_id == self.this_device_id {
+        if device_id == *self.this_device_id.read().unwrap() {
             return Err(PqrrError::InsufficientPrivileges {
                 role: "UI".to_string(),
                 operation: "revoke_this_device".to_string(),
@@ -219,9 +1557,79 @@ impl AeternumEngine {
         Ok(())
     }
 
+    /// Check whether a revocation awaits its epoch rotation
+    ///
+    /// A revoked device keeps the current DEK until the next epoch upgrade,
+    /// so the UI should prompt `upgrade_epoch()` while this returns `true`.
+    /// Device registration and recovery are blocked until then.
+    pub fn has_pending_revocations(&self) -> bool {
+        self.state_machine.read().unwrap().has_pending_revocations()
+    }
+
+    /// Upgrade the vault to the next cryptographic epoch
+    ///
+    /// Blocks until the upgrade commits or aborts. Progress can be polled
+    /// from another thread with `get_upgrade_progress()`.
+    ///
+    /// # Errors
+    /// - `PqrrError::InvalidStateTransition` - The state machine is not Idle
+    /// - `PqrrError::OperationInProgress` - Another operation is in progress
+    /// - `PqrrError::HeaderIncomplete` - A device header could not be rewrapped
+    /// - `PqrrError::StorageError` - AUP shadow write failed, or the current
+    ///   epoch's DEK is not available
+    /// - `PqrrError::UpgradeFailed` - The commit failed and was rolled back
+    /// - `PqrrError::ReadOnlyMode` - Device is degraded
+    pub fn upgrade_epoch(&self) -> Result<()> {
+        self.ensure_writable()?;
+        *self.upgrade_progress.lock().unwrap() = None;
+
+        let vault_path = self.vault_path.read().unwrap().clone();
+        let mut state_machine = self.state_machine.write().unwrap();
+        let mut epoch_dek = self.epoch_dek.write().unwrap();
+        let current_dek = epoch_dek.as_ref().ok_or_else(|| {
+            PqrrError::storage_error("current epoch DEK not available".to_string())
+        })?;
+        let new_epoch = state_machine.current_epoch().next();
+        let progress = Arc::clone(&self.upgrade_progress);
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut state_machine)
+            .with_current_dek(current_dek)
+            .with_payload_rekey(Box::new(move |payload, old_dek, new_dek| {
+                rekey_items(payload, old_dek, new_dek, new_epoch)
+            }))
+            .with_progress_callback(Box::new(move |p| {
+                *progress.lock().unwrap() = Some(p);
+            }));
+        coordinator.execute_epoch_upgrade(&vault_path, new_epoch, Role::Authorized)?;
+        *epoch_dek = coordinator
+            .current_dek()
+            .map(|dek| DataEncryptionKey::from_bytes(*dek.as_bytes()));
+        self.vault_generation.fetch_add(1, Ordering::AcqRel);
+        drop(coordinator);
+
+        // The new epoch's wrapped DEKs, so a later open finds the current key
+        let headers = state_machine.device_headers().clone();
+        {
+            let _lock = VaultLock::hold(&vault_path)
+                .map_err(|e| PqrrError::storage_error(e.to_string()))?;
+            store_device_headers(Path::new(&vault_path), &headers)?;
+        }
+        *self.device_headers.write().unwrap() = headers;
+        Ok(())
+    }
+
+    /// Latest progress of the running epoch upgrade
+    ///
+    /// # Returns
+    /// The most recent report, or `None` if no upgrade has started. After an
+    /// upgrade finishes the last report (`Done` on success) is kept.
+    pub fn get_upgrade_progress(&self) -> Option<UpgradeProgress> {
+        *self.upgrade_progress.lock().unwrap()
+    }
+
     /// Initiate recovery protocol
     ///
-    /// Starts a 48-hour veto window for recovery.
+    /// Starts a 48-hour veto window for recovery. The attempt is recorded in
+    /// the vault's recovery attempt history before the ID is returned.
     ///
     /// # Returns
     /// Recovery request ID for tracking
@@ -229,21 +1637,41 @@ impl AeternumEngine {
     /// # Errors
     /// - `PqrrError::InvalidStateTransition` - Recovery already in progress
     /// - `PqrrError::InsufficientPrivileges` - Not authorized to initiate
+    /// - `PqrrError::PendingRevocation` - A revocation awaits epoch rotation
+    /// - `PqrrError::RecoveryRateLimited` - Too many recent attempts
+    /// - `PqrrError::ReadOnlyMode` - Device is degraded
+    /// - `PqrrError::StorageError` - Failed to save the attempt history
     pub fn initiate_recovery(&self) -> Result<String> {
+        self.ensure_writable()?;
+
+        let pending = self
+            .state_machine
+            .read()
+            .unwrap()
+            .pending_revocations()
+            .len();
+        if pending > 0 {
+            return Err(PqrrError::pending_revocation(pending as u32));
+        }
+
         // Generate recovery request ID
         let request_id = generate_recovery_id();
 
-        // In production, this would:
-        // 1. Transition state machine to RecoveryInitiated
-        // 2. Open 48-hour veto window
-        // 3. Notify other devices
+        let mut state_machine = self.state_machine.write().unwrap();
+        let now = state_machine.time_source().now_ms();
+        state_machine.transition_to_recovery_internal(request_id.clone(), now, Role::Recovery)?;
+        self.save_recovery_attempts(&state_machine)?;
+
+        // In production, this would also notify the other devices
 
-        // For demo, just return an ID
         Ok(request_id)
     }
 
     /// Submit veto for recovery request
     ///
+    /// Allowed in Degraded state: a veto only blocks a pending recovery
+    /// (Invariant #4), so read-only devices can still exercise it.
+    ///
     /// # Arguments
     /// - `recovery_id`: Recovery request ID to veto
     ///
@@ -282,6 +1710,48 @@ impl AeternumEngine {
         Ok(!vault_blob.is_empty())
     }
 
+    /// Re-check device integrity and leave Degraded state
+    ///
+    /// # Arguments
+    /// - `integrity_token`: Fresh integrity token (Play Integrity verdict)
+    ///
+    /// # Returns
+    /// `true` if the token passed and the engine is writable again,
+    /// `false` if the token was rejected and the engine stays read-only
+    ///
+    /// # Errors
+    /// - `PqrrError::InvalidStateTransition` - Device has been revoked
+    /// - `PqrrError::StorageError` - Failed to save the recovery attempt
+    ///   history
+    pub fn recheck_integrity(&self, integrity_token: Vec<u8>) -> Result<bool> {
+        let mut state_machine = self.state_machine.write().unwrap();
+        let state = state_machine.state();
+
+        if state.is_terminal() {
+            return Err(PqrrError::invalid_transition(
+                state.as_str().to_string(),
+                "Idle".to_string(),
+                "cannot recheck integrity of a revoked device".to_string(),
+            ));
+        }
+
+        if !matches!(state, ProtocolState::Degraded) {
+            return Ok(true);
+        }
+
+        // In production, this would verify the Play Integrity verdict
+        // (signature, nonce freshness, device/app integrity labels).
+
+        // For demo, any non-empty token passes
+        if integrity_token.is_empty() {
+            return Ok(false);
+        }
+
+        state_machine.return_to_idle_internal()?;
+        self.save_recovery_attempts(&state_machine)?;
+        Ok(true)
+    }
+
     /// Shutdown the engine - Clean up resources
     ///
     /// Should be called when the app is shutting down or vault is no longer needed.
@@ -303,7 +1773,7 @@ mod tests {
     #[test]
     fn test_engine_creation() {
         let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
-        assert_eq!(engine.vault_path, "/tmp/test_vault");
+        assert_eq!(*engine.vault_path.read().unwrap(), "/tmp/test_vault");
     }
 
     #[test]
@@ -315,6 +1785,642 @@ mod tests {
         assert!(session.is_valid());
     }
 
+    #[test]
+    fn test_upgrade_epoch_reports_progress() {
+        use crate::protocol::epoch_upgrade::UpgradePhase;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
+        engine
+            .initialize_vault(
+                test_mnemonic(),
+                temp_dir.path().display().to_string(),
+                false,
+            )
+            .unwrap();
+        assert_eq!(engine.get_upgrade_progress(), None);
+
+        engine.upgrade_epoch().unwrap();
+
+        assert_eq!(
+            engine.get_upgrade_progress(),
+            Some(UpgradeProgress {
+                target_epoch: 2,
+                phase: UpgradePhase::Done,
+            })
+        );
+        assert_eq!(
+            engine.state_machine.read().unwrap().current_epoch().version,
+            2
+        );
+        // The next upgrade unseals the vault with the DEK of epoch 2
+        engine.upgrade_epoch().unwrap();
+        assert_eq!(
+            engine.state_machine.read().unwrap().current_epoch().version,
+            3
+        );
+    }
+
+    fn test_mnemonic() -> String {
+        use crate::models::key_hierarchy::MnemonicLength;
+        let (mnemonic, _) = MasterSeed::generate_mnemonic(MnemonicLength::Words24).unwrap();
+        mnemonic.to_string()
+    }
+
+    #[test]
+    fn test_initialize_vault_then_reopen_and_cold_recover() {
+        use crate::storage::aug::{read_vault_blob, read_vault_epoch};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_dir = temp_dir.path().join("vault");
+        let mnemonic = test_mnemonic();
+
+        let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
+        let report = engine
+            .initialize_vault(mnemonic.clone(), vault_dir.display().to_string(), false)
+            .unwrap();
+
+        let vault_path = vault_dir.join(VAULT_FILE_NAME);
+        assert_eq!(report.vault_path, vault_path.display().to_string());
+        assert_eq!(report.epoch, 1);
+        assert!(!report.replaced_existing);
+        assert_eq!(*engine.vault_path.read().unwrap(), report.vault_path);
+
+        // Re-open from disk
+        assert_eq!(read_vault_epoch(&vault_path).unwrap(), 1);
+        assert_eq!(read_vault_blob(&vault_path).unwrap().epoch.version, 1);
+        let metadata = SqliteMetadataStore::open(vault_dir.join(METADATA_FILE_NAME)).unwrap();
+        assert_eq!(
+            crate::storage::MetadataStore::get_local_epoch(&metadata).unwrap(),
+            1
+        );
+
+        // State machine at epoch 1 with only the shadow anchor
+        let anchor = {
+            let state_machine = engine.state_machine.read().unwrap();
+            assert_eq!(state_machine.current_epoch().version, 1);
+            assert_eq!(state_machine.device_headers().len(), 1);
+            let anchor = state_machine.device_headers()[&DeviceId::shadow_anchor()].clone();
+            anchor
+        };
+        assert_eq!(read_device_headers(&vault_path).unwrap(), vec![anchor]);
+
+        // Cold recovery from the same mnemonic yields the anchor's DEK
+        let first = AeternumEngine::cold_recover(&mnemonic, &vault_dir).unwrap();
+        let second = AeternumEngine::cold_recover(&mnemonic, &vault_dir).unwrap();
+        assert!(first.device_id.is_shadow_anchor());
+        assert_eq!(first.dek.as_bytes(), second.dek.as_bytes());
+
+        let other = AeternumEngine::cold_recover(&test_mnemonic(), &vault_dir);
+        assert!(matches!(other, Err(PqrrError::AuthenticationFailed { .. })));
+    }
+
+    #[test]
+    fn test_initialize_vault_refuses_existing_vault() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_dir = temp_dir.path().display().to_string();
+        let vault_path = temp_dir.path().join(VAULT_FILE_NAME);
+        let first_mnemonic = test_mnemonic();
+
+        let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
+        engine
+            .initialize_vault(first_mnemonic.clone(), vault_dir.clone(), false)
+            .unwrap();
+        let original = std::fs::read(&vault_path).unwrap();
+
+        let second_mnemonic = test_mnemonic();
+        let result = engine.initialize_vault(second_mnemonic.clone(), vault_dir.clone(), false);
+        assert!(matches!(
+            result,
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+        assert_eq!(std::fs::read(&vault_path).unwrap(), original);
+
+        // Forced: the new mnemonic owns the vault, the old one no longer opens it
+        let report = engine
+            .initialize_vault(second_mnemonic.clone(), vault_dir.clone(), true)
+            .unwrap();
+        assert!(report.replaced_existing);
+        assert!(AeternumEngine::cold_recover(&second_mnemonic, &vault_dir).is_ok());
+        assert!(AeternumEngine::cold_recover(&first_mnemonic, &vault_dir).is_err());
+    }
+
+    #[test]
+    fn test_initialize_vault_failed_overwrite_keeps_existing_vault() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_dir = temp_dir.path().display().to_string();
+        let vault_path = temp_dir.path().join(VAULT_FILE_NAME);
+        let mnemonic = test_mnemonic();
+
+        let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
+        engine
+            .initialize_vault(mnemonic.clone(), vault_dir.clone(), false)
+            .unwrap();
+        let original = std::fs::read(&vault_path).unwrap();
+
+        // A file where the staging directory belongs makes staging fail
+        std::fs::write(staging_dir(&vault_path), b"blocker").unwrap();
+        let result = engine.initialize_vault(test_mnemonic(), vault_dir.clone(), true);
+        assert!(matches!(result, Err(PqrrError::StorageError { .. })));
+
+        assert_eq!(std::fs::read(&vault_path).unwrap(), original);
+        assert!(AeternumEngine::cold_recover(&mnemonic, &vault_dir).is_ok());
+    }
+
+    #[test]
+    fn test_initialize_vault_derives_anchor_key_from_mnemonic() {
+        let first_dir = tempfile::TempDir::new().unwrap();
+        let second_dir = tempfile::TempDir::new().unwrap();
+        let mnemonic = test_mnemonic();
+
+        let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
+        for dir in [&first_dir, &second_dir] {
+            engine
+                .initialize_vault(mnemonic.clone(), dir.path().display().to_string(), false)
+                .unwrap();
+        }
+
+        let expected = recovery_key_from_mnemonic(&mnemonic)
+            .unwrap()
+            .derive_anchor_keypair()
+            .public;
+        for dir in [&first_dir, &second_dir] {
+            let headers = read_device_headers(&dir.path().join(VAULT_FILE_NAME)).unwrap();
+            assert_eq!(headers.len(), 1);
+            assert_eq!(headers[0].public_key, expected);
+        }
+    }
+
+    #[test]
+    fn test_initialize_vault_failure_leaves_nothing_behind() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
+
+        // Invalid mnemonic: the directory is never created
+        let vault_dir = temp_dir.path().join("invalid");
+        let result = engine.initialize_vault(
+            "not a mnemonic".to_string(),
+            vault_dir.display().to_string(),
+            false,
+        );
+        assert!(matches!(
+            result,
+            Err(PqrrError::AuthenticationFailed { .. })
+        ));
+        assert!(!vault_dir.exists());
+
+        // Shadow write blocked after the side files are written
+        let vault_dir = temp_dir.path().join("blocked");
+        std::fs::create_dir(&vault_dir).unwrap();
+        let blocker = vault_dir.join("vault.db.tmp");
+        std::fs::create_dir(&blocker).unwrap();
+
+        let result =
+            engine.initialize_vault(test_mnemonic(), vault_dir.display().to_string(), false);
+        assert!(matches!(result, Err(PqrrError::StorageError { .. })));
+        let mut left: Vec<_> = std::fs::read_dir(&vault_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        left.sort();
+        // The lock file is never deleted (see `storage::lock`)
+        let lock_path = VaultLock::lock_path(vault_dir.join(VAULT_FILE_NAME));
+        assert_eq!(left, vec![lock_path, blocker]);
+
+        // The engine is still on its previous vault
+        assert_eq!(*engine.vault_path.read().unwrap(), "/tmp/test_vault");
+    }
+
+    fn password_engine(dir: &tempfile::TempDir) -> AeternumEngine {
+        AeternumEngine::new_with_path(dir.path().join(VAULT_FILE_NAME).display().to_string())
+            .unwrap()
+            .with_kdf_config(Argon2idConfig::new(8192, 1, 1, 32))
+    }
+
+    #[test]
+    fn test_unlock_with_password() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let engine = password_engine(&dir);
+        let salt = vec![7u8; 16];
+        engine
+            .initialize_vault_with_password("correct horse".to_string(), salt.clone())
+            .unwrap();
+
+        let handle = engine
+            .unlock_with_password("correct horse".to_string(), salt)
+            .unwrap();
+        let session = engine.session(handle).unwrap();
+        assert!(session.is_valid());
+
+        engine.close_session(handle);
+        assert!(!session.is_valid());
+        assert_eq!(
+            engine.session(handle).err(),
+            Some(PqrrError::invalid_handle(handle.id))
+        );
+
+        // The reopened session reuses the slot; the old handle stays dead
+        let reopened = engine
+            .unlock_with_password("correct horse".to_string(), vec![7u8; 16])
+            .unwrap();
+        assert_ne!(reopened, handle);
+        assert!(engine.session(handle).is_err());
+        assert!(engine.session(reopened).unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_password_survives_engine_restart() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let salt = vec![7u8; 16];
+        password_engine(&dir)
+            .initialize_vault_with_password("correct horse".to_string(), salt.clone())
+            .unwrap();
+
+        let restarted = password_engine(&dir);
+        let handle = restarted
+            .unlock_with_password("correct horse".to_string(), salt.clone())
+            .unwrap();
+        assert!(restarted.session(handle).unwrap().is_valid());
+        assert!(matches!(
+            restarted.unlock_with_password("battery staple".to_string(), salt.clone()),
+            Err(PqrrError::AuthenticationFailed { .. })
+        ));
+        assert!(matches!(
+            restarted.initialize_vault_with_password("battery staple".to_string(), salt),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+    }
+
+    #[test]
+    fn test_unlock_respects_session_cap() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let engine = password_engine(&dir).with_max_sessions(1);
+        let salt = vec![7u8; 16];
+        engine
+            .initialize_vault_with_password("correct horse".to_string(), salt.clone())
+            .unwrap();
+
+        let handle = engine
+            .unlock_with_password("correct horse".to_string(), salt.clone())
+            .unwrap();
+        assert_eq!(
+            engine.unlock_with_password("correct horse".to_string(), salt.clone()),
+            Err(PqrrError::too_many_sessions(1))
+        );
+
+        engine.close_session(handle);
+        assert!(engine
+            .unlock_with_password("correct horse".to_string(), salt)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_unlock_with_wrong_password() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let engine = password_engine(&dir);
+        let salt = vec![7u8; 16];
+
+        // No password enrolled yet
+        let result = engine.unlock_with_password("correct horse".to_string(), salt.clone());
+        assert!(matches!(
+            result,
+            Err(PqrrError::AuthenticationFailed { .. })
+        ));
+
+        engine
+            .initialize_vault_with_password("correct horse".to_string(), salt.clone())
+            .unwrap();
+
+        let wrong_password = engine.unlock_with_password("battery staple".to_string(), salt);
+        let wrong_salt = engine.unlock_with_password("correct horse".to_string(), vec![8u8; 16]);
+        assert_eq!(wrong_password, wrong_salt);
+        assert!(matches!(
+            wrong_password,
+            Err(PqrrError::AuthenticationFailed { .. })
+        ));
+        assert!(engine.sessions.lock().is_empty());
+    }
+
+    #[test]
+    fn test_unlock_handle_carries_no_key_material() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let engine = password_engine(&dir);
+        let salt = vec![7u8; 16];
+        engine
+            .initialize_vault_with_password("correct horse".to_string(), salt.clone())
+            .unwrap();
+
+        let first = engine
+            .unlock_with_password("correct horse".to_string(), salt.clone())
+            .unwrap();
+        let second = engine
+            .unlock_with_password("correct horse".to_string(), salt)
+            .unwrap();
+
+        // The FFI return type is a bare counter, independent of the vault key
+        assert_eq!(
+            std::mem::size_of::<VaultSessionHandle>(),
+            std::mem::size_of::<u64>()
+        );
+        assert_eq!(second.id, first.id + 1);
+
+        // Re-enrolling would discard the wrapped key
+        assert!(engine
+            .initialize_vault_with_password("other".to_string(), vec![1u8; 16])
+            .is_err());
+    }
+
+    fn item_session(engine: &AeternumEngine, dir: &tempfile::TempDir) -> VaultSessionHandle {
+        engine
+            .initialize_vault(test_mnemonic(), dir.path().display().to_string(), false)
+            .unwrap();
+        unlock_items(engine)
+    }
+
+    fn unlock_items(engine: &AeternumEngine) -> VaultSessionHandle {
+        let salt = vec![7u8; 16];
+        engine
+            .initialize_vault_with_password("correct horse".to_string(), salt.clone())
+            .unwrap();
+        engine
+            .unlock_with_password("correct horse".to_string(), salt)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_item_crud_roundtrip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let engine = password_engine(&dir);
+        let handle = item_session(&engine, &dir);
+        assert!(engine.list_items(handle).unwrap().is_empty());
+
+        engine
+            .put_item(handle, "b".to_string(), b"bravo".to_vec())
+            .unwrap();
+        engine
+            .put_item(handle, "a".to_string(), b"alpha".to_vec())
+            .unwrap();
+        assert_eq!(engine.get_item(handle, "a".to_string()).unwrap(), b"alpha");
+        assert_eq!(engine.list_items(handle).unwrap(), ["a", "b"]);
+
+        // Replacing re-encrypts only that item
+        let vault_path = dir.path().join(VAULT_FILE_NAME);
+        let stored = || {
+            let dek = engine.epoch_dek.read().unwrap();
+            load_items(&vault_path, dek.as_ref().unwrap()).unwrap()
+        };
+        let untouched = stored().items[&ItemId::new("b")].clone();
+        engine
+            .put_item(handle, "a".to_string(), b"alpha 2".to_vec())
+            .unwrap();
+        assert_eq!(
+            engine.get_item(handle, "a".to_string()).unwrap(),
+            b"alpha 2"
+        );
+        assert_eq!(stored().items[&ItemId::new("b")], untouched);
+
+        assert!(engine.delete_item(handle, "a".to_string()).unwrap());
+        assert!(!engine.delete_item(handle, "a".to_string()).unwrap());
+        assert!(matches!(
+            engine.get_item(handle, "a".to_string()),
+            Err(PqrrError::ItemNotFound { .. })
+        ));
+        assert_eq!(engine.list_items(handle).unwrap(), ["b"]);
+        assert_eq!(stored().items.len(), 1);
+
+        // A closed session can no longer reach the items
+        engine.close_session(handle);
+        assert!(engine.get_item(handle, "b".to_string()).is_err());
+        assert!(engine.list_items(handle).is_err());
+    }
+
+    #[test]
+    fn test_items_survive_engine_restart() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mnemonic = test_mnemonic();
+        let engine = password_engine(&dir);
+        engine
+            .initialize_vault(mnemonic.clone(), dir.path().display().to_string(), false)
+            .unwrap();
+        let handle = unlock_items(&engine);
+        engine
+            .put_item(handle, "a".to_string(), b"alpha".to_vec())
+            .unwrap();
+        drop(engine);
+
+        let engine = password_engine(&dir);
+        engine
+            .open_vault_with_mnemonic(mnemonic, dir.path().display().to_string())
+            .unwrap();
+        let handle = engine
+            .unlock_with_password("correct horse".to_string(), vec![7u8; 16])
+            .unwrap();
+        assert_eq!(engine.get_item(handle, "a".to_string()).unwrap(), b"alpha");
+    }
+
+    #[test]
+    fn test_imported_vault_unlocks() {
+        let src = tempfile::TempDir::new().unwrap();
+        let dest = tempfile::TempDir::new().unwrap();
+        let mnemonic = test_mnemonic();
+        let engine = password_engine(&src);
+        engine
+            .initialize_vault(mnemonic.clone(), src.path().display().to_string(), false)
+            .unwrap();
+        let handle = unlock_items(&engine);
+        engine
+            .put_item(handle, "a".to_string(), b"alpha".to_vec())
+            .unwrap();
+        drop(engine);
+
+        let archive_path = src.path().join("vault.aex");
+        crate::storage::export::export_vault_with_config(
+            src.path().join(VAULT_FILE_NAME),
+            "passphrase",
+            &archive_path,
+            Argon2idConfig::new(8192, 1, 1, 32),
+        )
+        .unwrap();
+        crate::storage::import_vault(&archive_path, "passphrase", dest.path()).unwrap();
+
+        let engine = password_engine(&dest);
+        let handle = engine
+            .unlock_with_password("correct horse".to_string(), vec![7u8; 16])
+            .unwrap();
+        engine
+            .open_vault_with_mnemonic(mnemonic, dest.path().display().to_string())
+            .unwrap();
+        assert_eq!(engine.get_item(handle, "a".to_string()).unwrap(), b"alpha");
+    }
+
+    #[test]
+    fn test_items_survive_epoch_upgrade() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mnemonic = test_mnemonic();
+        let engine = password_engine(&dir);
+        engine
+            .initialize_vault(mnemonic.clone(), dir.path().display().to_string(), false)
+            .unwrap();
+        let handle = unlock_items(&engine);
+        engine
+            .put_item(handle, "a".to_string(), b"alpha".to_vec())
+            .unwrap();
+
+        engine.upgrade_epoch().unwrap();
+
+        // The open session reloads its view under the new DEK
+        assert_eq!(engine.get_item(handle, "a".to_string()).unwrap(), b"alpha");
+        engine
+            .put_item(handle, "b".to_string(), b"bravo".to_vec())
+            .unwrap();
+        drop(engine);
+
+        // The persisted headers carry the new epoch's DEK
+        let engine = password_engine(&dir);
+        engine
+            .open_vault_with_mnemonic(mnemonic, dir.path().display().to_string())
+            .unwrap();
+        assert_eq!(
+            engine.state_machine.read().unwrap().current_epoch().version,
+            2
+        );
+        let handle = engine
+            .unlock_with_password("correct horse".to_string(), vec![7u8; 16])
+            .unwrap();
+        assert_eq!(engine.list_items(handle).unwrap(), ["a", "b"]);
+    }
+
+    fn keystore_engine(
+        dir: &tempfile::TempDir,
+        wrapper: &Arc<crate::crypto::wrap::AeadKeyWrapper>,
+    ) -> AeternumEngine {
+        password_engine(dir).with_key_wrapper(wrapper.clone())
+    }
+
+    #[test]
+    fn test_open_vault_with_keystore_wrapped_device_key() {
+        use crate::crypto::wrap::AeadKeyWrapper;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let vault_dir = dir.path().display().to_string();
+        let vault_path = dir.path().join(VAULT_FILE_NAME);
+        let wrapper = Arc::new(AeadKeyWrapper::generate());
+        let engine = keystore_engine(&dir, &wrapper);
+        let handle = item_session(&engine, &dir);
+        engine
+            .put_item(handle, "a".to_string(), b"alpha".to_vec())
+            .unwrap();
+        engine.upgrade_epoch().unwrap();
+        let device_id = *engine.this_device_id.read().unwrap();
+        assert!(engine
+            .device_headers
+            .read()
+            .unwrap()
+            .contains_key(&device_id));
+        drop(engine);
+
+        // Both the device secret and the vault key are keystore-wrapped
+        for path in [
+            device_secret_path(&vault_path),
+            password_wrap_path(&vault_path),
+        ] {
+            assert_eq!(std::fs::read(path).unwrap()[0], VK_WRAP_KEYSTORE);
+        }
+
+        let engine = keystore_engine(&dir, &wrapper);
+        engine.open_vault(vault_dir.clone()).unwrap();
+        assert_eq!(*engine.this_device_id.read().unwrap(), device_id);
+        let handle = engine
+            .unlock_with_password("correct horse".to_string(), vec![7u8; 16])
+            .unwrap();
+        assert_eq!(engine.get_item(handle, "a".to_string()).unwrap(), b"alpha");
+
+        // Without the keystore key neither opens
+        let engine = password_engine(&dir);
+        assert!(matches!(
+            engine.open_vault(vault_dir.clone()),
+            Err(PqrrError::StorageError { .. })
+        ));
+        let engine = keystore_engine(&dir, &Arc::new(AeadKeyWrapper::generate()));
+        assert!(matches!(
+            engine.open_vault(vault_dir),
+            Err(PqrrError::AuthenticationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_password_wrapped_vk_migrates_to_keystore() {
+        use crate::crypto::wrap::AeadKeyWrapper;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let vault_path = dir.path().join(VAULT_FILE_NAME);
+        let engine = password_engine(&dir);
+        item_session(&engine, &dir);
+        assert_eq!(
+            std::fs::read(password_wrap_path(&vault_path))
+                .unwrap()
+                .len(),
+            VK_WRAP_LEN
+        );
+        assert!(!device_secret_path(&vault_path).exists());
+        drop(engine);
+
+        let wrapper = Arc::new(AeadKeyWrapper::generate());
+        let engine = keystore_engine(&dir, &wrapper);
+        engine
+            .unlock_with_password("correct horse".to_string(), vec![7u8; 16])
+            .unwrap();
+        let stored = std::fs::read(password_wrap_path(&vault_path)).unwrap();
+        assert_eq!(stored[0], VK_WRAP_KEYSTORE);
+        assert_ne!(stored.len(), VK_WRAP_LEN);
+
+        let engine = keystore_engine(&dir, &wrapper);
+        assert!(engine
+            .unlock_with_password("correct horse".to_string(), vec![7u8; 16])
+            .is_ok());
+        let engine = password_engine(&dir);
+        assert!(matches!(
+            engine.unlock_with_password("correct horse".to_string(), vec![7u8; 16]),
+            Err(PqrrError::StorageError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_item_writes_denied_while_degraded() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let engine = password_engine(&dir);
+        let handle = item_session(&engine, &dir);
+        engine
+            .put_item(handle, "a".to_string(), b"alpha".to_vec())
+            .unwrap();
+
+        degrade(&engine, "integrity verdict failed");
+        assert!(is_read_only(engine.put_item(
+            handle,
+            "b".to_string(),
+            b"bravo".to_vec()
+        )));
+        assert!(is_read_only(engine.delete_item(handle, "a".to_string())));
+        assert_eq!(engine.get_item(handle, "a".to_string()).unwrap(), b"alpha");
+        assert_eq!(engine.list_items(handle).unwrap(), ["a"]);
+    }
+
+    #[test]
+    fn test_check_session_rejects_expired_session() {
+        let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
+
+        let session = Arc::new(engine.unlock_with_timeout(vec![1u8, 2, 3, 4], 0).unwrap());
+        std::thread::sleep(std::time::Duration::from_millis(5));
+
+        let result = engine.check_session(Arc::clone(&session));
+        assert!(matches!(
+            result.unwrap_err(),
+            PqrrError::SessionExpired { .. }
+        ));
+        assert!(!session.is_valid());
+        assert!(engine.check_session(session).is_err());
+    }
+
     #[test]
     fn test_get_device_list() {
         let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
@@ -327,7 +2433,7 @@ mod tests {
     #[test]
     fn test_revoke_this_device_fails() {
         let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
-        let this_device_id = engine.this_device_id.as_bytes().to_vec();
+        let this_device_id = engine.this_device_id.read().unwrap().as_bytes().to_vec();
 
         let result = engine.revoke_device(this_device_id);
         assert!(result.is_err());
@@ -345,6 +2451,71 @@ mod tests {
         assert!(!recovery_id.is_empty());
     }
 
+    #[test]
+    fn test_recovery_attempts_survive_engine_restart() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mnemonic = test_mnemonic();
+        let vault_path = dir.path().join(VAULT_FILE_NAME);
+        let engine = password_engine(&dir);
+        engine
+            .initialize_vault(mnemonic.clone(), dir.path().display().to_string(), false)
+            .unwrap();
+        let recovery_id = engine.initiate_recovery().unwrap();
+        drop(engine);
+
+        let engine = password_engine(&dir);
+        let attempts = engine.state_machine.read().unwrap().recovery_attempts();
+        assert_eq!(attempts.attempts()[0].request_id, recovery_id);
+
+        engine
+            .open_vault_with_mnemonic(mnemonic.clone(), dir.path().display().to_string())
+            .unwrap();
+        let attempts = engine.state_machine.read().unwrap().recovery_attempts();
+        assert_eq!(attempts.attempts().len(), 1);
+
+        // A corrupt history is refused rather than reset
+        std::fs::write(recovery_attempts_path(&vault_path), b"garbage").unwrap();
+        assert!(matches!(
+            AeternumEngine::new_with_path(vault_path.display().to_string()),
+            Err(PqrrError::StorageError { .. })
+        ));
+        assert!(matches!(
+            engine.open_vault_with_mnemonic(mnemonic, dir.path().display().to_string()),
+            Err(PqrrError::StorageError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_vault_without_attempt_history_opens() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mnemonic = test_mnemonic();
+        let vault_path = dir.path().join(VAULT_FILE_NAME);
+        let engine = password_engine(&dir);
+        engine
+            .initialize_vault(mnemonic.clone(), dir.path().display().to_string(), false)
+            .unwrap();
+        drop(engine);
+
+        // As written before the history existed
+        std::fs::remove_file(recovery_attempts_path(&vault_path)).unwrap();
+
+        let engine = password_engine(&dir);
+        assert!(recovery_attempts_path(&vault_path).exists());
+        std::fs::remove_file(recovery_attempts_path(&vault_path)).unwrap();
+        engine
+            .open_vault_with_mnemonic(mnemonic, dir.path().display().to_string())
+            .unwrap();
+        assert!(engine
+            .state_machine
+            .read()
+            .unwrap()
+            .recovery_attempts()
+            .attempts()
+            .is_empty());
+        assert!(recovery_attempts_path(&vault_path).exists());
+        assert!(engine.initiate_recovery().is_ok());
+    }
+
     #[test]
     fn test_verify_vault_integrity_empty_blob() {
         let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
@@ -360,4 +2531,232 @@ mod tests {
 
         assert!(result); // Non-empty blob is valid (demo)
     }
+
+    fn degrade(engine: &AeternumEngine, reason: &str) {
+        engine
+            .state_machine
+            .write()
+            .unwrap()
+            .transition_to_degraded_with_reason(reason.to_string())
+            .unwrap();
+    }
+
+    fn is_read_only(result: Result<impl Sized>) -> bool {
+        matches!(result, Err(PqrrError::ReadOnlyMode { .. }))
+    }
+
+    #[test]
+    fn test_degraded_allow_deny_matrix() {
+        let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
+        let other_device = DeviceId::generate().as_bytes().to_vec();
+
+        degrade(&engine, "integrity verdict failed");
+
+        // Mutating operations are denied
+        assert!(is_read_only(
+            engine.initialize_hardware_vault(vec![1, 2, 3])
+        ));
+        assert!(is_read_only(engine.revoke_device(other_device.clone())));
+        assert!(is_read_only(engine.initiate_recovery()));
+
+        // Read/decrypt operations keep working
+        let session = engine.unlock(vec![1, 2, 3]).unwrap();
+        assert!(session.is_valid());
+        assert!(engine.get_device_list().is_ok());
+        assert!(engine.verify_vault_integrity(vec![1, 2, 3]).unwrap());
+        assert!(engine.submit_veto("rec_1".to_string()).is_ok());
+
+        // Back to Idle: mutating operations are allowed again
+        assert!(engine.recheck_integrity(vec![0xAA]).unwrap());
+        assert!(engine.initialize_hardware_vault(vec![1, 2, 3]).is_ok());
+        assert!(engine.initiate_recovery().is_ok());
+        assert!(!is_read_only(engine.revoke_device(other_device)));
+    }
+
+    #[test]
+    fn test_read_only_error_carries_reason() {
+        let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
+        degrade(&engine, "root detected");
+
+        match engine.initialize_hardware_vault(vec![]) {
+            Err(PqrrError::ReadOnlyMode { reason }) => assert_eq!(reason, "root detected"),
+            other => panic!("expected ReadOnlyMode, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_recheck_integrity_rejects_empty_token() {
+        let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
+        degrade(&engine, "integrity verdict failed");
+
+        assert!(!engine.recheck_integrity(vec![]).unwrap());
+        assert!(is_read_only(engine.initiate_recovery()));
+    }
+
+    #[test]
+    fn test_recheck_integrity_from_revoked_fails() {
+        let engine = AeternumEngine::new_with_path("/tmp/test_vault".to_string()).unwrap();
+        engine
+            .state_machine
+            .write()
+            .unwrap()
+            .transition_to_revoked_internal()
+            .unwrap();
+
+        let result = engine.recheck_integrity(vec![0xAA]);
+        assert!(matches!(
+            result,
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+    }
+
+    /// Engine with a shadow anchor plus 50 devices; every 5th is revoked
+    fn engine_with_devices() -> AeternumEngine {
+        use crate::crypto::kem::{KyberCipherText, KyberPublicKeyBytes};
+        use crate::models::device::DeviceStatus;
+
+        let epoch = CryptoEpoch::initial();
+        let mut headers = HashMap::new();
+
+        let mut anchor = DeviceHeader::new(
+            DeviceId::shadow_anchor(),
+            epoch,
+            KyberPublicKeyBytes([0u8; 1568]),
+            KyberCipherText([0u8; 1568]),
+        );
+        anchor.created_at = 0;
+        headers.insert(anchor.device_id, anchor);
+
+        // Insert in reverse creation order to exercise sorting
+        for i in (0..50u8).rev() {
+            let mut id = [0u8; 16];
+            id[0] = i + 1;
+            let device_id = DeviceId::from_bytes(id);
+            let mut header = DeviceHeader::new(
+                device_id,
+                epoch,
+                KyberPublicKeyBytes([0u8; 1568]),
+                KyberCipherText([0u8; 1568]),
+            );
+            header.created_at = 1_000 + i as u64;
+            if i % 5 == 0 {
+                header.status = DeviceStatus::Revoked;
+            }
+            headers.insert(device_id, header);
+        }
+
+        let state_machine = PqrrStateMachine::create(epoch, headers);
+        AeternumEngine::new(
+            "/tmp/test_vault".to_string(),
+            state_machine,
+            DeviceId::generate(),
+        )
+    }
+
+    #[test]
+    fn test_list_devices_pagination_boundaries() {
+        let engine = engine_with_devices();
+
+        let first = engine
+            .list_devices(DeviceFilter::All, 0, 20, false)
+            .unwrap();
+        let second = engine
+            .list_devices(DeviceFilter::All, 20, 20, false)
+            .unwrap();
+        let last = engine
+            .list_devices(DeviceFilter::All, 40, 20, false)
+            .unwrap();
+        assert_eq!(first.len(), 20);
+        assert_eq!(second.len(), 20);
+        assert_eq!(last.len(), 10);
+
+        let all: Vec<u64> = first
+            .iter()
+            .chain(&second)
+            .chain(&last)
+            .map(|d| d.created_at)
+            .collect();
+        let expected: Vec<u64> = (1_000..1_050).collect();
+        assert_eq!(all, expected);
+
+        assert_eq!(
+            engine
+                .list_devices(DeviceFilter::All, 49, 20, false)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(engine
+            .list_devices(DeviceFilter::All, 50, 20, false)
+            .unwrap()
+            .is_empty());
+        assert!(engine
+            .list_devices(DeviceFilter::All, u32::MAX, 20, false)
+            .unwrap()
+            .is_empty());
+        assert!(engine
+            .list_devices(DeviceFilter::All, 0, 0, false)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            engine
+                .list_devices(DeviceFilter::All, 0, u32::MAX, false)
+                .unwrap()
+                .len(),
+            50
+        );
+    }
+
+    #[test]
+    fn test_list_devices_filter() {
+        let engine = engine_with_devices();
+
+        let active = engine
+            .list_devices(DeviceFilter::Active, 0, 100, false)
+            .unwrap();
+        let revoked = engine
+            .list_devices(DeviceFilter::Revoked, 0, 100, false)
+            .unwrap();
+        let all = engine
+            .list_devices(DeviceFilter::All, 0, 100, false)
+            .unwrap();
+
+        assert_eq!(active.len(), 40);
+        assert_eq!(revoked.len(), 10);
+        assert_eq!(all.len(), 50);
+        assert!(active.iter().all(|d| d.status == "Active"));
+        assert!(revoked.iter().all(|d| d.status == "Revoked"));
+
+        let page = engine
+            .list_devices(DeviceFilter::Revoked, 8, 5, false)
+            .unwrap();
+        assert_eq!(
+            page.iter().map(|d| d.created_at).collect::<Vec<_>>(),
+            vec![1_040, 1_045]
+        );
+    }
+
+    #[test]
+    fn test_list_devices_shadow_anchor_flag() {
+        let engine = engine_with_devices();
+
+        let hidden = engine
+            .list_devices(DeviceFilter::All, 0, 100, false)
+            .unwrap();
+        assert!(hidden.iter().all(|d| !d.is_shadow_anchor));
+
+        let shown = engine
+            .list_devices(DeviceFilter::Active, 0, 1, true)
+            .unwrap();
+        assert_eq!(shown.len(), 1);
+        assert!(shown[0].is_shadow_anchor);
+        assert_eq!(shown[0].device_id, DeviceId::shadow_anchor().to_string());
+        assert_eq!(
+            engine
+                .list_devices(DeviceFilter::All, 0, 100, true)
+                .unwrap()
+                .len(),
+            51
+        );
+    }
 }
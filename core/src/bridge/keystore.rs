@@ -0,0 +1,109 @@
+//! # Hardware Keystore Wrapping
+//!
+//! UniFFI callback interface through which the Android app wraps locally
+//! stored key material with a key held in the hardware keystore
+//! (StrongBox).
+//!
+//! Kotlin implements [`HardwareKeyWrapper`] and hands it to
+//! [`AeternumEngine::set_hardware_key_wrapper`]. Rust adapts it to the
+//! crypto layer's [`KeyWrapper`] with [`ForeignKeyWrapper`], which the
+//! engine passes to [`VaultStorage::store_device_secret`] and
+//! [`VaultStorage::load_device_secret`] and uses for the password-wrapped
+//! vault key. Only wrapped blobs and the secret being wrapped cross the
+//! boundary; the keystore key never leaves the hardware.
+//!
+//! [`AeternumEngine::set_hardware_key_wrapper`]: crate::bridge::AeternumEngine::set_hardware_key_wrapper
+//! [`VaultStorage::store_device_secret`]: crate::storage::VaultStorage::store_device_secret
+//! [`VaultStorage::load_device_secret`]: crate::storage::VaultStorage::load_device_secret
+
+use std::sync::Arc;
+
+use crate::crypto::error::{CryptoError, Result as CryptoResult};
+use crate::crypto::wrap::KeyWrapper;
+use crate::protocol::error::PqrrError;
+
+/// Key wrapping implemented by the platform keystore
+///
+/// Errors must not describe the key material; a short reason such as
+/// "key invalidated" is enough.
+#[uniffi::export(with_foreign)]
+pub trait HardwareKeyWrapper: Send + Sync {
+    /// Wrap `plaintext` with the keystore key
+    fn wrap(&self, plaintext: Vec<u8>) -> Result<Vec<u8>, PqrrError>;
+
+    /// Unwrap a blob produced by [`wrap`](Self::wrap)
+    fn unwrap(&self, wrapped: Vec<u8>) -> Result<Vec<u8>, PqrrError>;
+}
+
+impl From<uniffi::UnexpectedUniFFICallbackError> for PqrrError {
+    fn from(e: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        PqrrError::storage_error(format!("Keystore callback failed: {}", e.reason))
+    }
+}
+
+/// [`KeyWrapper`] backed by a foreign [`HardwareKeyWrapper`]
+pub struct ForeignKeyWrapper {
+    inner: Arc<dyn HardwareKeyWrapper>,
+}
+
+impl ForeignKeyWrapper {
+    /// Adapt `inner` for the crypto and storage layers
+    pub fn new(inner: Arc<dyn HardwareKeyWrapper>) -> Self {
+        Self { inner }
+    }
+}
+
+impl KeyWrapper for ForeignKeyWrapper {
+    fn wrap(&self, plaintext: &[u8]) -> CryptoResult<Vec<u8>> {
+        self.inner
+            .wrap(plaintext.to_vec())
+            .map_err(|e| CryptoError::KeyWrapError(e.to_string()))
+    }
+
+    fn unwrap(&self, wrapped: &[u8]) -> CryptoResult<Vec<u8>> {
+        self.inner
+            .unwrap(wrapped.to_vec())
+            .map_err(|e| CryptoError::KeyWrapError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::kem::KyberKEM;
+    use crate::crypto::wrap::AeadKeyWrapper;
+    use crate::storage::{VaultFile, VaultStorage};
+
+    /// Stand-in for the Kotlin keystore implementation
+    struct SoftwareKeystore(AeadKeyWrapper);
+
+    impl HardwareKeyWrapper for SoftwareKeystore {
+        fn wrap(&self, plaintext: Vec<u8>) -> Result<Vec<u8>, PqrrError> {
+            KeyWrapper::wrap(&self.0, &plaintext)
+                .map_err(|e| PqrrError::storage_error(e.to_string()))
+        }
+
+        fn unwrap(&self, wrapped: Vec<u8>) -> Result<Vec<u8>, PqrrError> {
+            KeyWrapper::unwrap(&self.0, &wrapped)
+                .map_err(|_| PqrrError::authentication_failed("key invalidated".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_foreign_wrapper_protects_device_secret() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault = VaultFile::new(temp_dir.path().join("vault.db"));
+        let wrapper =
+            ForeignKeyWrapper::new(Arc::new(SoftwareKeystore(AeadKeyWrapper::generate())));
+        let keypair = KyberKEM::generate_keypair();
+
+        vault
+            .store_device_secret(&keypair.secret, &wrapper)
+            .unwrap();
+        let loaded = vault.load_device_secret(&wrapper).unwrap();
+        assert_eq!(loaded.as_bytes(), keypair.secret.as_bytes());
+
+        let other = ForeignKeyWrapper::new(Arc::new(SoftwareKeystore(AeadKeyWrapper::generate())));
+        assert!(vault.load_device_secret(&other).is_err());
+    }
+}
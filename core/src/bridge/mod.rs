@@ -8,6 +8,11 @@
 //! - `VaultSession` - Decryption session with handle-based access
 //! - `AeternumEngine` - Main engine for UI operations
 //! - `DeviceInfo` - Sanitized device information
+//! - `DeviceSummary` - Lightweight device list entry
+//! - `VaultSessionHandle` - Opaque ID of a password-unlocked session
+//! - `InitReport` - Outcome of first-run vault creation
+//! - `PairingPayload` - QR-code payload for onboarding a new device
+//! - `HardwareKeyWrapper` - Keystore callback wrapping device secrets at rest
 //!
 //! ## Security Guarantees
 //!
@@ -19,6 +24,8 @@
 //!
 //! - `session` - Vault session implementation
 //! - `engine` - Aeternum engine implementation
+//! - `pairing` - Device pairing QR payload
+//! - `keystore` - Hardware keystore wrapping callback
 //! - `types` - Bridge-specific types
 
 #![warn(missing_docs)]
@@ -26,13 +33,17 @@
 #![warn(unused_imports)]
 
 pub mod engine;
+pub mod keystore;
+pub mod pairing;
 pub mod session;
 pub mod types;
 
 // Re-export for UniFFI
 pub use engine::AeternumEngine;
+pub use keystore::{ForeignKeyWrapper, HardwareKeyWrapper};
+pub use pairing::PairingPayload;
 pub use session::VaultSession;
-pub use types::DeviceInfo;
+pub use types::{DeviceFilter, DeviceInfo, DeviceSummary, InitReport, VaultSessionHandle};
 
 #[cfg(test)]
 mod tests;
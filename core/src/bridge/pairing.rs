@@ -0,0 +1,371 @@
+//! # Device Pairing Payload
+//!
+//! QR-code payload shown by a new device so an existing device can start
+//! pairing with it over BLE.
+//!
+//! ## Flow
+//!
+//! ```text
+//! New device                                   Existing device
+//! ──────────                                   ───────────────
+//! keypair = generate_initiator_keypair()
+//! payload = PairingPayload::new(&keypair)
+//! show QR(payload.encode())        ──QR──►     payload = PairingPayload::decode(qr)?
+//! Handshake::initiate_with_keypair(
+//!     payload.context_id(), caps, keypair)
+//!                                  ──BLE──►    payload.verify_hello(&hello)?
+//!                                              Handshake::respond(&hello, caps)?
+//! ```
+//!
+//! ## Encoding
+//!
+//! A Kyber-1024 public key is 1568 bytes, too large for a reliably scannable
+//! QR code, so the payload carries only a BLAKE3 commitment to it. The full
+//! key travels in the handshake hello and is checked against the commitment
+//! with [`PairingPayload::verify_kyber_commitment`].
+//!
+//! ```text
+//! format (1) | major (1) | minor (1) | session_id (16) | x25519_pk (32)
+//! | kyber_commitment (32) | expires_at_ms u64 BE (8)
+//! ```
+//!
+//! The 91 bytes are encoded as unpadded base64url (122 characters).
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+use crate::crypto::ct::ct_eq;
+use crate::crypto::ecdh::X25519PublicKeyBytes;
+use crate::crypto::hash::Blake3Hasher;
+use crate::crypto::kem::KyberPublicKeyBytes;
+use crate::protocol::error::{PqrrError, Result};
+use crate::protocol::time::{SystemTimeSource, TimeSource};
+use crate::sync::handshake::{Handshake, InitiatorKeyPair};
+use crate::sync::version::{ProtocolVersion, VersionNegotiation};
+
+/// Pairing payload format version
+pub const PAIRING_PAYLOAD_FORMAT: u8 = 1;
+
+/// Upper bound on the encoded payload length (characters)
+pub const MAX_PAIRING_PAYLOAD_LEN: usize = 2048;
+
+/// How long a pairing payload stays valid (5 minutes)
+pub const PAIRING_TTL_MS: u64 = 5 * 60 * 1000;
+
+/// Domain separation for the Kyber public key commitment
+const KYBER_COMMITMENT_DOMAIN: &[u8] = b"aeternum pairing kyber-commitment v1";
+
+/// Domain separation for the handshake context ID
+const CONTEXT_ID_DOMAIN: &[u8] = b"aeternum pairing context-id v1";
+
+/// Encoded payload size in bytes (before base64url)
+const PAYLOAD_SIZE: usize = 1 + 2 + 16 + 32 + 32 + 8;
+
+/// QR-code pairing payload of a new device
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairingPayload {
+    /// Random pairing session identifier
+    pub session_id: [u8; 16],
+
+    /// Protocol version of the new device
+    pub protocol_version: ProtocolVersion,
+
+    /// X25519 public key of the new device
+    pub x25519_pk: X25519PublicKeyBytes,
+
+    /// BLAKE3 commitment to the new device's Kyber-1024 public key
+    pub kyber_commitment: [u8; 32],
+
+    /// Expiry (Unix milliseconds)
+    pub expires_at_ms: u64,
+}
+
+impl PairingPayload {
+    /// Create a payload for `keypair`, valid for [`PAIRING_TTL_MS`]
+    ///
+    /// The session ID is drawn from the OS CSPRNG.
+    pub fn new(keypair: &InitiatorKeyPair) -> Self {
+        Self::with_time(keypair, &SystemTimeSource)
+    }
+
+    /// Create a payload whose expiry is computed from `time`
+    pub fn with_time(keypair: &InitiatorKeyPair, time: &dyn TimeSource) -> Self {
+        let mut session_id = [0u8; 16];
+        getrandom::getrandom(&mut session_id).expect("CSPRNG failure");
+
+        Self {
+            session_id,
+            protocol_version: ProtocolVersion::current(),
+            x25519_pk: keypair.x25519.public,
+            kyber_commitment: kyber_commitment(&keypair.kyber.public),
+            expires_at_ms: time.now_ms().saturating_add(PAIRING_TTL_MS),
+        }
+    }
+
+    /// Encode as an unpadded base64url string for the QR code
+    pub fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(PAYLOAD_SIZE);
+        bytes.push(PAIRING_PAYLOAD_FORMAT);
+        bytes.extend_from_slice(&self.protocol_version.to_bytes());
+        bytes.extend_from_slice(&self.session_id);
+        bytes.extend_from_slice(self.x25519_pk.as_bytes());
+        bytes.extend_from_slice(&self.kyber_commitment);
+        bytes.extend_from_slice(&self.expires_at_ms.to_be_bytes());
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Decode a scanned payload, checking its format, version and expiry
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::InvalidPairingPayload` - Malformed payload, unknown
+    ///   format, or incompatible protocol version
+    /// - `PqrrError::PairingExpired` - The payload has expired
+    pub fn decode(encoded: &str) -> Result<Self> {
+        Self::decode_with_time(encoded, &SystemTimeSource)
+    }
+
+    /// Decode a scanned payload, checking expiry against `time`
+    ///
+    /// # Errors
+    ///
+    /// Same as [`decode`](Self::decode).
+    pub fn decode_with_time(encoded: &str, time: &dyn TimeSource) -> Result<Self> {
+        if encoded.len() > MAX_PAIRING_PAYLOAD_LEN {
+            return Err(PqrrError::invalid_pairing_payload(format!(
+                "payload is {} characters, limit {}",
+                encoded.len(),
+                MAX_PAIRING_PAYLOAD_LEN
+            )));
+        }
+
+        let bytes = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|e| PqrrError::invalid_pairing_payload(format!("bad base64url: {}", e)))?;
+        if bytes.len() != PAYLOAD_SIZE {
+            return Err(PqrrError::invalid_pairing_payload(format!(
+                "expected {} bytes, got {}",
+                PAYLOAD_SIZE,
+                bytes.len()
+            )));
+        }
+
+        if bytes[0] != PAIRING_PAYLOAD_FORMAT {
+            return Err(PqrrError::invalid_pairing_payload(format!(
+                "unsupported payload format {}",
+                bytes[0]
+            )));
+        }
+
+        let protocol_version = ProtocolVersion::from_bytes(&bytes[1..3])
+            .map_err(|e| PqrrError::invalid_pairing_payload(e.to_string()))?;
+        VersionNegotiation::negotiate(ProtocolVersion::current(), protocol_version)
+            .map_err(|e| PqrrError::invalid_pairing_payload(e.to_string()))?;
+
+        let session_id: [u8; 16] = bytes[3..19].try_into().unwrap();
+        let x25519_pk: [u8; 32] = bytes[19..51].try_into().unwrap();
+        let kyber_commitment: [u8; 32] = bytes[51..83].try_into().unwrap();
+        let expires_at_ms = u64::from_be_bytes(bytes[83..91].try_into().unwrap());
+
+        let now_ms = time.now_ms();
+        if now_ms >= expires_at_ms {
+            return Err(PqrrError::pairing_expired(expires_at_ms, now_ms));
+        }
+
+        Ok(Self {
+            session_id,
+            protocol_version,
+            x25519_pk: X25519PublicKeyBytes(x25519_pk),
+            kyber_commitment,
+            expires_at_ms,
+        })
+    }
+
+    /// Handshake context ID bound to this pairing session
+    pub fn context_id(&self) -> [u8; 32] {
+        let mut hasher = Blake3Hasher::new();
+        hasher.update(CONTEXT_ID_DOMAIN).update(&self.session_id);
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Check that `kyber_pk` is the key this payload committed to
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::InvalidPairingPayload` if the key does not match.
+    pub fn verify_kyber_commitment(&self, kyber_pk: &KyberPublicKeyBytes) -> Result<()> {
+        if !ct_eq(&kyber_commitment(kyber_pk), &self.kyber_commitment) {
+            return Err(PqrrError::invalid_pairing_payload(
+                "Kyber public key does not match commitment".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check a handshake hello from the new device against this payload
+    ///
+    /// The hello must use this pairing's context ID and present the X25519
+    /// key and committed Kyber key from the QR code. Call this before
+    /// `Handshake::respond`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::InvalidPairingPayload` if the hello is malformed
+    /// or does not match the payload.
+    pub fn verify_hello(&self, hello: &[u8]) -> Result<()> {
+        let parsed = Handshake::parse_hello(hello)
+            .map_err(|e| PqrrError::invalid_pairing_payload(e.to_string()))?;
+
+        if !ct_eq(&parsed.context_id, &self.context_id()) {
+            return Err(PqrrError::invalid_pairing_payload(
+                "handshake context does not match pairing session".to_string(),
+            ));
+        }
+        if !ct_eq(
+            parsed.public_key.x25519_pk.as_bytes(),
+            self.x25519_pk.as_bytes(),
+        ) {
+            return Err(PqrrError::invalid_pairing_payload(
+                "X25519 public key does not match payload".to_string(),
+            ));
+        }
+        self.verify_kyber_commitment(&parsed.public_key.kyber_pk)
+    }
+}
+
+/// BLAKE3 commitment to a Kyber-1024 public key
+pub fn kyber_commitment(kyber_pk: &KyberPublicKeyBytes) -> [u8; 32] {
+    let mut hasher = Blake3Hasher::new();
+    hasher
+        .update(KYBER_COMMITMENT_DOMAIN)
+        .update(kyber_pk.as_bytes());
+    *hasher.finalize().as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::time::MockTimeSource;
+    use crate::sync::handshake::HybridHandshake;
+    use crate::sync::CapabilityFlags;
+
+    fn payload_at(now_ms: u64) -> (InitiatorKeyPair, PairingPayload) {
+        let keypair = HybridHandshake::generate_initiator_keypair();
+        let payload = PairingPayload::with_time(&keypair, &MockTimeSource::new(now_ms));
+        (keypair, payload)
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let (_, payload) = payload_at(1_000);
+        let encoded = payload.encode();
+
+        let decoded =
+            PairingPayload::decode_with_time(&encoded, &MockTimeSource::new(2_000)).unwrap();
+        assert_eq!(decoded, payload);
+        assert_eq!(decoded.expires_at_ms, 1_000 + PAIRING_TTL_MS);
+        assert_eq!(decoded.protocol_version, ProtocolVersion::current());
+    }
+
+    #[test]
+    fn test_encoded_length_bound() {
+        let (_, payload) = payload_at(0);
+        let encoded = payload.encode();
+
+        assert!(encoded.len() <= MAX_PAIRING_PAYLOAD_LEN);
+        assert_eq!(encoded.len(), 122);
+        assert!(encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+
+        let oversized = "A".repeat(MAX_PAIRING_PAYLOAD_LEN + 1);
+        assert!(matches!(
+            PairingPayload::decode_with_time(&oversized, &MockTimeSource::new(0)),
+            Err(PqrrError::InvalidPairingPayload { .. })
+        ));
+    }
+
+    #[test]
+    fn test_expired_payload_rejected() {
+        let (_, payload) = payload_at(1_000);
+        let encoded = payload.encode();
+
+        let at_expiry = MockTimeSource::new(payload.expires_at_ms);
+        assert_eq!(
+            PairingPayload::decode_with_time(&encoded, &at_expiry),
+            Err(PqrrError::pairing_expired(
+                payload.expires_at_ms,
+                payload.expires_at_ms
+            ))
+        );
+    }
+
+    #[test]
+    fn test_version_and_format_validated() {
+        let (_, mut payload) = payload_at(0);
+        let time = MockTimeSource::new(0);
+
+        payload.protocol_version = ProtocolVersion { major: 2, minor: 0 };
+        assert!(matches!(
+            PairingPayload::decode_with_time(&payload.encode(), &time),
+            Err(PqrrError::InvalidPairingPayload { .. })
+        ));
+
+        payload.protocol_version = ProtocolVersion::current();
+        let mut bytes = URL_SAFE_NO_PAD.decode(payload.encode()).unwrap();
+        bytes[0] = PAIRING_PAYLOAD_FORMAT + 1;
+        assert!(matches!(
+            PairingPayload::decode_with_time(&URL_SAFE_NO_PAD.encode(&bytes), &time),
+            Err(PqrrError::InvalidPairingPayload { .. })
+        ));
+
+        bytes[0] = PAIRING_PAYLOAD_FORMAT;
+        bytes.pop();
+        assert!(matches!(
+            PairingPayload::decode_with_time(&URL_SAFE_NO_PAD.encode(&bytes), &time),
+            Err(PqrrError::InvalidPairingPayload { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tampered_commitment_detected() {
+        let (keypair, mut payload) = payload_at(0);
+        assert!(payload
+            .verify_kyber_commitment(&keypair.kyber.public)
+            .is_ok());
+
+        payload.kyber_commitment[0] ^= 0x01;
+        assert!(matches!(
+            payload.verify_kyber_commitment(&keypair.kyber.public),
+            Err(PqrrError::InvalidPairingPayload { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_hello_during_handshake() {
+        let caps = CapabilityFlags::default();
+        let (keypair, payload) = payload_at(0);
+        let scanned =
+            PairingPayload::decode_with_time(&payload.encode(), &MockTimeSource::new(0)).unwrap();
+
+        let (initiator, hello) =
+            Handshake::initiate_with_keypair(payload.context_id(), caps, keypair);
+        scanned.verify_hello(&hello).unwrap();
+
+        let (responder, response) = Handshake::respond(&hello, caps).unwrap();
+        let (initiator_keys, finished) = initiator.finalize(&response).unwrap();
+        let (responder_keys, _) = responder.finalize(&finished.unwrap()).unwrap();
+        assert_eq!(initiator_keys.send.key, responder_keys.recv.key);
+
+        // A hello from a keypair the QR code did not advertise is rejected
+        let (_, other_hello) = Handshake::initiate_with_keypair(
+            payload.context_id(),
+            caps,
+            HybridHandshake::generate_initiator_keypair(),
+        );
+        assert!(matches!(
+            scanned.verify_hello(&other_hello),
+            Err(PqrrError::InvalidPairingPayload { .. })
+        ));
+    }
+}
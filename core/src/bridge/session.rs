@@ -7,6 +7,9 @@
 //! - All decryption operations happen in Rust memory
 //! - Vault Key (VK) is zeroized on lock/drop
 //! - UI layer only receives plaintext strings, never keys
+//! - Sessions created with an idle timeout lock themselves on the first
+//!   use after the timeout, so a session the UI forgot to drop does not
+//!   keep plaintext alive indefinitely
 //!
 //! ## Architecture
 //!
@@ -16,11 +19,14 @@
 //!            ← plaintext string
 //! ```
 
+use crate::crypto::secure_mem::LockedBuffer;
+use crate::models::vault::items::VaultContents;
 use crate::protocol::error::{PqrrError, Result};
+use crate::protocol::time::{SystemTimeSource, TimeSource};
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, RwLock};
-use zeroize::Zeroizing;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use zeroize::Zeroize;
 
 /// Vault session - Handle-based access to decrypted data
 ///
@@ -29,10 +35,11 @@ use zeroize::Zeroizing;
 /// - `lock()` is called explicitly
 /// - The session is dropped
 /// - App goes to background
+/// - The session is used after its idle timeout has elapsed
 #[derive(uniffi::Object)]
 pub struct VaultSession {
-    /// Vault Key (VK) - Automatically zeroized on drop
-    vault_key: Zeroizing<Vec<u8>>,
+    /// Vault Key (VK) - Held in locked memory, zeroized on lock or drop
+    vault_key: Mutex<Option<LockedBuffer>>,
 
     /// Session metadata
     epoch: u32,
@@ -41,24 +48,58 @@ pub struct VaultSession {
     /// Use Arc<AtomicBool> for thread-safe interior mutability
     valid: Arc<AtomicBool>,
 
+    /// Idle timeout in milliseconds (`None` = never expires)
+    idle_timeout_ms: Option<u64>,
+
+    /// Time of the last successful operation (Unix milliseconds)
+    last_access_ms: AtomicU64,
+
     /// Simulated vault data (for UI demo)
     /// In production, this would be encrypted at-rest
     /// Use RwLock for interior mutability
     vault_data: Arc<RwLock<HashMap<String, HashMap<String, String>>>>,
+
+    /// This session's view of the vault items, with the epoch it was
+    /// loaded at (`None` until the first item access; dropped on lock)
+    items: Mutex<Option<(u64, VaultContents)>>,
 }
 
 impl VaultSession {
     /// Create a new vault session (internal constructor)
     ///
     /// # Arguments
-    /// - `vault_key`: Decrypted vault key
+    /// - `vault_key`: Decrypted vault key (copied into locked memory, then zeroized)
     /// - `epoch`: Current epoch
     pub fn new(vault_key: Vec<u8>, epoch: u32) -> Self {
+        Self::with_idle_timeout(vault_key, epoch, None)
+    }
+
+    /// Create a vault session that locks after `idle_timeout_ms` of inactivity
+    ///
+    /// Every successful operation resets the idle timer. The first
+    /// operation after the timeout zeroizes the vault key and fails with
+    /// `PqrrError::SessionExpired`.
+    ///
+    /// # Arguments
+    /// - `vault_key`: Decrypted vault key (copied into locked memory, then zeroized)
+    /// - `epoch`: Current epoch
+    /// - `idle_timeout_ms`: Maximum idle time in milliseconds
+    pub fn new_with_timeout(vault_key: Vec<u8>, epoch: u32, idle_timeout_ms: u64) -> Self {
+        Self::with_idle_timeout(vault_key, epoch, Some(idle_timeout_ms))
+    }
+
+    fn with_idle_timeout(mut vault_key: Vec<u8>, epoch: u32, idle_timeout_ms: Option<u64>) -> Self {
+        let locked_key = LockedBuffer::from_slice(&vault_key).expect("vault key allocation failed");
+        vault_key.zeroize();
+
         Self {
-            vault_key: Zeroizing::new(vault_key),
+            vault_key: Mutex::new(Some(locked_key)),
             epoch,
             valid: Arc::new(AtomicBool::new(true)),
+            idle_timeout_ms,
+            last_access_ms: AtomicU64::new(SystemTimeSource.now_ms()),
             vault_data: Arc::new(RwLock::new(Self::demo_vault_data())),
+            items: Mutex::new(None),
         }
     }
 
@@ -84,7 +125,63 @@ impl VaultSession {
 
     /// Check if session is valid (internal)
     fn is_valid_internal(&self) -> bool {
-        self.valid.load(Ordering::Acquire)
+        self.valid.load(Ordering::Acquire) && self.vault_key.lock().unwrap().is_some()
+    }
+
+    /// Whether the idle timeout has elapsed at `now_ms`
+    ///
+    /// Sessions without a timeout never expire.
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        self.idle_timeout_ms.is_some_and(|timeout| {
+            now_ms.saturating_sub(self.last_access_ms.load(Ordering::Acquire)) > timeout
+        })
+    }
+
+    /// Gate an operation on the session being unlocked and not idle
+    ///
+    /// An expired session is locked before the error is returned. On
+    /// success the idle timer is reset.
+    ///
+    /// # Errors
+    /// - `PqrrError::InsufficientPrivileges` - Session locked
+    /// - `PqrrError::SessionExpired` - Idle timeout elapsed
+    pub(crate) fn ensure_active(&self, operation: &str) -> Result<()> {
+        if !self.is_valid_internal() {
+            return Err(PqrrError::InsufficientPrivileges {
+                role: "Session".to_string(),
+                operation: operation.to_string(),
+            });
+        }
+
+        let now_ms = SystemTimeSource.now_ms();
+        if self.is_expired(now_ms) {
+            let idle_ms = now_ms.saturating_sub(self.last_access_ms.load(Ordering::Acquire));
+            self.lock();
+            return Err(PqrrError::session_expired(
+                idle_ms,
+                self.idle_timeout_ms.unwrap_or_default(),
+            ));
+        }
+
+        self.last_access_ms.store(now_ms, Ordering::Release);
+        Ok(())
+    }
+
+    /// Run `f` on this session's view of the vault items
+    ///
+    /// The view is filled in by the engine, which loads it from the vault.
+    /// Gated like any other operation by [`Self::ensure_active`].
+    ///
+    /// # Errors
+    /// - `PqrrError::InsufficientPrivileges` - Session locked
+    /// - `PqrrError::SessionExpired` - Idle timeout elapsed
+    pub(crate) fn with_items<T>(
+        &self,
+        operation: &str,
+        f: impl FnOnce(&mut Option<(u64, VaultContents)>) -> Result<T>,
+    ) -> Result<T> {
+        self.ensure_active(operation)?;
+        f(&mut self.items.lock().unwrap())
     }
 
     /// Invalidate session (internal)
@@ -119,15 +216,11 @@ impl VaultSession {
     ///
     /// # Errors
     /// - `PqrrError::InsufficientPrivileges` - Session invalid or locked
+    /// - `PqrrError::SessionExpired` - Idle timeout elapsed (session now locked)
     /// - `PqrrError::HeaderIncomplete` - Record or field not found
     pub fn decrypt_field(&self, record_id: String, field_key: String) -> Result<String> {
         // Check session validity
-        if !self.is_valid_internal() {
-            return Err(PqrrError::InsufficientPrivileges {
-                role: "Session".to_string(),
-                operation: "decrypt_field".to_string(),
-            });
-        }
+        self.ensure_active("decrypt_field")?;
 
         // Lookup record
         let data = self.vault_data.read().unwrap();
@@ -167,6 +260,7 @@ impl VaultSession {
     ///
     /// # Errors
     /// - `PqrrError::InsufficientPrivileges` - Session invalid or locked
+    /// - `PqrrError::SessionExpired` - Idle timeout elapsed (session now locked)
     pub fn store_entry(
         &self,
         record_id: String,
@@ -174,12 +268,7 @@ impl VaultSession {
         plaintext_value: String,
     ) -> Result<()> {
         // Check session validity
-        if !self.is_valid_internal() {
-            return Err(PqrrError::InsufficientPrivileges {
-                role: "Session".to_string(),
-                operation: "store_entry".to_string(),
-            });
-        }
+        self.ensure_active("store_entry")?;
 
         // Get or create record
         let mut data = self.vault_data.write().unwrap();
@@ -215,8 +304,20 @@ impl VaultSession {
         // Invalidate session
         self.invalidate();
 
-        // INVARIANT: Vault key will be zeroized when Zeroizing<Vec<u8>> is dropped
-        // The zeroize happens automatically via the Zeroize trait on drop
+        // INVARIANT: Dropping the LockedBuffer zeroizes the vault key before
+        // its pages are unlocked
+        drop(self.vault_key.lock().unwrap().take());
+
+        // Drop this session's item view
+        drop(self.items.lock().unwrap().take());
+
+        // Drop plaintext record contents as well
+        for record in self.vault_data.write().unwrap().values_mut() {
+            for value in record.values_mut() {
+                value.zeroize();
+            }
+            record.clear();
+        }
     }
 }
 
@@ -272,6 +373,55 @@ mod tests {
         assert!(!session.is_valid());
     }
 
+    #[test]
+    fn test_is_expired_after_idle_timeout() {
+        let session = VaultSession::new_with_timeout(vec![1u8, 2, 3, 4], 1, 1_000);
+        let last_access = session.last_access_ms.load(Ordering::Acquire);
+
+        assert!(!session.is_expired(last_access + 1_000));
+        assert!(session.is_expired(last_access + 1_001));
+
+        // Sessions without a timeout never expire
+        let session = VaultSession::new(vec![1u8, 2, 3, 4], 1);
+        assert!(!session.is_expired(u64::MAX));
+    }
+
+    #[test]
+    fn test_expired_session_zeroizes_and_fails() {
+        let session = VaultSession::new_with_timeout(vec![1u8, 2, 3, 4], 1, 1_000);
+        assert!(session
+            .decrypt_field("rec_001".to_string(), "title".to_string())
+            .is_ok());
+
+        // Idle for longer than the timeout
+        session.last_access_ms.fetch_sub(5_000, Ordering::AcqRel);
+
+        let result = session.decrypt_field("rec_002".to_string(), "content".to_string());
+        assert!(matches!(
+            result.unwrap_err(),
+            PqrrError::SessionExpired {
+                timeout_ms: 1_000,
+                ..
+            }
+        ));
+        assert!(session.vault_key.lock().unwrap().is_none());
+        assert!(!session.is_valid());
+        assert!(session
+            .vault_data
+            .read()
+            .unwrap()
+            .values()
+            .all(|r| r.is_empty()));
+
+        // Locked from now on
+        assert!(matches!(
+            session
+                .store_entry("rec_003".to_string(), "k".to_string(), "v".to_string())
+                .unwrap_err(),
+            PqrrError::InsufficientPrivileges { .. }
+        ));
+    }
+
     #[test]
     fn test_decrypt_after_lock_fails() {
         let vault_key = vec![1u8, 2, 3, 4];
@@ -2,7 +2,8 @@
 //!
 //! Bridge-specific types for UniFFI interface.
 
-use crate::models::device::DeviceId;
+use crate::models::device::{DeviceHeader, DeviceId, DeviceStatus};
+use crate::protocol::error::{PqrrError, Result};
 use crate::protocol::ProtocolState;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -72,6 +73,237 @@ impl DeviceInfo {
     }
 }
 
+/// Device list filter for `AeternumEngine::list_devices`
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceFilter {
+    /// Only Active devices
+    Active,
+
+    /// Only Revoked devices
+    Revoked,
+
+    /// Every device regardless of status
+    All,
+}
+
+impl DeviceFilter {
+    /// Check whether a device with this status passes the filter
+    pub fn matches(&self, status: DeviceStatus) -> bool {
+        match self {
+            DeviceFilter::Active => status == DeviceStatus::Active,
+            DeviceFilter::Revoked => status == DeviceStatus::Revoked,
+            DeviceFilter::All => true,
+        }
+    }
+}
+
+/// Device summary - Lightweight list entry
+///
+/// Carries only what a device list needs to render, so the UI layer does
+/// not have to deserialize full device headers.
+#[derive(uniffi::Record, Debug, Clone, PartialEq, Eq)]
+pub struct DeviceSummary {
+    /// Device identifier (dashed UUID, see `DeviceId::to_uuid_string`)
+    pub device_id: String,
+
+    /// Device status (Active, Revoked, Degraded)
+    pub status: String,
+
+    /// Epoch version of the device header
+    pub epoch: u32,
+
+    /// Header creation timestamp (Unix milliseconds)
+    pub created_at: u64,
+
+    /// Whether this is the shadow anchor (Device_0)
+    pub is_shadow_anchor: bool,
+}
+
+impl DeviceSummary {
+    /// Create DeviceSummary from a device header
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::EpochOutOfRange` if the header epoch does not
+    /// fit in a `u32`.
+    pub fn from_header(header: &DeviceHeader) -> Result<Self> {
+        Ok(Self {
+            device_id: header.device_id.to_string(),
+            status: format!("{:?}", header.status),
+            epoch: PqrrError::epoch_to_u32(header.epoch.version)?,
+            created_at: header.created_at,
+            is_shadow_anchor: header.device_id.is_shadow_anchor(),
+        })
+    }
+}
+
+/// Vault session handle - Opaque session identifier for the UI layer
+///
+/// Returned by `AeternumEngine::unlock_with_password`. Carries only a
+/// numeric ID; the vault key stays inside the engine's session table.
+/// The ID is a [`HandleRegistry`] handle, so it stops working once the
+/// session is closed, even if its slot is reused.
+#[derive(uniffi::Record, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VaultSessionHandle {
+    /// Session identifier (generation-checked, unique per engine)
+    pub id: u64,
+}
+
+/// Default cap on live sessions per engine
+pub const DEFAULT_MAX_SESSIONS: usize = 16;
+
+/// Slot of a [`HandleRegistry`]
+#[derive(Debug)]
+struct Slot<T> {
+    /// Generation of the current (or last) occupant
+    generation: u32,
+    /// Occupant, `None` while the slot is free
+    value: Option<T>,
+}
+
+/// Table of values addressed by generation-checked 64-bit handles
+///
+/// A handle packs `(generation << 32) | index`. Every insert into a slot
+/// bumps its generation, so a handle kept after [`remove`](Self::remove)
+/// no longer matches once the slot is reused; the stale handle is rejected
+/// instead of reaching the new value. A slot whose generation reached
+/// `u32::MAX` is retired rather than wrapped, so a generation is never
+/// issued twice for the same index. Generation 0 is never issued, so 0 is
+/// never a valid handle.
+///
+/// Not synchronized; the engine keeps it behind a `parking_lot::Mutex`,
+/// which does not poison when a holder panics.
+#[derive(Debug)]
+pub struct HandleRegistry<T> {
+    /// Slots by index
+    slots: Vec<Slot<T>>,
+    /// Indices of free, reusable slots
+    free: Vec<u32>,
+    /// Number of occupied slots
+    live: usize,
+    /// Maximum number of occupied slots
+    max_live: usize,
+}
+
+impl<T> HandleRegistry<T> {
+    /// Create an empty registry holding at most `max_live` values
+    pub fn new(max_live: usize) -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            live: 0,
+            max_live,
+        }
+    }
+
+    /// Maximum number of live values
+    pub fn max_live(&self) -> usize {
+        self.max_live
+    }
+
+    /// Number of live values
+    pub fn len(&self) -> usize {
+        self.live
+    }
+
+    /// Whether no value is live
+    pub fn is_empty(&self) -> bool {
+        self.live == 0
+    }
+
+    /// Store `value` and return its handle
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::TooManySessions` if `max_live` values are live.
+    pub fn insert(&mut self, value: T) -> Result<u64> {
+        if self.live >= self.max_live {
+            return Err(PqrrError::too_many_sessions(
+                u32::try_from(self.max_live).unwrap_or(u32::MAX),
+            ));
+        }
+
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                let index = u32::try_from(self.slots.len())
+                    .map_err(|_| PqrrError::too_many_sessions(u32::MAX))?;
+                self.slots.push(Slot {
+                    generation: 0,
+                    value: None,
+                });
+                index
+            }
+        };
+
+        let slot = &mut self.slots[index as usize];
+        slot.generation += 1;
+        slot.value = Some(value);
+        self.live += 1;
+        Ok(compose_handle(index, slot.generation))
+    }
+
+    /// Value behind `handle`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::InvalidHandle` if the handle was never issued,
+    /// was removed, or its generation does not match the slot.
+    pub fn get(&self, handle: u64) -> Result<&T> {
+        let (index, generation) = split_handle(handle);
+        self.slots
+            .get(index as usize)
+            .filter(|slot| slot.generation == generation)
+            .and_then(|slot| slot.value.as_ref())
+            .ok_or_else(|| PqrrError::invalid_handle(handle))
+    }
+
+    /// Remove and return the value behind `handle`
+    ///
+    /// # Errors
+    ///
+    /// Same as [`get`](Self::get).
+    pub fn remove(&mut self, handle: u64) -> Result<T> {
+        let (index, generation) = split_handle(handle);
+        let value = self
+            .slots
+            .get_mut(index as usize)
+            .filter(|slot| slot.generation == generation)
+            .and_then(|slot| slot.value.take())
+            .ok_or_else(|| PqrrError::invalid_handle(handle))?;
+
+        self.live -= 1;
+        // A slot out of generations is retired, never wrapped
+        if generation < u32::MAX {
+            self.free.push(index);
+        }
+        Ok(value)
+    }
+}
+
+/// Pack a slot index and generation into a handle
+fn compose_handle(index: u32, generation: u32) -> u64 {
+    (u64::from(generation) << 32) | u64::from(index)
+}
+
+/// Unpack a handle into its slot index and generation
+fn split_handle(handle: u64) -> (u32, u32) {
+    (handle as u32, (handle >> 32) as u32)
+}
+
+/// Result of `AeternumEngine::initialize_vault`
+#[derive(uniffi::Record, Debug, Clone, PartialEq, Eq)]
+pub struct InitReport {
+    /// Path of the created vault file
+    pub vault_path: String,
+
+    /// Epoch version of the new vault
+    pub epoch: u32,
+
+    /// Whether an existing vault was overwritten
+    pub replaced_existing: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -108,4 +340,141 @@ mod tests {
         assert_eq!(info.last_seen_timestamp, timestamp);
         assert!(info.is_this_device);
     }
+
+    #[test]
+    fn test_device_filter_matches() {
+        assert!(DeviceFilter::Active.matches(DeviceStatus::Active));
+        assert!(!DeviceFilter::Active.matches(DeviceStatus::Revoked));
+        assert!(DeviceFilter::Revoked.matches(DeviceStatus::Revoked));
+        assert!(!DeviceFilter::Revoked.matches(DeviceStatus::Degraded));
+        assert!(DeviceFilter::All.matches(DeviceStatus::Degraded));
+    }
+
+    #[test]
+    fn test_device_summary_from_header() {
+        let header = DeviceHeader::new(
+            DeviceId::shadow_anchor(),
+            crate::models::epoch::CryptoEpoch::initial(),
+            crate::crypto::kem::KyberPublicKeyBytes([0u8; 1568]),
+            crate::crypto::kem::KyberCipherText([0u8; 1568]),
+        );
+        let summary = DeviceSummary::from_header(&header).unwrap();
+
+        assert_eq!(summary.device_id, "00000000-0000-0000-0000-000000000000");
+        assert_eq!(summary.status, "Active");
+        assert_eq!(summary.epoch, 1);
+        assert_eq!(summary.created_at, header.created_at);
+        assert!(summary.is_shadow_anchor);
+    }
+
+    #[test]
+    fn test_device_summary_rejects_epoch_beyond_u32() {
+        let mut header = DeviceHeader::new(
+            DeviceId::shadow_anchor(),
+            crate::models::epoch::CryptoEpoch::initial(),
+            crate::crypto::kem::KyberPublicKeyBytes([0u8; 1568]),
+            crate::crypto::kem::KyberCipherText([0u8; 1568]),
+        );
+        header.epoch.version = u64::from(u32::MAX) + 1;
+
+        assert_eq!(
+            DeviceSummary::from_header(&header),
+            Err(PqrrError::EpochOutOfRange {
+                epoch: u64::from(u32::MAX) + 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_stale_handle_rejected_after_reinsert() {
+        let mut registry = HandleRegistry::new(4);
+        let first = registry.insert("first").unwrap();
+        assert_eq!(registry.get(first), Ok(&"first"));
+
+        assert_eq!(registry.remove(first), Ok("first"));
+        let second = registry.insert("second").unwrap();
+
+        // Same slot, new generation
+        assert_eq!(split_handle(second).0, split_handle(first).0);
+        assert_ne!(second, first);
+        assert_eq!(registry.get(first), Err(PqrrError::invalid_handle(first)));
+        assert_eq!(
+            registry.remove(first),
+            Err(PqrrError::invalid_handle(first))
+        );
+        assert_eq!(registry.get(second), Ok(&"second"));
+        assert_eq!(registry.get(0), Err(PqrrError::invalid_handle(0)));
+    }
+
+    #[test]
+    fn test_exhausted_slot_is_retired() {
+        let mut registry = HandleRegistry::new(4);
+        let handle = registry.insert(1u32).unwrap();
+        registry.remove(handle).unwrap();
+        registry.slots[0].generation = u32::MAX - 1;
+
+        let last = registry.insert(2).unwrap();
+        assert_eq!(split_handle(last), (0, u32::MAX));
+        registry.remove(last).unwrap();
+
+        // The slot never wraps back to an earlier generation
+        let next = registry.insert(3).unwrap();
+        assert_eq!(split_handle(next), (1, 1));
+        assert!(registry.get(last).is_err());
+        assert!(registry.get(compose_handle(0, 1)).is_err());
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_registry_cap() {
+        let mut registry = HandleRegistry::new(2);
+        let a = registry.insert('a').unwrap();
+        registry.insert('b').unwrap();
+        assert_eq!(registry.insert('c'), Err(PqrrError::too_many_sessions(2)));
+        assert_eq!(registry.len(), 2);
+
+        registry.remove(a).unwrap();
+        assert!(registry.insert('c').is_ok());
+        assert_eq!(registry.max_live(), 2);
+    }
+
+    #[test]
+    fn test_concurrent_access_survives_panicking_holder() {
+        use parking_lot::Mutex;
+        use std::sync::Arc;
+        use std::thread;
+
+        let registry = Arc::new(Mutex::new(HandleRegistry::new(64)));
+
+        let workers: Vec<_> = (0..8u32)
+            .map(|n| {
+                let registry = Arc::clone(&registry);
+                thread::spawn(move || {
+                    for i in 0..100u32 {
+                        let handle = registry.lock().insert(n * 1000 + i).unwrap();
+                        assert_eq!(registry.lock().get(handle), Ok(&(n * 1000 + i)));
+                        assert_eq!(registry.lock().remove(handle), Ok(n * 1000 + i));
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        let panicking = Arc::clone(&registry);
+        let result = thread::spawn(move || {
+            let mut guard = panicking.lock();
+            guard.insert(7).unwrap();
+            panic!("holder panics with the lock held");
+        })
+        .join();
+        assert!(result.is_err());
+
+        // No poisoning: the registry stays usable and consistent
+        let mut registry = registry.lock();
+        assert_eq!(registry.len(), 1);
+        let handle = registry.insert(8).unwrap();
+        assert_eq!(registry.get(handle), Ok(&8));
+    }
 }
@@ -8,6 +8,8 @@
 //! - `XChaCha20Nonce`: 24-byte nonce (safe for random generation)
 //! - `AuthTag`: 16-byte authentication tag (Poly1305)
 //! - `AeadCipher`: Encryption/decryption operations
+//! - `stream`: Chunked STREAM encryption for large payloads (parallel with
+//!   the `parallel` feature)
 //!
 //! ## Security Properties
 //!
@@ -31,8 +33,14 @@
 //! assert_eq!(plaintext, b"secret");
 //! ```
 
+pub mod stream;
 mod xchacha20;
 
+use crate::crypto::fixed::{impl_fixed_public, impl_fixed_secret};
+#[cfg(not(feature = "mlock"))]
+use crate::crypto::secret::SecretBytes;
+#[cfg(feature = "mlock")]
+use crate::crypto::secure_mem::LockedBytes;
 use rand::RngCore;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
@@ -44,7 +52,9 @@ pub use xchacha20::{
 /// XChaCha20-Poly1305 key (32 bytes)
 ///
 /// This key automatically zeroizes when dropped, ensuring sensitive
-/// key material doesn't remain in memory.
+/// key material doesn't remain in memory. With the `mlock` feature the
+/// bytes are also kept in locked memory
+/// ([`LockedBytes`](crate::crypto::secure_mem::LockedBytes)).
 ///
 /// # Example
 ///
@@ -59,37 +69,15 @@ pub use xchacha20::{
 /// let key = XChaCha20Key::from_bytes(&bytes).unwrap();
 /// ```
 #[derive(Clone, Zeroize, ZeroizeOnDrop)]
-pub struct XChaCha20Key([u8; 32]);
+pub struct XChaCha20Key(KeyBytes);
 
-// Implement Debug manually to avoid leaking key material
-impl std::fmt::Debug for XChaCha20Key {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("XChaCha20Key")
-            .field("bytes", &"[REDACTED]")
-            .finish()
-    }
-}
-
-impl XChaCha20Key {
-    /// Generate a new random key using the system CSPRNG.
-    ///
-    /// This is the recommended way to create encryption keys.
-    pub fn generate() -> Self {
-        use rand::rngs::OsRng;
-        let mut bytes = [0u8; 32];
-        OsRng.fill_bytes(&mut bytes);
-        Self(bytes)
-    }
+/// Backing storage for [`XChaCha20Key`]
+#[cfg(not(feature = "mlock"))]
+type KeyBytes = SecretBytes<32>;
+#[cfg(feature = "mlock")]
+type KeyBytes = LockedBytes<32>;
 
-    /// Create a key from raw bytes.
-    ///
-    /// # Arguments
-    ///
-    /// - `bytes`: Exactly 32 bytes of key material
-    ///
-    /// # Errors
-    ///
-    /// Returns `CryptoError::InvalidKeyLength` if `bytes.len() != 32`.
+impl_fixed_secret!(XChaCha20Key, KeyBytes;
     ///
     /// # Example
     ///
@@ -99,27 +87,14 @@ impl XChaCha20Key {
     /// let bytes = [42u8; 32];
     /// let key = XChaCha20Key::from_bytes(&bytes).unwrap();
     /// ```
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::crypto::error::CryptoError> {
-        if bytes.len() != 32 {
-            return Err(crate::crypto::error::CryptoError::InvalidKeyLength {
-                expected: 32,
-                actual: bytes.len(),
-            });
-        }
-        let mut key = [0u8; 32];
-        key.copy_from_slice(bytes);
-        Ok(Self(key))
-    }
+);
 
-    /// Get a reference to the key bytes.
-    ///
-    /// # Security Note
+impl XChaCha20Key {
+    /// Generate a new random key using the system CSPRNG.
     ///
-    /// Be careful not to copy or log the returned bytes.
-    /// Use this method only when interfacing with low-level
-    /// cryptographic APIs.
-    pub fn as_bytes(&self) -> &[u8; 32] {
-        &self.0
+    /// This is the recommended way to create encryption keys.
+    pub fn generate() -> Self {
+        Self(KeyBytes::random())
     }
 }
 
@@ -163,12 +138,9 @@ impl XChaCha20Nonce {
         OsRng.fill_bytes(&mut bytes);
         Self(bytes)
     }
+}
 
-    /// Create a nonce from raw bytes.
-    ///
-    /// # Arguments
-    ///
-    /// - `bytes`: Exactly 24 bytes of nonce material
+impl_fixed_public!(XChaCha20Nonce, 24, array;
     ///
     /// # Example
     ///
@@ -178,32 +150,7 @@ impl XChaCha20Nonce {
     /// let bytes = [0u8; 24];
     /// let nonce = XChaCha20Nonce::from_bytes(bytes);
     /// ```
-    pub fn from_bytes(bytes: [u8; 24]) -> Self {
-        Self(bytes)
-    }
-
-    /// Try to create a nonce from a byte slice.
-    ///
-    /// # Errors
-    ///
-    /// Returns `CryptoError::InvalidKeyLength` if `bytes.len() != 24`.
-    pub fn try_from_slice(bytes: &[u8]) -> Result<Self, crate::crypto::error::CryptoError> {
-        if bytes.len() != 24 {
-            return Err(crate::crypto::error::CryptoError::InvalidKeyLength {
-                expected: 24,
-                actual: bytes.len(),
-            });
-        }
-        let mut nonce = [0u8; 24];
-        nonce.copy_from_slice(bytes);
-        Ok(Self(nonce))
-    }
-
-    /// Get a reference to the nonce bytes.
-    pub fn as_bytes(&self) -> &[u8; 24] {
-        &self.0
-    }
-}
+);
 
 /// Authentication tag (16 bytes / 128 bits)
 ///
@@ -227,38 +174,7 @@ impl XChaCha20Nonce {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AuthTag([u8; 16]);
 
-impl AuthTag {
-    /// Create a tag from raw bytes.
-    ///
-    /// # Arguments
-    ///
-    /// - `bytes`: Exactly 16 bytes of tag data
-    pub fn from_bytes(bytes: [u8; 16]) -> Self {
-        Self(bytes)
-    }
-
-    /// Try to create a tag from a byte slice.
-    ///
-    /// # Errors
-    ///
-    /// Returns `CryptoError::InvalidKeyLength` if `bytes.len() != 16`.
-    pub fn try_from_slice(bytes: &[u8]) -> Result<Self, crate::crypto::error::CryptoError> {
-        if bytes.len() != 16 {
-            return Err(crate::crypto::error::CryptoError::InvalidKeyLength {
-                expected: 16,
-                actual: bytes.len(),
-            });
-        }
-        let mut tag = [0u8; 16];
-        tag.copy_from_slice(bytes);
-        Ok(Self(tag))
-    }
-
-    /// Get a reference to the tag bytes.
-    pub fn as_bytes(&self) -> &[u8; 16] {
-        &self.0
-    }
-}
+impl_fixed_public!(AuthTag, 16, array);
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,277 @@
+//! # Chunked STREAM Encryption
+//!
+//! Encrypts large payloads as a sequence of independently sealed chunks,
+//! following the STREAM construction (Hoang, Reyhanitabar, Rogaway, Vizár),
+//! so chunks can be processed in parallel without giving up ordering or
+//! truncation resistance.
+//!
+//! ## Construction
+//!
+//! The plaintext is split into [`STREAM_CHUNK_SIZE`] chunks. Chunk `i` is
+//! sealed with XChaCha20-Poly1305 under the nonce
+//!
+//! ```text
+//! base_nonce[0..19] || i (u32 BE) || last_flag (0x00 or 0x01)
+//! ```
+//!
+//! and the ciphertext is the concatenation of the sealed chunks. Binding
+//! the index into the nonce rejects reordered chunks; the last-chunk flag
+//! rejects truncation at a chunk boundary. An empty plaintext is one empty
+//! final chunk.
+//!
+//! ## Parallelism
+//!
+//! With the `parallel` feature, [`seal`] and [`open`] process chunks on the
+//! rayon thread pool. Every chunk nonce is a function of its index alone,
+//! so the output is bit-identical to [`seal_sequential`].
+
+use super::{AeadCipher, XChaCha20Nonce, NONCE_SIZE, TAG_SIZE};
+use crate::crypto::error::{CryptoError, Result};
+
+/// Plaintext bytes per chunk (64 KiB)
+pub const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Sealed size of a full chunk
+const SEALED_CHUNK_SIZE: usize = STREAM_CHUNK_SIZE + TAG_SIZE;
+
+/// Bytes of the base nonce kept as the per-stream prefix
+const NONCE_PREFIX_SIZE: usize = NONCE_SIZE - 5;
+
+/// Encrypt `plaintext` as a chunked stream
+///
+/// Runs chunks in parallel with the `parallel` feature; the result is the
+/// same as [`seal_sequential`] either way.
+///
+/// # Errors
+///
+/// Returns `CryptoError::AeadError` if a chunk fails to encrypt, or
+/// `CryptoError::InternalError` if the plaintext has more than `u32::MAX`
+/// chunks.
+pub fn seal(cipher: &AeadCipher, nonce: &XChaCha20Nonce, plaintext: &[u8]) -> Result<Vec<u8>> {
+    seal_with_aad(cipher, nonce, plaintext, &[])
+}
+
+/// Encrypt `plaintext` as a chunked stream, binding `aad` to every chunk
+///
+/// With an empty `aad` the output equals [`seal`].
+///
+/// # Errors
+///
+/// Same as [`seal`].
+pub fn seal_with_aad(
+    cipher: &AeadCipher,
+    nonce: &XChaCha20Nonce,
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>> {
+    let chunks = split(plaintext, STREAM_CHUNK_SIZE)?;
+    let sealed = map_chunks(&chunks, |index, chunk| {
+        seal_chunk(cipher, nonce, index, chunks.len(), chunk, aad)
+    })?;
+    Ok(sealed.concat())
+}
+
+/// Encrypt `plaintext` as a chunked stream on the calling thread
+///
+/// # Errors
+///
+/// Same as [`seal`].
+pub fn seal_sequential(
+    cipher: &AeadCipher,
+    nonce: &XChaCha20Nonce,
+    plaintext: &[u8],
+) -> Result<Vec<u8>> {
+    let chunks = split(plaintext, STREAM_CHUNK_SIZE)?;
+    let mut sealed = Vec::with_capacity(plaintext.len() + chunks.len() * TAG_SIZE);
+    for (index, chunk) in chunks.iter().enumerate() {
+        sealed.extend_from_slice(&seal_chunk(cipher, nonce, index, chunks.len(), chunk, &[])?);
+    }
+    Ok(sealed)
+}
+
+/// Decrypt a chunked stream produced by [`seal`]
+///
+/// # Errors
+///
+/// Returns `CryptoError::AeadError` if any chunk was modified, reordered,
+/// or dropped, or if the key or nonce is wrong.
+pub fn open(cipher: &AeadCipher, nonce: &XChaCha20Nonce, ciphertext: &[u8]) -> Result<Vec<u8>> {
+    open_with_aad(cipher, nonce, ciphertext, &[])
+}
+
+/// Decrypt a chunked stream produced by [`seal_with_aad`]
+///
+/// # Errors
+///
+/// Same as [`open`]; also fails if `aad` differs from the one sealed.
+pub fn open_with_aad(
+    cipher: &AeadCipher,
+    nonce: &XChaCha20Nonce,
+    ciphertext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>> {
+    if ciphertext.is_empty() {
+        return Err(CryptoError::aead("Stream ciphertext is empty"));
+    }
+    let chunks = split(ciphertext, SEALED_CHUNK_SIZE)?;
+    let opened = map_chunks(&chunks, |index, chunk| {
+        cipher.decrypt(&chunk_nonce(nonce, index, chunks.len()), chunk, Some(aad))
+    })?;
+    Ok(opened.concat())
+}
+
+/// Split `data` into `size`-byte chunks; empty input is one empty chunk
+fn split(data: &[u8], size: usize) -> Result<Vec<&[u8]>> {
+    let chunks: Vec<&[u8]> = if data.is_empty() {
+        vec![data]
+    } else {
+        data.chunks(size).collect()
+    };
+    if chunks.len() > u32::MAX as usize {
+        return Err(CryptoError::internal(format!(
+            "Stream too long: {} chunks",
+            chunks.len()
+        )));
+    }
+    Ok(chunks)
+}
+
+/// Nonce of chunk `index` out of `count`
+fn chunk_nonce(base: &XChaCha20Nonce, index: usize, count: usize) -> XChaCha20Nonce {
+    let mut nonce = *base.as_bytes();
+    nonce[NONCE_PREFIX_SIZE..NONCE_SIZE - 1].copy_from_slice(&(index as u32).to_be_bytes());
+    nonce[NONCE_SIZE - 1] = u8::from(index + 1 == count);
+    XChaCha20Nonce::from_bytes(nonce)
+}
+
+/// Seal chunk `index` out of `count`
+fn seal_chunk(
+    cipher: &AeadCipher,
+    base: &XChaCha20Nonce,
+    index: usize,
+    count: usize,
+    chunk: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>> {
+    cipher.encrypt(&chunk_nonce(base, index, count), chunk, Some(aad))
+}
+
+/// Apply `f` to every chunk, keeping chunk order in the output
+#[cfg(feature = "parallel")]
+fn map_chunks<F>(chunks: &[&[u8]], f: F) -> Result<Vec<Vec<u8>>>
+where
+    F: Fn(usize, &[u8]) -> Result<Vec<u8>> + Sync + Send,
+{
+    use rayon::prelude::*;
+    chunks
+        .par_iter()
+        .enumerate()
+        .map(|(index, chunk)| f(index, chunk))
+        .collect()
+}
+
+/// Apply `f` to every chunk, keeping chunk order in the output
+#[cfg(not(feature = "parallel"))]
+fn map_chunks<F>(chunks: &[&[u8]], f: F) -> Result<Vec<Vec<u8>>>
+where
+    F: Fn(usize, &[u8]) -> Result<Vec<u8>>,
+{
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(index, chunk)| f(index, chunk))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::aead::XChaCha20Key;
+
+    fn setup() -> (AeadCipher, XChaCha20Nonce) {
+        let key = XChaCha20Key::from_bytes(&[0x42; 32]).unwrap();
+        (
+            AeadCipher::new(&key),
+            XChaCha20Nonce::from_bytes([0x24; 24]),
+        )
+    }
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let (cipher, nonce) = setup();
+
+        for len in [
+            0,
+            1,
+            STREAM_CHUNK_SIZE - 1,
+            STREAM_CHUNK_SIZE,
+            STREAM_CHUNK_SIZE + 1,
+            3 * STREAM_CHUNK_SIZE + 17,
+        ] {
+            let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let sealed = seal(&cipher, &nonce, &plaintext).unwrap();
+
+            let chunks = len.div_ceil(STREAM_CHUNK_SIZE).max(1);
+            assert_eq!(sealed.len(), len + chunks * TAG_SIZE);
+            assert_eq!(open(&cipher, &nonce, &sealed).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let (cipher, nonce) = setup();
+        let plaintext: Vec<u8> = (0..(5 * STREAM_CHUNK_SIZE + 123))
+            .map(|i| (i * 7) as u8)
+            .collect();
+
+        assert_eq!(
+            seal(&cipher, &nonce, &plaintext).unwrap(),
+            seal_sequential(&cipher, &nonce, &plaintext).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_open_rejects_reordered_and_truncated_chunks() {
+        let (cipher, nonce) = setup();
+        let plaintext = vec![0xA5u8; 3 * STREAM_CHUNK_SIZE];
+        let sealed = seal(&cipher, &nonce, &plaintext).unwrap();
+
+        // Swap the first two chunks
+        let mut reordered = sealed.clone();
+        reordered[..SEALED_CHUNK_SIZE]
+            .copy_from_slice(&sealed[SEALED_CHUNK_SIZE..2 * SEALED_CHUNK_SIZE]);
+        reordered[SEALED_CHUNK_SIZE..2 * SEALED_CHUNK_SIZE]
+            .copy_from_slice(&sealed[..SEALED_CHUNK_SIZE]);
+        assert!(open(&cipher, &nonce, &reordered).is_err());
+
+        // Drop the final chunk at a chunk boundary
+        assert!(open(&cipher, &nonce, &sealed[..2 * SEALED_CHUNK_SIZE]).is_err());
+
+        // Flip a bit in the middle chunk
+        let mut tampered = sealed.clone();
+        tampered[SEALED_CHUNK_SIZE + 5] ^= 0x01;
+        assert!(open(&cipher, &nonce, &tampered).is_err());
+
+        assert!(open(&cipher, &nonce, &[]).is_err());
+    }
+
+    #[test]
+    fn test_aad_bound_to_every_chunk() {
+        let (cipher, nonce) = setup();
+        let plaintext = vec![0x5Au8; 2 * STREAM_CHUNK_SIZE + 1];
+
+        let sealed = seal_with_aad(&cipher, &nonce, &plaintext, b"header").unwrap();
+        assert_eq!(
+            open_with_aad(&cipher, &nonce, &sealed, b"header").unwrap(),
+            plaintext
+        );
+        assert!(open_with_aad(&cipher, &nonce, &sealed, b"headex").is_err());
+        assert!(open(&cipher, &nonce, &sealed).is_err());
+
+        // Empty AAD is the plain stream
+        assert_eq!(
+            seal_with_aad(&cipher, &nonce, &plaintext, &[]).unwrap(),
+            seal(&cipher, &nonce, &plaintext).unwrap()
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! # Constant-Time Comparison
+//!
+//! The one comparison for key material, MACs, digests and commitments,
+//! backed by `subtle`. `==` on such values can return as soon as a byte
+//! differs and leak how long the matching prefix is.
+
+use subtle::ConstantTimeEq;
+
+/// Constant-time equality of two byte strings
+///
+/// Slices of different lengths compare unequal; timing reveals only the
+/// lengths, never the contents.
+#[must_use]
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ct_eq_equal() {
+        assert!(ct_eq(&[7u8; 32], &[7u8; 32]));
+        assert!(ct_eq(&[], &[]));
+    }
+
+    #[test]
+    fn test_ct_eq_differs_in_any_byte() {
+        let a = [0x5au8; 32];
+        for i in 0..a.len() {
+            let mut b = a;
+            b[i] ^= 0x01;
+            assert!(!ct_eq(&a, &b));
+        }
+    }
+
+    #[test]
+    fn test_ct_eq_length_mismatch() {
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2]));
+        assert!(!ct_eq(&[], &[0]));
+    }
+}
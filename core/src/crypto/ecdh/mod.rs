@@ -12,6 +12,7 @@
 //! - `X25519ECDH`: Diffie-Hellman operations
 //! - `HybridKeyExchange`: Hybrid KEX combining Kyber + X25519
 //! - `HybridSharedSecret`: Combined shared secret (zeroizes on drop)
+//! - `KexTranscript`: Public values of a hybrid exchange, bound into the secret
 //!
 //! ## Example
 //!
@@ -28,30 +29,26 @@
 
 mod x25519;
 
-use crate::crypto::kem::KyberSharedSecret;
+use crate::crypto::fixed::{impl_fixed_public, impl_fixed_secret};
+use crate::crypto::hash::{hash, HashOutput};
+use crate::crypto::kem::{KyberCipherText, KyberPublicKeyBytes, KyberSharedSecret};
+use crate::crypto::redact::impl_redacted_debug;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// X25519 public key (32 bytes)
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct X25519PublicKeyBytes(pub [u8; 32]);
 
-impl X25519PublicKeyBytes {
-    /// Create from bytes
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::crypto::error::CryptoError> {
-        if bytes.len() != 32 {
-            return Err(crate::crypto::error::CryptoError::InvalidKeyLength {
-                expected: 32,
-                actual: bytes.len(),
-            });
-        }
-        let mut key = [0u8; 32];
-        key.copy_from_slice(bytes);
-        Ok(Self(key))
-    }
+impl_fixed_public!(X25519PublicKeyBytes, 32);
 
-    /// Get the key bytes
-    pub fn as_bytes(&self) -> &[u8; 32] {
-        &self.0
+impl X25519PublicKeyBytes {
+    /// Short identifier for pairing UI and logs, e.g. `a1b2-c3d4-e5f6-0718`
+    ///
+    /// First 8 bytes of the key's BLAKE3 hash; see [`HashOutput::fingerprint`].
+    ///
+    /// [`HashOutput::fingerprint`]: crate::crypto::hash::HashOutput::fingerprint
+    pub fn fingerprint(&self) -> String {
+        crate::crypto::hash::hash(&self.0).fingerprint()
     }
 }
 
@@ -61,25 +58,7 @@ impl X25519PublicKeyBytes {
 #[derive(Zeroize, ZeroizeOnDrop)]
 pub struct X25519SecretKeyBytes(pub [u8; 32]);
 
-impl X25519SecretKeyBytes {
-    /// Create from bytes
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::crypto::error::CryptoError> {
-        if bytes.len() != 32 {
-            return Err(crate::crypto::error::CryptoError::InvalidKeyLength {
-                expected: 32,
-                actual: bytes.len(),
-            });
-        }
-        let mut key = [0u8; 32];
-        key.copy_from_slice(bytes);
-        Ok(Self(key))
-    }
-
-    /// Get the key bytes
-    pub fn as_bytes(&self) -> &[u8; 32] {
-        &self.0
-    }
-}
+impl_fixed_secret!(X25519SecretKeyBytes, [u8; 32]);
 
 /// X25519 shared secret (32 bytes)
 ///
@@ -87,25 +66,7 @@ impl X25519SecretKeyBytes {
 #[derive(Zeroize, ZeroizeOnDrop)]
 pub struct EcdhSharedSecret(pub [u8; 32]);
 
-impl EcdhSharedSecret {
-    /// Create from bytes
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::crypto::error::CryptoError> {
-        if bytes.len() != 32 {
-            return Err(crate::crypto::error::CryptoError::InvalidKeyLength {
-                expected: 32,
-                actual: bytes.len(),
-            });
-        }
-        let mut secret = [0u8; 32];
-        secret.copy_from_slice(bytes);
-        Ok(Self(secret))
-    }
-
-    /// Get the secret bytes
-    pub fn as_bytes(&self) -> &[u8; 32] {
-        &self.0
-    }
-}
+impl_fixed_secret!(EcdhSharedSecret, [u8; 32]);
 
 /// X25519 key pair
 pub struct X25519KeyPair {
@@ -124,7 +85,9 @@ pub struct X25519ECDH;
 
 /// Hybrid shared secret combining Kyber and X25519
 ///
-/// Automatically zeroizes on drop.
+/// Automatically zeroizes on drop. Use
+/// [`split_directional`](Self::split_directional) to get separate send and
+/// receive keys instead of encrypting both directions under one key.
 #[derive(Zeroize, ZeroizeOnDrop)]
 pub struct HybridSharedSecret {
     /// The Kyber-1024 shared secret component
@@ -135,6 +98,57 @@ pub struct HybridSharedSecret {
     pub combined: [u8; 64],
 }
 
+impl_redacted_debug!(HybridSharedSecret, |secret| &secret.combined);
+
+/// Public transcript of a hybrid key exchange
+///
+/// Binds the combined secret to the exact keys and ciphertext exchanged,
+/// so a secret cannot be replayed under a different transcript.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KexTranscript {
+    /// X25519 public key of the initiator
+    pub initiator_x25519_public: X25519PublicKeyBytes,
+    /// X25519 public key of the responder
+    pub responder_x25519_public: X25519PublicKeyBytes,
+    /// Kyber-1024 public key the ciphertext was encapsulated to
+    pub kyber_public: KyberPublicKeyBytes,
+    /// Kyber-1024 ciphertext
+    pub kyber_ciphertext: KyberCipherText,
+}
+
+impl KexTranscript {
+    /// Create a transcript
+    pub fn new(
+        initiator_x25519_public: X25519PublicKeyBytes,
+        responder_x25519_public: X25519PublicKeyBytes,
+        kyber_public: KyberPublicKeyBytes,
+        kyber_ciphertext: KyberCipherText,
+    ) -> Self {
+        Self {
+            initiator_x25519_public,
+            responder_x25519_public,
+            kyber_public,
+            kyber_ciphertext,
+        }
+    }
+
+    /// BLAKE3 hash of the transcript
+    ///
+    /// All fields have fixed lengths, so the encoding is a plain
+    /// concatenation:
+    /// ```text
+    /// initiator_x25519 (32) || responder_x25519 (32) || kyber_pk (1568) || kyber_ct (1568)
+    /// ```
+    pub fn digest(&self) -> HashOutput {
+        let mut encoded = Vec::with_capacity(32 + 32 + 1568 + 1568);
+        encoded.extend_from_slice(self.initiator_x25519_public.as_bytes());
+        encoded.extend_from_slice(self.responder_x25519_public.as_bytes());
+        encoded.extend_from_slice(&self.kyber_public.0);
+        encoded.extend_from_slice(&self.kyber_ciphertext.0);
+        hash(&encoded)
+    }
+}
+
 /// Hybrid key exchange combining Kyber-1024 and X25519.
 ///
 /// Provides defense-in-depth by combining a post-quantum KEM
@@ -159,6 +173,19 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_public_key_fingerprint() {
+        let key = X25519PublicKeyBytes([0x22u8; 32]);
+        assert_eq!(key.fingerprint(), key.fingerprint());
+        assert_eq!(key.fingerprint().len(), 19);
+
+        // Distinct keys give distinct fingerprints
+        let fingerprints: std::collections::HashSet<String> = (0..=255u8)
+            .map(|i| X25519PublicKeyBytes([i; 32]).fingerprint())
+            .collect();
+        assert_eq!(fingerprints.len(), 256);
+    }
+
     #[test]
     fn test_secret_key_length() {
         let bytes = [0u8; 32];
@@ -29,11 +29,25 @@
 //! ```
 
 use super::{
-    EcdhSharedSecret, HybridKeyExchange, HybridSharedSecret, X25519KeyPair, X25519PublicKeyBytes,
-    X25519SecretKeyBytes, X25519ECDH,
+    EcdhSharedSecret, HybridKeyExchange, HybridSharedSecret, KexTranscript, X25519KeyPair,
+    X25519PublicKeyBytes, X25519SecretKeyBytes, X25519ECDH,
 };
+use crate::crypto::aead::{XChaCha20Key, KEY_SIZE};
 use crate::crypto::error::{CryptoError, Result};
+use crate::crypto::hash::DeriveKey;
 use crate::crypto::kem::KyberSharedSecret;
+#[cfg(any(test, feature = "deterministic"))]
+use zeroize::Zeroize;
+use zeroize::Zeroizing;
+
+/// Domain separation context for [`HybridKeyExchange::combine`]
+const TRANSCRIPT_KEX_CONTEXT: &str = "aeternum v5 hybrid-kex kyber1024+x25519 transcript";
+
+/// Context for the initiator → responder traffic key
+const INITIATOR_TO_RESPONDER_CONTEXT: &str = "aeternum v5 hybrid-kex traffic initiator->responder";
+
+/// Context for the responder → initiator traffic key
+const RESPONDER_TO_INITIATOR_CONTEXT: &str = "aeternum v5 hybrid-kex traffic responder->initiator";
 
 impl X25519ECDH {
     /// Generate a new X25519 keypair using the system CSPRNG.
@@ -62,6 +76,32 @@ impl X25519ECDH {
         }
     }
 
+    /// Derive an X25519 keypair from a fixed 32-byte seed.
+    ///
+    /// The seed is clamped as specified in RFC 7748 Section 5 (clear the
+    /// low 3 bits, clear the top bit, set bit 254) and used as the secret
+    /// scalar. The same seed always yields the same keypair.
+    ///
+    /// Only for test vectors and reproducible transcripts: available in
+    /// unit tests or with the `deterministic` feature, never in production
+    /// builds.
+    #[cfg(any(test, feature = "deterministic"))]
+    pub fn keypair_from_seed(seed: &[u8; 32]) -> X25519KeyPair {
+        let mut scalar = *seed;
+        scalar[0] &= 248;
+        scalar[31] &= 127;
+        scalar[31] |= 64;
+
+        let secret = x25519_dalek::StaticSecret::from(scalar);
+        let public = x25519_dalek::PublicKey::from(&secret);
+        scalar.zeroize();
+
+        X25519KeyPair {
+            public: X25519PublicKeyBytes(public.to_bytes()),
+            secret: X25519SecretKeyBytes(secret.to_bytes()),
+        }
+    }
+
     /// Perform Diffie-Hellman key agreement.
     ///
     /// Computes a shared secret from the local secret key and the
@@ -100,7 +140,7 @@ impl X25519ECDH {
         secret_key: &X25519SecretKeyBytes,
         public_key: &X25519PublicKeyBytes,
     ) -> Result<EcdhSharedSecret> {
-        let secret = x25519_dalek::StaticSecret::from(secret_key.0);
+        let secret = x25519_dalek::StaticSecret::from(*secret_key.as_bytes());
         let public = x25519_dalek::PublicKey::from(public_key.0);
 
         let shared = secret.diffie_hellman(&public);
@@ -128,7 +168,7 @@ impl X25519ECDH {
     ///
     /// The corresponding `X25519PublicKeyBytes`.
     pub fn public_from_secret(secret_key: &X25519SecretKeyBytes) -> X25519PublicKeyBytes {
-        let secret = x25519_dalek::StaticSecret::from(secret_key.0);
+        let secret = x25519_dalek::StaticSecret::from(*secret_key.as_bytes());
         let public = x25519_dalek::PublicKey::from(&secret);
         X25519PublicKeyBytes(public.to_bytes())
     }
@@ -189,6 +229,84 @@ impl HybridKeyExchange {
             combined,
         }
     }
+
+    /// Combine Kyber-1024 and X25519 shared secrets, bound to the transcript.
+    ///
+    /// Unlike [`combine_secrets`](Self::combine_secrets), the derivation
+    /// also covers both X25519 public keys, the Kyber public key and the
+    /// Kyber ciphertext, so the combined secret is only reproduced by a
+    /// party that saw exactly the same exchange.
+    ///
+    /// # Security
+    ///
+    /// The combined secret is derived as:
+    /// ```text
+    /// combined = BLAKE3-derive_key(
+    ///     context = "aeternum v5 hybrid-kex kyber1024+x25519 transcript",
+    ///     input   = kyber_secret || x25519_secret || BLAKE3(transcript),
+    ///     length  = 64
+    /// )
+    /// ```
+    /// See [`KexTranscript::digest`] for the transcript encoding. All
+    /// intermediate buffers are zeroized.
+    pub fn combine(
+        kyber_secret: KyberSharedSecret,
+        x25519_secret: EcdhSharedSecret,
+        transcript: &KexTranscript,
+    ) -> HybridSharedSecret {
+        let dk = crate::crypto::hash::DeriveKey::new(&[], TRANSCRIPT_KEX_CONTEXT);
+
+        let mut ikm = Zeroizing::new(Vec::with_capacity(96));
+        ikm.extend_from_slice(kyber_secret.as_bytes());
+        ikm.extend_from_slice(x25519_secret.as_bytes());
+        ikm.extend_from_slice(transcript.digest().as_bytes());
+
+        let derived = Zeroizing::new(dk.derive(&ikm, 64));
+
+        let mut combined = [0u8; 64];
+        combined.copy_from_slice(&derived);
+
+        HybridSharedSecret {
+            kyber_secret,
+            x25519_secret,
+            combined,
+        }
+    }
+}
+
+impl HybridSharedSecret {
+    /// Split the combined secret into one key per traffic direction.
+    ///
+    /// Returns `(initiator_to_responder, responder_to_initiator)`. Both
+    /// peers get the same pair; they differ only in which half they send
+    /// with:
+    ///
+    /// | Peer      | send                     | recv                     |
+    /// |-----------|--------------------------|--------------------------|
+    /// | Initiator | `initiator_to_responder` | `responder_to_initiator` |
+    /// | Responder | `responder_to_initiator` | `initiator_to_responder` |
+    ///
+    /// # Security
+    ///
+    /// ```text
+    /// key_i2r = BLAKE3-derive_key("aeternum v5 hybrid-kex traffic initiator->responder", combined)
+    /// key_r2i = BLAKE3-derive_key("aeternum v5 hybrid-kex traffic responder->initiator", combined)
+    /// ```
+    /// Distinct contexts make the two keys independent, so a nonce used in
+    /// one direction can never collide with the other direction's key.
+    pub fn split_directional(&self) -> (XChaCha20Key, XChaCha20Key) {
+        let derive = |context| {
+            let bytes =
+                Zeroizing::new(DeriveKey::new(&[], context).derive(&self.combined, KEY_SIZE));
+            // SAFETY: derive() always returns exactly KEY_SIZE bytes
+            XChaCha20Key::from_bytes(&bytes).unwrap()
+        };
+
+        (
+            derive(INITIATOR_TO_RESPONDER_CONTEXT),
+            derive(RESPONDER_TO_INITIATOR_CONTEXT),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -321,6 +439,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_keypair_from_seed_rfc7748_vector() {
+        // RFC 7748 Section 6.1 private keys used as seeds
+        let alice_seed: [u8; 32] =
+            hex::decode("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let bob_seed: [u8; 32] =
+            hex::decode("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        let alice = X25519ECDH::keypair_from_seed(&alice_seed);
+        let bob = X25519ECDH::keypair_from_seed(&bob_seed);
+
+        assert_eq!(
+            hex::encode(alice.public.as_bytes()),
+            "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"
+        );
+        assert_eq!(
+            hex::encode(bob.public.as_bytes()),
+            "de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"
+        );
+
+        let ss = X25519ECDH::diffie_hellman(&alice.secret, &bob.public).unwrap();
+        assert_eq!(
+            hex::encode(ss.as_bytes()),
+            "4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742"
+        );
+    }
+
+    #[test]
+    fn test_keypair_from_seed_clamps_and_is_deterministic() {
+        let seed = [0xFFu8; 32];
+        let a = X25519ECDH::keypair_from_seed(&seed);
+        let b = X25519ECDH::keypair_from_seed(&seed);
+
+        assert_eq!(a.public, b.public);
+        assert_eq!(a.secret.as_bytes(), b.secret.as_bytes());
+
+        let secret = a.secret.as_bytes();
+        assert_eq!(secret[0] & 7, 0);
+        assert_eq!(secret[31] & 0x80, 0);
+        assert_eq!(secret[31] & 0x40, 0x40);
+
+        let other = X25519ECDH::keypair_from_seed(&[0x01u8; 32]);
+        assert_ne!(a.public, other.public);
+    }
+
     #[test]
     fn test_rfc7748_iterated_1() {
         // RFC 7748 Section 5.2 - After 1 iteration
@@ -493,6 +662,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_split_directional_keys_match_across_peers() {
+        let x_initiator = X25519ECDH::generate_keypair();
+        let x_responder = X25519ECDH::generate_keypair();
+        let kyber = crate::crypto::kem::KyberKEM::generate_keypair();
+        let (ks_responder, ct) = crate::crypto::kem::KyberKEM::encapsulate(&kyber.public).unwrap();
+        let ks_initiator = crate::crypto::kem::KyberKEM::decapsulate(&kyber.secret, &ct).unwrap();
+        let transcript = KexTranscript::new(
+            x_initiator.public,
+            x_responder.public,
+            kyber.public.clone(),
+            ct,
+        );
+
+        let initiator = HybridKeyExchange::combine(
+            ks_initiator,
+            X25519ECDH::diffie_hellman(&x_initiator.secret, &x_responder.public).unwrap(),
+            &transcript,
+        );
+        let responder = HybridKeyExchange::combine(
+            ks_responder,
+            X25519ECDH::diffie_hellman(&x_responder.secret, &x_initiator.public).unwrap(),
+            &transcript,
+        );
+
+        let (initiator_send, initiator_recv) = initiator.split_directional();
+        let (responder_recv, responder_send) = responder.split_directional();
+
+        assert_eq!(initiator_send.as_bytes(), responder_recv.as_bytes());
+        assert_eq!(initiator_recv.as_bytes(), responder_send.as_bytes());
+        assert_ne!(initiator_send.as_bytes(), initiator_recv.as_bytes());
+        assert_ne!(&initiator_send.as_bytes()[..], &initiator.combined[..32]);
+        assert_ne!(&initiator_recv.as_bytes()[..], &initiator.combined[32..]);
+    }
+
     #[test]
     fn test_hybrid_combined_length() {
         let ks = KyberSharedSecret::from_bytes(&[0x11u8; 32]).unwrap();
@@ -566,6 +770,71 @@ mod tests {
         );
     }
 
+    // -- Transcript-bound combination ---------------------------------------
+
+    fn vector_transcript() -> KexTranscript {
+        use crate::crypto::kem::{KyberCipherText, KyberPublicKeyBytes};
+
+        KexTranscript::new(
+            X25519PublicKeyBytes([0x01; 32]),
+            X25519PublicKeyBytes([0x02; 32]),
+            KyberPublicKeyBytes([0x03; 1568]),
+            KyberCipherText([0x04; 1568]),
+        )
+    }
+
+    fn vector_combine(transcript: &KexTranscript) -> [u8; 64] {
+        let ks = KyberSharedSecret::from_bytes(&[0x11u8; 32]).unwrap();
+        let xs = EcdhSharedSecret::from_bytes(&[0x22u8; 32]).unwrap();
+        HybridKeyExchange::combine(ks, xs, transcript).combined
+    }
+
+    #[test]
+    fn test_combine_test_vector() {
+        let transcript = vector_transcript();
+        assert_eq!(
+            transcript.digest().to_hex(),
+            "52a2cf5182daf6ba10cd2e552ecdbac4d122f6d8a8e3503635fcdab8f479667f"
+        );
+        assert_eq!(hex::encode(vector_combine(&transcript)), "8044bb89ca722dc0723203a1c8ab4fdd34feac781d254f454783486f90c190c0853fc09671a8091b8f512d978b7e3c98800cc6ec0fc9c384eb8dc55ef802bda3");
+
+        // The construction spelled out with the blake3 crate
+        let mut input = Vec::new();
+        input.extend_from_slice(&[0x11u8; 32]);
+        input.extend_from_slice(&[0x22u8; 32]);
+        input.extend_from_slice(transcript.digest().as_bytes());
+        let mut expected = [0u8; 64];
+        blake3::Hasher::new_derive_key("aeternum v5 hybrid-kex kyber1024+x25519 transcript")
+            .update(&input)
+            .finalize_xof()
+            .fill(&mut expected);
+        assert_eq!(vector_combine(&transcript), expected);
+    }
+
+    #[test]
+    fn test_combine_binds_transcript() {
+        let transcript = vector_transcript();
+        let base = vector_combine(&transcript);
+
+        // Swapping the X25519 public keys changes the secret
+        let mut swapped = transcript.clone();
+        std::mem::swap(
+            &mut swapped.initiator_x25519_public,
+            &mut swapped.responder_x25519_public,
+        );
+        assert_ne!(vector_combine(&swapped), base);
+
+        // So does any change to the Kyber ciphertext
+        let mut tampered = transcript.clone();
+        tampered.kyber_ciphertext.0[1567] ^= 0x01;
+        assert_ne!(vector_combine(&tampered), base);
+
+        // And the result differs from the transcript-free combination
+        let ks = KyberSharedSecret::from_bytes(&[0x11u8; 32]).unwrap();
+        let xs = EcdhSharedSecret::from_bytes(&[0x22u8; 32]).unwrap();
+        assert_ne!(HybridKeyExchange::combine_secrets(ks, xs).combined, base);
+    }
+
     // -- Multiple rounds test -----------------------------------------------
 
     #[test]
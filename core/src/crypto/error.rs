@@ -94,6 +94,58 @@ pub enum CryptoError {
     /// 3. User alert: Force high-priority warning
     #[error("Invariant violation: {0}")]
     InvariantViolation(String),
+
+    /// BIP-39 mnemonic validation failed
+    ///
+    /// The inner [`MnemonicError`] tells the caller which part of the
+    /// phrase is wrong so the UI can point the user at it.
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(MnemonicError),
+
+    /// Device identifier string could not be parsed
+    ///
+    /// Accepted forms are the dashed UUID (8-4-4-4-12) and 32 bare hex
+    /// digits; the message says which part was wrong.
+    #[error("Invalid device ID: {0}")]
+    InvalidDeviceId(String),
+
+    /// Wrapping or unwrapping key material failed
+    ///
+    /// Raised by [`KeyWrapper`](crate::crypto::wrap::KeyWrapper)
+    /// implementations, e.g. a hardware keystore that refused the key or a
+    /// wrapped blob that does not authenticate.
+    #[error("Key wrapping failed: {0}")]
+    KeyWrapError(String),
+
+    /// Power-on known-answer test failed
+    ///
+    /// A primitive produced output that does not match its published test
+    /// vector. The library must not be used; see [`crate::crypto::self_test`].
+    #[error("Self-test failed for {primitive}: {reason}")]
+    SelfTestFailed {
+        /// The primitive whose check failed
+        primitive: String,
+        /// What did not match
+        reason: String,
+    },
+}
+
+/// Reason a BIP-39 mnemonic was rejected
+///
+/// Never carries the words themselves, only their position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum MnemonicError {
+    /// The phrase does not have a supported number of words
+    #[error("wrong word count: {0}")]
+    WrongWordCount(usize),
+
+    /// The word at this (0-based) index is not in the English wordlist
+    #[error("unknown word at position {0}")]
+    UnknownWord(usize),
+
+    /// All words are valid but the checksum does not match
+    #[error("bad checksum")]
+    BadChecksum,
 }
 
 impl CryptoError {
@@ -121,6 +173,14 @@ impl CryptoError {
     pub fn internal(msg: impl Into<String>) -> Self {
         Self::InternalError(msg.into())
     }
+
+    /// Create a self-test failure for `primitive`
+    pub fn self_test(primitive: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::SelfTestFailed {
+            primitive: primitive.into(),
+            reason: reason.into(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -142,6 +202,15 @@ mod tests {
         assert!(matches!(err, CryptoError::KdfError(_)));
     }
 
+    #[test]
+    fn test_invalid_mnemonic_display() {
+        let err = CryptoError::InvalidMnemonic(MnemonicError::UnknownWord(3));
+        assert_eq!(
+            err.to_string(),
+            "Invalid mnemonic: unknown word at position 3"
+        );
+    }
+
     #[test]
     fn test_verification_failed() {
         let err = CryptoError::VerificationFailed;
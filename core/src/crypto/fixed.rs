@@ -0,0 +1,314 @@
+//! # Fixed-Length Byte Newtypes
+//!
+//! One place for the length check, copy and accessors shared by every
+//! fixed-size key, ciphertext, nonce and tag newtype in `crypto`.
+//!
+//! - `impl_fixed_public!` for public values held as a plain `[u8; N]`
+//!   tuple field (`KyberPublicKeyBytes`, `X25519PublicKeyBytes`,
+//!   `AuthTag`, ...)
+//! - `impl_fixed_secret!` for secrets held in a [`SecretBytes`] or
+//!   [`LockedBytes`] field, or in a public `[u8; N]` field that is part of
+//!   the type's API (`KyberSharedSecret`, `EcdhSharedSecret`, ...); also
+//!   supplies the redacted `Debug`/`Display` through `impl_redacted_debug!`
+//!
+//! Both generate `LEN`, `from_bytes`, `try_from_slice` and `as_bytes`, and
+//! every length mismatch is reported as the same
+//! `CryptoError::InvalidKeyLength { expected: N, actual }`. New traits that
+//! should apply to all of these types (serde, `ct_eq`, ...) belong here.
+//!
+//! The struct itself, its derives (including `Zeroize`/`ZeroizeOnDrop` for
+//! secrets) and its docs stay at the definition site.
+//!
+//! [`SecretBytes`]: crate::crypto::secret::SecretBytes
+//! [`LockedBytes`]: crate::crypto::secure_mem::LockedBytes
+
+use crate::crypto::error::{CryptoError, Result};
+
+/// Copy `bytes` into an array, which must be exactly `N` bytes long
+///
+/// # Errors
+///
+/// Returns `CryptoError::InvalidKeyLength` if `bytes.len() != N`.
+pub(crate) fn copy_exact<const N: usize>(bytes: &[u8]) -> Result<[u8; N]> {
+    bytes.try_into().map_err(|_| CryptoError::InvalidKeyLength {
+        expected: N,
+        actual: bytes.len(),
+    })
+}
+
+/// Implement the fixed-length API for a public `Name(pub [u8; N])` type
+///
+/// By default `from_bytes` takes a slice and is length-checked; `array`
+/// makes it take `[u8; N]` and return `Self` (nonces and tags, which are
+/// mostly built from arrays). `serde` adds byte-string
+/// `Serialize`/`Deserialize` with the same length check. Doc comments after
+/// a `;` are appended to the generated `from_bytes` docs.
+///
+/// ```ignore
+/// impl_fixed_public!(X25519PublicKeyBytes, 32);
+/// impl_fixed_public!(KyberCipherText, 1568, serde);
+/// impl_fixed_public!(AuthTag, 16, array);
+/// impl_fixed_public!(XChaCha20Nonce, 24, array;
+///     /// # Example
+///     /// ...
+/// );
+/// ```
+macro_rules! impl_fixed_public {
+    ($ty:ident, $len:expr $(; $(#[$doc:meta])*)?) => {
+        impl $ty {
+            /// Create from a byte slice.
+            ///
+            /// # Errors
+            ///
+            /// Returns `CryptoError::InvalidKeyLength` if the slice has the wrong length.
+            $($(#[$doc])*)?
+            pub fn from_bytes(bytes: &[u8]) -> $crate::crypto::error::Result<Self> {
+                Self::try_from_slice(bytes)
+            }
+        }
+
+        $crate::crypto::fixed::impl_fixed_public!(@common $ty, $len);
+    };
+
+    ($ty:ident, $len:expr, array $(; $(#[$doc:meta])*)?) => {
+        impl $ty {
+            /// Create from raw bytes.
+            $($(#[$doc])*)?
+            pub fn from_bytes(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        $crate::crypto::fixed::impl_fixed_public!(@common $ty, $len);
+    };
+
+    ($ty:ident, $len:expr, serde) => {
+        $crate::crypto::fixed::impl_fixed_public!($ty, $len);
+
+        impl ::serde::Serialize for $ty {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                serializer.serialize_bytes(&self.0)
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for $ty {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                struct Visitor;
+
+                impl<'de> ::serde::de::Visitor<'de> for Visitor {
+                    type Value = $ty;
+
+                    fn expecting(
+                        &self,
+                        formatter: &mut ::std::fmt::Formatter<'_>,
+                    ) -> ::std::fmt::Result {
+                        write!(formatter, "{} bytes for {}", $len, stringify!($ty))
+                    }
+
+                    fn visit_bytes<E>(self, value: &[u8]) -> ::std::result::Result<$ty, E>
+                    where
+                        E: ::serde::de::Error,
+                    {
+                        $crate::crypto::fixed::copy_exact(value)
+                            .map($ty)
+                            .map_err(|_| E::invalid_length(value.len(), &self))
+                    }
+                }
+
+                deserializer.deserialize_bytes(Visitor)
+            }
+        }
+    };
+
+    (@common $ty:ident, $len:expr) => {
+        impl $ty {
+            /// Length in bytes
+            pub const LEN: usize = $len;
+
+            /// Try to create from a byte slice.
+            ///
+            /// # Errors
+            ///
+            /// Returns `CryptoError::InvalidKeyLength` if the slice has the wrong length.
+            pub fn try_from_slice(bytes: &[u8]) -> $crate::crypto::error::Result<Self> {
+                $crate::crypto::fixed::copy_exact(bytes).map(Self)
+            }
+
+            /// Get a reference to the bytes.
+            pub fn as_bytes(&self) -> &[u8; $len] {
+                &self.0
+            }
+        }
+    };
+}
+
+/// Implement the fixed-length API for a secret `Name(Backing)` type
+///
+/// `Backing` is a [`SecretBytes<N>`](crate::crypto::secret::SecretBytes)
+/// or [`LockedBytes<N>`](crate::crypto::secure_mem::LockedBytes) (or an
+/// alias of one); both check the length in `from_slice`. `[u8; N]` is for
+/// types whose public tuple field must stay a plain array. Also implements
+/// the redacted `Debug`/`Display`. As with `impl_fixed_public!`, doc
+/// comments after a `;` are appended to the `from_bytes` docs.
+///
+/// ```ignore
+/// impl_fixed_secret!(XChaCha20Key, SecretBytes<32>);
+/// impl_fixed_secret!(EcdhSharedSecret, [u8; 32]);
+/// ```
+macro_rules! impl_fixed_secret {
+    ($ty:ident, [u8; $len:expr] $(; $(#[$doc:meta])*)?) => {
+        impl $ty {
+            /// Length in bytes
+            pub const LEN: usize = $len;
+
+            /// Create from a byte slice.
+            ///
+            /// # Errors
+            ///
+            /// Returns `CryptoError::InvalidKeyLength` if the slice has the wrong length.
+            $($(#[$doc])*)?
+            pub fn from_bytes(bytes: &[u8]) -> $crate::crypto::error::Result<Self> {
+                $crate::crypto::fixed::copy_exact(bytes).map(Self)
+            }
+
+            /// Alias of [`from_bytes`](Self::from_bytes).
+            ///
+            /// # Errors
+            ///
+            /// Returns `CryptoError::InvalidKeyLength` if the slice has the wrong length.
+            pub fn try_from_slice(bytes: &[u8]) -> $crate::crypto::error::Result<Self> {
+                Self::from_bytes(bytes)
+            }
+
+            /// Get a reference to the secret bytes.
+            ///
+            /// Do not copy or log the returned bytes.
+            pub fn as_bytes(&self) -> &[u8; $len] {
+                &self.0
+            }
+        }
+
+        $crate::crypto::redact::impl_redacted_debug!($ty, |secret| secret.as_bytes());
+    };
+
+    ($ty:ident, $backing:ty $(; $(#[$doc:meta])*)?) => {
+        impl $ty {
+            /// Length in bytes
+            pub const LEN: usize = <$backing>::LEN;
+
+            /// Create from a byte slice.
+            ///
+            /// # Errors
+            ///
+            /// Returns `CryptoError::InvalidKeyLength` if the slice has the wrong length.
+            $($(#[$doc])*)?
+            pub fn from_bytes(bytes: &[u8]) -> $crate::crypto::error::Result<Self> {
+                <$backing>::from_slice(bytes).map(Self)
+            }
+
+            /// Alias of [`from_bytes`](Self::from_bytes).
+            ///
+            /// # Errors
+            ///
+            /// Returns `CryptoError::InvalidKeyLength` if the slice has the wrong length.
+            pub fn try_from_slice(bytes: &[u8]) -> $crate::crypto::error::Result<Self> {
+                Self::from_bytes(bytes)
+            }
+
+            /// Get a reference to the secret bytes.
+            ///
+            /// Do not copy or log the returned bytes.
+            pub fn as_bytes(&self) -> &[u8; <$backing>::LEN] {
+                self.0.as_bytes()
+            }
+        }
+
+        $crate::crypto::redact::impl_redacted_debug!($ty, |secret| secret.as_bytes());
+    };
+}
+
+pub(crate) use impl_fixed_public;
+pub(crate) use impl_fixed_secret;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::aead::{AuthTag, XChaCha20Key, XChaCha20Nonce};
+    use crate::crypto::ecdh::{EcdhSharedSecret, X25519PublicKeyBytes, X25519SecretKeyBytes};
+    use crate::crypto::kem::{
+        KyberCipherText, KyberPublicKeyBytes, KyberSecretKeyBytes, KyberSharedSecret,
+    };
+
+    /// Assert `try_from_slice` accepts exactly `len` bytes and rejects
+    /// one byte short and one byte long with the uniform error
+    fn assert_exact_length<T>(len: usize, try_from_slice: impl Fn(&[u8]) -> Result<T>) {
+        let bytes = vec![0x5Au8; len + 1];
+        assert!(try_from_slice(&bytes[..len]).is_ok());
+
+        for actual in [len - 1, len + 1] {
+            match try_from_slice(&bytes[..actual]) {
+                Err(CryptoError::InvalidKeyLength {
+                    expected,
+                    actual: got,
+                }) => {
+                    assert_eq!(expected, len);
+                    assert_eq!(got, actual);
+                }
+                Err(other) => panic!("expected InvalidKeyLength, got {:?}", other),
+                Ok(_) => panic!("accepted {} bytes, expected {}", actual, len),
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_type_rejects_off_by_one_lengths() {
+        assert_exact_length(1568, KyberPublicKeyBytes::try_from_slice);
+        assert_exact_length(1568, KyberPublicKeyBytes::from_bytes);
+        assert_exact_length(3168, KyberSecretKeyBytes::try_from_slice);
+        assert_exact_length(3168, KyberSecretKeyBytes::from_bytes);
+        assert_exact_length(1568, KyberCipherText::try_from_slice);
+        assert_exact_length(1568, KyberCipherText::from_bytes);
+        assert_exact_length(32, KyberSharedSecret::try_from_slice);
+        assert_exact_length(32, KyberSharedSecret::from_bytes);
+        assert_exact_length(32, X25519PublicKeyBytes::try_from_slice);
+        assert_exact_length(32, X25519PublicKeyBytes::from_bytes);
+        assert_exact_length(32, X25519SecretKeyBytes::try_from_slice);
+        assert_exact_length(32, X25519SecretKeyBytes::from_bytes);
+        assert_exact_length(32, EcdhSharedSecret::try_from_slice);
+        assert_exact_length(32, EcdhSharedSecret::from_bytes);
+        assert_exact_length(32, XChaCha20Key::try_from_slice);
+        assert_exact_length(32, XChaCha20Key::from_bytes);
+        assert_exact_length(24, XChaCha20Nonce::try_from_slice);
+        assert_exact_length(16, AuthTag::try_from_slice);
+    }
+
+    #[test]
+    fn test_len_matches_as_bytes() {
+        let tag = AuthTag::from_bytes([1u8; 16]);
+        assert_eq!(tag.as_bytes().len(), AuthTag::LEN);
+        let nonce = XChaCha20Nonce::from_bytes([1u8; 24]);
+        assert_eq!(nonce.as_bytes().len(), XChaCha20Nonce::LEN);
+        let key = KyberSecretKeyBytes::from_bytes(&[1u8; 3168]).unwrap();
+        assert_eq!(key.as_bytes().len(), KyberSecretKeyBytes::LEN);
+        assert_eq!(KyberPublicKeyBytes::LEN, 1568);
+        assert_eq!(XChaCha20Key::LEN, 32);
+    }
+
+    #[test]
+    fn test_serde_rejects_wrong_length() {
+        let ct = KyberCipherText([7u8; 1568]);
+        let encoded = bincode::serialize(&ct).unwrap();
+        let decoded: KyberCipherText = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded, ct);
+
+        let short = bincode::serialize(&serde_bytes::Bytes::new(&[7u8; 1567])).unwrap();
+        assert!(bincode::deserialize::<KyberCipherText>(&short).is_err());
+        assert!(bincode::deserialize::<KyberPublicKeyBytes>(&short).is_err());
+    }
+}
@@ -5,6 +5,8 @@
 //! ## Modes
 //!
 //! - **Hash mode**: One-shot or incremental hashing via [`Blake3Hasher`]
+//! - **Keyed hash mode**: 32-byte-keyed MAC via [`Blake3Hasher::new_keyed`]
+//!   or [`keyed_hash`]
 //! - **Key derivation mode**: Domain-separated KDF via [`DeriveKey`]
 //!
 //! ## Security Properties
@@ -33,6 +35,7 @@ use super::HashOutput;
 /// let oneshot = hash(b"hello world");
 /// assert_eq!(incremental, oneshot);
 /// ```
+#[derive(Clone)]
 pub struct Blake3Hasher {
     inner: blake3::Hasher,
 }
@@ -45,6 +48,16 @@ impl Blake3Hasher {
         }
     }
 
+    /// Create a new BLAKE3 hasher in keyed hash (MAC) mode.
+    ///
+    /// The output is a MAC over the fed data under `key`; it differs from
+    /// the unkeyed hash of the same data.
+    pub fn new_keyed(key: &[u8; 32]) -> Self {
+        Self {
+            inner: blake3::Hasher::new_keyed(key),
+        }
+    }
+
     /// Feed data into the hasher.
     ///
     /// Can be called multiple times to process data incrementally.
@@ -85,6 +98,24 @@ pub fn hash(data: &[u8]) -> HashOutput {
     HashOutput::from_bytes(*h.as_bytes())
 }
 
+/// Compute the keyed BLAKE3 hash (MAC) of `data` in one shot.
+///
+/// Equivalent to [`Blake3Hasher::new_keyed`] followed by `update(data)`
+/// and `finalize()`.
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::crypto::hash::{hash, keyed_hash};
+///
+/// let mac = keyed_hash(&[7u8; 32], b"hello");
+/// assert_ne!(mac, hash(b"hello"));
+/// ```
+pub fn keyed_hash(key: &[u8; 32], data: &[u8]) -> HashOutput {
+    let h = blake3::keyed_hash(key, data);
+    HashOutput::from_bytes(*h.as_bytes())
+}
+
 /// BLAKE3 key derivation context.
 ///
 /// Uses BLAKE3's built-in `derive_key` mode for domain-separated
@@ -229,6 +260,31 @@ mod tests {
 
     // ── Key derivation ──────────────────────────────────────────────
 
+    #[test]
+    fn test_keyed_hash_matches_reference() {
+        let key = [0x42u8; 32];
+        let expected = blake3::keyed_hash(&key, b"vault data");
+        assert_eq!(
+            keyed_hash(&key, b"vault data").as_bytes(),
+            expected.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_keyed_incremental_equals_oneshot() {
+        let key = [0x42u8; 32];
+        let mut hasher = Blake3Hasher::new_keyed(&key);
+        hasher.update(b"vault ").update(b"data");
+        assert_eq!(hasher.finalize(), keyed_hash(&key, b"vault data"));
+    }
+
+    #[test]
+    fn test_keyed_hash_depends_on_key() {
+        let data = b"vault data";
+        assert_ne!(keyed_hash(&[1u8; 32], data), keyed_hash(&[2u8; 32], data));
+        assert_ne!(keyed_hash(&[1u8; 32], data), hash(data));
+    }
+
     #[test]
     fn test_derive_key_deterministic() {
         let dk = DeriveKey::new(b"test-salt", "aeternum test context");
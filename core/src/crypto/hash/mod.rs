@@ -7,6 +7,7 @@
 //! - [`HashOutput`]: 32-byte hash output type (implements `Zeroize`)
 //! - [`Blake3Hasher`]: Incremental hasher with update/finalize API
 //! - [`hash`]: One-shot convenience function
+//! - [`keyed_hash`]: One-shot keyed hash (MAC)
 //! - [`DeriveKey`]: BLAKE3-based key derivation with domain separation
 
 mod blake3;
@@ -14,7 +15,7 @@ mod blake3;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 // Re-export all public items from the blake3 submodule
-pub use self::blake3::{hash, Blake3Hasher, DeriveKey};
+pub use self::blake3::{hash, keyed_hash, Blake3Hasher, DeriveKey};
 
 /// 32-byte BLAKE3 hash output.
 ///
@@ -42,6 +43,19 @@ impl HashOutput {
     pub fn to_hex(&self) -> String {
         hex::encode(self.0)
     }
+
+    /// Short human-readable identifier: the first 8 bytes as hyphen-grouped
+    /// hex, e.g. `a1b2-c3d4-e5f6-0718`.
+    ///
+    /// For display and logging only; 64 bits is too short to serve as a
+    /// collision-resistant identifier.
+    pub fn fingerprint(&self) -> String {
+        self.0[..8]
+            .chunks(2)
+            .map(hex::encode)
+            .collect::<Vec<_>>()
+            .join("-")
+    }
 }
 
 impl AsRef<[u8]> for HashOutput {
@@ -54,6 +68,16 @@ impl AsRef<[u8]> for HashOutput {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fingerprint_format() {
+        let mut bytes = [0xFFu8; 32];
+        bytes[..8].copy_from_slice(&[0xa1, 0xb2, 0xc3, 0xd4, 0xe5, 0xf6, 0x07, 0x18]);
+        assert_eq!(
+            HashOutput::from_bytes(bytes).fingerprint(),
+            "a1b2-c3d4-e5f6-0718"
+        );
+    }
+
     #[test]
     fn test_hash_output_creation() {
         let bytes = [42u8; 32];
@@ -15,7 +15,9 @@
 //! This implementation follows RFC 9106 (Argon2 Memory-Hard Function
 //! for Password Hashing and Proof-of-Work Applications).
 
-use super::{Argon2idConfig, DerivedKey};
+use std::sync::Arc;
+
+use super::{Argon2idConfig, DerivedKey, KdfCache};
 use crate::crypto::error::{CryptoError, Result};
 use argon2::{Algorithm, Argon2, Params, Version};
 
@@ -48,6 +50,8 @@ pub const MIN_SALT_LENGTH: usize = 16;
 /// ```
 pub struct Argon2idKDF {
     config: Argon2idConfig,
+    /// Optional output cache (disabled by default)
+    cache: Option<Arc<KdfCache>>,
 }
 
 impl Argon2idKDF {
@@ -61,6 +65,7 @@ impl Argon2idKDF {
     pub fn new() -> Self {
         Self {
             config: Argon2idConfig::default(),
+            cache: None,
         }
     }
 
@@ -83,7 +88,21 @@ impl Argon2idKDF {
     /// ```
     pub fn with_config(config: Argon2idConfig) -> Result<Self> {
         config.validate()?;
-        Ok(Self { config })
+        Ok(Self {
+            config,
+            cache: None,
+        })
+    }
+
+    /// Attach an output cache to this KDF.
+    ///
+    /// Subsequent derivations with identical (password, salt, parameters)
+    /// are served from `cache` instead of re-running Argon2id. The cache can
+    /// be shared between KDF instances; entries derived under a different
+    /// configuration never match.
+    pub fn with_cache(mut self, cache: Arc<KdfCache>) -> Self {
+        self.cache = Some(cache);
+        self
     }
 
     /// Get the current configuration.
@@ -127,25 +146,7 @@ impl Argon2idKDF {
             });
         }
 
-        // Build Argon2id parameters
-        let params = Params::new(
-            self.config.m_cost,
-            self.config.t_cost,
-            self.config.p_cost,
-            Some(self.config.output_len),
-        )
-        .map_err(|e| CryptoError::kdf(format!("Invalid Argon2id parameters: {}", e)))?;
-
-        // Create Argon2id context
-        let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
-
-        // Derive the key
-        let mut output = vec![0u8; self.config.output_len];
-        argon2
-            .hash_password_into(password, salt, &mut output)
-            .map_err(|e| CryptoError::kdf(format!("Key derivation failed: {}", e)))?;
-
-        Ok(DerivedKey(output))
+        self.derive_cached(password, salt, self.config)
     }
 
     /// Derive a key with a custom output length.
@@ -185,12 +186,36 @@ impl Argon2idKDF {
             )));
         }
 
+        let config = Argon2idConfig {
+            output_len,
+            ..self.config
+        };
+        self.derive_cached(password, salt, config)
+    }
+
+    /// Derive through the cache if one is attached.
+    fn derive_cached(
+        &self,
+        password: &[u8],
+        salt: &[u8],
+        config: Argon2idConfig,
+    ) -> Result<DerivedKey> {
+        match &self.cache {
+            Some(cache) => cache.get_or_derive(password, salt, &config, || {
+                Self::derive_raw(password, salt, &config)
+            }),
+            None => Self::derive_raw(password, salt, &config),
+        }
+    }
+
+    /// Run Argon2id with the given parameters.
+    fn derive_raw(password: &[u8], salt: &[u8], config: &Argon2idConfig) -> Result<DerivedKey> {
         // Build Argon2id parameters
         let params = Params::new(
-            self.config.m_cost,
-            self.config.t_cost,
-            self.config.p_cost,
-            Some(output_len),
+            config.m_cost,
+            config.t_cost,
+            config.p_cost,
+            Some(config.output_len),
         )
         .map_err(|e| CryptoError::kdf(format!("Invalid Argon2id parameters: {}", e)))?;
 
@@ -198,7 +223,7 @@ impl Argon2idKDF {
         let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
 
         // Derive the key
-        let mut output = vec![0u8; output_len];
+        let mut output = vec![0u8; config.output_len];
         argon2
             .hash_password_into(password, salt, &mut output)
             .map_err(|e| CryptoError::kdf(format!("Key derivation failed: {}", e)))?;
@@ -0,0 +1,312 @@
+//! # Argon2id Output Cache
+//!
+//! Opt-in memoization of Argon2id results so that repeated unlocks with the
+//! same password, salt and parameters do not pay the full memory-hard cost.
+//!
+//! ## Security Properties
+//!
+//! - The raw password is never stored; entries are keyed by a BLAKE3 hash of
+//!   (password commitment, salt, config)
+//! - Cached key material is held in `Zeroizing` buffers
+//! - [`KdfCache::invalidate_all`] zeroizes every cached key immediately
+//! - The cache is bounded; the oldest entry is evicted (and zeroized) first
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::Mutex;
+use zeroize::{Zeroize, Zeroizing};
+
+use super::{Argon2idConfig, DerivedKey};
+use crate::crypto::ct::ct_eq;
+use crate::crypto::hash::{Blake3Hasher, DeriveKey, HashOutput};
+
+/// Domain separation context for the password commitment
+const PASSWORD_COMMITMENT_CONTEXT: &str = "aeternum v5 kdf-cache password-commitment";
+
+/// Default maximum number of cached entries
+pub const DEFAULT_KDF_CACHE_CAPACITY: usize = 4;
+
+/// Bounded cache of Argon2id outputs.
+///
+/// Not enabled by default: attach it to a KDF with
+/// [`Argon2idKDF::with_cache`](super::Argon2idKDF::with_cache).
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use aeternum_core::crypto::kdf::{Argon2idConfig, Argon2idKDF, KdfCache};
+///
+/// let cache = Arc::new(KdfCache::new(4));
+/// let config = Argon2idConfig::new(8192, 1, 1, 32);
+/// let kdf = Argon2idKDF::with_config(config).unwrap().with_cache(cache.clone());
+///
+/// let salt = [0u8; 16];
+/// let k1 = kdf.derive_key(b"password", &salt).unwrap();
+/// let k2 = kdf.derive_key(b"password", &salt).unwrap();
+/// assert_eq!(k1.as_bytes(), k2.as_bytes());
+/// assert_eq!(cache.hits(), 1);
+///
+/// cache.invalidate_all();
+/// assert!(cache.is_empty());
+/// ```
+pub struct KdfCache {
+    /// Cached entries in insertion order (oldest first)
+    entries: Mutex<Vec<(HashOutput, Zeroizing<Vec<u8>>)>>,
+    /// Maximum number of entries
+    capacity: usize,
+    /// Number of lookups served from the cache
+    hits: AtomicU64,
+    /// Number of lookups that required a fresh derivation
+    misses: AtomicU64,
+}
+
+impl KdfCache {
+    /// Create a new cache holding at most `capacity` entries.
+    ///
+    /// A capacity of 0 disables caching while keeping the counters.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(Vec::with_capacity(capacity)),
+            capacity,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Maximum number of entries.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Current number of cached entries.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Check if the cache holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+
+    /// Number of lookups served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of lookups that missed the cache.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Zeroize and drop every cached key.
+    pub fn invalidate_all(&self) {
+        let mut entries = self.entries.lock();
+        for (_, key) in entries.iter_mut() {
+            key.zeroize();
+        }
+        entries.clear();
+    }
+
+    /// Return the cached key for the given inputs, or derive and store it.
+    pub(crate) fn get_or_derive<F>(
+        &self,
+        password: &[u8],
+        salt: &[u8],
+        config: &Argon2idConfig,
+        derive: F,
+    ) -> crate::crypto::error::Result<DerivedKey>
+    where
+        F: FnOnce() -> crate::crypto::error::Result<DerivedKey>,
+    {
+        let cache_key = Self::cache_key(password, salt, config);
+
+        if let Some((_, key)) = self
+            .entries
+            .lock()
+            .iter()
+            .find(|(k, _)| ct_eq(k.as_bytes(), cache_key.as_bytes()))
+        {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(DerivedKey(key.to_vec()));
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let derived = derive()?;
+
+        if self.capacity > 0 {
+            let mut entries = self.entries.lock();
+            if entries.len() >= self.capacity {
+                let (_, mut evicted) = entries.remove(0);
+                evicted.zeroize();
+            }
+            entries.push((cache_key, Zeroizing::new(derived.as_bytes().to_vec())));
+        }
+
+        Ok(derived)
+    }
+
+    /// Compute the cache key: `BLAKE3(commitment || salt_len || salt || config)`.
+    fn cache_key(password: &[u8], salt: &[u8], config: &Argon2idConfig) -> HashOutput {
+        let commitment =
+            Zeroizing::new(DeriveKey::new(&[], PASSWORD_COMMITMENT_CONTEXT).derive(password, 32));
+
+        let mut hasher = Blake3Hasher::new();
+        hasher
+            .update(&commitment)
+            .update(&(salt.len() as u64).to_be_bytes())
+            .update(salt)
+            .update(&config.m_cost.to_be_bytes())
+            .update(&config.t_cost.to_be_bytes())
+            .update(&config.p_cost.to_be_bytes())
+            .update(&(config.output_len as u64).to_be_bytes());
+        hasher.finalize()
+    }
+}
+
+impl Default for KdfCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_KDF_CACHE_CAPACITY)
+    }
+}
+
+impl Drop for KdfCache {
+    fn drop(&mut self) {
+        self.invalidate_all();
+    }
+}
+
+impl std::fmt::Debug for KdfCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print cached key material
+        f.debug_struct("KdfCache")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity)
+            .field("hits", &self.hits())
+            .field("misses", &self.misses())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::kdf::Argon2idKDF;
+    use std::sync::Arc;
+
+    fn cached_kdf(cache: &Arc<KdfCache>) -> Argon2idKDF {
+        let config = Argon2idConfig::new(8192, 1, 1, 32);
+        Argon2idKDF::with_config(config)
+            .unwrap()
+            .with_cache(cache.clone())
+    }
+
+    #[test]
+    fn test_second_derivation_hits_cache() {
+        let cache = Arc::new(KdfCache::new(4));
+        let kdf = cached_kdf(&cache);
+        let salt = [0x42u8; 16];
+
+        let key1 = kdf.derive_key(b"password", &salt).unwrap();
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 1);
+
+        let key2 = kdf.derive_key(b"password", &salt).unwrap();
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+        assert_eq!(key1.as_bytes(), key2.as_bytes());
+    }
+
+    #[test]
+    fn test_different_salt_misses() {
+        let cache = Arc::new(KdfCache::new(4));
+        let kdf = cached_kdf(&cache);
+
+        let key1 = kdf.derive_key(b"password", &[0x42u8; 16]).unwrap();
+        let key2 = kdf.derive_key(b"password", &[0x43u8; 16]).unwrap();
+
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 2);
+        assert_ne!(key1.as_bytes(), key2.as_bytes());
+    }
+
+    #[test]
+    fn test_different_password_misses() {
+        let cache = Arc::new(KdfCache::new(4));
+        let kdf = cached_kdf(&cache);
+        let salt = [0x42u8; 16];
+
+        kdf.derive_key(b"password1", &salt).unwrap();
+        kdf.derive_key(b"password2", &salt).unwrap();
+
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[test]
+    fn test_config_change_bypasses_cache() {
+        let cache = Arc::new(KdfCache::new(4));
+        let salt = [0x42u8; 16];
+
+        let kdf1 = cached_kdf(&cache);
+        let kdf2 = Argon2idKDF::with_config(Argon2idConfig::new(8192, 2, 1, 32))
+            .unwrap()
+            .with_cache(cache.clone());
+
+        let key1 = kdf1.derive_key(b"password", &salt).unwrap();
+        let key2 = kdf2.derive_key(b"password", &salt).unwrap();
+
+        assert_eq!(cache.hits(), 0);
+        assert_ne!(key1.as_bytes(), key2.as_bytes());
+
+        // Custom output length is part of the key as well
+        let key3 = kdf1.derive_key_with_length(b"password", &salt, 64).unwrap();
+        assert_eq!(key3.len(), 64);
+        assert_eq!(cache.hits(), 0);
+    }
+
+    #[test]
+    fn test_invalidate_all_zeroizes_and_recomputes() {
+        let cache = Arc::new(KdfCache::new(4));
+        let kdf = cached_kdf(&cache);
+        let salt = [0x42u8; 16];
+
+        let key1 = kdf.derive_key(b"password", &salt).unwrap();
+        assert_eq!(cache.len(), 1);
+
+        cache.invalidate_all();
+        assert!(cache.is_empty());
+
+        let key2 = kdf.derive_key(b"password", &salt).unwrap();
+        assert_eq!(cache.hits(), 0);
+        assert_eq!(cache.misses(), 2);
+        assert_eq!(key1.as_bytes(), key2.as_bytes());
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let cache = Arc::new(KdfCache::new(2));
+        let kdf = cached_kdf(&cache);
+
+        kdf.derive_key(b"a", &[1u8; 16]).unwrap();
+        kdf.derive_key(b"b", &[2u8; 16]).unwrap();
+        kdf.derive_key(b"c", &[3u8; 16]).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        // "a" was evicted
+        kdf.derive_key(b"a", &[1u8; 16]).unwrap();
+        assert_eq!(cache.hits(), 0);
+
+        // "c" is still cached
+        kdf.derive_key(b"c", &[3u8; 16]).unwrap();
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn test_debug_does_not_leak_keys() {
+        let cache = KdfCache::default();
+        let debug = format!("{:?}", cache);
+        assert!(debug.contains("KdfCache"));
+        assert!(debug.contains("capacity"));
+    }
+}
@@ -8,6 +8,7 @@
 //! - [`Argon2idConfig`]: Configuration with safe defaults (OWASP 2024)
 //! - [`DerivedKey`]: Output key material that zeroizes on drop
 //! - [`Argon2idKDF`]: Key derivation function with validation
+//! - [`KdfCache`]: Opt-in bounded cache of derived keys
 //!
 //! ## Security Properties
 //!
@@ -27,11 +28,14 @@
 //! ```
 
 mod argon2id;
+mod cache;
 
+use crate::crypto::redact::impl_redacted_debug;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 // Re-export the Argon2id KDF implementation
 pub use self::argon2id::{Argon2idKDF, MIN_SALT_LENGTH};
+pub use self::cache::{KdfCache, DEFAULT_KDF_CACHE_CAPACITY};
 
 /// Argon2id configuration with OWASP 2024 recommended defaults
 #[derive(Debug, Clone, Copy)]
@@ -107,14 +111,7 @@ impl Argon2idConfig {
 #[derive(Zeroize, ZeroizeOnDrop)]
 pub struct DerivedKey(pub Vec<u8>);
 
-impl std::fmt::Debug for DerivedKey {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Never print the actual key bytes to prevent leakage
-        f.debug_struct("DerivedKey")
-            .field("len", &self.0.len())
-            .finish_non_exhaustive()
-    }
-}
+impl_redacted_debug!(DerivedKey, |key| key.as_bytes());
 
 impl DerivedKey {
     /// Get the key bytes
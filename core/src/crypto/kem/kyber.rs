@@ -31,12 +31,15 @@ use super::{
     KyberCipherText, KyberKEM, KyberKeyPair, KyberPublicKeyBytes, KyberSecretKeyBytes,
     KyberSharedSecret,
 };
+use crate::crypto::ct::ct_eq;
 use crate::crypto::error::{CryptoError, Result};
 use pqcrypto_kyber::kyber1024;
 use pqcrypto_traits::kem::{
     Ciphertext as CiphertextTrait, PublicKey as PublicKeyTrait, SecretKey as SecretKeyTrait,
     SharedSecret as SharedSecretTrait,
 };
+use std::os::raw::c_int;
+use zeroize::Zeroizing;
 
 /// Kyber-1024 public key size in bytes
 pub const PUBLIC_KEY_SIZE: usize = 1568;
@@ -50,12 +53,24 @@ pub const CIPHERTEXT_SIZE: usize = 1568;
 /// Kyber-1024 shared secret size in bytes
 pub const SHARED_SECRET_SIZE: usize = 32;
 
+extern "C" {
+    // Compiled by pqcrypto-kyber from PQClean but not re-exported by it
+    fn PQCLEAN_KYBER1024_CLEAN_crypto_kem_keypair_derand(
+        pk: *mut u8,
+        sk: *mut u8,
+        coins: *const u8,
+    ) -> c_int;
+}
+
 impl KyberKEM {
     /// Generate a new Kyber-1024 keypair using the system CSPRNG.
     ///
     /// This is the recommended way to create Kyber keypairs. The underlying
     /// implementation uses PQClean's reference C code compiled from source.
     ///
+    /// For reproducible keys from a seed see
+    /// [`keypair_from_seed`](Self::keypair_from_seed).
+    ///
     /// # Returns
     ///
     /// A `KyberKeyPair` containing the public and secret keys.
@@ -76,12 +91,53 @@ impl KyberKEM {
         let mut pub_arr = [0u8; 1568];
         pub_arr.copy_from_slice(pk_bytes);
 
-        let mut sec_arr = [0u8; 3168];
-        sec_arr.copy_from_slice(sk_bytes);
+        KyberKeyPair {
+            public: KyberPublicKeyBytes(pub_arr),
+            secret: KyberSecretKeyBytes::from_bytes(sk_bytes)
+                .expect("PQClean secret key is always 3168 bytes"),
+        }
+    }
+
+    /// Derive a Kyber-1024 keypair from a fixed 64-byte seed.
+    ///
+    /// The seed is the `d || z` coins of the standard Kyber keygen and is
+    /// passed to PQClean's derandomized `crypto_kem_keypair_derand`, so the
+    /// same seed always yields the same keypair and the keys interoperate
+    /// with [`generate_keypair`](Self::generate_keypair) output.
+    ///
+    /// Only for test vectors and reproducible transcripts: available in
+    /// unit tests or with the `deterministic` feature, never in production
+    /// builds.
+    #[cfg(any(test, feature = "deterministic"))]
+    pub fn keypair_from_seed(seed: &[u8; 64]) -> KyberKeyPair {
+        Self::derive_keypair(seed)
+    }
+
+    /// Derive a Kyber-1024 keypair from secret seed material.
+    ///
+    /// Same construction as [`keypair_from_seed`](Self::keypair_from_seed),
+    /// for keys that must be re-derivable from a secret, such as the
+    /// Device_0 key recovered from the mnemonic. `seed` must be a
+    /// KDF output that never leaves the process.
+    pub(crate) fn derive_keypair(seed: &[u8; 64]) -> KyberKeyPair {
+        let mut pub_arr = [0u8; PUBLIC_KEY_SIZE];
+        let mut secret = Zeroizing::new([0u8; SECRET_KEY_SIZE]);
+
+        // SAFETY: the buffers have exactly the sizes PQClean writes
+        // (public key, secret key) and reads (2 × 32 coin bytes).
+        let rc = unsafe {
+            PQCLEAN_KYBER1024_CLEAN_crypto_kem_keypair_derand(
+                pub_arr.as_mut_ptr(),
+                secret.as_mut_ptr(),
+                seed.as_ptr(),
+            )
+        };
+        assert_eq!(rc, 0, "PQClean derandomized keygen cannot fail");
 
         KyberKeyPair {
             public: KyberPublicKeyBytes(pub_arr),
-            secret: KyberSecretKeyBytes(sec_arr),
+            secret: KyberSecretKeyBytes::from_bytes(&secret[..])
+                .expect("PQClean secret key is always 3168 bytes"),
         }
     }
 
@@ -172,7 +228,7 @@ impl KyberKEM {
         secret_key: &KyberSecretKeyBytes,
         ciphertext: &KyberCipherText,
     ) -> Result<KyberSharedSecret> {
-        let sk = SecretKeyTrait::from_bytes(&secret_key.0).map_err(|e| {
+        let sk = SecretKeyTrait::from_bytes(secret_key.as_bytes()).map_err(|e| {
             CryptoError::kem(format!("Invalid secret key for decapsulation: {}", e))
         })?;
 
@@ -190,12 +246,76 @@ impl KyberKEM {
     }
 }
 
+impl KyberKeyPair {
+    /// Assemble a keypair from separately stored halves.
+    ///
+    /// Both halves are checked against each other with a test round trip:
+    /// a fresh secret is encapsulated to `public` and decapsulated with
+    /// `secret`. A secret key from another pair (or a corrupted one) hits
+    /// Kyber's implicit rejection and yields a different shared secret, so
+    /// swapped or damaged key files are caught at load time instead of at
+    /// the first real decapsulation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::KemError` if the halves do not belong to the
+    /// same keypair.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::crypto::kem::{KyberKEM, KyberKeyPair, KyberSecretKeyBytes};
+    ///
+    /// let keypair = KyberKEM::generate_keypair();
+    /// let secret = KyberSecretKeyBytes::from_bytes(keypair.secret.as_bytes()).unwrap();
+    /// assert!(KyberKeyPair::from_parts(keypair.public.clone(), secret).is_ok());
+    ///
+    /// let other = KyberKEM::generate_keypair();
+    /// assert!(KyberKeyPair::from_parts(keypair.public, other.secret).is_err());
+    /// ```
+    pub fn from_parts(
+        public: KyberPublicKeyBytes,
+        secret: KyberSecretKeyBytes,
+    ) -> Result<KyberKeyPair> {
+        let (expected, ciphertext) = KyberKEM::encapsulate(&public)?;
+        let actual = KyberKEM::decapsulate(&secret, &ciphertext)?;
+
+        if !ct_eq(expected.as_bytes(), actual.as_bytes()) {
+            return Err(CryptoError::kem(
+                "Secret key does not match public key".to_string(),
+            ));
+        }
+
+        Ok(KyberKeyPair { public, secret })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     // ── Basic functionality tests ────────────────────────────────────
 
+    #[test]
+    fn test_keypair_from_seed_is_deterministic() {
+        let seed = [0x5Au8; 64];
+        let a = KyberKEM::keypair_from_seed(&seed);
+        let b = KyberKEM::keypair_from_seed(&seed);
+        assert_eq!(a.public, b.public);
+        assert_eq!(a.secret.as_bytes(), b.secret.as_bytes());
+
+        let mut other_seed = seed;
+        other_seed[63] ^= 1;
+        let other = KyberKEM::keypair_from_seed(&other_seed);
+        assert_ne!(a.secret.as_bytes(), other.secret.as_bytes());
+
+        // Seeded keys are ordinary Kyber keys
+        let (ss, ct) = KyberKEM::encapsulate(&a.public).unwrap();
+        let recovered = KyberKEM::decapsulate(&b.secret, &ct).unwrap();
+        assert_eq!(ss.as_bytes(), recovered.as_bytes());
+        assert!(KyberKeyPair::from_parts(a.public.clone(), b.secret).is_ok());
+    }
+
     #[test]
     fn test_keypair_generation() {
         let kp = KyberKEM::generate_keypair();
@@ -371,6 +491,35 @@ mod tests {
         assert_eq!(ss1.as_bytes(), ss2.as_bytes());
     }
 
+    // ── Keypair consistency tests ────────────────────────────────────
+
+    #[test]
+    fn test_from_parts_accepts_matching_pair() {
+        let kp = KyberKEM::generate_keypair();
+        let public = KyberPublicKeyBytes::from_bytes(kp.public.as_bytes()).unwrap();
+        let secret = KyberSecretKeyBytes::from_bytes(kp.secret.as_bytes()).unwrap();
+
+        let restored = KyberKeyPair::from_parts(public, secret).unwrap();
+        let (ss1, ct) = KyberKEM::encapsulate(&restored.public).unwrap();
+        let ss2 = KyberKEM::decapsulate(&restored.secret, &ct).unwrap();
+        assert_eq!(ss1.as_bytes(), ss2.as_bytes());
+    }
+
+    #[test]
+    fn test_from_parts_rejects_mismatched_pair() {
+        let kp1 = KyberKEM::generate_keypair();
+        let kp2 = KyberKEM::generate_keypair();
+
+        let result = KyberKeyPair::from_parts(kp1.public.clone(), kp2.secret);
+        assert!(matches!(result, Err(CryptoError::KemError(_))));
+
+        // A secret key with a corrupted byte is rejected as well
+        let mut corrupted = kp1.secret.as_bytes().to_vec();
+        corrupted[0] ^= 0x01;
+        let secret = KyberSecretKeyBytes::from_bytes(&corrupted).unwrap();
+        assert!(KyberKeyPair::from_parts(kp1.public, secret).is_err());
+    }
+
     // ── Multiple rounds test ─────────────────────────────────────────
 
     #[test]
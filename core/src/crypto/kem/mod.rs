@@ -11,6 +11,8 @@
 //! - `KyberSharedSecret`: 32-byte shared secret (zeroizes on drop)
 //! - `KyberKeyPair`: Public/secret key pair
 //! - `KyberKEM`: Encapsulation/decapsulation operations
+//! - `wrap_dek_for_devices` / `unwrap_dek`: DEK fan-out to a device set
+//!   and its inverse
 //!
 //! ## Key Sizes (pqcrypto-kyber 0.8.1 / PQClean)
 //!
@@ -34,6 +36,11 @@
 
 mod kyber;
 
+use crate::crypto::error::Result;
+use crate::crypto::fixed::{impl_fixed_public, impl_fixed_secret};
+use crate::crypto::secure_mem::LockedBytes;
+use crate::models::device::DeviceId;
+use crate::models::key_hierarchy::{DataEncryptionKey, WrappedDek};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 // Re-export constants from kyber module
@@ -43,176 +50,34 @@ pub use kyber::{CIPHERTEXT_SIZE, PUBLIC_KEY_SIZE, SECRET_KEY_SIZE, SHARED_SECRET
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct KyberPublicKeyBytes(pub [u8; 1568]);
 
-// Implement serialization using serde_bytes
-impl serde::Serialize for KyberPublicKeyBytes {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_bytes(&self.0)
-    }
-}
-
-impl<'de> serde::Deserialize<'de> for KyberPublicKeyBytes {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        deserializer.deserialize_bytes(KyberPublicKeyBytesVisitor)
-    }
-}
-
-struct KyberPublicKeyBytesVisitor;
-
-impl<'de> serde::de::Visitor<'de> for KyberPublicKeyBytesVisitor {
-    type Value = KyberPublicKeyBytes;
-
-    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        formatter.write_str("1568-byte Kyber public key")
-    }
-
-    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        if value.len() != 1568 {
-            return Err(serde::de::Error::invalid_length(
-                value.len(),
-                &"expected 1568 bytes for KyberPublicKeyBytes",
-            ));
-        }
-        let mut key = [0u8; 1568];
-        key.copy_from_slice(value);
-        Ok(KyberPublicKeyBytes(key))
-    }
-}
+impl_fixed_public!(KyberPublicKeyBytes, 1568, serde);
 
 impl KyberPublicKeyBytes {
-    /// Create from a byte slice.
+    /// Short identifier for pairing UI and logs, e.g. `a1b2-c3d4-e5f6-0718`
     ///
-    /// # Errors
+    /// First 8 bytes of the key's BLAKE3 hash; see [`HashOutput::fingerprint`].
     ///
-    /// Returns `CryptoError::InvalidKeyLength` if `bytes.len() != 1568`.
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::crypto::error::CryptoError> {
-        if bytes.len() != 1568 {
-            return Err(crate::crypto::error::CryptoError::InvalidKeyLength {
-                expected: 1568,
-                actual: bytes.len(),
-            });
-        }
-        let mut key = [0u8; 1568];
-        key.copy_from_slice(bytes);
-        Ok(Self(key))
-    }
-
-    /// Get the key bytes.
-    pub fn as_bytes(&self) -> &[u8; 1568] {
-        &self.0
+    /// [`HashOutput::fingerprint`]: crate::crypto::hash::HashOutput::fingerprint
+    pub fn fingerprint(&self) -> String {
+        crate::crypto::hash::hash(&self.0).fingerprint()
     }
 }
 
 /// Kyber-1024 secret key (3168 bytes, PQClean)
 ///
 /// Automatically zeroizes on drop to prevent secret key material
-/// from persisting in memory.
+/// from persisting in memory. Held in a [`LockedBytes`] so it is
+/// never swapped to disk.
 #[derive(Zeroize, ZeroizeOnDrop)]
-pub struct KyberSecretKeyBytes(pub [u8; 3168]);
-
-impl KyberSecretKeyBytes {
-    /// Create from a byte slice.
-    ///
-    /// # Errors
-    ///
-    /// Returns `CryptoError::InvalidKeyLength` if `bytes.len() != 3168`.
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::crypto::error::CryptoError> {
-        if bytes.len() != 3168 {
-            return Err(crate::crypto::error::CryptoError::InvalidKeyLength {
-                expected: 3168,
-                actual: bytes.len(),
-            });
-        }
-        let mut key = [0u8; 3168];
-        key.copy_from_slice(bytes);
-        Ok(Self(key))
-    }
+pub struct KyberSecretKeyBytes(LockedBytes<3168>);
 
-    /// Get the key bytes.
-    pub fn as_bytes(&self) -> &[u8; 3168] {
-        &self.0
-    }
-}
+impl_fixed_secret!(KyberSecretKeyBytes, LockedBytes<3168>);
 
 /// Kyber-1024 encapsulated ciphertext (1568 bytes)
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct KyberCipherText(pub [u8; 1568]);
 
-// Implement serialization using serde_bytes
-impl serde::Serialize for KyberCipherText {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_bytes(&self.0)
-    }
-}
-
-impl<'de> serde::Deserialize<'de> for KyberCipherText {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        deserializer.deserialize_bytes(KyberCipherTextVisitor)
-    }
-}
-
-struct KyberCipherTextVisitor;
-
-impl<'de> serde::de::Visitor<'de> for KyberCipherTextVisitor {
-    type Value = KyberCipherText;
-
-    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        formatter.write_str("1568-byte Kyber ciphertext")
-    }
-
-    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        if value.len() != 1568 {
-            return Err(serde::de::Error::invalid_length(
-                value.len(),
-                &"expected 1568 bytes for KyberCipherText",
-            ));
-        }
-        let mut ct = [0u8; 1568];
-        ct.copy_from_slice(value);
-        Ok(KyberCipherText(ct))
-    }
-}
-
-impl KyberCipherText {
-    /// Create from a byte slice.
-    ///
-    /// # Errors
-    ///
-    /// Returns `CryptoError::InvalidKeyLength` if `bytes.len() != 1568`.
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::crypto::error::CryptoError> {
-        if bytes.len() != 1568 {
-            return Err(crate::crypto::error::CryptoError::InvalidKeyLength {
-                expected: 1568,
-                actual: bytes.len(),
-            });
-        }
-        let mut ct = [0u8; 1568];
-        ct.copy_from_slice(bytes);
-        Ok(Self(ct))
-    }
-
-    /// Get the ciphertext bytes.
-    pub fn as_bytes(&self) -> &[u8; 1568] {
-        &self.0
-    }
-}
+impl_fixed_public!(KyberCipherText, 1568, serde);
 
 /// Kyber-1024 shared secret (32 bytes)
 ///
@@ -221,29 +86,7 @@ impl KyberCipherText {
 #[derive(Zeroize, ZeroizeOnDrop)]
 pub struct KyberSharedSecret(pub [u8; 32]);
 
-impl KyberSharedSecret {
-    /// Create from a byte slice.
-    ///
-    /// # Errors
-    ///
-    /// Returns `CryptoError::InvalidKeyLength` if `bytes.len() != 32`.
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::crypto::error::CryptoError> {
-        if bytes.len() != 32 {
-            return Err(crate::crypto::error::CryptoError::InvalidKeyLength {
-                expected: 32,
-                actual: bytes.len(),
-            });
-        }
-        let mut secret = [0u8; 32];
-        secret.copy_from_slice(bytes);
-        Ok(Self(secret))
-    }
-
-    /// Get the secret bytes.
-    pub fn as_bytes(&self) -> &[u8; 32] {
-        &self.0
-    }
-}
+impl_fixed_secret!(KyberSharedSecret, [u8; 32]);
 
 /// Kyber-1024 key pair containing public and secret keys.
 pub struct KyberKeyPair {
@@ -261,6 +104,64 @@ pub struct KyberKeyPair {
 /// All operations are implemented as associated functions (no instance state).
 pub struct KyberKEM;
 
+/// Wrap `dek` for every device in `pubkeys`
+///
+/// One Kyber-1024 encapsulation and AEAD wrap per device, each with its
+/// own nonce (see [`DataEncryptionKey::wrap_for_device`]). A bare
+/// [`KyberCipherText`] cannot carry a chosen DEK, so each device gets a
+/// full [`WrappedDek`]. With the `parallel` feature the devices are
+/// processed on the rayon thread pool; results keep the order of
+/// `pubkeys` either way, and a failure for one device does not stop the
+/// others.
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::crypto::kem::{unwrap_dek, wrap_dek_for_devices, KyberKEM};
+/// use aeternum_core::models::{DataEncryptionKey, DeviceId};
+///
+/// let phone = KyberKEM::generate_keypair();
+/// let laptop = KyberKEM::generate_keypair();
+/// let pubkeys = [
+///     (DeviceId::generate(), phone.public.clone()),
+///     (DeviceId::generate(), laptop.public.clone()),
+/// ];
+///
+/// let dek = DataEncryptionKey::generate();
+/// let wrapped = wrap_dek_for_devices(&dek, &pubkeys);
+/// let on_laptop = unwrap_dek(&laptop.secret, wrapped[1].1.as_ref().unwrap()).unwrap();
+/// assert_eq!(on_laptop.as_bytes(), dek.as_bytes());
+/// ```
+pub fn wrap_dek_for_devices(
+    dek: &DataEncryptionKey,
+    pubkeys: &[(DeviceId, KyberPublicKeyBytes)],
+) -> Vec<(DeviceId, Result<WrappedDek>)> {
+    let wrap = |(device_id, public_key): &(DeviceId, KyberPublicKeyBytes)| {
+        (*device_id, dek.wrap_for_device(public_key))
+    };
+
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        pubkeys.par_iter().map(wrap).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        pubkeys.iter().map(wrap).collect()
+    }
+}
+
+/// Unwrap a DEK wrapped by [`wrap_dek_for_devices`] with the device's secret key
+///
+/// # Errors
+///
+/// Returns `CryptoError::AeadError` if `secret` does not belong to the
+/// device `wrapped` was made for or any part of it was modified, and
+/// `CryptoError::KemError` for a malformed secret key.
+pub fn unwrap_dek(secret: &KyberSecretKeyBytes, wrapped: &WrappedDek) -> Result<DataEncryptionKey> {
+    DataEncryptionKey::unwrap(wrapped, secret)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,6 +186,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_public_key_fingerprint() {
+        let key = KyberPublicKeyBytes([0x11u8; 1568]);
+        let fingerprint = key.fingerprint();
+
+        // Four hyphen-separated groups of four hex digits
+        assert_eq!(fingerprint.len(), 19);
+        assert!(fingerprint
+            .split('-')
+            .all(|group| group.len() == 4 && group.chars().all(|c| c.is_ascii_hexdigit())));
+
+        // Stable, and distinct for a key differing in one byte
+        assert_eq!(fingerprint, key.clone().fingerprint());
+        let mut other = key.clone();
+        other.0[1567] ^= 0x01;
+        assert_ne!(fingerprint, other.fingerprint());
+    }
+
     #[test]
     fn test_secret_key_from_bytes_valid() {
         let bytes = [0u8; 3168];
@@ -323,4 +242,36 @@ mod tests {
         let result = KyberSharedSecret::from_bytes(&[0u8; 16]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_wrap_dek_for_devices_each_device_unwraps() {
+        let keypairs: Vec<_> = (0..4).map(|_| KyberKEM::generate_keypair()).collect();
+        let pubkeys: Vec<_> = keypairs
+            .iter()
+            .map(|kp| (DeviceId::generate(), kp.public.clone()))
+            .collect();
+        let dek = DataEncryptionKey::generate();
+
+        let wrapped = wrap_dek_for_devices(&dek, &pubkeys);
+        assert_eq!(wrapped.len(), pubkeys.len());
+
+        for (i, (device_id, wrapped)) in wrapped.iter().enumerate() {
+            assert_eq!(*device_id, pubkeys[i].0);
+            let wrapped = wrapped.as_ref().unwrap();
+            let unwrapped = unwrap_dek(&keypairs[i].secret, wrapped).unwrap();
+            assert_eq!(unwrapped.as_bytes(), dek.as_bytes());
+        }
+        assert!(wrap_dek_for_devices(&dek, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_unwrap_dek_wrong_secret_fails() {
+        let device = KyberKEM::generate_keypair();
+        let other = KyberKEM::generate_keypair();
+        let dek = DataEncryptionKey::generate();
+
+        let wrapped = wrap_dek_for_devices(&dek, &[(DeviceId::generate(), device.public)]);
+        let wrapped = wrapped[0].1.as_ref().unwrap();
+        assert!(unwrap_dek(&other.secret, wrapped).is_err());
+    }
 }
@@ -18,6 +18,13 @@
 //! - `aead` - XChaCha20-Poly1305 authenticated encryption
 //! - `kem` - Kyber-1024 post-quantum key encapsulation
 //! - `ecdh` - X25519 elliptic curve Diffie-Hellman
+//! - `fixed` - Shared length-checked API for the fixed-size byte newtypes
+//! - `ct` - Constant-time comparison of secrets and MACs
+//! - `secret` - Fixed-size zeroizing secret buffer behind the key newtypes
+//! - `secure_mem` - Page-locked buffers for long-lived key material
+//! - `wrap` - Opaque key wrapping layer (hardware keystore or software AEAD)
+//! - `redact` - Redacted `Debug`/`Display` for every secret-bearing type
+//! - `self_test` - Power-on known-answer tests
 
 // Error handling
 pub mod error;
@@ -29,14 +36,43 @@ pub mod hash;
 pub mod kdf;
 pub mod kem;
 
+// Fixed-size newtype plumbing
+mod fixed;
+
+// Constant-time comparison
+pub mod ct;
+
+// Memory protection
+pub mod redact;
+pub mod secret;
+pub mod secure_mem;
+
+// Key wrapping for secrets at rest
+pub mod wrap;
+
+// Power-on self-test
+pub mod self_test;
+
 // Re-export common types at the crypto module level
-pub use error::{CryptoError, Result};
+pub use error::{CryptoError, MnemonicError, Result};
+
+// Re-export secure memory types
+pub use secret::SecretBytes;
+pub use secure_mem::{LockedBuffer, LockedBytes};
+
+// Re-export key wrapping types
+pub use wrap::{AeadKeyWrapper, KeyWrapper};
+
+// Re-export self-test entry point
+pub use self_test::run_self_test;
 
 // Re-export hash types
-pub use hash::{hash as blake3_hash, Blake3Hasher, DeriveKey, HashOutput};
+pub use hash::{
+    hash as blake3_hash, keyed_hash as blake3_keyed_hash, Blake3Hasher, DeriveKey, HashOutput,
+};
 
 // Re-export KDF types
-pub use kdf::{Argon2idConfig, Argon2idKDF, DerivedKey};
+pub use kdf::{Argon2idConfig, Argon2idKDF, DerivedKey, KdfCache};
 
 // Re-export AEAD types
 pub use aead::{AeadCipher, AuthTag, XChaCha20Key, XChaCha20Nonce};
@@ -49,6 +85,6 @@ pub use kem::{
 
 // Re-export ECDH types
 pub use ecdh::{
-    EcdhSharedSecret, HybridKeyExchange, HybridSharedSecret, X25519KeyPair, X25519PublicKeyBytes,
-    X25519SecretKeyBytes, X25519ECDH,
+    EcdhSharedSecret, HybridKeyExchange, HybridSharedSecret, KexTranscript, X25519KeyPair,
+    X25519PublicKeyBytes, X25519SecretKeyBytes, X25519ECDH,
 };
@@ -0,0 +1,195 @@
+//! # Redacted Formatting
+//!
+//! Compile-time guard against secret material reaching logs.
+//!
+//! Every secret-bearing type in `crypto` and `models::key_hierarchy`
+//! implements [`Redacted`] through `impl_redacted_debug!`, which also
+//! supplies its `Debug` and `Display` impls. Both print only the type name
+//! and the secret's length:
+//!
+//! ```text
+//! XChaCha20Key([REDACTED; 32 bytes])
+//! ```
+//!
+//! A hand-written `Debug` on a secret type is a review red flag; use the
+//! macro instead. Tests that genuinely need the bytes call
+//! [`Redacted::expose_secret_for_test`], which only exists under
+//! `cfg(test)`.
+
+use std::fmt;
+
+/// Marker for types holding secret material
+///
+/// Implemented by `impl_redacted_debug!` together with redacted
+/// `Debug` and `Display` impls; do not implement it by hand.
+pub trait Redacted {
+    /// Name printed in place of the contents
+    const TYPE_NAME: &'static str;
+
+    /// Length of the secret material in bytes
+    fn secret_len(&self) -> usize;
+
+    /// Raw secret bytes, for tests that must compare key material
+    #[cfg(test)]
+    #[doc(hidden)]
+    fn expose_secret_for_test(&self) -> Vec<u8>;
+}
+
+/// Write `Name([REDACTED; N bytes])` for a [`Redacted`] value
+pub fn fmt_redacted<T: Redacted + ?Sized>(value: &T, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+        f,
+        "{}([REDACTED; {} bytes])",
+        T::TYPE_NAME,
+        value.secret_len()
+    )
+}
+
+/// Implement [`Redacted`], `Debug` and `Display` for a secret type
+///
+/// The closure-like argument names the value and returns its secret
+/// bytes (anything that coerces to `&[u8]`); only their length is ever
+/// printed.
+///
+/// ```ignore
+/// impl_redacted_debug!(XChaCha20Key, |key| key.as_bytes());
+/// ```
+macro_rules! impl_redacted_debug {
+    ($ty:ident, |$this:ident| $bytes:expr) => {
+        impl $ty {
+            fn redacted_bytes(&self) -> &[u8] {
+                let $this = self;
+                $bytes
+            }
+        }
+
+        impl $crate::crypto::redact::Redacted for $ty {
+            const TYPE_NAME: &'static str = stringify!($ty);
+
+            fn secret_len(&self) -> usize {
+                self.redacted_bytes().len()
+            }
+
+            #[cfg(test)]
+            fn expose_secret_for_test(&self) -> Vec<u8> {
+                self.redacted_bytes().to_vec()
+            }
+        }
+
+        impl ::std::fmt::Debug for $ty {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                $crate::crypto::redact::fmt_redacted(self, f)
+            }
+        }
+
+        impl ::std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                $crate::crypto::redact::fmt_redacted(self, f)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_redacted_debug;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::aead::XChaCha20Key;
+    use crate::crypto::ecdh::{EcdhSharedSecret, HybridSharedSecret, X25519SecretKeyBytes};
+    use crate::crypto::kdf::DerivedKey;
+    use crate::crypto::kem::{KyberSecretKeyBytes, KyberSharedSecret};
+    use crate::models::key_hierarchy::{
+        DataEncryptionKey, IdentityKey, MasterSeed, RecoveryKey, VaultKey,
+    };
+
+    /// Secret bytes with no run a formatter could produce by accident
+    fn pattern<const N: usize>() -> [u8; N] {
+        std::array::from_fn(|i| 0xA7 ^ (i as u8).wrapping_mul(31))
+    }
+
+    /// Assert that neither `Debug` nor `Display` leaks the secret
+    fn assert_redacted<T: Redacted + fmt::Debug + fmt::Display>(value: &T) {
+        let secret = value.expose_secret_for_test();
+        let hex = hex::encode(&secret[..8]);
+        let raw = String::from_utf8_lossy(&secret[..8]).into_owned();
+        let expected = format!("{}([REDACTED; {} bytes])", T::TYPE_NAME, secret.len());
+
+        for out in [
+            format!("{:?}", value),
+            format!("{:#?}", value),
+            value.to_string(),
+        ] {
+            assert_eq!(out, expected);
+            assert!(!out.to_lowercase().contains(&hex));
+            assert!(!out.contains(&raw));
+            assert!(!out.contains(&format!("{:?}", &secret[..4])));
+        }
+    }
+
+    #[test]
+    fn test_xchacha20_key_redacted() {
+        assert_redacted(&XChaCha20Key::from_bytes(&pattern::<32>()).unwrap());
+    }
+
+    #[test]
+    fn test_derived_key_redacted() {
+        assert_redacted(&DerivedKey(pattern::<48>().to_vec()));
+    }
+
+    #[test]
+    fn test_kyber_secret_key_redacted() {
+        assert_redacted(&KyberSecretKeyBytes::from_bytes(&pattern::<3168>()).unwrap());
+    }
+
+    #[test]
+    fn test_kyber_shared_secret_redacted() {
+        assert_redacted(&KyberSharedSecret::from_bytes(&pattern::<32>()).unwrap());
+    }
+
+    #[test]
+    fn test_x25519_secret_key_redacted() {
+        assert_redacted(&X25519SecretKeyBytes::from_bytes(&pattern::<32>()).unwrap());
+    }
+
+    #[test]
+    fn test_ecdh_shared_secret_redacted() {
+        assert_redacted(&EcdhSharedSecret::from_bytes(&pattern::<32>()).unwrap());
+    }
+
+    #[test]
+    fn test_hybrid_shared_secret_redacted() {
+        let secret = HybridSharedSecret {
+            kyber_secret: KyberSharedSecret::from_bytes(&pattern::<32>()).unwrap(),
+            x25519_secret: EcdhSharedSecret::from_bytes(&pattern::<32>()).unwrap(),
+            combined: pattern::<64>(),
+        };
+        assert_redacted(&secret);
+        assert!(!format!("{:?}", secret).contains("KyberSharedSecret"));
+    }
+
+    #[test]
+    fn test_master_seed_redacted() {
+        assert_redacted(&MasterSeed::from_bytes(pattern::<64>()));
+    }
+
+    #[test]
+    fn test_identity_key_redacted() {
+        assert_redacted(&IdentityKey::from_bytes(pattern::<32>()));
+    }
+
+    #[test]
+    fn test_recovery_key_redacted() {
+        assert_redacted(&RecoveryKey::from_bytes(pattern::<32>()));
+    }
+
+    #[test]
+    fn test_data_encryption_key_redacted() {
+        assert_redacted(&DataEncryptionKey::from_bytes(pattern::<32>()));
+    }
+
+    #[test]
+    fn test_vault_key_redacted() {
+        assert_redacted(&VaultKey::from_bytes(pattern::<32>()));
+    }
+}
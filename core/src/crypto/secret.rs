@@ -0,0 +1,229 @@
+//! # Secret Bytes
+//!
+//! Fixed-size secret buffer for key newtypes with a private field
+//! (`XChaCha20Key`, session secrets in `sync::handshake`, ...).
+//!
+//! ## Guarantees
+//!
+//! - Zeroized on drop
+//! - Construction from a slice is length-checked
+//! - `Debug` prints only a fixed `[REDACTED]` marker, independent of the
+//!   contents
+//! - Equality is constant-time ([`SecretBytes::ct_eq`]); `PartialEq` is
+//!   deliberately not implemented
+//!
+//! Newtypes wrap a `SecretBytes<N>` and expose their own
+//! `from_bytes`/`as_bytes`; the in-memory layout is a plain `[u8; N]`, so
+//! nothing that serializes the raw bytes changes. Types whose tuple field
+//! is public (`KyberSharedSecret`, `IdentityKey`, ...) keep their
+//! `pub [u8; N]` field and share only the accessors and redaction.
+
+use crate::crypto::ct::ct_eq;
+use crate::crypto::error::{CryptoError, Result};
+use rand::RngCore;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// `N` bytes of secret material, zeroized on drop
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::crypto::secret::SecretBytes;
+///
+/// let key = SecretBytes::<32>::from_slice(&[7u8; 32]).unwrap();
+/// assert_eq!(key.as_bytes(), &[7u8; 32]);
+/// assert_eq!(format!("{:?}", key), "SecretBytes<32>([REDACTED])");
+/// assert!(SecretBytes::<32>::from_slice(&[7u8; 16]).is_err());
+/// ```
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+#[repr(transparent)]
+pub struct SecretBytes<const N: usize>([u8; N]);
+
+impl<const N: usize> SecretBytes<N> {
+    /// Length in bytes
+    pub const LEN: usize = N;
+
+    /// Take ownership of `bytes`
+    pub const fn new(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+
+    /// Copy `bytes`, which must be exactly `N` bytes long
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InvalidKeyLength` if `bytes.len() != N`.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != N {
+            return Err(CryptoError::InvalidKeyLength {
+                expected: N,
+                actual: bytes.len(),
+            });
+        }
+        let mut secret = Self([0u8; N]);
+        secret.0.copy_from_slice(bytes);
+        Ok(secret)
+    }
+
+    /// Fill from the system CSPRNG
+    pub fn random() -> Self {
+        let mut secret = Self([0u8; N]);
+        rand::rngs::OsRng.fill_bytes(&mut secret.0);
+        secret
+    }
+
+    /// Borrow the raw bytes
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    /// Constant-time equality
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq(&self.0, &other.0)
+    }
+
+    /// Write `name([REDACTED])`, for the `Debug` impl of a wrapping type
+    pub fn fmt_redacted(&self, name: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}([REDACTED])", name)
+    }
+}
+
+impl<const N: usize> fmt::Debug for SecretBytes<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_redacted(&format!("SecretBytes<{}>", N), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::aead::XChaCha20Key;
+    use crate::crypto::ecdh::{EcdhSharedSecret, X25519SecretKeyBytes};
+    use crate::crypto::kem::KyberSharedSecret;
+    use crate::models::key_hierarchy::{DataEncryptionKey, IdentityKey, RecoveryKey, VaultKey};
+
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+
+    #[test]
+    fn test_from_slice_checks_length() {
+        assert!(SecretBytes::<32>::from_slice(&[1u8; 32]).is_ok());
+        assert!(matches!(
+            SecretBytes::<32>::from_slice(&[1u8; 31]),
+            Err(CryptoError::InvalidKeyLength {
+                expected: 32,
+                actual: 31
+            })
+        ));
+        assert_eq!(SecretBytes::<24>::LEN, 24);
+    }
+
+    #[test]
+    fn test_zeroize_clears_bytes() {
+        let mut secret = SecretBytes::new([0xAB; 32]);
+        secret.zeroize();
+        assert_eq!(secret.as_bytes(), &[0u8; 32]);
+    }
+
+    #[test]
+    fn test_random_and_ct_eq() {
+        let a = SecretBytes::<32>::random();
+        let b = SecretBytes::<32>::random();
+        assert!(a.ct_eq(&a.clone()));
+        assert!(!a.ct_eq(&b));
+    }
+
+    #[test]
+    fn test_debug_is_independent_of_contents() {
+        let zeros = format!("{:?}", SecretBytes::new([0u8; 16]));
+        let ones = format!("{:?}", SecretBytes::new([0xFFu8; 16]));
+        assert_eq!(zeros, "SecretBytes<16>([REDACTED])");
+        assert_eq!(zeros, ones);
+    }
+
+    #[test]
+    fn test_key_types_zeroize() {
+        assert_zeroize_on_drop::<XChaCha20Key>();
+        assert_zeroize_on_drop::<KyberSharedSecret>();
+        assert_zeroize_on_drop::<X25519SecretKeyBytes>();
+        assert_zeroize_on_drop::<EcdhSharedSecret>();
+        assert_zeroize_on_drop::<IdentityKey>();
+        assert_zeroize_on_drop::<RecoveryKey>();
+        assert_zeroize_on_drop::<DataEncryptionKey>();
+        assert_zeroize_on_drop::<VaultKey>();
+
+        let bytes = [0x5Au8; 32];
+        let mut key = XChaCha20Key::from_bytes(&bytes).unwrap();
+        key.zeroize();
+        assert_eq!(key.as_bytes(), &[0u8; 32]);
+
+        let mut kyber = KyberSharedSecret::from_bytes(&bytes).unwrap();
+        kyber.zeroize();
+        assert_eq!(kyber.as_bytes(), &[0u8; 32]);
+
+        let mut x25519 = X25519SecretKeyBytes::from_bytes(&bytes).unwrap();
+        x25519.zeroize();
+        assert_eq!(x25519.as_bytes(), &[0u8; 32]);
+
+        let mut ecdh = EcdhSharedSecret::from_bytes(&bytes).unwrap();
+        ecdh.zeroize();
+        assert_eq!(ecdh.as_bytes(), &[0u8; 32]);
+
+        let mut identity = IdentityKey::from_bytes(bytes);
+        identity.zeroize();
+        assert_eq!(identity.as_bytes(), &[0u8; 32]);
+
+        let mut recovery = RecoveryKey::from_bytes(bytes);
+        recovery.zeroize();
+        assert_eq!(recovery.as_bytes(), &[0u8; 32]);
+
+        let mut dek = DataEncryptionKey::from_bytes(bytes);
+        dek.zeroize();
+        assert_eq!(dek.as_bytes(), &[0u8; 32]);
+
+        let mut vk = VaultKey::from_bytes(bytes);
+        vk.zeroize();
+        assert_eq!(vk.as_bytes(), &[0u8; 32]);
+    }
+
+    #[test]
+    fn test_key_types_redact_debug() {
+        let bytes = [0x5Au8; 32];
+        let cases = [
+            (
+                format!("{:?}", XChaCha20Key::from_bytes(&bytes).unwrap()),
+                "XChaCha20Key",
+            ),
+            (
+                format!("{:?}", KyberSharedSecret::from_bytes(&bytes).unwrap()),
+                "KyberSharedSecret",
+            ),
+            (
+                format!("{:?}", X25519SecretKeyBytes::from_bytes(&bytes).unwrap()),
+                "X25519SecretKeyBytes",
+            ),
+            (
+                format!("{:?}", EcdhSharedSecret::from_bytes(&bytes).unwrap()),
+                "EcdhSharedSecret",
+            ),
+            (
+                format!("{:?}", IdentityKey::from_bytes(bytes)),
+                "IdentityKey",
+            ),
+            (
+                format!("{:?}", RecoveryKey::from_bytes(bytes)),
+                "RecoveryKey",
+            ),
+            (
+                format!("{:?}", DataEncryptionKey::from_bytes(bytes)),
+                "DataEncryptionKey",
+            ),
+            (format!("{:?}", VaultKey::from_bytes(bytes)), "VaultKey"),
+        ];
+
+        for (debug, name) in cases {
+            assert_eq!(debug, format!("{}([REDACTED; 32 bytes])", name));
+            assert!(!debug.to_lowercase().contains("5a"));
+        }
+    }
+}
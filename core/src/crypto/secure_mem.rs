@@ -0,0 +1,443 @@
+//! # Secure Memory
+//!
+//! Page-locked buffers for long-lived key material.
+//!
+//! ## Security Properties
+//!
+//! - Memory is locked with `mlock` (unix) or `VirtualLock` (Windows) so the
+//!   kernel never writes it to swap
+//! - Contents are zeroized before the pages are unlocked and freed
+//! - Debug output never shows the contents
+//!
+//! ## Graceful Degradation
+//!
+//! Locking can fail, most commonly when `RLIMIT_MEMLOCK` is exhausted. The
+//! buffer is still returned and zeroized on drop; [`LockedBuffer::is_locked`]
+//! reports whether the lock took effect. On platforms without a lock
+//! primitive the buffer is always unlocked and a warning is logged.
+//!
+//! ## Which Keys Are Locked
+//!
+//! Long-lived key material (`MasterSeed`, `KyberSecretKeyBytes`, the
+//! session vault key) always lives in a [`LockedBuffer`]. Short-lived
+//! symmetric keys (`XChaCha20Key`) are created per message and per stream;
+//! giving each one a locked page would quickly exhaust `RLIMIT_MEMLOCK`, so
+//! they are only backed by [`LockedBytes`] with the `mlock` feature enabled.
+
+use crate::crypto::ct::ct_eq;
+use crate::crypto::error::{CryptoError, Result};
+use rand::RngCore;
+use std::alloc::{self, Layout};
+use std::fmt;
+use std::ptr::NonNull;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Allocation alignment and granularity
+///
+/// Locking works on whole pages; page-aligning each buffer keeps unrelated
+/// heap data out of the locked pages.
+pub const PAGE_SIZE: usize = 4096;
+
+/// Heap buffer held in locked memory
+///
+/// The allocation is rounded up to a multiple of [`PAGE_SIZE`]; only the
+/// first `len` bytes are exposed.
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::crypto::secure_mem::LockedBuffer;
+///
+/// let mut buf = LockedBuffer::new(32).unwrap();
+/// buf.as_mut_slice().copy_from_slice(&[7u8; 32]);
+/// assert_eq!(buf.as_slice(), &[7u8; 32]);
+/// ```
+pub struct LockedBuffer {
+    ptr: NonNull<u8>,
+    len: usize,
+    capacity: usize,
+    locked: bool,
+}
+
+// SAFETY: LockedBuffer uniquely owns its allocation, like Box<[u8]>
+unsafe impl Send for LockedBuffer {}
+// SAFETY: shared access only hands out `&[u8]`
+unsafe impl Sync for LockedBuffer {}
+
+impl LockedBuffer {
+    /// Allocate a zero-filled buffer of `len` bytes and try to lock it
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InternalError` if the size overflows or the
+    /// allocation fails. Failing to lock the memory is not an error; check
+    /// [`is_locked`](Self::is_locked).
+    pub fn new(len: usize) -> Result<Self> {
+        let layout = len
+            .max(1)
+            .checked_next_multiple_of(PAGE_SIZE)
+            .and_then(|capacity| Layout::from_size_align(capacity, PAGE_SIZE).ok())
+            .ok_or_else(|| {
+                CryptoError::internal(format!("Locked buffer size too large: {} bytes", len))
+            })?;
+
+        // SAFETY: layout has a non-zero size
+        let raw = unsafe { alloc::alloc_zeroed(layout) };
+        let ptr = NonNull::new(raw).ok_or_else(|| {
+            CryptoError::internal(format!(
+                "Locked buffer allocation failed: {} bytes",
+                layout.size()
+            ))
+        })?;
+
+        let locked = lock_pages(ptr.as_ptr(), layout.size());
+        if !locked {
+            eprintln!(
+                "[SecureMem] Could not lock {} bytes; key material may be swapped",
+                layout.size()
+            );
+        }
+
+        Ok(Self {
+            ptr,
+            len,
+            capacity: layout.size(),
+            locked,
+        })
+    }
+
+    /// Allocate a locked buffer holding a copy of `bytes`
+    ///
+    /// # Errors
+    ///
+    /// See [`new`](Self::new).
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        let mut buf = Self::new(bytes.len())?;
+        buf.as_mut_slice().copy_from_slice(bytes);
+        Ok(buf)
+    }
+
+    /// Number of usable bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the buffer holds no bytes
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the pages are locked in memory
+    ///
+    /// `false` means the buffer works normally but may be swapped to disk.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Borrow the contents
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: ptr is valid for `capacity >= len` initialized bytes
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Mutably borrow the contents
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: ptr is valid for `capacity >= len` initialized bytes and
+        // uniquely borrowed through `&mut self`
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Zeroize for LockedBuffer {
+    fn zeroize(&mut self) {
+        // SAFETY: ptr is valid for `capacity` initialized bytes
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.capacity) }.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for LockedBuffer {}
+
+impl Drop for LockedBuffer {
+    fn drop(&mut self) {
+        self.zeroize();
+
+        if self.locked {
+            unlock_pages(self.ptr.as_ptr(), self.capacity);
+        }
+
+        // SAFETY: allocated in `new` with this exact layout
+        unsafe {
+            alloc::dealloc(
+                self.ptr.as_ptr(),
+                Layout::from_size_align_unchecked(self.capacity, PAGE_SIZE),
+            );
+        }
+    }
+}
+
+// Secure Debug implementation (never expose contents)
+impl fmt::Debug for LockedBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LockedBuffer")
+            .field("len", &self.len)
+            .field("locked", &self.locked)
+            .field("data", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// `N` bytes of secret material in a [`LockedBuffer`]
+///
+/// Drop-in replacement for [`SecretBytes<N>`](crate::crypto::secret::SecretBytes)
+/// with the same constructors and accessors; the bytes live on their own
+/// locked page instead of inline.
+///
+/// # Panics
+///
+/// The infallible constructors panic if the page allocation fails, like
+/// `Box::new` does on out-of-memory.
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::crypto::secure_mem::LockedBytes;
+///
+/// let key = LockedBytes::<32>::from_slice(&[7u8; 32]).unwrap();
+/// assert_eq!(key.as_bytes(), &[7u8; 32]);
+/// assert!(LockedBytes::<32>::from_slice(&[7u8; 16]).is_err());
+/// ```
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct LockedBytes<const N: usize>(LockedBuffer);
+
+impl<const N: usize> LockedBytes<N> {
+    /// Length in bytes
+    pub const LEN: usize = N;
+
+    /// Copy `bytes` into a locked page
+    pub fn new(bytes: [u8; N]) -> Self {
+        let mut bytes = bytes;
+        let secret = Self(LockedBuffer::from_slice(&bytes).expect("locked page allocation failed"));
+        bytes.zeroize();
+        secret
+    }
+
+    /// Copy `bytes`, which must be exactly `N` bytes long
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InvalidKeyLength` if `bytes.len() != N`, or
+    /// `CryptoError::InternalError` if the allocation fails.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != N {
+            return Err(CryptoError::InvalidKeyLength {
+                expected: N,
+                actual: bytes.len(),
+            });
+        }
+        LockedBuffer::from_slice(bytes).map(Self)
+    }
+
+    /// Fill from the system CSPRNG
+    pub fn random() -> Self {
+        let mut buf = LockedBuffer::new(N).expect("locked page allocation failed");
+        rand::rngs::OsRng.fill_bytes(buf.as_mut_slice());
+        Self(buf)
+    }
+
+    /// Borrow the raw bytes
+    pub fn as_bytes(&self) -> &[u8; N] {
+        self.0
+            .as_slice()
+            .try_into()
+            .expect("LockedBytes always holds N bytes")
+    }
+
+    /// Whether the page is locked in memory
+    pub fn is_locked(&self) -> bool {
+        self.0.is_locked()
+    }
+
+    /// Constant-time equality
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq(self.as_bytes(), other.as_bytes())
+    }
+
+    /// Write `name([REDACTED])`, for the `Debug` impl of a wrapping type
+    pub fn fmt_redacted(&self, name: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}([REDACTED])", name)
+    }
+}
+
+impl<const N: usize> Clone for LockedBytes<N> {
+    fn clone(&self) -> Self {
+        Self(LockedBuffer::from_slice(self.0.as_slice()).expect("locked page allocation failed"))
+    }
+}
+
+impl<const N: usize> fmt::Debug for LockedBytes<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_redacted(&format!("LockedBytes<{}>", N), f)
+    }
+}
+
+// ============================================================================
+// Platform Lock Primitives
+// ============================================================================
+
+#[cfg(unix)]
+extern "C" {
+    fn mlock(addr: *const std::ffi::c_void, len: usize) -> std::ffi::c_int;
+    fn munlock(addr: *const std::ffi::c_void, len: usize) -> std::ffi::c_int;
+}
+
+/// Lock `len` bytes at `ptr`; returns `false` if the kernel refused
+#[cfg(unix)]
+fn lock_pages(ptr: *mut u8, len: usize) -> bool {
+    // SAFETY: mlock only inspects the address range; failure is reported
+    unsafe { mlock(ptr.cast(), len) == 0 }
+}
+
+#[cfg(unix)]
+fn unlock_pages(ptr: *mut u8, len: usize) {
+    // SAFETY: range was locked by `lock_pages`
+    unsafe {
+        munlock(ptr.cast(), len);
+    }
+}
+
+#[cfg(windows)]
+#[link(name = "kernel32")]
+extern "system" {
+    fn VirtualLock(addr: *mut std::ffi::c_void, size: usize) -> i32;
+    fn VirtualUnlock(addr: *mut std::ffi::c_void, size: usize) -> i32;
+}
+
+/// Lock `len` bytes at `ptr`; returns `false` if the working set is too small
+#[cfg(windows)]
+fn lock_pages(ptr: *mut u8, len: usize) -> bool {
+    // SAFETY: VirtualLock only inspects the address range; failure is reported
+    unsafe { VirtualLock(ptr.cast(), len) != 0 }
+}
+
+#[cfg(windows)]
+fn unlock_pages(ptr: *mut u8, len: usize) {
+    // SAFETY: range was locked by `lock_pages`
+    unsafe {
+        VirtualUnlock(ptr.cast(), len);
+    }
+}
+
+/// No lock primitive on this platform
+#[cfg(not(any(unix, windows)))]
+fn lock_pages(_ptr: *mut u8, _len: usize) -> bool {
+    false
+}
+
+#[cfg(not(any(unix, windows)))]
+fn unlock_pages(_ptr: *mut u8, _len: usize) {}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocation_drop_roundtrip() {
+        for len in [0, 1, 64, PAGE_SIZE, PAGE_SIZE + 1, 3168] {
+            let buf = LockedBuffer::new(len).unwrap();
+            assert_eq!(buf.len(), len);
+            assert_eq!(buf.is_empty(), len == 0);
+            assert!(buf.as_slice().iter().all(|&b| b == 0));
+            assert_eq!(buf.ptr.as_ptr() as usize % PAGE_SIZE, 0);
+            drop(buf);
+        }
+    }
+
+    #[test]
+    fn test_contents_survive_lock() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut buf = LockedBuffer::from_slice(&data).unwrap();
+        assert_eq!(buf.as_slice(), &data[..]);
+
+        buf.as_mut_slice()[0] = 0xAA;
+        assert_eq!(buf.as_slice()[0], 0xAA);
+        assert_eq!(&buf.as_slice()[1..], &data[1..]);
+    }
+
+    #[test]
+    fn test_zeroize_clears_contents() {
+        let mut buf = LockedBuffer::from_slice(&[0x42; 64]).unwrap();
+        buf.zeroize();
+        assert!(buf.as_slice().iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_absurd_size_degrades_to_error() {
+        assert!(LockedBuffer::new(usize::MAX).is_err());
+        assert!(LockedBuffer::new(isize::MAX as usize).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_lock_failure_reported_not_fatal() {
+        // The zero page is never mapped, so the kernel refuses the lock
+        assert!(!lock_pages(std::ptr::null_mut(), PAGE_SIZE));
+    }
+
+    #[test]
+    fn test_locked_bytes_matches_secret_bytes() {
+        let locked = LockedBytes::<32>::from_slice(&[0x5A; 32]).unwrap();
+        assert_eq!(locked.as_bytes(), &[0x5A; 32]);
+        assert!(locked.ct_eq(&locked.clone()));
+        assert!(!locked.ct_eq(&LockedBytes::random()));
+        assert_eq!(format!("{:?}", locked), "LockedBytes<32>([REDACTED])");
+        assert!(matches!(
+            LockedBytes::<32>::from_slice(&[0u8; 31]),
+            Err(CryptoError::InvalidKeyLength {
+                expected: 32,
+                actual: 31
+            })
+        ));
+
+        let mut locked = LockedBytes::new([0xAB; 24]);
+        locked.zeroize();
+        assert_eq!(locked.as_bytes(), &[0u8; 24]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_locked_pages_are_resident() {
+        extern "C" {
+            fn mincore(addr: *mut std::ffi::c_void, len: usize, vec: *mut u8) -> i32;
+        }
+
+        let mut buf = LockedBuffer::from_slice(&[0x42; 64]).unwrap();
+        if !buf.is_locked() {
+            // Best effort: RLIMIT_MEMLOCK may be exhausted in this sandbox
+            return;
+        }
+
+        let mut residency = [0u8; 1];
+        // SAFETY: the range is one mapped, page-aligned page and
+        // `residency` holds one entry per page
+        let rc = unsafe { mincore(buf.ptr.as_ptr().cast(), PAGE_SIZE, residency.as_mut_ptr()) };
+        assert_eq!(rc, 0);
+        assert_eq!(residency[0] & 1, 1);
+
+        buf.zeroize();
+        assert!(buf.as_slice().iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_debug_never_prints_contents() {
+        let buf = LockedBuffer::from_slice(b"super secret key material").unwrap();
+        let debug_str = format!("{:?}", buf);
+
+        assert!(debug_str.contains("[REDACTED]"));
+        assert!(!debug_str.contains("secret"));
+        assert!(!debug_str.contains("115")); // 's' as a byte
+    }
+}
@@ -0,0 +1,139 @@
+//! # Key Wrapping
+//!
+//! Opaque extra protection layer for key material at rest.
+//!
+//! A [`KeyWrapper`] turns secret bytes into a blob only it can turn back.
+//! On Android the bridge implements it with a key held in the hardware
+//! keystore (StrongBox), so a copy of the app's files is useless without
+//! the device; [`AeadKeyWrapper`] is the software implementation for tests
+//! and platforms without a keystore.
+//!
+//! Wrappers are applied on top of the storage format, never instead of
+//! it: callers still authenticate what they read back, and a wrapper
+//! failure is reported as `CryptoError::KeyWrapError`, never a panic.
+
+use crate::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce, NONCE_SIZE};
+use crate::crypto::error::{CryptoError, Result};
+
+/// Associated data binding [`AeadKeyWrapper`] blobs to their purpose
+const AEAD_WRAP_AAD: &[u8] = b"aeternum-key-wrap-v1";
+
+/// Wraps and unwraps secret key material
+///
+/// Implementations must be deterministic in what they accept back:
+/// `unwrap(wrap(x)) == x`, and `unwrap` of anything else fails.
+pub trait KeyWrapper: Send + Sync {
+    /// Wrap `plaintext` into an opaque blob
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::KeyWrapError` if the wrapping key is
+    /// unavailable or refuses the operation.
+    fn wrap(&self, plaintext: &[u8]) -> Result<Vec<u8>>;
+
+    /// Recover the plaintext from a blob produced by [`wrap`](Self::wrap)
+    ///
+    /// The caller owns the returned secret and must zeroize it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::KeyWrapError` if `wrapped` was not produced by
+    /// this wrapper or has been modified.
+    fn unwrap(&self, wrapped: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Software [`KeyWrapper`] using XChaCha20-Poly1305
+///
+/// Blob layout: `nonce (24 B) || ciphertext || tag (16 B)`.
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::crypto::wrap::{AeadKeyWrapper, KeyWrapper};
+///
+/// let wrapper = AeadKeyWrapper::generate();
+/// let wrapped = wrapper.wrap(b"secret").unwrap();
+/// assert_eq!(wrapper.unwrap(&wrapped).unwrap(), b"secret");
+/// assert!(AeadKeyWrapper::generate().unwrap(&wrapped).is_err());
+/// ```
+pub struct AeadKeyWrapper {
+    cipher: AeadCipher,
+}
+
+impl AeadKeyWrapper {
+    /// Wrapper using `key`
+    pub fn new(key: &XChaCha20Key) -> Self {
+        Self {
+            cipher: AeadCipher::new(key),
+        }
+    }
+
+    /// Wrapper using a fresh random key
+    pub fn generate() -> Self {
+        Self::new(&XChaCha20Key::generate())
+    }
+}
+
+impl KeyWrapper for AeadKeyWrapper {
+    fn wrap(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = XChaCha20Nonce::random();
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext, Some(AEAD_WRAP_AAD))
+            .map_err(|e| CryptoError::KeyWrapError(e.to_string()))?;
+
+        let mut wrapped = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+        wrapped.extend_from_slice(nonce.as_bytes());
+        wrapped.extend_from_slice(&ciphertext);
+        Ok(wrapped)
+    }
+
+    fn unwrap(&self, wrapped: &[u8]) -> Result<Vec<u8>> {
+        if wrapped.len() < NONCE_SIZE {
+            return Err(CryptoError::KeyWrapError(format!(
+                "wrapped blob is {} bytes, shorter than its nonce",
+                wrapped.len()
+            )));
+        }
+        let (nonce, ciphertext) = wrapped.split_at(NONCE_SIZE);
+        let nonce = XChaCha20Nonce::try_from_slice(nonce)?;
+        self.cipher
+            .decrypt(&nonce, ciphertext, Some(AEAD_WRAP_AAD))
+            .map_err(|_| CryptoError::KeyWrapError("wrapped blob did not authenticate".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let wrapper = AeadKeyWrapper::new(&XChaCha20Key::from_bytes(&[9u8; 32]).unwrap());
+        let wrapped = wrapper.wrap(&[0x42; 3168]).unwrap();
+        assert_eq!(wrapped.len(), NONCE_SIZE + 3168 + 16);
+        assert_eq!(wrapper.unwrap(&wrapped).unwrap(), vec![0x42; 3168]);
+    }
+
+    #[test]
+    fn test_wrap_is_randomized() {
+        let wrapper = AeadKeyWrapper::generate();
+        assert_ne!(wrapper.wrap(b"k").unwrap(), wrapper.wrap(b"k").unwrap());
+    }
+
+    #[test]
+    fn test_unwrap_rejects_tampered_and_truncated() {
+        let wrapper = AeadKeyWrapper::generate();
+        let mut wrapped = wrapper.wrap(b"secret").unwrap();
+        let last = wrapped.len() - 1;
+        wrapped[last] ^= 0x01;
+        assert!(matches!(
+            wrapper.unwrap(&wrapped),
+            Err(CryptoError::KeyWrapError(_))
+        ));
+        assert!(matches!(
+            wrapper.unwrap(&wrapped[..10]),
+            Err(CryptoError::KeyWrapError(_))
+        ));
+    }
+}
@@ -20,7 +20,8 @@
 //! ## Safety Guarantees
 //!
 //! - All secret keys are automatically zeroized on drop
-//! - Memory is locked where possible (mlock support)
+//! - Long-lived key material is held in locked memory where possible
+//!   (`crypto::secure_mem`; the `mlock` feature extends this to session keys)
 //! - Constant-time operations for secret data
 
 #![warn(missing_docs)]
@@ -145,7 +146,7 @@ mod tests {
         let _ = models::CryptoEpoch::new(1, CryptoAlgorithm::V1);
 
         // key_hierarchy 子模块
-        let _ = models::key_hierarchy::MasterSeed([0u8; 64]);
+        let _ = models::key_hierarchy::MasterSeed::from_bytes([0u8; 64]);
 
         // epoch 子模块
         let _ = models::epoch::CryptoAlgorithm::V1;
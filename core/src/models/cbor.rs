@@ -0,0 +1,192 @@
+//! # CBOR Encoding
+//!
+//! Self-describing alternative to the bincode encoding of stored models
+//! (enabled with the `cbor` feature).
+//!
+//! bincode is compact but carries no field names or types, so a stored
+//! [`DeviceHeader`](super::DeviceHeader) or [`VaultBlob`](super::VaultBlob)
+//! cannot be inspected with generic tools or read from another language
+//! without re-implementing the Rust layout. The CBOR form (RFC 8949) can.
+//!
+//! ## Envelope
+//!
+//! Every value is wrapped in a map that names the schema it was written
+//! with:
+//!
+//! ```text
+//! { "schema_version": 1, "kind": "DeviceHeader", "data": { ... } }
+//! ```
+//!
+//! Decoding rejects a different `kind` and any schema version newer than
+//! [`CBOR_SCHEMA_VERSION`].
+
+use crate::crypto::error::{CryptoError, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Schema version written into every CBOR envelope
+pub const CBOR_SCHEMA_VERSION: u32 = 1;
+
+/// Envelope as written
+#[derive(Serialize)]
+struct EnvelopeRef<'a, T> {
+    schema_version: u32,
+    kind: &'a str,
+    data: &'a T,
+}
+
+/// Envelope as read
+#[derive(Deserialize)]
+struct Envelope<T> {
+    schema_version: u32,
+    kind: String,
+    data: T,
+}
+
+/// Encode `value` as a CBOR envelope of type `kind`
+pub(crate) fn encode<T: Serialize>(kind: &str, value: &T) -> Result<Vec<u8>> {
+    let envelope = EnvelopeRef {
+        schema_version: CBOR_SCHEMA_VERSION,
+        kind,
+        data: value,
+    };
+
+    let mut out = Vec::new();
+    ciborium::ser::into_writer(&envelope, &mut out)
+        .map_err(|e| CryptoError::InternalError(format!("{} CBOR encoding failed: {}", kind, e)))?;
+    Ok(out)
+}
+
+/// Decode a CBOR envelope of type `kind`
+///
+/// # Errors
+///
+/// Returns `CryptoError::InternalError` if the bytes are not valid CBOR,
+/// the envelope holds another kind, or it was written with a newer schema.
+pub(crate) fn decode<T: DeserializeOwned>(kind: &str, bytes: &[u8]) -> Result<T> {
+    let envelope: Envelope<T> = ciborium::de::from_reader(bytes)
+        .map_err(|e| CryptoError::InternalError(format!("{} CBOR decoding failed: {}", kind, e)))?;
+
+    if envelope.kind != kind {
+        return Err(CryptoError::InternalError(format!(
+            "CBOR envelope holds {}, expected {}",
+            envelope.kind, kind
+        )));
+    }
+    if envelope.schema_version > CBOR_SCHEMA_VERSION {
+        return Err(CryptoError::InternalError(format!(
+            "Unsupported {} CBOR schema version {} (newest supported: {})",
+            kind, envelope.schema_version, CBOR_SCHEMA_VERSION
+        )));
+    }
+
+    Ok(envelope.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::kem::{KyberCipherText, KyberPublicKeyBytes};
+    use crate::models::epoch::CryptoEpoch;
+    use crate::models::{DegradeReason, DeviceHeader, DeviceId, VaultBlob};
+    use ciborium::value::Value;
+
+    fn sample_header() -> DeviceHeader {
+        let mut header = DeviceHeader::new(
+            DeviceId::from_bytes([0x42; 16]),
+            CryptoEpoch::initial(),
+            KyberPublicKeyBytes([0x11; 1568]),
+            KyberCipherText([0x22; 1568]),
+        );
+        header.dek_nonce = [0x33; 24];
+        header.dek_ciphertext = vec![0x44; 48];
+        header
+            .degrade(DegradeReason::IntegrityCheckFailed, 1_000)
+            .unwrap();
+        header
+    }
+
+    fn sample_blob() -> VaultBlob {
+        VaultBlob::new(
+            VaultBlob::CURRENT_BLOB_VERSION,
+            CryptoEpoch::initial(),
+            vec![0x55; 100],
+            [0x66; 16],
+            [0x77; 24],
+        )
+    }
+
+    /// Look up a text key in a CBOR map
+    fn field<'a>(value: &'a Value, key: &str) -> &'a Value {
+        value
+            .as_map()
+            .unwrap()
+            .iter()
+            .find(|(k, _)| k.as_text() == Some(key))
+            .map(|(_, v)| v)
+            .unwrap_or_else(|| panic!("missing field {}", key))
+    }
+
+    #[test]
+    fn test_device_header_cbor_matches_bincode() {
+        let header = sample_header();
+
+        let from_cbor = DeviceHeader::from_cbor(&header.to_cbor().unwrap()).unwrap();
+        let from_bincode = DeviceHeader::deserialize(&header.serialize()).unwrap();
+        assert_eq!(from_cbor, from_bincode);
+        assert_eq!(from_cbor, header);
+    }
+
+    #[test]
+    fn test_vault_blob_cbor_matches_bincode() {
+        let blob = sample_blob();
+
+        let from_cbor = VaultBlob::from_cbor(&blob.to_cbor().unwrap()).unwrap();
+        let from_bincode = VaultBlob::deserialize(&blob.serialize().unwrap()).unwrap();
+        assert_eq!(from_cbor.blob_version, from_bincode.blob_version);
+        assert_eq!(from_cbor.epoch, from_bincode.epoch);
+        assert_eq!(from_cbor.ciphertext, from_bincode.ciphertext);
+        assert_eq!(from_cbor.auth_tag, from_bincode.auth_tag);
+        assert_eq!(from_cbor.nonce, from_bincode.nonce);
+    }
+
+    #[test]
+    fn test_cbor_envelope_is_self_describing() {
+        let bytes = sample_blob().to_cbor().unwrap();
+        let value: Value = ciborium::de::from_reader(bytes.as_slice()).unwrap();
+
+        assert_eq!(
+            field(&value, "schema_version").as_integer(),
+            Some(CBOR_SCHEMA_VERSION.into())
+        );
+        assert_eq!(field(&value, "kind").as_text(), Some("VaultBlob"));
+        // Byte fields are CBOR byte strings, not arrays of integers
+        let data = field(&value, "data");
+        assert_eq!(
+            field(data, "ciphertext").as_bytes().map(Vec::len),
+            Some(100)
+        );
+    }
+
+    #[test]
+    fn test_cbor_rejects_wrong_kind_and_newer_schema() {
+        let blob_bytes = sample_blob().to_cbor().unwrap();
+        assert!(DeviceHeader::from_cbor(&blob_bytes).is_err());
+
+        let newer = encode_with_version("VaultBlob", &sample_blob(), CBOR_SCHEMA_VERSION + 1);
+        assert!(VaultBlob::from_cbor(&newer).is_err());
+
+        assert!(VaultBlob::from_cbor(&[0xff, 0x00]).is_err());
+    }
+
+    fn encode_with_version<T: Serialize>(kind: &str, value: &T, version: u32) -> Vec<u8> {
+        let envelope = EnvelopeRef {
+            schema_version: version,
+            kind,
+            data: value,
+        };
+        let mut out = Vec::new();
+        ciborium::ser::into_writer(&envelope, &mut out).unwrap();
+        out
+    }
+}
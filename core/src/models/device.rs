@@ -5,9 +5,11 @@
 //!
 //! ## Components
 //!
-//! - `DeviceId`: 16-byte UUID for device identification
+//! - `DeviceId`: 16-byte RFC 4122 UUID for device identification
 //! - `DeviceStatus`: Device state (Active/Revoked/Degraded)
 //! - `DeviceHeader`: Encrypted metadata stored server-side
+//! - `canonical_serialize` / `headers_digest`: Order-independent encoding
+//!   and digest of a device header set
 //!
 //! ## Shadow Anchor (Device_0)
 //!
@@ -15,10 +17,17 @@
 //! cold anchor, making it indistinguishable from regular devices
 //! in the server's view. This preserves privacy by preventing
 //! attackers from identifying which device is the recovery anchor.
+//! It is the RFC 4122 nil UUID and carries no version/variant bits.
 
+use crate::crypto::error::{CryptoError, Result};
+use crate::crypto::hash::{Blake3Hasher, HashOutput};
 use crate::crypto::kem::{KyberCipherText, KyberPublicKeyBytes};
 use crate::models::epoch::CryptoEpoch;
-use serde::{Deserialize, Serialize};
+use crate::models::key_hierarchy::WrappedDek;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::str::FromStr;
 
 // ============================================================================
 // Role & Operation Types (for Invariant #3)
@@ -107,6 +116,20 @@ impl Operation {
 ///
 /// Each device in the Aeternum system has a unique 16-byte identifier.
 /// Device_0 (shadow anchor) uses all zeros as a fixed identifier.
+///
+/// Displays as a lowercase dashed UUID (`8-4-4-4-12`), the form used by
+/// the Android side and server logs. [`DeviceId::parse`] (and `FromStr`)
+/// accepts that form or 32 bare hex digits, in any case.
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::models::DeviceId;
+///
+/// let id: DeviceId = "0123456789ABCDEF0123456789abcdef".parse().unwrap();
+/// assert_eq!(id.to_string(), "01234567-89ab-cdef-0123-456789abcdef");
+/// assert_eq!(DeviceId::parse(&id.to_uuid_string()).unwrap(), id);
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DeviceId(pub [u8; 16]);
 
@@ -135,6 +158,9 @@ impl DeviceId {
     /// Uses the operating system's cryptographically secure random number
     /// generator to ensure uniqueness and unpredictability.
     ///
+    /// The result is an RFC 4122 version-4 UUID (version nibble `4`,
+    /// variant bits `10`), so it is never the all-zero shadow anchor.
+    ///
     /// # Example
     ///
     /// ```
@@ -146,9 +172,69 @@ impl DeviceId {
     pub fn generate() -> Self {
         let mut bytes = [0u8; 16];
         getrandom::getrandom(&mut bytes).expect("CSPRNG failure");
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
         Self(bytes)
     }
 
+    /// Parse a dashed UUID or 32 bare hex digits (case-insensitive)
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InvalidDeviceId` if the string has the wrong
+    /// length, misplaced dashes, or non-hex characters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::models::DeviceId;
+    ///
+    /// let dashed = DeviceId::parse("00112233-4455-6677-8899-aabbccddeeff").unwrap();
+    /// let bare = DeviceId::parse("00112233445566778899AABBCCDDEEFF").unwrap();
+    /// assert_eq!(dashed, bare);
+    /// assert!(DeviceId::parse("not-a-uuid").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self> {
+        let hex_digits: String = match s.len() {
+            32 => s.to_string(),
+            36 => {
+                let dashes_ok = s
+                    .char_indices()
+                    .all(|(i, c)| (c == '-') == matches!(i, 8 | 13 | 18 | 23));
+                if !dashes_ok {
+                    return Err(CryptoError::InvalidDeviceId(
+                        "dashes must separate 8-4-4-4-12 groups".to_string(),
+                    ));
+                }
+                s.chars().filter(|&c| c != '-').collect()
+            }
+            len => {
+                return Err(CryptoError::InvalidDeviceId(format!(
+                    "expected 32 hex digits or 36-character UUID, got {} characters",
+                    len
+                )))
+            }
+        };
+
+        let mut bytes = [0u8; 16];
+        hex::decode_to_slice(&hex_digits, &mut bytes)
+            .map_err(|e| CryptoError::InvalidDeviceId(e.to_string()))?;
+        Ok(Self(bytes))
+    }
+
+    /// Format as a lowercase dashed UUID (`8-4-4-4-12`)
+    pub fn to_uuid_string(&self) -> String {
+        let hex = hex::encode(self.0);
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+
     /// Check if this is the shadow anchor (Device_0)
     ///
     /// Device_0 uses all-zero identifier to represent the cold anchor,
@@ -208,11 +294,23 @@ impl DeviceId {
 
 impl std::fmt::Display for DeviceId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Display as hex string
-        for byte in &self.0 {
-            write!(f, "{:02x}", byte)?;
-        }
-        Ok(())
+        f.write_str(&self.to_uuid_string())
+    }
+}
+
+impl FromStr for DeviceId {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&str> for DeviceId {
+    type Error = CryptoError;
+
+    fn try_from(s: &str) -> Result<Self> {
+        Self::parse(s)
     }
 }
 
@@ -223,7 +321,10 @@ impl std::fmt::Display for DeviceId {
 /// Device status enumeration
 ///
 /// Represents the current state of a device in the PQRR protocol.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Encoded by its stable [`as_u8`](Self::as_u8) code rather than its
+/// variant index, so reordering variants cannot change stored headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DeviceStatus {
     /// Device is active and trusted
     ///
@@ -247,9 +348,101 @@ pub enum DeviceStatus {
     /// - Have limited functionality (read-only pending verification)
     /// - May have failed Play Integrity verification
     /// - Require user intervention to restore full functionality
+    /// - Are not counted as active for header completeness (Invariant #2)
+    /// - Keep their veto right (Invariant #4)
     Degraded,
 }
 
+impl DeviceStatus {
+    /// Whether a device in this status may veto a recovery
+    ///
+    /// Active and Degraded devices may veto; only Revoked devices lose
+    /// the right (Invariant #4).
+    pub fn can_veto(&self) -> bool {
+        !matches!(self, DeviceStatus::Revoked)
+    }
+
+    /// Stable wire code of this status
+    ///
+    /// Codes 3..=255 are reserved for future statuses; a code is never
+    /// reassigned.
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            DeviceStatus::Active => 0,
+            DeviceStatus::Revoked => 1,
+            DeviceStatus::Degraded => 2,
+        }
+    }
+
+    /// Parse a status from its wire code
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InternalError` for an unassigned code.
+    pub fn from_u8(code: u8) -> Result<Self> {
+        match code {
+            0 => Ok(DeviceStatus::Active),
+            1 => Ok(DeviceStatus::Revoked),
+            2 => Ok(DeviceStatus::Degraded),
+            _ => Err(CryptoError::internal(format!(
+                "Unknown device status code: {}",
+                code
+            ))),
+        }
+    }
+}
+
+impl Serialize for DeviceStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        // A u32 keeps the bincode layout of headers written before the
+        // stable codes, whose variant indices equal the codes
+        serializer.serialize_u32(u32::from(self.as_u8()))
+    }
+}
+
+impl<'de> Deserialize<'de> for DeviceStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let code = u32::deserialize(deserializer)?;
+        u8::try_from(code)
+            .ok()
+            .and_then(|code| DeviceStatus::from_u8(code).ok())
+            .ok_or_else(|| D::Error::custom(format!("unknown device status code {}", code)))
+    }
+}
+
+/// Why a device was put into Degraded status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DegradeReason {
+    /// Platform integrity verification (e.g. Play Integrity) failed
+    IntegrityCheckFailed,
+    /// Hardware key attestation failed or expired
+    AttestationFailed,
+    /// The device's public key does not match its header
+    KeyMismatch,
+    /// Any other reason, described for the user
+    Other(String),
+}
+
+impl std::fmt::Display for DegradeReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DegradeReason::IntegrityCheckFailed => write!(f, "integrity check failed"),
+            DegradeReason::AttestationFailed => write!(f, "attestation failed"),
+            DegradeReason::KeyMismatch => write!(f, "key mismatch"),
+            DegradeReason::Other(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+/// Record of why and when a device was degraded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DegradedInfo {
+    /// Why the device was degraded
+    pub reason: DegradeReason,
+    /// When the device was degraded (Unix milliseconds)
+    pub since_ms: u64,
+}
+
 // ============================================================================
 // Device Header
 // ============================================================================
@@ -288,11 +481,61 @@ pub struct DeviceHeader {
     /// NOTE: KEM serialization will be added when VaultBlob is implemented
     pub encrypted_dek: KyberCipherText,
 
+    /// XChaCha20 nonce of `dek_ciphertext`
+    pub dek_nonce: [u8; 24],
+
+    /// DEK encrypted under the key derived from `encrypted_dek`
+    ///
+    /// Empty for headers created with only an encapsulation (see
+    /// [`wrapped_dek`](Self::wrapped_dek)).
+    #[serde(with = "serde_bytes")]
+    pub dek_ciphertext: Vec<u8>,
+
     /// Current device status
     pub status: DeviceStatus,
 
     /// Creation timestamp (Unix milliseconds)
     pub created_at: u64,
+
+    /// Why and since when the device is Degraded
+    ///
+    /// `None` unless `status` is `Degraded`. Headers written before this
+    /// field existed decode with `None`.
+    #[serde(default)]
+    pub degraded_info: Option<DegradedInfo>,
+}
+
+/// Device header layout before `degraded_info` was added
+///
+/// bincode is not self-describing, so `#[serde(default)]` cannot fill in
+/// a missing trailing field; legacy bytes are decoded with this layout.
+#[derive(Deserialize)]
+pub(crate) struct LegacyDeviceHeader {
+    device_id: DeviceId,
+    epoch: CryptoEpoch,
+    public_key: KyberPublicKeyBytes,
+    encrypted_dek: KyberCipherText,
+    dek_nonce: [u8; 24],
+    #[serde(with = "serde_bytes")]
+    dek_ciphertext: Vec<u8>,
+    status: DeviceStatus,
+    created_at: u64,
+}
+
+impl From<LegacyDeviceHeader> for DeviceHeader {
+    fn from(legacy: LegacyDeviceHeader) -> Self {
+        Self {
+            device_id: legacy.device_id,
+            epoch: legacy.epoch,
+            public_key: legacy.public_key,
+            encrypted_dek: legacy.encrypted_dek,
+            dek_nonce: legacy.dek_nonce,
+            dek_ciphertext: legacy.dek_ciphertext,
+            status: legacy.status,
+            created_at: legacy.created_at,
+            degraded_info: None,
+        }
+    }
 }
 
 impl DeviceHeader {
@@ -339,9 +582,75 @@ impl DeviceHeader {
             epoch,
             public_key,
             encrypted_dek,
+            dek_nonce: [0u8; 24],
+            dek_ciphertext: Vec::new(),
             status: DeviceStatus::Active,
             created_at: current_timestamp_ms(),
+            degraded_info: None,
+        }
+    }
+
+    /// Create a device header holding a wrapped DEK
+    ///
+    /// # Arguments
+    ///
+    /// - `device_id`: Unique device identifier
+    /// - `epoch`: Cryptographic epoch for this device
+    /// - `public_key`: Device's Kyber-1024 public key
+    /// - `wrapped`: DEK wrapped for `public_key`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::models::{DataEncryptionKey, DeviceId, DeviceHeader};
+    /// use aeternum_core::models::epoch::CryptoEpoch;
+    /// use aeternum_core::crypto::kem::KyberKEM;
+    ///
+    /// let keypair = KyberKEM::generate_keypair();
+    /// let dek = DataEncryptionKey::generate();
+    /// let wrapped = dek.wrap_for_device(&keypair.public).unwrap();
+    ///
+    /// let header = DeviceHeader::with_wrapped_dek(
+    ///     DeviceId::generate(),
+    ///     CryptoEpoch::initial(),
+    ///     keypair.public,
+    ///     wrapped,
+    /// );
+    ///
+    /// let unwrapped =
+    ///     DataEncryptionKey::unwrap(&header.wrapped_dek().unwrap(), &keypair.secret).unwrap();
+    /// assert_eq!(unwrapped.as_bytes(), dek.as_bytes());
+    /// ```
+    pub fn with_wrapped_dek(
+        device_id: DeviceId,
+        epoch: CryptoEpoch,
+        public_key: KyberPublicKeyBytes,
+        wrapped: WrappedDek,
+    ) -> Self {
+        let mut header = Self::new(device_id, epoch, public_key, wrapped.kem_ciphertext.clone());
+        header.set_wrapped_dek(wrapped);
+        header
+    }
+
+    /// Replace the DEK held by this header
+    pub fn set_wrapped_dek(&mut self, wrapped: WrappedDek) {
+        self.encrypted_dek = wrapped.kem_ciphertext;
+        self.dek_nonce = wrapped.nonce;
+        self.dek_ciphertext = wrapped.aead_ciphertext;
+    }
+
+    /// DEK wrapped for this device
+    ///
+    /// Returns `None` if the header only carries an encapsulation.
+    pub fn wrapped_dek(&self) -> Option<WrappedDek> {
+        if self.dek_ciphertext.is_empty() {
+            return None;
         }
+        Some(WrappedDek {
+            kem_ciphertext: self.encrypted_dek.clone(),
+            aead_ciphertext: self.dek_ciphertext.clone(),
+            nonce: self.dek_nonce,
+        })
     }
 
     /// Create a shadow anchor header (Device_0)
@@ -384,8 +693,11 @@ impl DeviceHeader {
             epoch,
             public_key,
             encrypted_dek,
+            dek_nonce: [0u8; 24],
+            dek_ciphertext: Vec::new(),
             status: DeviceStatus::Active,
             created_at: current_timestamp_ms(),
+            degraded_info: None,
         }
     }
 
@@ -414,6 +726,81 @@ impl DeviceHeader {
     /// ```
     pub fn revoke(&mut self) {
         self.status = DeviceStatus::Revoked;
+        self.degraded_info = None;
+    }
+
+    /// Put this device into Degraded status
+    ///
+    /// Records `reason` and `at_ms` in [`degraded_info`](Self::degraded_info).
+    /// Degrading an already Degraded device replaces the recorded reason.
+    ///
+    /// # Arguments
+    ///
+    /// - `reason`: Why the device is degraded
+    /// - `at_ms`: When it was degraded (Unix milliseconds)
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InvariantViolation` if the device is Revoked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::models::device::{DegradeReason, DeviceHeader, DeviceStatus};
+    /// use aeternum_core::models::DeviceId;
+    /// use aeternum_core::models::epoch::CryptoEpoch;
+    /// use aeternum_core::crypto::kem::KyberKEM;
+    ///
+    /// let keypair = KyberKEM::generate_keypair();
+    /// let (_ss, encrypted_dek) = KyberKEM::encapsulate(&keypair.public).unwrap();
+    /// let mut header = DeviceHeader::new(
+    ///     DeviceId::generate(),
+    ///     CryptoEpoch::initial(),
+    ///     keypair.public,
+    ///     encrypted_dek,
+    /// );
+    ///
+    /// header.degrade(DegradeReason::IntegrityCheckFailed, 1_000).unwrap();
+    /// assert_eq!(header.status, DeviceStatus::Degraded);
+    ///
+    /// header.restore().unwrap();
+    /// assert_eq!(header.status, DeviceStatus::Active);
+    /// assert!(header.degraded_info.is_none());
+    /// ```
+    pub fn degrade(&mut self, reason: DegradeReason, at_ms: u64) -> Result<()> {
+        if self.status == DeviceStatus::Revoked {
+            return Err(CryptoError::InvariantViolation(format!(
+                "cannot degrade revoked device {}",
+                self.device_id
+            )));
+        }
+        self.status = DeviceStatus::Degraded;
+        self.degraded_info = Some(DegradedInfo {
+            reason,
+            since_ms: at_ms,
+        });
+        Ok(())
+    }
+
+    /// Return a Degraded device to Active status
+    ///
+    /// Clears [`degraded_info`](Self::degraded_info).
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InvariantViolation` unless the device is
+    /// Degraded. In particular a Revoked device cannot be restored; it
+    /// must re-enroll.
+    pub fn restore(&mut self) -> Result<()> {
+        if self.status != DeviceStatus::Degraded {
+            return Err(CryptoError::InvariantViolation(format!(
+                "cannot restore device {} from {:?}",
+                self.device_id, self.status
+            )));
+        }
+        self.status = DeviceStatus::Active;
+        self.degraded_info = None;
+        Ok(())
     }
 
     /// Check if this header belongs to the given epoch
@@ -493,9 +880,10 @@ impl DeviceHeader {
     ///
     /// Deserialized `DeviceHeader`.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if deserialization fails (corrupted data).
+    /// Returns `CryptoError::InternalError` if the data is truncated or
+    /// corrupted.
     ///
     /// # Example
     ///
@@ -512,12 +900,112 @@ impl DeviceHeader {
     /// let header = DeviceHeader::new(device_id, epoch, keypair.public, encrypted_dek);
     /// let serialized = header.serialize();
     ///
-    /// let deserialized = DeviceHeader::deserialize(&serialized);
+    /// let deserialized = DeviceHeader::deserialize(&serialized).unwrap();
     /// assert_eq!(deserialized.device_id, header.device_id);
     /// ```
-    pub fn deserialize(bytes: &[u8]) -> Self {
-        bincode::deserialize(bytes).expect("DeviceHeader deserialization failed - corrupted data")
+    ///
+    /// Headers written before `degraded_info` existed are also accepted.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self> {
+        decode_with_legacy::<DeviceHeader, LegacyDeviceHeader>(bytes).map_err(|e| {
+            CryptoError::InternalError(format!("DeviceHeader deserialization failed: {}", e))
+        })
+    }
+
+    /// Encode this header as a CBOR envelope
+    ///
+    /// Self-describing alternative to [`serialize`](Self::serialize); see
+    /// [`models::cbor`](crate::models::cbor).
+    #[cfg(feature = "cbor")]
+    pub fn to_cbor(&self) -> Result<Vec<u8>> {
+        super::cbor::encode("DeviceHeader", self)
+    }
+
+    /// Decode a header written by [`to_cbor`](Self::to_cbor)
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InternalError` if the bytes are not a
+    /// `DeviceHeader` envelope of a supported schema version.
+    #[cfg(feature = "cbor")]
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self> {
+        super::cbor::decode("DeviceHeader", bytes)
+    }
+}
+
+/// Decode a bincode header list, accepting the pre-`degraded_info` layout
+pub(crate) fn decode_header_list(bytes: &[u8]) -> bincode::Result<Vec<DeviceHeader>> {
+    super::decode_bounded::<Vec<DeviceHeader>>(bytes).or_else(|err| {
+        super::decode_bounded::<Vec<LegacyDeviceHeader>>(bytes)
+            .map(|legacy| legacy.into_iter().map(DeviceHeader::from).collect())
+            .map_err(|_| err)
+    })
+}
+
+/// Decode `T`, falling back to its legacy layout `L`
+///
+/// Legacy bytes lack the trailing option tag, so they never decode as the
+/// current layout. The current layout's error is reported if both fail.
+fn decode_with_legacy<T, L>(bytes: &[u8]) -> bincode::Result<T>
+where
+    T: serde::de::DeserializeOwned,
+    L: serde::de::DeserializeOwned + Into<T>,
+{
+    super::decode_bounded::<T>(bytes).or_else(|err| {
+        super::decode_bounded::<L>(bytes)
+            .map(Into::into)
+            .map_err(|_| err)
+    })
+}
+
+// ============================================================================
+// Header Set Encoding
+// ============================================================================
+
+/// Serialize a device header set in canonical order
+///
+/// `HashMap` iteration order is random, so the same header set can
+/// serialize differently on two devices. This sorts entries by device ID
+/// bytes and writes each as a 4-byte big-endian length followed by the
+/// bincode-encoded header.
+///
+/// # Arguments
+///
+/// - `headers`: Device header set
+///
+/// # Returns
+///
+/// Canonical byte encoding, identical for equal header sets.
+pub fn canonical_serialize(headers: &HashMap<DeviceId, DeviceHeader>) -> Vec<u8> {
+    let mut entries: Vec<(&DeviceId, &DeviceHeader)> = headers.iter().collect();
+    entries.sort_by_key(|(id, _)| id.0);
+
+    let mut out = Vec::new();
+    for (_, header) in entries {
+        let bytes = header.serialize();
+        out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        out.extend_from_slice(&bytes);
     }
+    out
+}
+
+/// Compute the BLAKE3 digest of a device header set
+///
+/// Hashes the output of [`canonical_serialize`], so two devices holding
+/// the same headers compute the same digest regardless of insertion order.
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::models::device::headers_digest;
+/// use std::collections::HashMap;
+///
+/// let digest = headers_digest(&HashMap::new());
+/// assert_eq!(digest.as_bytes().len(), 32);
+/// ```
+pub fn headers_digest(headers: &HashMap<DeviceId, DeviceHeader>) -> HashOutput {
+    let mut hasher = Blake3Hasher::new();
+    hasher.update(&canonical_serialize(headers));
+    hasher.finalize()
 }
 
 // ============================================================================
@@ -575,6 +1063,83 @@ mod tests {
         assert_eq!(anchor.0, [0u8; 16]);
     }
 
+    #[test]
+    fn test_device_id_generate_sets_uuid_v4_bits() {
+        for _ in 0..64 {
+            let id = DeviceId::generate();
+            assert_eq!(id.0[6] >> 4, 4, "version nibble");
+            assert_eq!(id.0[8] >> 6, 0b10, "variant bits");
+            assert_eq!(id.to_uuid_string().as_bytes()[14], b'4');
+        }
+    }
+
+    #[test]
+    fn test_device_id_uuid_roundtrip() {
+        let id = DeviceId::from_bytes([
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ]);
+        assert_eq!(id.to_uuid_string(), "00112233-4455-6677-8899-aabbccddeeff");
+        assert_eq!(id.to_string(), id.to_uuid_string());
+
+        for input in [
+            "00112233-4455-6677-8899-aabbccddeeff",
+            "00112233-4455-6677-8899-AABBCCDDEEFF",
+            "00112233445566778899aabbccddeeff",
+            "00112233445566778899AaBbCcDdEeFf",
+        ] {
+            assert_eq!(DeviceId::parse(input).unwrap(), id, "{}", input);
+        }
+        assert_eq!(
+            "00112233445566778899aabbccddeeff"
+                .parse::<DeviceId>()
+                .unwrap(),
+            id
+        );
+        assert_eq!(
+            DeviceId::try_from("00112233-4455-6677-8899-aabbccddeeff").unwrap(),
+            id
+        );
+
+        let generated = DeviceId::generate();
+        assert_eq!(DeviceId::parse(&generated.to_string()).unwrap(), generated);
+    }
+
+    #[test]
+    fn test_device_id_parse_rejects_malformed() {
+        for input in [
+            "",
+            "0011223344556677",
+            "00112233445566778899aabbccddeeff00",
+            "00112233445566778899aabbccddeefg",
+            "00112233-4455-6677-8899-aabbccddeefz",
+            "0011223-34455-6677-8899-aabbccddeeff",
+            "00112233+4455+6677+8899+aabbccddeeff",
+            "00112233-4455-6677-8899aabbccddeeff-",
+            "0011223344556677-8899-aabbccddeeff-",
+            "ü0112233445566778899aabbccddeef",
+        ] {
+            assert!(
+                matches!(DeviceId::parse(input), Err(CryptoError::InvalidDeviceId(_))),
+                "{:?} should be rejected",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_device_id_shadow_anchor_uuid() {
+        let anchor = DeviceId::shadow_anchor();
+        assert_eq!(
+            anchor.to_uuid_string(),
+            "00000000-0000-0000-0000-000000000000"
+        );
+        assert_eq!(DeviceId::parse(&anchor.to_string()).unwrap(), anchor);
+        assert!(DeviceId::parse("00000000000000000000000000000000")
+            .unwrap()
+            .is_shadow_anchor());
+    }
+
     #[test]
     fn test_device_id_as_bytes() {
         let bytes = [42u8; 16];
@@ -614,6 +1179,32 @@ mod tests {
         assert_ne!(DeviceStatus::Revoked, DeviceStatus::Degraded);
     }
 
+    #[test]
+    fn test_device_status_codes() {
+        let cases = [
+            (DeviceStatus::Active, 0u8),
+            (DeviceStatus::Revoked, 1),
+            (DeviceStatus::Degraded, 2),
+        ];
+        for (status, code) in cases {
+            assert_eq!(status.as_u8(), code);
+            assert_eq!(DeviceStatus::from_u8(code).unwrap(), status);
+            // Same bytes as the derived encoding of earlier headers
+            assert_eq!(
+                bincode::serialize(&status).unwrap(),
+                u32::from(code).to_le_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn test_device_status_rejects_unknown_codes() {
+        assert!(DeviceStatus::from_u8(3).is_err());
+        assert!(DeviceStatus::from_u8(u8::MAX).is_err());
+        assert!(bincode::deserialize::<DeviceStatus>(&3u32.to_le_bytes()).is_err());
+        assert!(bincode::deserialize::<DeviceStatus>(&256u32.to_le_bytes()).is_err());
+    }
+
     // ------------------------------------------------------------------------
     // DeviceHeader Tests
     // ------------------------------------------------------------------------
@@ -661,6 +1252,46 @@ mod tests {
         assert_eq!(header.status, DeviceStatus::Revoked);
     }
 
+    #[test]
+    fn test_device_header_degrade_lifecycle() {
+        let mut header = header_with_id(1);
+        assert!(header.restore().is_err(), "Active cannot be restored");
+
+        header.degrade(DegradeReason::KeyMismatch, 5_000).unwrap();
+        assert_eq!(header.status, DeviceStatus::Degraded);
+        assert_eq!(
+            header.degraded_info,
+            Some(DegradedInfo {
+                reason: DegradeReason::KeyMismatch,
+                since_ms: 5_000,
+            })
+        );
+        assert!(header.status.can_veto());
+
+        // Degrading again replaces the reason
+        header
+            .degrade(DegradeReason::Other("lost attestation".to_string()), 6_000)
+            .unwrap();
+        assert_eq!(header.degraded_info.as_ref().unwrap().since_ms, 6_000);
+
+        header.restore().unwrap();
+        assert_eq!(header.status, DeviceStatus::Active);
+        assert!(header.degraded_info.is_none());
+
+        // Revoked is terminal: neither degrade nor restore leaves it
+        header
+            .degrade(DegradeReason::AttestationFailed, 7_000)
+            .unwrap();
+        header.revoke();
+        assert!(header.degraded_info.is_none());
+        assert!(header.restore().is_err());
+        assert!(header
+            .degrade(DegradeReason::IntegrityCheckFailed, 8_000)
+            .is_err());
+        assert_eq!(header.status, DeviceStatus::Revoked);
+        assert!(!header.status.can_veto());
+    }
+
     #[test]
     fn test_device_header_belongs_to_epoch() {
         let device_id = DeviceId::generate();
@@ -699,7 +1330,7 @@ mod tests {
         );
 
         // Deserialize
-        let deserialized = DeviceHeader::deserialize(&serialized);
+        let deserialized = DeviceHeader::deserialize(&serialized).unwrap();
 
         // Verify all fields match
         assert_eq!(deserialized.device_id, header.device_id);
@@ -721,7 +1352,7 @@ mod tests {
         let header = DeviceHeader::new(device_id, epoch1.clone(), keypair.public, encrypted_dek);
 
         let serialized = header.serialize();
-        let deserialized = DeviceHeader::deserialize(&serialized);
+        let deserialized = DeviceHeader::deserialize(&serialized).unwrap();
 
         // Epoch must be preserved exactly
         assert_eq!(deserialized.epoch.version, 5);
@@ -738,7 +1369,7 @@ mod tests {
         let header = DeviceHeader::shadow_anchor(epoch.clone(), keypair.public, encrypted_dek);
 
         let serialized = header.serialize();
-        let deserialized = DeviceHeader::deserialize(&serialized);
+        let deserialized = DeviceHeader::deserialize(&serialized).unwrap();
 
         // Shadow anchor must preserve all-zero device ID
         assert!(deserialized.device_id.is_shadow_anchor());
@@ -746,11 +1377,33 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
     fn test_device_header_deserialize_invalid_data() {
-        // Test that deserializing invalid data panics
+        // Test that deserializing invalid data fails without panicking
         let invalid_data = vec![0xFF, 0xFF, 0xFF];
-        DeviceHeader::deserialize(&invalid_data);
+        assert!(DeviceHeader::deserialize(&invalid_data).is_err());
+    }
+
+    #[test]
+    fn test_device_header_deserialize_rejects_forged_length() {
+        // A key length prefix far beyond the input must not be trusted
+        let device_id = DeviceId::generate();
+        let keypair = KyberKEM::generate_keypair();
+        let (_ss, encrypted_dek) = KyberKEM::encapsulate(&keypair.public).unwrap();
+        let header = DeviceHeader::new(
+            device_id,
+            CryptoEpoch::initial(),
+            keypair.public,
+            encrypted_dek,
+        );
+
+        let mut serialized = header.serialize();
+        // Dropping only the trailing option tag leaves a valid legacy header
+        let truncated_len = serialized.len() - 2;
+        assert!(DeviceHeader::deserialize(&serialized[..truncated_len]).is_err());
+
+        // Public key length prefix follows device ID (16) and epoch (8 + 8 + 4)
+        serialized[36..44].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(DeviceHeader::deserialize(&serialized).is_err());
     }
 
     #[test]
@@ -769,9 +1422,106 @@ mod tests {
 
         // Serialize and deserialize
         let serialized = header.serialize();
-        let deserialized = DeviceHeader::deserialize(&serialized);
+        let deserialized = DeviceHeader::deserialize(&serialized).unwrap();
 
         // Status must be preserved
         assert_eq!(deserialized.status, DeviceStatus::Revoked);
     }
+
+    #[test]
+    fn test_device_header_serialize_with_degraded_info() {
+        let mut header = header_with_id(2);
+        header
+            .degrade(DegradeReason::IntegrityCheckFailed, 9_000)
+            .unwrap();
+
+        let deserialized = DeviceHeader::deserialize(&header.serialize()).unwrap();
+        assert_eq!(deserialized, header);
+    }
+
+    /// Bytes of `header` in the layout before `degraded_info` existed
+    fn legacy_bytes(header: &DeviceHeader) -> Vec<u8> {
+        // `None` is a single trailing 0x00 tag
+        let mut bytes = header.serialize();
+        assert_eq!(bytes.pop(), Some(0));
+        bytes
+    }
+
+    #[test]
+    fn test_device_header_deserialize_legacy_layout() {
+        let header = header_with_id(3);
+
+        let deserialized = DeviceHeader::deserialize(&legacy_bytes(&header)).unwrap();
+        assert_eq!(deserialized, header);
+        assert!(deserialized.degraded_info.is_none());
+
+        // A header list written in the old layout decodes too
+        let headers = vec![header_with_id(4), header_with_id(5)];
+        let mut list = (headers.len() as u64).to_le_bytes().to_vec();
+        for header in &headers {
+            list.extend_from_slice(&legacy_bytes(header));
+        }
+        assert_eq!(decode_header_list(&list).unwrap(), headers);
+        assert_eq!(
+            decode_header_list(&bincode::serialize(&headers).unwrap()).unwrap(),
+            headers
+        );
+    }
+
+    // ------------------------------------------------------------------------
+    // Header Set Encoding Tests
+    // ------------------------------------------------------------------------
+
+    fn header_with_id(first_byte: u8) -> DeviceHeader {
+        let mut id = [0u8; 16];
+        id[0] = first_byte;
+        let mut header = DeviceHeader::new(
+            DeviceId::from_bytes(id),
+            CryptoEpoch::initial(),
+            KyberPublicKeyBytes([first_byte; 1568]),
+            KyberCipherText([first_byte; 1568]),
+        );
+        header.created_at = 1_000 + first_byte as u64;
+        header
+    }
+
+    #[test]
+    fn test_headers_digest_independent_of_insertion_order() {
+        let headers: Vec<DeviceHeader> = (1..=8).map(header_with_id).collect();
+
+        let forward: HashMap<_, _> = headers.iter().map(|h| (h.device_id, h.clone())).collect();
+        let mut reverse = HashMap::new();
+        for h in headers.iter().rev() {
+            reverse.insert(h.device_id, h.clone());
+        }
+
+        assert_eq!(canonical_serialize(&forward), canonical_serialize(&reverse));
+        assert_eq!(headers_digest(&forward), headers_digest(&reverse));
+    }
+
+    #[test]
+    fn test_headers_digest_changes_with_status() {
+        let mut headers: HashMap<_, _> = (1..=4)
+            .map(header_with_id)
+            .map(|h| (h.device_id, h))
+            .collect();
+        let before = headers_digest(&headers);
+
+        let id = header_with_id(2).device_id;
+        headers.get_mut(&id).unwrap().status = DeviceStatus::Revoked;
+
+        assert_ne!(before, headers_digest(&headers));
+    }
+
+    #[test]
+    fn test_canonical_serialize_length_prefixed() {
+        let header = header_with_id(1);
+        let headers = HashMap::from([(header.device_id, header.clone())]);
+
+        let bytes = canonical_serialize(&headers);
+        let body = header.serialize();
+        assert_eq!(&bytes[..4], &(body.len() as u32).to_be_bytes());
+        assert_eq!(&bytes[4..], &body[..]);
+        assert!(canonical_serialize(&HashMap::new()).is_empty());
+    }
 }
@@ -3,13 +3,27 @@
 //! This module defines the epoch system used to track cryptographic
 //! algorithm versions and ensure monotonic progression.
 
-use serde::{Deserialize, Serialize};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::crypto::error::{CryptoError, Result};
 
 /// Cryptographic algorithm identifier
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// An epoch upgrade may move to a newer algorithm together with the epoch
+/// version; code with algorithm-specific behavior dispatches on
+/// [`CryptoEpoch::algorithm`].
+///
+/// Encoded by its stable [`as_u8`](Self::as_u8) code rather than its
+/// variant index, so reordering variants cannot change stored epochs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CryptoAlgorithm {
     /// v1: Kyber-1024 + X25519 + XChaCha20-Poly1305 + Argon2id + BLAKE3
     V1,
+    /// v2: as v1, except the epoch DEK is derived from the VK with
+    /// BLAKE3 `derive_key` (XOF) instead of Argon2id, and the
+    /// DEK-encrypted VK is bound to its epoch as AEAD associated data
+    V2,
 }
 
 impl CryptoAlgorithm {
@@ -17,12 +31,82 @@ impl CryptoAlgorithm {
     pub fn version(&self) -> u32 {
         match self {
             CryptoAlgorithm::V1 => 1,
+            CryptoAlgorithm::V2 => 2,
+        }
+    }
+
+    /// Parse an algorithm from its version number
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InternalError` for an unknown version; there
+    /// is no fallback algorithm.
+    pub fn from_version(version: u8) -> Result<Self> {
+        match version {
+            1 => Ok(CryptoAlgorithm::V1),
+            2 => Ok(CryptoAlgorithm::V2),
+            _ => Err(CryptoError::internal(format!(
+                "Unknown crypto algorithm version: {}",
+                version
+            ))),
+        }
+    }
+
+    /// Stable wire code of this algorithm (its version number)
+    ///
+    /// Code 0 and codes 3..=255 are reserved; a code is never reassigned.
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            CryptoAlgorithm::V1 => 1,
+            CryptoAlgorithm::V2 => 2,
         }
     }
 
+    /// Parse an algorithm from its wire code
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InternalError` for an unassigned code.
+    pub fn from_u8(code: u8) -> Result<Self> {
+        Self::from_version(code)
+    }
+
     /// Check if this algorithm is supported
     pub fn is_supported(&self) -> bool {
-        matches!(self, CryptoAlgorithm::V1)
+        matches!(self, CryptoAlgorithm::V1 | CryptoAlgorithm::V2)
+    }
+
+    /// Vault blob format version that first carries this algorithm
+    pub fn blob_version(&self) -> u32 {
+        match self {
+            CryptoAlgorithm::V1 => 1,
+            CryptoAlgorithm::V2 => 2,
+        }
+    }
+}
+
+/// Serde variant index of V1 before the stable codes
+///
+/// Epochs stored before then hold V1 as 0; V2 was not yet released.
+const LEGACY_V1_INDEX: u32 = 0;
+
+impl Serialize for CryptoAlgorithm {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        // Same width as the derived variant index in bincode
+        serializer.serialize_u32(u32::from(self.as_u8()))
+    }
+}
+
+impl<'de> Deserialize<'de> for CryptoAlgorithm {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let code = u32::deserialize(deserializer)?;
+        if code == LEGACY_V1_INDEX {
+            return Ok(CryptoAlgorithm::V1);
+        }
+        u8::try_from(code)
+            .ok()
+            .and_then(|code| CryptoAlgorithm::from_u8(code).ok())
+            .ok_or_else(|| D::Error::custom(format!("unknown crypto algorithm code {}", code)))
     }
 }
 
@@ -60,6 +144,11 @@ impl CryptoEpoch {
         Self::new(self.version + 1, self.algorithm)
     }
 
+    /// Create the next epoch (version + 1) under `algorithm`
+    pub fn next_with_algorithm(&self, algorithm: CryptoAlgorithm) -> Self {
+        Self::new(self.version + 1, algorithm)
+    }
+
     /// Format epoch as a string
     pub fn as_string(&self) -> String {
         format!(
@@ -94,6 +183,70 @@ mod tests {
     #[test]
     fn test_crypto_algorithm_supported() {
         assert!(CryptoAlgorithm::V1.is_supported());
+        assert!(CryptoAlgorithm::V2.is_supported());
+    }
+
+    #[test]
+    fn test_crypto_algorithm_from_version() {
+        for algorithm in [CryptoAlgorithm::V1, CryptoAlgorithm::V2] {
+            let version = algorithm.version() as u8;
+            assert_eq!(CryptoAlgorithm::from_version(version).unwrap(), algorithm);
+        }
+        assert!(CryptoAlgorithm::from_version(0).is_err());
+        assert!(CryptoAlgorithm::from_version(3).is_err());
+        assert!(CryptoAlgorithm::from_version(u8::MAX).is_err());
+    }
+
+    #[test]
+    fn test_crypto_algorithm_codes() {
+        for (algorithm, code) in [(CryptoAlgorithm::V1, 1u8), (CryptoAlgorithm::V2, 2)] {
+            assert_eq!(algorithm.as_u8(), code);
+            assert_eq!(CryptoAlgorithm::from_u8(code).unwrap(), algorithm);
+            let bytes = bincode::serialize(&algorithm).unwrap();
+            assert_eq!(bytes, u32::from(code).to_le_bytes());
+            assert_eq!(
+                bincode::deserialize::<CryptoAlgorithm>(&bytes).unwrap(),
+                algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn test_crypto_algorithm_rejects_unknown_codes() {
+        assert!(CryptoAlgorithm::from_u8(0).is_err());
+        assert!(CryptoAlgorithm::from_u8(3).is_err());
+        assert!(bincode::deserialize::<CryptoAlgorithm>(&3u32.to_le_bytes()).is_err());
+        assert!(bincode::deserialize::<CryptoAlgorithm>(&258u32.to_le_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_legacy_v1_index_still_decodes() {
+        let mut epoch = CryptoEpoch::new(4, CryptoAlgorithm::V1);
+        epoch.timestamp = 1_000;
+
+        // Layout written before the stable codes: algorithm as index 0
+        let mut legacy = bincode::serialize(&epoch).unwrap();
+        let algorithm = legacy.len() - 4;
+        legacy[algorithm..].copy_from_slice(&0u32.to_le_bytes());
+
+        assert_eq!(bincode::deserialize::<CryptoEpoch>(&legacy).unwrap(), epoch);
+    }
+
+    #[test]
+    fn test_unknown_algorithm_rejected_on_deserialize() {
+        let mut serialized = bincode::serialize(&CryptoEpoch::initial()).unwrap();
+
+        // 算法判别值位于版本号与时间戳之后
+        serialized[16..20].copy_from_slice(&7u32.to_le_bytes());
+        assert!(bincode::deserialize::<CryptoEpoch>(&serialized).is_err());
+    }
+
+    #[test]
+    fn test_next_with_algorithm() {
+        let epoch = CryptoEpoch::initial();
+        let next = epoch.next_with_algorithm(CryptoAlgorithm::V2);
+        assert_eq!(next.version, epoch.version + 1);
+        assert_eq!(next.algorithm, CryptoAlgorithm::V2);
     }
 
     #[test]
@@ -21,26 +21,87 @@
 //!             └─ XChaCha20 encryption → VK
 //! ```
 //!
+//! ## DEK Wrapping
+//!
+//! ```text
+//! Kyber-1024 Encaps(DK_pub) → (ss, kem_ct)
+//! BLAKE3-Derive(ss, "Aeternum_DEK_Wrap_v1") → KEK (32 bytes)
+//! XChaCha20-Poly1305(KEK, nonce, DEK, aad = kem_ct) → aead_ct
+//! ```
+//!
+//! [`DataEncryptionKey::wrap_for_device`] and [`DataEncryptionKey::unwrap`]
+//! are the only code paths that turn a KEM shared secret into a DEK
+//! wrapping key. [`crate::crypto::kem::wrap_dek_for_devices`] fans the wrap
+//! out over a set of device public keys when rekeying.
+//!
 //! ## Security Properties
 //!
 //! - All secret types implement `Zeroize` and `ZeroizeOnDrop`
 //! - Debug implementations never expose actual key material
 //! - Key derivation is deterministic and reproducible
 
-use crate::crypto::error::{CryptoError, Result};
+use crate::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce};
+use crate::crypto::error::{CryptoError, MnemonicError, Result};
 use crate::crypto::hash::DeriveKey;
+use crate::crypto::kem::{
+    KyberCipherText, KyberKEM, KyberKeyPair, KyberPublicKeyBytes, KyberSecretKeyBytes,
+    KyberSharedSecret,
+};
+use crate::crypto::redact::impl_redacted_debug;
+use crate::crypto::secure_mem::LockedBuffer;
 use pbkdf2::pbkdf2_hmac;
+use serde::{Deserialize, Serialize};
 use sha2::Sha512;
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 // Domain separation context strings (MUST match Cold-Anchor-Recovery.md spec)
 const IDENTITY_KEY_CONTEXT: &str = "Aeternum_Identity_v1";
 const RECOVERY_KEY_CONTEXT: &str = "Aeternum_Recovery_v1";
+const ANCHOR_KEY_CONTEXT: &str = "Aeternum_Device0_KEM_v1";
+
+/// Context for deriving the DEK wrapping key from a KEM shared secret
+///
+/// Frozen: changing it makes every existing device header undecryptable.
+pub const DEK_WRAP_CONTEXT: &str = "Aeternum_DEK_Wrap_v1";
 
 // PBKDF2 parameters (MUST match Cold-Anchor-Recovery.md spec)
 const PBKDF2_ITERATIONS: u32 = 2048;
 const SEED_SIZE: usize = 64; // 512-bit seed
 
+/// Supported BIP-39 mnemonic lengths for [`MasterSeed::generate_mnemonic`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MnemonicLength {
+    /// 12 words (128-bit entropy + 4-bit checksum)
+    Words12,
+    /// 24 words (256-bit entropy + 8-bit checksum), the Aeternum default
+    Words24,
+}
+
+impl MnemonicLength {
+    /// Number of words in the phrase
+    pub fn word_count(self) -> usize {
+        match self {
+            Self::Words12 => 12,
+            Self::Words24 => 24,
+        }
+    }
+
+    /// Number of entropy bytes encoded by the phrase
+    pub fn entropy_len(self) -> usize {
+        match self {
+            Self::Words12 => 16,
+            Self::Words24 => 32,
+        }
+    }
+}
+
+/// Encode raw entropy as an English BIP-39 phrase
+fn mnemonic_from_entropy(entropy: &[u8]) -> Result<Zeroizing<String>> {
+    let mnemonic = bip39::Mnemonic::from_entropy(entropy)
+        .map_err(|e| CryptoError::internal(format!("BIP-39 encoding failed: {}", e)))?;
+    Ok(Zeroizing::new(mnemonic.to_string()))
+}
+
 /// Master Root Seed - 512-bit seed derived from 24-word mnemonic
 ///
 /// This is the root of all key derivation in Aeternum. It is derived
@@ -51,8 +112,9 @@ const SEED_SIZE: usize = 64; // 512-bit seed
 /// - Implements `Zeroize` and `ZeroizeOnDrop` for automatic memory erasure
 /// - Debug output never shows actual key material
 /// - The seed should only exist in memory during initial setup or recovery
+/// - Stored in a [`LockedBuffer`] so the seed is never swapped to disk
 #[derive(Zeroize, ZeroizeOnDrop)]
-pub struct MasterSeed(pub [u8; 64]);
+pub struct MasterSeed(LockedBuffer);
 
 impl MasterSeed {
     /// Derive MasterSeed from a BIP-39 mnemonic phrase.
@@ -90,13 +152,92 @@ impl MasterSeed {
         // Derive the seed using PBKDF2-HMAC-SHA512
         // BIP-39: seed = PBKDF2-HMAC-SHA512(mnemonic, "mnemonic" + passphrase, 2048)
         // We use an empty passphrase (standard behavior)
-        let mut seed = [0u8; SEED_SIZE];
+        let mut seed = LockedBuffer::new(SEED_SIZE)?;
         let salt = b"mnemonic"; // BIP-39 standard salt (empty passphrase case)
-        pbkdf2_hmac::<Sha512>(mnemonic.as_bytes(), salt, PBKDF2_ITERATIONS, &mut seed);
+        pbkdf2_hmac::<Sha512>(
+            mnemonic.as_bytes(),
+            salt,
+            PBKDF2_ITERATIONS,
+            seed.as_mut_slice(),
+        );
 
         Ok(MasterSeed(seed))
     }
 
+    /// Generate a fresh BIP-39 mnemonic and the MasterSeed derived from it.
+    ///
+    /// Entropy comes from the system CSPRNG; the checksum and English
+    /// wordlist follow BIP-39 exactly, so the phrase is interchangeable with
+    /// other BIP-39 implementations.
+    ///
+    /// # Arguments
+    ///
+    /// * `word_count` - 12 or 24 words
+    ///
+    /// # Returns
+    ///
+    /// The space-separated mnemonic (zeroized on drop) and its `MasterSeed`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InternalError` if the BIP-39 encoder rejects the
+    /// entropy, which indicates a bug rather than bad input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::models::key_hierarchy::{MasterSeed, MnemonicLength};
+    ///
+    /// let (mnemonic, seed) = MasterSeed::generate_mnemonic(MnemonicLength::Words24).unwrap();
+    /// assert_eq!(mnemonic.split_whitespace().count(), 24);
+    /// assert!(MasterSeed::validate_mnemonic(&mnemonic).is_ok());
+    /// # let _ = seed;
+    /// ```
+    pub fn generate_mnemonic(word_count: MnemonicLength) -> Result<(Zeroizing<String>, Self)> {
+        use rand::RngCore;
+
+        let mut entropy = Zeroizing::new([0u8; 32]);
+        let entropy = &mut entropy[..word_count.entropy_len()];
+        rand::thread_rng().fill_bytes(entropy);
+
+        let phrase = mnemonic_from_entropy(entropy)?;
+        let seed = Self::from_mnemonic(&phrase)?;
+
+        Ok((phrase, seed))
+    }
+
+    /// Validate a BIP-39 mnemonic without deriving a seed.
+    ///
+    /// Only the English wordlist and 12- or 24-word phrases are accepted,
+    /// matching what [`generate_mnemonic`](Self::generate_mnemonic) produces.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::InvalidMnemonic` with:
+    /// - `MnemonicError::WrongWordCount` if the phrase is not 12 or 24 words
+    /// - `MnemonicError::UnknownWord` if a word is not in the wordlist
+    /// - `MnemonicError::BadChecksum` if the checksum does not match
+    pub fn validate_mnemonic(mnemonic: &str) -> std::result::Result<(), CryptoError> {
+        let words = mnemonic.split_whitespace().count();
+        if words != MnemonicLength::Words12.word_count()
+            && words != MnemonicLength::Words24.word_count()
+        {
+            return Err(CryptoError::InvalidMnemonic(MnemonicError::WrongWordCount(
+                words,
+            )));
+        }
+
+        bip39::Mnemonic::parse_in_normalized(bip39::Language::English, mnemonic)
+            .map(|_| ())
+            .map_err(|e| {
+                CryptoError::InvalidMnemonic(match e {
+                    bip39::Error::UnknownWord(index) => MnemonicError::UnknownWord(index),
+                    bip39::Error::InvalidChecksum => MnemonicError::BadChecksum,
+                    _ => MnemonicError::WrongWordCount(words),
+                })
+            })
+    }
+
     /// Derive the Identity Key (IK) from the master seed.
     ///
     /// Uses BLAKE3 key derivation mode with domain separation.
@@ -106,11 +247,11 @@ impl MasterSeed {
     ///
     /// A 32-byte `IdentityKey`.
     pub fn derive_identity_key(&self) -> IdentityKey {
-        let dk = DeriveKey::new(&self.0, IDENTITY_KEY_CONTEXT);
-        let key_bytes = dk.derive(&self.0, 32);
+        let dk = DeriveKey::new(self.as_bytes(), IDENTITY_KEY_CONTEXT);
+        let key_bytes = dk.derive(self.as_bytes(), 32);
         // SAFETY: derive() always returns exactly 32 bytes when length=32
         let key_array: [u8; 32] = key_bytes.try_into().unwrap();
-        IdentityKey(key_array)
+        IdentityKey::from_bytes(key_array)
     }
 
     /// Derive the Recovery Key (RK) from the master seed.
@@ -122,11 +263,11 @@ impl MasterSeed {
     ///
     /// A 32-byte `RecoveryKey`.
     pub fn derive_recovery_key(&self) -> RecoveryKey {
-        let dk = DeriveKey::new(&self.0, RECOVERY_KEY_CONTEXT);
-        let key_bytes = dk.derive(&self.0, 32);
+        let dk = DeriveKey::new(self.as_bytes(), RECOVERY_KEY_CONTEXT);
+        let key_bytes = dk.derive(self.as_bytes(), 32);
         // SAFETY: derive() always returns exactly 32 bytes when length=32
         let key_array: [u8; 32] = key_bytes.try_into().unwrap();
-        RecoveryKey(key_array)
+        RecoveryKey::from_bytes(key_array)
     }
 
     /// Get a reference to the raw seed bytes.
@@ -136,7 +277,8 @@ impl MasterSeed {
     /// This exposes the raw seed material. Use with caution and
     /// ensure the result is not logged or persisted insecurely.
     pub fn as_bytes(&self) -> &[u8; 64] {
-        &self.0
+        // SAFETY: the buffer is always allocated with SEED_SIZE bytes
+        self.0.as_slice().try_into().unwrap()
     }
 
     /// Create a MasterSeed from raw bytes.
@@ -145,17 +287,18 @@ impl MasterSeed {
     ///
     /// This bypasses BIP-39 validation. Use only when you have
     /// a verified seed from a trusted source.
-    pub fn from_bytes(bytes: [u8; 64]) -> Self {
-        MasterSeed(bytes)
+    ///
+    /// # Panics
+    ///
+    /// Panics if a 64-byte buffer cannot be allocated.
+    pub fn from_bytes(mut bytes: [u8; 64]) -> Self {
+        let seed = LockedBuffer::from_slice(&bytes).expect("64-byte allocation failed");
+        bytes.zeroize();
+        MasterSeed(seed)
     }
 }
 
-// Secure Debug implementation (never expose key material)
-impl std::fmt::Debug for MasterSeed {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("MasterSeed([REDACTED])")
-    }
-}
+impl_redacted_debug!(MasterSeed, |key| key.as_bytes());
 
 /// Identity Key - used for authentication and signing
 ///
@@ -180,12 +323,7 @@ impl IdentityKey {
     }
 }
 
-// Secure Debug implementation
-impl std::fmt::Debug for IdentityKey {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("IdentityKey([REDACTED])")
-    }
-}
+impl_redacted_debug!(IdentityKey, |key| key.as_bytes());
 
 /// Recovery Key - used for vault recovery
 ///
@@ -208,15 +346,24 @@ impl RecoveryKey {
     pub fn from_bytes(bytes: [u8; 32]) -> Self {
         RecoveryKey(bytes)
     }
-}
 
-// Secure Debug implementation
-impl std::fmt::Debug for RecoveryKey {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("RecoveryKey([REDACTED])")
+    /// Derive the Device_0 (shadow anchor) Kyber-1024 keypair.
+    ///
+    /// Uses BLAKE3 key derivation mode with domain separation to expand
+    /// the recovery key into the 64-byte Kyber keygen seed. The context
+    /// string is "Aeternum_Device0_KEM_v1", so the same mnemonic always
+    /// yields the same anchor key.
+    pub fn derive_anchor_keypair(&self) -> KyberKeyPair {
+        let dk = DeriveKey::new(self.as_bytes(), ANCHOR_KEY_CONTEXT);
+        let seed = Zeroizing::new(dk.derive(self.as_bytes(), 64));
+        // SAFETY: derive() always returns exactly 64 bytes when length=64
+        let seed: Zeroizing<[u8; 64]> = Zeroizing::new(seed[..].try_into().unwrap());
+        KyberKEM::derive_keypair(&seed)
     }
 }
 
+impl_redacted_debug!(RecoveryKey, |key| key.as_bytes());
+
 /// Device Key - hardware-generated key (only holds key_id)
 ///
 /// The private key never leaves the hardware security module (StrongBox/KeyStore).
@@ -292,15 +439,100 @@ impl DataEncryptionKey {
         rand::thread_rng().fill_bytes(&mut bytes);
         DataEncryptionKey(bytes)
     }
-}
 
-// Secure Debug implementation
-impl std::fmt::Debug for DataEncryptionKey {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("DataEncryptionKey([REDACTED])")
+    /// Wrap this DEK for a device.
+    ///
+    /// Encapsulates a fresh shared secret to `device_public`, derives the
+    /// wrapping key with [`DEK_WRAP_CONTEXT`] and encrypts the DEK under a
+    /// random nonce, binding the KEM ciphertext as associated data.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::KemError` for an invalid public key, or
+    /// `CryptoError::AeadError` if encryption fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::crypto::kem::KyberKEM;
+    /// use aeternum_core::models::key_hierarchy::DataEncryptionKey;
+    ///
+    /// let keypair = KyberKEM::generate_keypair();
+    /// let dek = DataEncryptionKey::generate();
+    ///
+    /// let wrapped = dek.wrap_for_device(&keypair.public).unwrap();
+    /// let unwrapped = DataEncryptionKey::unwrap(&wrapped, &keypair.secret).unwrap();
+    /// assert_eq!(unwrapped.as_bytes(), dek.as_bytes());
+    /// ```
+    pub fn wrap_for_device(&self, device_public: &KyberPublicKeyBytes) -> Result<WrappedDek> {
+        let (shared_secret, kem_ciphertext) = KyberKEM::encapsulate(device_public)?;
+        let nonce = XChaCha20Nonce::random();
+        let aead_ciphertext = AeadCipher::new(&dek_wrap_key(&shared_secret)).encrypt(
+            &nonce,
+            self.as_bytes(),
+            Some(kem_ciphertext.as_bytes()),
+        )?;
+
+        Ok(WrappedDek {
+            kem_ciphertext,
+            aead_ciphertext,
+            nonce: *nonce.as_bytes(),
+        })
     }
+
+    /// Unwrap a DEK with the device's secret key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CryptoError::AeadError` if the secret key does not match or
+    /// any part of `wrapped` was modified, and `CryptoError::KemError` for a
+    /// malformed secret key.
+    pub fn unwrap(wrapped: &WrappedDek, device_secret: &KyberSecretKeyBytes) -> Result<Self> {
+        let shared_secret = KyberKEM::decapsulate(device_secret, &wrapped.kem_ciphertext)?;
+        let plaintext = Zeroizing::new(AeadCipher::new(&dek_wrap_key(&shared_secret)).decrypt(
+            &XChaCha20Nonce::from_bytes(wrapped.nonce),
+            &wrapped.aead_ciphertext,
+            Some(wrapped.kem_ciphertext.as_bytes()),
+        )?);
+
+        let bytes: [u8; 32] =
+            plaintext
+                .as_slice()
+                .try_into()
+                .map_err(|_| CryptoError::InvalidKeyLength {
+                    expected: 32,
+                    actual: plaintext.len(),
+                })?;
+        Ok(DataEncryptionKey::from_bytes(bytes))
+    }
+}
+
+/// Derive the AEAD key that wraps a DEK from a KEM shared secret
+fn dek_wrap_key(shared_secret: &KyberSharedSecret) -> XChaCha20Key {
+    let key_bytes =
+        Zeroizing::new(DeriveKey::new(&[], DEK_WRAP_CONTEXT).derive(shared_secret.as_bytes(), 32));
+    // SAFETY: derive() always returns exactly 32 bytes when length=32
+    XChaCha20Key::from_bytes(&key_bytes).unwrap()
+}
+
+/// DEK wrapped for one device
+///
+/// Produced by [`DataEncryptionKey::wrap_for_device`]; stored in the
+/// device's header.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WrappedDek {
+    /// Kyber-1024 encapsulation to the device's public key
+    pub kem_ciphertext: KyberCipherText,
+
+    /// XChaCha20-Poly1305 encryption of the DEK (32 bytes + 16-byte tag)
+    pub aead_ciphertext: Vec<u8>,
+
+    /// XChaCha20 nonce
+    pub nonce: [u8; 24],
 }
 
+impl_redacted_debug!(DataEncryptionKey, |key| key.as_bytes());
+
 /// Vault Key - encrypts user data
 ///
 /// The VK is the actual symmetric key used with XChaCha20-Poly1305
@@ -337,12 +569,7 @@ impl VaultKey {
     }
 }
 
-// Secure Debug implementation
-impl std::fmt::Debug for VaultKey {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("VaultKey([REDACTED])")
-    }
-}
+impl_redacted_debug!(VaultKey, |key| key.as_bytes());
 
 // ============================================================================
 // Tests
@@ -398,11 +625,120 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ── Mnemonic Generation Tests ─────────────────────────────────────────
+
+    #[test]
+    fn test_generate_mnemonic_validates() {
+        for length in [MnemonicLength::Words12, MnemonicLength::Words24] {
+            let (mnemonic, _seed) = MasterSeed::generate_mnemonic(length).unwrap();
+            assert_eq!(mnemonic.split_whitespace().count(), length.word_count());
+            assert!(MasterSeed::validate_mnemonic(&mnemonic).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_generate_mnemonic_roundtrip() {
+        let (mnemonic, seed) = MasterSeed::generate_mnemonic(MnemonicLength::Words24).unwrap();
+        let rederived = MasterSeed::from_mnemonic(&mnemonic).unwrap();
+        assert_eq!(seed.as_bytes(), rederived.as_bytes());
+
+        // Fresh entropy every call
+        let (other, _) = MasterSeed::generate_mnemonic(MnemonicLength::Words24).unwrap();
+        assert_ne!(*mnemonic, *other);
+    }
+
+    #[test]
+    fn test_validate_mnemonic_bit_flipped_word_fails_checksum() {
+        let (mnemonic, _) = MasterSeed::generate_mnemonic(MnemonicLength::Words24).unwrap();
+        let wordlist = bip39::Language::English.word_list();
+
+        // Flip the lowest bit of the first word's index: still a valid word,
+        // but the entropy no longer matches the checksum
+        let mut words: Vec<&str> = mnemonic.split_whitespace().collect();
+        let index = wordlist.iter().position(|w| *w == words[0]).unwrap();
+        words[0] = wordlist[index ^ 1];
+
+        assert!(matches!(
+            MasterSeed::validate_mnemonic(&words.join(" ")),
+            Err(CryptoError::InvalidMnemonic(MnemonicError::BadChecksum))
+        ));
+    }
+
+    #[test]
+    fn test_validate_mnemonic_unknown_word() {
+        let mut words: Vec<&str> = BIP39_TEST_MNEMONIC_24.split_whitespace().collect();
+        words[5] = "aeternum";
+
+        assert!(matches!(
+            MasterSeed::validate_mnemonic(&words.join(" ")),
+            Err(CryptoError::InvalidMnemonic(MnemonicError::UnknownWord(5)))
+        ));
+    }
+
+    #[test]
+    fn test_validate_mnemonic_wrong_word_count() {
+        // 15 words is valid BIP-39 but not a length Aeternum supports
+        let fifteen = ["abandon"; 15].join(" ");
+        assert!(matches!(
+            MasterSeed::validate_mnemonic(&fifteen),
+            Err(CryptoError::InvalidMnemonic(MnemonicError::WrongWordCount(
+                15
+            )))
+        ));
+        assert!(matches!(
+            MasterSeed::validate_mnemonic(""),
+            Err(CryptoError::InvalidMnemonic(MnemonicError::WrongWordCount(
+                0
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_mnemonic_bip39_vectors() {
+        // Entropy → mnemonic vectors from the BIP-39 reference test suite
+        let vectors: [(&str, &str); 5] = [
+            (
+                "00000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            ),
+            (
+                "80808080808080808080808080808080",
+                "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                BIP39_TEST_MNEMONIC_24,
+            ),
+        ];
+
+        for (entropy, expected) in vectors {
+            let mnemonic = mnemonic_from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(mnemonic.as_str(), expected);
+            assert!(MasterSeed::validate_mnemonic(expected).is_ok());
+        }
+
+        // Seed for the all-zero 12-word vector with an empty passphrase
+        let seed = MasterSeed::from_mnemonic(vectors[0].1).unwrap();
+        assert_eq!(
+            hex::encode(seed.as_bytes()),
+            "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc1\
+             9a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4"
+        );
+    }
+
     #[test]
     fn test_master_seed_debug_redacted() {
         let seed = MasterSeed::from_mnemonic(BIP39_TEST_MNEMONIC_24).unwrap();
         let debug_str = format!("{:?}", seed);
-        assert_eq!(debug_str, "MasterSeed([REDACTED])");
+        assert_eq!(debug_str, "MasterSeed([REDACTED; 64 bytes])");
         assert!(!debug_str.contains("abandon"));
     }
 
@@ -443,7 +779,7 @@ mod tests {
         let seed = MasterSeed::from_mnemonic(BIP39_TEST_MNEMONIC_24).unwrap();
         let ik = seed.derive_identity_key();
         let debug_str = format!("{:?}", ik);
-        assert_eq!(debug_str, "IdentityKey([REDACTED])");
+        assert_eq!(debug_str, "IdentityKey([REDACTED; 32 bytes])");
     }
 
     // ── Recovery Key Derivation Tests ───────────────────────────────────────
@@ -468,7 +804,23 @@ mod tests {
         let seed = MasterSeed::from_mnemonic(BIP39_TEST_MNEMONIC_24).unwrap();
         let rk = seed.derive_recovery_key();
         let debug_str = format!("{:?}", rk);
-        assert_eq!(debug_str, "RecoveryKey([REDACTED])");
+        assert_eq!(debug_str, "RecoveryKey([REDACTED; 32 bytes])");
+    }
+
+    #[test]
+    fn test_derive_anchor_keypair_deterministic() {
+        let seed = MasterSeed::from_mnemonic(BIP39_TEST_MNEMONIC_24).unwrap();
+        let a = seed.derive_recovery_key().derive_anchor_keypair();
+        let b = seed.derive_recovery_key().derive_anchor_keypair();
+        assert_eq!(a.public, b.public);
+
+        // The re-derived secret key opens a ciphertext for the first one
+        let (ss, ct) = KyberKEM::encapsulate(&a.public).unwrap();
+        let opened = KyberKEM::decapsulate(&b.secret, &ct).unwrap();
+        assert_eq!(ss.as_bytes(), opened.as_bytes());
+
+        let other = RecoveryKey::from_bytes([0x5Au8; 32]).derive_anchor_keypair();
+        assert_ne!(a.public, other.public);
     }
 
     // ── Context Isolation Tests ─────────────────────────────────────────────
@@ -563,7 +915,7 @@ mod tests {
     fn test_dek_debug_redacted() {
         let dek = DataEncryptionKey::from_bytes([0u8; 32]);
         let debug_str = format!("{:?}", dek);
-        assert_eq!(debug_str, "DataEncryptionKey([REDACTED])");
+        assert_eq!(debug_str, "DataEncryptionKey([REDACTED; 32 bytes])");
     }
 
     #[test]
@@ -574,6 +926,53 @@ mod tests {
 
     // ── VaultKey Tests ──────────────────────────────────────────────────────
 
+    #[test]
+    fn test_dek_wrap_unwrap_roundtrip() {
+        let keypair = KyberKEM::generate_keypair();
+        let dek = DataEncryptionKey::generate();
+
+        let wrapped = dek.wrap_for_device(&keypair.public).unwrap();
+        assert_eq!(wrapped.aead_ciphertext.len(), 32 + 16);
+
+        let unwrapped = DataEncryptionKey::unwrap(&wrapped, &keypair.secret).unwrap();
+        assert_eq!(unwrapped.as_bytes(), dek.as_bytes());
+    }
+
+    #[test]
+    fn test_dek_unwrap_wrong_secret_key_fails() {
+        let keypair = KyberKEM::generate_keypair();
+        let other = KyberKEM::generate_keypair();
+        let wrapped = DataEncryptionKey::generate()
+            .wrap_for_device(&keypair.public)
+            .unwrap();
+
+        let result = DataEncryptionKey::unwrap(&wrapped, &other.secret);
+        assert!(matches!(result, Err(CryptoError::AeadError(_))));
+    }
+
+    #[test]
+    fn test_dek_unwrap_tampered_kem_ciphertext_fails() {
+        let keypair = KyberKEM::generate_keypair();
+        let mut wrapped = DataEncryptionKey::generate()
+            .wrap_for_device(&keypair.public)
+            .unwrap();
+        wrapped.kem_ciphertext.0[0] ^= 0x01;
+
+        let result = DataEncryptionKey::unwrap(&wrapped, &keypair.secret);
+        assert!(matches!(result, Err(CryptoError::AeadError(_))));
+    }
+
+    #[test]
+    fn test_dek_wrap_context_stability_vector() {
+        assert_eq!(DEK_WRAP_CONTEXT, "Aeternum_DEK_Wrap_v1");
+
+        let key = dek_wrap_key(&KyberSharedSecret::from_bytes(&[0x42; 32]).unwrap());
+        assert_eq!(
+            hex::encode(key.as_bytes()),
+            "c360e1cd94d10d48533dab329acf0c441d2728b3955d9bc205d97a04f0c02503"
+        );
+    }
+
     #[test]
     fn test_vk_from_bytes() {
         let bytes = [0x99u8; 32];
@@ -585,7 +984,7 @@ mod tests {
     fn test_vk_debug_redacted() {
         let vk = VaultKey::from_bytes([0u8; 32]);
         let debug_str = format!("{:?}", vk);
-        assert_eq!(debug_str, "VaultKey([REDACTED])");
+        assert_eq!(debug_str, "VaultKey([REDACTED; 32 bytes])");
     }
 
     #[test]
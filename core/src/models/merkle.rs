@@ -0,0 +1,301 @@
+//! # Header Merkle Tree
+//!
+//! BLAKE3 Merkle tree over a device header set, for cheap sync diffing.
+//!
+//! [`headers_digest`](super::device::headers_digest) tells two devices
+//! *whether* their header sets differ; the tree also tells them *where*.
+//! Peers exchange 32-byte roots first and only walk down (or compare
+//! [`MerkleTree::leaves`]) when the roots disagree, so an unchanged fleet
+//! costs one hash on the wire and a changed one costs the differing
+//! headers.
+//!
+//! ## Construction
+//!
+//! Leaves are the serialized [`DeviceHeader`]s sorted by [`DeviceId`]
+//! bytes, so the root does not depend on map iteration order.
+//!
+//! ```text
+//! leaf = BLAKE3(0x00 || DeviceHeader::serialize())
+//! node = BLAKE3(0x01 || left || right)
+//! ```
+//!
+//! The prefixes keep a leaf from being passed off as an inner node. An
+//! odd node at the end of a level is carried up unchanged. The root of
+//! the empty set is `BLAKE3("")`.
+
+use crate::crypto::hash::{hash, Blake3Hasher, HashOutput};
+use crate::models::device::{DeviceHeader, DeviceId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Domain prefix of a leaf hash
+const LEAF_PREFIX: u8 = 0x00;
+
+/// Domain prefix of an inner node hash
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash of one serialized header
+fn leaf_hash(header: &DeviceHeader) -> [u8; 32] {
+    let mut hasher = Blake3Hasher::new();
+    hasher.update(&[LEAF_PREFIX]).update(&header.serialize());
+    *hasher.finalize().as_bytes()
+}
+
+/// Hash of two child nodes
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Blake3Hasher::new();
+    hasher.update(&[NODE_PREFIX]).update(left).update(right);
+    *hasher.finalize().as_bytes()
+}
+
+/// Merkle tree over a device header set
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::models::merkle::MerkleTree;
+/// use std::collections::HashMap;
+///
+/// let empty = MerkleTree::from_headers(&HashMap::new());
+/// assert!(empty.is_empty());
+/// assert_eq!(empty.root().as_bytes().len(), 32);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// Leaf device IDs, sorted
+    ids: Vec<DeviceId>,
+    /// `levels[0]` are the leaf hashes; the last level holds the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Build the tree over `headers`, keyed by device ID
+    pub fn from_headers(headers: &HashMap<DeviceId, DeviceHeader>) -> Self {
+        let mut entries: Vec<(&DeviceId, &DeviceHeader)> = headers.iter().collect();
+        entries.sort_by_key(|(id, _)| id.0);
+
+        let ids = entries.iter().map(|(id, _)| **id).collect();
+        let mut levels = vec![entries
+            .iter()
+            .map(|(_, header)| leaf_hash(header))
+            .collect::<Vec<_>>()];
+
+        while levels.last().map_or(0, Vec::len) > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [odd] => *odd,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { ids, levels }
+    }
+
+    /// Root hash; equal roots mean equal header sets
+    pub fn root(&self) -> HashOutput {
+        match self.levels.last().and_then(|level| level.first()) {
+            Some(root) => HashOutput::from_bytes(*root),
+            None => hash(&[]),
+        }
+    }
+
+    /// Number of leaves (headers)
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether the tree has no leaves
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Leaves in tree order, as `(device_id, leaf_hash)`
+    pub fn leaves(&self) -> impl Iterator<Item = (&DeviceId, &[u8; 32])> {
+        self.ids.iter().zip(&self.levels[0])
+    }
+
+    /// Devices whose header differs between `self` and `other`
+    ///
+    /// Includes devices present in only one of the trees. Empty when the
+    /// roots match.
+    pub fn diff(&self, other: &MerkleTree) -> Vec<DeviceId> {
+        if self.root() == other.root() {
+            return Vec::new();
+        }
+
+        let theirs: HashMap<&DeviceId, &[u8; 32]> = other.leaves().collect();
+        let mut differing: Vec<DeviceId> = self
+            .leaves()
+            .filter(|(id, leaf)| theirs.get(id) != Some(leaf))
+            .map(|(id, _)| *id)
+            .collect();
+        differing.extend(other.ids.iter().filter(|id| !self.ids.contains(id)));
+        differing.sort_by_key(|id| id.0);
+        differing
+    }
+
+    /// Inclusion proof for the header of `device_id`
+    ///
+    /// Returns `None` if the device is not in the tree.
+    pub fn proof(&self, device_id: &DeviceId) -> Option<MerkleProof> {
+        let mut index = self.ids.iter().position(|id| id == device_id)?;
+        let mut path = Vec::new();
+
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = index ^ 1;
+            if let Some(hash) = level.get(sibling) {
+                path.push(ProofStep {
+                    sibling: *hash,
+                    sibling_is_left: sibling < index,
+                });
+            }
+            index /= 2;
+        }
+
+        Some(MerkleProof {
+            device_id: *device_id,
+            path,
+        })
+    }
+}
+
+/// One step of a [`MerkleProof`], from the leaf towards the root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStep {
+    /// Hash of the sibling node
+    pub sibling: [u8; 32],
+    /// Whether the sibling is the left child
+    pub sibling_is_left: bool,
+}
+
+/// Inclusion proof of one header in a [`MerkleTree`]
+///
+/// Levels where the node was carried up without a sibling have no step.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Device the proof is for
+    pub device_id: DeviceId,
+    /// Sibling hashes from the leaf level upwards
+    pub path: Vec<ProofStep>,
+}
+
+/// Check that `header` is a leaf of the tree with root `root`
+///
+/// Fails if the header does not belong to the proof's device, or if the
+/// header or any step of the proof was altered.
+pub fn verify_proof(root: &HashOutput, header: &DeviceHeader, proof: &MerkleProof) -> bool {
+    if header.device_id != proof.device_id {
+        return false;
+    }
+
+    let computed = proof.path.iter().fold(leaf_hash(header), |node, step| {
+        if step.sibling_is_left {
+            node_hash(&step.sibling, &node)
+        } else {
+            node_hash(&node, &step.sibling)
+        }
+    });
+    computed == *root.as_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::kem::{KyberCipherText, KyberPublicKeyBytes};
+    use crate::models::epoch::CryptoEpoch;
+
+    fn header(n: u8) -> DeviceHeader {
+        let mut header = DeviceHeader::new(
+            DeviceId::from_bytes([n; 16]),
+            CryptoEpoch::initial(),
+            KyberPublicKeyBytes([n; 1568]),
+            KyberCipherText([n.wrapping_add(1); 1568]),
+        );
+        // Fixed timestamps so equal inputs serialize identically
+        header.created_at = 1_000;
+        header.epoch.timestamp = 1_000;
+        header
+    }
+
+    fn header_set(order: &[u8]) -> HashMap<DeviceId, DeviceHeader> {
+        order
+            .iter()
+            .map(|&n| (header(n).device_id, header(n)))
+            .collect()
+    }
+
+    #[test]
+    fn test_root_independent_of_insertion_order() {
+        let a = MerkleTree::from_headers(&header_set(&[1, 2, 3, 4, 5]));
+        let b = MerkleTree::from_headers(&header_set(&[5, 3, 1, 4, 2]));
+        assert_eq!(a.root(), b.root());
+        assert_eq!(a.len(), 5);
+
+        let ids: Vec<u8> = a.leaves().map(|(id, _)| id.0[0]).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+
+        let c = MerkleTree::from_headers(&header_set(&[1, 2, 3, 4]));
+        assert_ne!(a.root(), c.root());
+        assert_eq!(MerkleTree::from_headers(&HashMap::new()).root(), hash(&[]));
+    }
+
+    #[test]
+    fn test_inclusion_proof_for_every_leaf() {
+        for count in 1..=7u8 {
+            let headers = header_set(&(1..=count).collect::<Vec<_>>());
+            let tree = MerkleTree::from_headers(&headers);
+            let root = tree.root();
+
+            for (id, header) in &headers {
+                let proof = tree.proof(id).unwrap();
+                assert!(verify_proof(&root, header, &proof), "{} leaves", count);
+            }
+        }
+        assert!(MerkleTree::from_headers(&header_set(&[1]))
+            .proof(&DeviceId::from_bytes([9; 16]))
+            .is_none());
+    }
+
+    #[test]
+    fn test_tampered_proof_rejected() {
+        let headers = header_set(&[1, 2, 3, 4, 5]);
+        let tree = MerkleTree::from_headers(&headers);
+        let root = tree.root();
+        let target = header(3);
+        let proof = tree.proof(&target.device_id).unwrap();
+
+        let mut bad_sibling = proof.clone();
+        bad_sibling.path[0].sibling[0] ^= 0x01;
+        assert!(!verify_proof(&root, &target, &bad_sibling));
+
+        let mut bad_side = proof.clone();
+        bad_side.path[0].sibling_is_left = !bad_side.path[0].sibling_is_left;
+        assert!(!verify_proof(&root, &target, &bad_side));
+
+        let mut bad_header = header(3);
+        bad_header.dek_nonce[0] ^= 0x01;
+        assert!(!verify_proof(&root, &bad_header, &proof));
+
+        // A valid proof for another device does not vouch for this header
+        assert!(!verify_proof(&root, &header(2), &proof));
+    }
+
+    #[test]
+    fn test_diff_reports_changed_and_missing_devices() {
+        let ours = header_set(&[1, 2, 3, 4]);
+        let mut theirs = header_set(&[1, 2, 3, 5]);
+        theirs.get_mut(&header(2).device_id).unwrap().dek_nonce = [0xEE; 24];
+
+        let a = MerkleTree::from_headers(&ours);
+        let b = MerkleTree::from_headers(&theirs);
+        let differing: Vec<u8> = a.diff(&b).iter().map(|id| id.0[0]).collect();
+        assert_eq!(differing, vec![2, 4, 5]);
+        assert!(a.diff(&a.clone()).is_empty());
+    }
+}
@@ -8,7 +8,9 @@
 //! - `key_hierarchy` - Master Root Seed and derived key types (IK, RK, DEK, VK)
 //! - `epoch` - Cryptographic epoch and algorithm versioning
 //! - `device` - Device identifiers and headers
-//! - `vault` - Encrypted data containers (VaultBlob, VaultHeader)
+//! - `vault` - Encrypted data containers (VaultBlob, VaultHeader) and vault items
+//! - `merkle` - BLAKE3 Merkle tree over device headers for sync diffing
+//! - `cbor` - Self-describing CBOR encoding of stored models (`cbor` feature)
 //!
 //! ## Design Principles
 //!
@@ -26,15 +28,54 @@
 #![warn(missing_docs)]
 
 // Sub-modules (will be implemented in subsequent phases)
+#[cfg(feature = "cbor")]
+pub mod cbor;
 pub mod device;
 pub mod epoch;
 pub mod key_hierarchy;
+pub mod merkle;
 pub mod vault;
 
 // Re-export common types for convenience
-pub use device::{DeviceHeader, DeviceId, DeviceStatus, Operation, Role};
+pub use device::{
+    canonical_serialize, headers_digest, DegradeReason, DegradedInfo, DeviceHeader, DeviceId,
+    DeviceStatus, Operation, Role,
+};
 pub use epoch::{CryptoAlgorithm, CryptoEpoch};
 pub use key_hierarchy::{
-    DataEncryptionKey, DeviceKey, IdentityKey, MasterSeed, RecoveryKey, VaultKey,
+    DataEncryptionKey, DeviceKey, IdentityKey, MasterSeed, MnemonicLength, RecoveryKey, VaultKey,
+    WrappedDek,
 };
-pub use vault::{VaultBlob, VaultHeader};
+pub use merkle::{verify_proof, MerkleProof, MerkleTree, ProofStep};
+pub use vault::items::{EncryptedItem, ItemId, VaultContents};
+pub use vault::{VaultBlob, VaultFormatError, VaultHeader, VaultMetadata};
+
+/// Decode bincode written by `bincode::serialize` from untrusted bytes
+///
+/// Same encoding as `bincode::deserialize`, but length prefixes larger than
+/// the remaining input are rejected before anything is allocated, so a
+/// forged length field cannot trigger a huge allocation.
+pub(crate) fn decode_bounded<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> bincode::Result<T> {
+    use bincode::Options;
+
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(bytes.len() as u64)
+        .deserialize(bytes)
+}
+
+/// Like [`decode_bounded`], but advances `bytes` past the decoded value
+///
+/// For formats that append version-dependent fields after a value.
+pub(crate) fn decode_bounded_from<T: serde::de::DeserializeOwned>(
+    bytes: &mut &[u8],
+) -> bincode::Result<T> {
+    use bincode::Options;
+
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+        .with_limit(bytes.len() as u64)
+        .deserialize_from(bytes)
+}
@@ -0,0 +1,248 @@
+//! Plaintext compression for vault blobs
+//!
+//! Vault payloads are mostly JSON and shrink 5-10x under zstd, which
+//! shortens BLE sync and cuts flash writes. A blob can therefore carry its
+//! plaintext compressed before encryption:
+//!
+//! ```text
+//! ciphertext = STREAM(VK, zstd(plaintext), aad = COMPRESSION_AAD || algo || decompressed_len)
+//! ```
+//!
+//! ## Safety controls
+//!
+//! - **Off by default.** Compressing before encrypting makes the
+//!   ciphertext length depend on the plaintext's content, not just its
+//!   size, so an observer of successive blobs learns how compressible the
+//!   vault is. Vault contents are not attacker-influenced the way a web
+//!   response is, so this is a length-class leak rather than a CRIME-style
+//!   oracle, but it is a leak; callers opt in per upgrade.
+//! - **Declared size.** The decompressed length is stored in the blob and
+//!   authenticated as AEAD associated data together with the algorithm.
+//!   Decompression stops one byte past the declared length and rejects any
+//!   other length, and no declared length above
+//!   [`MAX_DECOMPRESSED_LEN`] is accepted, so a crafted frame cannot
+//!   expand without bound.
+//! - **Small payloads bypass.** Payloads under [`MIN_COMPRESS_LEN`] bytes
+//!   are stored uncompressed; the frame overhead would outweigh the gain.
+//!   Payloads that do not shrink are stored uncompressed as well.
+//!
+//! Uncompressed blobs use no associated data, so their ciphertext is the
+//! same as before compression existed. Only blob_version 2 records the
+//! compression fields; version 1 blobs are never compressed.
+
+use crate::crypto::error::{CryptoError, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+
+/// Payloads shorter than this are never compressed
+pub const MIN_COMPRESS_LEN: usize = 256;
+
+/// Largest decompressed payload a blob may declare (256 MiB)
+pub const MAX_DECOMPRESSED_LEN: u64 = 256 * 1024 * 1024;
+
+/// Default zstd level for [`CompressionAlgo::zstd_default`]
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Associated data prefix binding the compression parameters
+const COMPRESSION_AAD: &[u8] = b"Aeternum_VaultBlobCompression_v1";
+
+/// Compression applied to a blob's plaintext before encryption
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompressionAlgo {
+    /// Stored as is
+    #[default]
+    None,
+    /// zstd at the given level
+    Zstd {
+        /// Compression level (only used when compressing)
+        level: i32,
+    },
+}
+
+impl CompressionAlgo {
+    /// zstd at [`DEFAULT_ZSTD_LEVEL`]
+    #[must_use]
+    pub const fn zstd_default() -> Self {
+        Self::Zstd {
+            level: DEFAULT_ZSTD_LEVEL,
+        }
+    }
+
+    /// Whether the plaintext is stored compressed
+    #[must_use]
+    pub const fn is_compressed(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+
+    /// Stable wire code of the algorithm
+    const fn code(&self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Zstd { .. } => 1,
+        }
+    }
+
+    /// Size of the bincode encoding (variant tag plus fields)
+    pub(crate) const fn encoded_len(&self) -> usize {
+        match self {
+            Self::None => 4,
+            Self::Zstd { .. } => 4 + 4,
+        }
+    }
+
+    /// AEAD associated data for a blob compressed with `self`
+    ///
+    /// Empty for [`CompressionAlgo::None`], so uncompressed blobs keep
+    /// their original encryption.
+    pub(crate) fn aad(&self, decompressed_len: u64) -> Vec<u8> {
+        match self {
+            Self::None => Vec::new(),
+            Self::Zstd { level } => [
+                COMPRESSION_AAD,
+                &[self.code()],
+                &level.to_be_bytes(),
+                &decompressed_len.to_be_bytes(),
+            ]
+            .concat(),
+        }
+    }
+}
+
+/// Compress `data` with `algo` where it pays off
+///
+/// Returns the algorithm actually applied and the bytes to encrypt.
+/// Falls back to [`CompressionAlgo::None`] for payloads under
+/// [`MIN_COMPRESS_LEN`], over [`MAX_DECOMPRESSED_LEN`], or that do not
+/// shrink.
+///
+/// # Errors
+///
+/// Returns `CryptoError::InternalError` if the zstd level is out of range
+/// or compression fails.
+pub(crate) fn compress(algo: CompressionAlgo, data: &[u8]) -> Result<(CompressionAlgo, Vec<u8>)> {
+    let CompressionAlgo::Zstd { level } = algo else {
+        return Ok((CompressionAlgo::None, data.to_vec()));
+    };
+    if !zstd::compression_level_range().contains(&level) {
+        return Err(CryptoError::internal(format!(
+            "Invalid zstd level {}",
+            level
+        )));
+    }
+    if data.len() < MIN_COMPRESS_LEN || data.len() as u64 > MAX_DECOMPRESSED_LEN {
+        return Ok((CompressionAlgo::None, data.to_vec()));
+    }
+
+    let compressed = zstd::bulk::compress(data, level)
+        .map_err(|e| CryptoError::internal(format!("zstd compression failed: {}", e)))?;
+    if compressed.len() >= data.len() {
+        return Ok((CompressionAlgo::None, data.to_vec()));
+    }
+    Ok((algo, compressed))
+}
+
+/// Reverse [`compress`], enforcing the declared size
+///
+/// # Errors
+///
+/// Returns `CryptoError::InternalError` if `declared_len` exceeds
+/// [`MAX_DECOMPRESSED_LEN`], the frame is invalid, or it does not expand
+/// to exactly `declared_len` bytes.
+pub(crate) fn decompress(algo: CompressionAlgo, data: &[u8], declared_len: u64) -> Result<Vec<u8>> {
+    if !algo.is_compressed() {
+        return Ok(data.to_vec());
+    }
+    if declared_len > MAX_DECOMPRESSED_LEN {
+        return Err(CryptoError::internal(format!(
+            "Declared decompressed size {} exceeds limit {}",
+            declared_len, MAX_DECOMPRESSED_LEN
+        )));
+    }
+
+    let decoder = zstd::stream::read::Decoder::with_buffer(data)
+        .map_err(|e| CryptoError::internal(format!("zstd decompression failed: {}", e)))?;
+    // Read at most one byte past the declared size: enough to detect a lie
+    // without letting the frame expand further
+    let mut out = Vec::with_capacity(declared_len as usize);
+    decoder
+        .take(declared_len + 1)
+        .read_to_end(&mut out)
+        .map_err(|e| CryptoError::internal(format!("zstd decompression failed: {}", e)))?;
+
+    if out.len() as u64 != declared_len {
+        return Err(CryptoError::internal(format!(
+            "Decompressed size does not match declared size {}",
+            declared_len
+        )));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_like(len: usize) -> Vec<u8> {
+        br#"{"site":"example.com","user":"alice","password":"hunter2"},"#
+            .iter()
+            .copied()
+            .cycle()
+            .take(len)
+            .collect()
+    }
+
+    #[test]
+    fn test_compress_roundtrip() {
+        let data = json_like(10_000);
+        let (algo, compressed) = compress(CompressionAlgo::zstd_default(), &data).unwrap();
+        assert_eq!(algo, CompressionAlgo::zstd_default());
+        assert!(compressed.len() * 5 < data.len());
+        assert_eq!(
+            decompress(algo, &compressed, data.len() as u64).unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn test_declared_size_lie_rejected() {
+        let data = json_like(4_096);
+        let (algo, compressed) = compress(CompressionAlgo::zstd_default(), &data).unwrap();
+
+        for declared in [0, 4_095, 4_097, 1_000_000] {
+            assert!(decompress(algo, &compressed, declared).is_err());
+        }
+        assert!(decompress(algo, &compressed, MAX_DECOMPRESSED_LEN + 1).is_err());
+    }
+
+    #[test]
+    fn test_bypass_small_and_incompressible_payloads() {
+        let small = json_like(MIN_COMPRESS_LEN - 1);
+        let (algo, stored) = compress(CompressionAlgo::zstd_default(), &small).unwrap();
+        assert_eq!(algo, CompressionAlgo::None);
+        assert_eq!(stored, small);
+
+        let random: Vec<u8> = (0..4_096).map(|_| rand::random()).collect();
+        let (algo, stored) = compress(CompressionAlgo::zstd_default(), &random).unwrap();
+        assert_eq!(algo, CompressionAlgo::None);
+        assert_eq!(stored, random);
+
+        assert!(compress(CompressionAlgo::Zstd { level: 1_000 }, &json_like(1_000)).is_err());
+    }
+
+    #[test]
+    fn test_aad_binds_parameters() {
+        assert!(CompressionAlgo::None.aad(1_000).is_empty());
+
+        let algo = CompressionAlgo::zstd_default();
+        assert_ne!(algo.aad(1_000), algo.aad(1_001));
+        assert_ne!(
+            algo.aad(1_000),
+            CompressionAlgo::Zstd { level: 4 }.aad(1_000)
+        );
+        assert_eq!(bincode::serialize(&algo).unwrap().len(), algo.encoded_len());
+        assert_eq!(
+            bincode::serialize(&CompressionAlgo::None).unwrap().len(),
+            CompressionAlgo::None.encoded_len()
+        );
+    }
+}
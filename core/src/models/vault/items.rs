@@ -0,0 +1,544 @@
+//! Vault items with per-item encryption
+//!
+//! [`VaultContents`] stores each item as its own AEAD ciphertext, so
+//! adding, replacing or deleting one item re-encrypts only that item and
+//! the index, never the rest of the vault.
+//!
+//! ## Layout
+//!
+//! ```text
+//! item   = nonce (24) || XChaCha20-Poly1305(DEK, plaintext, aad = ITEM_AAD || id)
+//! index  = { id -> BLAKE3(nonce || ciphertext) }
+//! tag    = Poly1305 tag of XChaCha20-Poly1305(DEK, "", aad = INDEX_AAD || index)
+//! ```
+//!
+//! Every item has a fresh random nonce. Binding the ID into the item's
+//! associated data means a ciphertext moved to another ID no longer
+//! decrypts.
+//!
+//! ## Integrity
+//!
+//! - A modified item fails its index digest and AEAD tag; other items
+//!   still decrypt.
+//! - The index is authenticated as a whole, so removing an item together
+//!   with its index entry breaks the index tag, and removing only the item
+//!   leaves a dangling index entry. Both are reported by
+//!   [`VaultContents::verify_index`].
+//!
+//! ## Serialization
+//!
+//! [`VaultContents::to_blob`] packs the contents into a [`VaultBlob`]: the
+//! items and index are the blob's `ciphertext`, and the index nonce and tag
+//! are its `nonce` and `auth_tag`.
+
+use crate::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce, NONCE_SIZE, TAG_SIZE};
+use crate::crypto::ct::ct_eq;
+use crate::crypto::hash::hash;
+use crate::models::epoch::CryptoEpoch;
+use crate::models::key_hierarchy::DataEncryptionKey;
+use crate::models::vault::{VaultBlob, VaultFormatError, CURRENT_BLOB_VERSION};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// Associated data prefix for item ciphertexts
+const ITEM_AAD: &[u8] = b"Aeternum_VaultItem_v1";
+
+/// Associated data prefix for the index tag
+const INDEX_AAD: &[u8] = b"Aeternum_VaultItemIndex_v1";
+
+type Result<T> = std::result::Result<T, VaultFormatError>;
+
+/// Identifier of a vault item
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ItemId(String);
+
+impl ItemId {
+    /// Create an item ID
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// The ID as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for ItemId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ItemId({:?})", self.0)
+    }
+}
+
+impl fmt::Display for ItemId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// One encrypted item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedItem {
+    /// XChaCha20 nonce, random per encryption
+    pub nonce: [u8; NONCE_SIZE],
+    /// Ciphertext including the Poly1305 tag
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedItem {
+    /// BLAKE3 digest recorded in the index
+    pub fn digest(&self) -> [u8; 32] {
+        let mut input = Vec::with_capacity(NONCE_SIZE + self.ciphertext.len());
+        input.extend_from_slice(&self.nonce);
+        input.extend_from_slice(&self.ciphertext);
+        *hash(&input).as_bytes()
+    }
+}
+
+/// Items and index as stored in the blob's `ciphertext`
+#[derive(Serialize, Deserialize)]
+struct StoredContents {
+    items: BTreeMap<ItemId, EncryptedItem>,
+    index: BTreeMap<ItemId, [u8; 32]>,
+}
+
+/// Individually encrypted vault items with an authenticated index
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::models::key_hierarchy::DataEncryptionKey;
+/// use aeternum_core::models::vault::items::{ItemId, VaultContents};
+///
+/// let dek = DataEncryptionKey::generate();
+/// let mut contents = VaultContents::new(&dek)?;
+///
+/// let id = ItemId::new("login/example.com");
+/// contents.put(&dek, id.clone(), b"hunter2")?;
+/// assert_eq!(contents.get(&dek, &id)?.unwrap().as_slice(), b"hunter2");
+///
+/// assert!(contents.delete(&dek, &id)?);
+/// assert!(contents.get(&dek, &id)?.is_none());
+/// # Ok::<(), aeternum_core::models::vault::VaultFormatError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct VaultContents {
+    /// Encrypted items by ID
+    pub items: BTreeMap<ItemId, EncryptedItem>,
+    /// Digest of every item, authenticated by `index_tag`
+    index: BTreeMap<ItemId, [u8; 32]>,
+    /// Nonce of the index tag
+    index_nonce: [u8; NONCE_SIZE],
+    /// Tag over the index
+    index_tag: [u8; TAG_SIZE],
+}
+
+impl VaultContents {
+    /// Create empty contents with a sealed empty index
+    ///
+    /// # Errors
+    ///
+    /// Returns `VaultFormatError::CryptoFailed` if sealing the index fails.
+    pub fn new(dek: &DataEncryptionKey) -> Result<Self> {
+        let mut contents = Self {
+            items: BTreeMap::new(),
+            index: BTreeMap::new(),
+            index_nonce: [0u8; NONCE_SIZE],
+            index_tag: [0u8; TAG_SIZE],
+        };
+        contents.seal_index(dek)?;
+        Ok(contents)
+    }
+
+    /// Encrypt `plaintext` as item `id`, replacing any previous value
+    ///
+    /// Only this item and the index are re-encrypted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VaultFormatError::CryptoFailed` if encryption fails.
+    pub fn put(&mut self, dek: &DataEncryptionKey, id: ItemId, plaintext: &[u8]) -> Result<()> {
+        let nonce = XChaCha20Nonce::random();
+        let ciphertext = cipher(dek)?
+            .encrypt(&nonce, plaintext, Some(&item_aad(&id)))
+            .map_err(|e| VaultFormatError::crypto(format!("Item encryption failed: {}", e)))?;
+        let item = EncryptedItem {
+            nonce: *nonce.as_bytes(),
+            ciphertext,
+        };
+
+        self.index.insert(id.clone(), item.digest());
+        self.items.insert(id, item);
+        self.seal_index(dek)
+    }
+
+    /// Decrypt item `id`, or `None` if the index does not list it
+    ///
+    /// # Errors
+    ///
+    /// - `VaultFormatError::ConsistencyCheckFailed` if the index lists the item
+    ///   but it is missing
+    /// - `VaultFormatError::AuthenticationFailed` if the item does not match its
+    ///   index digest or fails to decrypt
+    pub fn get(&self, dek: &DataEncryptionKey, id: &ItemId) -> Result<Option<Zeroizing<Vec<u8>>>> {
+        let Some(digest) = self.index.get(id) else {
+            return Ok(None);
+        };
+        let item = self.items.get(id).ok_or_else(|| {
+            VaultFormatError::consistency_check(format!("Item {} is indexed but missing", id))
+        })?;
+        if !ct_eq(&item.digest(), digest) {
+            return Err(VaultFormatError::authentication(format!(
+                "Item {} does not match its index digest",
+                id
+            )));
+        }
+
+        cipher(dek)?
+            .decrypt(
+                &XChaCha20Nonce::from_bytes(item.nonce),
+                &item.ciphertext,
+                Some(&item_aad(id)),
+            )
+            .map(|plaintext| Some(Zeroizing::new(plaintext)))
+            .map_err(|_| VaultFormatError::authentication(format!("Item {} failed to decrypt", id)))
+    }
+
+    /// Delete item `id`, returning whether it existed
+    ///
+    /// # Errors
+    ///
+    /// Returns `VaultFormatError::CryptoFailed` if resealing the index fails.
+    pub fn delete(&mut self, dek: &DataEncryptionKey, id: &ItemId) -> Result<bool> {
+        let existed = self.index.remove(id).is_some() | self.items.remove(id).is_some();
+        if existed {
+            self.seal_index(dek)?;
+        }
+        Ok(existed)
+    }
+
+    /// IDs of all indexed items, in order
+    pub fn ids(&self) -> impl Iterator<Item = &ItemId> {
+        self.index.keys()
+    }
+
+    /// Number of indexed items
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Whether the index is empty
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Check the index tag and that index and items list the same IDs
+    ///
+    /// Per-item digests are checked lazily by [`Self::get`], so one
+    /// damaged item does not make the others unreadable.
+    ///
+    /// # Errors
+    ///
+    /// - `VaultFormatError::AuthenticationFailed` if the index tag does not
+    ///   verify
+    /// - `VaultFormatError::ConsistencyCheckFailed` if an indexed item is
+    ///   missing or an item is not indexed
+    pub fn verify_index(&self, dek: &DataEncryptionKey) -> Result<()> {
+        let mut sealed = Vec::with_capacity(TAG_SIZE);
+        sealed.extend_from_slice(&self.index_tag);
+        cipher(dek)?
+            .decrypt(
+                &XChaCha20Nonce::from_bytes(self.index_nonce),
+                &sealed,
+                Some(&self.index_aad()),
+            )
+            .map_err(|_| VaultFormatError::authentication("Item index tag mismatch"))?;
+
+        if let Some(id) = self.index.keys().find(|id| !self.items.contains_key(id)) {
+            return Err(VaultFormatError::consistency_check(format!(
+                "Item {} is indexed but missing",
+                id
+            )));
+        }
+        if let Some(id) = self.items.keys().find(|id| !self.index.contains_key(id)) {
+            return Err(VaultFormatError::consistency_check(format!(
+                "Item {} is not indexed",
+                id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Re-encrypt every item and the index from `old_dek` to `new_dek`
+    ///
+    /// Used when an epoch upgrade replaces the DEK. Every item gets a fresh
+    /// nonce; the index is verified first.
+    ///
+    /// # Errors
+    ///
+    /// Any error of [`Self::verify_index`] or [`Self::get`] under
+    /// `old_dek`, or `VaultFormatError::CryptoFailed` if re-encryption fails.
+    pub fn rekey(&self, old_dek: &DataEncryptionKey, new_dek: &DataEncryptionKey) -> Result<Self> {
+        self.verify_index(old_dek)?;
+
+        let mut rekeyed = Self::new(new_dek)?;
+        for id in self.ids() {
+            let plaintext = self.get(old_dek, id)?.ok_or_else(|| {
+                VaultFormatError::consistency_check(format!("Item {} is indexed but missing", id))
+            })?;
+            rekeyed.put(new_dek, id.clone(), &plaintext)?;
+        }
+        Ok(rekeyed)
+    }
+
+    /// Pack the contents into a [`VaultBlob`] for `epoch`
+    ///
+    /// # Errors
+    ///
+    /// Returns `VaultFormatError::CryptoFailed` if serialization fails.
+    pub fn to_blob(&self, epoch: CryptoEpoch) -> Result<VaultBlob> {
+        let stored = StoredContents {
+            items: self.items.clone(),
+            index: self.index.clone(),
+        };
+        let ciphertext = bincode::serialize(&stored)
+            .map_err(|e| VaultFormatError::crypto(format!("Item serialization failed: {}", e)))?;
+        Ok(VaultBlob::new(
+            CURRENT_BLOB_VERSION,
+            epoch,
+            ciphertext,
+            self.index_tag,
+            self.index_nonce,
+        ))
+    }
+
+    /// Unpack contents written by [`Self::to_blob`] and verify the index
+    ///
+    /// # Errors
+    ///
+    /// Returns `VaultFormatError::CryptoFailed` if the blob cannot be decoded,
+    /// or any error of [`Self::verify_index`].
+    pub fn from_blob(blob: &VaultBlob, dek: &DataEncryptionKey) -> Result<Self> {
+        let stored: StoredContents = crate::models::decode_bounded(&blob.ciphertext)
+            .map_err(|e| VaultFormatError::crypto(format!("Item deserialization failed: {}", e)))?;
+        let contents = Self {
+            items: stored.items,
+            index: stored.index,
+            index_nonce: blob.nonce,
+            index_tag: blob.auth_tag,
+        };
+        contents.verify_index(dek)?;
+        Ok(contents)
+    }
+
+    /// Reseal the index under a fresh nonce
+    fn seal_index(&mut self, dek: &DataEncryptionKey) -> Result<()> {
+        let nonce = XChaCha20Nonce::random();
+        let tag = cipher(dek)?
+            .encrypt(&nonce, &[], Some(&self.index_aad()))
+            .map_err(|e| VaultFormatError::crypto(format!("Item index sealing failed: {}", e)))?;
+        self.index_nonce = *nonce.as_bytes();
+        self.index_tag.copy_from_slice(&tag);
+        Ok(())
+    }
+
+    /// Canonical encoding of the index, prefixed with `INDEX_AAD`
+    fn index_aad(&self) -> Vec<u8> {
+        let mut aad = INDEX_AAD.to_vec();
+        aad.extend_from_slice(&(self.index.len() as u64).to_le_bytes());
+        for (id, digest) in &self.index {
+            aad.extend_from_slice(&(id.0.len() as u64).to_le_bytes());
+            aad.extend_from_slice(id.0.as_bytes());
+            aad.extend_from_slice(digest);
+        }
+        aad
+    }
+}
+
+/// The DEK as an AEAD cipher
+fn cipher(dek: &DataEncryptionKey) -> Result<AeadCipher> {
+    XChaCha20Key::from_bytes(dek.as_bytes())
+        .map(|key| AeadCipher::new(&key))
+        .map_err(|e| VaultFormatError::crypto(format!("Invalid DEK: {}", e)))
+}
+
+/// Associated data binding an item ciphertext to its ID
+fn item_aad(id: &ItemId) -> Vec<u8> {
+    let mut aad = ITEM_AAD.to_vec();
+    aad.extend_from_slice(id.0.as_bytes());
+    aad
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(dek: &DataEncryptionKey) -> VaultContents {
+        let mut contents = VaultContents::new(dek).unwrap();
+        contents.put(dek, ItemId::new("a"), b"alpha").unwrap();
+        contents.put(dek, ItemId::new("b"), b"bravo").unwrap();
+        contents.put(dek, ItemId::new("c"), b"charlie").unwrap();
+        contents
+    }
+
+    #[test]
+    fn test_crud_roundtrip() {
+        let dek = DataEncryptionKey::generate();
+        let mut contents = sample(&dek);
+        let a = ItemId::new("a");
+
+        assert_eq!(
+            contents.get(&dek, &a).unwrap().unwrap().as_slice(),
+            b"alpha"
+        );
+        assert_eq!(
+            contents.ids().map(ItemId::as_str).collect::<Vec<_>>(),
+            ["a", "b", "c"]
+        );
+
+        // Replacing an item leaves the others untouched
+        let untouched = contents.items[&ItemId::new("b")].clone();
+        contents.put(&dek, a.clone(), b"alpha 2").unwrap();
+        assert_eq!(
+            contents.get(&dek, &a).unwrap().unwrap().as_slice(),
+            b"alpha 2"
+        );
+        assert_eq!(contents.items[&ItemId::new("b")], untouched);
+
+        assert!(contents.delete(&dek, &a).unwrap());
+        assert!(!contents.delete(&dek, &a).unwrap());
+        assert!(contents.get(&dek, &a).unwrap().is_none());
+        assert_eq!(contents.len(), 2);
+
+        // Through the VaultBlob container
+        let blob = contents.to_blob(CryptoEpoch::initial()).unwrap();
+        let bytes = blob.serialize().unwrap();
+        let reloaded =
+            VaultContents::from_blob(&VaultBlob::deserialize(&bytes).unwrap(), &dek).unwrap();
+        assert_eq!(
+            reloaded
+                .get(&dek, &ItemId::new("c"))
+                .unwrap()
+                .unwrap()
+                .as_slice(),
+            b"charlie"
+        );
+
+        // Another key cannot open it
+        let other = DataEncryptionKey::generate();
+        assert!(matches!(
+            VaultContents::from_blob(&blob, &other),
+            Err(VaultFormatError::AuthenticationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_rekey_moves_items_to_new_dek() {
+        let old_dek = DataEncryptionKey::generate();
+        let new_dek = DataEncryptionKey::generate();
+        let contents = sample(&old_dek);
+
+        let rekeyed = contents.rekey(&old_dek, &new_dek).unwrap();
+        rekeyed.verify_index(&new_dek).unwrap();
+        assert!(rekeyed.verify_index(&old_dek).is_err());
+        assert_eq!(
+            rekeyed.ids().collect::<Vec<_>>(),
+            contents.ids().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            rekeyed
+                .get(&new_dek, &ItemId::new("b"))
+                .unwrap()
+                .unwrap()
+                .as_slice(),
+            b"bravo"
+        );
+
+        // The old contents only open with the old DEK
+        assert!(contents.rekey(&new_dek, &old_dek).is_err());
+    }
+
+    #[test]
+    fn test_tampered_item_does_not_affect_others() {
+        let dek = DataEncryptionKey::generate();
+        let mut contents = sample(&dek);
+        contents
+            .items
+            .get_mut(&ItemId::new("b"))
+            .unwrap()
+            .ciphertext[0] ^= 0x01;
+
+        // The index itself is intact
+        contents.verify_index(&dek).unwrap();
+        assert!(matches!(
+            contents.get(&dek, &ItemId::new("b")),
+            Err(VaultFormatError::AuthenticationFailed(_))
+        ));
+        assert_eq!(
+            contents
+                .get(&dek, &ItemId::new("a"))
+                .unwrap()
+                .unwrap()
+                .as_slice(),
+            b"alpha"
+        );
+        assert_eq!(
+            contents
+                .get(&dek, &ItemId::new("c"))
+                .unwrap()
+                .unwrap()
+                .as_slice(),
+            b"charlie"
+        );
+    }
+
+    #[test]
+    fn test_swapped_item_rejected() {
+        let dek = DataEncryptionKey::generate();
+        let mut contents = sample(&dek);
+        let a = contents.items[&ItemId::new("a")].clone();
+        contents.items.insert(ItemId::new("b"), a.clone());
+        contents.index.insert(ItemId::new("b"), a.digest());
+
+        // Rewriting the index without the DEK breaks its tag
+        assert!(matches!(
+            contents.verify_index(&dek),
+            Err(VaultFormatError::AuthenticationFailed(_))
+        ));
+        // And the ciphertext is bound to its original ID
+        assert!(contents.get(&dek, &ItemId::new("b")).is_err());
+    }
+
+    #[test]
+    fn test_index_mac_detects_deletion_by_byte_surgery() {
+        let dek = DataEncryptionKey::generate();
+        let contents = sample(&dek);
+        let blob = contents.to_blob(CryptoEpoch::initial()).unwrap();
+
+        // Drop item "c" and its index entry from the serialized payload,
+        // re-encoding it exactly as an attacker editing the file would
+        let mut stored: StoredContents = bincode::deserialize(&blob.ciphertext).unwrap();
+        stored.items.remove(&ItemId::new("c"));
+        let only_item = bincode::serialize(&stored).unwrap();
+        stored.index.remove(&ItemId::new("c"));
+        let item_and_index = bincode::serialize(&stored).unwrap();
+
+        let mut forged = blob.clone();
+        forged.ciphertext = only_item;
+        assert!(matches!(
+            VaultContents::from_blob(&forged, &dek),
+            Err(VaultFormatError::ConsistencyCheckFailed(_))
+        ));
+
+        forged.ciphertext = item_and_index;
+        assert!(matches!(
+            VaultContents::from_blob(&forged, &dek),
+            Err(VaultFormatError::AuthenticationFailed(_))
+        ));
+
+        // The untouched blob still loads
+        VaultContents::from_blob(&blob, &dek).unwrap();
+    }
+}
@@ -0,0 +1,964 @@
+//! Vault Blob and encrypted storage containers
+//!
+//! This module defines data structures for encrypted vault storage,
+//! including VaultBlob (encrypted data container) and VaultHeader
+//! (file metadata).
+//!
+//! ## File Format
+//!
+//! The vault file consists of:
+//! - VaultHeader (32 bytes, fixed)
+//! - VaultBlob (variable length, serialized)
+//!
+//! ## Header Layout
+//!
+//! ```text
+//! magic (8) | blob_version u32 BE | epoch_version u64 BE
+//! | data_length u64 BE | checksum (4)
+//! ```
+//!
+//! The checksum is the first 4 bytes of BLAKE3 over bytes 0-27, so a
+//! corrupted header is rejected before its length or epoch is trusted.
+//! Headers written before the checksum existed have these bytes zeroed;
+//! a zeroed checksum is accepted only up to [`PRE_CHECKSUM_BLOB_VERSION`],
+//! since every later version was introduced after the checksum.
+//!
+//! ## Version Compatibility
+//!
+//! - blob_version 1: Initial format with V1 algorithms
+//! - blob_version 2: Adds V2 algorithms; a V2 epoch is never stored in a
+//!   version 1 blob. Appends the [`compression`] fields
+//!   (`compression`, `decompressed_len`) after the nonce, and seals the
+//!   ciphertext as a chunked STREAM instead of one AEAD message
+//! - Future versions must maintain backward compatibility for reading
+//!
+//! ## Items
+//!
+//! [`items`] layers individually encrypted vault items on top of the blob
+//! container.
+
+use crate::crypto::error::{CryptoError, Result};
+use crate::crypto::hash::hash;
+use crate::models::epoch::CryptoEpoch;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub mod compression;
+pub mod items;
+
+pub use compression::CompressionAlgo;
+
+/// Errors from parsing vault headers and opening vault items
+///
+/// The models layer does not depend on storage; `StorageError` converts
+/// from this at the storage boundary.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum VaultFormatError {
+    /// Header or item index is malformed or inconsistent
+    #[error("Consistency check failed: {0}")]
+    ConsistencyCheckFailed(String),
+
+    /// Header checksum does not match its contents
+    #[error("Header checksum mismatch: {0}")]
+    HeaderChecksumMismatch(String),
+
+    /// An item or the item index failed authentication
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+
+    /// Encryption or serialization failed
+    #[error("Crypto operation failed: {0}")]
+    CryptoFailed(String),
+}
+
+impl VaultFormatError {
+    /// Create a consistency check error from a string message
+    pub fn consistency_check(msg: impl Into<String>) -> Self {
+        Self::ConsistencyCheckFailed(msg.into())
+    }
+
+    /// Create a header checksum mismatch error from a string message
+    pub fn header_checksum(msg: impl Into<String>) -> Self {
+        Self::HeaderChecksumMismatch(msg.into())
+    }
+
+    /// Create an authentication error from a string message
+    pub fn authentication(msg: impl Into<String>) -> Self {
+        Self::AuthenticationFailed(msg.into())
+    }
+
+    /// Create a crypto operation error from a string message
+    pub fn crypto(msg: impl Into<String>) -> Self {
+        Self::CryptoFailed(msg.into())
+    }
+}
+
+/// Magic bytes for vault file identification (7 bytes + 1 byte padding)
+pub const VAULT_MAGIC: [u8; 8] = *b"AETERNM\0";
+
+/// Current vault blob format version
+pub const CURRENT_BLOB_VERSION: u32 = 2;
+
+/// Last blob format version whose headers may predate the checksum
+pub const PRE_CHECKSUM_BLOB_VERSION: u32 = 1;
+
+/// First blob format version that records compression
+pub const COMPRESSION_BLOB_VERSION: u32 = 2;
+
+/// First blob format version whose ciphertext is a chunked STREAM
+///
+/// Earlier blobs hold one XChaCha20-Poly1305 message without associated data.
+pub const STREAM_BLOB_VERSION: u32 = 2;
+
+/// Vault Blob - complete encrypted data container
+///
+/// This structure contains encrypted vault data along with
+/// all necessary metadata for decryption and verification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultBlob {
+    /// Blob format version
+    pub blob_version: u32,
+    /// Cryptographic epoch of this blob
+    pub epoch: CryptoEpoch,
+    /// Encrypted data
+    #[serde(with = "serde_bytes")]
+    pub ciphertext: Vec<u8>,
+    /// AEAD authentication tag (16 bytes)
+    pub auth_tag: [u8; 16],
+    /// XChaCha20 nonce (24 bytes)
+    pub nonce: [u8; 24],
+    /// Compression applied to the plaintext before encryption
+    #[serde(default)]
+    pub compression: CompressionAlgo,
+    /// Plaintext length after decompression (0 when uncompressed)
+    #[serde(default)]
+    pub decompressed_len: u64,
+}
+
+/// Fields shared by every blob version, in bincode order
+#[derive(Serialize)]
+struct BlobFieldsRef<'a> {
+    blob_version: u32,
+    epoch: &'a CryptoEpoch,
+    #[serde(with = "serde_bytes")]
+    ciphertext: &'a [u8],
+    auth_tag: &'a [u8; 16],
+    nonce: &'a [u8; 24],
+}
+
+/// Owned form of [`BlobFieldsRef`]
+#[derive(Deserialize)]
+struct BlobFields {
+    blob_version: u32,
+    epoch: CryptoEpoch,
+    #[serde(with = "serde_bytes")]
+    ciphertext: Vec<u8>,
+    auth_tag: [u8; 16],
+    nonce: [u8; 24],
+}
+
+/// Compression fields appended from [`COMPRESSION_BLOB_VERSION`] on
+#[derive(Serialize, Deserialize)]
+struct CompressionFields {
+    compression: CompressionAlgo,
+    decompressed_len: u64,
+}
+
+impl VaultBlob {
+    /// Current blob format version
+    pub const CURRENT_BLOB_VERSION: u32 = CURRENT_BLOB_VERSION;
+
+    /// Create a new VaultBlob
+    ///
+    /// # Arguments
+    ///
+    /// * `blob_version` - Format version number
+    /// * `epoch` - Cryptographic epoch identifier
+    /// * `ciphertext` - Encrypted vault data
+    /// * `auth_tag` - AEAD authentication tag (16 bytes)
+    /// * `nonce` - XChaCha20 nonce (24 bytes)
+    #[must_use]
+    pub const fn new(
+        blob_version: u32,
+        epoch: CryptoEpoch,
+        ciphertext: Vec<u8>,
+        auth_tag: [u8; 16],
+        nonce: [u8; 24],
+    ) -> Self {
+        Self {
+            blob_version,
+            epoch,
+            ciphertext,
+            auth_tag,
+            nonce,
+            compression: CompressionAlgo::None,
+            decompressed_len: 0,
+        }
+    }
+
+    /// Record that the plaintext was compressed with `compression`
+    ///
+    /// `decompressed_len` is the plaintext length before compression.
+    #[must_use]
+    pub const fn with_compression(
+        mut self,
+        compression: CompressionAlgo,
+        decompressed_len: u64,
+    ) -> Self {
+        self.compression = compression;
+        self.decompressed_len = decompressed_len;
+        self
+    }
+
+    /// Whether this blob's format records compression
+    const fn has_compression_fields(&self) -> bool {
+        self.blob_version >= COMPRESSION_BLOB_VERSION
+    }
+
+    /// Serialize VaultBlob to bytes using bincode
+    ///
+    /// Version 1 blobs keep their original layout; later versions append
+    /// the compression fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CryptoError` if serialization fails.
+    pub fn serialize(&self) -> Result<Vec<u8>> {
+        let fields = BlobFieldsRef {
+            blob_version: self.blob_version,
+            epoch: &self.epoch,
+            ciphertext: &self.ciphertext,
+            auth_tag: &self.auth_tag,
+            nonce: &self.nonce,
+        };
+        let mut bytes = bincode::serialize(&fields)
+            .map_err(|e| CryptoError::InternalError(format!("Serialization failed: {}", e)))?;
+
+        if self.has_compression_fields() {
+            let compression = CompressionFields {
+                compression: self.compression,
+                decompressed_len: self.decompressed_len,
+            };
+            bincode::serialize_into(&mut bytes, &compression)
+                .map_err(|e| CryptoError::InternalError(format!("Serialization failed: {}", e)))?;
+        }
+        Ok(bytes)
+    }
+
+    /// Deserialize a VaultBlob from bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CryptoError` if deserialization fails or
+    /// if the blob version is unsupported.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self> {
+        let mut rest = bytes;
+        let fields: BlobFields = super::decode_bounded_from(&mut rest)
+            .map_err(|e| CryptoError::InternalError(format!("Deserialization failed: {}", e)))?;
+        let blob = Self::new(
+            fields.blob_version,
+            fields.epoch,
+            fields.ciphertext,
+            fields.auth_tag,
+            fields.nonce,
+        );
+        if !blob.has_compression_fields() {
+            return Ok(blob);
+        }
+
+        let compression: CompressionFields = super::decode_bounded_from(&mut rest)
+            .map_err(|e| CryptoError::InternalError(format!("Deserialization failed: {}", e)))?;
+        Ok(blob.with_compression(compression.compression, compression.decompressed_len))
+    }
+
+    /// Encode this blob as a CBOR envelope
+    ///
+    /// Self-describing alternative to [`serialize`](Self::serialize); see
+    /// [`models::cbor`](crate::models::cbor).
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CryptoError` if encoding fails.
+    #[cfg(feature = "cbor")]
+    pub fn to_cbor(&self) -> Result<Vec<u8>> {
+        crate::models::cbor::encode("VaultBlob", self)
+    }
+
+    /// Decode a blob written by [`to_cbor`](Self::to_cbor)
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CryptoError` if the bytes are not a `VaultBlob` envelope
+    /// of a supported schema version.
+    #[cfg(feature = "cbor")]
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self> {
+        crate::models::cbor::decode("VaultBlob", bytes)
+    }
+
+    /// Validate the VaultBlob structure
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CryptoError` if:
+    /// - The blob version is unsupported
+    /// - The blob version predates the epoch's algorithm
+    /// - The blob version cannot record its compression
+    /// - The declared decompressed size exceeds the limit
+    /// - The authentication tag length is invalid
+    /// - The nonce length is invalid
+    pub fn validate(&self) -> Result<()> {
+        // Check blob version support
+        if self.blob_version > Self::CURRENT_BLOB_VERSION {
+            return Err(CryptoError::InternalError(format!(
+                "Unsupported blob version: {} (max supported: {})",
+                self.blob_version,
+                Self::CURRENT_BLOB_VERSION
+            )));
+        }
+
+        // The blob format must be able to carry the epoch's algorithm
+        if self.blob_version < self.epoch.algorithm.blob_version() {
+            return Err(CryptoError::InternalError(format!(
+                "Blob version {} cannot carry algorithm v{}",
+                self.blob_version,
+                self.epoch.algorithm.version()
+            )));
+        }
+
+        // A version 1 blob would silently drop its compression fields
+        if !self.has_compression_fields()
+            && (self.compression.is_compressed() || self.decompressed_len != 0)
+        {
+            return Err(CryptoError::InternalError(format!(
+                "Blob version {} cannot record compression",
+                self.blob_version
+            )));
+        }
+        if self.decompressed_len > compression::MAX_DECOMPRESSED_LEN {
+            return Err(CryptoError::InternalError(format!(
+                "Declared decompressed size {} exceeds limit {}",
+                self.decompressed_len,
+                compression::MAX_DECOMPRESSED_LEN
+            )));
+        }
+
+        // Validate auth tag length (XChaCha20-Poly1305 uses 16-byte tag)
+        // Note: auth_tag is already [u8; 16], so this is always valid
+        // This check is for future-proofing if the type changes
+
+        // Validate nonce length (XChaCha20 uses 24-byte nonce)
+        // Note: nonce is already [u8; 24], so this is always valid
+        // This check is for future-proofing if the type changes
+
+        Ok(())
+    }
+
+    /// Get total size of serialized VaultBlob
+    ///
+    /// This returns size in bytes that the blob would occupy
+    /// when serialized to disk, matching `serialize().len()`; it is
+    /// recorded as the header's `data_length`.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        std::mem::size_of::<u32>() // blob_version
+            + self.epoch.size() // epoch
+            + std::mem::size_of::<u64>() // ciphertext length prefix
+            + self.ciphertext.len() // ciphertext
+            + self.auth_tag.len() // auth_tag
+            + self.nonce.len() // nonce
+            + if self.has_compression_fields() {
+                self.compression.encoded_len() + std::mem::size_of::<u64>() // compression
+            } else {
+                0
+            }
+    }
+}
+
+/// Vault Header - file metadata (fixed 32 bytes)
+///
+/// This structure provides a fixed-size header for vault files,
+/// enabling quick validation and metadata retrieval without
+/// reading the entire file.
+#[derive(Debug, Clone)]
+pub struct VaultHeader {
+    /// Magic bytes: "AETERNM" (7 bytes) + 1 byte padding
+    pub magic: [u8; 8],
+    /// Blob format version
+    pub blob_version: u32,
+    /// Epoch version number
+    pub epoch_version: u64,
+    /// Length of encrypted data (VaultBlob)
+    pub data_length: u64,
+}
+
+/// Non-secret vault metadata for diagnostics
+///
+/// Only describes the vault; it never holds ciphertext, nonce, tag or any
+/// key material, so it is safe to show to support staff or attach to a
+/// bug report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VaultMetadata {
+    /// Magic bytes as text, without the padding byte ("AETERNM")
+    pub magic: String,
+    /// Blob format version
+    pub blob_version: u32,
+    /// Epoch version number
+    pub epoch_version: u64,
+    /// Length of the encrypted VaultBlob in bytes
+    pub data_length: u64,
+    /// Creation time of the vault's epoch (Unix milliseconds)
+    ///
+    /// Only known when the blob is available; `None` from a bare header.
+    pub created_at: Option<u64>,
+}
+
+impl VaultMetadata {
+    /// Metadata known from the 32-byte header alone
+    #[must_use]
+    pub fn from_header(header: &VaultHeader) -> Self {
+        Self {
+            magic: String::from_utf8_lossy(&header.magic)
+                .trim_end_matches('\0')
+                .to_string(),
+            blob_version: header.blob_version,
+            epoch_version: header.epoch_version,
+            data_length: header.data_length,
+            created_at: None,
+        }
+    }
+
+    /// Metadata of a vault whose blob has been read
+    ///
+    /// Adds the epoch creation time from `blob`; nothing else is taken
+    /// from the blob.
+    #[must_use]
+    pub fn from_vault(header: &VaultHeader, blob: &VaultBlob) -> Self {
+        Self {
+            created_at: Some(blob.epoch.timestamp),
+            ..Self::from_header(header)
+        }
+    }
+
+    /// Encode as a single-line JSON object
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("VaultMetadata serialization should never fail")
+    }
+}
+
+impl VaultHeader {
+    /// Magic bytes for vault file identification
+    pub const MAGIC: [u8; 8] = VAULT_MAGIC;
+
+    /// Create a new VaultHeader from a VaultBlob
+    ///
+    /// # Arguments
+    ///
+    /// * `blob` - The VaultBlob to create a header for
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CryptoError` if the blob validation fails.
+    #[must_use]
+    pub fn new(blob: &VaultBlob) -> Self {
+        Self {
+            magic: Self::MAGIC,
+            blob_version: blob.blob_version,
+            epoch_version: blob.epoch.version,
+            data_length: blob.size() as u64,
+        }
+    }
+
+    /// Non-secret metadata of this vault as JSON
+    ///
+    /// For diagnostics and support: see [`VaultMetadata`]. The header
+    /// carries no key material, ciphertext, nonce or tag, so none can leak.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::models::vault::{VaultBlob, VaultHeader};
+    /// use aeternum_core::models::epoch::CryptoEpoch;
+    ///
+    /// let blob = VaultBlob::new(2, CryptoEpoch::initial(), vec![0u8; 64], [0u8; 16], [0u8; 24]);
+    /// let json = VaultHeader::new(&blob).to_metadata_json();
+    /// assert!(json.contains("\"magic\":\"AETERNM\""));
+    /// ```
+    #[must_use]
+    pub fn to_metadata_json(&self) -> String {
+        VaultMetadata::from_header(self).to_json()
+    }
+
+    /// Serialize VaultHeader to a fixed 32-byte array
+    ///
+    /// The reserved bytes 28-31 carry the header checksum.
+    #[must_use]
+    pub fn to_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+
+        // Copy magic bytes (0-7)
+        bytes[0..8].copy_from_slice(&self.magic);
+
+        // Copy blob_version (8-11)
+        bytes[8..12].copy_from_slice(&self.blob_version.to_be_bytes());
+
+        // Copy epoch_version (12-19)
+        bytes[12..20].copy_from_slice(&self.epoch_version.to_be_bytes());
+
+        // Copy data_length (20-27)
+        bytes[20..28].copy_from_slice(&self.data_length.to_be_bytes());
+
+        // Checksum over bytes 0-27 (28-31)
+        let checksum = Self::checksum_of(&bytes[..28]);
+        bytes[28..32].copy_from_slice(&checksum);
+
+        bytes
+    }
+
+    /// Header checksum: the first 4 bytes of BLAKE3 over `covered`
+    fn checksum_of(covered: &[u8]) -> [u8; 4] {
+        let digest = hash(covered);
+        digest.as_bytes()[..4].try_into().unwrap()
+    }
+
+    /// Parse a VaultHeader from bytes, requiring a valid checksum
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Input bytes (must be at least 32 bytes)
+    ///
+    /// # Errors
+    ///
+    /// Returns a `VaultFormatError` if:
+    /// - The input is too short (< 32 bytes)
+    /// - The magic bytes don't match
+    /// - The checksum doesn't match (`HeaderChecksumMismatch`). A zeroed
+    ///   checksum on a header up to [`PRE_CHECKSUM_BLOB_VERSION`] was
+    ///   written before the checksum existed and is accepted.
+    pub fn from_bytes(bytes: &[u8]) -> std::result::Result<Self, VaultFormatError> {
+        if bytes.len() < 32 {
+            return Err(VaultFormatError::consistency_check(format!(
+                "Header too short: expected 32 bytes, got {}",
+                bytes.len()
+            )));
+        }
+
+        // Verify magic bytes
+        let magic: [u8; 8] = bytes[0..8].try_into().unwrap();
+        if magic != Self::MAGIC {
+            return Err(VaultFormatError::consistency_check(format!(
+                "Invalid vault magic bytes: expected {:?}, got {:?}",
+                Self::MAGIC.to_vec(),
+                magic.to_vec()
+            )));
+        }
+
+        // Parse blob_version
+        let blob_version = u32::from_be_bytes(bytes[8..12].try_into().unwrap());
+
+        // Verify checksum before trusting any other field
+        let stored: [u8; 4] = bytes[28..32].try_into().unwrap();
+        let is_legacy = blob_version <= PRE_CHECKSUM_BLOB_VERSION && stored == [0u8; 4];
+        if !is_legacy {
+            let computed = Self::checksum_of(&bytes[..28]);
+            if stored != computed {
+                return Err(VaultFormatError::header_checksum(format!(
+                    "expected {}, got {}",
+                    hex::encode(computed),
+                    hex::encode(stored)
+                )));
+            }
+        }
+
+        // Parse epoch_version
+        let epoch_version = u64::from_be_bytes(bytes[12..20].try_into().unwrap());
+
+        // Parse data_length
+        let data_length = u64::from_be_bytes(bytes[20..28].try_into().unwrap());
+
+        Ok(Self {
+            magic,
+            blob_version,
+            epoch_version,
+            data_length,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ----------------------------------------------------------------------
+    // Magic & Constants Tests
+    // ----------------------------------------------------------------------
+
+    #[test]
+    fn test_magic_bytes() {
+        assert_eq!(&VAULT_MAGIC[..7], b"AETERNM");
+        assert_eq!(VAULT_MAGIC[7], 0);
+    }
+
+    #[test]
+    fn test_current_blob_version() {
+        assert_eq!(CURRENT_BLOB_VERSION, 2);
+        assert_eq!(VaultBlob::CURRENT_BLOB_VERSION, 2);
+    }
+
+    // ----------------------------------------------------------------------
+    // VaultHeader Tests
+    // ----------------------------------------------------------------------
+
+    #[test]
+    fn test_header_magic_identification() {
+        let epoch = CryptoEpoch::initial();
+        let blob = VaultBlob::new(1, epoch, vec![1, 2, 3], [0u8; 16], [0u8; 24]);
+        let header = VaultHeader::new(&blob);
+
+        // 验证魔数正确
+        assert_eq!(&header.magic, &VaultHeader::MAGIC);
+        assert_eq!(header.magic, VAULT_MAGIC);
+    }
+
+    #[test]
+    fn test_header_metadata_json_excludes_secrets() {
+        let epoch = CryptoEpoch::new(7, crate::models::epoch::CryptoAlgorithm::V1);
+        let blob = VaultBlob::new(2, epoch, vec![0xAB; 100], [0xCD; 16], [0xEF; 24]);
+        let header = VaultHeader::new(&blob);
+
+        let json = header.to_metadata_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        // 包含纪元版本与长度
+        assert_eq!(value["epoch_version"], 7);
+        assert_eq!(value["data_length"], blob.size() as u64);
+        assert_eq!(value["blob_version"], 2);
+        assert_eq!(value["magic"], "AETERNM");
+        assert!(value["created_at"].is_null());
+
+        // 不包含任何密文、nonce 或认证标签字段
+        let keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(keys.len(), 5, "unexpected metadata fields: {:?}", keys);
+        for secret in ["ciphertext", "nonce", "tag", "key"] {
+            assert!(!json.contains(secret), "metadata mentions {}", secret);
+        }
+        // 字节值 171/205/239 (0xAB/0xCD/0xEF) 不应出现
+        for byte in ["171", "205", "239"] {
+            assert!(!json.contains(byte));
+        }
+
+        // 提供 blob 时补充纪元创建时间
+        let full = VaultMetadata::from_vault(&header, &blob);
+        assert_eq!(full.created_at, Some(epoch.timestamp));
+    }
+
+    #[test]
+    fn test_header_serialization_roundtrip() {
+        let epoch = CryptoEpoch::initial();
+        let blob = VaultBlob::new(1, epoch, vec![1, 2, 3], [0u8; 16], [0u8; 24]);
+        let header = VaultHeader::new(&blob);
+
+        // 序列化
+        let bytes = header.to_bytes();
+
+        // 验证长度
+        assert_eq!(bytes.len(), 32);
+
+        // 反序列化
+        let parsed = VaultHeader::from_bytes(&bytes).expect("Failed to parse header");
+
+        // 验证数据一致性
+        assert_eq!(parsed.magic, header.magic);
+        assert_eq!(parsed.blob_version, header.blob_version);
+        assert_eq!(parsed.epoch_version, header.epoch_version);
+        assert_eq!(parsed.data_length, header.data_length);
+    }
+
+    #[test]
+    fn test_header_from_bytes_magic_validation() {
+        let mut bytes = [0u8; 32];
+
+        // 设置正确的魔数
+        bytes[0..8].copy_from_slice(&VAULT_MAGIC);
+
+        // 验证正确魔数能通过
+        assert!(VaultHeader::from_bytes(&bytes).is_ok());
+
+        // 篡改魔数
+        bytes[0] = 0xFF;
+
+        // 验证错误魔数被拒绝
+        assert!(VaultHeader::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_header_checksum_detects_single_bit_flips() {
+        let epoch = CryptoEpoch::new(9, crate::models::epoch::CryptoAlgorithm::V1);
+        let blob = VaultBlob::new(1, epoch, vec![7u8; 64], [0u8; 16], [0u8; 24]);
+        let bytes = VaultHeader::new(&blob).to_bytes();
+
+        // 校验和写入保留字节
+        assert_ne!(&bytes[28..32], &[0u8; 4]);
+
+        // 翻转每个字段中的每一位都必须被检测到
+        for bit in 0..(32 * 8) {
+            let mut corrupted = bytes;
+            corrupted[bit / 8] ^= 1 << (bit % 8);
+
+            let result = VaultHeader::from_bytes(&corrupted);
+            if bit < 64 {
+                // 魔数损坏在校验和之前被拒绝
+                assert!(matches!(
+                    result,
+                    Err(VaultFormatError::ConsistencyCheckFailed(_))
+                ));
+            } else {
+                assert!(
+                    matches!(result, Err(VaultFormatError::HeaderChecksumMismatch(_))),
+                    "bit {} flip not detected",
+                    bit
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_header_legacy_zeroed_checksum() {
+        let epoch = CryptoEpoch::new(4, crate::models::epoch::CryptoAlgorithm::V1);
+        let blob = VaultBlob::new(1, epoch, vec![1, 2, 3], [0u8; 16], [0u8; 24]);
+        let mut bytes = VaultHeader::new(&blob).to_bytes();
+
+        // 模拟校验和引入之前写入的头部
+        bytes[28..32].fill(0);
+
+        // 校验和之前的版本接受
+        let parsed = VaultHeader::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.epoch_version, 4);
+        assert_eq!(parsed.blob_version, 1);
+
+        // 重新序列化后带上校验和
+        let upgraded = parsed.to_bytes();
+        assert_ne!(&upgraded[28..32], &[0u8; 4]);
+        assert!(VaultHeader::from_bytes(&upgraded).is_ok());
+
+        // 校验和之后引入的版本必须带校验和
+        let blob = VaultBlob::new(2, epoch, vec![1, 2, 3], [0u8; 16], [0u8; 24]);
+        let mut bytes = VaultHeader::new(&blob).to_bytes();
+        bytes[28..32].fill(0);
+        assert!(matches!(
+            VaultHeader::from_bytes(&bytes),
+            Err(VaultFormatError::HeaderChecksumMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_header_from_bytes_too_short() {
+        // 测试长度不足的输入
+        assert!(VaultHeader::from_bytes(&[0u8; 31]).is_err());
+        assert!(VaultHeader::from_bytes(&[0u8; 16]).is_err());
+        assert!(VaultHeader::from_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_header_blob_metadata() {
+        let epoch = CryptoEpoch::new(5, crate::models::epoch::CryptoAlgorithm::V1);
+        let blob = VaultBlob::new(2, epoch, vec![1, 2, 3, 4, 5], [0u8; 16], [0u8; 24]);
+        let header = VaultHeader::new(&blob);
+
+        // 验证 epoch 版本正确
+        assert_eq!(header.epoch_version, 5);
+
+        // 验证 blob 版本正确
+        assert_eq!(header.blob_version, 2);
+    }
+
+    // ----------------------------------------------------------------------
+    // VaultBlob Tests
+    // ----------------------------------------------------------------------
+
+    #[test]
+    fn test_blob_validation() {
+        let epoch = CryptoEpoch::initial();
+        let blob = VaultBlob::new(1, epoch, vec![1, 2, 3], [0u8; 16], [0u8; 24]);
+
+        // 正常版本应通过验证
+        assert!(blob.validate().is_ok());
+    }
+
+    #[test]
+    fn test_blob_validation_rejects_algorithm_newer_than_format() {
+        let epoch = CryptoEpoch::new(3, crate::models::epoch::CryptoAlgorithm::V2);
+
+        // V2 纪元不能存放在 blob_version 1 中
+        let blob = VaultBlob::new(1, epoch, vec![1, 2, 3], [0u8; 16], [0u8; 24]);
+        assert!(blob.validate().is_err());
+
+        let blob = VaultBlob::new(2, epoch, vec![1, 2, 3], [0u8; 16], [0u8; 24]);
+        assert!(blob.validate().is_ok());
+    }
+
+    #[test]
+    fn test_blob_validation_unsupported_version() {
+        let epoch = CryptoEpoch::initial();
+        // 使用不支持的版本号
+        let blob = VaultBlob::new(999, epoch, vec![1, 2, 3], [0u8; 16], [0u8; 24]);
+
+        // 应返回错误
+        assert!(blob.validate().is_err());
+    }
+
+    #[test]
+    fn test_blob_serialization() {
+        let epoch = CryptoEpoch::initial();
+        let blob = VaultBlob::new(
+            1,
+            epoch.clone(),
+            vec![1, 2, 3, 4, 5],
+            [0xAA; 16],
+            [0xBB; 24],
+        );
+
+        // 序列化
+        let serialized = blob.serialize().expect("Failed to serialize");
+
+        // 反序列化
+        let deserialized = VaultBlob::deserialize(&serialized).expect("Failed to deserialize");
+
+        // 验证数据一致性
+        assert_eq!(deserialized.blob_version, blob.blob_version);
+        assert_eq!(deserialized.epoch.version, blob.epoch.version);
+        assert_eq!(deserialized.ciphertext, blob.ciphertext);
+        assert_eq!(deserialized.auth_tag, blob.auth_tag);
+        assert_eq!(deserialized.nonce, blob.nonce);
+    }
+
+    #[test]
+    fn test_blob_compression_fields_roundtrip() {
+        let epoch = CryptoEpoch::initial();
+        let blob = VaultBlob::new(2, epoch, vec![9u8; 40], [0xAA; 16], [0xBB; 24])
+            .with_compression(CompressionAlgo::Zstd { level: 5 }, 4_096);
+
+        let bytes = blob.serialize().unwrap();
+        assert_eq!(bytes.len(), blob.size());
+
+        let parsed = VaultBlob::deserialize(&bytes).unwrap();
+        assert_eq!(parsed.compression, CompressionAlgo::Zstd { level: 5 });
+        assert_eq!(parsed.decompressed_len, 4_096);
+        assert_eq!(parsed.ciphertext, blob.ciphertext);
+
+        // version 2 缺少压缩字段时拒绝解析
+        assert!(VaultBlob::deserialize(&bytes[..bytes.len() - 8]).is_err());
+    }
+
+    #[test]
+    fn test_v1_blob_layout_unchanged() {
+        let epoch = CryptoEpoch::initial();
+        let blob = VaultBlob::new(1, epoch, vec![1, 2, 3], [0xAA; 16], [0xBB; 24]);
+
+        // version 1 布局与引入压缩之前的 bincode 编码逐字节一致
+        let legacy = bincode::serialize(&(
+            1u32,
+            epoch,
+            serde_bytes::Bytes::new(&[1, 2, 3]),
+            [0xAAu8; 16],
+            [0xBBu8; 24],
+        ))
+        .unwrap();
+        assert_eq!(blob.serialize().unwrap(), legacy);
+        assert_eq!(
+            VaultBlob::deserialize(&legacy).unwrap().compression,
+            CompressionAlgo::None
+        );
+
+        // version 1 无法记录压缩，验证时拒绝
+        let compressed = blob.with_compression(CompressionAlgo::zstd_default(), 100);
+        assert!(compressed.validate().is_err());
+    }
+
+    #[test]
+    fn test_blob_deserialization_invalid_data() {
+        // 无效的二进制数据
+        let invalid_data = vec![0xFF, 0xFF, 0xFF];
+
+        // 应返回错误
+        assert!(VaultBlob::deserialize(&invalid_data).is_err());
+    }
+
+    #[test]
+    fn test_blob_size() {
+        let epoch = CryptoEpoch::initial();
+        let ciphertext = vec![1u8; 1000];
+        let blob = VaultBlob::new(1, epoch, ciphertext, [0u8; 16], [0u8; 24]);
+
+        // size() 与序列化后的实际长度一致（写入 Header 的 data_length）
+        assert_eq!(blob.size(), blob.serialize().unwrap().len());
+
+        let empty = VaultBlob::new(1, epoch, vec![], [0u8; 16], [0u8; 24]);
+        assert_eq!(empty.size(), empty.serialize().unwrap().len());
+    }
+
+    // ----------------------------------------------------------------------
+    // Integration Tests
+    // ----------------------------------------------------------------------
+
+    #[test]
+    fn test_vault_file_structure() {
+        // 测试完整的 Vault 文件结构
+        let epoch = CryptoEpoch::initial();
+        let ciphertext = vec![1, 2, 3, 4, 5];
+        let auth_tag = [0xAA; 16];
+        let nonce = [0xBB; 24];
+
+        let blob = VaultBlob::new(1, epoch.clone(), ciphertext.clone(), auth_tag, nonce);
+        let header = VaultHeader::new(&blob);
+
+        // 验证头部信息
+        assert_eq!(header.epoch_version, epoch.version);
+        assert_eq!(header.blob_version, 1);
+
+        // 验证 Blob 可以正确序列化/反序列化
+        let serialized = blob.serialize().expect("Failed to serialize");
+        let deserialized = VaultBlob::deserialize(&serialized).expect("Failed to deserialize");
+
+        assert_eq!(deserialized.ciphertext, ciphertext);
+        assert_eq!(deserialized.auth_tag, auth_tag);
+        assert_eq!(deserialized.nonce, nonce);
+    }
+
+    #[test]
+    fn test_blob_epoch_monotonicity() {
+        // 测试纪元单调性（Invariant #1）
+        let epoch1 = CryptoEpoch::initial();
+        let epoch2 = epoch1.next();
+
+        let blob1 = VaultBlob::new(1, epoch1.clone(), vec![], [0u8; 16], [0u8; 24]);
+        let blob2 = VaultBlob::new(1, epoch2.clone(), vec![], [0u8; 16], [0u8; 24]);
+
+        // 验证纪元版本严格递增
+        assert!(epoch2.version > epoch1.version);
+        assert_eq!(blob1.epoch.version, 1);
+        assert_eq!(blob2.epoch.version, 2);
+    }
+
+    #[test]
+    fn test_vault_header_and_blob_consistency() {
+        // 测试 VaultHeader 和 VaultBlob 之间的一致性
+        let epoch = CryptoEpoch::new(10, crate::models::epoch::CryptoAlgorithm::V1);
+        let blob = VaultBlob::new(1, epoch, vec![1, 2, 3], [0u8; 16], [0u8; 24]);
+        let header = VaultHeader::new(&blob);
+
+        // 验证纪元版本一致性
+        assert_eq!(header.epoch_version, blob.epoch.version);
+
+        // 验证 blob 版本一致性
+        assert_eq!(header.blob_version, blob.blob_version);
+
+        // 验证数据长度合理性
+        assert!(header.data_length > 0);
+    }
+}
@@ -20,10 +20,11 @@
 //!
 //! Each active device must have exactly one valid header to access DEK.
 
-use crate::crypto::kem::{KyberCipherText, KyberPublicKeyBytes};
+use crate::crypto::kem::{wrap_dek_for_devices, KyberKEM, KyberPublicKeyBytes};
 use crate::models::device::{DeviceHeader, DeviceId, DeviceStatus, Role};
+use crate::models::key_hierarchy::DataEncryptionKey;
 use crate::protocol::error::{PqrrError, Result};
-use crate::protocol::pqrr::PqrrStateMachine;
+use crate::protocol::pqrr::{OperationKind, PqrrStateMachine, RevocationTicket};
 
 // ============================================================================
 // Device Registration
@@ -33,6 +34,8 @@ use crate::protocol::pqrr::PqrrStateMachine;
 ///
 /// Creates a new device entry with proper header for current epoch.
 /// Enforces Invariant #2 by ensuring new device gets exactly one header.
+/// The header holds a Kyber encapsulation to `public_key`; the DEK is
+/// wrapped for the device by the next epoch upgrade.
 ///
 /// # Arguments
 ///
@@ -44,8 +47,11 @@ use crate::protocol::pqrr::PqrrStateMachine;
 /// # Returns
 ///
 /// - `Ok(())` if device registered successfully
-/// - `Err(PqrrError::HeaderIncomplete)` if Invariant #2 violated
+/// - `Err(PqrrError::HeaderIncomplete)` if Invariant #2 violated or
+///   `public_key` cannot be encapsulated to
 /// - `Err(PqrrError::InvalidStateTransition)` if not in Idle state
+/// - `Err(PqrrError::PendingRevocation)` if a revocation awaits rotation
+/// - `Err(PqrrError::OperationInProgress)` if another operation is running
 ///
 /// # Example
 ///
@@ -66,10 +72,43 @@ use crate::protocol::pqrr::PqrrStateMachine;
 /// assert!(sm.is_device_active(device_id.as_bytes().to_vec()));
 /// ```
 pub fn register_device(
+    state_machine: &mut PqrrStateMachine,
+    device_id: DeviceId,
+    public_key: KyberPublicKeyBytes,
+    role: Role,
+) -> Result<()> {
+    insert_device(state_machine, device_id, public_key, role, None)
+}
+
+/// Register a new device and wrap the current DEK for it
+///
+/// Behaves like [`register_device`], but the header carries `dek` wrapped
+/// for `public_key` (see [`wrap_dek_for_devices`]), so the device can open
+/// the vault before the next epoch upgrade.
+///
+/// # Returns
+///
+/// - `Ok(())` if device registered successfully
+/// - `Err(PqrrError::HeaderIncomplete)` if `dek` cannot be wrapped for
+///   `public_key`
+/// - Any error returned by [`register_device`]
+pub fn register_device_with_dek(
+    state_machine: &mut PqrrStateMachine,
+    device_id: DeviceId,
+    public_key: KyberPublicKeyBytes,
+    role: Role,
+    dek: &DataEncryptionKey,
+) -> Result<()> {
+    insert_device(state_machine, device_id, public_key, role, Some(dek))
+}
+
+/// Shared body of [`register_device`] and [`register_device_with_dek`]
+fn insert_device(
     state_machine: &mut PqrrStateMachine,
     device_id: DeviceId,
     public_key: KyberPublicKeyBytes,
     _role: Role,
+    dek: Option<&DataEncryptionKey>,
 ) -> Result<()> {
     // Check valid state: Idle only
     if !matches!(
@@ -83,6 +122,12 @@ pub fn register_device(
         ));
     }
 
+    // A revoked device must lose the DEK before the device set grows
+    let pending = state_machine.pending_revocations().len();
+    if pending > 0 {
+        return Err(PqrrError::pending_revocation(pending as u32));
+    }
+
     // Check device doesn't already exist
     if state_machine.device_headers().contains_key(&device_id) {
         return Err(PqrrError::header_incomplete(
@@ -91,24 +136,79 @@ pub fn register_device(
         ));
     }
 
-    // Generate wrapped DEK for this device (placeholder for Phase 4)
-    let wrapped_dek: KyberCipherText = KyberCipherText([0u8; 1568]);
+    let guard = state_machine.begin_operation(OperationKind::RegisterDevice)?;
 
-    // Create device header with Active status
-    let mut header = DeviceHeader::new(
-        device_id,
-        *state_machine.current_epoch(),
-        public_key,
-        wrapped_dek,
-    );
+    let epoch = state_machine.current_epoch();
+    let mut header = match dek {
+        Some(dek) => {
+            let (_, wrapped) = wrap_dek_for_devices(dek, &[(device_id, public_key.clone())])
+                .pop()
+                .expect("one result per device");
+            let wrapped = wrapped.map_err(|e| {
+                PqrrError::header_incomplete(
+                    format!("{:?}", device_id),
+                    format!("DEK wrap failed: {}", e),
+                )
+            })?;
+            DeviceHeader::with_wrapped_dek(device_id, epoch, public_key, wrapped)
+        }
+        None => {
+            // Encapsulate to the device's key; the DEK is wrapped for it by
+            // the next epoch upgrade
+            let (_shared_secret, encrypted_dek) =
+                KyberKEM::encapsulate(&public_key).map_err(|e| {
+                    PqrrError::header_incomplete(
+                        format!("{:?}", device_id),
+                        format!("KEM encapsulation failed: {}", e),
+                    )
+                })?;
+            DeviceHeader::new(device_id, epoch, public_key, encrypted_dek)
+        }
+    };
     header.status = DeviceStatus::Active;
 
     // Add to device headers
     state_machine.device_headers_mut().insert(device_id, header);
 
+    guard.complete();
     Ok(())
 }
 
+/// Register a new device, enforcing a cap on active devices
+///
+/// Behaves like [`register_device`] but refuses the registration when it
+/// would leave more than `max_devices` active devices. The header set is
+/// shipped on every sync, so this bounds its size. Only `Active` devices
+/// count; revoked and degraded devices do not.
+///
+/// # Returns
+///
+/// - `Ok(())` if device registered successfully
+/// - `Err(PqrrError::DeviceLimitExceeded)` if the cap is already reached
+/// - Any error returned by [`register_device`]
+pub fn register_device_limited(
+    state_machine: &mut PqrrStateMachine,
+    device_id: DeviceId,
+    public_key: KyberPublicKeyBytes,
+    role: Role,
+    max_devices: usize,
+) -> Result<()> {
+    let active = state_machine
+        .device_headers()
+        .values()
+        .filter(|header| header.status == DeviceStatus::Active)
+        .count();
+
+    if active >= max_devices {
+        return Err(PqrrError::device_limit_exceeded(
+            max_devices.min(u32::MAX as usize) as u32,
+            active as u32,
+        ));
+    }
+
+    register_device(state_machine, device_id, public_key, role)
+}
+
 // ============================================================================
 // Invariant #2: Header Completeness Validation
 // ============================================================================
@@ -138,11 +238,11 @@ pub fn register_device(
 /// assert!(validate_header_completeness(&sm).is_ok());
 /// ```
 pub fn validate_header_completeness(state_machine: &PqrrStateMachine) -> Result<()> {
-    let headers = state_machine.device_headers();
     let current_epoch = state_machine.current_epoch();
+    let headers = state_machine.device_headers();
 
     // Check 1: Each active device has a header
-    for (device_id, header) in headers {
+    for (device_id, header) in headers.iter() {
         if header.status == DeviceStatus::Active {
             // Verify header belongs to current epoch
             if header.epoch.version != current_epoch.version {
@@ -198,6 +298,12 @@ fn header_can_be_unwrapped(_header: &DeviceHeader) -> bool {
 /// Removes a device from active set and cleans up its header.
 /// Enforces Invariant #2 by ensuring revoked devices have no valid headers.
 ///
+/// The revoked device keeps the current DEK until the epoch rotates, so the
+/// revocation is recorded as pending on the state machine. Registration and
+/// recovery initiation stay blocked until
+/// [`apply_epoch_upgrade_internal`](PqrrStateMachine::apply_epoch_upgrade_internal)
+/// moves to a higher epoch.
+///
 /// # Arguments
 ///
 /// - `state_machine`: Mutable reference to PQRR state machine
@@ -205,8 +311,10 @@ fn header_can_be_unwrapped(_header: &DeviceHeader) -> bool {
 ///
 /// # Returns
 ///
-/// - `Ok(())` if device revoked successfully
+/// - `Ok(RevocationTicket)` recording the revoked device and epoch
 /// - `Err(PqrrError::HeaderIncomplete)` if device not found
+/// - `Err(PqrrError::InvalidStateTransition)` if not in Idle state
+/// - `Err(PqrrError::OperationInProgress)` if another operation is running
 ///
 /// # Example
 ///
@@ -222,11 +330,16 @@ fn header_can_be_unwrapped(_header: &DeviceHeader) -> bool {
 /// let keypair = KyberKEM::generate_keypair();
 /// register_device(&mut sm, device_id.clone(), keypair.public, Role::Authorized).unwrap();
 ///
-/// revoke_device(&mut sm, &device_id).unwrap();
+/// let ticket = revoke_device(&mut sm, &device_id).unwrap();
 ///
+/// assert_eq!(ticket.device_id, device_id);
 /// assert!(!sm.is_device_active(device_id.as_bytes().to_vec()));
+/// assert!(sm.has_pending_revocations());
 /// ```
-pub fn revoke_device(state_machine: &mut PqrrStateMachine, device_id: &DeviceId) -> Result<()> {
+pub fn revoke_device(
+    state_machine: &mut PqrrStateMachine,
+    device_id: &DeviceId,
+) -> Result<RevocationTicket> {
     // Check device exists
     if !state_machine.device_headers().contains_key(device_id) {
         return Err(PqrrError::header_incomplete(
@@ -235,12 +348,24 @@ pub fn revoke_device(state_machine: &mut PqrrStateMachine, device_id: &DeviceId)
         ));
     }
 
+    let guard = state_machine.begin_operation(OperationKind::RevokeDevice)?;
+
     // Mark device as revoked
     if let Some(header) = state_machine.device_headers_mut().get_mut(device_id) {
         header.status = DeviceStatus::Revoked;
     }
 
-    Ok(())
+    let ticket = RevocationTicket {
+        device_id: *device_id,
+        epoch: state_machine.current_epoch().version,
+    };
+    let pending = state_machine.pending_revocations_mut();
+    if !pending.iter().any(|t| t.device_id == *device_id) {
+        pending.push(ticket.clone());
+    }
+
+    guard.complete();
+    Ok(ticket)
 }
 
 /// Clean up revoked device headers
@@ -262,14 +387,15 @@ pub fn cleanup_revoked_headers(
     device_id: &DeviceId,
 ) -> Result<()> {
     // Check device is revoked
-    let header = state_machine
+    let status = state_machine
         .device_headers()
         .get(device_id)
+        .map(|h| h.status)
         .ok_or_else(|| {
             PqrrError::header_incomplete(format!("{:?}", device_id), "device not found".to_string())
         })?;
 
-    if header.status != DeviceStatus::Revoked {
+    if status != DeviceStatus::Revoked {
         return Err(PqrrError::header_incomplete(
             format!("{:?}", device_id),
             "device is not revoked".to_string(),
@@ -282,6 +408,40 @@ pub fn cleanup_revoked_headers(
     Ok(())
 }
 
+/// Revoke a device and garbage-collect its stale headers
+///
+/// Operates on a flat header list, as persisted next to the vault, where a
+/// device can hold headers from several epochs. All headers of `device_id`
+/// are removed except the newest, which stays behind as a `Revoked`
+/// tombstone for audit. Headers of other devices are never touched, so
+/// their Invariant #2 status is unchanged.
+///
+/// # Returns
+///
+/// Number of headers removed (0 if the device has no headers)
+pub fn revoke_and_cleanup(headers: &mut Vec<DeviceHeader>, device_id: &DeviceId) -> usize {
+    let tombstone = match headers
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| h.device_id == *device_id)
+        .max_by_key(|(_, h)| h.epoch.version)
+    {
+        Some((index, _)) => index,
+        None => return 0,
+    };
+    headers[tombstone].status = DeviceStatus::Revoked;
+
+    let before = headers.len();
+    let mut index = 0;
+    headers.retain(|h| {
+        let keep = h.device_id != *device_id || index == tombstone;
+        index += 1;
+        keep
+    });
+
+    before - headers.len()
+}
+
 /// Get device registration status
 ///
 /// # Arguments
@@ -339,7 +499,7 @@ pub fn get_revoked_devices(state_machine: &PqrrStateMachine) -> Vec<DeviceId> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::kem::KyberKEM;
+    use crate::crypto::kem::KyberCipherText;
     use crate::models::epoch::{CryptoAlgorithm, CryptoEpoch};
 
     // ------------------------------------------------------------------------
@@ -356,6 +516,28 @@ mod tests {
 
         assert!(register_device(&mut sm, device_id.clone(), keypair.public, role).is_ok());
         assert!(sm.is_device_active(device_id.as_bytes().to_vec()));
+
+        // The header carries a real encapsulation to the device's key
+        let header = &sm.device_headers()[&device_id];
+        assert_ne!(header.encrypted_dek, KyberCipherText([0u8; 1568]));
+        assert!(KyberKEM::decapsulate(&keypair.secret, &header.encrypted_dek).is_ok());
+        assert!(header.wrapped_dek().is_none());
+    }
+
+    #[test]
+    fn test_register_device_with_dek_unwraps_current_dek() {
+        let mut sm = PqrrStateMachine::new(0);
+        let dek = DataEncryptionKey::generate();
+
+        let device_id = DeviceId::generate();
+        let keypair = KyberKEM::generate_keypair();
+        register_device_with_dek(&mut sm, device_id, keypair.public, Role::Authorized, &dek)
+            .unwrap();
+        assert!(sm.is_device_active(device_id.as_bytes().to_vec()));
+
+        let wrapped = sm.device_headers()[&device_id].wrapped_dek().unwrap();
+        let unwrapped = crate::crypto::kem::unwrap_dek(&keypair.secret, &wrapped).unwrap();
+        assert_eq!(unwrapped.as_bytes(), dek.as_bytes());
     }
 
     #[test]
@@ -398,6 +580,72 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_register_device_limited_boundary() {
+        let mut sm = PqrrStateMachine::new(0);
+        let keypair = KyberKEM::generate_keypair();
+
+        // Filling up to exactly the limit succeeds
+        for _ in 0..3 {
+            register_device_limited(
+                &mut sm,
+                DeviceId::generate(),
+                keypair.public.clone(),
+                Role::Authorized,
+                3,
+            )
+            .unwrap();
+        }
+        assert_eq!(get_active_devices(&sm).len(), 3);
+
+        // One over the limit is refused and leaves the set untouched
+        let result = register_device_limited(
+            &mut sm,
+            DeviceId::generate(),
+            keypair.public.clone(),
+            Role::Authorized,
+            3,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            PqrrError::DeviceLimitExceeded {
+                limit: 3,
+                active: 3
+            }
+        );
+        assert_eq!(sm.device_headers().len(), 3);
+    }
+
+    #[test]
+    fn test_register_device_limited_ignores_revoked() {
+        let mut sm = PqrrStateMachine::new(0);
+        let keypair = KyberKEM::generate_keypair();
+
+        let revoked = DeviceId::generate();
+        register_device_limited(
+            &mut sm,
+            revoked,
+            keypair.public.clone(),
+            Role::Authorized,
+            1,
+        )
+        .unwrap();
+        revoke_device(&mut sm, &revoked).unwrap();
+        sm.apply_epoch_upgrade_internal(CryptoEpoch::new(1, CryptoAlgorithm::V1))
+            .unwrap();
+
+        // The revoked device no longer counts against the cap
+        assert!(register_device_limited(
+            &mut sm,
+            DeviceId::generate(),
+            keypair.public.clone(),
+            Role::Authorized,
+            1
+        )
+        .is_ok());
+        assert_eq!(get_active_devices(&sm).len(), 1);
+    }
+
     // ------------------------------------------------------------------------
     // Invariant #2: Header Completeness Tests
     // ------------------------------------------------------------------------
@@ -477,6 +725,59 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_revoke_device_blocks_registration_until_epoch_upgrade() {
+        let mut sm = PqrrStateMachine::new(1);
+        let keypair = KyberKEM::generate_keypair();
+
+        let revoked = DeviceId::generate();
+        register_device(&mut sm, revoked, keypair.public.clone(), Role::Authorized).unwrap();
+
+        let ticket = revoke_device(&mut sm, &revoked).unwrap();
+        assert_eq!(
+            ticket,
+            RevocationTicket {
+                device_id: revoked,
+                epoch: 1
+            }
+        );
+        assert!(sm.has_pending_revocations());
+        assert_eq!(sm.pending_revocations(), vec![ticket]);
+
+        // Registration and recovery stay blocked until the epoch rotates
+        let result = register_device(
+            &mut sm,
+            DeviceId::generate(),
+            keypair.public.clone(),
+            Role::Authorized,
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            PqrrError::PendingRevocation { pending: 1 }
+        );
+        assert!(matches!(
+            sm.transition_to_recovery_internal("req_1".to_string(), 0, Role::Recovery),
+            Err(PqrrError::PendingRevocation { pending: 1 })
+        ));
+
+        // A failed upgrade leaves the revocation pending
+        assert!(sm
+            .apply_epoch_upgrade_internal(CryptoEpoch::new(1, CryptoAlgorithm::V1))
+            .is_err());
+        assert!(sm.has_pending_revocations());
+
+        sm.apply_epoch_upgrade_internal(CryptoEpoch::new(2, CryptoAlgorithm::V1))
+            .unwrap();
+        assert!(!sm.has_pending_revocations());
+        assert!(register_device(
+            &mut sm,
+            DeviceId::generate(),
+            keypair.public,
+            Role::Authorized
+        )
+        .is_ok());
+    }
+
     #[test]
     fn test_cleanup_revoked_headers_success() {
         let mut sm = PqrrStateMachine::new(0);
@@ -508,6 +809,51 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_revoke_and_cleanup_leaves_one_tombstone() {
+        let keypair = KyberKEM::generate_keypair();
+        let header = |device_id: DeviceId, version: u64| {
+            let mut header = DeviceHeader::new(
+                device_id,
+                CryptoEpoch::new(version, CryptoAlgorithm::V1),
+                keypair.public.clone(),
+                KyberCipherText([0u8; 1568]),
+            );
+            header.status = DeviceStatus::Active;
+            header
+        };
+
+        let revoked = DeviceId::generate();
+        let other = DeviceId::generate();
+        let mut headers = vec![
+            header(revoked, 1),
+            header(other, 3),
+            header(revoked, 3),
+            header(revoked, 2),
+        ];
+
+        assert_eq!(revoke_and_cleanup(&mut headers, &revoked), 2);
+        assert_eq!(headers.len(), 2);
+
+        let tombstone = headers.iter().find(|h| h.device_id == revoked).unwrap();
+        assert_eq!(tombstone.epoch.version, 3);
+        assert_eq!(tombstone.status, DeviceStatus::Revoked);
+
+        // The other device still satisfies Invariant #2
+        assert!(
+            crate::storage::InvariantValidator::check_all_headers_complete(
+                &headers,
+                &CryptoEpoch::new(3, CryptoAlgorithm::V1)
+            )
+            .is_ok()
+        );
+
+        // Nothing left to collect; unknown devices are a no-op
+        assert_eq!(revoke_and_cleanup(&mut headers, &revoked), 0);
+        assert_eq!(revoke_and_cleanup(&mut headers, &DeviceId::generate()), 0);
+        assert_eq!(headers.len(), 2);
+    }
+
     // ------------------------------------------------------------------------
     // Helper Function Tests
     // ------------------------------------------------------------------------
@@ -0,0 +1,821 @@
+//! # Epoch Follow
+//!
+//! Follower side of a cross-device epoch upgrade.
+//!
+//! The device that runs [`EpochUpgradeCoordinator`](super::EpochUpgradeCoordinator)
+//! commits epoch `n+1` locally and then tells every other active device
+//! about it with an [`EpochAnnounce`]: the new epoch, a BLAKE3 commitment to
+//! the new DEK, and the new header set. Each follower receives the
+//! announcement together with its own new header and drives an
+//! [`EpochFollower`] through:
+//!
+//! ```text
+//! AwaitingHeader ──receive──▶ VerifyingHeader ──verify──▶ ApplyingLocally ──apply──▶ Done
+//!                   │                              │                         │
+//!                   └──────────────────────────────┴─────────────────────────┴──▶ Failed
+//! ```
+//!
+//! ## Verification
+//!
+//! Before anything is written, the follower checks that
+//!
+//! 1. its header is part of the announced set and belongs to the new epoch,
+//! 2. the header unwraps with the local Kyber secret key,
+//! 3. the unwrapped DEK matches the announced commitment,
+//! 4. the new epoch is exactly one step ahead of the local one
+//!    ([`InvariantValidator::check_epoch_monotonicity`]), and
+//! 5. the DEK this device derives from its own vault key for the new epoch
+//!    is the announced DEK, so the header it is about to install will open
+//!    the vault it is about to write.
+//!
+//! ## Applying
+//!
+//! The vault is rewritten with the AUP functions (prepare, shadow write,
+//! atomic commit). Only after the commit does the state machine move to the
+//! new epoch and take the announced header set.
+//!
+//! ## Failure
+//!
+//! Every failure moves the follower to `Failed` with a [`FollowRejection`]
+//! whose [`code`](FollowRejection::code) the wire layer can send back to the
+//! initiator. Failures up to and including the commit leave the vault and
+//! the state machine untouched: the shadow file is discarded and the state
+//! machine is only changed after the commit.
+
+use crate::crypto::aead::XChaCha20Key;
+use crate::crypto::ct::ct_eq;
+use crate::crypto::hash::{Blake3Hasher, HashOutput};
+use crate::crypto::kem::{unwrap_dek, KyberSecretKeyBytes};
+use crate::models::device::{headers_digest, DeviceHeader, DeviceId};
+use crate::models::epoch::CryptoEpoch;
+use crate::protocol::error::{PqrrError, Result};
+use crate::protocol::pqrr::{OperationGuard, OperationKind, PqrrStateMachine};
+use crate::storage::aug::{
+    aup_atomic_commit, aup_prepare_with_compression, aup_shadow_write, open_vault, read_vault_blob,
+    read_vault_key, AupPreparation,
+};
+use crate::storage::metadata::MetadataStore;
+use crate::storage::{InvariantValidator, StorageError, VaultLock};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// Domain separation for the DEK commitment
+const DEK_COMMITMENT_DOMAIN: &[u8] = b"aeternum epoch-follow dek-commitment v1";
+
+/// BLAKE3 commitment to an epoch DEK
+pub fn dek_commitment(dek: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Blake3Hasher::new();
+    hasher.update(DEK_COMMITMENT_DOMAIN).update(dek);
+    *hasher.finalize().as_bytes()
+}
+
+// ============================================================================
+// Announcement
+// ============================================================================
+
+/// Announcement of a committed epoch upgrade, sent by the initiator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochAnnounce {
+    /// The epoch the initiator committed
+    pub new_epoch: CryptoEpoch,
+    /// [`dek_commitment`] of the new epoch's DEK
+    pub dek_commitment: [u8; 32],
+    /// New-epoch header of every active device
+    pub headers: Vec<DeviceHeader>,
+}
+
+impl EpochAnnounce {
+    /// Build the announcement for `new_epoch`, committing to `new_dek`
+    pub fn new(new_epoch: CryptoEpoch, new_dek: &XChaCha20Key, headers: Vec<DeviceHeader>) -> Self {
+        Self {
+            new_epoch,
+            dek_commitment: dek_commitment(new_dek.as_bytes()),
+            headers,
+        }
+    }
+
+    /// Announced headers keyed by device ID
+    pub fn header_map(&self) -> HashMap<DeviceId, DeviceHeader> {
+        self.headers
+            .iter()
+            .map(|header| (header.device_id, header.clone()))
+            .collect()
+    }
+
+    /// Canonical digest of the announced header set
+    ///
+    /// Equal to [`PqrrStateMachine::headers_digest`] on every device once
+    /// the upgrade has been applied.
+    pub fn headers_digest(&self) -> HashOutput {
+        headers_digest(&self.header_map())
+    }
+}
+
+// ============================================================================
+// States and Rejections
+// ============================================================================
+
+/// Why a follower rejected an epoch announcement
+///
+/// The codes are stable and may be sent back to the initiator; they carry
+/// no key material.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FollowRejection {
+    /// Header is not this device's, not in the announced set, or not of
+    /// the announced epoch
+    UnexpectedHeader,
+    /// Header does not unwrap with the local Kyber secret key
+    DecapsulationFailed,
+    /// Unwrapped DEK does not match the announced commitment
+    CommitmentMismatch,
+    /// Announced epoch is not exactly one step ahead (Invariant #1)
+    EpochNotMonotonic,
+    /// Announced DEK is not the one the local vault key derives
+    VaultKeyMismatch,
+    /// State machine is busy with another operation
+    LocalStateBusy,
+    /// Reading or writing the local vault failed
+    StorageFailed,
+}
+
+impl FollowRejection {
+    /// Stable wire code
+    pub fn code(&self) -> u8 {
+        match self {
+            Self::UnexpectedHeader => 1,
+            Self::DecapsulationFailed => 2,
+            Self::CommitmentMismatch => 3,
+            Self::EpochNotMonotonic => 4,
+            Self::VaultKeyMismatch => 5,
+            Self::LocalStateBusy => 6,
+            Self::StorageFailed => 7,
+        }
+    }
+
+    /// Look up a rejection by its wire code
+    pub fn from_code(code: u8) -> Option<Self> {
+        [
+            Self::UnexpectedHeader,
+            Self::DecapsulationFailed,
+            Self::CommitmentMismatch,
+            Self::EpochNotMonotonic,
+            Self::VaultKeyMismatch,
+            Self::LocalStateBusy,
+            Self::StorageFailed,
+        ]
+        .into_iter()
+        .find(|rejection| rejection.code() == code)
+    }
+
+    /// Get string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UnexpectedHeader => "unexpected header",
+            Self::DecapsulationFailed => "decapsulation failed",
+            Self::CommitmentMismatch => "DEK commitment mismatch",
+            Self::EpochNotMonotonic => "epoch not monotonic",
+            Self::VaultKeyMismatch => "vault key mismatch",
+            Self::LocalStateBusy => "local state busy",
+            Self::StorageFailed => "storage failed",
+        }
+    }
+}
+
+impl fmt::Display for FollowRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// State of an [`EpochFollower`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowState {
+    /// Waiting for the announcement and this device's header
+    AwaitingHeader,
+    /// Header received; checks pending
+    VerifyingHeader,
+    /// Checks passed; vault rewrite pending
+    ApplyingLocally,
+    /// Vault and state machine are at the new epoch
+    Done,
+    /// Announcement rejected
+    Failed(FollowRejection),
+}
+
+impl FollowState {
+    /// Get string representation
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AwaitingHeader => "AwaitingHeader",
+            Self::VerifyingHeader => "VerifyingHeader",
+            Self::ApplyingLocally => "ApplyingLocally",
+            Self::Done => "Done",
+            Self::Failed(_) => "Failed",
+        }
+    }
+}
+
+// ============================================================================
+// Follower
+// ============================================================================
+
+/// Follower side of a cross-device epoch upgrade
+///
+/// Drive it with [`receive`](Self::receive), [`verify`](Self::verify) and
+/// [`apply`](Self::apply), or all three at once with
+/// [`follow`](Self::follow).
+pub struct EpochFollower<'a> {
+    /// Local state machine, moved to the new epoch by `apply`
+    state_machine: &'a mut PqrrStateMachine,
+
+    /// This device
+    device_id: DeviceId,
+
+    /// Current state
+    state: FollowState,
+
+    /// Announcement being followed
+    announce: Option<EpochAnnounce>,
+
+    /// This device's new header
+    header: Option<DeviceHeader>,
+
+    /// AUP Phase 1 output, built by `verify`
+    preparation: Option<AupPreparation>,
+
+    /// Held from `verify` until the upgrade is applied or rejected
+    vault_lock: Option<VaultLock>,
+
+    /// Held from `verify` until the upgrade is applied or rejected
+    guard: Option<OperationGuard>,
+}
+
+impl<'a> EpochFollower<'a> {
+    /// Create a follower for `device_id` in `AwaitingHeader`
+    pub fn new(state_machine: &'a mut PqrrStateMachine, device_id: DeviceId) -> Self {
+        Self {
+            state_machine,
+            device_id,
+            state: FollowState::AwaitingHeader,
+            announce: None,
+            header: None,
+            preparation: None,
+            vault_lock: None,
+            guard: None,
+        }
+    }
+
+    /// Current state
+    pub fn state(&self) -> FollowState {
+        self.state
+    }
+
+    /// Accept an announcement and this device's new header
+    ///
+    /// The header must be addressed to this device, carry the announced
+    /// epoch, and appear unchanged in the announced header set.
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::InvalidStateTransition` if not in `AwaitingHeader`
+    /// - `PqrrError::EpochFollowRejected` with
+    ///   [`FollowRejection::UnexpectedHeader`] if the header does not fit
+    ///   the announcement
+    pub fn receive(&mut self, announce: EpochAnnounce, header: DeviceHeader) -> Result<()> {
+        self.expect_state(FollowState::AwaitingHeader, FollowState::VerifyingHeader)?;
+
+        let reason = if header.device_id != self.device_id {
+            Some("header addressed to another device".to_string())
+        } else if header.epoch != announce.new_epoch {
+            Some(format!(
+                "header is for epoch {}, announcement for {}",
+                header.epoch.version, announce.new_epoch.version
+            ))
+        } else if !announce.headers.contains(&header) {
+            Some("header not in the announced set".to_string())
+        } else if announce
+            .headers
+            .iter()
+            .any(|other| other.epoch != announce.new_epoch)
+        {
+            Some("announced set mixes epochs".to_string())
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(self.reject(FollowRejection::UnexpectedHeader, reason));
+        }
+
+        self.announce = Some(announce);
+        self.header = Some(header);
+        self.state = FollowState::VerifyingHeader;
+        Ok(())
+    }
+
+    /// Run every check against the local vault without writing
+    ///
+    /// Locks the vault, unwraps the new DEK with `secret_key`, checks it
+    /// against the announced commitment, checks epoch monotonicity, and
+    /// prepares the new-epoch vault from the vault at `vault_path` opened
+    /// with `current_dek`. The prepared DEK must be the announced one.
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::InvalidStateTransition` if not in `VerifyingHeader`
+    /// - `PqrrError::EpochFollowRejected` with the failed check
+    pub fn verify(
+        &mut self,
+        vault_path: impl AsRef<Path>,
+        secret_key: &KyberSecretKeyBytes,
+        current_dek: &XChaCha20Key,
+    ) -> Result<()> {
+        self.expect_state(FollowState::VerifyingHeader, FollowState::ApplyingLocally)?;
+        match self.verify_inner(vault_path.as_ref(), secret_key, current_dek) {
+            Ok(preparation) => {
+                self.preparation = Some(preparation);
+                self.state = FollowState::ApplyingLocally;
+                Ok(())
+            }
+            Err((rejection, reason)) => Err(self.reject(rejection, reason)),
+        }
+    }
+
+    /// Checks of [`verify`](Self::verify), returning the AUP Phase 1 output
+    fn verify_inner(
+        &mut self,
+        vault_path: &Path,
+        secret_key: &KyberSecretKeyBytes,
+        current_dek: &XChaCha20Key,
+    ) -> std::result::Result<AupPreparation, (FollowRejection, String)> {
+        let (Some(announce), Some(header)) = (&self.announce, &self.header) else {
+            unreachable!("receive() stores the announcement before VerifyingHeader");
+        };
+
+        // Nothing else may change the epoch or the vault until we are done
+        if !self.state_machine.state().can_upgrade_epoch() {
+            return Err((
+                FollowRejection::LocalStateBusy,
+                format!("state machine is {}", self.state_machine.state().as_str()),
+            ));
+        }
+        let guard = self
+            .state_machine
+            .begin_operation(OperationKind::EpochUpgrade)
+            .map_err(|e| (FollowRejection::LocalStateBusy, e.to_string()))?;
+        self.guard = Some(guard);
+        let vault_lock = VaultLock::try_acquire(vault_path)
+            .map_err(|e| (FollowRejection::LocalStateBusy, e.to_string()))?;
+        self.vault_lock = Some(vault_lock);
+
+        // Checks 1-2: the header unwraps to the committed DEK
+        let wrapped = header.wrapped_dek().ok_or_else(|| {
+            (
+                FollowRejection::DecapsulationFailed,
+                "header carries no wrapped DEK".to_string(),
+            )
+        })?;
+        let new_dek = unwrap_dek(secret_key, &wrapped)
+            .map_err(|e| (FollowRejection::DecapsulationFailed, e.to_string()))?;
+        if !ct_eq(
+            &dek_commitment(new_dek.as_bytes()),
+            &announce.dek_commitment,
+        ) {
+            return Err((
+                FollowRejection::CommitmentMismatch,
+                "unwrapped DEK does not match the announced commitment".to_string(),
+            ));
+        }
+
+        // Check 3: Invariant #1
+        let current_epoch = self.state_machine.current_epoch();
+        InvariantValidator::check_epoch_monotonicity(&current_epoch, &announce.new_epoch)
+            .map_err(|e| (FollowRejection::EpochNotMonotonic, e.to_string()))?;
+
+        // Check 4: AUP Phase 1 from the local vault yields the announced DEK
+        let storage = |e: StorageError| (FollowRejection::StorageFailed, e.to_string());
+        let blob = read_vault_blob(vault_path).map_err(storage)?;
+        if blob.epoch.version != current_epoch.version {
+            return Err((
+                FollowRejection::StorageFailed,
+                format!(
+                    "vault is at epoch {}, state machine at {}",
+                    blob.epoch.version, current_epoch.version
+                ),
+            ));
+        }
+        let vault_key = read_vault_key(vault_path)
+            .map_err(storage)?
+            .ok_or_else(|| {
+                (
+                    FollowRejection::StorageFailed,
+                    "vault has no key region".to_string(),
+                )
+            })?;
+        let vault_data = open_vault(&blob, &vault_key, current_dek).map_err(storage)?;
+        let preparation = aup_prepare_with_compression(
+            &blob.epoch,
+            announce.new_epoch.algorithm,
+            blob.compression,
+            &vault_key.encrypted_vk,
+            &vault_key.vk_nonce,
+            current_dek,
+            &vault_data,
+        )
+        .map_err(storage)?;
+        if !ct_eq(preparation.new_dek.as_bytes(), new_dek.as_bytes()) {
+            return Err((
+                FollowRejection::VaultKeyMismatch,
+                "announced DEK is not derived from this vault's key".to_string(),
+            ));
+        }
+
+        Ok(preparation)
+    }
+
+    /// Write the new epoch and adopt it
+    ///
+    /// Runs AUP Phases 2-3 on `vault_path`, then moves the state machine to
+    /// the announced epoch and replaces its headers with the announced set.
+    ///
+    /// # Returns
+    ///
+    /// The new epoch's DEK, the `current_dek` of the next upgrade.
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::InvalidStateTransition` if not in `ApplyingLocally`
+    /// - `PqrrError::EpochFollowRejected` with
+    ///   [`FollowRejection::StorageFailed`] if the shadow write or commit
+    ///   fails; the vault stays at the old epoch
+    pub fn apply(
+        &mut self,
+        vault_path: impl AsRef<Path>,
+        metadata: &mut dyn MetadataStore,
+    ) -> Result<XChaCha20Key> {
+        self.expect_state(FollowState::ApplyingLocally, FollowState::Done)?;
+        let vault_path = vault_path.as_ref();
+        let (Some(announce), Some(preparation)) = (self.announce.take(), self.preparation.take())
+        else {
+            unreachable!("verify() stores the preparation before ApplyingLocally");
+        };
+
+        // AUP Phases 2-3; an uncommitted shadow file is deleted on drop
+        let committed = aup_shadow_write(vault_path, &preparation).and_then(|shadow| {
+            aup_atomic_commit(vault_path, shadow, &preparation.new_epoch, metadata)
+        });
+        match committed {
+            Ok(()) => {}
+            // The vault is already at the new epoch; crash recovery heals
+            // Local_Epoch on the next startup
+            Err(StorageError::MetadataFailed(e)) => {
+                eprintln!(
+                    "[EpochFollow] Local_Epoch update failed, will heal on next startup: {}",
+                    e
+                );
+            }
+            Err(e) => {
+                return Err(self.reject(
+                    FollowRejection::StorageFailed,
+                    format!("AUP commit failed: {}", e),
+                ))
+            }
+        }
+        drop(self.vault_lock.take());
+
+        // Only now does the local state machine move
+        if let Err(e) = self
+            .state_machine
+            .apply_epoch_upgrade_internal(announce.new_epoch)
+        {
+            return Err(self.reject(
+                FollowRejection::EpochNotMonotonic,
+                format!(
+                    "{} (vault committed at epoch {}; run crash recovery)",
+                    e, announce.new_epoch.version
+                ),
+            ));
+        }
+        *self.state_machine.device_headers_mut() = announce.header_map();
+
+        if let Some(guard) = self.guard.take() {
+            guard.complete();
+        }
+        self.header = None;
+        self.state = FollowState::Done;
+
+        eprintln!(
+            "[EpochFollow] Followed epoch upgrade to {}",
+            announce.new_epoch.version
+        );
+        Ok(preparation.new_dek)
+    }
+
+    /// Receive, verify and apply in one call
+    ///
+    /// # Errors
+    ///
+    /// The first error of [`receive`](Self::receive),
+    /// [`verify`](Self::verify) or [`apply`](Self::apply).
+    pub fn follow(
+        &mut self,
+        announce: EpochAnnounce,
+        header: DeviceHeader,
+        vault_path: impl AsRef<Path>,
+        secret_key: &KyberSecretKeyBytes,
+        current_dek: &XChaCha20Key,
+        metadata: &mut dyn MetadataStore,
+    ) -> Result<XChaCha20Key> {
+        self.receive(announce, header)?;
+        self.verify(vault_path.as_ref(), secret_key, current_dek)?;
+        self.apply(vault_path, metadata)
+    }
+
+    /// Fail with `InvalidStateTransition` unless in `expected`
+    fn expect_state(&self, expected: FollowState, next: FollowState) -> Result<()> {
+        if self.state != expected {
+            return Err(PqrrError::invalid_transition(
+                self.state.as_str().to_string(),
+                next.as_str().to_string(),
+                format!("expected {}", expected.as_str()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Move to `Failed`, dropping everything held for the upgrade
+    fn reject(&mut self, rejection: FollowRejection, reason: String) -> PqrrError {
+        eprintln!("[EpochFollow] Rejected: {}: {}", rejection, reason);
+        self.announce = None;
+        self.header = None;
+        self.preparation = None;
+        self.vault_lock = None;
+        if let Some(guard) = self.guard.take() {
+            guard.abort();
+        }
+        self.state = FollowState::Failed(rejection);
+        PqrrError::epoch_follow_rejected(rejection, reason)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::aead::{AeadCipher, XChaCha20Nonce};
+    use crate::crypto::kem::{KyberKEM, KyberKeyPair};
+    use crate::models::epoch::CryptoAlgorithm;
+    use crate::models::key_hierarchy::DataEncryptionKey;
+    use crate::storage::aug::{aup_prepare_with_algorithm, read_vault_epoch};
+    use crate::storage::metadata::InMemoryMetadataStore;
+    use tempfile::TempDir;
+
+    /// One device: its vault, keys and state machine at epoch 1
+    struct Device {
+        _dir: TempDir,
+        vault_path: std::path::PathBuf,
+        keypair: KyberKeyPair,
+        id: DeviceId,
+        dek: XChaCha20Key,
+        state_machine: PqrrStateMachine,
+    }
+
+    /// Create a device whose vault holds `data` under `vk` at epoch 1 (V2)
+    fn device(vk: &[u8; 32], data: &[u8]) -> Device {
+        let dir = TempDir::new().unwrap();
+        let vault_path = dir.path().join("vault.db");
+
+        // Epoch 0 (V1) is only a bootstrap to derive the epoch 1 vault
+        let bootstrap_dek = XChaCha20Key::from_bytes(&[7u8; 32]).unwrap();
+        let nonce = XChaCha20Nonce::from_bytes(crate::storage::aug::LEGACY_VK_NONCE);
+        let encrypted_vk = AeadCipher::new(&bootstrap_dek)
+            .encrypt(&nonce, vk, None)
+            .unwrap();
+        let prep = aup_prepare_with_algorithm(
+            &CryptoEpoch::new(0, CryptoAlgorithm::V1),
+            CryptoAlgorithm::V2,
+            &encrypted_vk,
+            nonce.as_bytes(),
+            &bootstrap_dek,
+            data,
+        )
+        .unwrap();
+        let shadow = aup_shadow_write(&vault_path, &prep).unwrap();
+        aup_atomic_commit(
+            &vault_path,
+            shadow,
+            &prep.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap();
+
+        Device {
+            _dir: dir,
+            vault_path,
+            keypair: KyberKEM::generate_keypair(),
+            id: DeviceId::generate(),
+            dek: prep.new_dek,
+            state_machine: PqrrStateMachine::create(prep.new_epoch, HashMap::new()),
+        }
+    }
+
+    /// Announcement of epoch 2 for `follower`, with the DEK `dek`
+    fn announce(follower: &Device, dek: &XChaCha20Key) -> (EpochAnnounce, DeviceHeader) {
+        let new_epoch = follower.state_machine.current_epoch().next();
+        let wrapped = DataEncryptionKey::from_bytes(*dek.as_bytes())
+            .wrap_for_device(&follower.keypair.public)
+            .unwrap();
+        let header = DeviceHeader::with_wrapped_dek(
+            follower.id,
+            new_epoch,
+            follower.keypair.public.clone(),
+            wrapped,
+        );
+        (
+            EpochAnnounce::new(new_epoch, dek, vec![header.clone()]),
+            header,
+        )
+    }
+
+    /// DEK the follower's own vault key derives for epoch 2
+    fn next_dek(device: &Device) -> XChaCha20Key {
+        let key = read_vault_key(&device.vault_path).unwrap().unwrap();
+        let blob = read_vault_blob(&device.vault_path).unwrap();
+        aup_prepare_with_compression(
+            &blob.epoch,
+            blob.epoch.algorithm,
+            blob.compression,
+            &key.encrypted_vk,
+            &key.vk_nonce,
+            &device.dek,
+            b"",
+        )
+        .unwrap()
+        .new_dek
+    }
+
+    /// Follow `announce` and check that nothing local changed on failure
+    fn follow_rejected(
+        device: &mut Device,
+        announce: EpochAnnounce,
+        header: DeviceHeader,
+    ) -> FollowRejection {
+        let before = std::fs::read(&device.vault_path).unwrap();
+        let epoch = device.state_machine.current_epoch();
+        let digest = device.state_machine.headers_digest();
+
+        let mut follower = EpochFollower::new(&mut device.state_machine, device.id);
+        let err = follower
+            .follow(
+                announce,
+                header,
+                &device.vault_path,
+                &device.keypair.secret,
+                &device.dek,
+                &mut InMemoryMetadataStore::default(),
+            )
+            .unwrap_err();
+        let FollowState::Failed(rejection) = follower.state() else {
+            panic!("follower not failed: {:?}", follower.state());
+        };
+        assert!(matches!(
+            err,
+            PqrrError::EpochFollowRejected { rejection: reported, .. } if reported == rejection
+        ));
+
+        assert_eq!(std::fs::read(&device.vault_path).unwrap(), before);
+        assert_eq!(device.state_machine.current_epoch(), epoch);
+        assert_eq!(device.state_machine.headers_digest(), digest);
+        assert_eq!(device.state_machine.active_operation(), None);
+        rejection
+    }
+
+    #[test]
+    fn test_follow_applies_announced_epoch() {
+        let mut device = device(&[1u8; 32], b"vault data");
+        let dek = next_dek(&device);
+        let (announce, header) = announce(&device, &dek);
+        let digest = announce.headers_digest();
+
+        let mut follower = EpochFollower::new(&mut device.state_machine, device.id);
+        assert_eq!(follower.state(), FollowState::AwaitingHeader);
+        follower.receive(announce, header).unwrap();
+        assert_eq!(follower.state(), FollowState::VerifyingHeader);
+        follower
+            .verify(&device.vault_path, &device.keypair.secret, &device.dek)
+            .unwrap();
+        assert_eq!(follower.state(), FollowState::ApplyingLocally);
+        let new_dek = follower
+            .apply(&device.vault_path, &mut InMemoryMetadataStore::default())
+            .unwrap();
+        assert_eq!(follower.state(), FollowState::Done);
+
+        assert_eq!(new_dek.as_bytes(), dek.as_bytes());
+        assert_eq!(read_vault_epoch(&device.vault_path).unwrap(), 2);
+        assert_eq!(device.state_machine.current_epoch().version, 2);
+        assert_eq!(device.state_machine.headers_digest(), digest);
+
+        let blob = read_vault_blob(&device.vault_path).unwrap();
+        let key = read_vault_key(&device.vault_path).unwrap().unwrap();
+        assert_eq!(open_vault(&blob, &key, &new_dek).unwrap(), b"vault data");
+    }
+
+    #[test]
+    fn test_rejections_leave_local_state_untouched() {
+        let mut device = device(&[2u8; 32], b"vault data");
+        let dek = next_dek(&device);
+
+        // Commitment to another DEK
+        let (mut bad_commitment, header) = announce(&device, &dek);
+        bad_commitment.dek_commitment[0] ^= 0x01;
+        assert_eq!(
+            follow_rejected(&mut device, bad_commitment, header),
+            FollowRejection::CommitmentMismatch
+        );
+
+        // Header wrapped for another device's key
+        let (announce_ok, _) = announce(&device, &dek);
+        let stranger = KyberKEM::generate_keypair();
+        let mut foreign = announce_ok.headers[0].clone();
+        foreign.set_wrapped_dek(
+            DataEncryptionKey::from_bytes(*dek.as_bytes())
+                .wrap_for_device(&stranger.public)
+                .unwrap(),
+        );
+        let foreign_announce = EpochAnnounce {
+            headers: vec![foreign.clone()],
+            ..announce_ok
+        };
+        assert_eq!(
+            follow_rejected(&mut device, foreign_announce, foreign),
+            FollowRejection::DecapsulationFailed
+        );
+
+        // Consistent announcement, but of a DEK this vault does not derive
+        let other = XChaCha20Key::generate();
+        let (wrong_dek, header) = announce(&device, &other);
+        assert_eq!(
+            follow_rejected(&mut device, wrong_dek, header),
+            FollowRejection::VaultKeyMismatch
+        );
+
+        // Epoch skipping a version
+        let (mut skipping, mut header) = announce(&device, &dek);
+        header.epoch.version += 1;
+        skipping.new_epoch = header.epoch;
+        skipping.headers = vec![header.clone()];
+        assert_eq!(
+            follow_rejected(&mut device, skipping, header),
+            FollowRejection::EpochNotMonotonic
+        );
+
+        // Header not in the announced set
+        let (mut missing, header) = announce(&device, &dek);
+        missing.headers.clear();
+        assert_eq!(
+            follow_rejected(&mut device, missing, header),
+            FollowRejection::UnexpectedHeader
+        );
+    }
+
+    #[test]
+    fn test_busy_state_machine_rejected() {
+        let mut device = device(&[3u8; 32], b"vault data");
+        let dek = next_dek(&device);
+        let (announce, header) = announce(&device, &dek);
+
+        let _held = device
+            .state_machine
+            .begin_operation(OperationKind::RegisterDevice)
+            .unwrap();
+        let mut follower = EpochFollower::new(&mut device.state_machine, device.id);
+        follower.receive(announce, header).unwrap();
+        assert!(follower
+            .verify(&device.vault_path, &device.keypair.secret, &device.dek)
+            .is_err());
+        assert_eq!(
+            follower.state(),
+            FollowState::Failed(FollowRejection::LocalStateBusy)
+        );
+
+        // Out-of-order calls are state errors, not rejections
+        assert!(matches!(
+            follower.apply(&device.vault_path, &mut InMemoryMetadataStore::default()),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejection_codes_round_trip() {
+        for code in 1..=7 {
+            let rejection = FollowRejection::from_code(code).unwrap();
+            assert_eq!(rejection.code(), code);
+        }
+        assert_eq!(FollowRejection::from_code(0), None);
+        assert_eq!(FollowRejection::from_code(8), None);
+    }
+}
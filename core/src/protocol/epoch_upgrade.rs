@@ -10,6 +10,23 @@
 //! - **Invariant #3 Enforcement**: Prevents RECOVERY role from executing σ_rotate
 //! - **Crash Recovery**: Ensures vault consistency after interrupted upgrades
 //! - **Device Header Updates**: Manages header regeneration for all active devices
+//! - **Two-Phase Header Update**: New headers are staged in the `RekeyingContext`
+//!   and the vault is only committed once every active device has one
+//! - **Rollback**: Any failed step rolls the state machine back to Idle and
+//!   deletes the shadow file; up to and including the commit, the vault
+//!   stays at the old epoch
+//! - **Progress Reporting**: An optional [`ProgressCallback`] receives an
+//!   [`UpgradeProgress`] at every phase boundary and during shadow writing
+//! - **Dry Run**: [`EpochUpgradeCoordinator::plan`] reports what an upgrade
+//!   would change and everything that would block it, without writing;
+//!   [`EpochUpgradeCoordinator::execute`] refuses a plan made stale by a
+//!   header change
+//! - **Parallel Rekeying**: With the `parallel` feature, per-device DEK
+//!   wrapping ([`wrap_dek_for_devices`]) and vault re-encryption run on the
+//!   rayon thread pool; the output is identical to the sequential path
+//! - **Payload Rekeying**: An optional [`PayloadRekey`] re-encrypts vault
+//!   data keyed with the epoch DEK (vault items) under the new DEK in the
+//!   same AUP write, so no committed epoch holds data under the old DEK
 //!
 //! ## Architecture
 //!
@@ -20,7 +37,7 @@
 //! │  │         EpochUpgradeCoordinator                     │  │
 //! │  │  - execute_epoch_upgrade()                        │  │
 //! │  │  - execute_rotation() (Invariant #3 check)         │  │
-//! │  │  - integrate_shadow_write()                        │  │
+//! │  │  - abort() / rollback()                            │  │
 //! │  └─────────────────────────────────────────────────────┘  │
 //! │                          │                                │
 //! │                          ▼                                │
@@ -50,12 +67,14 @@
 //! ```no_run
 //! use aeternum_core::protocol::epoch_upgrade::EpochUpgradeCoordinator;
 //! use aeternum_core::protocol::PqrrStateMachine;
-//! use aeternum_core::models::{CryptoEpoch, Role};
-//! use std::path::Path;
+//! use aeternum_core::models::{CryptoEpoch, DataEncryptionKey, Role};
+//! use std::path::{Path, PathBuf};
 //!
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let current_dek = DataEncryptionKey::generate();
 //! let mut state_machine = PqrrStateMachine::new(0);
-//! let mut coordinator = EpochUpgradeCoordinator::new(&mut state_machine);
+//! let mut coordinator =
+//!     EpochUpgradeCoordinator::new(&mut state_machine).with_current_dek(&current_dek);
 //!
 //! // Attempt epoch upgrade (checks Invariant #3)
 //! let new_epoch = CryptoEpoch::new(2, aeternum_core::models::CryptoAlgorithm::V1);
@@ -71,13 +90,171 @@
 //! # }
 //! ```
 
-use crate::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce};
-use crate::models::device::{Operation, Role};
+use crate::crypto::aead::{XChaCha20Key, KEY_SIZE, TAG_SIZE};
+use crate::crypto::hash::HashOutput;
+use crate::crypto::kem::{wrap_dek_for_devices, KyberPublicKeyBytes};
+use crate::models::device::{
+    headers_digest, DeviceHeader, DeviceId, DeviceStatus, Operation, Role,
+};
 use crate::models::epoch::CryptoEpoch;
+use crate::models::key_hierarchy::{DataEncryptionKey, WrappedDek};
 use crate::protocol::error::{PqrrError, Result};
-use crate::protocol::pqrr::PqrrStateMachine;
-use crate::storage::aug::{aup_atomic_commit, aup_prepare, aup_shadow_write};
-use std::path::Path;
+use crate::protocol::pqrr::{
+    OperationKind, PqrrStateMachine, ProtocolState, RekeyProgressInfo, RekeyingContext,
+};
+use crate::storage::aug::{
+    aup_atomic_commit, aup_prepare_rekeyed, aup_shadow_write_with_progress, open_vault,
+    read_vault_blob, read_vault_key,
+};
+use crate::storage::metadata::{InMemoryMetadataStore, MetadataStore};
+use crate::storage::{ShadowFile, StorageError, VaultLock};
+use std::path::{Path, PathBuf};
+
+// ============================================================================
+// Progress Reporting
+// ============================================================================
+
+/// Phase of an epoch upgrade in progress
+///
+/// Phases are reported in declaration order; a failed upgrade stops
+/// reporting at the phase that failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum UpgradePhase {
+    /// AUP Phase 1: building the new-epoch vault in memory
+    Preparing,
+    /// Encapsulating the new DEK for each active device
+    EncapsulatingDevices {
+        /// Devices with a staged header
+        done: u32,
+        /// Devices that need a new header
+        total: u32,
+    },
+    /// AUP Phase 2: writing the shadow file
+    WritingShadow {
+        /// Bytes written so far
+        bytes_done: u64,
+        /// Total size of the shadow file
+        bytes_total: u64,
+    },
+    /// AUP Phase 3: atomic rename onto the vault
+    Committing,
+    /// Upgrade committed and the state machine is back in Idle
+    Done,
+}
+
+/// Progress report for an epoch upgrade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Record)]
+pub struct UpgradeProgress {
+    /// Epoch version being upgraded to
+    pub target_epoch: u64,
+    /// Current phase
+    pub phase: UpgradePhase,
+}
+
+/// Callback receiving epoch upgrade progress
+pub type ProgressCallback = Box<dyn Fn(UpgradeProgress) + Send>;
+
+/// Re-encrypts vault data keyed with the epoch DEK
+///
+/// Called with `(vault_data, current_dek, new_dek)` during AUP Phase 1; the
+/// returned bytes become the new epoch's vault data.
+pub type PayloadRekey =
+    Box<dyn Fn(&[u8], &DataEncryptionKey, &DataEncryptionKey) -> Result<Vec<u8>> + Send>;
+
+// ============================================================================
+// Dry Run
+// ============================================================================
+
+/// Simulation of an epoch upgrade, built by [`EpochUpgradeCoordinator::plan`]
+///
+/// Describes what [`EpochUpgradeCoordinator::execute`] would do without
+/// touching the vault or the state machine. `headers_digest` pins the
+/// device headers the plan was computed from; executing after they change
+/// is refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradePlan {
+    /// Vault file the upgrade rewrites
+    pub vault_path: PathBuf,
+    /// Role the upgrade runs as
+    pub role: Role,
+    /// Epoch at planning time
+    pub current_epoch: CryptoEpoch,
+    /// Epoch the upgrade moves to
+    pub target_epoch: CryptoEpoch,
+    /// Active devices that get a new-epoch header, in device ID order
+    pub rewrap: Vec<DeviceId>,
+    /// Devices left without a new-epoch header
+    pub skipped: Vec<SkippedDevice>,
+    /// Revoked devices whose pending revocation this rotation completes
+    pub completes_revocations: Vec<DeviceId>,
+    /// Estimated bytes written: the rewritten vault plus the new headers
+    pub estimated_bytes: u64,
+    /// Invariant checks the upgrade runs, evaluated against current state
+    pub checks: Vec<PlannedCheck>,
+    /// Everything that would make the upgrade fail; empty if it can run
+    pub blockers: Vec<UpgradeBlocker>,
+    /// Digest of the device headers at planning time
+    pub headers_digest: HashOutput,
+}
+
+impl UpgradePlan {
+    /// Whether the plan found no blockers
+    pub fn is_ready(&self) -> bool {
+        self.blockers.is_empty()
+    }
+}
+
+/// Device an upgrade would not rewrap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedDevice {
+    /// Device identifier
+    pub device_id: DeviceId,
+    /// Status that excludes it (Revoked or Degraded)
+    pub status: DeviceStatus,
+}
+
+/// Invariant check an upgrade runs, with its outcome on current state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedCheck {
+    /// Invariant number (1-4)
+    pub invariant: u8,
+    /// What is checked
+    pub description: String,
+    /// Whether the check passes now
+    pub passed: bool,
+}
+
+/// Condition that would make a planned upgrade fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpgradeBlocker {
+    /// The role may not execute σ_rotate (Invariant #3)
+    PermissionDenied {
+        /// Role of the upgrade
+        role: Role,
+    },
+    /// The state machine is not Idle
+    NotIdle {
+        /// Current protocol state
+        state: String,
+    },
+    /// Another protocol operation holds the state machine
+    OperationInProgress {
+        /// Operation in progress
+        operation: OperationKind,
+    },
+    /// An active device has no usable Kyber public key
+    MissingPublicKey {
+        /// Device identifier
+        device_id: DeviceId,
+    },
+    /// An active device's header is not at the current epoch (Invariant #2)
+    StaleHeader {
+        /// Device identifier
+        device_id: DeviceId,
+        /// Epoch version of its header
+        header_epoch: u64,
+    },
+}
 
 // ============================================================================
 // Epoch Upgrade Coordinator
@@ -93,6 +270,13 @@ use std::path::Path;
 /// ## Fields
 ///
 /// - `state_machine`: Reference to PQRR state machine for state transitions
+/// - `shadow_file`: Uncommitted AUP shadow file of the upgrade in progress
+/// - `vault_lock`: Exclusive vault lock, held from AUP Phase 1 until commit or abort
+/// - `progress`: Optional callback receiving [`UpgradeProgress`] reports
+/// - `metadata`: Optional store whose `Local_Epoch` is updated on commit
+/// - `current_dek`: DEK of the vault's current epoch; replaced by the new
+///   epoch's DEK on commit
+/// - `payload_rekey`: Optional [`PayloadRekey`] applied to the vault data
 ///
 /// ## Invariant Enforcement
 ///
@@ -104,6 +288,24 @@ use std::path::Path;
 pub struct EpochUpgradeCoordinator<'a> {
     /// Reference to PQRR state machine
     state_machine: &'a mut PqrrStateMachine,
+
+    /// Shadow file written in AUP Phase 2, held until commit or abort
+    shadow_file: Option<ShadowFile>,
+
+    /// Vault lock taken before AUP Phase 1, held until commit or abort
+    vault_lock: Option<VaultLock>,
+
+    /// Progress callback, invoked at phase boundaries
+    progress: Option<ProgressCallback>,
+
+    /// Metadata store updated after the AUP commit
+    metadata: Option<&'a mut dyn MetadataStore>,
+
+    /// DEK that unseals the vault key of the current epoch
+    current_dek: Option<DataEncryptionKey>,
+
+    /// Re-encrypts epoch-DEK-keyed vault data for the new epoch
+    payload_rekey: Option<PayloadRekey>,
 }
 
 impl<'a> EpochUpgradeCoordinator<'a> {
@@ -117,7 +319,81 @@ impl<'a> EpochUpgradeCoordinator<'a> {
     ///
     /// A new coordinator instance
     pub fn new(state_machine: &'a mut PqrrStateMachine) -> Self {
-        Self { state_machine }
+        Self {
+            state_machine,
+            shadow_file: None,
+            vault_lock: None,
+            progress: None,
+            metadata: None,
+            current_dek: None,
+            payload_rekey: None,
+        }
+    }
+
+    /// Unseal the current vault with `dek`
+    ///
+    /// `dek` is the current epoch's DEK, as unwrapped from any device
+    /// header. AUP Phase 1 decrypts the stored vault key and the vault
+    /// contents with it; an upgrade without it fails before touching the
+    /// vault.
+    pub fn with_current_dek(mut self, dek: &DataEncryptionKey) -> Self {
+        self.current_dek = Some(DataEncryptionKey::from_bytes(*dek.as_bytes()));
+        self
+    }
+
+    /// DEK of the vault's current epoch
+    ///
+    /// After a committed upgrade this is the new epoch's DEK, the one
+    /// wrapped into every new device header.
+    pub fn current_dek(&self) -> Option<&DataEncryptionKey> {
+        self.current_dek.as_ref()
+    }
+
+    /// Re-encrypt the vault data for the new epoch with `rekey`
+    ///
+    /// Without it the vault data is carried over unchanged, which is only
+    /// correct for data that is not keyed with the epoch DEK.
+    pub fn with_payload_rekey(mut self, rekey: PayloadRekey) -> Self {
+        self.payload_rekey = Some(rekey);
+        self
+    }
+
+    /// Report upgrade progress to `callback`
+    ///
+    /// The callback is invoked at every phase boundary, after each staged
+    /// device header and at least every [`AUP_PROGRESS_INTERVAL`] bytes of
+    /// shadow writing.
+    ///
+    /// [`AUP_PROGRESS_INTERVAL`]: crate::storage::aug::AUP_PROGRESS_INTERVAL
+    pub fn with_progress_callback(mut self, callback: ProgressCallback) -> Self {
+        self.progress = Some(callback);
+        self
+    }
+
+    /// Update `Local_Epoch` in `store` when an upgrade commits
+    ///
+    /// Without a store, the commit only renames the vault file and the
+    /// caller is responsible for recording `Local_Epoch`.
+    pub fn with_metadata_store(mut self, store: &'a mut dyn MetadataStore) -> Self {
+        self.metadata = Some(store);
+        self
+    }
+
+    /// Progress of the rekey in progress, or `None` outside Rekeying
+    ///
+    /// See [`PqrrStateMachine::get_rekey_progress`].
+    pub fn rekey_progress(&self) -> Option<RekeyProgressInfo> {
+        self.state_machine.get_rekey_progress()
+    }
+
+    /// Invoke the progress callback, if any
+    fn report(&self, new_epoch: &CryptoEpoch, phase: UpgradePhase) {
+        if let Some(callback) = &self.progress {
+            callback(UpgradeProgress {
+                target_epoch: new_epoch.version,
+                phase,
+            });
+        }
     }
 
     // ------------------------------------------------------------------------
@@ -179,6 +455,179 @@ impl<'a> EpochUpgradeCoordinator<'a> {
         Ok(())
     }
 
+    // ------------------------------------------------------------------------
+    // Dry Run
+    // ------------------------------------------------------------------------
+
+    /// Plan an upgrade to the next epoch without writing anything
+    ///
+    /// Evaluates every precondition of
+    /// [`execute_epoch_upgrade`](Self::execute_epoch_upgrade) against the
+    /// current state and collects all failures in
+    /// [`UpgradePlan::blockers`] instead of stopping at the first one.
+    /// Pending revocations are not blockers: the rotation is what
+    /// completes them.
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::StorageError` if the vault file exists but its size
+    ///   cannot be read
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use aeternum_core::protocol::epoch_upgrade::EpochUpgradeCoordinator;
+    /// # use aeternum_core::protocol::PqrrStateMachine;
+    /// # use aeternum_core::models::Role;
+    /// # fn main() -> aeternum_core::protocol::Result<()> {
+    /// let mut sm = PqrrStateMachine::new(1);
+    /// let coordinator = EpochUpgradeCoordinator::new(&mut sm);
+    ///
+    /// let plan = coordinator.plan("/data/vault.db", Role::Recovery)?;
+    /// assert_eq!(plan.target_epoch.version, 2);
+    /// assert!(!plan.is_ready());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn plan(&self, vault_path: impl AsRef<Path>, role: Role) -> Result<UpgradePlan> {
+        let sm = &*self.state_machine;
+        let vault_path = vault_path.as_ref().to_path_buf();
+        let current_epoch = sm.current_epoch();
+        let target_epoch = current_epoch.next();
+        let state = sm.state();
+        let mut blockers = Vec::new();
+
+        let permitted = role.can_permit_operation(Operation::SigmaRotate);
+        if !permitted {
+            blockers.push(UpgradeBlocker::PermissionDenied { role });
+        }
+        if !state.can_upgrade_epoch() {
+            blockers.push(UpgradeBlocker::NotIdle {
+                state: state.as_str().to_string(),
+            });
+        }
+        if let Some(operation) = sm.active_operation() {
+            blockers.push(UpgradeBlocker::OperationInProgress { operation });
+        }
+
+        let headers = sm.device_headers();
+        let mut entries: Vec<&DeviceHeader> = headers.values().collect();
+        entries.sort_by_key(|header| header.device_id.0);
+
+        let mut rewrap = Vec::new();
+        let mut skipped = Vec::new();
+        let mut header_bytes = 0u64;
+        let mut headers_current = true;
+        for header in entries {
+            if header.status != DeviceStatus::Active {
+                skipped.push(SkippedDevice {
+                    device_id: header.device_id,
+                    status: header.status,
+                });
+                continue;
+            }
+            if header.public_key.as_bytes().iter().all(|&b| b == 0) {
+                blockers.push(UpgradeBlocker::MissingPublicKey {
+                    device_id: header.device_id,
+                });
+            }
+            if !header.belongs_to_epoch(&current_epoch) {
+                headers_current = false;
+                blockers.push(UpgradeBlocker::StaleHeader {
+                    device_id: header.device_id,
+                    header_epoch: header.epoch.version,
+                });
+            }
+            // The new header carries a fresh encapsulation and wrapped DEK
+            header_bytes += (header.serialize().len() - header.dek_ciphertext.len()
+                + KEY_SIZE
+                + TAG_SIZE) as u64;
+            rewrap.push(header.device_id);
+        }
+        let headers_digest = headers_digest(&headers);
+        drop(headers);
+
+        let vault_bytes = match std::fs::metadata(&vault_path) {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => {
+                return Err(PqrrError::storage_error(format!(
+                    "Failed to read vault size {}: {}",
+                    vault_path.display(),
+                    e
+                )))
+            }
+        };
+
+        let checks = vec![
+            PlannedCheck {
+                invariant: 1,
+                description: format!(
+                    "target epoch {} is above current epoch {}",
+                    target_epoch.version, current_epoch.version
+                ),
+                passed: target_epoch.version > current_epoch.version,
+            },
+            PlannedCheck {
+                invariant: 2,
+                description: "every active device holds a current-epoch header".to_string(),
+                passed: headers_current,
+            },
+            PlannedCheck {
+                invariant: 3,
+                description: format!("role {} may execute σ_rotate", role.as_str()),
+                passed: permitted,
+            },
+        ];
+
+        Ok(UpgradePlan {
+            vault_path,
+            role,
+            current_epoch,
+            target_epoch,
+            rewrap,
+            skipped,
+            completes_revocations: sm
+                .pending_revocations()
+                .into_iter()
+                .map(|ticket| ticket.device_id)
+                .collect(),
+            estimated_bytes: vault_bytes + header_bytes,
+            checks,
+            blockers,
+            headers_digest,
+        })
+    }
+
+    /// Execute a plan made by [`plan`](Self::plan)
+    ///
+    /// Runs [`execute_epoch_upgrade`](Self::execute_epoch_upgrade) with the
+    /// plan's vault, target epoch and role.
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::UpgradeFailed` (step `plan`) if the plan has blockers,
+    ///   or the epoch or device headers changed since it was made
+    /// - Any error of `execute_epoch_upgrade`
+    pub fn execute(&mut self, plan: &UpgradePlan) -> Result<()> {
+        if !plan.is_ready() {
+            return Err(PqrrError::upgrade_failed(
+                "plan".to_string(),
+                format!("{} blocker(s): {:?}", plan.blockers.len(), plan.blockers),
+            ));
+        }
+        if self.state_machine.current_epoch() != plan.current_epoch
+            || self.state_machine.headers_digest() != plan.headers_digest
+        {
+            return Err(PqrrError::upgrade_failed(
+                "plan".to_string(),
+                "state changed since planning; plan again".to_string(),
+            ));
+        }
+
+        self.execute_epoch_upgrade(&plan.vault_path, plan.target_epoch, plan.role)
+    }
+
     // ------------------------------------------------------------------------
     // Epoch Upgrade Execution (AUP Integration)
     // ------------------------------------------------------------------------
@@ -188,9 +637,15 @@ impl<'a> EpochUpgradeCoordinator<'a> {
     /// This is the main entry point for epoch upgrades. It:
     /// 1. Checks Invariant #3 (role permissions)
     /// 2. Validates state machine is in Idle state
-    /// 3. Executes AUP three-phase protocol
+    /// 3. Executes AUP three-phase protocol, staging a new header for every
+    ///    active device before the atomic commit
     /// 4. Updates state machine to new epoch
     ///
+    /// If any step fails, the upgrade is rolled back (see
+    /// [`rollback`](Self::rollback)): the state machine returns to Idle at
+    /// the old epoch and the shadow file is deleted. A failure before or at
+    /// the commit leaves the vault file and device headers at the old epoch.
+    ///
     /// ## AUP Integration
     ///
     /// This method integrates with storage layer's AUP implementation:
@@ -211,6 +666,7 @@ impl<'a> EpochUpgradeCoordinator<'a> {
     /// - `Err(PqrrError::InvalidStateTransition)` if not in Idle state
     /// - `Err(PqrrError::EpochRegression)` if Invariant #1 violated
     /// - `Err(PqrrError::StorageError)` if AUP protocol failed
+    /// - `Err(PqrrError::OperationInProgress)` if another operation is running
     ///
     /// # Errors
     ///
@@ -224,21 +680,30 @@ impl<'a> EpochUpgradeCoordinator<'a> {
     /// Returns `PqrrError::EpochRegression` if:
     /// - `new_epoch <= current_epoch` (Invariant #1)
     ///
+    /// Returns `PqrrError::HeaderIncomplete` if:
+    /// - A device header could not be rewrapped for the new epoch (Invariant #2)
+    ///
     /// Returns `PqrrError::StorageError` if:
+    /// - No current DEK was provided, or it cannot unseal the vault key
     /// - AUP prepare phase failed
     /// - Shadow write failed (disk full, I/O error)
+    ///
+    /// Returns `PqrrError::UpgradeFailed` if:
     /// - Atomic commit failed (filesystem error)
+    /// - The state machine could not move to the committed epoch
+    /// - Rolling back a failed step failed as well
     ///
     /// # Example
     ///
     /// ```no_run
     /// # use aeternum_core::protocol::epoch_upgrade::EpochUpgradeCoordinator;
     /// # use aeternum_core::protocol::PqrrStateMachine;
-    /// # use aeternum_core::models::{CryptoEpoch, Role};
+    /// # use aeternum_core::models::{CryptoEpoch, DataEncryptionKey, Role};
     /// # use std::path::Path;
     /// # fn main() -> aeternum_core::protocol::Result<()> {
+    /// # let current_dek = DataEncryptionKey::generate();
     /// let mut sm = PqrrStateMachine::new(0);
-    /// let mut coordinator = EpochUpgradeCoordinator::new(&mut sm);
+    /// let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&current_dek);
     ///
     /// let new_epoch = CryptoEpoch::new(2, aeternum_core::models::CryptoAlgorithm::V1);
     ///
@@ -257,6 +722,37 @@ impl<'a> EpochUpgradeCoordinator<'a> {
         new_epoch: CryptoEpoch,
         role: Role,
     ) -> Result<()> {
+        self.execute_epoch_upgrade_with(
+            vault_path,
+            new_epoch,
+            role,
+            wrap_dek_for_devices,
+            |path, shadow, epoch, metadata| aup_atomic_commit(path, shadow, epoch, metadata),
+        )
+    }
+
+    /// Execute epoch upgrade with custom wrap and commit steps
+    ///
+    /// `wrap` wraps the new epoch's DEK for a set of devices, as
+    /// [`wrap_dek_for_devices`] does, and `commit` runs AUP Phase 3; tests
+    /// use them to inject failures.
+    fn execute_epoch_upgrade_with<F, C>(
+        &mut self,
+        vault_path: impl AsRef<Path>,
+        new_epoch: CryptoEpoch,
+        role: Role,
+        wrap: F,
+        commit: C,
+    ) -> Result<()>
+    where
+        F: FnOnce(&DataEncryptionKey, &[(DeviceId, KyberPublicKeyBytes)]) -> Vec<WrapResult>,
+        C: FnOnce(
+            &Path,
+            ShadowFile,
+            &CryptoEpoch,
+            &mut dyn MetadataStore,
+        ) -> std::result::Result<(), StorageError>,
+    {
         // Step 1: Invariant #3 check - RECOVERY role cannot execute σ_rotate
         self.execute_rotation(role, Operation::SigmaRotate)?;
 
@@ -270,6 +766,11 @@ impl<'a> EpochUpgradeCoordinator<'a> {
             ));
         }
 
+        // Held until the upgrade commits or aborts
+        let guard = self
+            .state_machine
+            .begin_operation(OperationKind::EpochUpgrade)?;
+
         eprintln!(
             "[EpochUpgrade] Starting epoch upgrade: {} -> {}",
             self.state_machine.current_epoch().version,
@@ -280,55 +781,359 @@ impl<'a> EpochUpgradeCoordinator<'a> {
         self.state_machine
             .transition_to_rekeying_internal(new_epoch)?;
 
-        // Step 4: AUP Phase 1 - Prepare
+        // Steps 4-6: Prepare, stage headers, shadow write. Nothing on disk
+        // changes until the commit, so the staging error is returned as-is
+        // once rolled back.
+        let new_dek = match self.stage_epoch_upgrade(vault_path.as_ref(), &new_epoch, wrap) {
+            Ok(new_dek) => new_dek,
+            Err(e) => {
+                eprintln!("[EpochUpgrade] Staging failed, rolling back: {}", e);
+                self.rollback().map_err(|rollback_error| {
+                    PqrrError::upgrade_failed(
+                        "staging".to_string(),
+                        format!("{}; rollback failed: {}", e, rollback_error),
+                    )
+                })?;
+                return Err(e);
+            }
+        };
+
+        // Step 7: AUP Phase 3 - Atomic Commit
+        self.report(&new_epoch, UpgradePhase::Committing);
+        let Some(shadow_file) = self.shadow_file.take() else {
+            let e = PqrrError::storage_error("AUP shadow file missing".to_string());
+            return Err(self.fail_step("commit", e));
+        };
+        let mut detached = InMemoryMetadataStore::default();
+        let metadata: &mut dyn MetadataStore = match self.metadata.as_deref_mut() {
+            Some(store) => store,
+            None => &mut detached,
+        };
+        match commit(vault_path.as_ref(), shadow_file, &new_epoch, metadata) {
+            Ok(()) => {}
+            // The vault is already at the new epoch; crash recovery heals
+            // Local_Epoch on the next startup, so the upgrade still completes.
+            Err(StorageError::MetadataFailed(e)) => {
+                eprintln!(
+                    "[EpochUpgrade] Local_Epoch update failed, will heal on next startup: {}",
+                    e
+                );
+            }
+            Err(e) => {
+                let e = PqrrError::storage_error(format!("AUP atomic commit failed: {}", e));
+                return Err(self.fail_step("commit", e));
+            }
+        }
+
+        drop(self.vault_lock.take());
+        self.current_dek = Some(new_dek);
+
+        eprintln!(
+            "[EpochUpgrade] AUP Phase 3 complete: vault={}",
+            vault_path.as_ref().display()
+        );
+
+        // Step 8: Update state machine epoch. The vault is already
+        // committed, so on failure only memory can be rolled back;
+        // `recover_from_crash` realigns it with the vault.
+        if let Err(e) = self.state_machine.apply_epoch_upgrade_internal(new_epoch) {
+            let e = PqrrError::storage_error(format!(
+                "{} (vault committed at epoch {}; run crash recovery)",
+                e, new_epoch.version
+            ));
+            return Err(self.fail_step("apply epoch", e));
+        }
+
+        // Step 9: Install staged headers (Invariant #2)
+        let staged = self
+            .state_machine
+            .rekeying_context_mut()
+            .map(|ctx| std::mem::take(&mut ctx.staged_headers))
+            .unwrap_or_default();
+        self.state_machine.device_headers_mut().extend(staged);
+
+        // Step 10: Return to Idle state
+        self.state_machine.return_to_idle_internal()?;
+        guard.complete();
+        self.report(&new_epoch, UpgradePhase::Done);
+
+        eprintln!(
+            "[EpochUpgrade] Epoch upgrade complete: epoch={}",
+            self.state_machine.current_epoch().version
+        );
+
+        Ok(())
+    }
+
+    /// Run AUP Phases 1-2 and stage a new header for every pending device
+    ///
+    /// Leaves the vault file untouched; on success the shadow file is held in
+    /// `self.shadow_file`, the `RekeyingContext` is complete and the new
+    /// epoch's DEK is returned.
+    fn stage_epoch_upgrade<F>(
+        &mut self,
+        vault_path: &Path,
+        new_epoch: &CryptoEpoch,
+        wrap: F,
+    ) -> Result<DataEncryptionKey>
+    where
+        F: FnOnce(&DataEncryptionKey, &[(DeviceId, KyberPublicKeyBytes)]) -> Vec<WrapResult>,
+    {
+        // Step 4: AUP Phase 1 - Prepare, excluding other writers of this vault
+        self.report(new_epoch, UpgradePhase::Preparing);
+        let vault_lock = VaultLock::try_acquire(vault_path)
+            .map_err(|e| PqrrError::storage_error(format!("Failed to lock vault: {}", e)))?;
+        self.vault_lock = Some(vault_lock);
         let current_epoch = self.state_machine.current_epoch();
-        // TODO: Get actual VK, DEK, and vault data from vault (placeholder for now)
-        // Create valid test data: 32-byte VK encrypted with current_dek
-        let test_vk = [0u8; 32]; // Placeholder VK
-        let test_dek = XChaCha20Key::generate();
-        let test_nonce = XChaCha20Nonce::from_bytes([
-            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
-            0x0E, 0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
-        ]); // Must match the nonce used in aup_prepare
-        let cipher = AeadCipher::new(&test_dek);
-        let current_vk = cipher
-            .encrypt(&test_nonce, &test_vk, None)
-            .map_err(|e| PqrrError::storage_error(format!("Failed to encrypt test VK: {}", e)))?;
-        let vault_data = b"placeholder_vault_data"; // Placeholder - should come from vault
-        let preparation = aup_prepare(current_epoch, &current_vk, &test_dek, vault_data)
-            .map_err(|e| PqrrError::storage_error(format!("AUP prepare failed: {}", e)))?;
+        let current_dek = self.current_dek.as_ref().ok_or_else(|| {
+            PqrrError::storage_error("current epoch DEK not provided".to_string())
+        })?;
+        let current_dek = XChaCha20Key::from_bytes(current_dek.as_bytes())
+            .map_err(|e| PqrrError::storage_error(format!("Invalid current DEK: {}", e)))?;
+        let stored_vk = read_vault_key(vault_path)
+            .map_err(|e| PqrrError::storage_error(format!("Failed to read vault key: {}", e)))?
+            .ok_or_else(|| {
+                PqrrError::storage_error(format!(
+                    "vault {} has no vault key region",
+                    vault_path.display()
+                ))
+            })?;
+        let blob = read_vault_blob(vault_path)
+            .map_err(|e| PqrrError::storage_error(format!("Failed to read vault: {}", e)))?;
+        let vault_data = open_vault(&blob, &stored_vk, &current_dek)
+            .map_err(|e| PqrrError::storage_error(format!("Failed to open vault: {}", e)))?;
+        let rekey = self.payload_rekey.as_ref();
+        let old_dek = DataEncryptionKey::from_bytes(*current_dek.as_bytes());
+        let preparation = aup_prepare_rekeyed(
+            &current_epoch,
+            new_epoch.algorithm,
+            &stored_vk.encrypted_vk,
+            &stored_vk.vk_nonce,
+            &current_dek,
+            move |new_dek| match rekey {
+                Some(rekey) => rekey(
+                    &vault_data,
+                    &old_dek,
+                    &DataEncryptionKey::from_bytes(*new_dek.as_bytes()),
+                )
+                .map_err(|e| StorageError::crypto(format!("Failed to rekey vault data: {}", e))),
+                None => Ok(vault_data),
+            },
+        )
+        .map_err(|e| PqrrError::storage_error(format!("AUP prepare failed: {}", e)))?;
+        let new_dek = DataEncryptionKey::from_bytes(*preparation.new_dek.as_bytes());
 
         eprintln!(
             "[EpochUpgrade] AUP Phase 1 complete: new_epoch={}",
             preparation.new_epoch.version
         );
 
-        // Step 5: AUP Phase 2 - Shadow Write
-        let shadow_file = aup_shadow_write(&vault_path, &preparation)
+        // Step 5: Stage new-epoch headers for every pending device
+        let pending = self.rekeying_context()?.pending_devices.clone();
+        let total = pending.len() as u32;
+        self.report(
+            new_epoch,
+            UpgradePhase::EncapsulatingDevices { done: 0, total },
+        );
+        let old_headers = pending
+            .into_iter()
+            .map(|device_id| {
+                self.state_machine
+                    .device_headers()
+                    .get(&device_id)
+                    .cloned()
+                    .ok_or_else(|| {
+                        PqrrError::header_incomplete(
+                            format!("{:?}", device_id),
+                            "no current header to rewrap".to_string(),
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // One Kyber encapsulation per device; parallel with the `parallel` feature
+        let pubkeys: Vec<_> = old_headers
+            .iter()
+            .map(|header| (header.device_id, header.public_key.clone()))
+            .collect();
+        let wrapped_deks = wrap(&new_dek, &pubkeys);
+        if wrapped_deks.len() != old_headers.len() {
+            return Err(PqrrError::header_incomplete(
+                format!("{} devices", old_headers.len()),
+                format!("DEK wrapped for {} devices", wrapped_deks.len()),
+            ));
+        }
+        for (index, (old_header, (device_id, wrapped))) in
+            old_headers.into_iter().zip(wrapped_deks).enumerate()
+        {
+            let wrapped = wrapped.map_err(|e| {
+                PqrrError::header_incomplete(
+                    format!("{:?}", device_id),
+                    format!("DEK wrap failed: {}", e),
+                )
+            })?;
+            let mut header = DeviceHeader {
+                epoch: *new_epoch,
+                ..old_header
+            };
+            header.set_wrapped_dek(wrapped);
+            self.rekeying_context()?.stage_header(header);
+            self.report(
+                new_epoch,
+                UpgradePhase::EncapsulatingDevices {
+                    done: index as u32 + 1,
+                    total,
+                },
+            );
+        }
+
+        // Step 6: AUP Phase 2 - Shadow Write
+        let shadow_file =
+            aup_shadow_write_with_progress(vault_path, &preparation, |bytes_done, bytes_total| {
+                self.report(
+                    new_epoch,
+                    UpgradePhase::WritingShadow {
+                        bytes_done,
+                        bytes_total,
+                    },
+                )
+            })
             .map_err(|e| PqrrError::storage_error(format!("AUP shadow write failed: {}", e)))?;
+        let temp_path = shadow_file.path().display().to_string();
+        self.shadow_file = Some(shadow_file);
+
+        let ctx = self.rekeying_context()?;
+        ctx.temp_vault_path = Some(temp_path.clone());
 
         eprintln!(
             "[EpochUpgrade] AUP Phase 2 complete: shadow_file={}",
-            shadow_file.path().display()
+            temp_path
         );
 
-        // Step 6: AUP Phase 3 - Atomic Commit
-        aup_atomic_commit(&vault_path, shadow_file, &preparation.new_epoch)
-            .map_err(|e| PqrrError::storage_error(format!("AUP atomic commit failed: {}", e)))?;
+        // Commit only once every device has a staged header. A vault without
+        // active devices has nothing to stage, which `is_complete()` reports
+        // as incomplete.
+        let ctx = self.rekeying_context()?;
+        let nothing_to_stage = ctx.pending_devices.is_empty() && ctx.completed_devices.is_empty();
+        if !(ctx.is_complete() || nothing_to_stage) {
+            return Err(PqrrError::header_incomplete(
+                format!("{:?}", ctx.pending_devices),
+                "devices still pending after staging".to_string(),
+            ));
+        }
 
         eprintln!(
-            "[EpochUpgrade] AUP Phase 3 complete: vault={}",
-            vault_path.as_ref().display()
+            "[EpochUpgrade] Staged {} device headers for epoch {}",
+            ctx.staged_headers.len(),
+            new_epoch.version
         );
 
-        // Step 7: Update state machine epoch
-        self.state_machine.apply_epoch_upgrade_internal(new_epoch)?;
+        // Invariant #2: every active device must be covered after the commit
+        self.state_machine
+            .check_staged_header_completeness(new_epoch)?;
+
+        Ok(new_dek)
+    }
+
+    /// Get the rekeying context of the upgrade in progress
+    fn rekeying_context(&mut self) -> Result<&mut RekeyingContext> {
+        let state = self.state_machine.state();
+        self.state_machine.rekeying_context_mut().ok_or_else(|| {
+            PqrrError::invalid_transition(
+                state.as_str().to_string(),
+                "Rekeying".to_string(),
+                "no rekeying context".to_string(),
+            )
+        })
+    }
+
+    /// Roll back a failed epoch upgrade
+    ///
+    /// Returns the in-memory state to Idle at the old epoch, deletes the
+    /// AUP shadow file and releases the vault lock, the same as
+    /// [`abort`](Self::abort). The vault file is never touched. Unlike
+    /// `abort`, it succeeds without an upgrade in progress, so it can run
+    /// after a failure at any step.
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::StorageError` if a residual shadow file cannot be removed
+    pub fn rollback(&mut self) -> Result<()> {
+        if matches!(self.state_machine.state(), ProtocolState::Rekeying) {
+            return self.abort();
+        }
+
+        drop(self.shadow_file.take());
+        drop(self.vault_lock.take());
+        Ok(())
+    }
+
+    /// Roll back after `step` failed with `error`
+    ///
+    /// Returns an `UpgradeFailed` error naming `step`, `error` and the
+    /// outcome of the rollback.
+    fn fail_step(&mut self, step: &str, error: PqrrError) -> PqrrError {
+        eprintln!("[EpochUpgrade] {} failed, rolling back: {}", step, error);
+        let reason = match self.rollback() {
+            Ok(()) => format!(
+                "{}; rolled back to epoch {}",
+                error,
+                self.state_machine.current_epoch().version
+            ),
+            Err(rollback_error) => format!("{}; rollback failed: {}", error, rollback_error),
+        };
+        PqrrError::upgrade_failed(step.to_string(), reason)
+    }
 
-        // Step 8: Return to Idle state
+    /// Abort an epoch upgrade that has not been committed
+    ///
+    /// Discards the staged headers, deletes the AUP shadow file (including
+    /// one left behind by a crashed process), releases the vault lock and
+    /// returns the state machine to Idle. The vault file, device headers and current epoch are left
+    /// at the old epoch.
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::InvalidStateTransition` if no upgrade is in progress
+    /// - `PqrrError::StorageError` if a residual shadow file cannot be removed
+    pub fn abort(&mut self) -> Result<()> {
+        let state = self.state_machine.state();
+        if !matches!(state, ProtocolState::Rekeying) {
+            return Err(PqrrError::invalid_transition(
+                state.as_str().to_string(),
+                "Idle".to_string(),
+                "no epoch upgrade in progress".to_string(),
+            ));
+        }
+
+        // Dropping an uncommitted shadow file deletes it
+        drop(self.shadow_file.take());
+
+        // A shadow file from a crashed process has no handle; remove it by path
+        let temp_path = self
+            .state_machine
+            .rekeying_context()
+            .and_then(|ctx| ctx.temp_vault_path.clone());
+        if let Some(temp_path) = temp_path {
+            match std::fs::remove_file(&temp_path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(PqrrError::storage_error(format!(
+                        "Failed to remove shadow file {}: {}",
+                        temp_path, e
+                    )))
+                }
+            }
+        }
+
+        drop(self.vault_lock.take());
+
+        // Dropping the rekeying context discards the staged headers
         self.state_machine.return_to_idle_internal()?;
 
         eprintln!(
-            "[EpochUpgrade] Epoch upgrade complete: epoch={}",
+            "[EpochUpgrade] Epoch upgrade aborted: epoch remains {}",
             self.state_machine.current_epoch().version
         );
 
@@ -396,11 +1201,11 @@ impl<'a> EpochUpgradeCoordinator<'a> {
                 vault_epoch, state_epoch
             );
 
-            // Recover by updating state machine to vault epoch
-            let recovered_epoch = CryptoEpoch::new(
-                vault_epoch as u64,
-                crate::models::CryptoAlgorithm::V1, // TODO: Read from vault
-            );
+            // Recover by updating state machine to the vault's epoch,
+            // including the algorithm it was upgraded to
+            let recovered_epoch = crate::storage::aug::read_vault_blob(vault_path)
+                .map_err(|e| PqrrError::storage_error(format!("Failed to read vault blob: {}", e)))?
+                .epoch;
 
             self.state_machine
                 .apply_epoch_upgrade_internal(recovered_epoch)?;
@@ -427,6 +1232,9 @@ impl<'a> EpochUpgradeCoordinator<'a> {
     }
 }
 
+/// Per-device outcome of wrapping the new epoch's DEK
+type WrapResult = (DeviceId, crate::crypto::Result<WrappedDek>);
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -435,10 +1243,58 @@ impl<'a> EpochUpgradeCoordinator<'a> {
 mod tests {
     use super::*;
     use crate::crypto::aead::{AeadCipher, XChaCha20Nonce};
+    use crate::crypto::error::CryptoError;
+    use crate::crypto::kem::{unwrap_dek, KyberKEM};
+    use crate::models::device::DegradeReason;
     use crate::models::epoch::CryptoAlgorithm;
-    use crate::protocol::pqrr::ProtocolState;
+    use crate::storage::aug::{aup_prepare, aup_shadow_write};
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use tempfile::TempDir;
 
+    /// Create a vault file at `epoch` and a state machine with `devices`
+    /// active headers at the same epoch
+    ///
+    /// Returns the state machine and the vault's epoch DEK.
+    fn setup_vault_with_devices(
+        vault_path: &Path,
+        epoch: u64,
+        devices: usize,
+    ) -> (PqrrStateMachine, DataEncryptionKey) {
+        let dek = XChaCha20Key::generate();
+        let nonce = XChaCha20Nonce::from_bytes([
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+        ]);
+        let encrypted_vk = AeadCipher::new(&dek)
+            .encrypt(&nonce, &[0u8; 32], None)
+            .unwrap();
+        let previous = CryptoEpoch::new(epoch - 1, CryptoAlgorithm::V1);
+        let prep = aup_prepare(&previous, &encrypted_vk, &dek, b"vault data").unwrap();
+        let shadow = aup_shadow_write(vault_path, &prep).unwrap();
+        aup_atomic_commit(
+            vault_path,
+            shadow,
+            &prep.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap();
+
+        let current = CryptoEpoch::new(epoch, CryptoAlgorithm::V1);
+        let headers: HashMap<DeviceId, DeviceHeader> = (0..devices)
+            .map(|_| {
+                let keypair = KyberKEM::generate_keypair();
+                let (_, encrypted_dek) = KyberKEM::encapsulate(&keypair.public).unwrap();
+                let header =
+                    DeviceHeader::new(DeviceId::generate(), current, keypair.public, encrypted_dek);
+                (header.device_id, header)
+            })
+            .collect();
+
+        let dek = DataEncryptionKey::from_bytes(*prep.new_dek.as_bytes());
+        (PqrrStateMachine::create(current, headers), dek)
+    }
+
     // ------------------------------------------------------------------------
     // execute_rotation() Tests (Invariant #3)
     // ------------------------------------------------------------------------
@@ -492,6 +1348,142 @@ mod tests {
         }
     }
 
+    // ------------------------------------------------------------------------
+    // plan() / execute() Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_plan_healthy_vault_then_execute() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 2, 5);
+        let digest_before = sm.headers_digest();
+        let vault_before = std::fs::read(&vault_path).unwrap();
+
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
+        let plan = coordinator.plan(&vault_path, Role::Authorized).unwrap();
+
+        assert!(plan.is_ready(), "unexpected blockers: {:?}", plan.blockers);
+        assert_eq!(plan.current_epoch.version, 2);
+        assert_eq!(plan.target_epoch.version, 3);
+        assert_eq!(plan.rewrap.len(), 5);
+        assert!(plan.rewrap.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(plan.skipped.is_empty());
+        assert!(plan.checks.iter().all(|check| check.passed));
+        assert_eq!(
+            plan.checks.iter().map(|c| c.invariant).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert!(plan.estimated_bytes > vault_before.len() as u64 + 5 * 1568 * 2);
+
+        // Planning writes nothing
+        assert_eq!(std::fs::read(&vault_path).unwrap(), vault_before);
+        assert_eq!(coordinator.state_machine.headers_digest(), digest_before);
+        assert_eq!(coordinator.state_machine.current_epoch().version, 2);
+
+        coordinator.execute(&plan).unwrap();
+        assert_eq!(coordinator.state_machine.current_epoch().version, 3);
+        assert!(coordinator
+            .state_machine
+            .device_headers()
+            .values()
+            .all(|h| h.epoch.version == 3));
+    }
+
+    #[test]
+    fn test_plan_reports_all_blockers() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 2, 4);
+        let mut ids: Vec<DeviceId> = sm.device_headers().keys().copied().collect();
+        ids.sort_by_key(|id| id.0);
+
+        let headers = sm.device_headers_mut();
+        headers.get_mut(&ids[0]).unwrap().public_key = KyberPublicKeyBytes([0u8; 1568]);
+        headers.get_mut(&ids[1]).unwrap().epoch = CryptoEpoch::new(1, CryptoAlgorithm::V1);
+        headers.get_mut(&ids[2]).unwrap().status = DeviceStatus::Revoked;
+        headers
+            .get_mut(&ids[3])
+            .unwrap()
+            .degrade(DegradeReason::IntegrityCheckFailed, 0)
+            .unwrap();
+        sm.transition_to_degraded_internal().unwrap();
+
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
+        let plan = coordinator.plan(&vault_path, Role::Recovery).unwrap();
+
+        assert_eq!(
+            plan.blockers,
+            vec![
+                UpgradeBlocker::PermissionDenied {
+                    role: Role::Recovery
+                },
+                UpgradeBlocker::NotIdle {
+                    state: "Degraded".to_string()
+                },
+                UpgradeBlocker::MissingPublicKey { device_id: ids[0] },
+                UpgradeBlocker::StaleHeader {
+                    device_id: ids[1],
+                    header_epoch: 1
+                },
+            ]
+        );
+        assert_eq!(plan.rewrap, vec![ids[0], ids[1]]);
+        assert_eq!(
+            plan.skipped,
+            vec![
+                SkippedDevice {
+                    device_id: ids[2],
+                    status: DeviceStatus::Revoked
+                },
+                SkippedDevice {
+                    device_id: ids[3],
+                    status: DeviceStatus::Degraded
+                },
+            ]
+        );
+        assert_eq!(
+            plan.checks.iter().map(|c| c.passed).collect::<Vec<_>>(),
+            vec![true, false, false]
+        );
+
+        let result = coordinator.execute(&plan);
+        assert!(matches!(result, Err(PqrrError::UpgradeFailed { ref step, .. }) if step == "plan"));
+        assert_eq!(coordinator.state_machine.current_epoch().version, 2);
+    }
+
+    #[test]
+    fn test_execute_rejects_stale_plan() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 2, 3);
+        let revoked = *sm.device_headers().keys().next().unwrap();
+
+        let plan = EpochUpgradeCoordinator::new(&mut sm)
+            .with_current_dek(&dek)
+            .plan(&vault_path, Role::Authorized)
+            .unwrap();
+        assert!(plan.is_ready());
+
+        crate::protocol::device_mgmt::revoke_device(&mut sm, &revoked).unwrap();
+
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
+        let result = coordinator.execute(&plan);
+        assert!(matches!(result, Err(PqrrError::UpgradeFailed { ref step, .. }) if step == "plan"));
+        assert_eq!(coordinator.state_machine.current_epoch().version, 2);
+        assert!(matches!(
+            coordinator.state_machine.state(),
+            ProtocolState::Idle
+        ));
+
+        // A fresh plan skips the revoked device and completes its revocation
+        let plan = coordinator.plan(&vault_path, Role::Authorized).unwrap();
+        assert_eq!(plan.completes_revocations, vec![revoked]);
+        assert_eq!(plan.rewrap.len(), 2);
+        coordinator.execute(&plan).unwrap();
+        assert!(!coordinator.state_machine.has_pending_revocations());
+    }
+
     // ------------------------------------------------------------------------
     // execute_epoch_upgrade() Tests
     // ------------------------------------------------------------------------
@@ -500,9 +1492,8 @@ mod tests {
     fn test_execute_epoch_upgrade_authorized_succeeds() {
         let temp_dir = TempDir::new().unwrap();
         let vault_path = temp_dir.path().join("vault.db");
-
-        let mut sm = PqrrStateMachine::new(0);
-        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm);
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 1, 0);
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
 
         let new_epoch = CryptoEpoch::new(2, CryptoAlgorithm::V1);
 
@@ -523,6 +1514,162 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_execute_epoch_upgrade_updates_metadata_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let mut metadata = InMemoryMetadataStore::default();
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 1, 0);
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm)
+            .with_current_dek(&dek)
+            .with_metadata_store(&mut metadata);
+        coordinator
+            .execute_epoch_upgrade(
+                &vault_path,
+                CryptoEpoch::new(2, CryptoAlgorithm::V1),
+                Role::Authorized,
+            )
+            .unwrap();
+        drop(coordinator);
+
+        assert_eq!(metadata.get_local_epoch().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_execute_epoch_upgrade_wraps_one_dek_for_every_device() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (_, dek) = setup_vault_with_devices(&vault_path, 1, 0);
+
+        let current = CryptoEpoch::new(1, CryptoAlgorithm::V1);
+        let keypairs: Vec<_> = (0..2).map(|_| KyberKEM::generate_keypair()).collect();
+        let headers: HashMap<DeviceId, DeviceHeader> = keypairs
+            .iter()
+            .map(|keypair| {
+                let (_, encrypted_dek) = KyberKEM::encapsulate(&keypair.public).unwrap();
+                let header = DeviceHeader::new(
+                    DeviceId::generate(),
+                    current,
+                    keypair.public.clone(),
+                    encrypted_dek,
+                );
+                (header.device_id, header)
+            })
+            .collect();
+        let mut sm = PqrrStateMachine::create(current, headers);
+
+        let new_epoch = CryptoEpoch::new(2, CryptoAlgorithm::V1);
+        EpochUpgradeCoordinator::new(&mut sm)
+            .with_current_dek(&dek)
+            .execute_epoch_upgrade(&vault_path, new_epoch, Role::Authorized)
+            .unwrap();
+
+        let upgraded = sm.device_headers();
+        let deks: Vec<[u8; 32]> = keypairs
+            .iter()
+            .map(|keypair| {
+                let header = upgraded
+                    .values()
+                    .find(|h| h.public_key == keypair.public)
+                    .unwrap();
+                assert_eq!(header.epoch.version, 2);
+                *unwrap_dek(&keypair.secret, &header.wrapped_dek().unwrap())
+                    .unwrap()
+                    .as_bytes()
+            })
+            .collect();
+        assert_eq!(deks[0], deks[1]);
+
+        // The wrapped DEK unseals the upgraded vault
+        let stored_vk = read_vault_key(&vault_path).unwrap().unwrap();
+        let blob = read_vault_blob(&vault_path).unwrap();
+        let dek = XChaCha20Key::from_bytes(&deks[0]).unwrap();
+        assert_eq!(open_vault(&blob, &stored_vk, &dek).unwrap(), b"vault data");
+    }
+
+    #[test]
+    fn test_execute_epoch_upgrade_reports_monotonic_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 1, 3);
+
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let new_epoch = CryptoEpoch::new(2, CryptoAlgorithm::V1);
+        EpochUpgradeCoordinator::new(&mut sm)
+            .with_current_dek(&dek)
+            .with_progress_callback(Box::new(move |p| sink.lock().unwrap().push(p)))
+            .execute_epoch_upgrade(&vault_path, new_epoch, Role::Authorized)
+            .unwrap();
+
+        let events = events.lock().unwrap();
+        assert!(events.iter().all(|p| p.target_epoch == 2));
+        assert_eq!(events.first().unwrap().phase, UpgradePhase::Preparing);
+        assert_eq!(events.last().unwrap().phase, UpgradePhase::Done);
+
+        let rank = |phase: &UpgradePhase| match phase {
+            UpgradePhase::Preparing => 0,
+            UpgradePhase::EncapsulatingDevices { .. } => 1,
+            UpgradePhase::WritingShadow { .. } => 2,
+            UpgradePhase::Committing => 3,
+            UpgradePhase::Done => 4,
+        };
+        let ranks: Vec<_> = events.iter().map(|p| rank(&p.phase)).collect();
+        assert!(ranks.windows(2).all(|w| w[0] <= w[1]), "{:?}", events);
+        assert_eq!(ranks.iter().filter(|&&r| r == 3).count(), 1);
+
+        let devices: Vec<_> = events
+            .iter()
+            .filter_map(|p| match p.phase {
+                UpgradePhase::EncapsulatingDevices { done, total } => Some((done, total)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(devices, vec![(0, 3), (1, 3), (2, 3), (3, 3)]);
+
+        let bytes: Vec<_> = events
+            .iter()
+            .filter_map(|p| match p.phase {
+                UpgradePhase::WritingShadow {
+                    bytes_done,
+                    bytes_total,
+                } => Some((bytes_done, bytes_total)),
+                _ => None,
+            })
+            .collect();
+        assert!(!bytes.is_empty());
+        assert!(bytes.windows(2).all(|w| w[0].0 <= w[1].0));
+        let (last_done, last_total) = *bytes.last().unwrap();
+        assert_eq!(last_done, last_total);
+        assert_eq!(std::fs::metadata(&vault_path).unwrap().len(), last_total);
+    }
+
+    #[test]
+    fn test_execute_epoch_upgrade_requires_current_dek() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, _) = setup_vault_with_devices(&vault_path, 1, 2);
+        let vault_before = std::fs::read(&vault_path).unwrap();
+        let new_epoch = CryptoEpoch::new(2, CryptoAlgorithm::V1);
+
+        let result = EpochUpgradeCoordinator::new(&mut sm).execute_epoch_upgrade(
+            &vault_path,
+            new_epoch,
+            Role::Authorized,
+        );
+        assert!(matches!(result, Err(PqrrError::StorageError { .. })));
+
+        // A DEK of another epoch cannot unseal the vault key
+        let result = EpochUpgradeCoordinator::new(&mut sm)
+            .with_current_dek(&DataEncryptionKey::generate())
+            .execute_epoch_upgrade(&vault_path, new_epoch, Role::Authorized);
+        assert!(matches!(result, Err(PqrrError::StorageError { .. })));
+
+        assert_eq!(std::fs::read(&vault_path).unwrap(), vault_before);
+        assert_eq!(sm.current_epoch().version, 1);
+        assert!(matches!(sm.state(), ProtocolState::Idle));
+    }
+
     #[test]
     fn test_execute_epoch_upgrade_recovery_fails() {
         let temp_dir = TempDir::new().unwrap();
@@ -577,6 +1724,253 @@ mod tests {
         }
     }
 
+    // ------------------------------------------------------------------------
+    // Two-Phase Header Update / abort() Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_encapsulation_failure_leaves_vault_and_headers_at_old_epoch() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 1, 5);
+
+        let vault_before = std::fs::read(&vault_path).unwrap();
+        let headers_before = sm.device_headers().clone();
+        let new_epoch = CryptoEpoch::new(2, CryptoAlgorithm::V1);
+
+        {
+            let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
+            let devices = AtomicUsize::new(0);
+            let result = coordinator.execute_epoch_upgrade_with(
+                &vault_path,
+                new_epoch,
+                Role::Authorized,
+                |new_dek, pubkeys| {
+                    devices.store(pubkeys.len(), Ordering::SeqCst);
+                    let mut wrapped = wrap_dek_for_devices(new_dek, pubkeys);
+                    wrapped[2].1 = Err(CryptoError::KemError(
+                        "injected encapsulation failure".to_string(),
+                    ));
+                    wrapped
+                },
+                |path, shadow, epoch, metadata| aup_atomic_commit(path, shadow, epoch, metadata),
+            );
+
+            assert!(matches!(result, Err(PqrrError::HeaderIncomplete { .. })));
+            // Every device is wrapped before any header is staged
+            assert_eq!(devices.load(Ordering::SeqCst), 5);
+        }
+
+        // Vault file, headers and state machine are untouched
+        assert_eq!(std::fs::read(&vault_path).unwrap(), vault_before);
+        assert_eq!(
+            crate::storage::aug::read_vault_epoch(&vault_path).unwrap(),
+            1
+        );
+        assert_eq!(*sm.device_headers(), headers_before);
+        assert_eq!(sm.current_epoch().version, 1);
+        assert!(matches!(sm.state(), ProtocolState::Idle));
+        assert!(sm.rekeying_context().is_none());
+        assert!(!crate::storage::ShadowWriter::new(&vault_path)
+            .temp_path()
+            .exists());
+
+        // Retry succeeds and rewraps every header
+        {
+            let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
+            coordinator
+                .execute_epoch_upgrade(&vault_path, new_epoch, Role::Authorized)
+                .unwrap();
+        }
+
+        assert_eq!(
+            crate::storage::aug::read_vault_epoch(&vault_path).unwrap(),
+            2
+        );
+        assert_eq!(sm.current_epoch().version, 2);
+        assert_eq!(sm.device_headers().len(), 5);
+        for (id, header) in sm.device_headers().iter() {
+            let before = &headers_before[id];
+            assert_eq!(header.epoch.version, 2);
+            assert_eq!(header.public_key, before.public_key);
+            assert_ne!(header.encrypted_dek, before.encrypted_dek);
+        }
+    }
+
+    #[test]
+    fn test_commit_failure_rolls_back_memory_and_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 1, 3);
+
+        let vault_before = std::fs::read(&vault_path).unwrap();
+        let headers_before = sm.device_headers().clone();
+        let shadow_path = crate::storage::ShadowWriter::new(&vault_path).temp_path();
+        let new_epoch = CryptoEpoch::new(2, CryptoAlgorithm::V1);
+        let mut metadata = InMemoryMetadataStore::default();
+        metadata.set_local_epoch(1).unwrap();
+
+        {
+            let mut coordinator = EpochUpgradeCoordinator::new(&mut sm)
+                .with_current_dek(&dek)
+                .with_metadata_store(&mut metadata);
+            let result = coordinator.execute_epoch_upgrade_with(
+                &vault_path,
+                new_epoch,
+                Role::Authorized,
+                wrap_dek_for_devices,
+                |_, shadow, _, _| {
+                    // The shadow file is complete when the rename fails
+                    assert!(shadow.path().exists());
+                    Err(StorageError::atomic_rename("injected rename failure"))
+                },
+            );
+
+            match result {
+                Err(PqrrError::UpgradeFailed { step, reason }) => {
+                    assert_eq!(step, "commit");
+                    assert!(reason.contains("injected rename failure"));
+                    assert!(reason.contains("rolled back to epoch 1"));
+                }
+                other => panic!("expected UpgradeFailed, got {:?}", other),
+            }
+        }
+
+        // Memory stays at the old epoch
+        assert_eq!(sm.current_epoch().version, 1);
+        assert!(matches!(sm.state(), ProtocolState::Idle));
+        assert!(sm.rekeying_context().is_none());
+        assert_eq!(*sm.device_headers(), headers_before);
+
+        // Disk stays at the old epoch
+        assert_eq!(std::fs::read(&vault_path).unwrap(), vault_before);
+        assert_eq!(
+            crate::storage::aug::read_vault_epoch(&vault_path).unwrap(),
+            1
+        );
+        assert_eq!(metadata.get_local_epoch().unwrap(), 1);
+        assert!(!shadow_path.exists());
+
+        // The vault lock was released, so a retry succeeds
+        EpochUpgradeCoordinator::new(&mut sm)
+            .with_current_dek(&dek)
+            .execute_epoch_upgrade(&vault_path, new_epoch, Role::Authorized)
+            .unwrap();
+        assert_eq!(sm.current_epoch().version, 2);
+    }
+
+    #[test]
+    fn test_rekey_progress_tracks_staged_devices() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 1, 2);
+        assert_eq!(
+            EpochUpgradeCoordinator::new(&mut sm)
+                .with_current_dek(&dek)
+                .rekey_progress(),
+            None
+        );
+
+        sm.transition_to_rekeying_internal(CryptoEpoch::new(2, CryptoAlgorithm::V1))
+            .unwrap();
+        let first = sm.rekeying_context().unwrap().pending_devices[0];
+        sm.rekeying_context_mut()
+            .unwrap()
+            .mark_device_completed(&first);
+
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
+        let progress = coordinator.rekey_progress().unwrap();
+        assert_eq!((progress.old_epoch, progress.new_epoch), (1, 2));
+        assert_eq!((progress.completed, progress.remaining), (1, 1));
+        assert_eq!(progress.progress, 0.5);
+
+        coordinator.abort().unwrap();
+        assert_eq!(coordinator.rekey_progress(), None);
+    }
+
+    #[test]
+    fn test_rollback_without_upgrade_in_progress_is_noop() {
+        let mut sm = PqrrStateMachine::new(3);
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm);
+
+        coordinator.rollback().unwrap();
+        assert_eq!(coordinator.state_machine.current_epoch().version, 3);
+        assert!(matches!(
+            coordinator.state_machine.state(),
+            ProtocolState::Idle
+        ));
+    }
+
+    #[test]
+    fn test_abort_removes_residual_shadow_and_returns_to_idle() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 1, 2);
+        let vault_before = std::fs::read(&vault_path).unwrap();
+
+        // Simulate a crash after the shadow write but before staging finished
+        sm.transition_to_rekeying_internal(CryptoEpoch::new(2, CryptoAlgorithm::V1))
+            .unwrap();
+        let temp_path = crate::storage::ShadowWriter::new(&vault_path).temp_path();
+        std::fs::write(&temp_path, b"partial shadow").unwrap();
+        sm.rekeying_context_mut().unwrap().temp_vault_path = Some(temp_path.display().to_string());
+
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
+        coordinator.abort().unwrap();
+
+        assert!(!temp_path.exists());
+        assert_eq!(std::fs::read(&vault_path).unwrap(), vault_before);
+        assert_eq!(sm.current_epoch().version, 1);
+        assert!(matches!(sm.state(), ProtocolState::Idle));
+    }
+
+    #[test]
+    fn test_abort_without_upgrade_in_progress_fails() {
+        let mut sm = PqrrStateMachine::new(0);
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm);
+
+        let result = coordinator.abort();
+        assert!(matches!(
+            result,
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+    }
+
+    #[test]
+    fn test_concurrent_writer_lock_aborts_upgrade() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 1, 2);
+        let vault_before = std::fs::read(&vault_path).unwrap();
+
+        // Another writer holds the vault
+        let other_writer = VaultLock::try_acquire(&vault_path).unwrap();
+
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
+        let result = coordinator.execute_epoch_upgrade(
+            &vault_path,
+            CryptoEpoch::new(2, CryptoAlgorithm::V1),
+            Role::Authorized,
+        );
+        assert!(matches!(result, Err(PqrrError::StorageError { .. })));
+        assert_eq!(std::fs::read(&vault_path).unwrap(), vault_before);
+        assert_eq!(sm.current_epoch().version, 1);
+        assert!(matches!(sm.state(), ProtocolState::Idle));
+
+        // Once released, the upgrade runs and releases the lock on commit
+        drop(other_writer);
+        let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
+        coordinator
+            .execute_epoch_upgrade(
+                &vault_path,
+                CryptoEpoch::new(2, CryptoAlgorithm::V1),
+                Role::Authorized,
+            )
+            .unwrap();
+        assert_eq!(sm.current_epoch().version, 2);
+        VaultLock::try_acquire(&vault_path).unwrap();
+    }
+
     // ------------------------------------------------------------------------
     // recover_from_crash() Tests
     // ------------------------------------------------------------------------
@@ -600,7 +1994,13 @@ mod tests {
 
         let prep = aup_prepare(&epoch1, &encrypted_vk, &dek, vault_data).unwrap();
         let shadow = aup_shadow_write(&vault_path, &prep).unwrap();
-        aup_atomic_commit(&vault_path, shadow, &prep.new_epoch).unwrap();
+        aup_atomic_commit(
+            &vault_path,
+            shadow,
+            &prep.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap();
 
         // Initialize state machine at epoch 2 (matching vault)
         let mut sm = PqrrStateMachine::new(prep.new_epoch.version as u32);
@@ -631,7 +2031,13 @@ mod tests {
 
         let prep = aup_prepare(&epoch1, &encrypted_vk, &dek, vault_data).unwrap();
         let shadow = aup_shadow_write(&vault_path, &prep).unwrap();
-        aup_atomic_commit(&vault_path, shadow, &prep.new_epoch).unwrap();
+        aup_atomic_commit(
+            &vault_path,
+            shadow,
+            &prep.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap();
 
         // Initialize state machine at epoch 1 (simulating crash during Phase 3)
         let mut sm = PqrrStateMachine::new(epoch1.version as u32);
@@ -668,28 +2074,28 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let vault_path = temp_dir.path().join("vault.db");
 
-        let mut sm = PqrrStateMachine::new(0);
+        let (mut sm, dek) = setup_vault_with_devices(&vault_path, 1, 2);
 
         // Verify initial state
-        assert_eq!(sm.current_epoch().version, 0);
+        assert_eq!(sm.current_epoch().version, 1);
         assert!(matches!(sm.state(), ProtocolState::Idle));
 
-        // Execute epoch upgrade (0 -> 1)
-        let new_epoch = CryptoEpoch::new(1, CryptoAlgorithm::V1);
+        // Execute epoch upgrade (1 -> 2)
+        let new_epoch = CryptoEpoch::new(2, CryptoAlgorithm::V1);
         {
-            let mut coordinator = EpochUpgradeCoordinator::new(&mut sm);
+            let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
             assert!(coordinator
                 .execute_epoch_upgrade(&vault_path, new_epoch, Role::Authorized)
                 .is_ok());
         }
 
         // Verify final state
-        assert_eq!(sm.current_epoch().version, 1);
+        assert_eq!(sm.current_epoch().version, 2);
         assert!(matches!(sm.state(), ProtocolState::Idle));
 
         // Verify vault file was updated
         let vault_epoch = crate::storage::aug::read_vault_epoch(&vault_path).unwrap();
-        assert_eq!(vault_epoch, 1);
+        assert_eq!(vault_epoch, 2);
     }
 
     #[test]
@@ -697,16 +2103,18 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let vault_path = temp_dir.path().join("vault.db");
 
-        let mut sm = PqrrStateMachine::new(0);
+        let (mut sm, mut dek) = setup_vault_with_devices(&vault_path, 1, 2);
 
-        // Execute 3 epoch upgrades
+        // Execute 3 epoch upgrades, each unsealing the vault with the
+        // previous upgrade's DEK
         for i in 2..=4 {
             let new_epoch = CryptoEpoch::new(i, CryptoAlgorithm::V1);
             {
-                let mut coordinator = EpochUpgradeCoordinator::new(&mut sm);
+                let mut coordinator = EpochUpgradeCoordinator::new(&mut sm).with_current_dek(&dek);
                 assert!(coordinator
                     .execute_epoch_upgrade(&vault_path, new_epoch, Role::Authorized)
                     .is_ok());
+                dek = DataEncryptionKey::from_bytes(*coordinator.current_dek().unwrap().as_bytes());
             }
 
             assert_eq!(sm.current_epoch().version, i);
@@ -11,7 +11,29 @@
 //! - `PermissionDenied` - Invariant #3 enforcement (RECOVERY cannot σ_rotate)
 //! - `InvalidStateTransition` - State machine logic error
 //! - `StorageError` - Storage layer error propagation
+//! - `ReadOnlyMode` - Mutation attempted while the device is Degraded
+//! - `ClockRegression` - Device clock moved backward during a recovery window
+//! - `UnauthorizedVeto` - Veto received from a device that is not active
+//! - `CausalBarrier` - Invariant #3 violation (role/operation pairing not permitted)
+//! - `InvalidVetoSignature` - Veto signature missing, forged, or bound to another request
+//! - `TransitionLogTampered` - Transition audit log hash chain is broken
+//! - `DeviceLimitExceeded` - Registration would exceed the active device cap
+//! - `OperationInProgress` - Another protocol operation holds the state machine
+//! - `PendingRevocation` - A revoked device still awaits its epoch rotation
+//! - `SessionExpired` - Vault session was used after its idle timeout
+//! - `AuthenticationFailed` - Vault could not be unlocked with the given credentials
+//! - `InvalidPairingPayload` - Pairing QR payload malformed or key commitment mismatch
+//! - `PairingExpired` - Pairing QR payload used after its expiry
+//! - `UpgradeFailed` - Epoch upgrade step failed and was rolled back
+//! - `ClockUnavailable` - The system clock could not be read
+//! - `RecoveryRateLimited` - Too many recent recovery initiations
+//! - `InvalidHandle` - Session handle unknown, closed, or from an earlier session
+//! - `TooManySessions` - Live session cap reached
+//! - `EpochFollowRejected` - Announced epoch upgrade rejected by a follower
+//! - `EpochOutOfRange` - Epoch version does not fit the 32-bit bridge representation
+//! - `ItemNotFound` - No vault item with the requested ID
 
+use crate::protocol::epoch_follow::FollowRejection;
 use std::fmt;
 
 /// Protocol error type
@@ -98,6 +120,218 @@ pub enum PqrrError {
         /// Renamed to `storage_msg` to avoid conflict with Throwable.message in Kotlin
         storage_msg: String,
     },
+
+    /// Read-only mode (Degraded state)
+    ///
+    /// This error occurs when a mutating operation is attempted while the
+    /// state machine is Degraded. Read and decrypt operations keep working.
+    ReadOnlyMode {
+        /// Why the device was degraded
+        reason: String,
+    },
+
+    /// Device clock moved backward
+    ///
+    /// This error occurs when the current time is earlier (beyond the drift
+    /// tolerance) than a timestamp already recorded for a recovery window,
+    /// which indicates clock manipulation.
+    ClockRegression {
+        /// Latest timestamp already recorded (Unix milliseconds)
+        recorded_ms: u64,
+        /// Current clock reading (Unix milliseconds)
+        now_ms: u64,
+    },
+
+    /// Veto from a device that is not active
+    ///
+    /// This error occurs when a revoked or unknown device submits a veto.
+    /// Only Active and Degraded devices may block a recovery (Invariant #4).
+    UnauthorizedVeto {
+        /// Device ID that sent the veto
+        device_id: String,
+    },
+
+    /// Invariant #3 violation: causal barrier
+    ///
+    /// This error occurs when a protocol step is attempted by a role that
+    /// Invariant #3 does not permit for it, e.g. a RECOVERY device trying
+    /// to complete a recovery that rotates keys.
+    CausalBarrier {
+        /// Device role that attempted the step
+        role: String,
+        /// Operation the step requires
+        operation: String,
+    },
+
+    /// Veto signature failed verification
+    ///
+    /// This error occurs when a veto's signature is missing, forged, or was
+    /// produced for a different recovery request or device.
+    InvalidVetoSignature {
+        /// Device ID claimed by the veto
+        device_id: String,
+    },
+
+    /// Transition audit log failed hash chain verification
+    ///
+    /// This error occurs when an entry of the transition log was modified,
+    /// reordered, or removed.
+    TransitionLogTampered {
+        /// Index of the first entry that does not verify
+        index: u32,
+    },
+
+    /// Active device cap reached
+    ///
+    /// This error occurs when registering a device would push the number
+    /// of active devices above the configured limit.
+    DeviceLimitExceeded {
+        /// Maximum number of active devices
+        limit: u32,
+        /// Active devices before the rejected registration
+        active: u32,
+    },
+
+    /// Another protocol operation is in progress
+    ///
+    /// This error occurs when an operation is started while another one
+    /// still holds the state machine's operation guard.
+    OperationInProgress {
+        /// Operation currently in progress
+        operation: String,
+    },
+
+    /// Revocation awaiting epoch rotation
+    ///
+    /// This error occurs when registering a device or initiating recovery
+    /// while a revoked device still holds the current DEK. An epoch upgrade
+    /// must follow the revocation first.
+    PendingRevocation {
+        /// Number of revocations awaiting rotation
+        pending: u32,
+    },
+
+    /// Vault session idle timeout elapsed
+    ///
+    /// This error occurs when a vault session is used after being idle for
+    /// longer than its timeout. The session is locked and must be replaced.
+    SessionExpired {
+        /// Time since the last use (milliseconds)
+        idle_ms: u64,
+        /// Configured idle timeout (milliseconds)
+        timeout_ms: u64,
+    },
+
+    /// Unlock credentials rejected
+    ///
+    /// This error occurs when the vault key cannot be unwrapped with the
+    /// supplied password. The reason is deliberately generic so callers
+    /// cannot tell a wrong password from a corrupted wrapping.
+    AuthenticationFailed {
+        /// Error reason
+        reason: String,
+    },
+
+    /// Pairing payload rejected
+    ///
+    /// This error occurs when a scanned pairing QR payload cannot be decoded,
+    /// speaks an incompatible protocol version, or when the Kyber public key
+    /// received during the handshake does not match its commitment.
+    InvalidPairingPayload {
+        /// Error reason
+        reason: String,
+    },
+
+    /// Pairing payload expired
+    ///
+    /// This error occurs when a pairing QR payload is decoded after its
+    /// expiry. The new device must display a fresh code.
+    PairingExpired {
+        /// Payload expiry (Unix milliseconds)
+        expires_at_ms: u64,
+        /// Current time (Unix milliseconds)
+        now_ms: u64,
+    },
+
+    /// Epoch upgrade failed
+    ///
+    /// This error occurs when a step of an epoch upgrade fails at or after
+    /// the commit, or when rolling back a failed step fails as well. The
+    /// reason includes the outcome of the rollback.
+    UpgradeFailed {
+        /// Step that failed
+        step: String,
+        /// Error reason
+        reason: String,
+    },
+
+    /// System clock could not be read
+    ///
+    /// This error occurs when the wall clock reads before the Unix epoch.
+    /// Timing decisions fail instead of treating the time as 0, which would
+    /// make every recovery window look expired.
+    ClockUnavailable {
+        /// Error reason
+        reason: String,
+    },
+
+    /// Recovery initiation rate limit exceeded
+    ///
+    /// This error occurs when a recovery window is already open, too many
+    /// recoveries were initiated recently, or the vault is locked out after
+    /// vetoed attempts. No window is opened and no device is prompted.
+    RecoveryRateLimited {
+        /// Time until an initiation may succeed (milliseconds)
+        retry_after_ms: u64,
+    },
+
+    /// Session handle rejected
+    ///
+    /// This error occurs when a handle was never issued, was closed, or
+    /// belongs to an earlier session whose slot has since been reused.
+    InvalidHandle {
+        /// Rejected handle
+        handle: u64,
+    },
+
+    /// Live session cap reached
+    ///
+    /// This error occurs when opening a session while the configured number
+    /// of sessions is already open. Close a session first.
+    TooManySessions {
+        /// Maximum number of live sessions
+        limit: u32,
+    },
+
+    /// Epoch announcement rejected by a follower
+    ///
+    /// This error occurs when a follower device cannot verify or apply an
+    /// epoch upgrade announced by another device. Up to the vault commit,
+    /// local state is unchanged. `rejection` is safe to report back to the
+    /// initiator; `reason` is for local logs.
+    EpochFollowRejected {
+        /// Coarse rejection reason
+        rejection: FollowRejection,
+        /// Error reason
+        reason: String,
+    },
+
+    /// Epoch version too large for the bridge
+    ///
+    /// Epochs are `u64` internally but exported as `u32`. This error occurs
+    /// instead of silently truncating a version above `u32::MAX`.
+    EpochOutOfRange {
+        /// Epoch version that does not fit
+        epoch: u64,
+    },
+
+    /// Vault item not found
+    ///
+    /// This error occurs when reading an item ID the vault does not list.
+    ItemNotFound {
+        /// Requested item ID
+        item_id: String,
+    },
 }
 
 impl PqrrError {
@@ -139,6 +373,129 @@ impl PqrrError {
         PqrrError::StorageError { storage_msg }
     }
 
+    /// Create a ReadOnlyMode error
+    pub fn read_only_mode(reason: String) -> Self {
+        PqrrError::ReadOnlyMode { reason }
+    }
+
+    /// Create a ClockRegression error
+    pub fn clock_regression(recorded_ms: u64, now_ms: u64) -> Self {
+        PqrrError::ClockRegression {
+            recorded_ms,
+            now_ms,
+        }
+    }
+
+    /// Create an UnauthorizedVeto error
+    pub fn unauthorized_veto(device_id: String) -> Self {
+        PqrrError::UnauthorizedVeto { device_id }
+    }
+
+    /// Create a CausalBarrier error
+    pub fn causal_barrier(role: String, operation: String) -> Self {
+        PqrrError::CausalBarrier { role, operation }
+    }
+
+    /// Create an InvalidVetoSignature error
+    pub fn invalid_veto_signature(device_id: String) -> Self {
+        PqrrError::InvalidVetoSignature { device_id }
+    }
+
+    /// Create a TransitionLogTampered error
+    pub fn transition_log_tampered(index: u32) -> Self {
+        PqrrError::TransitionLogTampered { index }
+    }
+
+    /// Create a DeviceLimitExceeded error
+    pub fn device_limit_exceeded(limit: u32, active: u32) -> Self {
+        PqrrError::DeviceLimitExceeded { limit, active }
+    }
+
+    /// Create an OperationInProgress error
+    pub fn operation_in_progress(operation: String) -> Self {
+        PqrrError::OperationInProgress { operation }
+    }
+
+    /// Create a PendingRevocation error
+    pub fn pending_revocation(pending: u32) -> Self {
+        PqrrError::PendingRevocation { pending }
+    }
+
+    /// Create an AuthenticationFailed error
+    pub fn authentication_failed(reason: String) -> Self {
+        PqrrError::AuthenticationFailed { reason }
+    }
+
+    /// Create a SessionExpired error
+    pub fn session_expired(idle_ms: u64, timeout_ms: u64) -> Self {
+        PqrrError::SessionExpired {
+            idle_ms,
+            timeout_ms,
+        }
+    }
+
+    /// Create an InvalidPairingPayload error
+    pub fn invalid_pairing_payload(reason: String) -> Self {
+        PqrrError::InvalidPairingPayload { reason }
+    }
+
+    /// Create a PairingExpired error
+    pub fn pairing_expired(expires_at_ms: u64, now_ms: u64) -> Self {
+        PqrrError::PairingExpired {
+            expires_at_ms,
+            now_ms,
+        }
+    }
+
+    /// Create an UpgradeFailed error
+    pub fn upgrade_failed(step: String, reason: String) -> Self {
+        PqrrError::UpgradeFailed { step, reason }
+    }
+
+    /// Create a ClockUnavailable error
+    pub fn clock_unavailable(reason: String) -> Self {
+        PqrrError::ClockUnavailable { reason }
+    }
+
+    /// Create a RecoveryRateLimited error
+    pub fn recovery_rate_limited(retry_after_ms: u64) -> Self {
+        PqrrError::RecoveryRateLimited { retry_after_ms }
+    }
+
+    /// Create an InvalidHandle error
+    pub fn invalid_handle(handle: u64) -> Self {
+        PqrrError::InvalidHandle { handle }
+    }
+
+    /// Create a TooManySessions error
+    pub fn too_many_sessions(limit: u32) -> Self {
+        PqrrError::TooManySessions { limit }
+    }
+
+    /// Create an EpochFollowRejected error
+    pub fn epoch_follow_rejected(rejection: FollowRejection, reason: String) -> Self {
+        PqrrError::EpochFollowRejected { rejection, reason }
+    }
+
+    /// Create an EpochOutOfRange error
+    pub fn epoch_out_of_range(epoch: u64) -> Self {
+        PqrrError::EpochOutOfRange { epoch }
+    }
+
+    /// Create an ItemNotFound error
+    pub fn item_not_found(item_id: String) -> Self {
+        PqrrError::ItemNotFound { item_id }
+    }
+
+    /// Convert an internal `u64` epoch version to the exported `u32`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::EpochOutOfRange` above `u32::MAX`.
+    pub fn epoch_to_u32(epoch: u64) -> Result<u32> {
+        u32::try_from(epoch).map_err(|_| Self::epoch_out_of_range(epoch))
+    }
+
     /// Check if this error represents an invariant violation
     pub fn is_invariant_violation(&self) -> bool {
         matches!(
@@ -146,6 +503,7 @@ impl PqrrError {
             PqrrError::EpochRegression { .. }
                 | PqrrError::HeaderIncomplete { .. }
                 | PqrrError::InsufficientPrivileges { .. }
+                | PqrrError::CausalBarrier { .. }
                 | PqrrError::Vetoed { .. }
         )
     }
@@ -156,6 +514,7 @@ impl PqrrError {
             PqrrError::EpochRegression { .. } => Some(1),
             PqrrError::HeaderIncomplete { .. } => Some(2),
             PqrrError::InsufficientPrivileges { .. } => Some(3),
+            PqrrError::CausalBarrier { .. } => Some(3),
             PqrrError::Vetoed { .. } => Some(4),
             _ => None,
         }
@@ -201,6 +560,102 @@ impl fmt::Display for PqrrError {
             PqrrError::StorageError { storage_msg } => {
                 write!(f, "Storage error: {}", storage_msg)
             }
+            PqrrError::ReadOnlyMode { reason } => {
+                write!(f, "Read-only mode: device degraded ({})", reason)
+            }
+            PqrrError::ClockRegression {
+                recorded_ms,
+                now_ms,
+            } => write!(
+                f,
+                "Clock regression: now={} is before recorded time {}",
+                now_ms, recorded_ms
+            ),
+            PqrrError::UnauthorizedVeto { device_id } => {
+                write!(f, "Veto rejected: device {} is not active", device_id)
+            }
+            PqrrError::CausalBarrier { role, operation } => write!(
+                f,
+                "Invariant #3 violation: causal barrier (role={}, op={})",
+                role, operation
+            ),
+            PqrrError::InvalidVetoSignature { device_id } => {
+                write!(
+                    f,
+                    "Veto rejected: invalid signature from device {}",
+                    device_id
+                )
+            }
+            PqrrError::TransitionLogTampered { index } => {
+                write!(f, "Transition log tampered at entry {}", index)
+            }
+            PqrrError::DeviceLimitExceeded { limit, active } => write!(
+                f,
+                "Device limit exceeded: {} active devices, limit {}",
+                active, limit
+            ),
+            PqrrError::OperationInProgress { operation } => {
+                write!(f, "Operation in progress: {}", operation)
+            }
+            PqrrError::PendingRevocation { pending } => write!(
+                f,
+                "Epoch rotation required: {} revocations pending",
+                pending
+            ),
+            PqrrError::AuthenticationFailed { reason } => {
+                write!(f, "Authentication failed: {}", reason)
+            }
+            PqrrError::SessionExpired {
+                idle_ms,
+                timeout_ms,
+            } => write!(
+                f,
+                "Session expired: idle for {} ms, timeout {} ms",
+                idle_ms, timeout_ms
+            ),
+            PqrrError::InvalidPairingPayload { reason } => {
+                write!(f, "Invalid pairing payload: {}", reason)
+            }
+            PqrrError::PairingExpired {
+                expires_at_ms,
+                now_ms,
+            } => write!(
+                f,
+                "Pairing payload expired at {} ms (now {} ms)",
+                expires_at_ms, now_ms
+            ),
+            PqrrError::UpgradeFailed { step, reason } => {
+                write!(f, "Epoch upgrade failed at {}: {}", step, reason)
+            }
+            PqrrError::ClockUnavailable { reason } => {
+                write!(f, "System clock unavailable: {}", reason)
+            }
+            PqrrError::RecoveryRateLimited { retry_after_ms } => write!(
+                f,
+                "Recovery rate limited: retry after {} ms",
+                retry_after_ms
+            ),
+            PqrrError::InvalidHandle { handle } => {
+                write!(f, "Invalid session handle: {:#018x}", handle)
+            }
+            PqrrError::TooManySessions { limit } => {
+                write!(f, "Too many sessions: limit {}", limit)
+            }
+            PqrrError::EpochFollowRejected { rejection, reason } => {
+                write!(
+                    f,
+                    "Epoch announcement rejected ({}, code {}): {}",
+                    rejection,
+                    rejection.code(),
+                    reason
+                )
+            }
+            PqrrError::EpochOutOfRange { epoch } => {
+                write!(f, "Epoch version {} exceeds the 32-bit range", epoch)
+            }
+            PqrrError::ItemNotFound { item_id } => {
+                write!(f, "Vault item not found: {}", item_id)
+            }
         }
     }
 }
@@ -278,4 +733,163 @@ mod tests {
         assert!(!err.is_invariant_violation());
         assert_eq!(err.invariant_number(), None);
     }
+
+    #[test]
+    fn test_error_read_only_mode() {
+        let err = PqrrError::read_only_mode("root detected".to_string());
+        assert!(!err.is_invariant_violation());
+        assert_eq!(err.invariant_number(), None);
+        assert!(err.to_string().contains("root detected"));
+    }
+
+    #[test]
+    fn test_error_clock_regression() {
+        let err = PqrrError::clock_regression(2_000, 1_000);
+        assert!(!err.is_invariant_violation());
+        assert_eq!(err.invariant_number(), None);
+        assert!(err.to_string().contains("now=1000"));
+    }
+
+    #[test]
+    fn test_error_unauthorized_veto() {
+        let err = PqrrError::unauthorized_veto("device_1".to_string());
+        assert!(!err.is_invariant_violation());
+        assert!(err.to_string().contains("device_1"));
+    }
+
+    #[test]
+    fn test_error_causal_barrier() {
+        let err = PqrrError::causal_barrier("RECOVERY".to_string(), "SIGMA_ROTATE".to_string());
+        assert!(err.is_invariant_violation());
+        assert_eq!(err.invariant_number(), Some(3));
+        assert!(err.to_string().contains("Invariant #3"));
+    }
+
+    #[test]
+    fn test_error_invalid_veto_signature() {
+        let err = PqrrError::invalid_veto_signature("device_1".to_string());
+        assert!(!err.is_invariant_violation());
+        assert!(err.to_string().contains("invalid signature"));
+    }
+
+    #[test]
+    fn test_error_transition_log_tampered() {
+        let err = PqrrError::transition_log_tampered(3);
+        assert!(!err.is_invariant_violation());
+        assert!(err.to_string().contains("entry 3"));
+    }
+
+    #[test]
+    fn test_error_device_limit_exceeded() {
+        let err = PqrrError::device_limit_exceeded(5, 5);
+        assert!(!err.is_invariant_violation());
+        assert!(err.to_string().contains("limit 5"));
+    }
+
+    #[test]
+    fn test_error_operation_in_progress() {
+        let err = PqrrError::operation_in_progress("EpochUpgrade".to_string());
+        assert!(!err.is_invariant_violation());
+        assert!(err.to_string().contains("EpochUpgrade"));
+    }
+
+    #[test]
+    fn test_error_pending_revocation() {
+        let err = PqrrError::pending_revocation(2);
+        assert!(!err.is_invariant_violation());
+        assert!(err.to_string().contains("2 revocations pending"));
+    }
+
+    #[test]
+    fn test_error_authentication_failed() {
+        let err = PqrrError::authentication_failed("wrong password".to_string());
+        assert!(!err.is_invariant_violation());
+        assert_eq!(err.to_string(), "Authentication failed: wrong password");
+    }
+
+    #[test]
+    fn test_error_session_expired() {
+        let err = PqrrError::session_expired(5_000, 1_000);
+        assert!(!err.is_invariant_violation());
+        assert!(err.to_string().contains("timeout 1000 ms"));
+    }
+
+    #[test]
+    fn test_error_invalid_pairing_payload() {
+        let err = PqrrError::invalid_pairing_payload("bad base64".to_string());
+        assert!(!err.is_invariant_violation());
+        assert_eq!(err.to_string(), "Invalid pairing payload: bad base64");
+    }
+
+    #[test]
+    fn test_error_pairing_expired() {
+        let err = PqrrError::pairing_expired(1_000, 2_000);
+        assert!(!err.is_invariant_violation());
+        assert!(err.to_string().contains("expired at 1000 ms"));
+    }
+
+    #[test]
+    fn test_error_upgrade_failed() {
+        let err = PqrrError::upgrade_failed("commit".to_string(), "rename failed".to_string());
+        assert!(!err.is_invariant_violation());
+        assert_eq!(err.invariant_number(), None);
+        assert_eq!(
+            err.to_string(),
+            "Epoch upgrade failed at commit: rename failed"
+        );
+    }
+
+    #[test]
+    fn test_error_clock_unavailable() {
+        let err = PqrrError::clock_unavailable("before Unix epoch".to_string());
+        assert!(!err.is_invariant_violation());
+        assert_eq!(err.invariant_number(), None);
+        assert_eq!(
+            err.to_string(),
+            "System clock unavailable: before Unix epoch"
+        );
+    }
+
+    #[test]
+    fn test_error_recovery_rate_limited() {
+        let err = PqrrError::recovery_rate_limited(3_600_000);
+        assert!(!err.is_invariant_violation());
+        assert_eq!(
+            err.to_string(),
+            "Recovery rate limited: retry after 3600000 ms"
+        );
+    }
+
+    #[test]
+    fn test_error_session_handles() {
+        let err = PqrrError::invalid_handle(0x0000_0002_0000_0001);
+        assert!(!err.is_invariant_violation());
+        assert_eq!(
+            err.to_string(),
+            "Invalid session handle: 0x0000000200000001"
+        );
+
+        let err = PqrrError::too_many_sessions(16);
+        assert_eq!(err.to_string(), "Too many sessions: limit 16");
+    }
+
+    #[test]
+    fn test_error_epoch_follow_rejected() {
+        let err = PqrrError::epoch_follow_rejected(
+            FollowRejection::CommitmentMismatch,
+            "unwrapped DEK does not match".to_string(),
+        );
+        assert!(!err.is_invariant_violation());
+        assert_eq!(
+            err.to_string(),
+            "Epoch announcement rejected (DEK commitment mismatch, code 3): unwrapped DEK does not match"
+        );
+    }
+
+    #[test]
+    fn test_error_item_not_found() {
+        let err = PqrrError::item_not_found("login/example.com".to_string());
+        assert!(!err.is_invariant_violation());
+        assert_eq!(err.to_string(), "Vault item not found: login/example.com");
+    }
 }
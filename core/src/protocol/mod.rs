@@ -15,7 +15,9 @@
 //! ## Modules
 //!
 //! - `pqrr` - PQRR state machine and epoch upgrade coordination
+//! - `epoch_follow` - Follower side of a cross-device epoch upgrade
 //! - `error` - Protocol-specific error types
+//! - `time` - Injectable wall-clock and monotonic time sources
 //!
 //! ## Four Mathematical Invariants
 //!
@@ -58,19 +60,35 @@
 
 // Sub-modules
 pub mod device_mgmt;
+pub mod epoch_follow;
 pub mod epoch_upgrade;
 pub mod error;
 pub mod pqrr;
 pub mod recovery;
+pub mod time;
 
 // Re-export common types
 pub use device_mgmt::{
     cleanup_revoked_headers, get_active_devices, get_revoked_devices, is_device_registered,
-    register_device, revoke_device, validate_header_completeness,
+    register_device, register_device_limited, revoke_and_cleanup, revoke_device,
+    validate_header_completeness,
+};
+pub use epoch_follow::{
+    dek_commitment, EpochAnnounce, EpochFollower, FollowRejection, FollowState,
+};
+pub use epoch_upgrade::{
+    EpochUpgradeCoordinator, PlannedCheck, ProgressCallback, SkippedDevice, UpgradeBlocker,
+    UpgradePhase, UpgradePlan, UpgradeProgress,
 };
-pub use epoch_upgrade::EpochUpgradeCoordinator;
 pub use error::{PqrrError, Result};
-pub use pqrr::{PqrrStateMachine, ProtocolState};
+pub use pqrr::{
+    OperationGuard, OperationKind, PqrrStateMachine, ProtocolState, RekeyProgressInfo,
+    RevocationTicket, TransitionEvent,
+};
 pub use recovery::{
-    check_veto_supremacy, RecoveryRequestId, RecoveryWindow, VetoMessage, VETO_WINDOW_MS,
+    check_veto_supremacy, finalize_promotion, promote_recovery, AttemptOutcome, PromotionRequest,
+    RecoveredVault, RecoveryAttempt, RecoveryAttemptTracker, RecoveryRateLimitPolicy,
+    RecoveryRequestId, RecoveryWindow, SignedVetoMessage, VetoKeyRegistry, VetoMessage,
+    VetoSigningKey, VetoVerifyingKey, VETO_WINDOW_MS,
 };
+pub use time::{MockTimeSource, SystemTimeSource, TimeSource};
@@ -11,6 +11,9 @@
 //! - **Invariant #3** - Causal entropy barrier (RECOVERY role cannot σ_rotate)
 //! - **Invariant #4** - Veto supremacy (48h veto window)
 //!
+//! Every state change is appended to a hash-chained audit log
+//! ([`TransitionEvent`]), so reordered or deleted entries are detectable.
+//!
 //! ## State Transitions
 //!
 //! ```text
@@ -30,10 +33,22 @@
 //!    └─────────┘      └───────────┘    └─────────┘
 //! ```
 
-use crate::models::device::{DeviceHeader, DeviceId};
+use crate::crypto::hash::{Blake3Hasher, HashOutput};
+use crate::models::device::{
+    headers_digest, DegradeReason, DeviceHeader, DeviceId, DeviceStatus, Operation, Role,
+};
 use crate::models::epoch::CryptoEpoch;
 use crate::protocol::error::{PqrrError, Result};
+use crate::protocol::recovery::{
+    is_clock_rollback, AttemptOutcome, RecoveryAttemptTracker, SignedVetoMessage, VetoKeyRegistry,
+};
+use crate::protocol::time::{default_time_source, TimeSource};
+use crate::storage::audit_log::{AuditEvent, AuditEventType, AuditSink};
+use crate::storage::invariant::InvariantValidator;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
 
 // ============================================================================
 // Protocol State Enumeration
@@ -43,7 +58,7 @@ use std::collections::{HashMap, HashSet};
 ///
 /// Represents all possible states in PQRR protocol state machine.
 /// Each state enforces specific invariants and allows specific transitions.
-#[derive(Debug, Clone, PartialEq, Eq, uniffi::Enum)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, uniffi::Enum)]
 pub enum ProtocolState {
     /// Idle state - no operations in progress
     ///
@@ -125,7 +140,7 @@ impl ProtocolState {
 /// Context for epoch upgrade (Rekeying state)
 ///
 /// Tracks progress of PQRR epoch upgrade across all devices.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RekeyingContext {
     /// Old epoch version (before upgrade)
     pub old_epoch: u32,
@@ -141,6 +156,12 @@ pub struct RekeyingContext {
 
     /// Shadow write temporary file path
     pub temp_vault_path: Option<String>,
+
+    /// New-epoch headers staged for commit (Invariant #2)
+    ///
+    /// Only installed into the state machine once every pending device has
+    /// a staged header, so a partial rewrap never reaches disk.
+    pub staged_headers: HashMap<DeviceId, DeviceHeader>,
 }
 
 impl RekeyingContext {
@@ -158,6 +179,7 @@ impl RekeyingContext {
             pending_devices: all_devices,
             completed_devices: HashSet::new(),
             temp_vault_path: None,
+            staged_headers: HashMap::new(),
         }
     }
 
@@ -173,6 +195,40 @@ impl RekeyingContext {
         self.pending_devices.retain(|id| id != device_id);
         self.completed_devices.insert(*device_id);
     }
+
+    /// Stage a rewrapped header and mark its device as completed
+    pub fn stage_header(&mut self, header: DeviceHeader) {
+        self.mark_device_completed(&header.device_id);
+        self.staged_headers.insert(header.device_id, header);
+    }
+
+    /// Fraction of devices with a completed header update, in `0.0..=1.0`
+    ///
+    /// A rekey without devices has nothing left to do and reports `1.0`.
+    pub fn progress(&self) -> f32 {
+        let completed = self.completed_devices.len();
+        let total = completed + self.pending_devices.len();
+        if total == 0 {
+            return 1.0;
+        }
+        completed as f32 / total as f32
+    }
+
+    /// Number of devices still pending a header update
+    pub fn remaining(&self) -> usize {
+        self.pending_devices.len()
+    }
+
+    /// Snapshot of this rekey's progress for the UI
+    pub fn progress_info(&self) -> RekeyProgressInfo {
+        RekeyProgressInfo {
+            old_epoch: self.old_epoch,
+            new_epoch: self.new_epoch,
+            completed: self.completed_devices.len() as u32,
+            remaining: self.remaining() as u32,
+            progress: self.progress(),
+        }
+    }
 }
 
 // ============================================================================
@@ -182,7 +238,7 @@ impl RekeyingContext {
 /// Context for recovery protocol (RecoveryInitiated state)
 ///
 /// Tracks active recovery attempt with 48h veto window.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RecoveryContext {
     /// Recovery request ID
     pub request_id: String,
@@ -194,10 +250,14 @@ pub struct RecoveryContext {
     pub end_time: u64,
 
     /// Initiator device role
-    pub initiator_role: String,
+    pub initiator_role: Role,
 
     /// Received veto signals
     pub vetoes: Vec<String>,
+
+    /// Latest wall-clock time observed while the window was checked
+    /// (Unix milliseconds)
+    pub last_observed_ms: u64,
 }
 
 impl RecoveryContext {
@@ -208,7 +268,7 @@ impl RecoveryContext {
     /// - `request_id`: Unique recovery request identifier
     /// - `start_time`: Window start time (Unix milliseconds)
     /// - `initiator_role`: Role of recovery initiator
-    pub fn new(request_id: String, start_time: u64, initiator_role: String) -> Self {
+    pub fn new(request_id: String, start_time: u64, initiator_role: Role) -> Self {
         // 48 hours in milliseconds
         let window_duration_ms = 48 * 60 * 60 * 1000;
         let end_time = start_time.saturating_add(window_duration_ms);
@@ -219,7 +279,22 @@ impl RecoveryContext {
             end_time,
             initiator_role,
             vetoes: Vec::new(),
+            last_observed_ms: start_time,
+        }
+    }
+
+    /// Record a wall-clock observation and check if the window is still open
+    ///
+    /// Fails closed: a reading earlier than the latest observation by more
+    /// than the drift tolerance means the clock was moved backward, so the
+    /// window is reported open and the reading is not recorded.
+    pub fn observe(&mut self, now_ms: u64) -> bool {
+        if is_clock_rollback(self.last_observed_ms, now_ms) {
+            return true;
         }
+
+        self.last_observed_ms = self.last_observed_ms.max(now_ms);
+        !self.is_window_expired(now_ms)
     }
 
     /// Check if current time is within veto window
@@ -248,6 +323,111 @@ impl RecoveryContext {
     }
 }
 
+// ============================================================================
+// Revocation Ticket
+// ============================================================================
+
+/// Record of a revocation awaiting its epoch rotation
+///
+/// A revoked device still holds the current DEK until the next epoch
+/// upgrade rewraps it for the remaining devices. While any ticket is
+/// pending, registration and recovery initiation are blocked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevocationTicket {
+    /// Device that was revoked
+    pub device_id: DeviceId,
+
+    /// Epoch version current at revocation
+    pub epoch: u64,
+}
+
+// ============================================================================
+// Transition Audit Log
+// ============================================================================
+
+/// Domain separator for transition log hashes
+const TRANSITION_LOG_CONTEXT: &[u8] = b"Aeternum_TransitionLog_v1";
+
+/// One protocol state change in the audit log
+///
+/// Each event stores the hash of the event before it (`prev_hash`, all zeros
+/// for the first event) and its own hash over all fields, forming a chain:
+/// modifying, reordering, or deleting an entry breaks verification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, uniffi::Record)]
+pub struct TransitionEvent {
+    /// State before the transition
+    pub from: ProtocolState,
+
+    /// State after the transition
+    pub to: ProtocolState,
+
+    /// When the transition happened (Unix milliseconds)
+    pub timestamp_ms: u64,
+
+    /// Why the transition happened
+    pub reason: String,
+
+    /// BLAKE3 hash of the previous event (32 bytes)
+    pub prev_hash: Vec<u8>,
+
+    /// BLAKE3 hash of this event, covering `prev_hash` (32 bytes)
+    pub hash: Vec<u8>,
+}
+
+impl TransitionEvent {
+    /// Create an event chained to `prev_hash`
+    fn new(
+        from: ProtocolState,
+        to: ProtocolState,
+        timestamp_ms: u64,
+        reason: String,
+        prev_hash: Vec<u8>,
+    ) -> Self {
+        let mut event = Self {
+            from,
+            to,
+            timestamp_ms,
+            reason,
+            prev_hash,
+            hash: Vec::new(),
+        };
+        event.hash = event.compute_hash().as_bytes().to_vec();
+        event
+    }
+
+    /// Hash of all fields except `hash` itself
+    fn compute_hash(&self) -> HashOutput {
+        let mut hasher = Blake3Hasher::new();
+        hasher.update(TRANSITION_LOG_CONTEXT);
+        hasher.update(&self.prev_hash);
+        for field in [self.from.as_str(), self.to.as_str(), self.reason.as_str()] {
+            hasher.update(&(field.len() as u32).to_be_bytes());
+            hasher.update(field.as_bytes());
+        }
+        hasher.update(&self.timestamp_ms.to_be_bytes());
+        hasher.finalize()
+    }
+
+    /// Verify the hash chain of a transition log
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if every event links to its predecessor and its hash matches
+    /// - `Err(PqrrError::TransitionLogTampered)` naming the first bad entry
+    pub fn verify_chain(log: &[TransitionEvent]) -> Result<()> {
+        let mut prev_hash = vec![0u8; 32];
+        for (index, event) in log.iter().enumerate() {
+            if event.prev_hash != prev_hash
+                || event.hash != event.compute_hash().as_bytes().as_slice()
+            {
+                return Err(PqrrError::transition_log_tampered(index as u32));
+            }
+            prev_hash.clone_from(&event.hash);
+        }
+        Ok(())
+    }
+}
+
 // ============================================================================
 // PQRR State Machine
 // ============================================================================
@@ -261,14 +441,101 @@ impl RecoveryContext {
 /// - Invariant #3: Causal entropy barrier
 /// - Invariant #4: Veto supremacy
 ///
+/// ## Thread Safety
+///
+/// All mutable state sits behind a single `RwLock`, so UniFFI-exported
+/// methods take `&self` and mutate through the lock. Every transition runs
+/// its invariant checks and its state change under one write lock. Rust
+/// callers holding `&mut self` reach the state through `RwLock::get_mut`
+/// without locking.
+///
+/// Multi-step operations (epoch upgrade, device registration and
+/// revocation, recovery initiation) span several lock acquisitions, so
+/// they additionally hold an [`OperationGuard`] from
+/// [`begin_operation`](Self::begin_operation) for their whole duration.
+#[derive(uniffi::Object)]
+pub struct PqrrStateMachine {
+    /// Mutable protocol state
+    core: RwLock<StateMachineCore>,
+
+    /// Operation currently holding an [`OperationGuard`], if any
+    active_operation: Arc<Mutex<Option<OperationKind>>>,
+}
+
+// ============================================================================
+// Operation Guard
+// ============================================================================
+
+/// Kind of multi-step protocol operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    /// Epoch upgrade (PQRR rekeying)
+    EpochUpgrade,
+    /// Device registration
+    RegisterDevice,
+    /// Device revocation
+    RevokeDevice,
+    /// Opening a recovery window
+    RecoveryInitiation,
+}
+
+impl OperationKind {
+    /// Get operation name as string
+    pub fn as_str(&self) -> &str {
+        match self {
+            OperationKind::EpochUpgrade => "EpochUpgrade",
+            OperationKind::RegisterDevice => "RegisterDevice",
+            OperationKind::RevokeDevice => "RevokeDevice",
+            OperationKind::RecoveryInitiation => "RecoveryInitiation",
+        }
+    }
+}
+
+/// Exclusive claim on a state machine for one multi-step operation
+///
+/// Obtained from [`PqrrStateMachine::begin_operation`]. At most one guard
+/// exists per state machine; it is released by [`complete`](Self::complete),
+/// [`abort`](Self::abort), or on drop. The guard does not borrow the state
+/// machine, so the holder can keep mutating it.
+#[must_use = "the operation is released as soon as the guard is dropped"]
+pub struct OperationGuard {
+    kind: OperationKind,
+    slot: Arc<Mutex<Option<OperationKind>>>,
+}
+
+impl OperationGuard {
+    /// Operation this guard was acquired for
+    pub fn kind(&self) -> OperationKind {
+        self.kind
+    }
+
+    /// Release the guard after the operation succeeded
+    pub fn complete(self) {}
+
+    /// Release the guard after the operation failed or was cancelled
+    pub fn abort(self) {
+        eprintln!("[PQRR] Operation {} aborted", self.kind.as_str());
+    }
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        *self.slot.lock().unwrap() = None;
+    }
+}
+
+/// Mutable state of [`PqrrStateMachine`]
+///
 /// ## Fields
 ///
 /// - `current_epoch`: Current cryptographic epoch (Invariant #1)
 /// - `state`: Current protocol state
 /// - `device_headers`: All device headers (Invariant #2)
 /// - `veto_signals`: Veto signals for recovery requests (Invariant #4)
-#[derive(uniffi::Object)]
-pub struct PqrrStateMachine {
+/// - `pending_revocations`: Revocations awaiting an epoch rotation
+/// - `transition_log`: Append-only, hash-chained record of state changes
+#[derive(Serialize, Deserialize)]
+struct StateMachineCore {
     /// Current epoch version (Invariant #1: must be monotonically increasing)
     current_epoch: CryptoEpoch,
 
@@ -286,40 +553,46 @@ pub struct PqrrStateMachine {
 
     /// Recovery context (when in RecoveryInitiated state)
     recovery_context: Option<RecoveryContext>,
+
+    /// Why the device was degraded (when in Degraded state)
+    degraded_reason: Option<String>,
+
+    /// Revocations not yet followed by an epoch upgrade
+    pending_revocations: Vec<RevocationTicket>,
+
+    /// Append-only audit log of state transitions
+    transition_log: Vec<TransitionEvent>,
+
+    /// Receiver of audit events (not persisted in snapshots)
+    #[serde(skip)]
+    audit_sink: Option<Arc<dyn AuditSink>>,
+
+    /// Clock for transition timestamps and recovery windows (not persisted)
+    #[serde(skip, default = "default_time_source")]
+    time_source: Arc<dyn TimeSource>,
+
+    /// Recovery initiation rate limits (persisted separately, not snapshotted)
+    #[serde(skip)]
+    recovery_attempts: RecoveryAttemptTracker,
 }
 
-/// Internal implementation (not exported to FFI)
-impl PqrrStateMachine {
-    /// Create a new PQRR state machine (internal constructor)
-    ///
-    /// # Arguments
-    ///
-    /// - `current_epoch`: Initial cryptographic epoch
-    /// - `device_headers`: Initial device headers (can be empty)
-    ///
-    /// # Returns
-    ///
-    /// A new state machine in Idle state
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// use aeternum_core::protocol::PqrrStateMachine;
-    /// use aeternum_core::models::epoch::CryptoEpoch;
-    /// use aeternum_core::protocol::pqrr::ProtocolState;
-    /// use std::collections::HashMap;
-    ///
-    /// let epoch = CryptoEpoch::initial();
-    /// let headers = HashMap::new();
-    /// let sm = PqrrStateMachine::create(epoch, headers);
-    ///
-    /// assert_eq!(sm.current_epoch().version, 1);
-    /// assert!(matches!(sm.state(), ProtocolState::Idle));
-    /// ```
-    pub fn create(
-        current_epoch: CryptoEpoch,
-        device_headers: HashMap<DeviceId, DeviceHeader>,
-    ) -> Self {
+/// Read guard over the device headers of a [`PqrrStateMachine`]
+///
+/// Dereferences to the header map. Holds the state machine's read lock,
+/// so drop it before calling exported methods that mutate state.
+pub struct DeviceHeadersRef<'a>(RwLockReadGuard<'a, StateMachineCore>);
+
+impl Deref for DeviceHeadersRef<'_> {
+    type Target = HashMap<DeviceId, DeviceHeader>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.device_headers
+    }
+}
+
+impl StateMachineCore {
+    /// Create the state in Idle
+    fn new(current_epoch: CryptoEpoch, device_headers: HashMap<DeviceId, DeviceHeader>) -> Self {
         Self {
             current_epoch,
             state: ProtocolState::Idle,
@@ -327,79 +600,123 @@ impl PqrrStateMachine {
             veto_signals: HashMap::new(),
             rekeying_context: None,
             recovery_context: None,
+            degraded_reason: None,
+            pending_revocations: Vec::new(),
+            transition_log: Vec::new(),
+            audit_sink: None,
+            time_source: default_time_source(),
+            recovery_attempts: RecoveryAttemptTracker::default(),
         }
     }
 
-    /// Get current epoch
-    ///
-    /// Returns reference to current cryptographic epoch.
-    pub fn current_epoch(&self) -> &CryptoEpoch {
-        &self.current_epoch
+    /// Forward an event to the audit sink, if any
+    fn audit(&self, event_type: AuditEventType, subject: String, detail: String) {
+        if let Some(sink) = &self.audit_sink {
+            sink.record(AuditEvent::new(
+                event_type,
+                self.current_epoch.version,
+                subject,
+                detail,
+            ));
+        }
     }
 
-    /// Get current state
-    ///
-    /// Returns reference to current protocol state.
-    pub fn state(&self) -> ProtocolState {
-        self.state.clone()
+    /// Run `op`, reporting an invariant violation to the audit sink
+    fn audited<T>(&mut self, op: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let result = op(self);
+        if let Err(e) = &result {
+            if let Some(invariant) = e.invariant_number() {
+                self.audit(
+                    AuditEventType::InvariantViolation,
+                    format!("Invariant #{}", invariant),
+                    e.to_string(),
+                );
+            }
+        }
+        result
     }
 
-    /// Get device headers
+    /// Append an event for a transition from `from` to the current state
     ///
-    /// Returns reference to all device headers.
-    pub fn device_headers(&self) -> &HashMap<DeviceId, DeviceHeader> {
-        &self.device_headers
-    }
+    /// The transition is also reported to the audit sink as `event_type`.
+    fn record_transition(
+        &mut self,
+        from: ProtocolState,
+        reason: String,
+        event_type: AuditEventType,
+    ) {
+        self.audit(
+            event_type,
+            format!("{} -> {}", from.as_str(), self.state.as_str()),
+            reason.clone(),
+        );
 
-    /// Get mutable reference to device headers
-    ///
-    /// Returns mutable reference to device headers.
-    pub fn device_headers_mut(&mut self) -> &mut HashMap<DeviceId, DeviceHeader> {
-        &mut self.device_headers
+        let prev_hash = self
+            .transition_log
+            .last()
+            .map_or_else(|| vec![0u8; 32], |event| event.hash.clone());
+        let event = TransitionEvent::new(
+            from,
+            self.state.clone(),
+            self.time_source.now_ms(),
+            reason,
+            prev_hash,
+        );
+        self.transition_log.push(event);
     }
 
-    /// Check if a device is active (internal method)
-    ///
-    /// # Arguments
-    ///
-    /// - `device_id`: Device identifier to check
-    ///
-    /// # Returns
+    /// Validate state loaded from a snapshot
     ///
-    /// `true` if device exists and is Active, `false` otherwise
-    pub fn is_device_active_internal(&self, device_id: &DeviceId) -> bool {
-        self.device_headers
-            .get(device_id)
-            .map(|h| h.status == crate::models::device::DeviceStatus::Active)
-            .unwrap_or(false)
-    }
+    /// Invariant #1: no header may be ahead of the current epoch, and an
+    /// in-flight upgrade must start at the current epoch and move forward.
+    /// Each context must be present exactly when its state is active, and
+    /// the transition log must verify.
+    fn validate_restored(&self) -> Result<()> {
+        TransitionEvent::verify_chain(&self.transition_log)?;
 
-    // ------------------------------------------------------------------------
-    // State Transitions (Internal)
-    // ------------------------------------------------------------------------
+        let current = self.current_epoch.version as u32;
+
+        for header in self.device_headers.values() {
+            if header.epoch.version > self.current_epoch.version {
+                return Err(PqrrError::epoch_regression(
+                    header.epoch.version as u32,
+                    current,
+                ));
+            }
+        }
+
+        for ticket in &self.pending_revocations {
+            if ticket.epoch > self.current_epoch.version {
+                return Err(PqrrError::epoch_regression(ticket.epoch as u32, current));
+            }
+        }
+
+        if let Some(ctx) = &self.rekeying_context {
+            if ctx.old_epoch != current || ctx.new_epoch <= current {
+                return Err(PqrrError::epoch_regression(current, ctx.new_epoch));
+            }
+        }
+
+        let (needs_rekeying, needs_recovery) = match self.state {
+            ProtocolState::Rekeying => (true, false),
+            ProtocolState::RecoveryInitiated => (false, true),
+            _ => (false, false),
+        };
+        if self.rekeying_context.is_some() != needs_rekeying
+            || self.recovery_context.is_some() != needs_recovery
+        {
+            return Err(PqrrError::invalid_transition(
+                "Snapshot".to_string(),
+                self.state.as_str().to_string(),
+                "context does not match state".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
 
     /// Transition to Rekeying state (internal)
-    ///
-    /// Initiates epoch upgrade (PQRR protocol).
-    /// Enforces Invariant #1: epoch monotonicity.
-    ///
-    /// # Arguments
-    ///
-    /// - `new_epoch`: New epoch version (must be > current)
-    ///
-    /// # Returns
-    ///
-    /// - `Ok(())` if transition successful
-    /// - `Err(PqrrError::EpochRegression)` if Invariant #1 violated
-    /// - `Err(PqrrError::InvalidStateTransition)` if not in Idle state
-    ///
-    /// # Invariant Enforcement
-    ///
-    /// **Invariant #1**: Epoch must be strictly increasing
-    /// ```text
-    /// new_epoch.version > current_epoch.version
-    /// ```
-    pub fn transition_to_rekeying_internal(&mut self, new_epoch: CryptoEpoch) -> Result<()> {
+    fn transition_to_rekeying_internal(&mut self, new_epoch: CryptoEpoch) -> Result<()> {
         // Must be in Idle state
         if !self.state.can_upgrade_epoch() {
             return Err(PqrrError::invalid_transition(
@@ -436,32 +753,25 @@ impl PqrrStateMachine {
             all_devices,
         );
 
+        let reason = format!(
+            "epoch upgrade {} -> {}",
+            context.old_epoch, context.new_epoch
+        );
+
         // Update state and context
-        self.state = ProtocolState::Rekeying;
+        let from = std::mem::replace(&mut self.state, ProtocolState::Rekeying);
         self.rekeying_context = Some(context);
+        self.record_transition(from, reason, AuditEventType::StateTransition);
 
         Ok(())
     }
 
     /// Transition to RecoveryInitiated state (internal)
-    ///
-    /// Initiates recovery protocol with 48h veto window.
-    ///
-    /// # Arguments
-    ///
-    /// - `request_id`: Unique recovery request identifier
-    /// - `start_time`: Window start time (Unix milliseconds)
-    /// - `initiator_role`: Role of recovery initiator
-    ///
-    /// # Returns
-    ///
-    /// - `Ok(())` if transition successful
-    /// - `Err(PqrrError::InvalidStateTransition)` if not in Idle state
-    pub fn transition_to_recovery_internal(
+    fn transition_to_recovery_internal(
         &mut self,
         request_id: String,
         start_time: u64,
-        initiator_role: String,
+        initiator_role: Role,
     ) -> Result<()> {
         // Must be in Idle state
         if !matches!(self.state, ProtocolState::Idle) {
@@ -472,45 +782,187 @@ impl PqrrStateMachine {
             ));
         }
 
+        // The revoked device still holds the DEK until the epoch rotates
+        if !self.pending_revocations.is_empty() {
+            return Err(PqrrError::pending_revocation(
+                self.pending_revocations.len() as u32,
+            ));
+        }
+
+        // Every initiation prompts every device for a veto
+        self.recovery_attempts.check(start_time)?;
+        self.recovery_attempts
+            .record_initiation(&request_id, start_time);
+
+        let reason = format!(
+            "recovery {} initiated by {}",
+            request_id,
+            initiator_role.as_str()
+        );
+
         // Create recovery context
         let context = RecoveryContext::new(request_id, start_time, initiator_role);
 
         // Update state and context
-        self.state = ProtocolState::RecoveryInitiated;
+        let from = std::mem::replace(&mut self.state, ProtocolState::RecoveryInitiated);
         self.recovery_context = Some(context);
+        self.record_transition(from, reason, AuditEventType::RecoveryStarted);
 
         Ok(())
     }
 
-    /// Transition to Degraded state (internal)
-    ///
-    /// Transitions to degraded mode when integrity check fails.
-    pub fn transition_to_degraded_internal(&mut self) -> Result<()> {
-        self.state = ProtocolState::Degraded;
-        self.rekeying_context = None;
-        self.recovery_context = None;
-        Ok(())
-    }
+    /// Complete recovery and return to Idle (internal)
+    fn complete_recovery_internal(
+        &mut self,
+        completer_role: Role,
+        current_time: u64,
+    ) -> Result<()> {
+        let context = match (&self.state, self.recovery_context.as_mut()) {
+            (ProtocolState::RecoveryInitiated, Some(context)) => context,
+            _ => {
+                return Err(PqrrError::invalid_transition(
+                    self.state.as_str().to_string(),
+                    "Idle".to_string(),
+                    "no recovery in progress".to_string(),
+                ))
+            }
+        };
+
+        // Invariant #3: completion rotates keys
+        let operation = Operation::SigmaRotate;
+        if !completer_role.can_permit_operation(operation) {
+            return Err(PqrrError::causal_barrier(
+                completer_role.as_str().to_string(),
+                operation.as_str().to_string(),
+            ));
+        }
 
-    /// Transition to Revoked state (internal)
-    ///
-    /// Transitions to revoked state (terminal).
-    pub fn transition_to_revoked_internal(&mut self) -> Result<()> {
-        self.state = ProtocolState::Revoked;
-        self.rekeying_context = None;
+        // Invariant #4: vetoes terminate recovery
+        if context.is_vetoed() {
+            return Err(PqrrError::vetoed(
+                context.request_id.clone(),
+                context.veto_count() as u32,
+            ));
+        }
+
+        // Fail-closed: a wall clock moved backward keeps the window open
+        if context.observe(current_time) {
+            return Err(PqrrError::invalid_transition(
+                self.state.as_str().to_string(),
+                "Idle".to_string(),
+                "veto window still open".to_string(),
+            ));
+        }
+
+        let reason = format!("recovery {} completed", context.request_id);
+        self.recovery_attempts.record_outcome(
+            &context.request_id,
+            AttemptOutcome::Completed,
+            current_time,
+        );
+
+        let from = std::mem::replace(&mut self.state, ProtocolState::Idle);
         self.recovery_context = None;
+        self.record_transition(from, reason, AuditEventType::RecoveryCompleted);
         Ok(())
     }
 
-    /// Return to Idle state (internal)
-    ///
-    /// Completes current operation and returns to Idle.
-    ///
-    /// # Returns
-    ///
-    /// - `Ok(())` if transition successful
-    /// - `Err(PqrrError::InvalidStateTransition)` if already terminal
-    pub fn return_to_idle_internal(&mut self) -> Result<()> {
+    /// Record a verified veto against the recovery in progress (internal)
+    fn record_veto_internal(&mut self, request_id: &str, device_id: String) -> Result<()> {
+        // Each device is counted once; a repeated veto after the recovery
+        // ended is a no-op
+        if self
+            .veto_signals
+            .get(request_id)
+            .is_some_and(|signals| signals.contains(&device_id))
+        {
+            return Ok(());
+        }
+
+        let in_progress = matches!(self.state, ProtocolState::RecoveryInitiated)
+            && self
+                .recovery_context
+                .as_ref()
+                .is_some_and(|context| context.request_id == request_id);
+        if !in_progress {
+            return Err(PqrrError::invalid_transition(
+                self.state.as_str().to_string(),
+                "Idle".to_string(),
+                format!("recovery {} is not in progress", request_id),
+            ));
+        }
+
+        self.veto_signals
+            .entry(request_id.to_string())
+            .or_default()
+            .push(device_id.clone());
+        self.audit(
+            AuditEventType::VetoReceived,
+            device_id,
+            format!("recovery {}", request_id),
+        );
+
+        // Invariant #4: a single veto terminates the recovery
+        let now = self.time_source.now_ms();
+        self.recovery_attempts
+            .record_outcome(request_id, AttemptOutcome::Vetoed, now);
+        let from = std::mem::replace(&mut self.state, ProtocolState::Idle);
+        self.recovery_context = None;
+        self.record_transition(
+            from,
+            format!("recovery {} vetoed", request_id),
+            AuditEventType::StateTransition,
+        );
+        Ok(())
+    }
+
+    /// Drop the recovery in progress, if any, recording how it ended
+    ///
+    /// Every transition that abandons a recovery goes through here, so the
+    /// rate limiter sees each attempt's outcome.
+    fn end_recovery(&mut self) {
+        if let Some(context) = self.recovery_context.take() {
+            let outcome = if context.is_vetoed() {
+                AttemptOutcome::Vetoed
+            } else {
+                AttemptOutcome::Abandoned
+            };
+            let now = self.time_source.now_ms();
+            self.recovery_attempts
+                .record_outcome(&context.request_id, outcome, now);
+        }
+    }
+
+    /// Transition to Degraded state (internal)
+    fn transition_to_degraded_internal(&mut self) -> Result<()> {
+        self.transition_to_degraded_with_reason("integrity verification failed".to_string())
+    }
+
+    /// Transition to Degraded state with an explicit reason (internal)
+    fn transition_to_degraded_with_reason(&mut self, reason: String) -> Result<()> {
+        let from = std::mem::replace(&mut self.state, ProtocolState::Degraded);
+        self.rekeying_context = None;
+        self.end_recovery();
+        self.degraded_reason = Some(reason.clone());
+        self.record_transition(from, reason, AuditEventType::StateTransition);
+        Ok(())
+    }
+
+    /// Transition to Revoked state (internal)
+    fn transition_to_revoked_internal(&mut self) -> Result<()> {
+        let from = std::mem::replace(&mut self.state, ProtocolState::Revoked);
+        self.rekeying_context = None;
+        self.end_recovery();
+        self.record_transition(
+            from,
+            "device revoked".to_string(),
+            AuditEventType::DeviceRevoked,
+        );
+        Ok(())
+    }
+
+    /// Return to Idle state (internal)
+    fn return_to_idle_internal(&mut self) -> Result<()> {
         match &self.state {
             ProtocolState::Revoked => Err(PqrrError::invalid_transition(
                 "Revoked".to_string(),
@@ -518,14 +970,664 @@ impl PqrrStateMachine {
                 "cannot return from terminal state".to_string(),
             )),
             _ => {
-                self.state = ProtocolState::Idle;
+                self.end_recovery();
+
+                let from = std::mem::replace(&mut self.state, ProtocolState::Idle);
                 self.rekeying_context = None;
-                self.recovery_context = None;
+                self.degraded_reason = None;
+                self.record_transition(
+                    from,
+                    "returned to idle".to_string(),
+                    AuditEventType::StateTransition,
+                );
                 Ok(())
             }
         }
     }
 
+    /// Apply epoch upgrade (internal)
+    fn apply_epoch_upgrade_internal(&mut self, new_epoch: CryptoEpoch) -> Result<()> {
+        // Invariant #1: Epoch monotonicity
+        if new_epoch.version <= self.current_epoch.version {
+            // MELTDOWN TRIGGERED: Invariant #1 violation
+            // This should never happen in production
+            return Err(PqrrError::epoch_regression(
+                self.current_epoch.version as u32,
+                new_epoch.version as u32,
+            ));
+        }
+
+        // Update epoch; the rotation completes every pending revocation
+        let old_version = self.current_epoch.version;
+        self.current_epoch = new_epoch;
+        self.pending_revocations.clear();
+        self.audit(
+            AuditEventType::EpochUpgrade,
+            format!("{} -> {}", old_version, self.current_epoch.version),
+            String::new(),
+        );
+        Ok(())
+    }
+}
+
+/// Internal implementation (not exported to FFI)
+impl PqrrStateMachine {
+    /// Create a new PQRR state machine (internal constructor)
+    ///
+    /// # Arguments
+    ///
+    /// - `current_epoch`: Initial cryptographic epoch
+    /// - `device_headers`: Initial device headers (can be empty)
+    ///
+    /// # Returns
+    ///
+    /// A new state machine in Idle state
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use aeternum_core::protocol::PqrrStateMachine;
+    /// use aeternum_core::models::epoch::CryptoEpoch;
+    /// use aeternum_core::protocol::pqrr::ProtocolState;
+    /// use std::collections::HashMap;
+    ///
+    /// let epoch = CryptoEpoch::initial();
+    /// let headers = HashMap::new();
+    /// let sm = PqrrStateMachine::create(epoch, headers);
+    ///
+    /// assert_eq!(sm.current_epoch().version, 1);
+    /// assert!(matches!(sm.state(), ProtocolState::Idle));
+    /// ```
+    pub fn create(
+        current_epoch: CryptoEpoch,
+        device_headers: HashMap<DeviceId, DeviceHeader>,
+    ) -> Self {
+        Self::create_with_time_source(current_epoch, device_headers, default_time_source())
+    }
+
+    /// Create a new state machine reading time from `time_source`
+    ///
+    /// [`create`](Self::create) uses the system clock; tests pass a
+    /// [`MockTimeSource`](crate::protocol::time::MockTimeSource) to drive
+    /// the veto window without sleeping.
+    pub fn create_with_time_source(
+        current_epoch: CryptoEpoch,
+        device_headers: HashMap<DeviceId, DeviceHeader>,
+        time_source: Arc<dyn TimeSource>,
+    ) -> Self {
+        let mut core = StateMachineCore::new(current_epoch, device_headers);
+        core.time_source = time_source;
+
+        Self {
+            core: RwLock::new(core),
+            active_operation: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Serialize the full state machine for persistence
+    ///
+    /// Captures the epoch, protocol state, device headers, veto signals and
+    /// any in-flight rekeying or recovery context. Restore with
+    /// [`restore`](Self::restore).
+    pub fn snapshot(&self) -> Vec<u8> {
+        bincode::serialize(&*self.core.read().unwrap())
+            .expect("state machine serialization cannot fail")
+    }
+
+    /// Rebuild a state machine from a [`snapshot`](Self::snapshot)
+    ///
+    /// The restored state is validated before use, so a tampered or
+    /// corrupted snapshot cannot smuggle in a state the transitions would
+    /// never have produced.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(PqrrStateMachine)` if the snapshot is well-formed and consistent
+    /// - `Err(PqrrError::StorageError)` if the bytes cannot be decoded
+    /// - `Err(PqrrError::EpochRegression)` if Invariant #1 is violated
+    /// - `Err(PqrrError::InvalidStateTransition)` if the state and its
+    ///   context disagree
+    /// - `Err(PqrrError::HeaderIncomplete)` if Invariant #2 is violated
+    /// - `Err(PqrrError::TransitionLogTampered)` if the audit log was altered
+    pub fn restore(bytes: &[u8]) -> Result<Self> {
+        let core: StateMachineCore = bincode::deserialize(bytes)
+            .map_err(|e| PqrrError::storage_error(format!("Corrupt state snapshot: {}", e)))?;
+
+        core.validate_restored()?;
+
+        let sm = Self {
+            core: RwLock::new(core),
+            active_operation: Arc::new(Mutex::new(None)),
+        };
+        sm.check_header_completeness_internal()?;
+
+        Ok(sm)
+    }
+
+    /// Lock-free access to the state through exclusive borrow
+    fn core_mut(&mut self) -> &mut StateMachineCore {
+        self.core.get_mut().unwrap()
+    }
+
+    /// Claim the state machine for a multi-step operation
+    ///
+    /// Atomically checks that the state is Idle and that no other operation
+    /// is in progress, then marks `kind` as in progress until the returned
+    /// guard is released. Never blocks: a concurrent caller fails at once.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(OperationGuard)` if the operation may proceed
+    /// - `Err(PqrrError::OperationInProgress)` if another guard is held
+    /// - `Err(PqrrError::InvalidStateTransition)` if not in Idle state
+    pub fn begin_operation(&self, kind: OperationKind) -> Result<OperationGuard> {
+        let core = self.core.read().unwrap();
+        let mut active = self.active_operation.lock().unwrap();
+
+        if let Some(current) = *active {
+            return Err(PqrrError::operation_in_progress(
+                current.as_str().to_string(),
+            ));
+        }
+        if core.state != ProtocolState::Idle {
+            return Err(PqrrError::invalid_transition(
+                core.state.as_str().to_string(),
+                kind.as_str().to_string(),
+                "operations can only begin in Idle state".to_string(),
+            ));
+        }
+
+        *active = Some(kind);
+        Ok(OperationGuard {
+            kind,
+            slot: Arc::clone(&self.active_operation),
+        })
+    }
+
+    /// Operation currently in progress, if any
+    pub fn active_operation(&self) -> Option<OperationKind> {
+        *self.active_operation.lock().unwrap()
+    }
+
+    /// Report transitions, epoch upgrades and invariant violations to `sink`
+    ///
+    /// The sink is not part of a [`snapshot`](Self::snapshot); set it again
+    /// after [`restore`](Self::restore).
+    pub fn set_audit_sink(&self, sink: Arc<dyn AuditSink>) {
+        self.core.write().unwrap().audit_sink = Some(sink);
+    }
+
+    /// Read time from `time_source` instead of the system clock
+    ///
+    /// Like the audit sink, the time source is not part of a
+    /// [`snapshot`](Self::snapshot); a restored state machine uses the
+    /// system clock until this is called.
+    pub fn set_time_source(&self, time_source: Arc<dyn TimeSource>) {
+        self.core.write().unwrap().time_source = time_source;
+    }
+
+    /// Time source used for transitions and recovery windows
+    pub fn time_source(&self) -> Arc<dyn TimeSource> {
+        Arc::clone(&self.core.read().unwrap().time_source)
+    }
+
+    /// Enforce the recovery rate limits recorded in `tracker`
+    ///
+    /// The tracker is not part of a [`snapshot`](Self::snapshot); load it
+    /// with [`RecoveryAttemptTracker::load`] and set it after
+    /// [`restore`](Self::restore), or a restart resets the limits.
+    pub fn set_recovery_attempts(&self, tracker: RecoveryAttemptTracker) {
+        self.core.write().unwrap().recovery_attempts = tracker;
+    }
+
+    /// Recovery attempts recorded so far, for persisting with
+    /// [`RecoveryAttemptTracker::save`]
+    pub fn recovery_attempts(&self) -> RecoveryAttemptTracker {
+        self.core.read().unwrap().recovery_attempts.clone()
+    }
+
+    /// Record the current wall-clock time in the recovery context
+    ///
+    /// Call periodically (and before taking a snapshot) while a recovery is
+    /// pending, so that a later backward clock change is detected even
+    /// across restarts. Does nothing outside `RecoveryInitiated`.
+    pub fn observe_time(&self) {
+        let mut core = self.core.write().unwrap();
+        let now = core.time_source.now_ms();
+        if let Some(context) = core.recovery_context.as_mut() {
+            context.observe(now);
+        }
+    }
+
+    /// Get current epoch
+    ///
+    /// Returns a copy of the current cryptographic epoch.
+    pub fn current_epoch(&self) -> CryptoEpoch {
+        self.core.read().unwrap().current_epoch
+    }
+
+    /// Get current state
+    ///
+    /// Returns a copy of the current protocol state.
+    pub fn state(&self) -> ProtocolState {
+        self.core.read().unwrap().state.clone()
+    }
+
+    /// Get device headers
+    ///
+    /// Returns a read guard over all device headers.
+    pub fn device_headers(&self) -> DeviceHeadersRef<'_> {
+        DeviceHeadersRef(self.core.read().unwrap())
+    }
+
+    /// Get mutable reference to device headers
+    ///
+    /// Returns mutable reference to device headers.
+    pub fn device_headers_mut(&mut self) -> &mut HashMap<DeviceId, DeviceHeader> {
+        &mut self.core_mut().device_headers
+    }
+
+    /// Compute the canonical digest of all device headers
+    ///
+    /// Identical header sets produce identical digests on every device,
+    /// so the digest can be compared during sync and integrity audits.
+    pub fn headers_digest(&self) -> HashOutput {
+        headers_digest(&self.core.read().unwrap().device_headers)
+    }
+
+    /// Get the rekeying context (when in Rekeying state)
+    pub fn rekeying_context(&self) -> Option<RekeyingContext> {
+        self.core.read().unwrap().rekeying_context.clone()
+    }
+
+    /// Get mutable reference to the rekeying context
+    pub fn rekeying_context_mut(&mut self) -> Option<&mut RekeyingContext> {
+        self.core_mut().rekeying_context.as_mut()
+    }
+
+    /// Get the recovery context (when in RecoveryInitiated state)
+    pub fn recovery_context(&self) -> Option<RecoveryContext> {
+        self.core.read().unwrap().recovery_context.clone()
+    }
+
+    /// Get mutable reference to the recovery context
+    pub fn recovery_context_mut(&mut self) -> Option<&mut RecoveryContext> {
+        self.core_mut().recovery_context.as_mut()
+    }
+
+    /// Get the reason the device was degraded
+    ///
+    /// Returns `None` unless the state machine is in Degraded state.
+    pub fn degraded_reason(&self) -> Option<String> {
+        let core = self.core.read().unwrap();
+        match core.state {
+            ProtocolState::Degraded => core.degraded_reason.clone(),
+            _ => None,
+        }
+    }
+
+    /// Get the revocations awaiting an epoch rotation
+    pub fn pending_revocations(&self) -> Vec<RevocationTicket> {
+        self.core.read().unwrap().pending_revocations.clone()
+    }
+
+    /// Get mutable reference to the pending revocations
+    pub fn pending_revocations_mut(&mut self) -> &mut Vec<RevocationTicket> {
+        &mut self.core_mut().pending_revocations
+    }
+
+    /// Check if a device is active (internal method)
+    ///
+    /// # Arguments
+    ///
+    /// - `device_id`: Device identifier to check
+    ///
+    /// # Returns
+    ///
+    /// `true` if device exists and is Active, `false` otherwise
+    pub fn is_device_active_internal(&self, device_id: &DeviceId) -> bool {
+        self.device_headers()
+            .get(device_id)
+            .map(|h| h.status == crate::models::device::DeviceStatus::Active)
+            .unwrap_or(false)
+    }
+
+    /// Put a device into Degraded status
+    ///
+    /// The device keeps its header and its veto right but no longer counts
+    /// as active, so header completeness (Invariant #2) stops requiring a
+    /// header for it. The degrade time comes from the state machine's
+    /// time source.
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::HeaderIncomplete` if the device has no header
+    /// - `PqrrError::InvalidStateTransition` if the device is Revoked
+    pub fn degrade_device(&mut self, device_id: &DeviceId, reason: DegradeReason) -> Result<()> {
+        let now_ms = self.time_source().now_ms();
+        let header = self.registered_header_mut(device_id, "Degraded")?;
+        header.degrade(reason, now_ms).map_err(|e| {
+            PqrrError::invalid_transition(
+                format!("{:?}", header.status),
+                "Degraded".to_string(),
+                e.to_string(),
+            )
+        })
+    }
+
+    /// Return a Degraded device to Active status
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::HeaderIncomplete` if the device has no header
+    /// - `PqrrError::InvalidStateTransition` if the device is not Degraded;
+    ///   a Revoked device must re-enroll instead
+    pub fn restore_device(&mut self, device_id: &DeviceId) -> Result<()> {
+        let header = self.registered_header_mut(device_id, "Active")?;
+        header.restore().map_err(|e| {
+            PqrrError::invalid_transition(
+                format!("{:?}", header.status),
+                "Active".to_string(),
+                e.to_string(),
+            )
+        })
+    }
+
+    /// Header of a registered, non-revoked device
+    fn registered_header_mut(
+        &mut self,
+        device_id: &DeviceId,
+        target: &str,
+    ) -> Result<&mut DeviceHeader> {
+        let header = self
+            .core_mut()
+            .device_headers
+            .get_mut(device_id)
+            .ok_or_else(|| {
+                PqrrError::header_incomplete(
+                    device_id.to_string(),
+                    "device is not registered".to_string(),
+                )
+            })?;
+        if header.status == DeviceStatus::Revoked {
+            return Err(PqrrError::invalid_transition(
+                "Revoked".to_string(),
+                target.to_string(),
+                "revoked devices must re-enroll".to_string(),
+            ));
+        }
+        Ok(header)
+    }
+
+    /// Check header completeness (internal)
+    ///
+    /// Enforces Invariant #2: every active device holds exactly one header
+    /// in the current epoch, and every header is filed under its own device.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the header set is complete
+    /// - `Err(PqrrError::HeaderIncomplete)` if Invariant #2 violated
+    pub fn check_header_completeness_internal(&self) -> Result<()> {
+        let core = self.core.read().unwrap();
+        let headers: Vec<DeviceHeader> = core.device_headers.values().cloned().collect();
+
+        InvariantValidator::audited(
+            InvariantValidator::check_all_headers_complete(&headers, &core.current_epoch),
+            core.audit_sink.as_deref(),
+            core.current_epoch.version,
+        )
+        .map_err(|e| {
+            PqrrError::header_incomplete("active device set".to_string(), e.to_string())
+        })?;
+
+        // A header filed under another device leaves that device without one
+        for (device_id, header) in &core.device_headers {
+            if header.device_id != *device_id {
+                return Err(PqrrError::header_incomplete(
+                    device_id.to_string(),
+                    format!("header belongs to device {}", header.device_id),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that the staged headers complete `new_epoch` (Invariant #2)
+    ///
+    /// Overlays the rekeying context's staged headers on the current header
+    /// set and requires every active device to hold exactly one header in
+    /// `new_epoch`. Revoked devices need none. Run before the AUP commit so
+    /// a vault that would lock an active device out never reaches disk.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the post-commit header set is complete
+    /// - `Err(PqrrError::InvalidStateTransition)` if no rekeying is in progress
+    /// - `Err(PqrrError::HeaderIncomplete)` if Invariant #2 would be violated
+    pub fn check_staged_header_completeness(&self, new_epoch: &CryptoEpoch) -> Result<()> {
+        let core = self.core.read().unwrap();
+        let context = core.rekeying_context.as_ref().ok_or_else(|| {
+            PqrrError::invalid_transition(
+                core.state.as_str().to_string(),
+                "Idle".to_string(),
+                "no rekeying in progress".to_string(),
+            )
+        })?;
+
+        let mut committed = core.device_headers.clone();
+        committed.extend(
+            context
+                .staged_headers
+                .iter()
+                .map(|(id, header)| (*id, header.clone())),
+        );
+        let headers: Vec<DeviceHeader> = committed.into_values().collect();
+
+        InvariantValidator::audited(
+            InvariantValidator::check_all_headers_complete(&headers, new_epoch),
+            core.audit_sink.as_deref(),
+            core.current_epoch.version,
+        )
+        .map_err(|e| PqrrError::header_incomplete("staged header set".to_string(), e.to_string()))
+    }
+
+    // ------------------------------------------------------------------------
+    // State Transitions (Internal)
+    // ------------------------------------------------------------------------
+
+    /// Transition to Rekeying state (internal)
+    ///
+    /// Initiates epoch upgrade (PQRR protocol).
+    /// Enforces Invariant #1: epoch monotonicity.
+    ///
+    /// # Arguments
+    ///
+    /// - `new_epoch`: New epoch version (must be > current)
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if transition successful
+    /// - `Err(PqrrError::EpochRegression)` if Invariant #1 violated
+    /// - `Err(PqrrError::InvalidStateTransition)` if not in Idle state
+    ///
+    /// # Invariant Enforcement
+    ///
+    /// **Invariant #1**: Epoch must be strictly increasing
+    /// ```text
+    /// new_epoch.version > current_epoch.version
+    /// ```
+    pub fn transition_to_rekeying_internal(&mut self, new_epoch: CryptoEpoch) -> Result<()> {
+        self.core_mut()
+            .audited(|core| core.transition_to_rekeying_internal(new_epoch))
+    }
+
+    /// Transition to RecoveryInitiated state (internal)
+    ///
+    /// Initiates recovery protocol with 48h veto window.
+    /// Blocked while a revocation awaits its epoch rotation.
+    ///
+    /// Any role may initiate: opening the veto window performs no management
+    /// operation, and RECOVERY devices are the expected initiators. The key
+    /// rotation that ends recovery is gated separately by
+    /// [`complete_recovery_internal`](Self::complete_recovery_internal).
+    ///
+    /// # Arguments
+    ///
+    /// - `request_id`: Unique recovery request identifier
+    /// - `start_time`: Window start time (Unix milliseconds)
+    /// - `initiator_role`: Role of recovery initiator
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if transition successful
+    /// - `Err(PqrrError::InvalidStateTransition)` if not in Idle state
+    /// - `Err(PqrrError::PendingRevocation)` if a revocation awaits rotation
+    /// - `Err(PqrrError::RecoveryRateLimited)` if the
+    ///   [recovery rate limits](Self::set_recovery_attempts) are exceeded
+    pub fn transition_to_recovery_internal(
+        &mut self,
+        request_id: String,
+        start_time: u64,
+        initiator_role: Role,
+    ) -> Result<()> {
+        let guard = self.begin_operation(OperationKind::RecoveryInitiation)?;
+        self.core_mut().audited(|core| {
+            core.transition_to_recovery_internal(request_id, start_time, initiator_role)
+        })?;
+        guard.complete();
+        Ok(())
+    }
+
+    /// Complete recovery and return to Idle (internal)
+    ///
+    /// Completing recovery rotates keys (σ_rotate), so Invariant #3 requires
+    /// an AUTHORIZED device even when recovery was initiated by a RECOVERY
+    /// device. Invariant #4 is checked as well: the recovery must not be
+    /// vetoed and the 48h window must have elapsed.
+    ///
+    /// # Arguments
+    ///
+    /// - `completer_role`: Role of the device completing recovery
+    /// - `current_time`: Current time (Unix milliseconds)
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if recovery completed
+    /// - `Err(PqrrError::InvalidStateTransition)` if not in RecoveryInitiated
+    ///   state or the veto window is still open
+    /// - `Err(PqrrError::CausalBarrier)` if `completer_role` is not AUTHORIZED
+    /// - `Err(PqrrError::Vetoed)` if any veto was received
+    pub fn complete_recovery_internal(
+        &mut self,
+        completer_role: Role,
+        current_time: u64,
+    ) -> Result<()> {
+        self.core_mut()
+            .audited(|core| core.complete_recovery_internal(completer_role, current_time))
+    }
+
+    /// Complete recovery at the time read from the state machine's time source
+    ///
+    /// Same checks as
+    /// [`complete_recovery_internal`](Self::complete_recovery_internal).
+    pub fn complete_recovery(&mut self, completer_role: Role) -> Result<()> {
+        let now = self.core_mut().time_source.now_ms();
+        self.complete_recovery_internal(completer_role, now)
+    }
+
+    /// Apply a veto received from another device
+    ///
+    /// Verifies `signed` against the sender's registered key and current
+    /// header, then records it against the recovery in progress and
+    /// reports it to the audit sink. A repeated veto from the same device
+    /// is accepted but not counted twice.
+    ///
+    /// # Arguments
+    ///
+    /// - `signed`: Received veto
+    /// - `registry`: Registered veto keys of the user's devices
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::UnauthorizedVeto` if the sender has no header, is
+    ///   Revoked or has no registered veto key
+    /// - `PqrrError::InvalidVetoSignature` if the signature does not verify
+    /// - `PqrrError::InvalidStateTransition` if the veto targets a recovery
+    ///   that is not in progress
+    ///
+    /// The first veto ends the recovery and returns the state machine to
+    /// Idle; a repeated veto from the same device is accepted as a no-op.
+    pub fn receive_veto(
+        &mut self,
+        signed: &SignedVetoMessage,
+        registry: &VetoKeyRegistry,
+    ) -> Result<()> {
+        let device_id = signed.veto.device_id;
+        let unauthorized = || PqrrError::unauthorized_veto(format!("{:?}", device_id));
+        let verifying_key = registry.get(&device_id).ok_or_else(unauthorized)?;
+        {
+            let headers = self.device_headers();
+            let header = headers.get(&device_id).ok_or_else(unauthorized)?;
+            signed.verify(verifying_key, header)?;
+        }
+
+        self.core_mut()
+            .audited(|core| core.record_veto_internal(&signed.request_id, device_id.to_string()))
+    }
+
+    /// Record a veto against the recovery in progress (internal)
+    ///
+    /// The caller has already authenticated the veto. Ends the recovery
+    /// with a `Vetoed` outcome and returns to Idle.
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::InvalidStateTransition` if `request_id` is not the
+    ///   recovery in progress and `device_id` has not vetoed it before
+    pub fn record_veto_internal(&mut self, request_id: &str, device_id: String) -> Result<()> {
+        self.core_mut()
+            .audited(|core| core.record_veto_internal(request_id, device_id))
+    }
+
+    /// Transition to Degraded state (internal)
+    ///
+    /// Transitions to degraded mode when integrity check fails.
+    pub fn transition_to_degraded_internal(&mut self) -> Result<()> {
+        self.core_mut()
+            .audited(|core| core.transition_to_degraded_internal())
+    }
+
+    /// Transition to Degraded state with an explicit reason (internal)
+    ///
+    /// The reason is reported to callers rejected by read-only mode.
+    pub fn transition_to_degraded_with_reason(&mut self, reason: String) -> Result<()> {
+        self.core_mut()
+            .audited(|core| core.transition_to_degraded_with_reason(reason))
+    }
+
+    /// Transition to Revoked state (internal)
+    ///
+    /// Transitions to revoked state (terminal).
+    pub fn transition_to_revoked_internal(&mut self) -> Result<()> {
+        self.core_mut()
+            .audited(|core| core.transition_to_revoked_internal())
+    }
+
+    /// Return to Idle state (internal)
+    ///
+    /// Completes current operation and returns to Idle.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if transition successful
+    /// - `Err(PqrrError::InvalidStateTransition)` if already terminal
+    pub fn return_to_idle_internal(&mut self) -> Result<()> {
+        self.core_mut()
+            .audited(|core| core.return_to_idle_internal())
+    }
+
     // ------------------------------------------------------------------------
     // Epoch Management (Invariant #1 Enforcement)
     // ------------------------------------------------------------------------
@@ -534,6 +1636,8 @@ impl PqrrStateMachine {
     ///
     /// Updates current epoch after successful PQRR.
     /// Enforces Invariant #1: epoch monotonicity.
+    /// Clears the pending revocations, since the revoked devices received
+    /// no header in the new epoch.
     ///
     /// # Arguments
     ///
@@ -556,19 +1660,8 @@ impl PqrrStateMachine {
     /// 2. State isolation (prevent corruption)
     /// 3. User alert (notify of invariant violation)
     pub fn apply_epoch_upgrade_internal(&mut self, new_epoch: CryptoEpoch) -> Result<()> {
-        // Invariant #1: Epoch monotonicity
-        if new_epoch.version <= self.current_epoch.version {
-            // MELTDOWN TRIGGERED: Invariant #1 violation
-            // This should never happen in production
-            return Err(PqrrError::epoch_regression(
-                self.current_epoch.version as u32,
-                new_epoch.version as u32,
-            ));
-        }
-
-        // Update epoch
-        self.current_epoch = new_epoch;
-        Ok(())
+        self.core_mut()
+            .audited(|core| core.apply_epoch_upgrade_internal(new_epoch))
     }
 }
 
@@ -588,38 +1681,34 @@ impl PqrrStateMachine {
     /// - `initial_epoch`: Initial epoch version
     #[uniffi::constructor]
     pub fn new(initial_epoch: u32) -> Self {
-        Self {
-            current_epoch: CryptoEpoch::new(
+        Self::create(
+            CryptoEpoch::new(
                 initial_epoch as u64,
                 crate::models::epoch::CryptoAlgorithm::V1,
             ),
-            state: ProtocolState::Idle,
-            device_headers: HashMap::new(),
-            veto_signals: HashMap::new(),
-            rekeying_context: None,
-            recovery_context: None,
-        }
+            HashMap::new(),
+        )
     }
 
     /// Get current epoch version (UniFFI exported)
     ///
     /// Returns the current epoch version as u32.
     pub fn get_current_epoch(&self) -> u32 {
-        self.current_epoch.version as u32
+        self.current_epoch().version as u32
     }
 
     /// Get current protocol state (UniFFI exported)
     ///
     /// Returns the current protocol state.
     pub fn get_state(&self) -> ProtocolState {
-        self.state.clone()
+        self.state()
     }
 
     /// Get device headers (UniFFI exported)
     ///
     /// Returns list of all device header information with serialized blobs.
     pub fn get_device_headers(&self) -> Vec<DeviceHeaderInfo> {
-        self.device_headers
+        self.device_headers()
             .iter()
             .map(|(device_id, header)| DeviceHeaderInfo {
                 device_id: device_id.to_string(),
@@ -630,6 +1719,19 @@ impl PqrrStateMachine {
             .collect()
     }
 
+    /// Get progress of the rekey in progress (UniFFI exported)
+    ///
+    /// Returns `None` outside the Rekeying state. The UI polls this while an
+    /// epoch upgrade runs in the background.
+    pub fn get_rekey_progress(&self) -> Option<RekeyProgressInfo> {
+        self.core
+            .read()
+            .unwrap()
+            .rekeying_context
+            .as_ref()
+            .map(RekeyingContext::progress_info)
+    }
+
     /// Check if a device is active (UniFFI exported)
     ///
     /// # Arguments
@@ -642,64 +1744,82 @@ impl PqrrStateMachine {
         }
         let mut bytes = [0u8; 16];
         bytes.copy_from_slice(&device_id_bytes);
-        let device_id = DeviceId::from_bytes(bytes);
-        self.device_headers
-            .get(&device_id)
-            .map(|h| h.status == crate::models::device::DeviceStatus::Active)
-            .unwrap_or(false)
+        self.is_device_active_internal(&DeviceId::from_bytes(bytes))
     }
 
     /// Transition to Rekeying state (UniFFI exported)
     ///
+    /// The new epoch keeps the current algorithm.
+    ///
     /// # Arguments
-    /// - `_new_epoch`: New epoch version
-    pub fn transition_to_rekeying(&self, _new_epoch: u32) -> Result<()> {
-        // Note: This requires interior mutability pattern for UniFFI
-        // For now, return error indicating this should be called from Rust
-        Err(PqrrError::invalid_transition(
-            self.state.as_str().to_string(),
-            "Rekeying".to_string(),
-            "State transitions must be done through Rust API".to_string(),
-        ))
+    /// - `new_epoch`: New epoch version
+    ///
+    /// # Errors
+    /// - `PqrrError::EpochRegression` - Invariant #1 violated
+    /// - `PqrrError::InvalidStateTransition` - Not in Idle state
+    pub fn transition_to_rekeying(&self, new_epoch: u32) -> Result<()> {
+        let mut core = self.core.write().unwrap();
+        let new_epoch = CryptoEpoch::new(new_epoch as u64, core.current_epoch.algorithm);
+        core.audited(|core| core.transition_to_rekeying_internal(new_epoch))
     }
 
     /// Transition to Degraded state (UniFFI exported)
     pub fn transition_to_degraded(&self) -> Result<()> {
-        Err(PqrrError::invalid_transition(
-            self.state.as_str().to_string(),
-            "Degraded".to_string(),
-            "State transitions must be done through Rust API".to_string(),
-        ))
+        self.core
+            .write()
+            .unwrap()
+            .audited(|core| core.transition_to_degraded_internal())
     }
 
     /// Transition to Revoked state (UniFFI exported)
     pub fn transition_to_revoked(&self) -> Result<()> {
-        Err(PqrrError::invalid_transition(
-            self.state.as_str().to_string(),
-            "Revoked".to_string(),
-            "State transitions must be done through Rust API".to_string(),
-        ))
+        self.core
+            .write()
+            .unwrap()
+            .audited(|core| core.transition_to_revoked_internal())
     }
 
     /// Return to Idle state (UniFFI exported)
+    ///
+    /// Leaving Degraded is not possible here: it requires a fresh integrity
+    /// verdict, checked by `AeternumEngine::recheck_integrity`.
+    ///
+    /// # Errors
+    /// - `PqrrError::InvalidStateTransition` - Already in terminal state, or
+    ///   Degraded
     pub fn return_to_idle(&self) -> Result<()> {
-        Err(PqrrError::invalid_transition(
-            self.state.as_str().to_string(),
-            "Idle".to_string(),
-            "State transitions must be done through Rust API".to_string(),
-        ))
+        let mut core = self.core.write().unwrap();
+        if core.state == ProtocolState::Degraded {
+            return Err(PqrrError::invalid_transition(
+                "Degraded".to_string(),
+                "Idle".to_string(),
+                "leaving Degraded requires an integrity recheck".to_string(),
+            ));
+        }
+        core.audited(|core| core.return_to_idle_internal())
     }
 
     /// Apply epoch upgrade (UniFFI exported)
     ///
+    /// The new epoch keeps the current algorithm.
+    ///
     /// # Arguments
     /// - `new_epoch`: New epoch version
+    ///
+    /// # Errors
+    /// - `PqrrError::EpochRegression` - Invariant #1 violated
     pub fn apply_epoch_upgrade(&self, new_epoch: u32) -> Result<()> {
-        Err(PqrrError::invalid_transition(
-            self.state.as_str().to_string(),
-            format!("Epoch{}", new_epoch),
-            "Epoch upgrades must be done through Rust API".to_string(),
-        ))
+        let mut core = self.core.write().unwrap();
+        let new_epoch = CryptoEpoch::new(new_epoch as u64, core.current_epoch.algorithm);
+        core.audited(|core| core.apply_epoch_upgrade_internal(new_epoch))
+    }
+
+    /// Check for revocations awaiting an epoch rotation (UniFFI exported)
+    ///
+    /// Returns `true` if a device was revoked since the last epoch
+    /// upgrade; the UI should prompt the user to rotate.
+    pub fn has_pending_revocations(&self) -> bool {
+        !self.core.read().unwrap().pending_revocations.is_empty()
     }
 
     /// Validate epoch monotonicity (UniFFI exported)
@@ -709,7 +1829,7 @@ impl PqrrStateMachine {
     ///
     /// Returns `true` if new_epoch > current_epoch.
     pub fn validate_epoch_monotonicity(&self, new_epoch: u32) -> bool {
-        new_epoch as u64 > self.current_epoch.version
+        new_epoch as u64 > self.current_epoch().version
     }
 
     /// Check veto supremacy (UniFFI exported)
@@ -719,7 +1839,10 @@ impl PqrrStateMachine {
     ///
     /// Returns `true` if veto signals exist.
     pub fn check_veto_supremacy(&self, request_id: String) -> bool {
-        self.veto_signals
+        self.core
+            .read()
+            .unwrap()
+            .veto_signals
             .get(&request_id)
             .map(|v| !v.is_empty())
             .unwrap_or(false)
@@ -727,10 +1850,24 @@ impl PqrrStateMachine {
 
     /// Validate header completeness (UniFFI exported)
     ///
-    /// Returns `true` if all devices have valid headers.
+    /// Returns `true` if every active device has exactly one header in
+    /// the current epoch (Invariant #2).
     pub fn validate_header_completeness(&self) -> bool {
-        // Basic check: all devices with Active status have headers
-        true // TODO: Implement full validation
+        self.check_header_completeness_internal().is_ok()
+    }
+
+    /// Get the transition audit log (UniFFI exported)
+    ///
+    /// Returns every state change in order, oldest first.
+    pub fn get_transition_log(&self) -> Vec<TransitionEvent> {
+        self.core.read().unwrap().transition_log.clone()
+    }
+
+    /// Verify the transition log hash chain (UniFFI exported)
+    ///
+    /// Returns `true` if no entry was modified, reordered, or removed.
+    pub fn verify_transition_log(&self) -> bool {
+        TransitionEvent::verify_chain(&self.core.read().unwrap().transition_log).is_ok()
     }
 }
 
@@ -752,6 +1889,27 @@ pub struct DeviceHeaderInfo {
     pub header_blob: Vec<u8>,
 }
 
+/// Rekey progress (simplified for FFI)
+///
+/// Snapshot of a [`RekeyingContext`] for progress display.
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct RekeyProgressInfo {
+    /// Epoch version being upgraded from
+    pub old_epoch: u32,
+
+    /// Epoch version being upgraded to
+    pub new_epoch: u32,
+
+    /// Devices with a completed header update
+    pub completed: u32,
+
+    /// Devices still pending a header update
+    pub remaining: u32,
+
+    /// Completed fraction, in `0.0..=1.0`
+    pub progress: f32,
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -761,6 +1919,8 @@ mod tests {
     use super::*;
     use crate::models::device::{DeviceHeader, DeviceStatus};
     use crate::models::epoch::CryptoAlgorithm;
+    use crate::protocol::recovery::VETO_WINDOW_MS;
+    use crate::protocol::time::MockTimeSource;
 
     // ------------------------------------------------------------------------
     // ProtocolState Tests
@@ -824,6 +1984,26 @@ mod tests {
         assert!(ctx.is_complete());
     }
 
+    #[test]
+    fn test_rekeying_context_stage_header() {
+        let keypair = crate::crypto::kem::KyberKEM::generate_keypair();
+        let (_, encrypted_dek) =
+            crate::crypto::kem::KyberKEM::encapsulate(&keypair.public).unwrap();
+        let device_id = DeviceId::generate();
+        let header = DeviceHeader::new(
+            device_id,
+            CryptoEpoch::new(2, CryptoAlgorithm::V1),
+            keypair.public,
+            encrypted_dek,
+        );
+
+        let mut ctx = RekeyingContext::new(1, 2, vec![device_id]);
+        ctx.stage_header(header.clone());
+
+        assert!(ctx.is_complete());
+        assert_eq!(ctx.staged_headers.get(&device_id), Some(&header));
+    }
+
     #[test]
     fn test_rekeying_context_mark_device_completed() {
         let device_id = DeviceId::generate();
@@ -838,23 +2018,84 @@ mod tests {
         assert_eq!(ctx.completed_devices.len(), 1);
     }
 
+    #[test]
+    fn test_rekeying_context_progress() {
+        let devices: Vec<DeviceId> = (0..4).map(|_| DeviceId::generate()).collect();
+        let mut ctx = RekeyingContext::new(1, 2, devices.clone());
+
+        // 0%
+        assert_eq!(ctx.progress(), 0.0);
+        assert_eq!(ctx.remaining(), 4);
+
+        // Partial
+        ctx.mark_device_completed(&devices[0]);
+        assert_eq!(ctx.progress(), 0.25);
+        assert_eq!(ctx.remaining(), 3);
+
+        // 100%
+        for device_id in &devices[1..] {
+            ctx.mark_device_completed(device_id);
+        }
+        assert_eq!(ctx.progress(), 1.0);
+        assert_eq!(ctx.remaining(), 0);
+
+        let info = ctx.progress_info();
+        assert_eq!(
+            info,
+            RekeyProgressInfo {
+                old_epoch: 1,
+                new_epoch: 2,
+                completed: 4,
+                remaining: 0,
+                progress: 1.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_rekeying_context_progress_without_devices() {
+        let ctx = RekeyingContext::new(1, 2, vec![]);
+        assert_eq!(ctx.progress(), 1.0);
+        assert_eq!(ctx.remaining(), 0);
+        assert_eq!(ctx.progress_info().completed, 0);
+    }
+
+    #[test]
+    fn test_get_rekey_progress() {
+        let mut sm = PqrrStateMachine::create(CryptoEpoch::initial(), HashMap::new());
+        assert_eq!(sm.get_rekey_progress(), None);
+
+        let device_id = DeviceId::generate();
+        sm.transition_to_rekeying_internal(CryptoEpoch::new(2, CryptoAlgorithm::V1))
+            .unwrap();
+        sm.rekeying_context_mut().unwrap().pending_devices = vec![device_id];
+        assert_eq!(sm.get_rekey_progress().unwrap().progress, 0.0);
+
+        sm.rekeying_context_mut()
+            .unwrap()
+            .mark_device_completed(&device_id);
+        let info = sm.get_rekey_progress().unwrap();
+        assert_eq!(info.completed, 1);
+        assert_eq!(info.progress, 1.0);
+    }
+
     // ------------------------------------------------------------------------
     // RecoveryContext Tests
     // ------------------------------------------------------------------------
 
     #[test]
     fn test_recovery_context_new() {
-        let ctx = RecoveryContext::new("req_1".to_string(), 1000, "AUTHORIZED".to_string());
+        let ctx = RecoveryContext::new("req_1".to_string(), 1000, Role::Authorized);
         assert_eq!(ctx.request_id, "req_1");
         assert_eq!(ctx.start_time, 1000);
         assert_eq!(ctx.end_time, 1000 + (48 * 60 * 60 * 1000));
-        assert_eq!(ctx.initiator_role, "AUTHORIZED");
+        assert_eq!(ctx.initiator_role, Role::Authorized);
         assert!(ctx.vetoes.is_empty());
     }
 
     #[test]
     fn test_recovery_context_is_within_window() {
-        let ctx = RecoveryContext::new("req_1".to_string(), 1000, "AUTHORIZED".to_string());
+        let ctx = RecoveryContext::new("req_1".to_string(), 1000, Role::Authorized);
 
         assert!(!ctx.is_within_window(999)); // Before start
         assert!(ctx.is_within_window(1000)); // At start
@@ -864,7 +2105,7 @@ mod tests {
 
     #[test]
     fn test_recovery_context_is_window_expired() {
-        let ctx = RecoveryContext::new("req_1".to_string(), 1000, "AUTHORIZED".to_string());
+        let ctx = RecoveryContext::new("req_1".to_string(), 1000, Role::Authorized);
 
         assert!(!ctx.is_window_expired(1000)); // At start
         assert!(!ctx.is_window_expired(1000 + (48 * 60 * 60 * 1000) / 2)); // Middle
@@ -874,7 +2115,7 @@ mod tests {
 
     #[test]
     fn test_recovery_context_is_vetoed() {
-        let mut ctx = RecoveryContext::new("req_1".to_string(), 1000, "AUTHORIZED".to_string());
+        let mut ctx = RecoveryContext::new("req_1".to_string(), 1000, Role::Authorized);
 
         assert!(!ctx.is_vetoed());
 
@@ -884,7 +2125,7 @@ mod tests {
 
     #[test]
     fn test_recovery_context_veto_count() {
-        let mut ctx = RecoveryContext::new("req_1".to_string(), 1000, "AUTHORIZED".to_string());
+        let mut ctx = RecoveryContext::new("req_1".to_string(), 1000, Role::Authorized);
 
         assert_eq!(ctx.veto_count(), 0);
 
@@ -930,6 +2171,25 @@ mod tests {
         assert_eq!(sm.device_headers().len(), 1);
     }
 
+    #[test]
+    fn test_pqrr_state_machine_headers_digest() {
+        let epoch = CryptoEpoch::initial();
+        let device_id = DeviceId::generate();
+        let header = DeviceHeader::new(
+            device_id,
+            epoch,
+            crate::crypto::kem::KyberPublicKeyBytes([0u8; 1568]),
+            crate::crypto::kem::KyberCipherText([0u8; 1568]),
+        );
+        let mut sm = PqrrStateMachine::create(epoch, HashMap::from([(device_id, header)]));
+        let before = sm.headers_digest();
+
+        assert_eq!(before, headers_digest(&sm.device_headers()));
+
+        sm.device_headers_mut().get_mut(&device_id).unwrap().status = DeviceStatus::Revoked;
+        assert_ne!(before, sm.headers_digest());
+    }
+
     // ------------------------------------------------------------------------
     // State Transition Tests
     // ------------------------------------------------------------------------
@@ -965,25 +2225,182 @@ mod tests {
     }
 
     #[test]
-    fn test_transition_to_recovery_success() {
+    fn test_transition_to_recovery_success() {
+        let epoch = CryptoEpoch::initial();
+        let headers = HashMap::new();
+        let mut sm = PqrrStateMachine::create(epoch, headers);
+
+        assert!(sm
+            .transition_to_recovery_internal("req_1".to_string(), 1000, Role::Authorized)
+            .is_ok());
+        assert!(matches!(sm.state(), ProtocolState::RecoveryInitiated));
+    }
+
+    #[test]
+    fn test_transition_to_recovery_records_recovery_initiator() {
+        let mut sm = PqrrStateMachine::create(CryptoEpoch::initial(), HashMap::new());
+
+        assert!(sm
+            .transition_to_recovery_internal("req_1".to_string(), 1000, Role::Recovery)
+            .is_ok());
+        assert!(matches!(sm.state(), ProtocolState::RecoveryInitiated));
+        assert_eq!(
+            sm.recovery_context().unwrap().initiator_role,
+            Role::Recovery
+        );
+    }
+
+    #[test]
+    fn test_transition_to_recovery_rejected_outside_idle() {
+        let mut sm = PqrrStateMachine::create(CryptoEpoch::initial(), HashMap::new());
+        sm.transition_to_recovery_internal("req_1".to_string(), 1000, Role::Recovery)
+            .unwrap();
+
+        let result = sm.transition_to_recovery_internal("req_2".to_string(), 2000, Role::Recovery);
+        assert!(matches!(
+            result,
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+        assert_eq!(sm.recovery_context().unwrap().request_id, "req_1");
+    }
+
+    #[test]
+    fn test_complete_recovery_requires_authorized() {
+        let mut sm = PqrrStateMachine::create(CryptoEpoch::initial(), HashMap::new());
+        sm.transition_to_recovery_internal("req_1".to_string(), 1000, Role::Recovery)
+            .unwrap();
+        let after_window = sm.recovery_context().unwrap().end_time;
+
+        let result = sm.complete_recovery_internal(Role::Recovery, after_window);
+        assert!(matches!(result, Err(PqrrError::CausalBarrier { .. })));
+        assert!(matches!(sm.state(), ProtocolState::RecoveryInitiated));
+
+        assert!(sm
+            .complete_recovery_internal(Role::Authorized, after_window)
+            .is_ok());
+        assert!(matches!(sm.state(), ProtocolState::Idle));
+        assert!(sm.recovery_context().is_none());
+    }
+
+    #[test]
+    fn test_complete_recovery_blocked_by_veto_and_open_window() {
+        let mut sm = PqrrStateMachine::create(CryptoEpoch::initial(), HashMap::new());
+        sm.transition_to_recovery_internal("req_1".to_string(), 1000, Role::Recovery)
+            .unwrap();
+        let after_window = sm.recovery_context().unwrap().end_time;
+
+        let result = sm.complete_recovery_internal(Role::Authorized, 2000);
+        assert!(matches!(
+            result,
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+
+        sm.recovery_context_mut()
+            .unwrap()
+            .add_veto("device_1".to_string());
+        let result = sm.complete_recovery_internal(Role::Authorized, after_window);
+        assert!(matches!(result, Err(PqrrError::Vetoed { .. })));
+        assert!(matches!(sm.state(), ProtocolState::RecoveryInitiated));
+    }
+
+    #[test]
+    fn test_complete_recovery_without_recovery_in_progress() {
+        let mut sm = PqrrStateMachine::create(CryptoEpoch::initial(), HashMap::new());
+
+        let result = sm.complete_recovery_internal(Role::Authorized, 1000);
+        assert!(matches!(
+            result,
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+    }
+
+    #[test]
+    fn test_complete_recovery_reads_time_source() {
+        let start = 1_700_000_000_000;
+        let time = Arc::new(MockTimeSource::new(start));
+        let mut sm = PqrrStateMachine::create_with_time_source(
+            CryptoEpoch::initial(),
+            HashMap::new(),
+            time.clone(),
+        );
+        sm.transition_to_recovery_internal("req_1".to_string(), start, Role::Recovery)
+            .unwrap();
+
+        time.advance(VETO_WINDOW_MS - 1);
+        assert!(matches!(
+            sm.complete_recovery(Role::Authorized),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+
+        time.advance(1);
+        sm.complete_recovery(Role::Authorized).unwrap();
+        assert!(matches!(sm.state(), ProtocolState::Idle));
+        assert_eq!(
+            sm.get_transition_log().last().unwrap().timestamp_ms,
+            start + VETO_WINDOW_MS
+        );
+    }
+
+    #[test]
+    fn test_complete_recovery_fails_closed_on_clock_rollback() {
+        let start = 1_700_000_000_000;
+        let time = Arc::new(MockTimeSource::new(start));
+        let mut sm = PqrrStateMachine::create_with_time_source(
+            CryptoEpoch::initial(),
+            HashMap::new(),
+            time.clone(),
+        );
+        sm.transition_to_recovery_internal("req_1".to_string(), start, Role::Recovery)
+            .unwrap();
+
+        // Window elapsed and observed, then the wall clock is set back to a
+        // time that is still past end_time
+        time.advance(2 * VETO_WINDOW_MS);
+        sm.observe_time();
+        time.set_now_ms(start + VETO_WINDOW_MS + 3_600_000);
+
+        // The observation survives a snapshot, so a restart does not help
+        let mut sm = PqrrStateMachine::restore(&sm.snapshot()).unwrap();
+        sm.set_time_source(time.clone());
+        assert!(matches!(
+            sm.complete_recovery(Role::Authorized),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+        assert!(matches!(sm.state(), ProtocolState::RecoveryInitiated));
+
+        // Clock restored: recovery completes
+        time.set_now_ms(start + 2 * VETO_WINDOW_MS);
+        sm.complete_recovery(Role::Authorized).unwrap();
+        assert!(matches!(sm.state(), ProtocolState::Idle));
+    }
+
+    #[test]
+    fn test_transition_to_degraded() {
         let epoch = CryptoEpoch::initial();
         let headers = HashMap::new();
         let mut sm = PqrrStateMachine::create(epoch, headers);
 
-        assert!(sm
-            .transition_to_recovery_internal("req_1".to_string(), 1000, "AUTHORIZED".to_string())
-            .is_ok());
-        assert!(matches!(sm.state(), ProtocolState::RecoveryInitiated));
+        assert!(sm.transition_to_degraded_internal().is_ok());
+        assert!(matches!(sm.state(), ProtocolState::Degraded));
+        assert_eq!(
+            sm.degraded_reason().as_deref(),
+            Some("integrity verification failed")
+        );
     }
 
     #[test]
-    fn test_transition_to_degraded() {
+    fn test_degraded_reason_cleared_on_return_to_idle() {
         let epoch = CryptoEpoch::initial();
         let headers = HashMap::new();
         let mut sm = PqrrStateMachine::create(epoch, headers);
+        assert_eq!(sm.degraded_reason(), None);
 
-        assert!(sm.transition_to_degraded_internal().is_ok());
-        assert!(matches!(sm.state(), ProtocolState::Degraded));
+        sm.transition_to_degraded_with_reason("root detected".to_string())
+            .unwrap();
+        assert_eq!(sm.degraded_reason().as_deref(), Some("root detected"));
+
+        sm.return_to_idle_internal().unwrap();
+        assert_eq!(sm.degraded_reason(), None);
     }
 
     #[test]
@@ -1107,6 +2524,72 @@ mod tests {
         assert!(!sm.is_device_active(device_id.as_bytes().to_vec()));
     }
 
+    #[test]
+    fn test_degrade_and_restore_device() {
+        let epoch = CryptoEpoch::initial();
+        let degraded = DeviceId::generate();
+        let other = DeviceId::generate();
+        let mut headers = HashMap::new();
+        headers.insert(degraded, header_for(degraded, epoch));
+        headers.insert(other, header_for(other, epoch));
+        let time = Arc::new(MockTimeSource::new(42_000));
+        let mut sm = PqrrStateMachine::create_with_time_source(epoch, headers, time);
+
+        sm.degrade_device(&degraded, DegradeReason::IntegrityCheckFailed)
+            .unwrap();
+        assert!(!sm.is_device_active_internal(&degraded));
+        let info = sm.device_headers()[&degraded]
+            .degraded_info
+            .clone()
+            .unwrap();
+        assert_eq!(info.reason, DegradeReason::IntegrityCheckFailed);
+        assert_eq!(info.since_ms, 42_000);
+
+        // A degraded device no longer needs a current header
+        sm.device_headers_mut().get_mut(&degraded).unwrap().epoch = epoch.next();
+        assert!(sm.check_header_completeness_internal().is_ok());
+        sm.device_headers_mut().get_mut(&degraded).unwrap().epoch = epoch;
+
+        sm.restore_device(&degraded).unwrap();
+        assert!(sm.is_device_active_internal(&degraded));
+        assert!(sm.device_headers()[&degraded].degraded_info.is_none());
+
+        // Only Degraded devices can be restored
+        assert!(matches!(
+            sm.restore_device(&other),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+        assert!(matches!(
+            sm.degrade_device(&DeviceId::generate(), DegradeReason::KeyMismatch),
+            Err(PqrrError::HeaderIncomplete { .. })
+        ));
+    }
+
+    #[test]
+    fn test_revoked_device_cannot_be_degraded_or_restored() {
+        let epoch = CryptoEpoch::initial();
+        let device_id = DeviceId::generate();
+        let mut header = header_for(device_id, epoch);
+        header.revoke();
+        let mut headers = HashMap::new();
+        headers.insert(device_id, header);
+        let mut sm = PqrrStateMachine::create(epoch, headers);
+
+        let result = sm.restore_device(&device_id);
+        assert!(matches!(
+            result,
+            Err(PqrrError::InvalidStateTransition { ref from, .. }) if from == "Revoked"
+        ));
+        assert!(matches!(
+            sm.degrade_device(&device_id, DegradeReason::AttestationFailed),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+        assert_eq!(
+            sm.device_headers()[&device_id].status,
+            DeviceStatus::Revoked
+        );
+    }
+
     #[test]
     fn test_is_device_active_not_found() {
         let epoch = CryptoEpoch::initial();
@@ -1116,4 +2599,618 @@ mod tests {
         let device_id = DeviceId::generate();
         assert!(!sm.is_device_active(device_id.as_bytes().to_vec()));
     }
+
+    // ------------------------------------------------------------------------
+    // UniFFI Exported Transition Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_exported_rekeying_cycle() {
+        let sm = PqrrStateMachine::new(1);
+
+        sm.transition_to_rekeying(2).unwrap();
+        assert_eq!(sm.get_state(), ProtocolState::Rekeying);
+        assert_eq!(sm.rekeying_context().unwrap().new_epoch, 2);
+
+        sm.apply_epoch_upgrade(2).unwrap();
+        sm.return_to_idle().unwrap();
+
+        assert_eq!(sm.get_state(), ProtocolState::Idle);
+        assert_eq!(sm.get_current_epoch(), 2);
+        assert!(sm.rekeying_context().is_none());
+    }
+
+    #[test]
+    fn test_exported_transitions_enforce_invariants() {
+        let sm = PqrrStateMachine::new(3);
+
+        assert!(matches!(
+            sm.transition_to_rekeying(3),
+            Err(PqrrError::EpochRegression { .. })
+        ));
+        assert!(matches!(
+            sm.apply_epoch_upgrade(2),
+            Err(PqrrError::EpochRegression { .. })
+        ));
+        assert_eq!(sm.get_state(), ProtocolState::Idle);
+
+        sm.transition_to_rekeying(4).unwrap();
+        assert!(matches!(
+            sm.transition_to_rekeying(5),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+
+        sm.transition_to_revoked().unwrap();
+        assert!(matches!(
+            sm.return_to_idle(),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+        assert_eq!(sm.get_current_epoch(), 3);
+    }
+
+    #[test]
+    fn test_exported_return_to_idle_cannot_leave_degraded() {
+        let mut sm = PqrrStateMachine::new(1);
+
+        sm.transition_to_degraded().unwrap();
+        assert_eq!(sm.get_state(), ProtocolState::Degraded);
+        assert!(sm.degraded_reason().is_some());
+
+        assert!(matches!(
+            sm.return_to_idle(),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+        assert_eq!(sm.get_state(), ProtocolState::Degraded);
+
+        // The engine's integrity recheck uses the internal transition
+        sm.return_to_idle_internal().unwrap();
+        assert_eq!(sm.get_state(), ProtocolState::Idle);
+        assert!(sm.degraded_reason().is_none());
+    }
+
+    #[test]
+    fn test_exported_transition_is_atomic_across_threads() {
+        let sm = std::sync::Arc::new(PqrrStateMachine::new(1));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let sm = std::sync::Arc::clone(&sm);
+                std::thread::spawn(move || sm.transition_to_rekeying(2).is_ok())
+            })
+            .collect();
+        let successes = handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .filter(|ok| *ok)
+            .count();
+
+        assert_eq!(successes, 1);
+        assert_eq!(sm.get_state(), ProtocolState::Rekeying);
+    }
+
+    // ------------------------------------------------------------------------
+    // Invariant #2: Header Completeness Tests
+    // ------------------------------------------------------------------------
+
+    fn header_for(device_id: DeviceId, epoch: CryptoEpoch) -> DeviceHeader {
+        DeviceHeader::new(
+            device_id,
+            epoch,
+            crate::crypto::kem::KyberPublicKeyBytes([0u8; 1568]),
+            crate::crypto::kem::KyberCipherText([0u8; 1568]),
+        )
+    }
+
+    #[test]
+    fn test_validate_header_completeness_complete_set() {
+        let epoch = CryptoEpoch::new(2, CryptoAlgorithm::V1);
+        let mut headers = HashMap::new();
+        for _ in 0..3 {
+            let id = DeviceId::generate();
+            headers.insert(id, header_for(id, epoch));
+        }
+        // Revoked devices may keep a stale header
+        let revoked = DeviceId::generate();
+        let mut stale = header_for(revoked, CryptoEpoch::initial());
+        stale.status = DeviceStatus::Revoked;
+        headers.insert(revoked, stale);
+
+        let sm = PqrrStateMachine::create(epoch, headers);
+        assert!(sm.validate_header_completeness());
+        assert!(sm.check_header_completeness_internal().is_ok());
+    }
+
+    #[test]
+    fn test_validate_header_completeness_missing_header() {
+        let epoch = CryptoEpoch::new(2, CryptoAlgorithm::V1);
+        let current = DeviceId::generate();
+        let lagging = DeviceId::generate();
+        let headers = HashMap::from([
+            (current, header_for(current, epoch)),
+            (lagging, header_for(lagging, CryptoEpoch::initial())),
+        ]);
+
+        let sm = PqrrStateMachine::create(epoch, headers);
+        assert!(!sm.validate_header_completeness());
+        assert!(matches!(
+            sm.check_header_completeness_internal(),
+            Err(PqrrError::HeaderIncomplete { .. })
+        ));
+    }
+
+    /// State machine at epoch 1 with `active` active devices and one revoked
+    /// device, rekeying to epoch 2
+    fn rekeying_with_devices(active: usize) -> (PqrrStateMachine, Vec<DeviceId>, CryptoEpoch) {
+        let current = CryptoEpoch::initial();
+        let new_epoch = CryptoEpoch::new(2, CryptoAlgorithm::V1);
+        let ids: Vec<DeviceId> = (0..active).map(|_| DeviceId::generate()).collect();
+        let mut headers: HashMap<DeviceId, DeviceHeader> = ids
+            .iter()
+            .map(|id| (*id, header_for(*id, current)))
+            .collect();
+        let revoked = DeviceId::generate();
+        let mut stale = header_for(revoked, current);
+        stale.status = DeviceStatus::Revoked;
+        headers.insert(revoked, stale);
+
+        let mut sm = PqrrStateMachine::create(current, headers);
+        sm.transition_to_rekeying_internal(new_epoch).unwrap();
+        (sm, ids, new_epoch)
+    }
+
+    #[test]
+    fn test_staged_header_completeness_blocks_missing_active_header() {
+        let (mut sm, ids, new_epoch) = rekeying_with_devices(3);
+        let ctx = sm.rekeying_context_mut().unwrap();
+        for id in &ids[..2] {
+            ctx.stage_header(header_for(*id, new_epoch));
+        }
+
+        assert!(matches!(
+            sm.check_staged_header_completeness(&new_epoch),
+            Err(PqrrError::HeaderIncomplete { .. })
+        ));
+    }
+
+    #[test]
+    fn test_staged_header_completeness_allows_revoked_without_header() {
+        let (mut sm, ids, new_epoch) = rekeying_with_devices(2);
+        let ctx = sm.rekeying_context_mut().unwrap();
+        for id in &ids {
+            ctx.stage_header(header_for(*id, new_epoch));
+        }
+
+        // The revoked device keeps only its old-epoch header
+        assert!(sm.check_staged_header_completeness(&new_epoch).is_ok());
+    }
+
+    #[test]
+    fn test_staged_header_completeness_requires_rekeying() {
+        let sm = PqrrStateMachine::create(CryptoEpoch::initial(), HashMap::new());
+        assert!(matches!(
+            sm.check_staged_header_completeness(&CryptoEpoch::initial()),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_header_completeness_duplicate_header() {
+        let epoch = CryptoEpoch::initial();
+        let device_a = DeviceId::generate();
+        let device_b = DeviceId::generate();
+        // device_a's slot holds a second header for device_b
+        let headers = HashMap::from([
+            (device_a, header_for(device_b, epoch)),
+            (device_b, header_for(device_b, epoch)),
+        ]);
+
+        let sm = PqrrStateMachine::create(epoch, headers);
+        assert!(!sm.validate_header_completeness());
+        assert!(matches!(
+            sm.check_header_completeness_internal(),
+            Err(PqrrError::HeaderIncomplete { .. })
+        ));
+    }
+
+    // ------------------------------------------------------------------------
+    // Snapshot / Restore Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_snapshot_restore_roundtrip() {
+        let epoch = CryptoEpoch::new(3, CryptoAlgorithm::V1);
+        let mut headers = HashMap::new();
+        for _ in 0..3 {
+            let id = DeviceId::generate();
+            headers.insert(id, header_for(id, epoch));
+        }
+
+        let mut sm = PqrrStateMachine::create(epoch, headers);
+        sm.transition_to_rekeying_internal(CryptoEpoch::new(4, CryptoAlgorithm::V1))
+            .unwrap();
+        let staged_id = *sm.device_headers().keys().next().unwrap();
+        let staged = header_for(staged_id, CryptoEpoch::new(4, CryptoAlgorithm::V1));
+        sm.rekeying_context_mut().unwrap().stage_header(staged);
+
+        let restored = PqrrStateMachine::restore(&sm.snapshot()).unwrap();
+        assert_eq!(restored.current_epoch(), sm.current_epoch());
+        assert_eq!(restored.state(), ProtocolState::Rekeying);
+        assert_eq!(*restored.device_headers(), *sm.device_headers());
+        assert_eq!(restored.rekeying_context(), sm.rekeying_context());
+        assert_eq!(restored.recovery_context(), None);
+        assert_eq!(restored.headers_digest(), sm.headers_digest());
+
+        // Recovery context survives as well
+        let mut sm = PqrrStateMachine::create(epoch, HashMap::new());
+        sm.transition_to_recovery_internal("req-1".to_string(), 1_000, Role::Recovery)
+            .unwrap();
+        sm.recovery_context_mut()
+            .unwrap()
+            .add_veto("device-a".to_string());
+
+        let restored = PqrrStateMachine::restore(&sm.snapshot()).unwrap();
+        assert_eq!(restored.state(), ProtocolState::RecoveryInitiated);
+        assert_eq!(restored.recovery_context(), sm.recovery_context());
+    }
+
+    #[test]
+    fn test_restore_rejects_corrupt_state() {
+        let epoch = CryptoEpoch::new(2, CryptoAlgorithm::V1);
+
+        // Undecodable bytes
+        assert!(matches!(
+            PqrrStateMachine::restore(&[0xFF; 7]),
+            Err(PqrrError::StorageError { .. })
+        ));
+        let bytes = PqrrStateMachine::create(epoch, HashMap::new()).snapshot();
+        assert!(matches!(
+            PqrrStateMachine::restore(&bytes[..bytes.len() - 1]),
+            Err(PqrrError::StorageError { .. })
+        ));
+
+        // Header from a future epoch: the machine's epoch has rolled back
+        let id = DeviceId::generate();
+        let future = header_for(id, CryptoEpoch::new(5, CryptoAlgorithm::V1));
+        let core = StateMachineCore::new(epoch, HashMap::from([(id, future)]));
+        assert!(matches!(
+            PqrrStateMachine::restore(&bincode::serialize(&core).unwrap()),
+            Err(PqrrError::EpochRegression { .. })
+        ));
+
+        // Rekeying context that does not move forward
+        let mut core = StateMachineCore::new(epoch, HashMap::new());
+        core.state = ProtocolState::Rekeying;
+        core.rekeying_context = Some(RekeyingContext::new(2, 2, vec![]));
+        assert!(matches!(
+            PqrrStateMachine::restore(&bincode::serialize(&core).unwrap()),
+            Err(PqrrError::EpochRegression { .. })
+        ));
+
+        // Rekeying state without its context
+        let mut core = StateMachineCore::new(epoch, HashMap::new());
+        core.state = ProtocolState::Rekeying;
+        assert!(matches!(
+            PqrrStateMachine::restore(&bincode::serialize(&core).unwrap()),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+    }
+
+    // ------------------------------------------------------------------------
+    // Transition Audit Log Tests
+    // ------------------------------------------------------------------------
+
+    fn logged_machine() -> PqrrStateMachine {
+        let mut sm = PqrrStateMachine::create(CryptoEpoch::initial(), HashMap::new());
+        sm.transition_to_rekeying_internal(CryptoEpoch::new(2, CryptoAlgorithm::V1))
+            .unwrap();
+        sm.return_to_idle_internal().unwrap();
+        sm.transition_to_degraded_internal().unwrap();
+        sm.return_to_idle_internal().unwrap();
+        sm.transition_to_recovery_internal("req-1".to_string(), 0, Role::Recovery)
+            .unwrap();
+        sm.transition_to_revoked_internal().unwrap();
+        sm
+    }
+
+    #[test]
+    fn test_transition_log_is_chained() {
+        let sm = logged_machine();
+        let log = sm.get_transition_log();
+
+        let pairs: Vec<(ProtocolState, ProtocolState)> =
+            log.iter().map(|e| (e.from.clone(), e.to.clone())).collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (ProtocolState::Idle, ProtocolState::Rekeying),
+                (ProtocolState::Rekeying, ProtocolState::Idle),
+                (ProtocolState::Idle, ProtocolState::Degraded),
+                (ProtocolState::Degraded, ProtocolState::Idle),
+                (ProtocolState::Idle, ProtocolState::RecoveryInitiated),
+                (ProtocolState::RecoveryInitiated, ProtocolState::Revoked),
+            ]
+        );
+
+        assert_eq!(log[0].prev_hash, vec![0u8; 32]);
+        for pair in log.windows(2) {
+            assert_eq!(pair[1].prev_hash, pair[0].hash);
+        }
+        assert!(log[0].reason.contains("1 -> 2"));
+        assert!(TransitionEvent::verify_chain(&log).is_ok());
+        assert!(sm.verify_transition_log());
+
+        // Rejected transitions are not logged
+        let mut sm = sm;
+        assert!(sm.return_to_idle_internal().is_err());
+        assert_eq!(sm.get_transition_log(), log);
+
+        // The log survives a snapshot round trip
+        let restored = PqrrStateMachine::restore(&sm.snapshot()).unwrap();
+        assert_eq!(restored.get_transition_log(), log);
+    }
+
+    #[test]
+    fn test_transition_log_detects_tampering() {
+        let log = logged_machine().get_transition_log();
+
+        let mut edited = log.clone();
+        edited[2].reason = "routine maintenance".to_string();
+        assert!(matches!(
+            TransitionEvent::verify_chain(&edited),
+            Err(PqrrError::TransitionLogTampered { index: 2 })
+        ));
+
+        let mut swapped = log.clone();
+        swapped.swap(1, 3);
+        assert!(matches!(
+            TransitionEvent::verify_chain(&swapped),
+            Err(PqrrError::TransitionLogTampered { index: 1 })
+        ));
+
+        let mut truncated = log.clone();
+        truncated.remove(0);
+        assert!(matches!(
+            TransitionEvent::verify_chain(&truncated),
+            Err(PqrrError::TransitionLogTampered { index: 0 })
+        ));
+
+        // A tampered snapshot is refused
+        let mut core = StateMachineCore::new(CryptoEpoch::initial(), HashMap::new());
+        core.transition_to_degraded_internal().unwrap();
+        core.transition_log[0].timestamp_ms += 1;
+        assert!(matches!(
+            PqrrStateMachine::restore(&bincode::serialize(&core).unwrap()),
+            Err(PqrrError::TransitionLogTampered { index: 0 })
+        ));
+    }
+
+    // ------------------------------------------------------------------------
+    // Audit Sink Tests
+    // ------------------------------------------------------------------------
+
+    #[derive(Default)]
+    struct RecordingSink(std::sync::Mutex<Vec<AuditEvent>>);
+
+    impl AuditSink for RecordingSink {
+        fn record(&self, event: AuditEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    #[test]
+    fn test_audit_sink_receives_transitions_and_violations() {
+        let sink = Arc::new(RecordingSink::default());
+        let mut sm = PqrrStateMachine::create(CryptoEpoch::initial(), HashMap::new());
+        sm.set_audit_sink(sink.clone());
+
+        sm.transition_to_rekeying_internal(CryptoEpoch::new(2, CryptoAlgorithm::V1))
+            .unwrap();
+        sm.apply_epoch_upgrade_internal(CryptoEpoch::new(2, CryptoAlgorithm::V1))
+            .unwrap();
+        sm.return_to_idle_internal().unwrap();
+        assert!(sm
+            .transition_to_rekeying_internal(CryptoEpoch::new(1, CryptoAlgorithm::V1))
+            .is_err());
+        sm.transition_to_recovery_internal("req-1".to_string(), 0, Role::Recovery)
+            .unwrap();
+        sm.transition_to_revoked_internal().unwrap();
+
+        let events = sink.0.lock().unwrap();
+        let types: Vec<AuditEventType> = events.iter().map(|e| e.event_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                AuditEventType::StateTransition,
+                AuditEventType::EpochUpgrade,
+                AuditEventType::StateTransition,
+                AuditEventType::InvariantViolation,
+                AuditEventType::RecoveryStarted,
+                AuditEventType::DeviceRevoked,
+            ]
+        );
+        assert_eq!(events[0].subject, "Idle -> Rekeying");
+        assert_eq!(events[1].subject, "1 -> 2");
+        assert_eq!(events[3].subject, "Invariant #1");
+        assert_eq!(events[3].epoch, 2);
+
+        // Rejections that are not invariant violations are not reported
+        drop(events);
+        assert!(sm.return_to_idle_internal().is_err());
+        assert_eq!(sink.0.lock().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_audit_sink_receives_verified_vetoes() {
+        use crate::protocol::recovery::{VetoMessage, VetoSigningKey};
+
+        let device_id = DeviceId::generate();
+        let header = DeviceHeader::new(
+            device_id,
+            CryptoEpoch::initial(),
+            crate::crypto::kem::KyberPublicKeyBytes([0u8; 1568]),
+            crate::crypto::kem::KyberCipherText([0u8; 1568]),
+        );
+        let signing_key = VetoSigningKey::generate();
+        let mut registry = VetoKeyRegistry::new();
+        registry
+            .register(device_id, signing_key.verifying_key())
+            .unwrap();
+
+        let sink = Arc::new(RecordingSink::default());
+        let mut sm =
+            PqrrStateMachine::create(CryptoEpoch::initial(), HashMap::from([(device_id, header)]));
+        sm.set_audit_sink(sink.clone());
+        sm.transition_to_recovery_internal("req-1".to_string(), 0, Role::Recovery)
+            .unwrap();
+
+        // Forged and misdirected vetoes are neither counted nor reported
+        let forged =
+            VetoMessage::new(device_id, None).to_signed(&VetoSigningKey::generate(), "req-1");
+        assert!(matches!(
+            sm.receive_veto(&forged, &registry),
+            Err(PqrrError::InvalidVetoSignature { .. })
+        ));
+        let misdirected = VetoMessage::new(device_id, None).to_signed(&signing_key, "req-2");
+        assert!(sm.receive_veto(&misdirected, &registry).is_err());
+        assert!(!sm.check_veto_supremacy("req-1".to_string()));
+
+        let veto = VetoMessage::new(device_id, Some("not me".to_string()))
+            .to_signed(&signing_key, "req-1");
+        sm.receive_veto(&veto, &registry).unwrap();
+        sm.receive_veto(&veto, &registry).unwrap();
+
+        assert!(sm.check_veto_supremacy("req-1".to_string()));
+        let events = sink.0.lock().unwrap();
+        let vetoes: Vec<&AuditEvent> = events
+            .iter()
+            .filter(|e| e.event_type == AuditEventType::VetoReceived)
+            .collect();
+        assert_eq!(vetoes.len(), 1);
+        assert_eq!(vetoes[0].subject, device_id.to_string());
+    }
+
+    #[test]
+    fn test_veto_ends_recovery_and_returns_to_idle() {
+        let mut sm = PqrrStateMachine::new(1);
+        sm.transition_to_recovery_internal("req-1".to_string(), 0, Role::Recovery)
+            .unwrap();
+
+        sm.record_veto_internal("req-1", "device-a".to_string())
+            .unwrap();
+
+        assert!(matches!(sm.state(), ProtocolState::Idle));
+        assert!(sm.recovery_context().is_none());
+        assert!(sm.check_veto_supremacy("req-1".to_string()));
+        let attempts = sm.recovery_attempts();
+        assert_eq!(attempts.attempts()[0].outcome, AttemptOutcome::Vetoed);
+
+        // Repeated vetoes are no-ops; new vetoes find no recovery in progress
+        sm.record_veto_internal("req-1", "device-a".to_string())
+            .unwrap();
+        assert!(sm
+            .record_veto_internal("req-1", "device-b".to_string())
+            .is_err());
+    }
+
+    #[test]
+    fn test_abandoned_recovery_records_outcome() {
+        let mut sm = PqrrStateMachine::new(1);
+        sm.transition_to_recovery_internal("req-1".to_string(), 0, Role::Recovery)
+            .unwrap();
+        sm.transition_to_degraded_internal().unwrap();
+        sm.return_to_idle_internal().unwrap();
+        sm.transition_to_recovery_internal("req-2".to_string(), 0, Role::Recovery)
+            .unwrap();
+        sm.transition_to_revoked_internal().unwrap();
+
+        let outcomes: Vec<AttemptOutcome> = sm
+            .recovery_attempts()
+            .attempts()
+            .iter()
+            .map(|attempt| attempt.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![AttemptOutcome::Abandoned, AttemptOutcome::Abandoned]
+        );
+    }
+
+    // ------------------------------------------------------------------------
+    // Operation Guard Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_begin_operation_exactly_one_winner() {
+        const THREADS: usize = 8;
+
+        for _ in 0..50 {
+            let sm = Arc::new(PqrrStateMachine::new(1));
+            let start = Arc::new(std::sync::Barrier::new(THREADS));
+            let done = Arc::new(std::sync::Barrier::new(THREADS));
+
+            let handles: Vec<_> = (0..THREADS)
+                .map(|_| {
+                    let (sm, start, done) = (sm.clone(), start.clone(), done.clone());
+                    std::thread::spawn(move || {
+                        start.wait();
+                        let result = sm.begin_operation(OperationKind::RegisterDevice);
+                        // Keep the winning guard alive until everyone has tried
+                        done.wait();
+                        match result {
+                            Ok(guard) => {
+                                guard.complete();
+                                true
+                            }
+                            Err(PqrrError::OperationInProgress { .. }) => false,
+                            Err(e) => panic!("unexpected error: {}", e),
+                        }
+                    })
+                })
+                .collect();
+
+            let winners = handles
+                .into_iter()
+                .map(|h| h.join().unwrap())
+                .filter(|won| *won)
+                .count();
+            assert_eq!(winners, 1);
+            assert_eq!(sm.active_operation(), None);
+        }
+    }
+
+    #[test]
+    fn test_operation_guard_blocks_entry_points() {
+        let mut sm = PqrrStateMachine::new(1);
+
+        let guard = sm.begin_operation(OperationKind::EpochUpgrade).unwrap();
+        assert_eq!(guard.kind(), OperationKind::EpochUpgrade);
+        assert_eq!(sm.active_operation(), Some(OperationKind::EpochUpgrade));
+        assert!(matches!(
+            sm.begin_operation(OperationKind::RevokeDevice),
+            Err(PqrrError::OperationInProgress { .. })
+        ));
+        assert!(matches!(
+            sm.transition_to_recovery_internal("req-1".to_string(), 0, Role::Recovery),
+            Err(PqrrError::OperationInProgress { .. })
+        ));
+        assert_eq!(sm.state(), ProtocolState::Idle);
+
+        // Aborting releases the guard
+        guard.abort();
+        assert_eq!(sm.active_operation(), None);
+        sm.transition_to_recovery_internal("req-1".to_string(), 0, Role::Recovery)
+            .unwrap();
+        assert_eq!(sm.active_operation(), None);
+
+        // Only Idle can begin an operation
+        assert!(matches!(
+            sm.begin_operation(OperationKind::EpochUpgrade),
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+
+        // Dropping releases as well
+        let sm = PqrrStateMachine::new(1);
+        drop(sm.begin_operation(OperationKind::RegisterDevice).unwrap());
+        assert!(sm.begin_operation(OperationKind::RegisterDevice).is_ok());
+    }
 }
@@ -10,6 +10,16 @@
 //! - **Invariant #4 Enforcement** - Veto signals have highest priority
 //! - **Recovery Window Tracking** - Manages active recovery attempts
 //! - **Time Drift Tolerance** - ±5min tolerance for clock skew
+//! - **Injectable Clock** - [`TimeSource`] decouples window logic from
+//!   `SystemTime`; a wall clock moved backward keeps the window open
+//! - **Veto Authentication** - Vetoes are signed with a per-device Ed25519
+//!   key, verified against the sender's registered public key and bound to
+//!   one recovery request
+//! - **Rate Limiting** - [`RecoveryAttemptTracker`] caps initiations and
+//!   locks the vault out after vetoed attempts
+//! - **Recovery Promotion** - [`promote_recovery`] / [`finalize_promotion`]
+//!   turn a cold recovery into a new AUTHORIZED device once the veto window
+//!   has elapsed
 //!
 //! ## Invariant #4: Veto Supremacy
 //!
@@ -19,9 +29,23 @@
 //!
 //! Any veto signal within the 48h window immediately terminates recovery.
 
-use crate::models::device::{DeviceId, Role};
+use crate::crypto::kem::{unwrap_dek, KyberKeyPair, KyberPublicKeyBytes, KyberSecretKeyBytes};
+use crate::models::decode_bounded;
+use crate::models::device::{DeviceHeader, DeviceId, Role};
+use crate::models::epoch::CryptoEpoch;
+use crate::models::key_hierarchy::DataEncryptionKey;
+use crate::protocol::device_mgmt::register_device;
+use crate::protocol::epoch_upgrade::EpochUpgradeCoordinator;
 use crate::protocol::error::{PqrrError, Result};
-use std::time::SystemTime;
+use crate::protocol::pqrr::PqrrStateMachine;
+use crate::protocol::time::{SystemTimeSource, TimeSource};
+use crate::storage::backend::VaultBackend;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use zeroize::Zeroizing;
 
 // ============================================================================
 // Constants
@@ -33,6 +57,12 @@ pub const VETO_WINDOW_MS: u64 = 172_800_000;
 /// Time drift tolerance: ±5 minutes in milliseconds
 pub const TIME_DRIFT_TOLERANCE_MS: u64 = 300_000;
 
+/// Domain-separation prefix for veto signatures
+const VETO_SIGNING_CONTEXT: &[u8] = b"Aeternum_Veto_v2";
+
+/// Veto signature length (Ed25519)
+pub const VETO_SIGNATURE_SIZE: usize = ed25519_dalek::SIGNATURE_LENGTH;
+
 // ============================================================================
 // Veto Message
 // ============================================================================
@@ -41,7 +71,23 @@ pub const TIME_DRIFT_TOLERANCE_MS: u64 = 300_000;
 ///
 /// Represents a veto message sent by an active device to terminate
 /// a recovery request.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// ## Authentication
+///
+/// `signature` is an Ed25519 signature over the canonical encoding
+///
+/// ```text
+/// "Aeternum_Veto_v2" || len(request_id):u32 BE || request_id || device_id:16
+///     || timestamp:u64 BE || has_reason:u8 || [len(reason):u32 BE || reason]
+/// ```
+///
+/// made with the sender's own [`VetoSigningKey`]. Receivers check it
+/// against the public key registered for the sender in a
+/// [`VetoKeyRegistry`], so no other device can forge a veto in its name or
+/// alter its reason, and a signed veto cannot be replayed against a
+/// different recovery request. For transmission, wrap it in a
+/// [`SignedVetoMessage`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct VetoMessage {
     /// Device ID that sent this veto
     pub device_id: DeviceId,
@@ -51,6 +97,9 @@ pub struct VetoMessage {
 
     /// Timestamp when veto was sent (Unix milliseconds)
     pub timestamp: u64,
+
+    /// Signature binding the veto to a request (empty until signed)
+    pub signature: Vec<u8>,
 }
 
 impl VetoMessage {
@@ -63,13 +112,18 @@ impl VetoMessage {
     ///
     /// # Returns
     ///
-    /// A new VetoMessage with current timestamp
+    /// A new unsigned VetoMessage with current timestamp
+    ///
+    /// A veto must be issuable even with a broken clock (Invariant #4), so
+    /// an unreadable clock gives timestamp 0. The timestamp only orders
+    /// vetoes; it never decides whether a veto counts.
     pub fn new(device_id: DeviceId, reason: Option<String>) -> Self {
-        let timestamp = current_timestamp_ms();
+        let timestamp = current_timestamp_ms().unwrap_or(0);
         Self {
             device_id,
             reason,
             timestamp,
+            signature: Vec::new(),
         }
     }
 
@@ -81,8 +135,303 @@ impl VetoMessage {
             device_id,
             reason,
             timestamp,
+            signature: Vec::new(),
+        }
+    }
+
+    /// Sign the veto for a recovery request
+    ///
+    /// # Arguments
+    ///
+    /// - `signing_key`: The sending device's veto signing key
+    /// - `request_id`: Recovery request this veto targets
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::protocol::recovery::{VetoMessage, VetoSigningKey};
+    /// use aeternum_core::models::device::DeviceId;
+    ///
+    /// let signing_key = VetoSigningKey::generate();
+    /// let mut veto = VetoMessage::new(DeviceId::generate(), None);
+    /// veto.sign(&signing_key, "rec_1");
+    ///
+    /// assert_eq!(veto.signature.len(), 64);
+    /// ```
+    pub fn sign(&mut self, signing_key: &VetoSigningKey, request_id: &str) {
+        let signature = signing_key.0.sign(&self.canonical_bytes(request_id));
+        self.signature = signature.to_bytes().to_vec();
+    }
+
+    /// Sign a copy of the veto for transmission
+    ///
+    /// # Arguments
+    ///
+    /// - `signing_key`: The sending device's veto signing key
+    /// - `request_id`: Recovery request this veto targets
+    pub fn to_signed(&self, signing_key: &VetoSigningKey, request_id: &str) -> SignedVetoMessage {
+        let mut veto = self.clone();
+        veto.sign(signing_key, request_id);
+        SignedVetoMessage {
+            request_id: request_id.to_string(),
+            veto,
+        }
+    }
+
+    /// Verify the veto against the sender's header and a recovery request
+    ///
+    /// # Arguments
+    ///
+    /// - `verifying_key`: Public key registered for the sending device
+    /// - `header`: Current header of the sending device
+    /// - `request_id`: Recovery request the veto must target
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::InvalidVetoSignature` if the header belongs to another
+    ///   device or the signature does not match
+    /// - `PqrrError::UnauthorizedVeto` if the sending device is Revoked;
+    ///   Degraded devices keep their veto right
+    pub fn verify(
+        &self,
+        verifying_key: &VetoVerifyingKey,
+        header: &DeviceHeader,
+        request_id: &str,
+    ) -> Result<()> {
+        if header.device_id != self.device_id {
+            return Err(PqrrError::invalid_veto_signature(format!(
+                "{:?}",
+                self.device_id
+            )));
+        }
+
+        if !header.status.can_veto() {
+            return Err(PqrrError::unauthorized_veto(format!(
+                "{:?}",
+                self.device_id
+            )));
+        }
+
+        let valid = match (
+            <&[u8; VETO_SIGNATURE_SIZE]>::try_from(self.signature.as_slice()),
+            VerifyingKey::from_bytes(&verifying_key.0),
+        ) {
+            (Ok(signature), Ok(key)) => key
+                .verify_strict(
+                    &self.canonical_bytes(request_id),
+                    &Signature::from_bytes(signature),
+                )
+                .is_ok(),
+            _ => false,
+        };
+
+        if !valid {
+            return Err(PqrrError::invalid_veto_signature(format!(
+                "{:?}",
+                self.device_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Length-prefixed encoding of every signed field
+    fn canonical_bytes(&self, request_id: &str) -> Vec<u8> {
+        let reason = self.reason.as_deref().map(str::as_bytes);
+        let mut message = Vec::with_capacity(
+            VETO_SIGNING_CONTEXT.len()
+                + 4
+                + request_id.len()
+                + 16
+                + 8
+                + 1
+                + reason.map_or(0, |r| 4 + r.len()),
+        );
+        message.extend_from_slice(VETO_SIGNING_CONTEXT);
+        message.extend_from_slice(&(request_id.len() as u32).to_be_bytes());
+        message.extend_from_slice(request_id.as_bytes());
+        message.extend_from_slice(self.device_id.as_bytes());
+        message.extend_from_slice(&self.timestamp.to_be_bytes());
+        match reason {
+            Some(reason) => {
+                message.push(1);
+                message.extend_from_slice(&(reason.len() as u32).to_be_bytes());
+                message.extend_from_slice(reason);
+            }
+            None => message.push(0),
+        }
+        message
+    }
+}
+
+/// Veto signed for one recovery request, as sent between devices
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::protocol::recovery::{SignedVetoMessage, VetoMessage, VetoSigningKey};
+/// use aeternum_core::models::device::DeviceId;
+///
+/// let signing_key = VetoSigningKey::generate();
+/// let veto = VetoMessage::new(DeviceId::generate(), Some("not me".to_string()));
+/// let signed = veto.to_signed(&signing_key, "rec_1");
+///
+/// let received = SignedVetoMessage::from_bytes(&signed.to_bytes()).unwrap();
+/// assert_eq!(received, signed);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedVetoMessage {
+    /// Recovery request the veto targets
+    pub request_id: String,
+
+    /// Signed veto
+    pub veto: VetoMessage,
+}
+
+impl SignedVetoMessage {
+    /// Serialize for transmission
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).expect("veto serialization cannot fail")
+    }
+
+    /// Deserialize a received veto
+    ///
+    /// Only decodes; call [`verify`](Self::verify) or
+    /// [`RecoveryWindow::add_signed_veto`] before acting on it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::StorageError` if the bytes are malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        decode_bounded(bytes)
+            .map_err(|e| PqrrError::storage_error(format!("Malformed veto message: {}", e)))
+    }
+
+    /// Verify the veto against the sender's header
+    ///
+    /// # Errors
+    ///
+    /// Same as [`VetoMessage::verify`].
+    pub fn verify(&self, verifying_key: &VetoVerifyingKey, header: &DeviceHeader) -> Result<()> {
+        self.veto.verify(verifying_key, header, &self.request_id)
+    }
+}
+
+// ============================================================================
+// Veto Keys
+// ============================================================================
+
+/// Per-device Ed25519 key for signing vetoes
+///
+/// Each device generates its own key and keeps it local; only the
+/// [`VetoVerifyingKey`] is shared, through a [`VetoKeyRegistry`]. The
+/// secret is zeroized on drop.
+pub struct VetoSigningKey(SigningKey);
+
+impl VetoSigningKey {
+    /// Generate a fresh signing key from the OS CSPRNG
+    pub fn generate() -> Self {
+        let mut seed = Zeroizing::new([0u8; 32]);
+        rand::rngs::OsRng.fill_bytes(seed.as_mut());
+        Self::from_bytes(&seed)
+    }
+
+    /// Restore a signing key from its 32-byte secret
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        Self(SigningKey::from_bytes(bytes))
+    }
+
+    /// Export the 32-byte secret for device-local storage
+    pub fn to_bytes(&self) -> Zeroizing<[u8; 32]> {
+        Zeroizing::new(self.0.to_bytes())
+    }
+
+    /// Public half to register with the other devices
+    pub fn verifying_key(&self) -> VetoVerifyingKey {
+        VetoVerifyingKey(self.0.verifying_key().to_bytes())
+    }
+}
+
+impl std::fmt::Debug for VetoSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("VetoSigningKey")
+            .field(&self.verifying_key())
+            .finish()
+    }
+}
+
+/// Public key that verifies one device's vetoes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VetoVerifyingKey(pub [u8; 32]);
+
+impl VetoVerifyingKey {
+    /// Raw Ed25519 public key bytes
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+/// Veto public keys registered for the user's devices
+///
+/// A device registers its [`VetoVerifyingKey`] once, when it joins. A
+/// registration cannot be replaced by a different key, so a peer cannot
+/// take over another device's veto right by re-registering it.
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::protocol::recovery::{VetoKeyRegistry, VetoSigningKey};
+/// use aeternum_core::models::device::DeviceId;
+///
+/// let device_id = DeviceId::generate();
+/// let mut registry = VetoKeyRegistry::new();
+/// registry.register(device_id, VetoSigningKey::generate().verifying_key()).unwrap();
+///
+/// assert!(registry.get(&device_id).is_some());
+/// assert!(registry.register(device_id, VetoSigningKey::generate().verifying_key()).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VetoKeyRegistry {
+    keys: HashMap<DeviceId, VetoVerifyingKey>,
+}
+
+impl VetoKeyRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the veto key of a device
+    ///
+    /// Registering the same key again is a no-op.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::InvalidVetoSignature` if a different key is
+    /// already registered for `device_id`.
+    pub fn register(&mut self, device_id: DeviceId, key: VetoVerifyingKey) -> Result<()> {
+        match self.keys.get(&device_id) {
+            Some(existing) if *existing != key => Err(PqrrError::invalid_veto_signature(format!(
+                "{:?}",
+                device_id
+            ))),
+            Some(_) => Ok(()),
+            None => {
+                self.keys.insert(device_id, key);
+                Ok(())
+            }
         }
     }
+
+    /// Registered key of a device, if any
+    pub fn get(&self, device_id: &DeviceId) -> Option<&VetoVerifyingKey> {
+        self.keys.get(device_id)
+    }
+
+    /// Drop a device's registration
+    pub fn remove(&mut self, device_id: &DeviceId) -> Option<VetoVerifyingKey> {
+        self.keys.remove(device_id)
+    }
 }
 
 // ============================================================================
@@ -158,6 +507,10 @@ pub struct RecoveryWindow {
 
     /// Received veto signals
     pub vetoes: Vec<VetoMessage>,
+
+    /// Latest wall-clock time observed while the window was checked
+    /// (Unix milliseconds)
+    pub last_observed_ms: u64,
 }
 
 impl RecoveryWindow {
@@ -198,12 +551,57 @@ impl RecoveryWindow {
             end_time,
             initiator_role,
             vetoes: Vec::new(),
+            last_observed_ms: start_time,
         }
     }
 
+    /// Check if the veto window is still open, reading the time from `time`
+    ///
+    /// Records the reading as the latest observation. If the wall clock
+    /// reads earlier than the latest observation by more than the ±5min
+    /// drift tolerance, the clock was moved backward and the window is
+    /// treated as open (fail-closed) without recording the reading.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::protocol::recovery::{RecoveryWindow, RecoveryRequestId, VETO_WINDOW_MS};
+    /// use aeternum_core::protocol::time::MockTimeSource;
+    /// use aeternum_core::models::device::Role;
+    ///
+    /// let time = MockTimeSource::new(0);
+    /// let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 0, Role::Authorized);
+    /// assert!(window.is_open_with(&time));
+    ///
+    /// time.advance(2 * VETO_WINDOW_MS);
+    /// assert!(!window.is_open_with(&time));
+    ///
+    /// // Setting the clock back does not close the window again
+    /// time.set_now_ms(VETO_WINDOW_MS + 3_600_000);
+    /// assert!(window.is_open_with(&time));
+    /// ```
+    pub fn is_open_with(&mut self, time: &dyn TimeSource) -> bool {
+        let now = time.now_ms();
+        if is_clock_rollback(self.last_observed_ms, now) {
+            eprintln!(
+                "[Recovery] Wall clock moved backward: observed={}, now={}; keeping window open",
+                self.last_observed_ms, now
+            );
+            return true;
+        }
+
+        self.last_observed_ms = self.last_observed_ms.max(now);
+        !self.is_window_expired(now)
+    }
+
     /// Check if current time is within veto window
     ///
-    /// Uses time drift tolerance (±5min) for boundary checks.
+    /// Uses time drift tolerance (±5min) for boundary checks: the window
+    /// covers `[start_time - tolerance, end_time + tolerance)`. Both bounds
+    /// saturate, so a `start_time` below the tolerance extends the window
+    /// down to 0, and a window ending near `u64::MAX` is cut off there.
+    /// Any `current_time` earlier than the window, however far, is simply
+    /// outside it.
     ///
     /// # Arguments
     ///
@@ -227,6 +625,9 @@ impl RecoveryWindow {
     ///
     /// Note: Due to time drift tolerance, the window will only be
     /// reported as expired when current_time >= end_time + tolerance.
+    /// A `current_time` before `start_time` is never expired. The bound
+    /// saturates at `u64::MAX`, so a window ending there only expires at
+    /// `current_time == u64::MAX`.
     ///
     /// # Arguments
     ///
@@ -260,35 +661,171 @@ impl RecoveryWindow {
         self.vetoes.len()
     }
 
-    /// Add a veto signal
+    /// Add a veto signal without any checks
+    ///
+    /// Internal use only: this neither verifies the sender nor
+    /// deduplicates, so callers handling vetoes from the network must use
+    /// [`add_signed_veto`](Self::add_signed_veto) instead.
+    ///
+    /// # Arguments
+    ///
+    /// - `veto`: Veto message to add
+    pub fn add_veto(&mut self, veto: VetoMessage) {
+        self.vetoes.push(veto);
+    }
+
+    /// Add a veto signal from an active device
+    ///
+    /// Rejects vetoes from devices outside `active_devices` (revoked or
+    /// unknown), so they cannot block a legitimate recovery. A second veto
+    /// from a device that already vetoed is ignored, so each device is
+    /// counted once. This checks membership only; vetoes received from
+    /// other devices must also pass [`add_signed_veto`](Self::add_signed_veto).
     ///
     /// # Arguments
     ///
     /// - `veto`: Veto message to add
+    /// - `active_devices`: Devices currently allowed to veto
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::UnauthorizedVeto` if the sender is not active.
     ///
     /// # Example
     ///
     /// ```
     /// use aeternum_core::protocol::recovery::{RecoveryWindow, RecoveryRequestId, VetoMessage};
     /// use aeternum_core::models::device::{DeviceId, Role};
+    /// use std::collections::HashSet;
     ///
-    /// let request_id = RecoveryRequestId::generate();
-    /// let mut window = RecoveryWindow::new(
-    ///     request_id.clone(),
-    ///     1000,
-    ///     Role::Authorized
-    /// );
+    /// let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+    /// let device_id = DeviceId::generate();
+    /// let active: HashSet<DeviceId> = [device_id].into_iter().collect();
+    ///
+    /// window.try_add_veto(VetoMessage::new(device_id, None), &active).unwrap();
+    /// window.try_add_veto(VetoMessage::new(device_id, None), &active).unwrap();
+    ///
+    /// assert_eq!(window.veto_count(), 1);
+    /// ```
+    pub fn try_add_veto(
+        &mut self,
+        veto: VetoMessage,
+        active_devices: &HashSet<DeviceId>,
+    ) -> Result<()> {
+        if !active_devices.contains(&veto.device_id) {
+            return Err(PqrrError::unauthorized_veto(format!(
+                "{:?}",
+                veto.device_id
+            )));
+        }
+
+        if self.vetoes.iter().any(|v| v.device_id == veto.device_id) {
+            return Ok(());
+        }
+
+        self.add_veto(veto);
+        Ok(())
+    }
+
+    /// Add a signed veto after verifying it
+    ///
+    /// Verifies the signature against the sender's registered key, this
+    /// window's request ID and the sender's current header, so forged
+    /// vetoes, vetoes replayed from another request, and vetoes from
+    /// non-Active devices are rejected. A second veto from the same device
+    /// is accepted but not counted twice.
+    ///
+    /// # Arguments
+    ///
+    /// - `veto`: Signed veto message
+    /// - `header`: Current header of the sending device
+    /// - `registry`: Registered veto keys of the user's devices
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::InvalidVetoSignature` if verification fails
+    /// - `PqrrError::UnauthorizedVeto` if the sender is Revoked or has no
+    ///   registered veto key
+    pub fn add_verified_veto(
+        &mut self,
+        veto: VetoMessage,
+        header: &DeviceHeader,
+        registry: &VetoKeyRegistry,
+    ) -> Result<()> {
+        let verifying_key = registry
+            .get(&veto.device_id)
+            .ok_or_else(|| PqrrError::unauthorized_veto(format!("{:?}", veto.device_id)))?;
+        veto.verify(verifying_key, header, self.request_id.as_str())?;
+
+        if self.vetoes.iter().any(|v| v.device_id == veto.device_id) {
+            return Ok(());
+        }
+
+        self.add_veto(veto);
+        Ok(())
+    }
+
+    /// Add a veto received from another device
+    ///
+    /// Rejects a veto signed for another recovery request, then verifies it
+    /// like [`add_verified_veto`](Self::add_verified_veto). Only verified
+    /// vetoes are counted; each device is counted once.
+    ///
+    /// # Arguments
+    ///
+    /// - `signed`: Received veto
+    /// - `header`: Current header of the sending device
+    /// - `registry`: Registered veto keys of the user's devices
+    ///
+    /// # Errors
+    ///
+    /// - `PqrrError::InvalidVetoSignature` if the request ID or signature
+    ///   does not match
+    /// - `PqrrError::UnauthorizedVeto` if the sender is Revoked or has no
+    ///   registered veto key
+    ///
+    /// # Example
     ///
+    /// ```
+    /// use aeternum_core::protocol::recovery::{
+    ///     RecoveryWindow, RecoveryRequestId, VetoKeyRegistry, VetoMessage, VetoSigningKey,
+    /// };
+    /// use aeternum_core::crypto::kem::{KyberCipherText, KyberPublicKeyBytes};
+    /// use aeternum_core::models::device::{DeviceHeader, DeviceId, Role};
+    /// use aeternum_core::models::epoch::CryptoEpoch;
+    ///
+    /// let signing_key = VetoSigningKey::generate();
     /// let device_id = DeviceId::generate();
-    /// let veto = VetoMessage::new(device_id, Some("Suspicious activity".to_string()));
+    /// let mut registry = VetoKeyRegistry::new();
+    /// registry.register(device_id, signing_key.verifying_key()).unwrap();
+    /// let header = DeviceHeader::new(
+    ///     device_id,
+    ///     CryptoEpoch::initial(),
+    ///     KyberPublicKeyBytes([0u8; 1568]),
+    ///     KyberCipherText([0u8; 1568]),
+    /// );
+    /// let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
     ///
-    /// window.add_veto(veto);
+    /// let signed = VetoMessage::new(device_id, None).to_signed(&signing_key, window.request_id.as_str());
+    /// window.add_signed_veto(signed.clone(), &header, &registry).unwrap();
+    /// window.add_signed_veto(signed, &header, &registry).unwrap();
     ///
-    /// assert!(window.is_vetoed());
     /// assert_eq!(window.veto_count(), 1);
     /// ```
-    pub fn add_veto(&mut self, veto: VetoMessage) {
-        self.vetoes.push(veto);
+    pub fn add_signed_veto(
+        &mut self,
+        signed: SignedVetoMessage,
+        header: &DeviceHeader,
+        registry: &VetoKeyRegistry,
+    ) -> Result<()> {
+        if signed.request_id != self.request_id.as_str() {
+            return Err(PqrrError::invalid_veto_signature(format!(
+                "{:?}",
+                signed.veto.device_id
+            )));
+        }
+
+        self.add_verified_veto(signed.veto, header, registry)
     }
 
     /// Check if recovery can complete
@@ -308,9 +845,48 @@ impl RecoveryWindow {
         self.is_window_expired(current_time) && !self.is_vetoed()
     }
 
+    /// Check if recovery can complete, reading the time from `clock`
+    ///
+    /// Unlike [`can_complete`](Self::can_complete), this also rejects a clock
+    /// that reads earlier than the window start, the latest veto or the
+    /// latest observation (beyond the ±5min drift tolerance): the device
+    /// clock moved backward, so the elapsed time cannot be trusted.
+    ///
+    /// # Arguments
+    ///
+    /// - `clock`: Time source
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if recovery can complete, `Ok(false)` if it must keep waiting
+    /// or has been vetoed
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::ClockRegression` if the clock moved backward.
+    pub fn can_complete_with_clock(&self, clock: &dyn TimeSource) -> Result<bool> {
+        let now = clock.now_ms();
+        let recorded = self
+            .vetoes
+            .iter()
+            .map(|veto| veto.timestamp)
+            .fold(self.start_time.max(self.last_observed_ms), u64::max);
+
+        if is_clock_rollback(recorded, now) {
+            return Err(PqrrError::clock_regression(recorded, now));
+        }
+
+        Ok(self.can_complete(now))
+    }
+
     /// Get remaining time in window (milliseconds)
     ///
-    /// Returns 0 if window has expired.
+    /// Returns 0 if window has expired. The result never exceeds
+    /// `VETO_WINDOW_MS`: a `current_time` before `start_time` (e.g. a clock
+    /// that is behind) reports the full window, not more.
+    ///
+    /// Unlike [`is_window_expired`](Self::is_window_expired), no drift
+    /// tolerance is applied; this is the time to show the user.
     ///
     /// # Arguments
     ///
@@ -318,12 +894,12 @@ impl RecoveryWindow {
     ///
     /// # Returns
     ///
-    /// Remaining milliseconds in veto window, or 0 if expired
+    /// Remaining milliseconds in veto window (at most `VETO_WINDOW_MS`), or
+    /// 0 if expired
     pub fn remaining_time(&self, current_time: u64) -> u64 {
-        if current_time >= self.end_time {
-            return 0;
-        }
-        self.end_time.saturating_sub(current_time)
+        self.end_time
+            .saturating_sub(current_time)
+            .min(VETO_WINDOW_MS)
     }
 }
 
@@ -365,16 +941,537 @@ pub fn check_veto_supremacy(window: &RecoveryWindow, current_time: u64) -> Resul
     Ok(())
 }
 
+// ============================================================================
+// Recovery Promotion (Invariant #3 Escalation Path)
+// ============================================================================
+
+/// Vault access obtained through cold recovery
+///
+/// Holds the DEK unwrapped with the RecoveryKey. The RECOVERY role may
+/// decrypt with it but may not rotate keys (Invariant #3); regaining
+/// management authority goes through [`promote_recovery`].
+#[derive(Debug)]
+pub struct RecoveredVault {
+    /// Device that performed the cold recovery
+    pub device_id: DeviceId,
+
+    /// Data encryption key recovered with the RecoveryKey (zeroized on drop)
+    pub dek: DataEncryptionKey,
+}
+
+impl RecoveredVault {
+    /// Unwrap the DEK from the recovering device's header
+    ///
+    /// # Arguments
+    ///
+    /// - `header`: Header of the recovering device (the shadow anchor for
+    ///   cold recovery)
+    /// - `secret_key`: Kyber secret key matching `header.public_key`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::HeaderIncomplete` if the header holds no wrapped
+    /// DEK or it does not unwrap with `secret_key` (Invariant #2).
+    pub fn unwrap_from_header(
+        header: &DeviceHeader,
+        secret_key: &KyberSecretKeyBytes,
+    ) -> Result<Self> {
+        let wrapped = header.wrapped_dek().ok_or_else(|| {
+            PqrrError::header_incomplete(
+                format!("{:?}", header.device_id),
+                "header holds no wrapped DEK".to_string(),
+            )
+        })?;
+        let dek = unwrap_dek(secret_key, &wrapped).map_err(|e| {
+            PqrrError::header_incomplete(
+                format!("{:?}", header.device_id),
+                format!("DEK unwrap failed: {}", e),
+            )
+        })?;
+
+        Ok(Self {
+            device_id: header.device_id,
+            dek,
+        })
+    }
+}
+
+/// Pending promotion of a recovered vault to a new AUTHORIZED device
+///
+/// Created by [`promote_recovery`]. Vetoes received while the 48h window
+/// is open are added to `window`; [`finalize_promotion`] only succeeds once
+/// the window has elapsed without any.
+#[derive(Debug)]
+pub struct PromotionRequest {
+    /// Veto window of the promotion
+    pub window: RecoveryWindow,
+
+    /// Device ID the new AUTHORIZED device will be registered under
+    pub new_device_id: DeviceId,
+
+    /// Kyber public key of the new device
+    pub new_public_key: KyberPublicKeyBytes,
+
+    /// Recovered vault access, dropped (and zeroized) with the request
+    recovered: RecoveredVault,
+}
+
+impl PromotionRequest {
+    /// Device that performed the cold recovery
+    pub fn recovery_device_id(&self) -> DeviceId {
+        self.recovered.device_id
+    }
+}
+
+/// Request promotion of a recovered vault to a new AUTHORIZED device
+///
+/// A RECOVERY device cannot rotate keys (Invariant #3), so after cold
+/// recovery it opens a regular recovery request instead: the state machine
+/// enters `RecoveryInitiated` and the 48h veto window starts. Nothing is
+/// registered or rotated until [`finalize_promotion`].
+///
+/// # Arguments
+///
+/// - `state_machine`: PQRR state machine (must be Idle)
+/// - `recovered`: Vault access obtained through cold recovery
+/// - `new_device_keypair`: Key pair of the device to promote
+///
+/// # Errors
+///
+/// - `PqrrError::InvalidStateTransition` if the state machine is not Idle
+/// - `PqrrError::OperationInProgress` if another operation is running
+/// - `PqrrError::RecoveryRateLimited` if recovery was initiated too often
+/// - `PqrrError::ClockUnavailable` if the system clock cannot be read
+pub fn promote_recovery(
+    state_machine: &mut PqrrStateMachine,
+    recovered: RecoveredVault,
+    new_device_keypair: &KyberKeyPair,
+) -> Result<PromotionRequest> {
+    let window = RecoveryWindow::new(
+        RecoveryRequestId::generate(),
+        current_timestamp_ms()?,
+        Role::Recovery,
+    );
+
+    state_machine.transition_to_recovery_internal(
+        window.request_id.to_string(),
+        window.start_time,
+        Role::Recovery,
+    )?;
+
+    eprintln!(
+        "[Recovery] Promotion requested: request={}, recovery_device={:?}",
+        window.request_id.as_str(),
+        recovered.device_id
+    );
+
+    Ok(PromotionRequest {
+        window,
+        new_device_id: DeviceId::generate(),
+        new_public_key: new_device_keypair.public.clone(),
+        recovered,
+    })
+}
+
+/// Finalize a promotion after its veto window
+///
+/// Gated by [`check_veto_supremacy`] (Invariant #4): any veto terminates
+/// the promotion. A call made while the window is still open changes
+/// nothing and can be repeated later. Once the window has elapsed, the recovery completes under
+/// the new device's AUTHORIZED identity, the device is registered, and the
+/// epoch is rotated so every active device gets a fresh header.
+///
+/// # Arguments
+///
+/// - `state_machine`: PQRR state machine (must be in the promotion's recovery)
+/// - `request`: Pending promotion
+/// - `vault_path`: Path to vault file
+/// - `new_epoch`: Epoch to rotate to
+/// - `current_time`: Current time (Unix milliseconds)
+///
+/// # Returns
+///
+/// The device ID of the newly AUTHORIZED device
+///
+/// # Errors
+///
+/// - `PqrrError::Vetoed` if any veto was received (Invariant #4)
+/// - `PqrrError::InvalidStateTransition` if the window is still open or the
+///   state machine is not in this promotion's recovery
+/// - Any error from device registration or the epoch upgrade
+pub fn finalize_promotion(
+    state_machine: &mut PqrrStateMachine,
+    request: &PromotionRequest,
+    vault_path: impl AsRef<Path>,
+    new_epoch: CryptoEpoch,
+    current_time: u64,
+) -> Result<DeviceId> {
+    // Invariant #4: Veto Supremacy. A veto ends the promotion's recovery.
+    if let Err(e) = check_veto_supremacy(&request.window, current_time) {
+        let in_progress = state_machine
+            .recovery_context()
+            .is_some_and(|context| context.request_id == request.window.request_id.as_str());
+        if let (true, Some(veto)) = (in_progress, request.window.vetoes.first()) {
+            state_machine.record_veto_internal(
+                request.window.request_id.as_str(),
+                veto.device_id.to_string(),
+            )?;
+        }
+        return Err(e);
+    }
+
+    if !request.window.is_window_expired(current_time) {
+        return Err(PqrrError::invalid_transition(
+            state_machine.state().as_str().to_string(),
+            "Idle".to_string(),
+            "veto window still open".to_string(),
+        ));
+    }
+
+    let in_progress = state_machine
+        .recovery_context()
+        .is_some_and(|context| context.request_id == request.window.request_id.as_str());
+    if !in_progress {
+        return Err(PqrrError::invalid_transition(
+            state_machine.state().as_str().to_string(),
+            "Idle".to_string(),
+            format!(
+                "promotion {} is not in progress",
+                request.window.request_id.as_str()
+            ),
+        ));
+    }
+
+    // From here on the new device acts as AUTHORIZED (Invariant #3)
+    state_machine.complete_recovery_internal(Role::Authorized, current_time)?;
+    register_device(
+        state_machine,
+        request.new_device_id,
+        request.new_public_key.clone(),
+        Role::Authorized,
+    )?;
+    EpochUpgradeCoordinator::new(state_machine)
+        .with_current_dek(&request.recovered.dek)
+        .execute_epoch_upgrade(vault_path, new_epoch, Role::Authorized)?;
+
+    eprintln!(
+        "[Recovery] Promotion finalized: request={}, new_device={:?}",
+        request.window.request_id.as_str(),
+        request.new_device_id
+    );
+
+    Ok(request.new_device_id)
+}
+
+// ============================================================================
+// Recovery Rate Limiting
+// ============================================================================
+
+/// Sliding window over which recovery initiations are counted (7 days)
+pub const RECOVERY_ATTEMPT_WINDOW_MS: u64 = 604_800_000;
+
+/// Lockout after the first vetoed recovery (24 hours)
+pub const RECOVERY_LOCKOUT_BASE_MS: u64 = 86_400_000;
+
+/// Upper bound of the veto lockout (30 days)
+pub const RECOVERY_LOCKOUT_MAX_MS: u64 = 2_592_000_000;
+
+/// Limits on recovery initiation
+///
+/// Every initiation opens a 48h window and prompts every device for a
+/// veto, so an attacker working through candidate recovery phrases must
+/// not be able to start attempts back-to-back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryRateLimitPolicy {
+    /// Recovery windows that may be open at the same time
+    pub max_active: u32,
+    /// Weighted initiations allowed per `attempt_window_ms`
+    pub max_initiations: u32,
+    /// Sliding window over which initiations are counted
+    pub attempt_window_ms: u64,
+    /// Weight of a vetoed attempt; every other attempt weighs 1
+    pub veto_weight: u32,
+    /// Lockout after a vetoed attempt, doubled for each consecutive veto
+    pub lockout_base_ms: u64,
+    /// Upper bound of the lockout
+    pub lockout_max_ms: u64,
+}
+
+impl Default for RecoveryRateLimitPolicy {
+    fn default() -> Self {
+        Self {
+            max_active: 1,
+            max_initiations: 3,
+            attempt_window_ms: RECOVERY_ATTEMPT_WINDOW_MS,
+            veto_weight: 2,
+            lockout_base_ms: RECOVERY_LOCKOUT_BASE_MS,
+            lockout_max_ms: RECOVERY_LOCKOUT_MAX_MS,
+        }
+    }
+}
+
+/// How a recorded recovery attempt ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AttemptOutcome {
+    /// Window opened and not yet concluded
+    Open,
+    /// Window elapsed without a veto and the recovery completed
+    Completed,
+    /// Recovery was abandoned without a veto
+    Abandoned,
+    /// At least one device vetoed the recovery
+    Vetoed,
+}
+
+/// One recorded recovery initiation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryAttempt {
+    /// Recovery request ID
+    pub request_id: String,
+    /// Initiation time (Unix milliseconds)
+    pub started_ms: u64,
+    /// How the attempt ended
+    pub outcome: AttemptOutcome,
+}
+
+impl RecoveryAttempt {
+    /// Whether the attempt still holds an open veto window at `now_ms`
+    fn is_active(&self, now_ms: u64) -> bool {
+        self.outcome == AttemptOutcome::Open
+            && now_ms < self.started_ms.saturating_add(VETO_WINDOW_MS)
+    }
+}
+
+/// Recovery initiations of one vault, for rate limiting
+///
+/// Consulted by
+/// [`PqrrStateMachine::transition_to_recovery_internal`], which rejects an
+/// initiation with `PqrrError::RecoveryRateLimited` when:
+///
+/// - [`max_active`](RecoveryRateLimitPolicy::max_active) windows are open,
+/// - the weighted number of initiations in the last
+///   [`attempt_window_ms`](RecoveryRateLimitPolicy::attempt_window_ms)
+///   reached [`max_initiations`](RecoveryRateLimitPolicy::max_initiations)
+///   (vetoed attempts weigh
+///   [`veto_weight`](RecoveryRateLimitPolicy::veto_weight)), or
+/// - the vault is locked out after `n` consecutive vetoed attempts, for
+///   `lockout_base_ms * 2^(n-1)` from the last veto.
+///
+/// The state machine does not snapshot the tracker; it is persisted on its
+/// own with [`save`](Self::save) and [`load`](Self::load) so a restart
+/// cannot reset the limits.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RecoveryAttemptTracker {
+    /// Limits in force (configuration, not persisted)
+    #[serde(skip)]
+    policy: RecoveryRateLimitPolicy,
+    /// Initiations within the attempt window, oldest first
+    attempts: Vec<RecoveryAttempt>,
+    /// Vetoed attempts since the last completed recovery
+    consecutive_vetoes: u32,
+    /// End of the current lockout (Unix milliseconds, 0 if none)
+    locked_until_ms: u64,
+}
+
+impl RecoveryAttemptTracker {
+    /// Create an empty tracker enforcing `policy`
+    pub fn new(policy: RecoveryRateLimitPolicy) -> Self {
+        Self {
+            policy,
+            ..Self::default()
+        }
+    }
+
+    /// Limits in force
+    pub fn policy(&self) -> RecoveryRateLimitPolicy {
+        self.policy
+    }
+
+    /// Recorded initiations, oldest first
+    pub fn attempts(&self) -> &[RecoveryAttempt] {
+        &self.attempts
+    }
+
+    /// Vetoed attempts since the last completed recovery
+    pub fn consecutive_vetoes(&self) -> u32 {
+        self.consecutive_vetoes
+    }
+
+    /// Check whether a recovery may be initiated at `now_ms`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::RecoveryRateLimited` with the time until every
+    /// limit is satisfied.
+    pub fn check(&self, now_ms: u64) -> Result<()> {
+        let retry_after_ms = self
+            .lockout_remaining(now_ms)
+            .max(self.active_remaining(now_ms))
+            .max(self.initiations_remaining(now_ms));
+
+        if retry_after_ms > 0 {
+            return Err(PqrrError::recovery_rate_limited(retry_after_ms));
+        }
+        Ok(())
+    }
+
+    /// Record an initiation at `now_ms`
+    pub fn record_initiation(&mut self, request_id: &str, now_ms: u64) {
+        self.prune(now_ms);
+        self.attempts.push(RecoveryAttempt {
+            request_id: request_id.to_string(),
+            started_ms: now_ms,
+            outcome: AttemptOutcome::Open,
+        });
+    }
+
+    /// Record how the attempt `request_id` ended at `now_ms`
+    ///
+    /// A veto extends the lockout; a completed recovery clears the veto
+    /// streak. Unknown request IDs only affect the streak.
+    pub fn record_outcome(&mut self, request_id: &str, outcome: AttemptOutcome, now_ms: u64) {
+        if let Some(attempt) = self
+            .attempts
+            .iter_mut()
+            .rev()
+            .find(|attempt| attempt.request_id == request_id)
+        {
+            attempt.outcome = outcome;
+        }
+
+        match outcome {
+            AttemptOutcome::Vetoed => {
+                self.consecutive_vetoes = self.consecutive_vetoes.saturating_add(1);
+                let shift = (self.consecutive_vetoes - 1).min(63);
+                let lockout = self
+                    .policy
+                    .lockout_base_ms
+                    .saturating_mul(1u64 << shift)
+                    .min(self.policy.lockout_max_ms);
+                self.locked_until_ms = self.locked_until_ms.max(now_ms.saturating_add(lockout));
+            }
+            AttemptOutcome::Completed => self.consecutive_vetoes = 0,
+            AttemptOutcome::Open | AttemptOutcome::Abandoned => {}
+        }
+    }
+
+    /// Persist the tracker to `path` through `backend`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::StorageError` if the write fails.
+    pub fn save<B: VaultBackend>(&self, backend: &B, path: &Path) -> Result<()> {
+        let bytes = bincode::serialize(self).map_err(|e| {
+            PqrrError::storage_error(format!("Failed to encode recovery attempts: {}", e))
+        })?;
+        backend
+            .atomic_write(path, &bytes)
+            .map_err(|e| PqrrError::storage_error(e.to_string()))
+    }
+
+    /// Load a tracker saved with [`save`](Self::save), enforcing `policy`
+    ///
+    /// A new vault starts from [`new`](Self::new) and saves it right away.
+    /// A missing or corrupt file is an error rather than an empty tracker,
+    /// so deleting it cannot reset the limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::StorageError` if the file cannot be read or
+    /// decoded.
+    pub fn load<B: VaultBackend>(
+        backend: &B,
+        path: &Path,
+        policy: RecoveryRateLimitPolicy,
+    ) -> Result<Self> {
+        let bytes = backend
+            .read(path)
+            .map_err(|e| PqrrError::storage_error(e.to_string()))?;
+        let mut tracker: Self = decode_bounded(&bytes).map_err(|e| {
+            PqrrError::storage_error(format!("Failed to decode recovery attempts: {}", e))
+        })?;
+        tracker.policy = policy;
+        Ok(tracker)
+    }
+
+    /// Drop attempts that left the attempt window and hold no open window
+    fn prune(&mut self, now_ms: u64) {
+        let window = self.policy.attempt_window_ms;
+        self.attempts.retain(|attempt| {
+            now_ms < attempt.started_ms.saturating_add(window) || attempt.is_active(now_ms)
+        });
+    }
+
+    fn lockout_remaining(&self, now_ms: u64) -> u64 {
+        self.locked_until_ms.saturating_sub(now_ms)
+    }
+
+    /// Time until an open window closes and frees a slot
+    fn active_remaining(&self, now_ms: u64) -> u64 {
+        let mut ends: Vec<u64> = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.is_active(now_ms))
+            .map(|attempt| attempt.started_ms.saturating_add(VETO_WINDOW_MS))
+            .collect();
+        let max_active = self.policy.max_active as usize;
+        if ends.len() < max_active {
+            return 0;
+        }
+        ends.sort_unstable();
+        ends[ends.len() - max_active].saturating_sub(now_ms)
+    }
+
+    /// Time until enough initiations age out to allow one more
+    fn initiations_remaining(&self, now_ms: u64) -> u64 {
+        let window = self.policy.attempt_window_ms;
+        let mut counted: Vec<(u64, u64)> = self
+            .attempts
+            .iter()
+            .filter(|attempt| now_ms < attempt.started_ms.saturating_add(window))
+            .map(|attempt| {
+                let weight = match attempt.outcome {
+                    AttemptOutcome::Vetoed => u64::from(self.policy.veto_weight),
+                    _ => 1,
+                };
+                (attempt.started_ms.saturating_add(window), weight)
+            })
+            .collect();
+        counted.sort_unstable();
+
+        let limit = u64::from(self.policy.max_initiations);
+        let mut total: u64 = counted.iter().map(|(_, weight)| weight).sum();
+        if total < limit {
+            return 0;
+        }
+        for (expires_ms, weight) in counted {
+            total -= weight;
+            if total < limit {
+                return expires_ms.saturating_sub(now_ms);
+            }
+        }
+        // max_initiations is 0: recovery is disabled
+        u64::MAX
+    }
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
 /// Get current Unix timestamp in milliseconds
-fn current_timestamp_ms() -> u64 {
-    SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .map(|d| d.as_millis() as u64)
-        .unwrap_or(0)
+///
+/// # Errors
+///
+/// Returns `PqrrError::ClockUnavailable` if the system clock reads before
+/// the Unix epoch.
+fn current_timestamp_ms() -> Result<u64> {
+    SystemTimeSource.try_now_ms()
+}
+
+/// Whether `now_ms` is earlier than `recorded_ms` beyond the drift tolerance
+pub(crate) fn is_clock_rollback(recorded_ms: u64, now_ms: u64) -> bool {
+    now_ms.saturating_add(TIME_DRIFT_TOLERANCE_MS) < recorded_ms
 }
 
 // ============================================================================
@@ -384,7 +1481,12 @@ fn current_timestamp_ms() -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::device::DeviceId;
+    use crate::models::device::{DegradeReason, DeviceId, DeviceStatus};
+    use crate::protocol::pqrr::ProtocolState;
+    use crate::protocol::time::MockTimeSource;
+    use crate::storage::backend::InMemoryBackend;
+    use std::collections::HashMap;
+    use std::sync::Arc;
 
     // ------------------------------------------------------------------------
     // VetoMessage Tests
@@ -559,6 +1661,43 @@ mod tests {
         assert_eq!(window.remaining_time(window.end_time + 1000), 0);
     }
 
+    #[test]
+    fn test_recovery_window_current_time_zero() {
+        let start_time = 1_700_000_000_000;
+        let window =
+            RecoveryWindow::new(RecoveryRequestId::generate(), start_time, Role::Authorized);
+
+        assert!(!window.is_within_window(0));
+        assert!(!window.is_window_expired(0));
+        assert_eq!(window.remaining_time(0), VETO_WINDOW_MS);
+    }
+
+    #[test]
+    fn test_recovery_window_start_time_zero() {
+        let window = RecoveryWindow::new(RecoveryRequestId::generate(), 0, Role::Authorized);
+
+        assert_eq!(window.end_time, VETO_WINDOW_MS);
+        assert!(window.is_within_window(0));
+        assert!(!window.is_window_expired(0));
+        assert_eq!(window.remaining_time(0), VETO_WINDOW_MS);
+        assert!(window.is_window_expired(VETO_WINDOW_MS + TIME_DRIFT_TOLERANCE_MS));
+    }
+
+    #[test]
+    fn test_recovery_window_start_time_near_max() {
+        let start_time = u64::MAX - 10;
+        let window =
+            RecoveryWindow::new(RecoveryRequestId::generate(), start_time, Role::Authorized);
+
+        assert_eq!(window.end_time, u64::MAX);
+        assert!(window.is_within_window(start_time));
+        assert!(!window.is_window_expired(start_time));
+        assert!(!window.is_window_expired(u64::MAX - 1));
+        assert_eq!(window.remaining_time(start_time), 10);
+        assert_eq!(window.remaining_time(0), VETO_WINDOW_MS);
+        assert_eq!(window.remaining_time(u64::MAX), 0);
+    }
+
     // ------------------------------------------------------------------------
     // Invariant #4: Veto Supremacy Tests
     // ------------------------------------------------------------------------
@@ -1025,11 +2164,9 @@ mod tests {
         let start_time = 1000;
         let window = RecoveryWindow::new(request_id, start_time, Role::Authorized);
 
-        // Before start
-        assert_eq!(
-            window.remaining_time(start_time - 100),
-            VETO_WINDOW_MS + 100
-        );
+        // Before start: clamped to the window length
+        assert_eq!(window.remaining_time(start_time - 100), VETO_WINDOW_MS);
+        assert_eq!(window.remaining_time(0), VETO_WINDOW_MS);
 
         // At start
         assert_eq!(window.remaining_time(start_time), VETO_WINDOW_MS);
@@ -1040,4 +2177,800 @@ mod tests {
         // After end
         assert_eq!(window.remaining_time(window.end_time + 1000), 0);
     }
+
+    // ------------------------------------------------------------------------
+    // Clock Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_can_complete_with_clock_advances_past_window() {
+        let start_time = 1_700_000_000_000;
+        let window =
+            RecoveryWindow::new(RecoveryRequestId::generate(), start_time, Role::Authorized);
+        let clock = MockTimeSource::new(start_time);
+
+        assert!(!window.can_complete_with_clock(&clock).unwrap());
+
+        clock.set_now_ms(window.end_time - 1);
+        assert!(!window.can_complete_with_clock(&clock).unwrap());
+
+        clock.set_now_ms(window.end_time + TIME_DRIFT_TOLERANCE_MS);
+        assert!(window.can_complete_with_clock(&clock).unwrap());
+    }
+
+    #[test]
+    fn test_is_open_with_fails_closed_on_clock_rollback() {
+        let start_time = 1_700_000_000_000;
+        let mut window =
+            RecoveryWindow::new(RecoveryRequestId::generate(), start_time, Role::Authorized);
+        let time = MockTimeSource::new(start_time);
+
+        // Window elapses; the observation is recorded
+        time.advance(VETO_WINDOW_MS + 10 * TIME_DRIFT_TOLERANCE_MS);
+        assert!(!window.is_open_with(&time));
+        let observed = window.last_observed_ms;
+        assert_eq!(observed, time.now_ms());
+
+        // Within drift tolerance of the observation: still expired
+        time.set_now_ms(observed - TIME_DRIFT_TOLERANCE_MS);
+        assert!(!window.is_open_with(&time));
+
+        // Clock moved back beyond tolerance, even though still past end_time
+        time.set_now_ms(observed - TIME_DRIFT_TOLERANCE_MS - 1);
+        assert!(window.is_window_expired(time.now_ms()));
+        assert!(window.is_open_with(&time));
+        assert_eq!(window.last_observed_ms, observed);
+        assert!(window.can_complete_with_clock(&time).is_err());
+
+        // Clock restored
+        time.set_now_ms(observed);
+        assert!(!window.is_open_with(&time));
+    }
+
+    #[test]
+    fn test_can_complete_with_clock_respects_veto() {
+        let start_time = 1_700_000_000_000;
+        let mut window =
+            RecoveryWindow::new(RecoveryRequestId::generate(), start_time, Role::Authorized);
+        window.add_veto(VetoMessage::with_timestamp(
+            DeviceId::generate(),
+            None,
+            start_time + 1000,
+        ));
+        let clock = MockTimeSource::new(window.end_time + TIME_DRIFT_TOLERANCE_MS);
+
+        assert!(!window.can_complete_with_clock(&clock).unwrap());
+    }
+
+    #[test]
+    fn test_can_complete_with_clock_rejects_backward_clock() {
+        let start_time = 1_700_000_000_000;
+        let window =
+            RecoveryWindow::new(RecoveryRequestId::generate(), start_time, Role::Authorized);
+
+        // Within drift tolerance: accepted
+        let clock = MockTimeSource::new(start_time - TIME_DRIFT_TOLERANCE_MS);
+        assert!(window.can_complete_with_clock(&clock).is_ok());
+
+        // Beyond tolerance: clock moved backward
+        clock.set_now_ms(start_time - TIME_DRIFT_TOLERANCE_MS - 1);
+        assert_eq!(
+            window.can_complete_with_clock(&clock),
+            Err(PqrrError::clock_regression(
+                start_time,
+                start_time - TIME_DRIFT_TOLERANCE_MS - 1
+            ))
+        );
+    }
+
+    #[test]
+    fn test_can_complete_with_clock_rejects_clock_before_veto() {
+        let start_time = 1_700_000_000_000;
+        let veto_time = start_time + 10 * TIME_DRIFT_TOLERANCE_MS;
+        let mut window =
+            RecoveryWindow::new(RecoveryRequestId::generate(), start_time, Role::Authorized);
+        window.add_veto(VetoMessage::with_timestamp(
+            DeviceId::generate(),
+            None,
+            veto_time,
+        ));
+
+        let clock = MockTimeSource::new(start_time);
+        assert!(matches!(
+            window.can_complete_with_clock(&clock),
+            Err(PqrrError::ClockRegression { recorded_ms, .. }) if recorded_ms == veto_time
+        ));
+    }
+
+    // ------------------------------------------------------------------------
+    // Veto Authentication Tests
+    // ------------------------------------------------------------------------
+
+    fn active_header(device_id: DeviceId) -> DeviceHeader {
+        DeviceHeader::new(
+            device_id,
+            crate::models::epoch::CryptoEpoch::initial(),
+            crate::crypto::kem::KyberPublicKeyBytes([0u8; 1568]),
+            crate::crypto::kem::KyberCipherText([0u8; 1568]),
+        )
+    }
+
+    fn signed_veto(
+        signing_key: &VetoSigningKey,
+        device_id: DeviceId,
+        request_id: &str,
+    ) -> VetoMessage {
+        let mut veto = VetoMessage::with_timestamp(device_id, None, 2000);
+        veto.sign(signing_key, request_id);
+        veto
+    }
+
+    fn registry_with(device_id: DeviceId, signing_key: &VetoSigningKey) -> VetoKeyRegistry {
+        let mut registry = VetoKeyRegistry::new();
+        registry
+            .register(device_id, signing_key.verifying_key())
+            .unwrap();
+        registry
+    }
+
+    #[test]
+    fn test_add_verified_veto_accepts_valid_signature() {
+        let signing_key = VetoSigningKey::from_bytes(&[7u8; 32]);
+        let device_id = DeviceId::generate();
+        let registry = registry_with(device_id, &signing_key);
+        let header = active_header(device_id);
+        let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+
+        let veto = signed_veto(&signing_key, device_id, window.request_id.as_str());
+        assert!(veto
+            .verify(
+                &signing_key.verifying_key(),
+                &header,
+                window.request_id.as_str()
+            )
+            .is_ok());
+
+        window
+            .add_verified_veto(veto.clone(), &header, &registry)
+            .unwrap();
+        window.add_verified_veto(veto, &header, &registry).unwrap();
+        assert_eq!(window.veto_count(), 1);
+    }
+
+    #[test]
+    fn test_add_verified_veto_rejects_forged_signature() {
+        let signing_key = VetoSigningKey::from_bytes(&[7u8; 32]);
+        let device_id = DeviceId::generate();
+        let other_device = DeviceId::generate();
+        let mut registry = registry_with(device_id, &signing_key);
+        registry
+            .register(other_device, VetoSigningKey::generate().verifying_key())
+            .unwrap();
+        let header = active_header(device_id);
+        let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+        let request_id = window.request_id.as_str().to_string();
+
+        // Unsigned
+        let unsigned = VetoMessage::with_timestamp(device_id, None, 2000);
+        // Signed with another device's key
+        let foreign = signed_veto(
+            &VetoSigningKey::from_bytes(&[8u8; 32]),
+            device_id,
+            &request_id,
+        );
+        // Valid signature with a tampered timestamp
+        let mut tampered = signed_veto(&signing_key, device_id, &request_id);
+        tampered.timestamp += 1;
+        // Valid signature presented for another device
+        let mut impersonated = signed_veto(&signing_key, device_id, &request_id);
+        impersonated.device_id = other_device;
+
+        for veto in [unsigned, foreign, tampered] {
+            let result = window.add_verified_veto(veto, &header, &registry);
+            assert!(matches!(
+                result,
+                Err(PqrrError::InvalidVetoSignature { .. })
+            ));
+        }
+        let result = window.add_verified_veto(impersonated, &header, &registry);
+        assert!(matches!(
+            result,
+            Err(PqrrError::InvalidVetoSignature { .. })
+        ));
+        assert_eq!(window.veto_count(), 0);
+    }
+
+    #[test]
+    fn test_add_verified_veto_rejects_replay_to_other_request() {
+        let signing_key = VetoSigningKey::from_bytes(&[7u8; 32]);
+        let device_id = DeviceId::generate();
+        let registry = registry_with(device_id, &signing_key);
+        let header = active_header(device_id);
+        let mut window = RecoveryWindow::new(
+            RecoveryRequestId::from_string("rec_current".to_string()),
+            1000,
+            Role::Authorized,
+        );
+
+        let replayed = signed_veto(&signing_key, device_id, "rec_previous");
+        let result = window.add_verified_veto(replayed, &header, &registry);
+
+        assert!(matches!(
+            result,
+            Err(PqrrError::InvalidVetoSignature { .. })
+        ));
+        assert!(!window.is_vetoed());
+    }
+
+    #[test]
+    fn test_add_verified_veto_rejects_revoked_device() {
+        let signing_key = VetoSigningKey::from_bytes(&[7u8; 32]);
+        let device_id = DeviceId::generate();
+        let registry = registry_with(device_id, &signing_key);
+        let mut header = active_header(device_id);
+        header.status = DeviceStatus::Revoked;
+        let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+
+        let veto = signed_veto(&signing_key, device_id, window.request_id.as_str());
+        let result = window.add_verified_veto(veto, &header, &registry);
+
+        assert!(matches!(result, Err(PqrrError::UnauthorizedVeto { .. })));
+        assert!(!window.is_vetoed());
+    }
+
+    #[test]
+    fn test_add_verified_veto_accepts_degraded_device() {
+        let signing_key = VetoSigningKey::from_bytes(&[7u8; 32]);
+        let device_id = DeviceId::generate();
+        let registry = registry_with(device_id, &signing_key);
+        let mut header = active_header(device_id);
+        header
+            .degrade(DegradeReason::IntegrityCheckFailed, 1500)
+            .unwrap();
+        let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+
+        let veto = signed_veto(&signing_key, device_id, window.request_id.as_str());
+        window.add_verified_veto(veto, &header, &registry).unwrap();
+
+        assert!(window.is_vetoed());
+    }
+
+    // ------------------------------------------------------------------------
+    // SignedVetoMessage Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_signed_veto_round_trips_through_bytes() {
+        let signing_key = VetoSigningKey::from_bytes(&[7u8; 32]);
+        let device_id = DeviceId::generate();
+        let registry = registry_with(device_id, &signing_key);
+        let header = active_header(device_id);
+        let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+
+        let signed = VetoMessage::with_timestamp(device_id, Some("not me".to_string()), 2000)
+            .to_signed(&signing_key, window.request_id.as_str());
+        let received = SignedVetoMessage::from_bytes(&signed.to_bytes()).unwrap();
+
+        assert_eq!(received, signed);
+        assert!(received
+            .verify(&signing_key.verifying_key(), &header)
+            .is_ok());
+        window
+            .add_signed_veto(received.clone(), &header, &registry)
+            .unwrap();
+        window
+            .add_signed_veto(received, &header, &registry)
+            .unwrap();
+        assert_eq!(window.veto_count(), 1);
+        assert!(check_veto_supremacy(&window, 2000).is_err());
+    }
+
+    #[test]
+    fn test_signed_veto_rejects_tampered_reason() {
+        let signing_key = VetoSigningKey::from_bytes(&[7u8; 32]);
+        let device_id = DeviceId::generate();
+        let registry = registry_with(device_id, &signing_key);
+        let header = active_header(device_id);
+        let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+        let request_id = window.request_id.as_str().to_string();
+
+        let mut altered = VetoMessage::with_timestamp(device_id, Some("not me".to_string()), 2000)
+            .to_signed(&signing_key, &request_id);
+        altered.veto.reason = Some("approved".to_string());
+        let mut stripped = VetoMessage::with_timestamp(device_id, Some("not me".to_string()), 2000)
+            .to_signed(&signing_key, &request_id);
+        stripped.veto.reason = None;
+
+        for signed in [altered, stripped] {
+            let result = window.add_signed_veto(signed, &header, &registry);
+            assert!(matches!(
+                result,
+                Err(PqrrError::InvalidVetoSignature { .. })
+            ));
+        }
+        assert!(!window.is_vetoed());
+    }
+
+    #[test]
+    fn test_signed_veto_rejects_wrong_key_and_request() {
+        let signing_key = VetoSigningKey::from_bytes(&[7u8; 32]);
+        let device_id = DeviceId::generate();
+        let registry = registry_with(device_id, &signing_key);
+        let header = active_header(device_id);
+        let mut window = RecoveryWindow::new(
+            RecoveryRequestId::from_string("rec_current".to_string()),
+            1000,
+            Role::Authorized,
+        );
+        let veto = VetoMessage::with_timestamp(device_id, None, 2000);
+
+        let foreign = veto.to_signed(&VetoSigningKey::from_bytes(&[8u8; 32]), "rec_current");
+        let other_request = veto.to_signed(&signing_key, "rec_previous");
+
+        for signed in [foreign, other_request] {
+            let result = window.add_signed_veto(signed, &header, &registry);
+            assert!(matches!(
+                result,
+                Err(PqrrError::InvalidVetoSignature { .. })
+            ));
+        }
+        assert!(!window.is_vetoed());
+    }
+
+    #[test]
+    fn test_signed_veto_rejects_revoked_device() {
+        let signing_key = VetoSigningKey::from_bytes(&[7u8; 32]);
+        let device_id = DeviceId::generate();
+        let registry = registry_with(device_id, &signing_key);
+        let mut header = active_header(device_id);
+        header.status = DeviceStatus::Revoked;
+        let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+
+        let signed = VetoMessage::new(device_id, Some("block".to_string()))
+            .to_signed(&signing_key, window.request_id.as_str());
+        let result = window.add_signed_veto(signed, &header, &registry);
+
+        assert!(matches!(result, Err(PqrrError::UnauthorizedVeto { .. })));
+        assert!(check_veto_supremacy(&window, 2000).is_ok());
+    }
+
+    #[test]
+    fn test_signed_veto_from_bytes_rejects_garbage() {
+        assert!(matches!(
+            SignedVetoMessage::from_bytes(&[0xff; 7]),
+            Err(PqrrError::StorageError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_add_verified_veto_rejects_unregistered_device() {
+        let signing_key = VetoSigningKey::from_bytes(&[7u8; 32]);
+        let device_id = DeviceId::generate();
+        let header = active_header(device_id);
+        let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+
+        let veto = signed_veto(&signing_key, device_id, window.request_id.as_str());
+        let result = window.add_verified_veto(veto, &header, &VetoKeyRegistry::new());
+
+        assert!(matches!(result, Err(PqrrError::UnauthorizedVeto { .. })));
+        assert!(!window.is_vetoed());
+    }
+
+    #[test]
+    fn test_veto_key_registry_refuses_key_replacement() {
+        let signing_key = VetoSigningKey::from_bytes(&[7u8; 32]);
+        let attacker_key = VetoSigningKey::from_bytes(&[8u8; 32]);
+        let device_id = DeviceId::generate();
+        let mut registry = registry_with(device_id, &signing_key);
+
+        assert!(registry
+            .register(device_id, signing_key.verifying_key())
+            .is_ok());
+        assert!(matches!(
+            registry.register(device_id, attacker_key.verifying_key()),
+            Err(PqrrError::InvalidVetoSignature { .. })
+        ));
+        assert_eq!(registry.get(&device_id), Some(&signing_key.verifying_key()));
+    }
+
+    #[test]
+    fn test_veto_signing_key_round_trips_through_bytes() {
+        let signing_key = VetoSigningKey::generate();
+        let restored = VetoSigningKey::from_bytes(&signing_key.to_bytes());
+
+        assert_eq!(restored.verifying_key(), signing_key.verifying_key());
+    }
+
+    // ------------------------------------------------------------------------
+    // try_add_veto() Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_try_add_veto_accepts_active_device() {
+        let device_id = DeviceId::generate();
+        let active: HashSet<DeviceId> = [device_id].into_iter().collect();
+        let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+
+        window
+            .try_add_veto(VetoMessage::new(device_id, None), &active)
+            .unwrap();
+
+        assert!(window.is_vetoed());
+        assert!(check_veto_supremacy(&window, 2000).is_err());
+    }
+
+    #[test]
+    fn test_try_add_veto_rejects_revoked_device() {
+        let active_device = DeviceId::generate();
+        let revoked_device = DeviceId::generate();
+        let active: HashSet<DeviceId> = [active_device].into_iter().collect();
+        let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+
+        let result = window.try_add_veto(
+            VetoMessage::new(revoked_device, Some("block".to_string())),
+            &active,
+        );
+
+        assert!(matches!(result, Err(PqrrError::UnauthorizedVeto { .. })));
+        assert!(!window.is_vetoed());
+        assert!(check_veto_supremacy(&window, 2000).is_ok());
+    }
+
+    #[test]
+    fn test_try_add_veto_deduplicates_by_device() {
+        let device1 = DeviceId::generate();
+        let device2 = DeviceId::generate();
+        let active: HashSet<DeviceId> = [device1, device2].into_iter().collect();
+        let mut window = RecoveryWindow::new(RecoveryRequestId::generate(), 1000, Role::Authorized);
+
+        for _ in 0..3 {
+            window
+                .try_add_veto(VetoMessage::with_timestamp(device1, None, 1500), &active)
+                .unwrap();
+        }
+        window
+            .try_add_veto(VetoMessage::with_timestamp(device2, None, 1600), &active)
+            .unwrap();
+
+        assert_eq!(window.veto_count(), 2);
+        // The first veto from a device is the one kept
+        assert_eq!(window.vetoes[0].timestamp, 1500);
+    }
+
+    // ------------------------------------------------------------------------
+    // Recovery Promotion Tests
+    // ------------------------------------------------------------------------
+
+    /// Create a vault file at epoch 1 and a state machine with one active device,
+    /// plus the vault as cold recovery would unlock it
+    fn setup_promotion(vault_path: &Path) -> (PqrrStateMachine, DeviceId, RecoveredVault) {
+        use crate::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce};
+        use crate::crypto::kem::KyberKEM;
+        use crate::models::epoch::CryptoAlgorithm;
+        use crate::storage::aug::{aup_atomic_commit, aup_prepare, aup_shadow_write};
+
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = AeadCipher::new(&dek)
+            .encrypt(
+                &XChaCha20Nonce::from_bytes(std::array::from_fn(|i| i as u8)),
+                &[0u8; 32],
+                None,
+            )
+            .unwrap();
+        let previous = CryptoEpoch::new(0, CryptoAlgorithm::V1);
+        let prep = aup_prepare(&previous, &encrypted_vk, &dek, b"vault data").unwrap();
+        let shadow = aup_shadow_write(vault_path, &prep).unwrap();
+        aup_atomic_commit(
+            vault_path,
+            shadow,
+            &prep.new_epoch,
+            &mut crate::storage::metadata::InMemoryMetadataStore::default(),
+        )
+        .unwrap();
+
+        let current = CryptoEpoch::new(1, CryptoAlgorithm::V1);
+        let keypair = KyberKEM::generate_keypair();
+        let (_, encrypted_dek) = KyberKEM::encapsulate(&keypair.public).unwrap();
+        let header =
+            DeviceHeader::new(DeviceId::generate(), current, keypair.public, encrypted_dek);
+        let device_id = header.device_id;
+
+        let headers = [(device_id, header)].into_iter().collect();
+        let recovered = RecoveredVault {
+            device_id: DeviceId::shadow_anchor(),
+            dek: DataEncryptionKey::from_bytes(*prep.new_dek.as_bytes()),
+        };
+        (
+            PqrrStateMachine::create(current, headers),
+            device_id,
+            recovered,
+        )
+    }
+
+    #[test]
+    fn test_recovered_vault_unwraps_anchor_header() {
+        use crate::crypto::kem::KyberKEM;
+
+        let keypair = KyberKEM::generate_keypair();
+        let dek = DataEncryptionKey::generate();
+        let anchor = DeviceHeader::with_wrapped_dek(
+            DeviceId::shadow_anchor(),
+            CryptoEpoch::initial(),
+            keypair.public.clone(),
+            dek.wrap_for_device(&keypair.public).unwrap(),
+        );
+
+        let recovered = RecoveredVault::unwrap_from_header(&anchor, &keypair.secret).unwrap();
+        assert!(recovered.device_id.is_shadow_anchor());
+        assert_eq!(recovered.dek.as_bytes(), dek.as_bytes());
+
+        let other = KyberKEM::generate_keypair();
+        assert!(matches!(
+            RecoveredVault::unwrap_from_header(&anchor, &other.secret),
+            Err(PqrrError::HeaderIncomplete { .. })
+        ));
+        assert!(matches!(
+            RecoveredVault::unwrap_from_header(
+                &active_header(DeviceId::generate()),
+                &keypair.secret
+            ),
+            Err(PqrrError::HeaderIncomplete { .. })
+        ));
+    }
+
+    #[test]
+    fn test_promotion_blocked_by_veto() {
+        use crate::crypto::kem::KyberKEM;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, existing_device, recovered) = setup_promotion(&vault_path);
+
+        let keypair = KyberKEM::generate_keypair();
+        let mut request = promote_recovery(&mut sm, recovered, &keypair).unwrap();
+        assert_eq!(sm.state().as_str(), "RecoveryInitiated");
+
+        // The existing device vetoes within the window
+        request
+            .window
+            .add_veto(VetoMessage::new(existing_device, None));
+
+        let new_device_id = request.new_device_id;
+        let after_window = request.window.end_time + TIME_DRIFT_TOLERANCE_MS;
+        let new_epoch = CryptoEpoch::new(2, crate::models::epoch::CryptoAlgorithm::V1);
+        let result = finalize_promotion(&mut sm, &request, &vault_path, new_epoch, after_window);
+
+        assert!(matches!(
+            result,
+            Err(PqrrError::Vetoed { veto_count: 1, .. })
+        ));
+        assert!(!sm.device_headers().contains_key(&new_device_id));
+        assert_eq!(sm.current_epoch().version, 1);
+        // The veto ended the recovery
+        assert_eq!(sm.state().as_str(), "Idle");
+        assert!(sm.recovery_context().is_none());
+    }
+
+    #[test]
+    fn test_promotion_succeeds_after_window() {
+        use crate::crypto::kem::KyberKEM;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, existing_device, recovered) = setup_promotion(&vault_path);
+
+        let keypair = KyberKEM::generate_keypair();
+        let request = promote_recovery(&mut sm, recovered, &keypair).unwrap();
+        let start_time = request.window.start_time;
+        let end_time = request.window.end_time;
+        let new_epoch = CryptoEpoch::new(2, crate::models::epoch::CryptoAlgorithm::V1);
+
+        // Window still open: nothing happens
+        let early = finalize_promotion(&mut sm, &request, &vault_path, new_epoch, start_time + 1);
+        assert!(matches!(
+            early,
+            Err(PqrrError::InvalidStateTransition { .. })
+        ));
+
+        let after_window = end_time + TIME_DRIFT_TOLERANCE_MS;
+        let new_device_id =
+            finalize_promotion(&mut sm, &request, &vault_path, new_epoch, after_window).unwrap();
+
+        assert_eq!(sm.state().as_str(), "Idle");
+        assert_eq!(sm.current_epoch().version, 2);
+        assert!(sm.is_device_active_internal(&new_device_id));
+        assert!(sm.is_device_active_internal(&existing_device));
+        for header in sm.device_headers().values() {
+            assert_eq!(header.epoch.version, 2);
+        }
+    }
+
+    #[test]
+    fn test_recovery_role_cannot_rotate_directly() {
+        use crate::crypto::kem::KyberKEM;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (mut sm, _, recovered) = setup_promotion(&vault_path);
+
+        let keypair = KyberKEM::generate_keypair();
+        let request = promote_recovery(&mut sm, recovered, &keypair).unwrap();
+        assert_ne!(request.recovery_device_id(), request.new_device_id);
+
+        // Invariant #3 still holds for the recovered device
+        let new_epoch = CryptoEpoch::new(2, crate::models::epoch::CryptoAlgorithm::V1);
+        let result = EpochUpgradeCoordinator::new(&mut sm).execute_epoch_upgrade(
+            &vault_path,
+            new_epoch,
+            Role::Recovery,
+        );
+
+        assert!(matches!(result, Err(PqrrError::PermissionDenied { .. })));
+        assert_eq!(sm.current_epoch().version, 1);
+    }
+
+    // ------------------------------------------------------------------------
+    // Recovery Rate Limiting Tests
+    // ------------------------------------------------------------------------
+
+    const HOUR_MS: u64 = 3_600_000;
+
+    fn rate_limited_machine(
+        policy: RecoveryRateLimitPolicy,
+    ) -> (PqrrStateMachine, Arc<MockTimeSource>) {
+        let time = Arc::new(MockTimeSource::new(1_700_000_000_000));
+        let sm = PqrrStateMachine::create_with_time_source(
+            CryptoEpoch::initial(),
+            HashMap::new(),
+            time.clone(),
+        );
+        sm.set_recovery_attempts(RecoveryAttemptTracker::new(policy));
+        (sm, time)
+    }
+
+    /// Initiate a recovery now, optionally veto it, and return to Idle
+    fn attempt(sm: &mut PqrrStateMachine, time: &MockTimeSource, id: &str, veto: bool) {
+        sm.transition_to_recovery_internal(id.to_string(), time.now_ms(), Role::Recovery)
+            .unwrap();
+        if veto {
+            sm.recovery_context_mut()
+                .unwrap()
+                .add_veto("device-a".to_string());
+        }
+        sm.return_to_idle_internal().unwrap();
+    }
+
+    fn retry_after(sm: &mut PqrrStateMachine, time: &MockTimeSource) -> u64 {
+        match sm.transition_to_recovery_internal("next".to_string(), time.now_ms(), Role::Recovery)
+        {
+            Err(PqrrError::RecoveryRateLimited { retry_after_ms }) => retry_after_ms,
+            other => panic!("expected RecoveryRateLimited, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_reached_and_decays() {
+        let (mut sm, time) = rate_limited_machine(RecoveryRateLimitPolicy::default());
+        let first = time.now_ms();
+
+        for i in 0..3 {
+            attempt(&mut sm, &time, &format!("req-{}", i), false);
+            time.advance(HOUR_MS);
+        }
+
+        // Rejected without opening a window or prompting devices
+        assert_eq!(
+            retry_after(&mut sm, &time),
+            first + RECOVERY_ATTEMPT_WINDOW_MS - time.now_ms()
+        );
+        assert!(matches!(sm.state(), ProtocolState::Idle));
+
+        // The oldest initiation leaves the 7-day window
+        time.set_now_ms(first + RECOVERY_ATTEMPT_WINDOW_MS);
+        sm.transition_to_recovery_internal("req-3".to_string(), time.now_ms(), Role::Recovery)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_vetoed_attempt_weighs_more_than_abandoned() {
+        let policy = RecoveryRateLimitPolicy {
+            lockout_base_ms: 0,
+            ..RecoveryRateLimitPolicy::default()
+        };
+
+        // Two abandoned attempts leave room for a third
+        let (mut sm, time) = rate_limited_machine(policy);
+        attempt(&mut sm, &time, "req-0", false);
+        attempt(&mut sm, &time, "req-1", false);
+        assert!(sm.recovery_attempts().check(time.now_ms()).is_ok());
+
+        // A vetoed and an abandoned attempt do not
+        let (mut sm, time) = rate_limited_machine(policy);
+        attempt(&mut sm, &time, "req-0", true);
+        attempt(&mut sm, &time, "req-1", false);
+        assert!(retry_after(&mut sm, &time) > 0);
+    }
+
+    #[test]
+    fn test_lockout_escalates_after_consecutive_vetoes() {
+        let policy = RecoveryRateLimitPolicy {
+            max_initiations: 100,
+            ..RecoveryRateLimitPolicy::default()
+        };
+        let (mut sm, time) = rate_limited_machine(policy);
+
+        for streak in 1..=3u32 {
+            attempt(&mut sm, &time, &format!("req-{}", streak), true);
+            let lockout = RECOVERY_LOCKOUT_BASE_MS << (streak - 1);
+            assert_eq!(retry_after(&mut sm, &time), lockout);
+            assert_eq!(sm.recovery_attempts().consecutive_vetoes(), streak);
+            time.advance(lockout);
+        }
+
+        // A completed recovery ends the streak
+        let start = time.now_ms();
+        sm.transition_to_recovery_internal("req-ok".to_string(), start, Role::Recovery)
+            .unwrap();
+        time.advance(VETO_WINDOW_MS);
+        sm.complete_recovery(Role::Authorized).unwrap();
+        assert_eq!(sm.recovery_attempts().consecutive_vetoes(), 0);
+
+        attempt(&mut sm, &time, "req-4", true);
+        assert_eq!(retry_after(&mut sm, &time), RECOVERY_LOCKOUT_BASE_MS);
+    }
+
+    #[test]
+    fn test_lockout_is_capped() {
+        let mut tracker = RecoveryAttemptTracker::new(RecoveryRateLimitPolicy::default());
+        for i in 0..40 {
+            tracker.record_outcome(&format!("req-{}", i), AttemptOutcome::Vetoed, 0);
+        }
+        assert_eq!(
+            tracker.check(0),
+            Err(PqrrError::recovery_rate_limited(RECOVERY_LOCKOUT_MAX_MS))
+        );
+    }
+
+    #[test]
+    fn test_single_active_window() {
+        let mut tracker = RecoveryAttemptTracker::new(RecoveryRateLimitPolicy::default());
+        tracker.record_initiation("req-0", 0);
+
+        assert_eq!(
+            tracker.check(HOUR_MS),
+            Err(PqrrError::recovery_rate_limited(VETO_WINDOW_MS - HOUR_MS))
+        );
+        assert!(tracker.check(VETO_WINDOW_MS).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit_survives_restart() {
+        let backend = InMemoryBackend::new();
+        let path = Path::new("/vault/recovery_attempts.bin");
+        let policy = RecoveryRateLimitPolicy::default();
+
+        let (mut sm, time) = rate_limited_machine(policy);
+        attempt(&mut sm, &time, "req-0", true);
+        sm.recovery_attempts().save(&backend, path).unwrap();
+        let snapshot = sm.snapshot();
+        drop(sm);
+
+        // Restored state machine starts without limits until the tracker is loaded
+        let mut restored = PqrrStateMachine::restore(&snapshot).unwrap();
+        restored.set_time_source(time.clone());
+        let loaded = RecoveryAttemptTracker::load(&backend, path, policy).unwrap();
+        assert_eq!(loaded.attempts().len(), 1);
+        assert_eq!(loaded.attempts()[0].outcome, AttemptOutcome::Vetoed);
+        restored.set_recovery_attempts(loaded);
+
+        assert_eq!(retry_after(&mut restored, &time), RECOVERY_LOCKOUT_BASE_MS);
+
+        // A missing or corrupt file does not silently reset the limits
+        assert!(matches!(
+            RecoveryAttemptTracker::load(&backend, Path::new("/vault/missing"), policy),
+            Err(PqrrError::StorageError { .. })
+        ));
+        backend.atomic_write(path, &[0xff]).unwrap();
+        assert!(RecoveryAttemptTracker::load(&backend, path, policy).is_err());
+    }
 }
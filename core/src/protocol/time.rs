@@ -0,0 +1,176 @@
+//! # Time Sources
+//!
+//! Injectable clocks for the recovery window, veto checks and the state
+//! machine's transition log.
+//!
+//! ## Clocks
+//!
+//! - **Wall clock** (`now_ms`) - Unix milliseconds; comparable across
+//!   devices and restarts, but the user can move it backward or forward
+//! - **Monotonic clock** (`monotonic_ms`) - never goes backward within a
+//!   process, but its origin is arbitrary and resets on restart
+//!
+//! Window boundaries are defined in wall-clock time. To keep a backward
+//! wall-clock change from dodging a veto window, callers persist the latest
+//! wall-clock observation and fail closed when the clock reads earlier than
+//! it by more than the drift tolerance (see
+//! [`RecoveryWindow::is_open_with`](crate::protocol::recovery::RecoveryWindow::is_open_with)).
+//!
+//! Production code uses [`SystemTimeSource`]; tests use [`MockTimeSource`]
+//! to drive windows deterministically without sleeping.
+
+use crate::protocol::error::{PqrrError, Result};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Instant, SystemTime};
+
+// ============================================================================
+// TimeSource
+// ============================================================================
+
+/// Source of wall-clock and monotonic time
+pub trait TimeSource: Send + Sync {
+    /// Current wall-clock time (Unix milliseconds)
+    fn now_ms(&self) -> u64;
+
+    /// Milliseconds on a clock that never goes backward within a process
+    fn monotonic_ms(&self) -> u64;
+}
+
+/// Default time source for new state machines
+pub fn default_time_source() -> Arc<dyn TimeSource> {
+    Arc::new(SystemTimeSource)
+}
+
+// ============================================================================
+// SystemTimeSource
+// ============================================================================
+
+/// Time source backed by `SystemTime` and `Instant`
+///
+/// [`TimeSource::now_ms`] reads 0 if the system clock is set before the
+/// Unix epoch. Code that starts a timing window uses
+/// [`try_now_ms`](Self::try_now_ms) instead, so a broken clock is an error
+/// rather than a window that started in 1970.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemTimeSource;
+
+impl SystemTimeSource {
+    /// Current wall-clock time (Unix milliseconds)
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqrrError::ClockUnavailable` if the clock reads before the
+    /// Unix epoch.
+    pub fn try_now_ms(&self) -> Result<u64> {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+            .map_err(|e| PqrrError::clock_unavailable(e.to_string()))
+    }
+}
+
+impl TimeSource for SystemTimeSource {
+    fn now_ms(&self) -> u64 {
+        self.try_now_ms().unwrap_or(0)
+    }
+
+    fn monotonic_ms(&self) -> u64 {
+        static ORIGIN: OnceLock<Instant> = OnceLock::new();
+        ORIGIN.get_or_init(Instant::now).elapsed().as_millis() as u64
+    }
+}
+
+// ============================================================================
+// MockTimeSource
+// ============================================================================
+
+/// Manually driven time source for tests
+///
+/// [`advance`](Self::advance) moves both clocks forward;
+/// [`set_now_ms`](Self::set_now_ms) changes only the wall clock, which is
+/// how a user changing the system time looks to the protocol.
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::protocol::time::{MockTimeSource, TimeSource};
+///
+/// let time = MockTimeSource::new(1_000);
+/// time.advance(500);
+/// assert_eq!(time.now_ms(), 1_500);
+/// assert_eq!(time.monotonic_ms(), 500);
+///
+/// // Wall clock moved backward, monotonic clock did not
+/// time.set_now_ms(0);
+/// assert_eq!(time.now_ms(), 0);
+/// assert_eq!(time.monotonic_ms(), 500);
+/// ```
+#[derive(Debug, Default)]
+pub struct MockTimeSource {
+    /// Wall-clock time (Unix milliseconds)
+    wall_ms: AtomicU64,
+
+    /// Monotonic time (milliseconds since creation)
+    monotonic_ms: AtomicU64,
+}
+
+impl MockTimeSource {
+    /// Create a mock time source reading `now_ms` on the wall clock
+    pub fn new(now_ms: u64) -> Self {
+        Self {
+            wall_ms: AtomicU64::new(now_ms),
+            monotonic_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Move both clocks forward by `ms`
+    pub fn advance(&self, ms: u64) {
+        self.wall_ms.fetch_add(ms, Ordering::AcqRel);
+        self.monotonic_ms.fetch_add(ms, Ordering::AcqRel);
+    }
+
+    /// Set the wall clock, leaving the monotonic clock untouched
+    pub fn set_now_ms(&self, now_ms: u64) {
+        self.wall_ms.store(now_ms, Ordering::Release);
+    }
+}
+
+impl TimeSource for MockTimeSource {
+    fn now_ms(&self) -> u64 {
+        self.wall_ms.load(Ordering::Acquire)
+    }
+
+    fn monotonic_ms(&self) -> u64 {
+        self.monotonic_ms.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_time_source_is_unix_time() {
+        let now = SystemTimeSource.now_ms();
+        // After 2020-01-01
+        assert!(now > 1_577_836_800_000);
+    }
+
+    #[test]
+    fn test_system_monotonic_never_decreases() {
+        let first = SystemTimeSource.monotonic_ms();
+        let second = SystemTimeSource.monotonic_ms();
+        assert!(second >= first);
+    }
+
+    #[test]
+    fn test_mock_wall_clock_rollback_keeps_monotonic() {
+        let time = MockTimeSource::new(10_000);
+        time.advance(2_000);
+        time.set_now_ms(1_000);
+
+        assert_eq!(time.now_ms(), 1_000);
+        assert_eq!(time.monotonic_ms(), 2_000);
+    }
+}
@@ -0,0 +1,612 @@
+//! # Audit Log
+//!
+//! Tamper-evident, append-only local log of protocol events, kept so that
+//! an invariant violation in the field can be traced back to what led up
+//! to it.
+//!
+//! ## Record Format
+//!
+//! Each record is length-prefixed; all integers are big-endian:
+//!
+//! ```text
+//! [Len:4][Seq:8][Timestamp ms:8][Type:1][Epoch:8]
+//! [SubjectLen:2][Subject][DetailLen:2][Detail][PrevHash:32][Hash:32]
+//! ```
+//!
+//! `Hash` is BLAKE3 over a domain separator and every preceding field of
+//! the record (`Seq` through `PrevHash`). `PrevHash` is the `Hash` of the
+//! previous record, all zeros for the first one, so modifying, reordering
+//! or removing a record breaks the chain from that point on.
+//!
+//! ## Safety Guarantees
+//!
+//! - Every append is synced to disk before returning
+//! - A record torn by a crash mid-append is dropped on the next open and
+//!   reported through [`AuditLog::truncated_bytes`]; all complete records
+//!   are kept. A length prefix no record could have is corruption, not a
+//!   torn append, and fails the open
+//! - The log is tamper-evident, not tamper-proof: an attacker with write
+//!   access can rebuild the whole chain, but cannot silently edit history
+//!   that was already exported
+//!
+//! ## Hooking Into Other Layers
+//!
+//! Producers depend only on the [`AuditSink`] trait, so the protocol layer
+//! can report events without knowing where they are stored.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use aeternum_core::storage::audit_log::{AuditEvent, AuditEventType, AuditLog};
+//! use std::path::Path;
+//!
+//! let log = AuditLog::open(Path::new("audit.log"))?;
+//! log.append(AuditEvent::new(AuditEventType::DeviceRevoked, 3, "device-a", "lost phone"))?;
+//! println!("{} valid records", log.verify_chain()?);
+//! # Ok::<(), aeternum_core::storage::StorageError>(())
+//! ```
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::crypto::hash::Blake3Hasher;
+use crate::storage::error::StorageError;
+
+/// Domain separator for record hashes
+const AUDIT_LOG_CONTEXT: &[u8] = b"Aeternum_AuditLog_v1";
+
+/// Length of a chain hash
+const HASH_LEN: usize = 32;
+
+/// Longest subject or detail stored; longer text is truncated
+pub const MAX_FIELD_LEN: usize = 1024;
+
+/// Record bytes after the length prefix, excluding subject and detail
+const FIXED_RECORD_LEN: usize = 8 + 8 + 1 + 8 + 2 + 2 + HASH_LEN + HASH_LEN;
+
+/// Largest record body, with subject and detail at [`MAX_FIELD_LEN`]
+const MAX_RECORD_LEN: usize = FIXED_RECORD_LEN + 2 * MAX_FIELD_LEN;
+
+/// Kind of event recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum AuditEventType {
+    /// Protocol state machine changed state
+    StateTransition = 1,
+    /// Current epoch advanced
+    EpochUpgrade = 2,
+    /// Veto received during a recovery window
+    VetoReceived = 3,
+    /// One of the four invariants was violated
+    InvariantViolation = 4,
+    /// Recovery window opened
+    RecoveryStarted = 5,
+    /// Recovery completed
+    RecoveryCompleted = 6,
+    /// A device was revoked
+    DeviceRevoked = 7,
+}
+
+impl AuditEventType {
+    /// Decode the on-disk type byte
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::StateTransition),
+            2 => Some(Self::EpochUpgrade),
+            3 => Some(Self::VetoReceived),
+            4 => Some(Self::InvariantViolation),
+            5 => Some(Self::RecoveryStarted),
+            6 => Some(Self::RecoveryCompleted),
+            7 => Some(Self::DeviceRevoked),
+            _ => None,
+        }
+    }
+}
+
+/// An event to append, with its context fields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEvent {
+    /// What happened
+    pub event_type: AuditEventType,
+    /// Epoch version when it happened
+    pub epoch: u64,
+    /// What it happened to (device ID, request ID, state name, ...)
+    pub subject: String,
+    /// Free-form detail, e.g. the error message of a violation
+    pub detail: String,
+}
+
+impl AuditEvent {
+    /// Create an event; subject and detail are capped at [`MAX_FIELD_LEN`]
+    pub fn new(
+        event_type: AuditEventType,
+        epoch: u64,
+        subject: impl Into<String>,
+        detail: impl Into<String>,
+    ) -> Self {
+        Self {
+            event_type,
+            epoch,
+            subject: truncate(subject.into()),
+            detail: truncate(detail.into()),
+        }
+    }
+}
+
+/// A record read back from the log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditRecord {
+    /// Position in the log, starting at 0
+    pub sequence: u64,
+    /// When the record was appended (Unix milliseconds)
+    pub timestamp_ms: u64,
+    /// The recorded event
+    pub event: AuditEvent,
+    /// Hash of the previous record
+    pub prev_hash: [u8; HASH_LEN],
+    /// Hash of this record
+    pub hash: [u8; HASH_LEN],
+}
+
+impl AuditRecord {
+    /// Encode every field covered by the hash
+    fn hashed_fields(&self) -> Vec<u8> {
+        let subject = self.event.subject.as_bytes();
+        let detail = self.event.detail.as_bytes();
+
+        let mut bytes = Vec::with_capacity(FIXED_RECORD_LEN + subject.len() + detail.len());
+        bytes.extend_from_slice(&self.sequence.to_be_bytes());
+        bytes.extend_from_slice(&self.timestamp_ms.to_be_bytes());
+        bytes.push(self.event.event_type as u8);
+        bytes.extend_from_slice(&self.event.epoch.to_be_bytes());
+        bytes.extend_from_slice(&(subject.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(subject);
+        bytes.extend_from_slice(&(detail.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(detail);
+        bytes.extend_from_slice(&self.prev_hash);
+        bytes
+    }
+
+    /// Chain hash of this record
+    fn compute_hash(&self) -> [u8; HASH_LEN] {
+        let mut hasher = Blake3Hasher::new();
+        hasher.update(AUDIT_LOG_CONTEXT);
+        hasher.update(&self.hashed_fields());
+        *hasher.finalize().as_bytes()
+    }
+
+    /// Encode the record with its length prefix
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut body = self.hashed_fields();
+        body.extend_from_slice(&self.hash);
+
+        let mut bytes = Vec::with_capacity(4 + body.len());
+        bytes.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&body);
+        bytes
+    }
+
+    /// Decode the record body that follows a length prefix
+    fn from_body(body: &[u8]) -> Result<Self, StorageError> {
+        let mut reader = FieldReader(body);
+        let sequence = reader.u64()?;
+        let timestamp_ms = reader.u64()?;
+        let type_byte = reader.take(1)?[0];
+        let event_type = AuditEventType::from_u8(type_byte).ok_or_else(|| {
+            StorageError::consistency_check(format!("Unknown audit event type {}", type_byte))
+        })?;
+        let epoch = reader.u64()?;
+        let subject = reader.string()?;
+        let detail = reader.string()?;
+        let prev_hash = reader.hash()?;
+        let hash = reader.hash()?;
+        if !reader.0.is_empty() {
+            return Err(StorageError::consistency_check(
+                "Trailing bytes in audit record",
+            ));
+        }
+
+        Ok(Self {
+            sequence,
+            timestamp_ms,
+            event: AuditEvent {
+                event_type,
+                epoch,
+                subject,
+                detail,
+            },
+            prev_hash,
+            hash,
+        })
+    }
+}
+
+/// Receiver of audit events
+///
+/// Implemented by [`AuditLog`]. Recording must not fail the operation
+/// being audited, so implementations report their own errors.
+pub trait AuditSink: Send + Sync {
+    /// Record one event
+    fn record(&self, event: AuditEvent);
+}
+
+/// Append-only, hash-chained audit log file
+pub struct AuditLog {
+    path: PathBuf,
+    /// `(next sequence, hash of last record)`
+    tail: Mutex<(u64, [u8; HASH_LEN])>,
+    /// Bytes of a torn record dropped by [`open`](Self::open)
+    truncated_bytes: u64,
+}
+
+impl AuditLog {
+    /// Open the log at `path`, creating it if missing
+    ///
+    /// A torn record left at the end by a crash mid-append is truncated;
+    /// [`truncated_bytes`](Self::truncated_bytes) reports how much was
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// - `ConsistencyCheckFailed` if a complete record cannot be decoded or
+    ///   a length prefix is out of range
+    /// - `ShadowWriteFailed` / `FsyncFailed` on I/O errors
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let path = path.as_ref().to_path_buf();
+        let data = match std::fs::read(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                return Err(StorageError::consistency_check(format!(
+                    "Failed to read audit log {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+
+        let (records, complete_len) = parse_records(&data)?;
+        let truncated_bytes = (data.len() - complete_len) as u64;
+        if truncated_bytes > 0 {
+            eprintln!(
+                "[AuditLog] Dropping {} bytes of torn record at end of {}",
+                data.len() - complete_len,
+                path.display()
+            );
+            let file = OpenOptions::new()
+                .write(true)
+                .open(&path)
+                .map_err(|e| StorageError::shadow_write(e.to_string()))?;
+            file.set_len(complete_len as u64)
+                .and_then(|_| file.sync_all())
+                .map_err(|e| {
+                    StorageError::fsync(format!(
+                        "Failed to truncate audit log {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+        }
+
+        let tail = records.last().map_or((0, [0u8; HASH_LEN]), |record| {
+            (record.sequence + 1, record.hash)
+        });
+
+        Ok(Self {
+            path,
+            tail: Mutex::new(tail),
+            truncated_bytes,
+        })
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Bytes of a torn record dropped when the log was opened
+    ///
+    /// Non-zero means the last append before the previous shutdown did not
+    /// complete; the event it was recording is lost.
+    pub fn truncated_bytes(&self) -> u64 {
+        self.truncated_bytes
+    }
+
+    /// Append an event, timestamped now, and sync it to disk
+    ///
+    /// # Errors
+    ///
+    /// - `ShadowWriteFailed` if the record cannot be written
+    /// - `FsyncFailed` if it cannot be synced
+    pub fn append(&self, event: AuditEvent) -> Result<(), StorageError> {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let mut tail = self.tail.lock().unwrap();
+        let mut record = AuditRecord {
+            sequence: tail.0,
+            timestamp_ms,
+            event,
+            prev_hash: tail.1,
+            hash: [0u8; HASH_LEN],
+        };
+        record.hash = record.compute_hash();
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| {
+                StorageError::shadow_write(format!(
+                    "Failed to open audit log {}: {}",
+                    self.path.display(),
+                    e
+                ))
+            })?;
+        file.write_all(&record.to_bytes()).map_err(|e| {
+            StorageError::shadow_write(format!(
+                "Failed to append to audit log {}: {}",
+                self.path.display(),
+                e
+            ))
+        })?;
+        file.sync_data().map_err(|e| {
+            StorageError::fsync(format!(
+                "Failed to fsync audit log {}: {}",
+                self.path.display(),
+                e
+            ))
+        })?;
+
+        *tail = (record.sequence + 1, record.hash);
+        Ok(())
+    }
+
+    /// Read all records, without verifying the chain
+    ///
+    /// # Errors
+    ///
+    /// - `ConsistencyCheckFailed` if the file cannot be read or decoded
+    pub fn records(&self) -> Result<Vec<AuditRecord>, StorageError> {
+        let _tail = self.tail.lock().unwrap();
+        let data = self.read_file()?;
+        let (records, complete_len) = parse_records(&data)?;
+        if complete_len < data.len() {
+            return Err(StorageError::consistency_check("Torn audit record"));
+        }
+        Ok(records)
+    }
+
+    /// Verify the hash chain of the whole log
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(n)` with the number of records if every record verifies
+    /// - `Err(ConsistencyCheckFailed)` naming the first record that breaks
+    ///   the chain
+    pub fn verify_chain(&self) -> Result<u64, StorageError> {
+        let records = self.records()?;
+
+        let mut prev_hash = [0u8; HASH_LEN];
+        for (index, record) in records.iter().enumerate() {
+            if record.sequence != index as u64
+                || record.prev_hash != prev_hash
+                || record.hash != record.compute_hash()
+            {
+                return Err(StorageError::consistency_check(format!(
+                    "Audit log chain broken at record {}",
+                    index
+                )));
+            }
+            prev_hash = record.hash;
+        }
+
+        Ok(records.len() as u64)
+    }
+
+    fn read_file(&self) -> Result<Vec<u8>, StorageError> {
+        match std::fs::read(&self.path) {
+            Ok(data) => Ok(data),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(StorageError::consistency_check(format!(
+                "Failed to read audit log {}: {}",
+                self.path.display(),
+                e
+            ))),
+        }
+    }
+}
+
+impl AuditSink for AuditLog {
+    fn record(&self, event: AuditEvent) {
+        if let Err(e) = self.append(event) {
+            eprintln!("[AuditLog] Failed to record event: {}", e);
+        }
+    }
+}
+
+/// Parse complete records, returning them and the bytes they span
+///
+/// Only an incomplete final record is left unparsed. A length prefix
+/// outside the possible record sizes is an error, so corruption in the
+/// middle of the log is never mistaken for a torn tail.
+fn parse_records(data: &[u8]) -> Result<(Vec<AuditRecord>, usize), StorageError> {
+    let mut records = Vec::new();
+    let mut offset = 0;
+    while data.len() - offset >= 4 {
+        let len = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        if !(FIXED_RECORD_LEN..=MAX_RECORD_LEN).contains(&len) {
+            return Err(StorageError::consistency_check(format!(
+                "Corrupt audit record length {} at offset {}",
+                len, offset
+            )));
+        }
+        let end = offset + 4 + len;
+        if end > data.len() {
+            break;
+        }
+        records.push(AuditRecord::from_body(&data[offset + 4..end])?);
+        offset = end;
+    }
+    Ok((records, offset))
+}
+
+/// Cap a field at [`MAX_FIELD_LEN`] bytes on a char boundary
+fn truncate(mut text: String) -> String {
+    if text.len() > MAX_FIELD_LEN {
+        let mut end = MAX_FIELD_LEN;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+    text
+}
+
+/// Cursor over the fields of a record body
+struct FieldReader<'a>(&'a [u8]);
+
+impl<'a> FieldReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], StorageError> {
+        if self.0.len() < n {
+            return Err(StorageError::consistency_check("Truncated audit record"));
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn u64(&mut self) -> Result<u64, StorageError> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String, StorageError> {
+        let len = u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as usize;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| StorageError::consistency_check("Audit record field is not UTF-8"))
+    }
+
+    fn hash(&mut self) -> Result<[u8; HASH_LEN], StorageError> {
+        Ok(self.take(HASH_LEN)?.try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_log(dir: &TempDir) -> AuditLog {
+        let log = AuditLog::open(dir.path().join("audit.log")).unwrap();
+        log.append(AuditEvent::new(
+            AuditEventType::StateTransition,
+            1,
+            "Idle -> Rekeying",
+            "epoch upgrade 1 -> 2",
+        ))
+        .unwrap();
+        log.append(AuditEvent::new(AuditEventType::EpochUpgrade, 2, "", ""))
+            .unwrap();
+        log.append(AuditEvent::new(
+            AuditEventType::InvariantViolation,
+            2,
+            "Invariant #2",
+            "header incomplete",
+        ))
+        .unwrap();
+        log
+    }
+
+    #[test]
+    fn test_chain_verifies_and_survives_reopen() {
+        let dir = TempDir::new().unwrap();
+        let log = sample_log(&dir);
+        assert_eq!(log.verify_chain().unwrap(), 3);
+
+        let records = log.records().unwrap();
+        assert_eq!(records[0].prev_hash, [0u8; HASH_LEN]);
+        assert_eq!(records[1].prev_hash, records[0].hash);
+        assert_eq!(records[2].event.subject, "Invariant #2");
+
+        // Reopening continues the same chain
+        let log = AuditLog::open(log.path()).unwrap();
+        log.append(AuditEvent::new(
+            AuditEventType::DeviceRevoked,
+            2,
+            "device-a",
+            "",
+        ))
+        .unwrap();
+        assert_eq!(log.verify_chain().unwrap(), 4);
+        assert_eq!(log.records().unwrap()[3].sequence, 3);
+    }
+
+    #[test]
+    fn test_detects_modified_middle_record() {
+        let dir = TempDir::new().unwrap();
+        let log = sample_log(&dir);
+        let records = log.records().unwrap();
+
+        // Retype the middle record in place: same length, still decodes
+        let mut data = std::fs::read(log.path()).unwrap();
+        let type_offset = records[0].to_bytes().len() + 4 + 8 + 8;
+        assert_eq!(data[type_offset], AuditEventType::EpochUpgrade as u8);
+        data[type_offset] = AuditEventType::VetoReceived as u8;
+        std::fs::write(log.path(), &data).unwrap();
+
+        let err = log.verify_chain().unwrap_err();
+        assert!(err.to_string().contains("record 1"));
+
+        // Dropping the middle record breaks the chain as well
+        let mut dropped = records[0].to_bytes();
+        dropped.extend_from_slice(&records[2].to_bytes());
+        std::fs::write(log.path(), &dropped).unwrap();
+        assert!(log.verify_chain().is_err());
+    }
+
+    #[test]
+    fn test_open_drops_torn_record() {
+        let dir = TempDir::new().unwrap();
+        let log = sample_log(&dir);
+        let full = std::fs::read(log.path()).unwrap();
+
+        // Crash in the middle of appending a fourth record
+        let mut torn = full.clone();
+        torn.extend_from_slice(&[0, 0, 0, 120, 1, 2, 3]);
+        std::fs::write(log.path(), &torn).unwrap();
+        assert!(log.verify_chain().is_err());
+
+        let log = AuditLog::open(log.path()).unwrap();
+        assert_eq!(log.truncated_bytes(), 7);
+        assert_eq!(std::fs::read(log.path()).unwrap(), full);
+        assert_eq!(log.verify_chain().unwrap(), 3);
+
+        let log = AuditLog::open(log.path()).unwrap();
+        assert_eq!(log.truncated_bytes(), 0);
+    }
+
+    #[test]
+    fn test_open_rejects_corrupt_length_prefix() {
+        let dir = TempDir::new().unwrap();
+        let log = sample_log(&dir);
+        let records = log.records().unwrap();
+
+        // A flipped length byte in the second record must not truncate the
+        // rest of the log as if it were torn
+        let mut data = std::fs::read(log.path()).unwrap();
+        let len_offset = records[0].to_bytes().len();
+        data[len_offset] = 0xff;
+        std::fs::write(log.path(), &data).unwrap();
+
+        assert!(matches!(
+            AuditLog::open(log.path()),
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+        assert_eq!(std::fs::read(log.path()).unwrap(), data);
+    }
+}
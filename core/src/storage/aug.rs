@@ -8,6 +8,20 @@
 //! 2. **影子写入 (Shadow Writing)**: 创建临时文件，写入 Header 和 Blob，强制 fsync
 //! 3. **原子替换 (Atomic Commit)**: POSIX rename + 更新 SQLCipher 元数据
 //!
+//! 纪元不变、只修改 vault 数据（如 vault 条目）时，阶段 1 由 [`aup_reseal`]
+//! 代替，阶段 2、3 不变。
+//!
+//! ## Vault 文件布局
+//!
+//! ```text
+//! [VaultHeader:32][VaultBlob:data_length][VK 区域:80]
+//! VK 区域 = [Magic:8][Nonce:24][EncryptedVK:48]
+//! ```
+//!
+//! VK 区域保存新纪元 DEK 加密的 VK，下一次 AUP 由 [`read_vault_key`] 读出后
+//! 交给 [`aup_prepare_with_nonce`]。VK 区域引入之前写入的文件没有此区域，
+//! 仍可正常读取。
+//!
 //! ## 设计原则
 //!
 //! - **无中间态**: 任何时刻 (Header, VaultBlob) 对必须是全纪元一致的
@@ -26,6 +40,7 @@
 //!
 //! ```no_run
 //! use aeternum_core::storage::aug::{aup_prepare, aup_shadow_write, aup_atomic_commit};
+//! use aeternum_core::storage::metadata::SqliteMetadataStore;
 //! use aeternum_core::models::{CryptoEpoch, VaultBlob};
 //! use aeternum_core::crypto::aead::XChaCha20Key;
 //! use std::path::Path;
@@ -37,6 +52,7 @@
 //! let current_dek = XChaCha20Key::generate();
 //! let vault_data = b"user data".to_vec();
 //! let vault_path = Path::new("vault.db");
+//! let mut metadata = SqliteMetadataStore::open("metadata.db")?;
 //!
 //! // 阶段 1: 预备
 //! let preparation = aup_prepare(&current_epoch, &current_vk, &current_dek, &vault_data)?;
@@ -44,23 +60,56 @@
 //! // 阶段 2: 影子写入
 //! let shadow_file = aup_shadow_write(&vault_path, &preparation)?;
 //!
-//! // 阶段 3: 原子提交（注意：此函数调用需要修改）
-//! // aup_atomic_commit(&vault_path, shadow_file, &metadata_db, new_epoch)?;
+//! // 阶段 3: 原子提交 + 更新 Local_Epoch
+//! aup_atomic_commit(&vault_path, shadow_file, &preparation.new_epoch, &mut metadata)?;
 //! # Ok(())
 //! # }
 //! ```
 
+use std::borrow::Cow;
 use std::io::Read;
 use std::io::Write;
 use std::path::Path;
 
-use crate::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce};
+use crate::crypto::aead::{stream, AeadCipher, XChaCha20Key, XChaCha20Nonce};
+use crate::crypto::hash::DeriveKey;
 use crate::crypto::kdf::Argon2idKDF;
-use crate::models::epoch::CryptoEpoch;
-use crate::models::vault::{VaultBlob, VaultHeader, VAULT_MAGIC};
+use crate::models::epoch::{CryptoAlgorithm, CryptoEpoch};
+use crate::models::vault::{
+    compression, CompressionAlgo, VaultBlob, VaultHeader, COMPRESSION_BLOB_VERSION,
+    PRE_CHECKSUM_BLOB_VERSION, STREAM_BLOB_VERSION,
+};
+use crate::storage::backend::{FileBackend, VaultBackend};
 use crate::storage::error::StorageError;
 use crate::storage::invariant::InvariantValidator;
-use crate::storage::shadow::{ShadowFile, ShadowWriter};
+use crate::storage::lock::VaultLock;
+use crate::storage::metadata::MetadataStore;
+use crate::storage::shadow::{reject_symlink, ShadowFile};
+use zeroize::Zeroizing;
+
+/// Vault 目录中的 Vault 文件名
+pub const VAULT_FILE_NAME: &str = "vault.db";
+
+/// Vault Header 固定长度
+pub(crate) const VAULT_HEADER_LEN: usize = 32;
+
+/// VK 区域魔数
+pub const VK_REGION_MAGIC: [u8; 8] = *b"AETVK\0\0\x01";
+
+/// 加密 VK 长度（32 字节 VK + 16 字节 Auth Tag）
+pub const ENCRYPTED_VK_LEN: usize = 48;
+
+/// VK 区域长度：[Magic:8][Nonce:24][EncryptedVK:48]
+pub const VK_REGION_LEN: usize = 8 + 24 + ENCRYPTED_VK_LEN;
+
+/// 旧版 AUP 加密 VK 使用的固定 nonce（VK 区域引入之前）
+pub const LEGACY_VK_NONCE: [u8; 24] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+    0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+];
+
+/// 影子写入进度回调的最大间隔（1 MiB）
+pub const AUP_PROGRESS_INTERVAL: usize = 1024 * 1024;
 
 // ============================================================================
 // AUP 阶段 1: 预备 (Preparation)
@@ -77,6 +126,23 @@ pub struct AupPreparation {
     pub prepared_blob: Vec<u8>,
     /// Vault Header（固定 32 字节）
     pub header: [u8; 32],
+    /// 新纪元 DEK（由 VK 与新纪元派生，下一次 AUP 的 `current_dek`）
+    pub new_dek: XChaCha20Key,
+    /// 使用新 DEK 加密的 VK（32 字节 VK + 16 字节 Auth Tag）
+    pub encrypted_vk: Vec<u8>,
+    /// 加密 VK 使用的 nonce
+    pub vk_nonce: [u8; 24],
+}
+
+impl AupPreparation {
+    /// 编码 VK 区域：[Magic:8][Nonce:24][EncryptedVK:48]
+    fn vk_region(&self) -> [u8; VK_REGION_LEN] {
+        let mut region = [0u8; VK_REGION_LEN];
+        region[0..8].copy_from_slice(&VK_REGION_MAGIC);
+        region[8..32].copy_from_slice(&self.vk_nonce);
+        region[32..].copy_from_slice(&self.encrypted_vk);
+        region
+    }
 }
 
 /// AUP 阶段 1：预备
@@ -138,26 +204,204 @@ pub fn aup_prepare(
     current_dek: &XChaCha20Key,
     vault_data: &[u8],
 ) -> Result<AupPreparation, StorageError> {
+    aup_prepare_with_nonce(
+        current_epoch,
+        current_vk_bytes,
+        &LEGACY_VK_NONCE,
+        current_dek,
+        vault_data,
+    )
+}
+
+/// AUP 阶段 1：预备，使用已存储的 VK nonce
+///
+/// 与 [`aup_prepare`] 相同，但当前 VK 在 `vk_nonce` 下解密。上一次 AUP 写入
+/// 的 VK 区域由 [`read_vault_key`] 读出，其 `encrypted_vk` 与 `vk_nonce`
+/// 连同上一次的 `new_dek` 即为本次的输入，使多纪元链可以逐次延续。
+///
+/// # Errors
+///
+/// 与 [`aup_prepare`] 相同。
+pub fn aup_prepare_with_nonce(
+    current_epoch: &CryptoEpoch,
+    current_vk_bytes: &[u8],
+    vk_nonce: &[u8; 24],
+    current_dek: &XChaCha20Key,
+    vault_data: &[u8],
+) -> Result<AupPreparation, StorageError> {
+    aup_prepare_with_algorithm(
+        current_epoch,
+        current_epoch.algorithm,
+        current_vk_bytes,
+        vk_nonce,
+        current_dek,
+        vault_data,
+    )
+}
+
+/// AUP 阶段 1：预备，同时迁移到 `algorithm`
+///
+/// 新纪元为 `current_epoch.version + 1` 且使用 `algorithm`。当前 VK 按当前
+/// 纪元的算法规则解封，新 DEK、VK 加密与 Blob 格式版本均按新纪元的算法规则
+/// 生成。
+///
+/// # Errors
+///
+/// 与 [`aup_prepare`] 相同；此外，如果 `algorithm` 早于当前纪元的算法
+/// （算法降级），返回 `StorageError::InvariantViolation`。
+pub fn aup_prepare_with_algorithm(
+    current_epoch: &CryptoEpoch,
+    algorithm: CryptoAlgorithm,
+    current_vk_bytes: &[u8],
+    vk_nonce: &[u8; 24],
+    current_dek: &XChaCha20Key,
+    vault_data: &[u8],
+) -> Result<AupPreparation, StorageError> {
+    aup_prepare_with_compression(
+        current_epoch,
+        algorithm,
+        CompressionAlgo::None,
+        current_vk_bytes,
+        vk_nonce,
+        current_dek,
+        vault_data,
+    )
+}
+
+/// AUP 阶段 1：预备，加密前按 `compression` 压缩 vault 数据
+///
+/// 与 [`aup_prepare_with_algorithm`] 相同，但 vault 数据先压缩再加密，解压
+/// 后长度写入 Blob 并作为关联数据认证（见
+/// [`compression`](crate::models::vault::compression)）。以下情况不压缩：
+///
+/// - 新纪元的 Blob 格式为 version 1（无法记录压缩字段）
+/// - 数据短于 [`compression::MIN_COMPRESS_LEN`] 字节，或压缩后没有变小
+///
+/// 压缩默认关闭：密文长度会反映明文的可压缩程度。
+///
+/// # Errors
+///
+/// 与 [`aup_prepare_with_algorithm`] 相同；压缩失败（如 zstd 级别无效）
+/// 返回 `StorageError::CryptoError`。
+pub fn aup_prepare_with_compression(
+    current_epoch: &CryptoEpoch,
+    algorithm: CryptoAlgorithm,
+    compression: CompressionAlgo,
+    current_vk_bytes: &[u8],
+    vk_nonce: &[u8; 24],
+    current_dek: &XChaCha20Key,
+    vault_data: &[u8],
+) -> Result<AupPreparation, StorageError> {
+    prepare_next_epoch(
+        current_epoch,
+        algorithm,
+        compression,
+        current_vk_bytes,
+        vk_nonce,
+        current_dek,
+        |_| Ok(Cow::Borrowed(vault_data)),
+    )
+}
+
+/// AUP 阶段 1：预备，vault 数据由新纪元 DEK 重新生成
+///
+/// 与 [`aup_prepare_with_algorithm`] 相同，但 vault 数据由 `vault_data`
+/// 根据新纪元的 DEK 生成。vault 数据中用纪元 DEK 加密的内容（如
+/// [`VaultContents`](crate::models::vault::items::VaultContents)）借此在
+/// 同一次升级中改用新 DEK 加密，不会出现数据仍使用旧 DEK 的已提交纪元。
+///
+/// # Errors
+///
+/// 与 [`aup_prepare_with_algorithm`] 相同；`vault_data` 返回的错误原样
+/// 传出。
+pub fn aup_prepare_rekeyed<F>(
+    current_epoch: &CryptoEpoch,
+    algorithm: CryptoAlgorithm,
+    current_vk_bytes: &[u8],
+    vk_nonce: &[u8; 24],
+    current_dek: &XChaCha20Key,
+    vault_data: F,
+) -> Result<AupPreparation, StorageError>
+where
+    F: FnOnce(&XChaCha20Key) -> Result<Vec<u8>, StorageError>,
+{
+    prepare_next_epoch(
+        current_epoch,
+        algorithm,
+        CompressionAlgo::None,
+        current_vk_bytes,
+        vk_nonce,
+        current_dek,
+        |new_dek| vault_data(new_dek).map(Cow::Owned),
+    )
+}
+
+/// AUP 阶段 1：在当前纪元重新封装 vault 数据
+///
+/// 不升级纪元：VK 与 DEK 保持不变（VK 换用新 nonce 重新加密），只替换
+/// vault 数据。返回的 [`AupPreparation`] 的 `new_epoch` 即 `current_epoch`，
+/// 照常交给 [`aup_shadow_write`] 与 [`aup_atomic_commit`]，因此 vault 数据
+/// 的每次修改同样是影子写入 + 原子替换。
+///
+/// # Errors
+///
+/// 返回 `StorageError::CryptoError` 如果 VK 解封失败或重新加密失败。
+pub fn aup_reseal(
+    current_epoch: &CryptoEpoch,
+    current_vk_bytes: &[u8],
+    vk_nonce: &[u8; 24],
+    current_dek: &XChaCha20Key,
+    vault_data: &[u8],
+) -> Result<AupPreparation, StorageError> {
+    let decrypt_nonce = XChaCha20Nonce::from_bytes(*vk_nonce);
+    let vk = Zeroizing::new(decrypt_vk(
+        current_epoch,
+        current_dek,
+        &decrypt_nonce,
+        current_vk_bytes,
+    )?);
+
+    seal_epoch(
+        *current_epoch,
+        CompressionAlgo::None,
+        &vk,
+        current_dek.clone(),
+        vault_data,
+    )
+}
+
+/// AUP 阶段 1 的公共部分：校验并派生新纪元，再由 [`seal_epoch`] 封装
+fn prepare_next_epoch<'a, F>(
+    current_epoch: &CryptoEpoch,
+    algorithm: CryptoAlgorithm,
+    compression: CompressionAlgo,
+    current_vk_bytes: &[u8],
+    vk_nonce: &[u8; 24],
+    current_dek: &XChaCha20Key,
+    vault_data: F,
+) -> Result<AupPreparation, StorageError>
+where
+    F: FnOnce(&XChaCha20Key) -> Result<Cow<'a, [u8]>, StorageError>,
+{
     // 步骤 1：计算新纪元
-    let new_epoch = current_epoch.next();
+    let new_epoch = current_epoch.next_with_algorithm(algorithm);
 
     // 验证纪元单调性（Invariant #1）
     InvariantValidator::check_epoch_monotonicity(current_epoch, &new_epoch)?;
 
-    // 步骤 2：解封当前 VK
+    // 算法只能升级，不能降级
+    if algorithm.version() < current_epoch.algorithm.version() {
+        return Err(StorageError::invariant(format!(
+            "Algorithm downgrade: v{} -> v{}",
+            current_epoch.algorithm.version(),
+            algorithm.version()
+        )));
+    }
+
+    // 步骤 2：解封当前 VK（按当前纪元的算法规则）
     // current_vk_bytes 格式：[加密的 VK (32字节)][Auth Tag (16字节)]
-    // 为了解密，我们需要从加密数据中提取 nonce
-    // 在实际实现中，nonce 应该存储在 header 或元数据中
-    // 这里我们使用一个固定 nonce 进行演示（生产环境应该使用存储的 nonce）
-    let decrypt_nonce = XChaCha20Nonce::from_bytes([
-        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
-        0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
-    ]);
-
-    let cipher = AeadCipher::new(current_dek);
-    let vk_decrypted = cipher
-        .decrypt(&decrypt_nonce, current_vk_bytes, None)
-        .map_err(|e| StorageError::crypto(format!("Failed to decrypt VK: {}", e)))?;
+    let decrypt_nonce = XChaCha20Nonce::from_bytes(*vk_nonce);
+    let vk_decrypted = decrypt_vk(current_epoch, current_dek, &decrypt_nonce, current_vk_bytes)?;
 
     // 验证 VK 长度（应该是 32 字节）
     if vk_decrypted.len() != 32 {
@@ -167,57 +411,88 @@ pub fn aup_prepare(
         )));
     }
 
-    // 步骤 3：派生新 DEK
-    // 使用 Argon2id 从 VK 和新纪元派生新的 DEK
-    let kdf = Argon2idKDF::new();
-    let salt = create_epoch_salt(&new_epoch);
-    let new_dek_bytes = kdf
-        .derive_key_with_length(&vk_decrypted, &salt, 32)
-        .map_err(|e| StorageError::crypto(format!("Failed to derive new DEK: {}", e)))?;
-
-    let new_dek = XChaCha20Key::from_bytes(new_dek_bytes.as_bytes())
-        .map_err(|e| StorageError::crypto(format!("Invalid DEK length: {}", e)))?;
-
-    // 步骤 4：使用新 DEK 重新加密 VK
-    // 注意：在实际实现中，这里应该保存 nonce 和加密后的 VK 以便后续使用
-    // 当前实现中，我们使用 VK 来加密 vault 数据，所以不需要保存加密的 VK
-    let _encrypt_nonce = XChaCha20Nonce::random();
-    let _new_cipher = AeadCipher::new(&new_dek);
-    let _vk_encrypted = _new_cipher
-        .encrypt(&_encrypt_nonce, &vk_decrypted, None)
+    // 步骤 3：按新纪元的算法派生新 DEK
+    let new_dek = derive_epoch_dek(&vk_decrypted, &new_epoch)?;
+
+    // 步骤 4：生成 vault 数据（可能依赖新 DEK）
+    let vault_data = vault_data(&new_dek)?;
+
+    seal_epoch(new_epoch, compression, &vk_decrypted, new_dek, &vault_data)
+}
+
+/// 用 `dek` 加密 VK、用 VK 加密 vault 数据，生成 `epoch` 的 [`AupPreparation`]
+fn seal_epoch(
+    new_epoch: CryptoEpoch,
+    compression: CompressionAlgo,
+    vk: &[u8],
+    new_dek: XChaCha20Key,
+    vault_data: &[u8],
+) -> Result<AupPreparation, StorageError> {
+    // 步骤 5：使用新 DEK 重新加密 VK
+    // 结果由影子写入保存在 Vault 文件的 VK 区域，供下一次 AUP 解封
+    let vk_nonce = XChaCha20Nonce::random();
+    let encrypted_vk = AeadCipher::new(&new_dek)
+        .encrypt(&vk_nonce, vk, vk_aad(&new_epoch).as_deref())
         .map_err(|e| StorageError::crypto(format!("Failed to encrypt VK: {}", e)))?;
 
-    // 步骤 5：创建 VaultBlob
+    // 步骤 6：创建 VaultBlob
     // VaultBlob 包含加密的 vault 数据（使用 VK 加密）
     // 注意：这里我们简化处理，直接将 vault_data 作为密文
     // 在实际实现中，vault_data 应该使用 VK 进行加密
     let vault_nonce = XChaCha20Nonce::random();
     let vault_cipher =
-        AeadCipher::new(&XChaCha20Key::from_bytes(&vk_decrypted).map_err(|e| {
+        AeadCipher::new(&XChaCha20Key::from_bytes(vk).map_err(|e| {
             StorageError::crypto(format!("Invalid VK for vault encryption: {}", e))
         })?);
-    let vault_ciphertext = vault_cipher
-        .encrypt(&vault_nonce, vault_data, None)
-        .map_err(|e| StorageError::crypto(format!("Failed to encrypt vault: {}", e)))?;
+    // 可选压缩：仅 version 2 起的 Blob 能记录压缩字段
+    let blob_version = new_epoch.algorithm.blob_version();
+    let requested = if blob_version >= COMPRESSION_BLOB_VERSION {
+        compression
+    } else {
+        CompressionAlgo::None
+    };
+    let (compression, plaintext) = compression::compress(requested, vault_data)
+        .map_err(|e| StorageError::crypto(format!("Failed to compress vault: {}", e)))?;
+    let decompressed_len = if compression.is_compressed() {
+        vault_data.len() as u64
+    } else {
+        0
+    };
+
+    // version 2 起分块 STREAM 加密：启用 `parallel` feature 时各分块并行加密，
+    // 输出与顺序路径逐位一致；压缩参数作为关联数据认证。
+    // version 1 保持原单次 AEAD 格式，旧版本仍可读取
+    let vault_ciphertext = if blob_version >= STREAM_BLOB_VERSION {
+        stream::seal_with_aad(
+            &vault_cipher,
+            &vault_nonce,
+            &plaintext,
+            &compression.aad(decompressed_len),
+        )
+    } else {
+        vault_cipher.encrypt(&vault_nonce, &plaintext, None)
+    }
+    .map_err(|e| StorageError::crypto(format!("Failed to encrypt vault: {}", e)))?;
 
-    // 提取 auth tag
+    // 提取 auth tag（STREAM 格式为最后一个分块的 tag）
     let auth_tag = AeadCipher::extract_tag(&vault_ciphertext)
         .map_err(|e| StorageError::crypto(format!("Failed to extract tag: {}", e)))?;
 
     let blob = VaultBlob::new(
-        VaultBlob::CURRENT_BLOB_VERSION,
+        blob_version,
         new_epoch,
         vault_ciphertext,
         *auth_tag.as_bytes(),
         *vault_nonce.as_bytes(),
-    );
+    )
+    .with_compression(compression, decompressed_len);
 
-    // 步骤 6：序列化 VaultBlob
+    // 步骤 7：序列化 VaultBlob
     let serialized_blob = blob
         .serialize()
         .map_err(|e| StorageError::crypto(format!("Failed to serialize blob: {}", e)))?;
 
-    // 步骤 7：创建 VaultHeader
+    // 步骤 8：创建 VaultHeader
     let vault_header = VaultHeader::new(&blob);
     let header_bytes = vault_header.to_bytes();
 
@@ -225,9 +500,102 @@ pub fn aup_prepare(
         new_epoch,
         prepared_blob: serialized_blob,
         header: header_bytes,
+        new_dek,
+        encrypted_vk,
+        vk_nonce: *vk_nonce.as_bytes(),
     })
 }
 
+/// V2 DEK 派生的 BLAKE3 上下文
+const DEK_CONTEXT_V2: &str = "Aeternum 2026 epoch DEK v2";
+
+/// V2 VK 加密的关联数据前缀（其后为纪元版本号）
+const VK_AAD_V2: &[u8] = b"Aeternum_EpochVK_v2";
+
+/// 按纪元的算法从 VK 派生该纪元的 DEK
+///
+/// - V1：Argon2id(VK, 纪元盐值)
+/// - V2：BLAKE3-derive_key(DEK_CONTEXT_V2, 纪元盐值 || VK)，XOF 输出 32 字节
+fn derive_epoch_dek(vk: &[u8], epoch: &CryptoEpoch) -> Result<XChaCha20Key, StorageError> {
+    let salt = create_epoch_salt(epoch);
+    let dek_bytes = match epoch.algorithm {
+        CryptoAlgorithm::V1 => Argon2idKDF::new()
+            .derive_key_with_length(vk, &salt, 32)
+            .map_err(|e| StorageError::crypto(format!("Failed to derive new DEK: {}", e)))?
+            .as_bytes()
+            .to_vec(),
+        CryptoAlgorithm::V2 => DeriveKey::new(&salt, DEK_CONTEXT_V2).derive(vk, 32),
+    };
+
+    XChaCha20Key::from_bytes(&dek_bytes)
+        .map_err(|e| StorageError::crypto(format!("Invalid DEK length: {}", e)))
+}
+
+/// 按纪元的算法构造 VK 加密的关联数据
+///
+/// V1 不使用关联数据；V2 将加密的 VK 绑定到其纪元版本。
+fn vk_aad(epoch: &CryptoEpoch) -> Option<Vec<u8>> {
+    match epoch.algorithm {
+        CryptoAlgorithm::V1 => None,
+        CryptoAlgorithm::V2 => Some([VK_AAD_V2, &epoch.version.to_be_bytes()].concat()),
+    }
+}
+
+/// 按 `epoch` 的算法规则解封 VK
+fn decrypt_vk(
+    epoch: &CryptoEpoch,
+    dek: &XChaCha20Key,
+    nonce: &XChaCha20Nonce,
+    encrypted_vk: &[u8],
+) -> Result<Vec<u8>, StorageError> {
+    AeadCipher::new(dek)
+        .decrypt(nonce, encrypted_vk, vk_aad(epoch).as_deref())
+        .map_err(|e| StorageError::crypto(format!("Failed to decrypt VK: {}", e)))
+}
+
+/// 解密 Vault 数据
+///
+/// 按 Blob 纪元的算法规则，用该纪元的 DEK 解封 VK 区域中的 VK，再用 VK
+/// 解密 Blob 中的 vault 数据；压缩过的数据解密后解压，并要求解压长度与
+/// Blob 声明的长度一致。
+///
+/// # Errors
+///
+/// - `StorageError::ConsistencyCheckFailed` 如果 Blob 无效
+/// - `StorageError::CryptoError` 如果 DEK 与纪元不匹配、VK 区域或 Blob 被篡改，
+///   或解压后的长度与声明不符
+pub fn open_vault(
+    blob: &VaultBlob,
+    vault_key: &StoredVaultKey,
+    dek: &XChaCha20Key,
+) -> Result<Vec<u8>, StorageError> {
+    blob.validate()
+        .map_err(|e| StorageError::consistency_check(format!("Invalid vault blob: {}", e)))?;
+
+    let vk_nonce = XChaCha20Nonce::from_bytes(vault_key.vk_nonce);
+    let vk = decrypt_vk(&blob.epoch, dek, &vk_nonce, &vault_key.encrypted_vk)?;
+    let vault_cipher = AeadCipher::new(
+        &XChaCha20Key::from_bytes(&vk)
+            .map_err(|e| StorageError::crypto(format!("Invalid VK length: {}", e)))?,
+    );
+
+    let vault_nonce = XChaCha20Nonce::from_bytes(blob.nonce);
+    let plaintext = if blob.blob_version >= STREAM_BLOB_VERSION {
+        stream::open_with_aad(
+            &vault_cipher,
+            &vault_nonce,
+            &blob.ciphertext,
+            &blob.compression.aad(blob.decompressed_len),
+        )
+    } else {
+        vault_cipher.decrypt(&vault_nonce, &blob.ciphertext, None)
+    }
+    .map_err(|e| StorageError::crypto(format!("Failed to decrypt vault: {}", e)))?;
+
+    compression::decompress(blob.compression, &plaintext, blob.decompressed_len)
+        .map_err(|e| StorageError::crypto(format!("Failed to decompress vault: {}", e)))
+}
+
 /// 创建纪元盐值用于 DEK 派生
 ///
 /// 使用纪元版本号创建一个确定性但唯一的盐值。
@@ -253,13 +621,14 @@ fn create_epoch_salt(epoch: &CryptoEpoch) -> [u8; 32] {
 ///
 /// 创建临时文件并写入新纪元数据：
 /// 1. 创建 `vault.tmp` 临时文件
-/// 2. 写入 Header_n+1 与 VaultBlob
+/// 2. 写入 Header_n+1、VaultBlob 与 VK 区域
 /// 3. 强制 fsync 确保数据物理落盘
 ///
 /// **关键安全保证**:
 /// - 使用 ShadowWriter 确保临时文件在同一目录
 /// - 所有写入后调用 `write_and_sync()` 强制落盘
 /// - ShadowFile 实现自动清理（未提交时删除）
+/// - 写入期间持有 [`VaultLock`]（当前线程已持有时沿用）
 ///
 /// # Arguments
 ///
@@ -282,6 +651,8 @@ fn create_epoch_salt(epoch: &CryptoEpoch) -> [u8; 32] {
 /// 返回 `StorageError::FsyncFailed` 如果：
 /// - `fsync()` 系统调用失败（硬件错误、文件系统损坏）
 ///
+/// 返回 `StorageError::VaultLocked` 如果其他写入者持有 Vault 锁。
+///
 /// # Example
 ///
 /// ```no_run
@@ -308,50 +679,102 @@ pub fn aup_shadow_write(
     vault_path: impl AsRef<Path>,
     preparation: &AupPreparation,
 ) -> Result<ShadowFile, StorageError> {
-    let vault_path = vault_path.as_ref();
+    aup_shadow_write_with_progress(vault_path, preparation, |_, _| {})
+}
 
-    // 创建影子写入器
-    let writer = ShadowWriter::new(vault_path);
+/// AUP 阶段 2：影子写入，并报告写入进度
+///
+/// 与 [`aup_shadow_write`] 相同，但 Blob 按 [`AUP_PROGRESS_INTERVAL`] 分块写入，
+/// 写完 Header、每个分块及 VK 区域后调用 `on_progress(bytes_done, bytes_total)`。
+/// 报告的字节数单调不减，最后一次等于 `bytes_total`（fsync 之前）。
+///
+/// # Errors
+///
+/// 与 [`aup_shadow_write`] 相同。
+pub fn aup_shadow_write_with_progress<F>(
+    vault_path: impl AsRef<Path>,
+    preparation: &AupPreparation,
+    on_progress: F,
+) -> Result<ShadowFile, StorageError>
+where
+    F: FnMut(u64, u64),
+{
+    let _lock = VaultLock::hold(&vault_path)?;
+    aup_shadow_write_to(&FileBackend, vault_path, preparation, on_progress)
+}
+
+/// AUP 阶段 2：在指定存储后端上影子写入
+///
+/// 与 [`aup_shadow_write_with_progress`] 相同，但影子写入由 `backend`
+/// 暂存，返回后端的暂存写入句柄；未提交即丢弃时写入被撤销。不获取
+/// [`VaultLock`]：写入者之间的互斥由 `backend` 负责。
+///
+/// # Errors
+///
+/// 与 [`aup_shadow_write`] 相同。
+pub fn aup_shadow_write_to<B, F>(
+    backend: &B,
+    vault_path: impl AsRef<Path>,
+    preparation: &AupPreparation,
+    mut on_progress: F,
+) -> Result<B::Shadow, StorageError>
+where
+    B: VaultBackend + ?Sized,
+    F: FnMut(u64, u64),
+{
+    let vault_path = vault_path.as_ref();
+    let vk_region = preparation.vk_region();
+    let bytes_total =
+        (preparation.header.len() + preparation.prepared_blob.len() + vk_region.len()) as u64;
 
-    // 开始影子写入（创建 .tmp 文件）
-    let mut shadow_file = writer.begin_shadow_write()?;
+    // 开始影子写入（文件后端创建 .tmp 文件）
+    let mut shadow = backend.begin_shadow_write(vault_path)?;
 
     // 写入 Vault Header（固定 32 字节）
-    shadow_file.write_all(&preparation.header).map_err(|e| {
+    shadow.write_all(&preparation.header).map_err(|e| {
         StorageError::shadow_write(format!(
-            "Failed to write header to {}: {}",
-            shadow_file.path().display(),
+            "Failed to write header to shadow of {}: {}",
+            vault_path.display(),
             e
         ))
     })?;
+    let mut bytes_done = preparation.header.len() as u64;
+    on_progress(bytes_done, bytes_total);
 
-    // 写入 VaultBlob（序列化的数据）
-    shadow_file
-        .write_all(&preparation.prepared_blob)
-        .map_err(|e| {
+    // 分块写入 VaultBlob（序列化的数据）
+    for chunk in preparation.prepared_blob.chunks(AUP_PROGRESS_INTERVAL) {
+        shadow.write_all(chunk).map_err(|e| {
             StorageError::shadow_write(format!(
-                "Failed to write blob to {}: {}",
-                shadow_file.path().display(),
+                "Failed to write blob to shadow of {}: {}",
+                vault_path.display(),
                 e
             ))
         })?;
+        bytes_done += chunk.len() as u64;
+        on_progress(bytes_done, bytes_total);
+    }
 
-    // 强制 fsync - 确保数据物理落盘
-    shadow_file.file().sync_all().map_err(|e| {
-        StorageError::fsync(format!(
-            "Failed to fsync {}: {}",
-            shadow_file.path().display(),
+    // 写入 VK 区域（紧随 Blob，Header 的 data_length 不包含此区域）
+    shadow.write_all(&vk_region).map_err(|e| {
+        StorageError::shadow_write(format!(
+            "Failed to write vault key to shadow of {}: {}",
+            vault_path.display(),
             e
         ))
     })?;
+    bytes_done += vk_region.len() as u64;
+    on_progress(bytes_done, bytes_total);
+
+    // 强制 fsync - 确保数据物理落盘
+    backend.sync_shadow(&shadow)?;
 
     eprintln!(
         "[AUP] Shadow write completed: {} (epoch {})",
-        shadow_file.path().display(),
+        vault_path.display(),
         preparation.new_epoch.version
     );
 
-    Ok(shadow_file)
+    Ok(shadow)
 }
 
 // ============================================================================
@@ -374,12 +797,14 @@ pub fn aup_shadow_write(
 ///
 /// - `vault_path`: 目标 Vault 文件路径（如 `vault.db`）
 /// - `shadow_file`: 阶段 2 返回的临时文件句柄
-/// - `new_epoch`: 新纪元版本（用于元数据更新）
+/// - `new_epoch`: 新纪元版本（写入 `Local_Epoch`）
+/// - `metadata`: 元数据库，重命名成功后在事务中更新 `Local_Epoch`
 ///
 /// # Returns
 ///
-/// - `Ok(())` 如果原子提交成功
-/// - `Err(StorageError::AtomicRenameFailed(..))` 如果重命名失败
+/// - `Ok(())` 如果原子提交及元数据更新成功
+/// - `Err(StorageError::AtomicRenameFailed(..))` 如果重命名失败（Vault 未改变）
+/// - `Err(StorageError::MetadataFailed(..))` 如果重命名成功但元数据更新失败
 ///
 /// # Errors
 ///
@@ -389,21 +814,29 @@ pub fn aup_shadow_write(
 /// - 权限被拒绝
 /// - I/O 错误
 ///
+/// 返回 `StorageError::VaultLocked` 如果其他写入者持有 Vault 锁（当前线程
+/// 已持有时沿用该锁）。
+///
 /// **注意**: 元数据更新（SQLCipher）失败时：
-/// - Blob 已升级（物理文件已替换）
+/// - Blob 已升级（物理文件已替换），调用方不得回滚
 /// - 元数据记录旧纪元
-/// - 启动时触发自愈逻辑（CrashRecovery::heal_blob_ahead）
+/// - 启动时触发自愈逻辑（`CrashRecovery::recover` → `heal_blob_ahead`）
+///
+/// 反之，若元数据已更新而重命名失败，启动时由 `CrashRecovery::heal`
+/// 重新执行 `vault.tmp` → `vault.db` 的重命名。
 ///
 /// # Example
 ///
 /// ```no_run
 /// use aeternum_core::storage::aug::{aup_prepare, aup_shadow_write, aup_atomic_commit};
+/// use aeternum_core::storage::metadata::{MetadataStore, SqliteMetadataStore};
 /// use aeternum_core::models::CryptoEpoch;
 /// use aeternum_core::crypto::aead::XChaCha20Key;
 /// use std::path::Path;
 ///
 /// # fn main() -> Result<(), aeternum_core::storage::StorageError> {
 /// let vault_path = Path::new("vault.db");
+/// let mut metadata = SqliteMetadataStore::open("metadata.db")?;
 /// let current_epoch = CryptoEpoch::initial();
 /// let current_vk = vec![0u8; 48]; // 加密的 VK（32字节 VK + 16字节 tag）
 /// let current_dek = XChaCha20Key::generate();
@@ -416,44 +849,71 @@ pub fn aup_shadow_write(
 /// let shadow_file = aup_shadow_write(&vault_path, &preparation)?;
 ///
 /// // 阶段 3: 原子提交
-/// aup_atomic_commit(&vault_path, shadow_file, &preparation.new_epoch)?;
-/// // vault.db 现在包含新纪元数据
+/// aup_atomic_commit(&vault_path, shadow_file, &preparation.new_epoch, &mut metadata)?;
+/// // vault.db 现在包含新纪元数据，Local_Epoch 已同步
+/// assert_eq!(metadata.get_local_epoch()?, preparation.new_epoch.version);
 /// # Ok(())
 /// # }
 /// ```
 pub fn aup_atomic_commit(
     vault_path: impl AsRef<Path>,
     shadow_file: ShadowFile,
-    _new_epoch: &CryptoEpoch, // 暂未使用，占位符（未来用于元数据更新）
+    new_epoch: &CryptoEpoch,
+    metadata: &mut dyn MetadataStore,
 ) -> Result<(), StorageError> {
-    let vault_path = vault_path.as_ref();
-
-    // 创建影子写入器用于提交
-    let writer = ShadowWriter::new(vault_path);
+    let _lock = VaultLock::hold(&vault_path)?;
+    aup_atomic_commit_to(&FileBackend, vault_path, shadow_file, new_epoch, metadata)
+}
 
-    // 执行原子重命名（vault.tmp → vault.db）
-    writer.commit_shadow_write(shadow_file).map_err(|e| {
-        // 尝试清理临时文件
-        let temp_path = vault_path.with_extension("db.tmp");
-        let _ = std::fs::remove_file(&temp_path);
+/// AUP 阶段 3：在指定存储后端上原子提交
+///
+/// 与 [`aup_atomic_commit`] 相同，但由 `backend` 提交
+/// [`aup_shadow_write_to`] 返回的暂存写入。与 [`aup_shadow_write_to`]
+/// 一样不获取 [`VaultLock`]。
+///
+/// # Errors
+///
+/// 与 [`aup_atomic_commit`] 相同。
+pub fn aup_atomic_commit_to<B>(
+    backend: &B,
+    vault_path: impl AsRef<Path>,
+    shadow: B::Shadow,
+    new_epoch: &CryptoEpoch,
+    metadata: &mut dyn MetadataStore,
+) -> Result<(), StorageError>
+where
+    B: VaultBackend + ?Sized,
+{
+    let vault_path = vault_path.as_ref();
 
-        StorageError::atomic_rename(format!(
-            "Failed to atomic rename {} to {}: {}",
-            temp_path.display(),
-            vault_path.display(),
-            e
-        ))
-    })?;
+    // 执行原子替换（文件后端：vault.tmp → vault.db）；失败时暂存写入已清理
+    backend
+        .commit_shadow_write(vault_path, shadow)
+        .map_err(|e| {
+            StorageError::atomic_rename(format!(
+                "Failed to atomic rename shadow of {}: {}",
+                vault_path.display(),
+                e
+            ))
+        })?;
 
     eprintln!(
         "[AUP] Atomic commit completed: {} (epoch {})",
         vault_path.display(),
-        _new_epoch.version
+        new_epoch.version
     );
 
-    // TODO: 元数据更新（需要 SQLCipher 集成）
-    // let metadata_db = ...;
-    // metadata_db.update_epoch(_new_epoch.version)?;
+    // 更新 Local_Epoch；失败时 Blob 已领先，由下次启动的 CrashRecovery 自愈
+    metadata.set_local_epoch(new_epoch.version).map_err(|e| {
+        eprintln!(
+            "[AUP] Local_Epoch update failed after commit, will heal on next startup: {}",
+            e
+        );
+        StorageError::metadata(format!(
+            "Vault committed at epoch {} but Local_Epoch update failed: {}",
+            new_epoch.version, e
+        ))
+    })?;
 
     Ok(())
 }
@@ -464,8 +924,8 @@ pub fn aup_atomic_commit(
 
 /// 读取 Vault 文件中的纪元版本
 ///
-/// 从 Vault Header 中读取纪元版本号。
-/// Vault Header 格式：[Magic:8][Version:4][Epoch:8][Length:8][Reserved:4]
+/// 从 Vault Header 中读取纪元版本号，信任纪元字段之前先校验 Header 校验和。
+/// Vault Header 格式：[Magic:8][Version:4][Epoch:8][Length:8][Checksum:4]
 ///
 /// # Arguments
 ///
@@ -474,10 +934,14 @@ pub fn aup_atomic_commit(
 /// # Returns
 ///
 /// - `Ok(u64)` 纪元版本号
-/// - `Err(StorageError::ConsistencyCheckFailed(..))` 如果读取失败
+/// - `Err(StorageError::ConsistencyCheckFailed(..))` 如果读取失败或路径是符号链接
+/// - `Err(StorageError::HeaderChecksumMismatch(..))` 如果 Header 已损坏
 pub fn read_vault_epoch(vault_path: impl AsRef<Path>) -> Result<u64, StorageError> {
     let vault_path = vault_path.as_ref();
 
+    // 拒绝符号链接（不跟随链接）
+    reject_symlink(vault_path)?;
+
     // 检查文件是否存在
     if !vault_path.exists() {
         return Err(StorageError::consistency_check(format!(
@@ -504,18 +968,7 @@ pub fn read_vault_epoch(vault_path: impl AsRef<Path>) -> Result<u64, StorageErro
         ))
     })?;
 
-    // 验证魔数
-    if header_bytes[0..8] != VAULT_MAGIC {
-        return Err(StorageError::consistency_check(format!(
-            "Invalid vault magic bytes: expected {:?}, got {:?}",
-            VAULT_MAGIC.to_vec(),
-            &header_bytes[0..8].to_vec()
-        )));
-    }
-
-    // 提取纪元版本（字节 12-19）
-    let epoch_bytes = header_bytes[12..20].try_into().unwrap();
-    let epoch = u64::from_be_bytes(epoch_bytes);
+    let epoch = parse_vault_epoch(&header_bytes)?;
 
     eprintln!(
         "[AUP] Read vault epoch: {} from {}",
@@ -526,6 +979,191 @@ pub fn read_vault_epoch(vault_path: impl AsRef<Path>) -> Result<u64, StorageErro
     Ok(epoch)
 }
 
+/// 读取完整的 Vault Blob
+///
+/// `aup_atomic_commit` 写入文件的逆操作：先读取并校验 32 字节 Header，
+/// 再反序列化其后的 [`VaultBlob`]。Header 以旧版模式解析（同 [`read_vault_epoch`]）。
+///
+/// 校验顺序：
+/// 1. Header 魔数与校验和
+/// 2. Header 之后的实际字节数等于 `data_length`（或再加一个 VK 区域）；
+///    基线写入的 version 1 文件为 `data_length + 8`
+/// 3. Blob 可反序列化，且 Blob 纪元等于 Header 纪元
+///
+/// # Arguments
+///
+/// - `vault_path`: Vault 文件路径
+///
+/// # Returns
+///
+/// - `Ok(VaultBlob)` 反序列化后的 Blob
+/// - `Err(StorageError::ConsistencyCheckFailed(..))` 如果读取失败、路径是符号链接、
+///   魔数无效、长度不匹配、Blob 无法反序列化或纪元不一致
+/// - `Err(StorageError::HeaderChecksumMismatch(..))` 如果 Header 已损坏
+pub fn read_vault_blob(vault_path: impl AsRef<Path>) -> Result<VaultBlob, StorageError> {
+    let vault_path = vault_path.as_ref();
+
+    // 拒绝符号链接（不跟随链接）
+    reject_symlink(vault_path)?;
+
+    let bytes = std::fs::read(vault_path).map_err(|e| {
+        StorageError::consistency_check(format!(
+            "Failed to read vault file {}: {}",
+            vault_path.display(),
+            e
+        ))
+    })?;
+
+    let header = VaultHeader::from_bytes(&bytes)?;
+    let body = blob_bytes(&bytes, &header).ok_or_else(|| {
+        StorageError::consistency_check(format!(
+            "Vault length mismatch in {}: header declares {} bytes, found {}",
+            vault_path.display(),
+            header.data_length,
+            bytes.len() - VAULT_HEADER_LEN
+        ))
+    })?;
+
+    let blob = VaultBlob::deserialize(body).map_err(|e| {
+        StorageError::consistency_check(format!(
+            "Failed to deserialize vault blob from {}: {}",
+            vault_path.display(),
+            e
+        ))
+    })?;
+
+    if blob.epoch.version != header.epoch_version {
+        return Err(StorageError::consistency_check(format!(
+            "Vault epoch mismatch in {}: header epoch {}, blob epoch {}",
+            vault_path.display(),
+            header.epoch_version,
+            blob.epoch.version
+        )));
+    }
+
+    Ok(blob)
+}
+
+/// 已存储的 VK（上一次 AUP 写入的 VK 区域）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredVaultKey {
+    /// 使用当前纪元 DEK 加密的 VK
+    pub encrypted_vk: Vec<u8>,
+    /// 加密 VK 使用的 nonce
+    pub vk_nonce: [u8; 24],
+}
+
+/// 读取 Vault 文件中的 VK 区域
+///
+/// # Returns
+///
+/// - `Ok(Some(StoredVaultKey))` VK 区域存在
+/// - `Ok(None)` 文件在 VK 区域引入之前写入
+/// - `Err(StorageError::ConsistencyCheckFailed(..))` 如果读取失败、长度不匹配或区域魔数无效
+/// - `Err(StorageError::HeaderChecksumMismatch(..))` 如果 Header 已损坏
+pub fn read_vault_key(
+    vault_path: impl AsRef<Path>,
+) -> Result<Option<StoredVaultKey>, StorageError> {
+    let vault_path = vault_path.as_ref();
+
+    // 拒绝符号链接（不跟随链接）
+    reject_symlink(vault_path)?;
+
+    let bytes = std::fs::read(vault_path).map_err(|e| {
+        StorageError::consistency_check(format!(
+            "Failed to read vault file {}: {}",
+            vault_path.display(),
+            e
+        ))
+    })?;
+    parse_vault_key(&bytes)
+}
+
+/// 从 Vault 文件内容中解析 VK 区域
+///
+/// # Errors
+///
+/// 与 [`read_vault_key`] 相同。
+pub fn parse_vault_key(bytes: &[u8]) -> Result<Option<StoredVaultKey>, StorageError> {
+    let header = VaultHeader::from_bytes(bytes)?;
+    let blob = blob_bytes(bytes, &header).ok_or_else(|| {
+        StorageError::consistency_check(format!(
+            "Vault length mismatch: header declares {} bytes, found {}",
+            header.data_length,
+            bytes.len() - VAULT_HEADER_LEN
+        ))
+    })?;
+
+    let region = &bytes[VAULT_HEADER_LEN + blob.len()..];
+    if region.is_empty() {
+        return Ok(None);
+    }
+    if region[0..8] != VK_REGION_MAGIC {
+        return Err(StorageError::consistency_check(
+            "Invalid vault key region magic",
+        ));
+    }
+
+    Ok(Some(StoredVaultKey {
+        encrypted_vk: region[32..].to_vec(),
+        vk_nonce: region[8..32].try_into().unwrap(),
+    }))
+}
+
+/// 基线 `data_length` 未计入的密文长度前缀字节数
+const LEGACY_LENGTH_PREFIX_LEN: usize = std::mem::size_of::<u64>();
+
+/// 基线 Blob 中密文长度前缀的位置：blob_version(4) + epoch(20) 之后
+const LEGACY_CIPHERTEXT_LEN_AT: usize = 24;
+
+/// 基线 `data_length` 中除密文外的字节：blob_version、epoch、auth_tag 与 nonce
+const LEGACY_FIXED_LEN: u64 = 4 + 20 + 16 + 24;
+
+/// Header 之后的 Blob 字节
+///
+/// 文件在 Blob 之后要么结束，要么恰好是一个 VK 区域；否则返回 `None`。
+///
+/// 基线版本写入的 version 1 文件中 `data_length` 未计入 8 字节的密文长度
+/// 前缀，且没有 VK 区域：Header 之后恰好多出 8 字节、且该前缀与
+/// `data_length` 相符时，整个剩余部分即为 Blob。
+fn blob_bytes<'a>(bytes: &'a [u8], header: &VaultHeader) -> Option<&'a [u8]> {
+    let body = &bytes[VAULT_HEADER_LEN..];
+    let blob_len = usize::try_from(header.data_length).ok()?;
+    match body.len().checked_sub(blob_len)? {
+        0 | VK_REGION_LEN => Some(&body[..blob_len]),
+        LEGACY_LENGTH_PREFIX_LEN
+            if header.blob_version <= PRE_CHECKSUM_BLOB_VERSION
+                && legacy_ciphertext_len(body)? + LEGACY_FIXED_LEN == header.data_length =>
+        {
+            Some(body)
+        }
+        _ => None,
+    }
+}
+
+/// 基线 Blob 中记录的密文长度
+fn legacy_ciphertext_len(body: &[u8]) -> Option<u64> {
+    let prefix =
+        body.get(LEGACY_CIPHERTEXT_LEN_AT..LEGACY_CIPHERTEXT_LEN_AT + LEGACY_LENGTH_PREFIX_LEN)?;
+    Some(u64::from_le_bytes(prefix.try_into().ok()?))
+}
+
+/// 从 Vault 文件内容中解析纪元版本
+///
+/// `bytes` 至少包含完整的 32 字节 Vault Header；其后的内容被忽略。
+/// 以旧版模式解析：校验和字节全零的旧 Header 仍可打开，使现有 Vault 不受影响；
+/// 下一次 AUP 提交会写入带校验和的新 Header。
+///
+/// # Returns
+///
+/// - `Ok(u64)` 纪元版本号
+/// - `Err(StorageError::ConsistencyCheckFailed(..))` 如果 Header 不完整或魔数无效
+/// - `Err(StorageError::HeaderChecksumMismatch(..))` 如果 Header 校验和不匹配
+pub fn parse_vault_epoch(bytes: &[u8]) -> Result<u64, StorageError> {
+    let header = VaultHeader::from_bytes(bytes)?;
+    Ok(header.epoch_version)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -533,6 +1171,10 @@ pub fn read_vault_epoch(vault_path: impl AsRef<Path>) -> Result<u64, StorageErro
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::vault::VAULT_MAGIC;
+    use crate::storage::metadata::{InMemoryMetadataStore, SqliteMetadataStore};
+    use crate::storage::recovery::{ConsistencyState, CrashRecovery, VaultFile};
+    use crate::storage::shadow::ShadowWriter;
     use std::fs;
     use tempfile::TempDir;
 
@@ -617,6 +1259,38 @@ mod tests {
         assert!(shadow_file.path().to_string_lossy().ends_with(".tmp"));
     }
 
+    #[test]
+    fn test_aup_shadow_write_reports_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+
+        let epoch = CryptoEpoch::initial();
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = create_test_encrypted_vk(&[0u8; 32], &dek);
+        // 超过两个分块的 Vault 数据
+        let vault_data = vec![0x5Au8; AUP_PROGRESS_INTERVAL * 2 + 1000];
+
+        let prep = aup_prepare(&epoch, &encrypted_vk, &dek, &vault_data).unwrap();
+        let mut reports = Vec::new();
+        let shadow_file = aup_shadow_write_with_progress(&vault_path, &prep, |done, total| {
+            reports.push((done, total))
+        })
+        .unwrap();
+
+        let total = fs::metadata(shadow_file.path()).unwrap().len();
+        assert!(reports.len() >= 4);
+        assert!(reports.iter().all(|&(_, t)| t == total));
+        assert_eq!(reports.last().unwrap().0, total);
+
+        // 字节数单调递增，间隔不超过一个分块
+        let mut previous = 0;
+        for &(done, _) in &reports {
+            assert!(done > previous);
+            assert!(done - previous <= AUP_PROGRESS_INTERVAL as u64);
+            previous = done;
+        }
+    }
+
     #[test]
     fn test_aup_shadow_write_includes_header() {
         let temp_dir = TempDir::new().unwrap();
@@ -711,7 +1385,13 @@ mod tests {
         let shadow_file = aup_shadow_write(&vault_path, &prep).unwrap();
 
         // 提交
-        aup_atomic_commit(&vault_path, shadow_file, &prep.new_epoch).unwrap();
+        aup_atomic_commit(
+            &vault_path,
+            shadow_file,
+            &prep.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap();
 
         // 临时文件应该消失
         assert!(!vault_path.with_extension("db.tmp").exists());
@@ -741,7 +1421,13 @@ mod tests {
         let shadow_file = aup_shadow_write(&vault_path, &prep).unwrap();
 
         // 提交
-        aup_atomic_commit(&vault_path, shadow_file, &prep.new_epoch).unwrap();
+        aup_atomic_commit(
+            &vault_path,
+            shadow_file,
+            &prep.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap();
 
         // 验证纪元（应该是 100 = 99 + 1）
         let read_epoch = read_vault_epoch(&vault_path).unwrap();
@@ -774,7 +1460,12 @@ mod tests {
         fs::remove_file(&temp_path).unwrap();
 
         // 提交应该失败
-        let result = aup_atomic_commit(&vault_path, shadow_file, &prep.new_epoch);
+        let result = aup_atomic_commit(
+            &vault_path,
+            shadow_file,
+            &prep.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        );
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("rename"));
     }
@@ -796,7 +1487,13 @@ mod tests {
 
         let prep = aup_prepare(&epoch, &encrypted_vk, &dek, vault_data).unwrap();
         let shadow_file = aup_shadow_write(&vault_path, &prep).unwrap();
-        aup_atomic_commit(&vault_path, shadow_file, &prep.new_epoch).unwrap();
+        aup_atomic_commit(
+            &vault_path,
+            shadow_file,
+            &prep.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap();
 
         let read_epoch = read_vault_epoch(&vault_path).unwrap();
         // aup_prepare 会创建纪元 124 (123 + 1)
@@ -847,6 +1544,344 @@ mod tests {
             .contains("Invalid vault magic"));
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_read_vault_epoch_rejects_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let real_path = temp_dir.path().join("real.db");
+        let link_path = temp_dir.path().join("vault.db");
+
+        let mut header = [0u8; 32];
+        header[0..8].copy_from_slice(&VAULT_MAGIC);
+        fs::write(&real_path, header).unwrap();
+        std::os::unix::fs::symlink(&real_path, &link_path).unwrap();
+
+        let result = read_vault_epoch(&link_path);
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+        assert!(result.unwrap_err().to_string().contains("symlink"));
+    }
+
+    #[test]
+    fn test_read_vault_epoch_detects_corrupted_header() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = create_test_encrypted_vk(&[0u8; 32], &dek);
+        let prep = aup_prepare(&CryptoEpoch::initial(), &encrypted_vk, &dek, b"data").unwrap();
+
+        // 纪元字段单比特翻转
+        let mut header = prep.header;
+        header[19] ^= 0x01;
+        fs::write(&vault_path, header).unwrap();
+        assert!(matches!(
+            read_vault_epoch(&vault_path),
+            Err(StorageError::HeaderChecksumMismatch(_))
+        ));
+
+        // 长度字段单比特翻转
+        let mut header = prep.header;
+        header[20] ^= 0x80;
+        fs::write(&vault_path, header).unwrap();
+        assert!(matches!(
+            read_vault_epoch(&vault_path),
+            Err(StorageError::HeaderChecksumMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_vault_epoch_accepts_legacy_header() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+
+        // 校验和引入之前的 Header：保留字节全零
+        let mut header = [0u8; 32];
+        header[0..8].copy_from_slice(&VAULT_MAGIC);
+        header[12..20].copy_from_slice(&6u64.to_be_bytes());
+        fs::write(&vault_path, header).unwrap();
+
+        assert_eq!(read_vault_epoch(&vault_path).unwrap(), 6);
+    }
+
+    // ------------------------------------------------------------------------
+    // read_vault_blob() Tests
+    // ------------------------------------------------------------------------
+
+    // 辅助函数：提交一个 Vault 并返回其准备结果
+    fn commit_test_vault(vault_path: &Path) -> AupPreparation {
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = create_test_encrypted_vk(&[0u8; 32], &dek);
+        let prep =
+            aup_prepare(&CryptoEpoch::initial(), &encrypted_vk, &dek, b"vault data").unwrap();
+        let shadow_file = aup_shadow_write(vault_path, &prep).unwrap();
+        aup_atomic_commit(
+            vault_path,
+            shadow_file,
+            &prep.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap();
+        prep
+    }
+
+    #[test]
+    fn test_read_vault_blob() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let prep = commit_test_vault(&vault_path);
+
+        let blob = read_vault_blob(&vault_path).unwrap();
+        let expected = VaultBlob::deserialize(&prep.prepared_blob).unwrap();
+        assert_eq!(blob.epoch, prep.new_epoch);
+        assert_eq!(blob.ciphertext, expected.ciphertext);
+        assert_eq!(blob.nonce, expected.nonce);
+        assert_eq!(blob.auth_tag, expected.auth_tag);
+    }
+
+    #[test]
+    fn test_read_vault_blob_opens_baseline_vault() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+
+        // 基线写入的文件：单次 AEAD 密文，V1 纪元按变体索引 0 编码，
+        // Header 校验和全零且 data_length 未计入 8 字节密文长度前缀
+        let vk = [0x44u8; 32];
+        let vault_data = b"baseline vault data";
+        let nonce = XChaCha20Nonce::random();
+        let ciphertext = AeadCipher::new(&XChaCha20Key::from_bytes(&vk).unwrap())
+            .encrypt(&nonce, vault_data, None)
+            .unwrap();
+        let mut body = Vec::new();
+        body.extend_from_slice(&1u32.to_le_bytes());
+        body.extend_from_slice(&5u64.to_le_bytes());
+        body.extend_from_slice(&1_700_000_000_000u64.to_le_bytes());
+        body.extend_from_slice(&0u32.to_le_bytes());
+        body.extend_from_slice(&(ciphertext.len() as u64).to_le_bytes());
+        body.extend_from_slice(&ciphertext);
+        body.extend_from_slice(&ciphertext[ciphertext.len() - 16..]);
+        body.extend_from_slice(nonce.as_bytes());
+        let data_length = (4 + 20 + ciphertext.len() + 16 + 24) as u64;
+
+        let mut file = [0u8; VAULT_HEADER_LEN].to_vec();
+        file[0..8].copy_from_slice(&VAULT_MAGIC);
+        file[8..12].copy_from_slice(&1u32.to_be_bytes());
+        file[12..20].copy_from_slice(&5u64.to_be_bytes());
+        file[20..28].copy_from_slice(&data_length.to_be_bytes());
+        file.extend_from_slice(&body);
+        fs::write(&vault_path, &file).unwrap();
+
+        let blob = read_vault_blob(&vault_path).unwrap();
+        assert_eq!(blob.blob_version, 1);
+        assert_eq!(blob.epoch.version, 5);
+        assert_eq!(blob.epoch.algorithm, CryptoAlgorithm::V1);
+        assert_eq!(read_vault_key(&vault_path).unwrap(), None);
+
+        let dek = XChaCha20Key::generate();
+        let stored = StoredVaultKey {
+            vk_nonce: LEGACY_VK_NONCE,
+            encrypted_vk: AeadCipher::new(&dek)
+                .encrypt(&XChaCha20Nonce::from_bytes(LEGACY_VK_NONCE), &vk, None)
+                .unwrap(),
+        };
+        assert_eq!(open_vault(&blob, &stored, &dek).unwrap(), vault_data);
+
+        // 多出的 8 字节与密文长度前缀不符时仍视为长度不匹配
+        file[20..28].copy_from_slice(&(data_length + 1).to_be_bytes());
+        file.push(0);
+        fs::write(&vault_path, &file).unwrap();
+        assert!(read_vault_blob(&vault_path)
+            .unwrap_err()
+            .to_string()
+            .contains("length mismatch"));
+    }
+
+    #[test]
+    fn test_read_vault_blob_rejects_length_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        commit_test_vault(&vault_path);
+        let content = fs::read(&vault_path).unwrap();
+
+        // 截断一个字节
+        fs::write(&vault_path, &content[..content.len() - 1]).unwrap();
+        let result = read_vault_blob(&vault_path);
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+        assert!(result.unwrap_err().to_string().contains("length mismatch"));
+
+        // 追加一个字节
+        let mut extended = content.clone();
+        extended.push(0);
+        fs::write(&vault_path, &extended).unwrap();
+        assert!(read_vault_blob(&vault_path)
+            .unwrap_err()
+            .to_string()
+            .contains("length mismatch"));
+
+        // 只有 Header
+        fs::write(&vault_path, &content[..VAULT_HEADER_LEN]).unwrap();
+        assert!(read_vault_blob(&vault_path)
+            .unwrap_err()
+            .to_string()
+            .contains("length mismatch"));
+    }
+
+    #[test]
+    fn test_read_vault_blob_rejects_magic_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        commit_test_vault(&vault_path);
+
+        let mut content = fs::read(&vault_path).unwrap();
+        content[0..8].copy_from_slice(b"INVALID!");
+        fs::write(&vault_path, &content).unwrap();
+
+        let result = read_vault_blob(&vault_path);
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid vault magic"));
+    }
+
+    #[test]
+    fn test_read_vault_blob_rejects_epoch_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+
+        // Header 声明纪元 6，Blob 实际纪元 5（长度相同，校验和有效）
+        let blob = VaultBlob::new(
+            1,
+            CryptoEpoch::new(5, crate::models::CryptoAlgorithm::V1),
+            vec![0xAB; 64],
+            [0u8; 16],
+            [0u8; 24],
+        );
+        let mut claimed = blob.clone();
+        claimed.epoch.version = 6;
+
+        let mut content = VaultHeader::new(&claimed).to_bytes().to_vec();
+        content.extend_from_slice(&blob.serialize().unwrap());
+        fs::write(&vault_path, &content).unwrap();
+
+        let result = read_vault_blob(&vault_path);
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+        assert!(result.unwrap_err().to_string().contains("epoch mismatch"));
+    }
+
+    #[test]
+    fn test_read_vault_blob_fails_nonexistent() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = read_vault_blob(temp_dir.path().join("nonexistent.db"));
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+    }
+
+    // ------------------------------------------------------------------------
+    // read_vault_key() Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_upgraded_vault_key_decrypts_under_new_dek() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let vk = [0x7Cu8; 32];
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = create_test_encrypted_vk(&vk, &dek);
+        let mut metadata = InMemoryMetadataStore::default();
+
+        let prep =
+            aup_prepare(&CryptoEpoch::initial(), &encrypted_vk, &dek, b"vault data").unwrap();
+        let shadow_file = aup_shadow_write(&vault_path, &prep).unwrap();
+        aup_atomic_commit(&vault_path, shadow_file, &prep.new_epoch, &mut metadata).unwrap();
+
+        let stored = read_vault_key(&vault_path).unwrap().unwrap();
+        assert_eq!(stored.encrypted_vk, prep.encrypted_vk);
+        assert_eq!(stored.vk_nonce, prep.vk_nonce);
+        assert_ne!(stored.vk_nonce, LEGACY_VK_NONCE);
+
+        // 新 DEK 可解密 VK，旧 DEK 不可
+        let nonce = XChaCha20Nonce::from_bytes(stored.vk_nonce);
+        let decrypted = AeadCipher::new(&prep.new_dek)
+            .decrypt(&nonce, &stored.encrypted_vk, None)
+            .unwrap();
+        assert_eq!(decrypted, vk);
+        assert!(AeadCipher::new(&dek)
+            .decrypt(&nonce, &stored.encrypted_vk, None)
+            .is_err());
+
+        // Blob 读取不受 VK 区域影响
+        assert_eq!(read_vault_blob(&vault_path).unwrap().epoch, prep.new_epoch);
+
+        // 下一次 AUP 直接使用已存储的 VK
+        let next = aup_prepare_with_nonce(
+            &prep.new_epoch,
+            &stored.encrypted_vk,
+            &stored.vk_nonce,
+            &prep.new_dek,
+            b"vault data",
+        )
+        .unwrap();
+        let shadow_file = aup_shadow_write(&vault_path, &next).unwrap();
+        aup_atomic_commit(&vault_path, shadow_file, &next.new_epoch, &mut metadata).unwrap();
+
+        let stored = read_vault_key(&vault_path).unwrap().unwrap();
+        let decrypted = AeadCipher::new(&next.new_dek)
+            .decrypt(
+                &XChaCha20Nonce::from_bytes(stored.vk_nonce),
+                &stored.encrypted_vk,
+                None,
+            )
+            .unwrap();
+        assert_eq!(decrypted, vk);
+        assert_eq!(read_vault_epoch(&vault_path).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_read_vault_key_legacy_vault_has_none() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let prep = commit_test_vault(&vault_path);
+
+        // VK 区域引入之前的文件：Header + Blob
+        let mut legacy = prep.header.to_vec();
+        legacy.extend_from_slice(&prep.prepared_blob);
+        fs::write(&vault_path, &legacy).unwrap();
+
+        assert_eq!(read_vault_key(&vault_path).unwrap(), None);
+        assert_eq!(read_vault_blob(&vault_path).unwrap().epoch, prep.new_epoch);
+    }
+
+    #[test]
+    fn test_read_vault_key_rejects_invalid_region() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        commit_test_vault(&vault_path);
+
+        let mut content = fs::read(&vault_path).unwrap();
+        let region_start = content.len() - VK_REGION_LEN;
+        content[region_start] ^= 0xFF;
+        fs::write(&vault_path, &content).unwrap();
+
+        assert!(matches!(
+            read_vault_key(&vault_path),
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+    }
+
     // ------------------------------------------------------------------------
     // End-to-End AUP Flow Tests
     // ------------------------------------------------------------------------
@@ -855,6 +1890,7 @@ mod tests {
     fn test_aup_full_flow() {
         let temp_dir = TempDir::new().unwrap();
         let vault_path = temp_dir.path().join("vault.db");
+        let mut metadata = SqliteMetadataStore::open(temp_dir.path().join("metadata.db")).unwrap();
 
         let current_epoch = CryptoEpoch::initial();
         let current_dek = XChaCha20Key::generate();
@@ -871,13 +1907,332 @@ mod tests {
         assert!(shadow_file.path().exists());
 
         // 阶段 3: 原子提交
-        aup_atomic_commit(&vault_path, shadow_file, &prep.new_epoch).unwrap();
+        aup_atomic_commit(&vault_path, shadow_file, &prep.new_epoch, &mut metadata).unwrap();
         assert!(vault_path.exists());
         assert!(!vault_path.with_extension("db.tmp").exists());
 
-        // 验证结果
+        // 验证结果：Blob 与 Local_Epoch 一致
         let final_epoch = read_vault_epoch(&vault_path).unwrap();
         assert_eq!(final_epoch, prep.new_epoch.version);
+        assert_eq!(metadata.get_local_epoch().unwrap(), final_epoch);
+    }
+
+    /// 元数据写入总是失败的存储（模拟重命名后、元数据更新前崩溃）
+    struct FailingMetadataStore;
+
+    impl MetadataStore for FailingMetadataStore {
+        fn get_local_epoch(&self) -> Result<u64, StorageError> {
+            Ok(0)
+        }
+
+        fn set_local_epoch(&mut self, _epoch: u64) -> Result<(), StorageError> {
+            Err(StorageError::metadata("simulated crash"))
+        }
+
+        fn get_device_count(&self) -> Result<u32, StorageError> {
+            Ok(0)
+        }
+
+        fn set_device_count(&mut self, _count: u32) -> Result<(), StorageError> {
+            Err(StorageError::metadata("simulated crash"))
+        }
+    }
+
+    #[test]
+    fn test_aup_metadata_failure_after_rename_heals_on_startup() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let metadata_path = temp_dir.path().join("metadata.db");
+
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = create_test_encrypted_vk(&[0u8; 32], &dek);
+
+        // 纪元 2 完整提交
+        let prep1 = aup_prepare(&CryptoEpoch::initial(), &encrypted_vk, &dek, b"data").unwrap();
+        let shadow1 = aup_shadow_write(&vault_path, &prep1).unwrap();
+        let mut metadata = SqliteMetadataStore::open(&metadata_path).unwrap();
+        aup_atomic_commit(&vault_path, shadow1, &prep1.new_epoch, &mut metadata).unwrap();
+        drop(metadata);
+
+        // 纪元 3：重命名成功，元数据更新失败
+        let prep2 = aup_prepare(&prep1.new_epoch, &encrypted_vk, &dek, b"data").unwrap();
+        let shadow2 = aup_shadow_write(&vault_path, &prep2).unwrap();
+        let result = aup_atomic_commit(
+            &vault_path,
+            shadow2,
+            &prep2.new_epoch,
+            &mut FailingMetadataStore,
+        );
+        assert!(matches!(result, Err(StorageError::MetadataFailed(_))));
+        assert_eq!(read_vault_epoch(&vault_path).unwrap(), 3);
+
+        // 下次启动：CrashRecovery 发现 BlobAhead 并自愈
+        let metadata = SqliteMetadataStore::open(&metadata_path).unwrap();
+        assert_eq!(metadata.get_local_epoch().unwrap(), 2);
+        let recovery = CrashRecovery::new(metadata, VaultFile::new(&vault_path));
+        let state = recovery.recover(&vault_path).unwrap();
+        assert_eq!(
+            state,
+            ConsistencyState::BlobAhead {
+                blob_epoch: 3,
+                metadata_epoch: 2
+            }
+        );
+        assert_eq!(
+            recovery.recover(&vault_path).unwrap(),
+            ConsistencyState::Consistent
+        );
+
+        let metadata = SqliteMetadataStore::open(&metadata_path).unwrap();
+        assert_eq!(metadata.get_local_epoch().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_open_vault_reads_one_shot_v1_blob() {
+        // 基线写入的 version 1 Blob：整个 vault 数据为一条 AEAD 消息
+        let vk = [0x22u8; 32];
+        let dek = XChaCha20Key::generate();
+        let vault_data: Vec<u8> = (0..(2 * stream::STREAM_CHUNK_SIZE + 7))
+            .map(|i| i as u8)
+            .collect();
+        let nonce = XChaCha20Nonce::random();
+        let ciphertext = AeadCipher::new(&XChaCha20Key::from_bytes(&vk).unwrap())
+            .encrypt(&nonce, &vault_data, None)
+            .unwrap();
+        let auth_tag = *AeadCipher::extract_tag(&ciphertext).unwrap().as_bytes();
+        let blob = VaultBlob::new(
+            1,
+            CryptoEpoch::initial(),
+            ciphertext,
+            auth_tag,
+            *nonce.as_bytes(),
+        );
+        let stored = StoredVaultKey {
+            vk_nonce: LEGACY_VK_NONCE,
+            encrypted_vk: AeadCipher::new(&dek)
+                .encrypt(&XChaCha20Nonce::from_bytes(LEGACY_VK_NONCE), &vk, None)
+                .unwrap(),
+        };
+
+        assert_eq!(open_vault(&blob, &stored, &dek).unwrap(), vault_data);
+    }
+
+    #[test]
+    fn test_aup_prepare_vault_data_is_stream_encrypted() {
+        let vk = [0x11u8; 32];
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = create_test_encrypted_vk(&vk, &dek);
+        let vault_data: Vec<u8> = (0..(3 * stream::STREAM_CHUNK_SIZE + 5))
+            .map(|i| i as u8)
+            .collect();
+
+        let prep = aup_prepare_with_algorithm(
+            &CryptoEpoch::initial(),
+            CryptoAlgorithm::V2,
+            &encrypted_vk,
+            &LEGACY_VK_NONCE,
+            &dek,
+            &vault_data,
+        )
+        .unwrap();
+        let blob = VaultBlob::deserialize(&prep.prepared_blob).unwrap();
+        assert_eq!(blob.blob_version, STREAM_BLOB_VERSION);
+
+        // 密文由 VK 与 blob nonce 分块加密，可按 STREAM 构造解密
+        let cipher = AeadCipher::new(&XChaCha20Key::from_bytes(&vk).unwrap());
+        let nonce = XChaCha20Nonce::from_bytes(blob.nonce);
+        assert_eq!(
+            stream::open(&cipher, &nonce, &blob.ciphertext).unwrap(),
+            vault_data
+        );
+        assert_eq!(
+            &blob.ciphertext[blob.ciphertext.len() - 16..],
+            &blob.auth_tag
+        );
+    }
+
+    // ------------------------------------------------------------------------
+    // Algorithm Migration Tests
+    // ------------------------------------------------------------------------
+
+    /// 提交一次 V1 -> V2 的纪元升级，返回 (VK, 预备结果)
+    fn commit_v2_upgrade(vault_path: &Path, vault_data: &[u8]) -> ([u8; 32], AupPreparation) {
+        let vk = [0x2Bu8; 32];
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = create_test_encrypted_vk(&vk, &dek);
+
+        let prep = aup_prepare_with_algorithm(
+            &CryptoEpoch::initial(),
+            CryptoAlgorithm::V2,
+            &encrypted_vk,
+            &LEGACY_VK_NONCE,
+            &dek,
+            vault_data,
+        )
+        .unwrap();
+        let shadow_file = aup_shadow_write(vault_path, &prep).unwrap();
+        aup_atomic_commit(
+            vault_path,
+            shadow_file,
+            &prep.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap();
+        (vk, prep)
+    }
+
+    #[test]
+    fn test_aup_v1_to_v2_upgrade_readable_under_v2() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (vk, prep) = commit_v2_upgrade(&vault_path, b"migrated data");
+
+        assert_eq!(prep.new_epoch.version, 2);
+        assert_eq!(prep.new_epoch.algorithm, CryptoAlgorithm::V2);
+
+        let blob = read_vault_blob(&vault_path).unwrap();
+        assert_eq!(blob.blob_version, CryptoAlgorithm::V2.blob_version());
+        assert_eq!(blob.epoch.algorithm, CryptoAlgorithm::V2);
+
+        // V2 DEK 由 BLAKE3 派生，与 V1 的 Argon2id 结果不同
+        let v2_dek = derive_epoch_dek(&vk, &blob.epoch).unwrap();
+        assert_eq!(v2_dek.as_bytes(), prep.new_dek.as_bytes());
+        let v1_dek = derive_epoch_dek(&vk, &CryptoEpoch::new(2, CryptoAlgorithm::V1)).unwrap();
+        assert_ne!(v1_dek.as_bytes(), v2_dek.as_bytes());
+
+        let stored = read_vault_key(&vault_path).unwrap().unwrap();
+        assert_eq!(
+            open_vault(&blob, &stored, &prep.new_dek).unwrap(),
+            b"migrated data"
+        );
+
+        // 后续升级沿用 V2
+        let next = aup_prepare_with_nonce(
+            &blob.epoch,
+            &stored.encrypted_vk,
+            &stored.vk_nonce,
+            &prep.new_dek,
+            b"next",
+        )
+        .unwrap();
+        assert_eq!(next.new_epoch.algorithm, CryptoAlgorithm::V2);
+    }
+
+    #[test]
+    fn test_v2_blob_opened_in_v1_mode_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let (vk, prep) = commit_v2_upgrade(&vault_path, b"migrated data");
+
+        let stored = read_vault_key(&vault_path).unwrap().unwrap();
+        let mut blob = read_vault_blob(&vault_path).unwrap();
+        blob.epoch.algorithm = CryptoAlgorithm::V1;
+
+        // 按 V1 规则：DEK 用 Argon2id 派生，VK 无关联数据
+        let v1_dek = derive_epoch_dek(&vk, &blob.epoch).unwrap();
+        assert!(matches!(
+            open_vault(&blob, &stored, &v1_dek),
+            Err(StorageError::CryptoFailed(_))
+        ));
+        // 即使持有正确的 DEK，V1 规则下 VK 关联数据也不匹配
+        assert!(matches!(
+            open_vault(&blob, &stored, &prep.new_dek),
+            Err(StorageError::CryptoFailed(_))
+        ));
+        // V1 规则下的 AUP 同样无法解封 VK
+        assert!(matches!(
+            aup_prepare_with_nonce(
+                &blob.epoch,
+                &stored.encrypted_vk,
+                &stored.vk_nonce,
+                &prep.new_dek,
+                b"next",
+            ),
+            Err(StorageError::CryptoFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_aup_rejects_algorithm_downgrade() {
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = create_test_encrypted_vk(&[0u8; 32], &dek);
+        let current = CryptoEpoch::new(4, CryptoAlgorithm::V2);
+
+        let result = aup_prepare_with_algorithm(
+            &current,
+            CryptoAlgorithm::V1,
+            &encrypted_vk,
+            &LEGACY_VK_NONCE,
+            &dek,
+            b"data",
+        );
+        assert!(matches!(result, Err(StorageError::InvariantViolation(_))));
+    }
+
+    #[test]
+    fn test_aup_full_flow_in_memory_backend() {
+        use crate::storage::backend::{InMemoryBackend, VaultBackend};
+
+        let backend = InMemoryBackend::new();
+        let vault_path = Path::new("vault.db");
+        let mut metadata = InMemoryMetadataStore::default();
+
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = create_test_encrypted_vk(&[0u8; 32], &dek);
+        let mut epoch = CryptoEpoch::initial();
+
+        for round in 0..3 {
+            let prep = aup_prepare(&epoch, &encrypted_vk, &dek, b"vault data").unwrap();
+
+            // 阶段 2：暂存写入在提交前不可见
+            let mut reports = Vec::new();
+            let shadow = aup_shadow_write_to(&backend, vault_path, &prep, |done, total| {
+                reports.push((done, total))
+            })
+            .unwrap();
+            let total = (prep.header.len() + prep.prepared_blob.len() + VK_REGION_LEN) as u64;
+            assert_eq!(reports.last(), Some(&(total, total)));
+            if round == 0 {
+                assert!(!backend.exists(vault_path));
+            } else {
+                assert_eq!(backend.read_epoch(vault_path).unwrap(), epoch.version);
+            }
+
+            // 阶段 3：原子提交并同步 Local_Epoch
+            aup_atomic_commit_to(&backend, vault_path, shadow, &prep.new_epoch, &mut metadata)
+                .unwrap();
+
+            let content = backend.read(vault_path).unwrap();
+            assert_eq!(&content[..32], &prep.header);
+            let blob_end = 32 + prep.prepared_blob.len();
+            assert_eq!(&content[32..blob_end], prep.prepared_blob.as_slice());
+            assert_eq!(&content[blob_end..], &prep.vk_region());
+            assert_eq!(
+                backend.read_epoch(vault_path).unwrap(),
+                prep.new_epoch.version
+            );
+            assert_eq!(metadata.get_local_epoch().unwrap(), prep.new_epoch.version);
+
+            epoch = prep.new_epoch;
+        }
+        assert_eq!(epoch.version, CryptoEpoch::initial().version + 3);
+    }
+
+    #[test]
+    fn test_aup_in_memory_uncommitted_shadow_is_discarded() {
+        use crate::storage::backend::{InMemoryBackend, VaultBackend};
+
+        let backend = InMemoryBackend::new();
+        let vault_path = Path::new("vault.db");
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = create_test_encrypted_vk(&[0u8; 32], &dek);
+
+        let prep = aup_prepare(&CryptoEpoch::initial(), &encrypted_vk, &dek, b"data").unwrap();
+        let shadow = aup_shadow_write_to(&backend, vault_path, &prep, |_, _| {}).unwrap();
+        drop(shadow);
+
+        assert!(!backend.exists(vault_path));
+        assert!(backend.read_epoch(vault_path).is_err());
     }
 
     #[test]
@@ -891,6 +2246,7 @@ mod tests {
         let current_vk = [0u8; 32];
         let encrypted_vk = create_test_encrypted_vk(&current_vk, &current_dek);
         let vault_data = b"test vault data";
+        let mut metadata = InMemoryMetadataStore::default();
 
         // 执行 3 次纪元升级
         for _ in 1..=3 {
@@ -898,10 +2254,11 @@ mod tests {
             // 这里为了测试简化，我们使用相同的 DEK 和 VK
             let prep = aup_prepare(&epoch, &encrypted_vk, &current_dek, vault_data).unwrap();
             let shadow_file = aup_shadow_write(&vault_path, &prep).unwrap();
-            aup_atomic_commit(&vault_path, shadow_file, &prep.new_epoch).unwrap();
+            aup_atomic_commit(&vault_path, shadow_file, &prep.new_epoch, &mut metadata).unwrap();
 
             let read_epoch = read_vault_epoch(&vault_path).unwrap();
             assert_eq!(read_epoch, epoch.version + 1);
+            assert_eq!(metadata.local_epoch, read_epoch);
 
             epoch = prep.new_epoch;
         }
@@ -925,7 +2282,13 @@ mod tests {
         // 创建初始数据
         let prep1 = aup_prepare(&epoch, &encrypted_vk, &dek, vault_data).unwrap();
         let shadow1 = aup_shadow_write(&vault_path, &prep1).unwrap();
-        aup_atomic_commit(&vault_path, shadow1, &prep1.new_epoch).unwrap();
+        aup_atomic_commit(
+            &vault_path,
+            shadow1,
+            &prep1.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap();
 
         let content1 = fs::read(&vault_path).unwrap();
         // 验证文件格式：[Header:32][Blob...]
@@ -940,7 +2303,13 @@ mod tests {
         // 升级到新纪元
         let prep2 = aup_prepare(&prep1.new_epoch, &encrypted_vk, &dek, vault_data).unwrap();
         let shadow2 = aup_shadow_write(&vault_path, &prep2).unwrap();
-        aup_atomic_commit(&vault_path, shadow2, &prep2.new_epoch).unwrap();
+        aup_atomic_commit(
+            &vault_path,
+            shadow2,
+            &prep2.new_epoch,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap();
 
         let content2 = fs::read(&vault_path).unwrap();
         assert!(content2.len() > 32);
@@ -949,4 +2318,161 @@ mod tests {
         let epoch2_val = u64::from_be_bytes(epoch_bytes2.try_into().unwrap());
         assert_eq!(epoch2_val, 3);
     }
+
+    // ------------------------------------------------------------------------
+    // Compression Tests
+    // ------------------------------------------------------------------------
+
+    /// 可压缩的 JSON 风格数据
+    fn json_vault_data(len: usize) -> Vec<u8> {
+        br#"{"site":"example.com","user":"alice","note":"shared"},"#
+            .iter()
+            .copied()
+            .cycle()
+            .take(len)
+            .collect()
+    }
+
+    /// 以 `algorithm` 与 `compression` 预备一次升级，返回 (预备结果, 已存储 VK)
+    fn prepare_compressed(
+        algorithm: CryptoAlgorithm,
+        compression: CompressionAlgo,
+        vault_data: &[u8],
+    ) -> (AupPreparation, StoredVaultKey) {
+        let dek = XChaCha20Key::generate();
+        let encrypted_vk = create_test_encrypted_vk(&[0x3Cu8; 32], &dek);
+        let prep = aup_prepare_with_compression(
+            &CryptoEpoch::initial(),
+            algorithm,
+            compression,
+            &encrypted_vk,
+            &LEGACY_VK_NONCE,
+            &dek,
+            vault_data,
+        )
+        .unwrap();
+        let stored = StoredVaultKey {
+            vk_nonce: prep.vk_nonce,
+            encrypted_vk: prep.encrypted_vk.clone(),
+        };
+        (prep, stored)
+    }
+
+    #[test]
+    fn test_aup_compression_roundtrip() {
+        let vault_data = json_vault_data(64 * 1024);
+
+        let (plain, plain_vk) =
+            prepare_compressed(CryptoAlgorithm::V2, CompressionAlgo::None, &vault_data);
+        let (packed, packed_vk) = prepare_compressed(
+            CryptoAlgorithm::V2,
+            CompressionAlgo::zstd_default(),
+            &vault_data,
+        );
+
+        let plain_blob = VaultBlob::deserialize(&plain.prepared_blob).unwrap();
+        let packed_blob = VaultBlob::deserialize(&packed.prepared_blob).unwrap();
+        assert_eq!(plain_blob.compression, CompressionAlgo::None);
+        assert_eq!(packed_blob.compression, CompressionAlgo::zstd_default());
+        assert_eq!(packed_blob.decompressed_len, vault_data.len() as u64);
+        assert!(packed_blob.ciphertext.len() * 5 < plain_blob.ciphertext.len());
+
+        // Header 的 data_length 覆盖压缩字段
+        let header = VaultHeader::from_bytes(&packed.header).unwrap();
+        assert_eq!(header.data_length, packed.prepared_blob.len() as u64);
+
+        assert_eq!(
+            open_vault(&plain_blob, &plain_vk, &plain.new_dek).unwrap(),
+            vault_data
+        );
+        assert_eq!(
+            open_vault(&packed_blob, &packed_vk, &packed.new_dek).unwrap(),
+            vault_data
+        );
+    }
+
+    #[test]
+    fn test_aup_compression_declared_size_authenticated() {
+        let vault_data = json_vault_data(8 * 1024);
+        let (prep, stored) = prepare_compressed(
+            CryptoAlgorithm::V2,
+            CompressionAlgo::zstd_default(),
+            &vault_data,
+        );
+        let blob = VaultBlob::deserialize(&prep.prepared_blob).unwrap();
+
+        // 声明长度被篡改：关联数据不匹配，解密失败
+        for declared in [blob.decompressed_len - 1, blob.decompressed_len * 1000] {
+            let mut lied = blob.clone();
+            lied.decompressed_len = declared;
+            assert!(matches!(
+                open_vault(&lied, &stored, &prep.new_dek),
+                Err(StorageError::CryptoFailed(_))
+            ));
+        }
+
+        // 去掉压缩标记同样被拒绝，不会把压缩数据当作明文返回
+        let mut stripped = blob.clone();
+        stripped.compression = CompressionAlgo::None;
+        stripped.decompressed_len = 0;
+        assert!(open_vault(&stripped, &stored, &prep.new_dek).is_err());
+
+        // 超出上限的声明长度在解密前即被拒绝
+        let mut bomb = blob;
+        bomb.decompressed_len = compression::MAX_DECOMPRESSED_LEN + 1;
+        assert!(matches!(
+            open_vault(&bomb, &stored, &prep.new_dek),
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_aup_compression_bypassed_for_tiny_payload() {
+        let vault_data = json_vault_data(compression::MIN_COMPRESS_LEN - 1);
+        let (prep, stored) = prepare_compressed(
+            CryptoAlgorithm::V2,
+            CompressionAlgo::zstd_default(),
+            &vault_data,
+        );
+        let blob = VaultBlob::deserialize(&prep.prepared_blob).unwrap();
+
+        assert_eq!(blob.compression, CompressionAlgo::None);
+        assert_eq!(blob.decompressed_len, 0);
+        assert_eq!(
+            open_vault(&blob, &stored, &prep.new_dek).unwrap(),
+            vault_data
+        );
+    }
+
+    #[test]
+    fn test_aup_compression_leaves_v1_blobs_unchanged() {
+        let vault_data = json_vault_data(16 * 1024);
+        let (prep, stored) = prepare_compressed(
+            CryptoAlgorithm::V1,
+            CompressionAlgo::zstd_default(),
+            &vault_data,
+        );
+        let blob = VaultBlob::deserialize(&prep.prepared_blob).unwrap();
+
+        // Version 1 Blob 无法记录压缩字段：保持原布局且不压缩
+        assert_eq!(blob.blob_version, 1);
+        assert_eq!(blob.compression, CompressionAlgo::None);
+        assert_eq!(prep.prepared_blob.len(), blob.size());
+        assert_eq!(
+            prep.prepared_blob.len(),
+            4 + blob.epoch.size() + 8 + blob.ciphertext.len() + 16 + 24
+        );
+
+        // 密文仍为原单次 AEAD 格式（无关联数据）
+        let cipher = AeadCipher::new(&XChaCha20Key::from_bytes(&[0x3Cu8; 32]).unwrap());
+        let nonce = XChaCha20Nonce::from_bytes(blob.nonce);
+        assert_eq!(
+            cipher.decrypt(&nonce, &blob.ciphertext, None).unwrap(),
+            vault_data
+        );
+        assert_eq!(
+            open_vault(&blob, &stored, &prep.new_dek).unwrap(),
+            vault_data
+        );
+    }
 }
@@ -0,0 +1,311 @@
+//! # Vault Storage Backends
+//!
+//! Abstracts where vault files live, so the AUP flow can run against the
+//! local filesystem or an alternative store.
+//!
+//! ## Backends
+//!
+//! - [`FileBackend`] - local filesystem through [`ShadowWriter`]
+//!   (temporary file + fsync + atomic rename)
+//! - [`InMemoryBackend`] - non-persistent store for tests
+//!
+//! ## Write Model
+//!
+//! Writes are two-phase, mirroring AUP phases 2 and 3: a shadow write is
+//! staged with [`VaultBackend::begin_shadow_write`], made durable with
+//! [`VaultBackend::sync_shadow`], and only becomes visible at its target
+//! path on [`VaultBackend::commit_shadow_write`]. A shadow dropped without
+//! being committed is discarded. [`VaultBackend::atomic_write`] runs all
+//! three steps at once.
+//!
+//! ## Example
+//!
+//! ```
+//! use aeternum_core::storage::backend::{InMemoryBackend, VaultBackend};
+//! use std::path::Path;
+//!
+//! let backend = InMemoryBackend::new();
+//! backend.atomic_write(Path::new("vault.db"), b"data")?;
+//! assert_eq!(backend.read(Path::new("vault.db"))?, b"data");
+//! # Ok::<(), aeternum_core::storage::StorageError>(())
+//! ```
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use parking_lot::Mutex;
+
+use super::aug::{parse_vault_epoch, read_vault_epoch};
+use super::error::StorageError;
+use super::shadow::{reject_symlink, ShadowFile, ShadowWriter};
+
+// ============================================================================
+// VaultBackend
+// ============================================================================
+
+/// Store for vault files with atomic replacement
+pub trait VaultBackend {
+    /// Staged write for one target path
+    ///
+    /// Dropping it without [`commit_shadow_write`](Self::commit_shadow_write)
+    /// discards the staged bytes.
+    type Shadow: Write;
+
+    /// Stage a new version of `path`
+    ///
+    /// The current contents of `path` stay visible until the commit.
+    fn begin_shadow_write(&self, path: &Path) -> Result<Self::Shadow, StorageError>;
+
+    /// Make the bytes written to `shadow` durable
+    ///
+    /// # Errors
+    ///
+    /// Returns `StorageError::FsyncFailed` if the bytes cannot be persisted.
+    fn sync_shadow(&self, shadow: &Self::Shadow) -> Result<(), StorageError>;
+
+    /// Atomically replace `path` with the staged bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns `StorageError::AtomicRenameFailed` if the replacement fails;
+    /// `path` is then unchanged.
+    fn commit_shadow_write(&self, path: &Path, shadow: Self::Shadow) -> Result<(), StorageError>;
+
+    /// Atomically replace `path` with `bytes`
+    ///
+    /// Readers see either the old or the new contents, never a mix.
+    fn atomic_write(&self, path: &Path, bytes: &[u8]) -> Result<(), StorageError> {
+        let mut shadow = self.begin_shadow_write(path)?;
+        shadow.write_all(bytes).map_err(|e| {
+            StorageError::shadow_write(format!(
+                "Failed to write shadow of {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        self.sync_shadow(&shadow)?;
+        self.commit_shadow_write(path, shadow)
+    }
+
+    /// Read the full contents of `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `StorageError::ConsistencyCheckFailed` if `path` does not
+    /// exist or cannot be read.
+    fn read(&self, path: &Path) -> Result<Vec<u8>, StorageError>;
+
+    /// Read the epoch from the vault header at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `StorageError::ConsistencyCheckFailed` if the file is missing,
+    /// shorter than a header, or has the wrong magic bytes.
+    fn read_epoch(&self, path: &Path) -> Result<u64, StorageError> {
+        parse_vault_epoch(&self.read(path)?)
+    }
+}
+
+// ============================================================================
+// FileBackend
+// ============================================================================
+
+/// Local filesystem backend
+///
+/// Shadow writes go through [`ShadowWriter`], so the target is replaced by
+/// an atomic rename, the parent directory is synced, and symlinked paths
+/// are refused.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileBackend;
+
+impl VaultBackend for FileBackend {
+    type Shadow = ShadowFile;
+
+    fn begin_shadow_write(&self, path: &Path) -> Result<ShadowFile, StorageError> {
+        ShadowWriter::new(path).begin_shadow_write()
+    }
+
+    fn sync_shadow(&self, shadow: &ShadowFile) -> Result<(), StorageError> {
+        shadow.sync().map_err(|e| {
+            StorageError::fsync(format!(
+                "Failed to fsync {}: {}",
+                shadow.path().display(),
+                e
+            ))
+        })
+    }
+
+    fn commit_shadow_write(&self, path: &Path, shadow: ShadowFile) -> Result<(), StorageError> {
+        ShadowWriter::new(path).commit_shadow_write(shadow)
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>, StorageError> {
+        reject_symlink(path)?;
+        std::fs::read(path).map_err(|e| {
+            StorageError::consistency_check(format!(
+                "Failed to read vault file {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    fn read_epoch(&self, path: &Path) -> Result<u64, StorageError> {
+        // Only the fixed-size header is read, not the whole blob
+        read_vault_epoch(path)
+    }
+}
+
+// ============================================================================
+// InMemoryBackend
+// ============================================================================
+
+/// Non-persistent backend for tests
+///
+/// Files live in a map keyed by path. A commit swaps the entry in one step,
+/// so it is atomic with respect to concurrent readers.
+#[derive(Debug, Default)]
+pub struct InMemoryBackend {
+    /// Committed file contents
+    files: Mutex<HashMap<PathBuf, Vec<u8>>>,
+}
+
+impl InMemoryBackend {
+    /// Create an empty backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check whether `path` holds committed contents
+    pub fn exists(&self, path: &Path) -> bool {
+        self.files.lock().contains_key(path)
+    }
+}
+
+/// Staged write of an [`InMemoryBackend`]
+#[derive(Debug, Default)]
+pub struct InMemoryShadow {
+    /// Bytes written so far
+    bytes: Vec<u8>,
+}
+
+impl Write for InMemoryShadow {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.bytes.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl VaultBackend for InMemoryBackend {
+    type Shadow = InMemoryShadow;
+
+    fn begin_shadow_write(&self, _path: &Path) -> Result<InMemoryShadow, StorageError> {
+        Ok(InMemoryShadow::default())
+    }
+
+    fn sync_shadow(&self, _shadow: &InMemoryShadow) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    fn commit_shadow_write(&self, path: &Path, shadow: InMemoryShadow) -> Result<(), StorageError> {
+        self.files.lock().insert(path.to_path_buf(), shadow.bytes);
+        Ok(())
+    }
+
+    fn read(&self, path: &Path) -> Result<Vec<u8>, StorageError> {
+        self.files.lock().get(path).cloned().ok_or_else(|| {
+            StorageError::consistency_check(format!(
+                "Vault file does not exist: {}",
+                path.display()
+            ))
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::vault::VAULT_MAGIC;
+    use tempfile::TempDir;
+
+    fn header(epoch: u64) -> Vec<u8> {
+        let mut header = vec![0u8; 32];
+        header[0..8].copy_from_slice(&VAULT_MAGIC);
+        header[12..20].copy_from_slice(&epoch.to_be_bytes());
+        header
+    }
+
+    #[test]
+    fn test_in_memory_atomic_write_and_read() {
+        let backend = InMemoryBackend::new();
+        let path = Path::new("vault.db");
+
+        assert!(backend.read(path).is_err());
+
+        backend.atomic_write(path, &header(7)).unwrap();
+        assert!(backend.exists(path));
+        assert_eq!(backend.read(path).unwrap(), header(7));
+        assert_eq!(backend.read_epoch(path).unwrap(), 7);
+
+        backend.atomic_write(path, &header(8)).unwrap();
+        assert_eq!(backend.read_epoch(path).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_in_memory_shadow_invisible_until_commit() {
+        let backend = InMemoryBackend::new();
+        let path = Path::new("vault.db");
+        backend.atomic_write(path, b"old").unwrap();
+
+        let mut shadow = backend.begin_shadow_write(path).unwrap();
+        shadow.write_all(b"new").unwrap();
+        assert_eq!(backend.read(path).unwrap(), b"old");
+
+        // Dropping an uncommitted shadow discards it
+        drop(shadow);
+        assert_eq!(backend.read(path).unwrap(), b"old");
+
+        let mut shadow = backend.begin_shadow_write(path).unwrap();
+        shadow.write_all(b"new").unwrap();
+        backend.commit_shadow_write(path, shadow).unwrap();
+        assert_eq!(backend.read(path).unwrap(), b"new");
+    }
+
+    #[test]
+    fn test_in_memory_read_epoch_rejects_bad_header() {
+        let backend = InMemoryBackend::new();
+
+        backend.atomic_write(Path::new("short.db"), b"abc").unwrap();
+        assert!(matches!(
+            backend.read_epoch(Path::new("short.db")),
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+
+        backend
+            .atomic_write(Path::new("magic.db"), &[0u8; 32])
+            .unwrap();
+        assert!(matches!(
+            backend.read_epoch(Path::new("magic.db")),
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_file_backend_matches_in_memory() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("vault.db");
+
+        FileBackend.atomic_write(&path, &header(3)).unwrap();
+        assert_eq!(FileBackend.read(&path).unwrap(), header(3));
+        assert_eq!(FileBackend.read_epoch(&path).unwrap(), 3);
+        assert!(!temp_dir.path().join("vault.db.tmp").exists());
+    }
+}
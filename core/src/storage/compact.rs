@@ -0,0 +1,371 @@
+//! # Vault Compaction
+//!
+//! Reclaims space accumulated over many epoch upgrades:
+//! - Prunes headers of devices revoked more than `keep_epochs` epochs ago
+//! - Removes residual shadow-write temp files left by crashed writes
+//! - Rewrites the vault file in canonical form, dropping trailing bytes
+//!   other than the VK region
+//!
+//! ## Safety Guarantees
+//!
+//! - Refuses to run while a rekeying or recovery is in progress, since
+//!   both still depend on the current header set
+//! - The shadow anchor header is never pruned
+//! - Headers of active or degraded devices are never pruned (Invariant #2)
+//! - Every file is replaced via `ShadowWriter` (atomic rename); a crash at
+//!   any point leaves either the old or the new file, both readable
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use aeternum_core::models::CryptoEpoch;
+//! use aeternum_core::protocol::PqrrStateMachine;
+//! use aeternum_core::storage::compact::compact_vault;
+//! use std::collections::HashMap;
+//! use std::path::Path;
+//!
+//! let sm = PqrrStateMachine::create(CryptoEpoch::initial(), HashMap::new());
+//! let report = compact_vault(Path::new("vault.db"), 3, &sm)?;
+//! println!("Pruned {} headers, reclaimed {} bytes", report.headers_pruned, report.bytes_reclaimed);
+//! # Ok::<(), aeternum_core::storage::StorageError>(())
+//! ```
+
+use std::path::Path;
+
+use crate::models::device::{DeviceHeader, DeviceId, DeviceStatus};
+use crate::models::vault::{VaultBlob, VaultHeader};
+use crate::protocol::PqrrStateMachine;
+use crate::storage::aug::{VK_REGION_LEN, VK_REGION_MAGIC};
+use crate::storage::error::StorageError;
+use crate::storage::export::{
+    device_headers_path, read_device_headers, validate_vault_file, write_atomically,
+};
+use crate::storage::lock::VaultLock;
+use crate::storage::shadow::ShadowWriter;
+
+/// Size of the vault header at the start of a vault file
+const VAULT_HEADER_LEN: usize = 32;
+
+/// Result of a successful [`compact_vault`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactionReport {
+    /// Epoch version of the compacted vault
+    pub epoch: u64,
+    /// Number of device headers removed
+    pub headers_pruned: usize,
+    /// Devices whose headers were removed
+    pub pruned_devices: Vec<DeviceId>,
+    /// Bytes freed across the vault, header file and residual temp files
+    pub bytes_reclaimed: u64,
+}
+
+/// Compact a vault and prune long-revoked device headers
+///
+/// A revoked device's header is pruned once the vault epoch is more than
+/// `keep_epochs` past the header's epoch. `state` is the live state
+/// machine; compaction is refused while it holds a rekeying or recovery
+/// context.
+///
+/// # Errors
+///
+/// - `InvariantViolation` if a rekeying or recovery is in progress
+/// - `ConsistencyCheckFailed` if the vault or its headers are malformed
+/// - `VaultLocked` if another writer holds the vault's lock
+/// - `ShadowWriteFailed` / `AtomicRenameFailed` / `FsyncFailed` on I/O errors
+pub fn compact_vault(
+    vault_path: impl AsRef<Path>,
+    keep_epochs: u32,
+    state: &PqrrStateMachine,
+) -> Result<CompactionReport, StorageError> {
+    let vault_path = vault_path.as_ref();
+
+    if state.rekeying_context().is_some() {
+        return Err(StorageError::invariant(
+            "Compaction refused: epoch upgrade in progress",
+        ));
+    }
+    if state.recovery_context().is_some() {
+        return Err(StorageError::invariant(
+            "Compaction refused: recovery window active",
+        ));
+    }
+
+    let _lock = VaultLock::hold(vault_path)?;
+    let mut bytes_reclaimed = 0;
+
+    // Leftovers from crashed shadow writes
+    let headers_path = device_headers_path(vault_path);
+    for target in [vault_path, headers_path.as_path()] {
+        let temp_path = ShadowWriter::new(target).temp_path();
+        bytes_reclaimed += file_len(&temp_path);
+        ShadowWriter::cleanup_residual(&temp_path)?;
+    }
+
+    let vault_file = std::fs::read(vault_path).map_err(|e| {
+        StorageError::consistency_check(format!(
+            "Failed to read vault {}: {}",
+            vault_path.display(),
+            e
+        ))
+    })?;
+    let epoch = validate_vault_file(&vault_file)?;
+
+    // Prune headers first: if we crash before the vault rewrite, the vault
+    // is untouched and the header file is already in its final form
+    let headers = read_device_headers(vault_path)?;
+    let (kept, pruned): (Vec<DeviceHeader>, Vec<DeviceHeader>) = headers
+        .into_iter()
+        .partition(|header| !is_prunable(header, epoch, keep_epochs));
+
+    if !pruned.is_empty() {
+        let old_len = file_len(&headers_path);
+        let bytes = bincode::serialize(&kept).map_err(|e| {
+            StorageError::crypto(format!("Device header serialization failed: {}", e))
+        })?;
+        write_atomically(&headers_path, &[&bytes])?;
+        bytes_reclaimed += old_len.saturating_sub(bytes.len() as u64);
+    }
+
+    // Canonical rewrite drops anything trailing the serialized blob except
+    // the VK region the next epoch upgrade reads
+    let blob = VaultBlob::deserialize(&vault_file[VAULT_HEADER_LEN..])
+        .map_err(|e| StorageError::consistency_check(format!("Invalid vault blob: {}", e)))?;
+    let blob_bytes = blob
+        .serialize()
+        .map_err(|e| StorageError::crypto(format!("Failed to serialize blob: {}", e)))?;
+    let header_bytes = VaultHeader::new(&blob).to_bytes();
+    let vk_region = vk_region_after(&vault_file, VAULT_HEADER_LEN + blob_bytes.len());
+    let new_len = (header_bytes.len() + blob_bytes.len() + vk_region.len()) as u64;
+    if new_len < vault_file.len() as u64 {
+        write_atomically(vault_path, &[&header_bytes, &blob_bytes, vk_region])?;
+        bytes_reclaimed += vault_file.len() as u64 - new_len;
+    }
+
+    let pruned_devices: Vec<DeviceId> = pruned.iter().map(|h| h.device_id).collect();
+
+    eprintln!(
+        "[COMPACT] Compacted vault {} (epoch {}): pruned {} headers, reclaimed {} bytes",
+        vault_path.display(),
+        epoch,
+        pruned_devices.len(),
+        bytes_reclaimed
+    );
+
+    Ok(CompactionReport {
+        epoch,
+        headers_pruned: pruned_devices.len(),
+        pruned_devices,
+        bytes_reclaimed,
+    })
+}
+
+/// Whether `header` belongs to a device revoked more than `keep_epochs` ago
+fn is_prunable(header: &DeviceHeader, vault_epoch: u64, keep_epochs: u32) -> bool {
+    !header.device_id.is_shadow_anchor()
+        && header.status == DeviceStatus::Revoked
+        && vault_epoch.saturating_sub(header.epoch.version) > keep_epochs as u64
+}
+
+/// The VK region starting at `offset` in `vault_file`, or an empty slice if
+/// absent (including when `offset` lies past the end of the file)
+fn vk_region_after(vault_file: &[u8], offset: usize) -> &[u8] {
+    let region = offset
+        .checked_add(VK_REGION_LEN)
+        .and_then(|end| vault_file.get(offset..end));
+    match region {
+        Some(region) if region.starts_with(&VK_REGION_MAGIC) => region,
+        _ => &[],
+    }
+}
+
+/// Size of the file at `path`, or 0 if it does not exist
+fn file_len(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::kem::{KyberCipherText, KyberPublicKeyBytes};
+    use crate::models::epoch::{CryptoAlgorithm, CryptoEpoch};
+    use crate::storage::aug::read_vault_epoch;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn epoch(version: u64) -> CryptoEpoch {
+        CryptoEpoch::new(version, CryptoAlgorithm::V1)
+    }
+
+    fn write_vault(path: &Path, version: u64) -> Vec<u8> {
+        let blob = VaultBlob::new(
+            VaultBlob::CURRENT_BLOB_VERSION,
+            epoch(version),
+            vec![0xAB; 64],
+            [0x11; 16],
+            [0x22; 24],
+        );
+        let mut bytes = VaultHeader::new(&blob).to_bytes().to_vec();
+        bytes.extend_from_slice(&blob.serialize().unwrap());
+        std::fs::write(path, &bytes).unwrap();
+        bytes
+    }
+
+    fn header(device_id: DeviceId, version: u64, status: DeviceStatus) -> DeviceHeader {
+        let mut header = DeviceHeader::new(
+            device_id,
+            epoch(version),
+            KyberPublicKeyBytes([0u8; 1568]),
+            KyberCipherText([0u8; 1568]),
+        );
+        header.status = status;
+        header
+    }
+
+    fn write_headers(vault_path: &Path, headers: &[DeviceHeader]) {
+        std::fs::write(
+            device_headers_path(vault_path),
+            bincode::serialize(headers).unwrap(),
+        )
+        .unwrap();
+    }
+
+    fn idle_state(version: u64) -> PqrrStateMachine {
+        PqrrStateMachine::create(epoch(version), HashMap::new())
+    }
+
+    #[test]
+    fn test_prunes_by_revocation_age() {
+        let dir = TempDir::new().unwrap();
+        let vault_path = dir.path().join("vault.db");
+        write_vault(&vault_path, 10);
+
+        let active = header(DeviceId::generate(), 10, DeviceStatus::Active);
+        let recent = header(DeviceId::generate(), 8, DeviceStatus::Revoked);
+        let boundary = header(DeviceId::generate(), 7, DeviceStatus::Revoked);
+        let old = header(DeviceId::generate(), 3, DeviceStatus::Revoked);
+        write_headers(
+            &vault_path,
+            &[
+                active.clone(),
+                recent.clone(),
+                boundary.clone(),
+                old.clone(),
+            ],
+        );
+        let headers_before = file_len(&device_headers_path(&vault_path));
+
+        let report = compact_vault(&vault_path, 3, &idle_state(10)).unwrap();
+        assert_eq!(report.epoch, 10);
+        assert_eq!(report.headers_pruned, 1);
+        assert_eq!(report.pruned_devices, vec![old.device_id]);
+        assert!(report.bytes_reclaimed > 0);
+        assert!(file_len(&device_headers_path(&vault_path)) < headers_before);
+
+        let remaining = read_device_headers(&vault_path).unwrap();
+        assert_eq!(remaining, vec![active, recent, boundary]);
+
+        // Nothing left to prune
+        let report = compact_vault(&vault_path, 3, &idle_state(10)).unwrap();
+        assert_eq!(report.headers_pruned, 0);
+        assert_eq!(report.bytes_reclaimed, 0);
+    }
+
+    #[test]
+    fn test_shadow_anchor_always_retained() {
+        let dir = TempDir::new().unwrap();
+        let vault_path = dir.path().join("vault.db");
+        write_vault(&vault_path, 20);
+
+        let anchor = header(DeviceId::shadow_anchor(), 1, DeviceStatus::Revoked);
+        write_headers(&vault_path, std::slice::from_ref(&anchor));
+
+        let report = compact_vault(&vault_path, 0, &idle_state(20)).unwrap();
+        assert_eq!(report.headers_pruned, 0);
+        assert_eq!(read_device_headers(&vault_path).unwrap(), vec![anchor]);
+    }
+
+    #[test]
+    fn test_refuses_during_rekeying() {
+        let dir = TempDir::new().unwrap();
+        let vault_path = dir.path().join("vault.db");
+        let original = write_vault(&vault_path, 5);
+        write_headers(
+            &vault_path,
+            &[header(DeviceId::generate(), 1, DeviceStatus::Revoked)],
+        );
+
+        let mut sm = idle_state(5);
+        sm.transition_to_rekeying_internal(epoch(6)).unwrap();
+        assert!(matches!(
+            compact_vault(&vault_path, 0, &sm),
+            Err(StorageError::InvariantViolation(_))
+        ));
+
+        let mut sm = idle_state(5);
+        sm.transition_to_recovery_internal("req".to_string(), 0, crate::models::Role::Recovery)
+            .unwrap();
+        assert!(matches!(
+            compact_vault(&vault_path, 0, &sm),
+            Err(StorageError::InvariantViolation(_))
+        ));
+
+        // Nothing touched
+        assert_eq!(std::fs::read(&vault_path).unwrap(), original);
+        assert_eq!(read_device_headers(&vault_path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_crash_mid_compaction_leaves_old_vault_intact() {
+        let dir = TempDir::new().unwrap();
+        let vault_path = dir.path().join("vault.db");
+        let mut original = write_vault(&vault_path, 4);
+        original.extend_from_slice(&[0u8; 100]);
+        std::fs::write(&vault_path, &original).unwrap();
+
+        // Crash after the shadow file was partly written, before rename
+        let temp_path = ShadowWriter::new(&vault_path).temp_path();
+        std::fs::write(&temp_path, &original[..40]).unwrap();
+
+        assert_eq!(std::fs::read(&vault_path).unwrap(), original);
+        assert_eq!(read_vault_epoch(&vault_path).unwrap(), 4);
+
+        // The next compaction clears the residue and trims the vault
+        let report = compact_vault(&vault_path, 1, &idle_state(4)).unwrap();
+        assert!(!temp_path.exists());
+        assert_eq!(report.bytes_reclaimed, 40 + 100);
+        assert_eq!(read_vault_epoch(&vault_path).unwrap(), 4);
+        assert_eq!(
+            std::fs::read(&vault_path).unwrap(),
+            &original[..original.len() - 100]
+        );
+    }
+
+    #[test]
+    fn test_canonical_rewrite_keeps_vk_region() {
+        let dir = TempDir::new().unwrap();
+        let vault_path = dir.path().join("vault.db");
+        let mut canonical = write_vault(&vault_path, 6);
+        canonical.extend_from_slice(&VK_REGION_MAGIC);
+        canonical.extend_from_slice(&[0x33; VK_REGION_LEN - 8]);
+
+        let mut padded = canonical.clone();
+        padded.extend_from_slice(&[0u8; 50]);
+        std::fs::write(&vault_path, &padded).unwrap();
+
+        let report = compact_vault(&vault_path, 1, &idle_state(6)).unwrap();
+        assert_eq!(report.bytes_reclaimed, 50);
+        assert_eq!(std::fs::read(&vault_path).unwrap(), canonical);
+    }
+
+    #[test]
+    fn test_vk_region_after_out_of_bounds_offset() {
+        let mut file = vec![0u8; 8];
+        file.extend_from_slice(&VK_REGION_MAGIC);
+        file.resize(8 + VK_REGION_LEN, 0xAA);
+
+        assert_eq!(vk_region_after(&file, 8).len(), VK_REGION_LEN);
+        assert!(vk_region_after(&file, 9).is_empty());
+        assert!(vk_region_after(&file, file.len() + 1).is_empty());
+        assert!(vk_region_after(&file, usize::MAX).is_empty());
+    }
+}
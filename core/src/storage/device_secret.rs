@@ -0,0 +1,166 @@
+//! # Device Secret at Rest
+//!
+//! Local storage format of this device's Kyber secret key.
+//!
+//! The key is stored next to the vault file (`<vault>.devkey`) behind a
+//! one-byte marker:
+//!
+//! ```text
+//! 0x00 || secret key (3168 B)          legacy plaintext at rest
+//! 0x01 || KeyWrapper::wrap(secret key) wrapped, e.g. by StrongBox
+//! ```
+//!
+//! New blobs are always wrapped. Plaintext blobs are still read so
+//! existing installs keep working, and
+//! [`VaultStorage::load_device_secret`](super::recovery::VaultStorage::load_device_secret)
+//! rewrites them wrapped on first load.
+
+use std::path::{Path, PathBuf};
+
+use zeroize::Zeroize;
+
+use super::error::StorageError;
+use crate::crypto::kem::KyberSecretKeyBytes;
+use crate::crypto::wrap::KeyWrapper;
+
+/// Suffix of the device secret file belonging to a vault file
+pub const DEVICE_SECRET_SUFFIX: &str = ".devkey";
+
+/// Marker of a legacy plaintext-at-rest device secret
+pub const DEVICE_SECRET_PLAINTEXT: u8 = 0x00;
+
+/// Marker of a [`KeyWrapper`]-protected device secret
+pub const DEVICE_SECRET_WRAPPED: u8 = 0x01;
+
+/// How a stored device secret was protected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceSecretFormat {
+    /// Legacy plaintext at rest; should be migrated
+    Plaintext,
+    /// Wrapped by a [`KeyWrapper`]
+    Wrapped,
+}
+
+/// Path of the device secret file belonging to `vault_path`
+pub fn device_secret_path(vault_path: &Path) -> PathBuf {
+    let mut path = vault_path.as_os_str().to_owned();
+    path.push(DEVICE_SECRET_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Encode `secret` in the wrapped format
+///
+/// # Errors
+///
+/// Returns `StorageError::CryptoFailed` if the wrapper fails.
+pub fn encode_device_secret(
+    secret: &KyberSecretKeyBytes,
+    wrapper: &dyn KeyWrapper,
+) -> Result<Vec<u8>, StorageError> {
+    let wrapped = wrapper
+        .wrap(secret.as_bytes())
+        .map_err(|e| StorageError::crypto(format!("Failed to wrap device secret: {}", e)))?;
+
+    let mut blob = Vec::with_capacity(1 + wrapped.len());
+    blob.push(DEVICE_SECRET_WRAPPED);
+    blob.extend_from_slice(&wrapped);
+    Ok(blob)
+}
+
+/// Decode a stored device secret in either format
+///
+/// # Errors
+///
+/// - `StorageError::AuthenticationFailed` if the wrapper cannot unwrap it
+/// - `StorageError::CryptoFailed` if the blob is empty, has an unknown
+///   marker, or does not hold a secret key of the right length
+pub fn decode_device_secret(
+    blob: &[u8],
+    wrapper: &dyn KeyWrapper,
+) -> Result<(KyberSecretKeyBytes, DeviceSecretFormat), StorageError> {
+    let (&marker, body) = blob
+        .split_first()
+        .ok_or_else(|| StorageError::crypto("Device secret blob is empty"))?;
+
+    match marker {
+        DEVICE_SECRET_PLAINTEXT => Ok((parse_secret(body)?, DeviceSecretFormat::Plaintext)),
+        DEVICE_SECRET_WRAPPED => {
+            let mut plaintext = wrapper.unwrap(body).map_err(|e| {
+                StorageError::authentication(format!("Device secret could not be unwrapped: {}", e))
+            })?;
+            let secret = parse_secret(&plaintext);
+            plaintext.zeroize();
+            Ok((secret?, DeviceSecretFormat::Wrapped))
+        }
+        other => Err(StorageError::crypto(format!(
+            "Unknown device secret marker {:#04x}",
+            other
+        ))),
+    }
+}
+
+/// Parse the raw secret key bytes
+fn parse_secret(bytes: &[u8]) -> Result<KyberSecretKeyBytes, StorageError> {
+    KyberSecretKeyBytes::from_bytes(bytes)
+        .map_err(|e| StorageError::crypto(format!("Invalid device secret: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::kem::{KyberKEM, SECRET_KEY_SIZE};
+    use crate::crypto::wrap::AeadKeyWrapper;
+
+    fn legacy_blob(secret: &KyberSecretKeyBytes) -> Vec<u8> {
+        let mut blob = vec![DEVICE_SECRET_PLAINTEXT];
+        blob.extend_from_slice(secret.as_bytes());
+        blob
+    }
+
+    #[test]
+    fn test_roundtrip_through_software_wrapper() {
+        let wrapper = AeadKeyWrapper::generate();
+        let keypair = KyberKEM::generate_keypair();
+
+        let blob = encode_device_secret(&keypair.secret, &wrapper).unwrap();
+        assert_eq!(blob[0], DEVICE_SECRET_WRAPPED);
+        assert!(!blob
+            .windows(64)
+            .any(|w| w == &keypair.secret.as_bytes()[..64]));
+
+        let (secret, format) = decode_device_secret(&blob, &wrapper).unwrap();
+        assert_eq!(format, DeviceSecretFormat::Wrapped);
+        assert_eq!(secret.as_bytes(), keypair.secret.as_bytes());
+    }
+
+    #[test]
+    fn test_legacy_plaintext_is_read() {
+        let keypair = KyberKEM::generate_keypair();
+        let (secret, format) =
+            decode_device_secret(&legacy_blob(&keypair.secret), &AeadKeyWrapper::generate())
+                .unwrap();
+        assert_eq!(format, DeviceSecretFormat::Plaintext);
+        assert_eq!(secret.as_bytes(), keypair.secret.as_bytes());
+    }
+
+    #[test]
+    fn test_unwrap_failure_is_clean_error() {
+        let keypair = KyberKEM::generate_keypair();
+        let blob = encode_device_secret(&keypair.secret, &AeadKeyWrapper::generate()).unwrap();
+
+        let result = decode_device_secret(&blob, &AeadKeyWrapper::generate());
+        assert!(matches!(result, Err(StorageError::AuthenticationFailed(_))));
+    }
+
+    #[test]
+    fn test_malformed_blobs_rejected() {
+        let wrapper = AeadKeyWrapper::generate();
+        assert!(decode_device_secret(&[], &wrapper).is_err());
+        assert!(decode_device_secret(&[0x7F; 10], &wrapper).is_err());
+        let short = vec![DEVICE_SECRET_PLAINTEXT; SECRET_KEY_SIZE];
+        assert!(matches!(
+            decode_device_secret(&short, &wrapper),
+            Err(StorageError::CryptoFailed(_))
+        ));
+    }
+}
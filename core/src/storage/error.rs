@@ -17,12 +17,18 @@
 //! │   ├── AtomicRenameFailed
 //! │   ├── FsyncFailed
 //! │   ├── ConsistencyCheckFailed
-//! │   └── InvariantViolation
+//! │   ├── InvariantViolation
+//! │   ├── CryptoFailed
+//! │   ├── AuthenticationFailed
+//! │   ├── MetadataFailed
+//! │   ├── HeaderChecksumMismatch
+//! │   └── VaultLocked
 //! └── FatalError (Unrecoverable)
 //!     ├── StorageInconsistency
 //!     └── InvariantViolationTriggered
 //! ```
 
+use crate::models::vault::VaultFormatError;
 use thiserror::Error;
 
 /// Result type alias for storage operations
@@ -94,6 +100,40 @@ pub enum StorageError {
     /// - Blob serialization failed
     #[error("Crypto operation failed: {0}")]
     CryptoFailed(String),
+
+    /// Authentication of encrypted data failed
+    ///
+    /// This may occur due to:
+    /// - Wrong passphrase
+    /// - Archive or blob tampered with
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+
+    /// Metadata database operation failed
+    ///
+    /// This may occur due to:
+    /// - Database cannot be opened, or the SQLCipher key is wrong
+    /// - Schema migration failure
+    /// - `Local_Epoch` update failure after an AUP commit (healed by
+    ///   `CrashRecovery` on the next startup)
+    #[error("Metadata operation failed: {0}")]
+    MetadataFailed(String),
+
+    /// Vault header checksum does not match its contents
+    ///
+    /// This may occur due to:
+    /// - Bit flip in the header on disk
+    /// - Truncated or partially overwritten header
+    #[error("Header checksum mismatch: {0}")]
+    HeaderChecksumMismatch(String),
+
+    /// Vault is locked by another writer
+    ///
+    /// This may occur due to:
+    /// - Another process running an epoch upgrade on the same vault
+    /// - A second app instance (e.g. backgrounded) holding the vault lock
+    #[error("Vault locked: {0}")]
+    VaultLocked(String),
 }
 
 impl StorageError {
@@ -126,6 +166,37 @@ impl StorageError {
     pub fn crypto(msg: impl Into<String>) -> Self {
         Self::CryptoFailed(msg.into())
     }
+
+    /// Create an authentication error from a string message
+    pub fn authentication(msg: impl Into<String>) -> Self {
+        Self::AuthenticationFailed(msg.into())
+    }
+
+    /// Create a metadata error from a string message
+    pub fn metadata(msg: impl Into<String>) -> Self {
+        Self::MetadataFailed(msg.into())
+    }
+
+    /// Create a header checksum mismatch error from a string message
+    pub fn header_checksum(msg: impl Into<String>) -> Self {
+        Self::HeaderChecksumMismatch(msg.into())
+    }
+
+    /// Create a vault locked error from a string message
+    pub fn vault_locked(msg: impl Into<String>) -> Self {
+        Self::VaultLocked(msg.into())
+    }
+}
+
+impl From<VaultFormatError> for StorageError {
+    fn from(error: VaultFormatError) -> Self {
+        match error {
+            VaultFormatError::ConsistencyCheckFailed(msg) => Self::ConsistencyCheckFailed(msg),
+            VaultFormatError::HeaderChecksumMismatch(msg) => Self::HeaderChecksumMismatch(msg),
+            VaultFormatError::AuthenticationFailed(msg) => Self::AuthenticationFailed(msg),
+            VaultFormatError::CryptoFailed(msg) => Self::CryptoFailed(msg),
+        }
+    }
 }
 
 /// Mathematical invariant violation types
@@ -314,6 +385,40 @@ mod tests {
         assert_eq!(err.to_string(), "Invariant violation: epoch rollback");
     }
 
+    #[test]
+    fn test_storage_error_authentication() {
+        let err = StorageError::authentication("wrong passphrase");
+        assert!(matches!(err, StorageError::AuthenticationFailed(_)));
+        assert_eq!(err.to_string(), "Authentication failed: wrong passphrase");
+    }
+
+    #[test]
+    fn test_storage_error_metadata() {
+        let err = StorageError::metadata("database locked");
+        assert!(matches!(err, StorageError::MetadataFailed(_)));
+        assert_eq!(
+            err.to_string(),
+            "Metadata operation failed: database locked"
+        );
+    }
+
+    #[test]
+    fn test_storage_error_header_checksum() {
+        let err = StorageError::header_checksum("expected 01020304, got 00000000");
+        assert!(matches!(err, StorageError::HeaderChecksumMismatch(_)));
+        assert_eq!(
+            err.to_string(),
+            "Header checksum mismatch: expected 01020304, got 00000000"
+        );
+    }
+
+    #[test]
+    fn test_storage_error_vault_locked() {
+        let err = StorageError::vault_locked("vault.db.lock is held");
+        assert!(matches!(err, StorageError::VaultLocked(_)));
+        assert_eq!(err.to_string(), "Vault locked: vault.db.lock is held");
+    }
+
     // ------------------------------------------------------------------------
     // InvariantViolation Tests
     // ------------------------------------------------------------------------
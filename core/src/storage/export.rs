@@ -0,0 +1,844 @@
+//! # Vault Export / Import
+//!
+//! Packages a vault into a single passphrase-encrypted archive so it can be
+//! moved between devices out of band (e.g. via SD card), without the wire
+//! protocol.
+//!
+//! ## Archive Format
+//!
+//! ```text
+//! [Magic:8 "AETEXP01"][m_cost:4][t_cost:4][p_cost:4][Salt:16][Nonce:24][Ciphertext+Tag]
+//! ```
+//!
+//! - The archive key is derived from the passphrase with Argon2id using the
+//!   random salt and the cost parameters stored in the archive header; import
+//!   rejects parameters above [`MAX_ARCHIVE_M_COST`], [`MAX_ARCHIVE_T_COST`]
+//!   and [`MAX_ARCHIVE_P_COST`] before deriving anything
+//! - The whole header is bound to the ciphertext as AEAD associated data
+//! - The encrypted payload contains the vault file (header + blob), the
+//!   device headers persisted next to it (`<vault>.headers`), if any, and
+//!   every other file kept next to the vault ([`VAULT_SIDECAR_SUFFIXES`]):
+//!   the sealed Device_0 key, the recovery attempt history, the
+//!   password-wrapped vault key and the device secret, as they are
+//! - The metadata database is not packaged; import sets its `Local_Epoch`
+//!   to the imported vault's epoch
+//!
+//! Sidecars wrapped by a hardware keystore (the device secret, and the
+//! vault key while a key wrapper is set) only open on the device that
+//! wrapped them. Elsewhere the vault is opened with the mnemonic instead.
+//!
+//! ## Safety Guarantees
+//!
+//! - A wrong passphrase or tampered archive fails with
+//!   `StorageError::AuthenticationFailed`; nothing is deserialized before
+//!   the AEAD tag has been verified
+//! - The imported vault is fully validated before anything is written
+//! - All files are written via `ShadowWriter` (atomic rename). Every file is
+//!   staged before any is committed, and the vault file is committed last,
+//!   so a crash never leaves the new vault next to the old sidecars
+//! - Import never replaces a vault with a newer epoch (Invariant #1)
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use aeternum_core::storage::export::{export_vault, import_vault};
+//! use std::path::Path;
+//!
+//! export_vault(Path::new("vault.db"), "correct horse", Path::new("vault.aex"))?;
+//! let report = import_vault(Path::new("vault.aex"), "correct horse", Path::new("/sdcard"))?;
+//! println!("Imported epoch {}", report.epoch);
+//! # Ok::<(), aeternum_core::storage::StorageError>(())
+//! ```
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce};
+use crate::crypto::kdf::{Argon2idConfig, Argon2idKDF};
+use crate::models::device::{decode_header_list, DeviceHeader, LegacyDeviceHeader};
+use crate::models::vault::{VaultBlob, VaultHeader};
+use crate::storage::aug::read_vault_epoch;
+use crate::storage::device_secret::DEVICE_SECRET_SUFFIX;
+use crate::storage::error::StorageError;
+use crate::storage::lock::VaultLock;
+use crate::storage::metadata::{SqliteMetadataStore, METADATA_FILE_NAME};
+use crate::storage::recovery::MetadataSource;
+use crate::storage::shadow::{ShadowFile, ShadowWriter};
+
+/// Archive magic bytes (format version 1)
+pub const EXPORT_MAGIC: [u8; 8] = *b"AETEXP01";
+
+/// Suffix of the device header file stored next to a vault
+pub const DEVICE_HEADERS_SUFFIX: &str = ".headers";
+
+/// Suffix of the file holding Device_0's sealed secret key
+pub const ANCHOR_SEAL_SUFFIX: &str = ".anchor";
+
+/// Suffix of the file holding the password-wrapped vault key
+pub const PASSWORD_WRAP_SUFFIX: &str = ".vkwrap";
+
+/// Suffix of the file holding the vault's recovery attempt history
+pub const RECOVERY_ATTEMPTS_SUFFIX: &str = ".attempts";
+
+/// Files next to a vault, besides its device headers, that an archive
+/// carries as opaque bytes
+pub const VAULT_SIDECAR_SUFFIXES: [&str; 4] = [
+    ANCHOR_SEAL_SUFFIX,
+    RECOVERY_ATTEMPTS_SUFFIX,
+    PASSWORD_WRAP_SUFFIX,
+    DEVICE_SECRET_SUFFIX,
+];
+
+/// Archive salt length in bytes
+const SALT_LEN: usize = 16;
+
+/// Archive nonce length in bytes
+const NONCE_LEN: usize = 24;
+
+/// Size of the vault header at the start of a vault file
+const VAULT_HEADER_LEN: usize = 32;
+
+/// Total archive header length (magic + KDF params + salt + nonce)
+const ARCHIVE_HEADER_LEN: usize = 8 + 12 + SALT_LEN + NONCE_LEN;
+
+/// AEAD tag length in bytes
+const TAG_LEN: usize = 16;
+
+/// Largest Argon2id memory cost accepted from an archive header (KiB, 256 MB)
+pub const MAX_ARCHIVE_M_COST: u32 = 256 * 1024;
+
+/// Largest Argon2id iteration count accepted from an archive header
+pub const MAX_ARCHIVE_T_COST: u32 = 10;
+
+/// Largest Argon2id parallelism accepted from an archive header
+pub const MAX_ARCHIVE_P_COST: u32 = 16;
+
+/// Encrypted archive contents
+#[derive(Serialize, Deserialize)]
+struct ExportPayload {
+    /// File name of the exported vault
+    file_name: String,
+    /// Raw vault file (32-byte header followed by the serialized blob)
+    vault_file: Vec<u8>,
+    /// Device headers persisted next to the vault
+    device_headers: Vec<DeviceHeader>,
+    /// Other files next to the vault, by suffix (see
+    /// [`VAULT_SIDECAR_SUFFIXES`])
+    sidecars: Vec<(String, Vec<u8>)>,
+}
+
+/// Archive contents written before sidecar files were included
+#[derive(Deserialize)]
+struct HeadersOnlyExportPayload {
+    file_name: String,
+    vault_file: Vec<u8>,
+    device_headers: Vec<DeviceHeader>,
+}
+
+impl From<HeadersOnlyExportPayload> for ExportPayload {
+    fn from(old: HeadersOnlyExportPayload) -> Self {
+        Self {
+            file_name: old.file_name,
+            vault_file: old.vault_file,
+            device_headers: old.device_headers,
+            sidecars: Vec::new(),
+        }
+    }
+}
+
+/// Archive contents written before headers carried `degraded_info`
+#[derive(Deserialize)]
+struct LegacyExportPayload {
+    file_name: String,
+    vault_file: Vec<u8>,
+    device_headers: Vec<LegacyDeviceHeader>,
+}
+
+impl From<LegacyExportPayload> for ExportPayload {
+    fn from(legacy: LegacyExportPayload) -> Self {
+        Self {
+            file_name: legacy.file_name,
+            vault_file: legacy.vault_file,
+            device_headers: legacy
+                .device_headers
+                .into_iter()
+                .map(DeviceHeader::from)
+                .collect(),
+            sidecars: Vec::new(),
+        }
+    }
+}
+
+/// Result of a successful [`import_vault`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Path of the imported vault file
+    pub vault_path: PathBuf,
+    /// Epoch version of the imported vault
+    pub epoch: u64,
+    /// Number of device headers restored
+    pub device_header_count: usize,
+    /// Whether an existing vault (same or older epoch) was replaced
+    pub replaced_existing: bool,
+}
+
+/// Path of the device header file belonging to `vault_path`
+pub fn device_headers_path(vault_path: &Path) -> PathBuf {
+    sidecar_path(vault_path, DEVICE_HEADERS_SUFFIX)
+}
+
+/// Path of the file with `suffix` belonging to `vault_path`
+pub fn sidecar_path(vault_path: &Path, suffix: &str) -> PathBuf {
+    let mut path = vault_path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Export a vault to an encrypted archive using default Argon2id parameters
+///
+/// See [`export_vault_with_config`].
+pub fn export_vault(
+    vault_path: impl AsRef<Path>,
+    passphrase: &str,
+    out_path: impl AsRef<Path>,
+) -> Result<(), StorageError> {
+    export_vault_with_config(vault_path, passphrase, out_path, Argon2idConfig::default())
+}
+
+/// Export a vault to an encrypted archive
+///
+/// Reads the vault file, its device headers and its sidecar files, encrypts
+/// them under a key derived from `passphrase`, and writes the archive
+/// atomically to `out_path`.
+///
+/// # Errors
+///
+/// - `ConsistencyCheckFailed` if the vault is missing or malformed
+/// - `CryptoFailed` if key derivation or encryption fails, or `config`
+///   exceeds the limits import accepts
+/// - `ShadowWriteFailed` / `AtomicRenameFailed` / `FsyncFailed` on I/O errors
+pub fn export_vault_with_config(
+    vault_path: impl AsRef<Path>,
+    passphrase: &str,
+    out_path: impl AsRef<Path>,
+    config: Argon2idConfig,
+) -> Result<(), StorageError> {
+    let vault_path = vault_path.as_ref();
+    let out_path = out_path.as_ref();
+
+    // An archive import would refuse is useless
+    check_kdf_limits(&config).map_err(|e| StorageError::crypto(e.to_string()))?;
+
+    let vault_file = std::fs::read(vault_path).map_err(|e| {
+        StorageError::consistency_check(format!(
+            "Failed to read vault {}: {}",
+            vault_path.display(),
+            e
+        ))
+    })?;
+    let epoch = validate_vault_file(&vault_file)?;
+
+    let device_headers = read_device_headers(vault_path)?;
+
+    let mut sidecars = Vec::new();
+    for suffix in VAULT_SIDECAR_SUFFIXES {
+        let path = sidecar_path(vault_path, suffix);
+        match std::fs::read(&path) {
+            Ok(bytes) => sidecars.push((suffix.to_string(), bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(StorageError::consistency_check(format!(
+                    "Failed to read {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        }
+    }
+
+    let file_name = vault_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| {
+            StorageError::consistency_check(format!(
+                "Invalid vault file name: {}",
+                vault_path.display()
+            ))
+        })?
+        .to_string();
+
+    let mut payload = ExportPayload {
+        file_name,
+        vault_file,
+        device_headers,
+        sidecars,
+    };
+    let plaintext = Zeroizing::new(
+        bincode::serialize(&payload)
+            .map_err(|e| StorageError::crypto(format!("Payload serialization failed: {}", e)))?,
+    );
+    // A legacy device secret is stored in plaintext
+    for (_, bytes) in &mut payload.sidecars {
+        bytes.zeroize();
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    let nonce = XChaCha20Nonce::random();
+
+    let mut header = Vec::with_capacity(ARCHIVE_HEADER_LEN);
+    header.extend_from_slice(&EXPORT_MAGIC);
+    header.extend_from_slice(&config.m_cost.to_be_bytes());
+    header.extend_from_slice(&config.t_cost.to_be_bytes());
+    header.extend_from_slice(&config.p_cost.to_be_bytes());
+    header.extend_from_slice(&salt);
+    header.extend_from_slice(nonce.as_bytes());
+
+    let key = derive_archive_key(passphrase, &salt, config)?;
+    let ciphertext = AeadCipher::new(&key)
+        .encrypt(&nonce, &plaintext, Some(&header))
+        .map_err(|e| StorageError::crypto(format!("Archive encryption failed: {}", e)))?;
+
+    write_atomically(out_path, &[&header, &ciphertext])?;
+
+    eprintln!(
+        "[EXPORT] Exported vault {} (epoch {}) to {}",
+        vault_path.display(),
+        epoch,
+        out_path.display()
+    );
+
+    Ok(())
+}
+
+/// Reject Argon2id parameters above the archive limits
+///
+/// Bounds the memory and time an attacker-supplied archive can make the
+/// importer spend on key derivation.
+fn check_kdf_limits(config: &Argon2idConfig) -> Result<(), StorageError> {
+    if config.m_cost > MAX_ARCHIVE_M_COST
+        || config.t_cost > MAX_ARCHIVE_T_COST
+        || config.p_cost > MAX_ARCHIVE_P_COST
+    {
+        return Err(StorageError::consistency_check(format!(
+            "Archive KDF parameters out of range: m_cost {} (max {}), t_cost {} (max {}), p_cost {} (max {})",
+            config.m_cost,
+            MAX_ARCHIVE_M_COST,
+            config.t_cost,
+            MAX_ARCHIVE_T_COST,
+            config.p_cost,
+            MAX_ARCHIVE_P_COST
+        )));
+    }
+    Ok(())
+}
+
+/// Import a vault from an encrypted archive into `dest_dir`
+///
+/// The archive is decrypted and the contained vault is fully validated
+/// before anything is written. The vault keeps its original file name; its
+/// device headers and sidecar files are restored next to it, stale ones the
+/// archive does not carry are removed, and the vault file is committed
+/// last. The metadata database's `Local_Epoch` is then set to the vault's
+/// epoch.
+///
+/// # Errors
+///
+/// - `AuthenticationFailed` if the passphrase is wrong or the archive was
+///   tampered with
+/// - `ConsistencyCheckFailed` if the archive is truncated or malformed, or
+///   its KDF parameters exceed the `MAX_ARCHIVE_*` limits
+/// - `InvariantViolation` if `dest_dir` already holds the same vault at a
+///   newer epoch (Invariant #1)
+/// - `VaultLocked` if another writer holds the destination vault's lock
+/// - `MetadataFailed` if the metadata database cannot be updated
+/// - `ShadowWriteFailed` / `AtomicRenameFailed` / `FsyncFailed` on I/O errors
+pub fn import_vault(
+    archive_path: impl AsRef<Path>,
+    passphrase: &str,
+    dest_dir: impl AsRef<Path>,
+) -> Result<ImportReport, StorageError> {
+    let archive_path = archive_path.as_ref();
+    let dest_dir = dest_dir.as_ref();
+
+    let archive = std::fs::read(archive_path).map_err(|e| {
+        StorageError::consistency_check(format!(
+            "Failed to read archive {}: {}",
+            archive_path.display(),
+            e
+        ))
+    })?;
+
+    if archive.len() < ARCHIVE_HEADER_LEN + TAG_LEN {
+        return Err(StorageError::consistency_check(format!(
+            "Archive truncated: {} bytes (minimum {})",
+            archive.len(),
+            ARCHIVE_HEADER_LEN + TAG_LEN
+        )));
+    }
+
+    let (header, ciphertext) = archive.split_at(ARCHIVE_HEADER_LEN);
+    if header[0..8] != EXPORT_MAGIC {
+        return Err(StorageError::consistency_check(
+            "Invalid archive magic bytes (expected AETEXP01)",
+        ));
+    }
+
+    let read_u32 =
+        |offset: usize| u32::from_be_bytes(header[offset..offset + 4].try_into().unwrap());
+    let config = Argon2idConfig::new(read_u32(8), read_u32(12), read_u32(16), 32);
+    // The header is untrusted until the tag verifies, which needs the key
+    check_kdf_limits(&config)?;
+    let salt = &header[20..20 + SALT_LEN];
+    let nonce = XChaCha20Nonce::try_from_slice(&header[20 + SALT_LEN..])
+        .map_err(|e| StorageError::consistency_check(format!("Invalid archive nonce: {}", e)))?;
+
+    let key = derive_archive_key(passphrase, salt, config)?;
+    let plaintext = Zeroizing::new(
+        AeadCipher::new(&key)
+            .decrypt(&nonce, ciphertext, Some(header))
+            .map_err(|_| {
+                StorageError::authentication(
+                    "Archive decryption failed: wrong passphrase or corrupted archive",
+                )
+            })?,
+    );
+
+    let mut payload: ExportPayload = bincode::deserialize(&plaintext)
+        .or_else(|err| {
+            bincode::deserialize::<HeadersOnlyExportPayload>(&plaintext)
+                .map(ExportPayload::from)
+                .or_else(|_| {
+                    bincode::deserialize::<LegacyExportPayload>(&plaintext).map(ExportPayload::from)
+                })
+                .map_err(|_| err)
+        })
+        .map_err(|e| {
+            StorageError::consistency_check(format!("Malformed archive payload: {}", e))
+        })?;
+    let epoch = validate_vault_file(&payload.vault_file)?;
+
+    // Only a plain file name may be used inside dest_dir
+    let file_name = Path::new(&payload.file_name);
+    if file_name.components().count() != 1 || file_name.file_name().is_none() {
+        return Err(StorageError::consistency_check(format!(
+            "Invalid vault file name in archive: {}",
+            payload.file_name
+        )));
+    }
+    if let Some((suffix, _)) = payload
+        .sidecars
+        .iter()
+        .find(|(suffix, _)| !VAULT_SIDECAR_SUFFIXES.contains(&suffix.as_str()))
+    {
+        return Err(StorageError::consistency_check(format!(
+            "Unknown vault file {} in archive",
+            suffix
+        )));
+    }
+    let metadata_epoch = u32::try_from(epoch).map_err(|_| {
+        StorageError::consistency_check(format!("Vault epoch {} exceeds metadata range", epoch))
+    })?;
+    let vault_path = dest_dir.join(file_name);
+    let _lock = VaultLock::hold(&vault_path)?;
+
+    // Invariant #1: never roll an existing vault back to an older epoch
+    let replaced_existing = vault_path.exists();
+    if replaced_existing {
+        let existing_epoch = read_vault_epoch(&vault_path)?;
+        if existing_epoch > epoch {
+            return Err(StorageError::invariant(format!(
+                "Invariant #1 violation: import would roll back {} from epoch {} to {}",
+                vault_path.display(),
+                existing_epoch,
+                epoch
+            )));
+        }
+    }
+
+    // Stage every file before committing any, then commit the vault last
+    let mut files: Vec<(PathBuf, Vec<u8>)> = Vec::new();
+    if !payload.device_headers.is_empty() {
+        let bytes = bincode::serialize(&payload.device_headers).map_err(|e| {
+            StorageError::crypto(format!("Device header serialization failed: {}", e))
+        })?;
+        files.push((device_headers_path(&vault_path), bytes));
+    }
+    for (suffix, bytes) in payload.sidecars.drain(..) {
+        files.push((sidecar_path(&vault_path, &suffix), bytes));
+    }
+    let staged = files
+        .iter()
+        .map(|(path, bytes)| stage_atomically(path, &[bytes]))
+        .collect::<Result<Vec<_>, _>>();
+    for (_, bytes) in &mut files {
+        bytes.zeroize();
+    }
+    let staged = staged?;
+    let staged_vault = stage_atomically(&vault_path, &[&payload.vault_file])?;
+
+    for (writer, shadow) in staged {
+        writer.commit_shadow_write(shadow)?;
+    }
+    let stale = std::iter::once(DEVICE_HEADERS_SUFFIX)
+        .chain(VAULT_SIDECAR_SUFFIXES)
+        .map(|suffix| sidecar_path(&vault_path, suffix))
+        .filter(|path| path.exists() && !files.iter().any(|(staged, _)| staged == path));
+    for path in stale {
+        std::fs::remove_file(&path).map_err(|e| {
+            StorageError::shadow_write(format!("Failed to remove stale {}: {}", path.display(), e))
+        })?;
+    }
+    let (writer, shadow) = staged_vault;
+    writer.commit_shadow_write(shadow)?;
+
+    // Local_Epoch follows the vault, as after an AUP commit
+    SqliteMetadataStore::open(vault_path.with_file_name(METADATA_FILE_NAME))?
+        .update_epoch(metadata_epoch)?;
+
+    eprintln!(
+        "[EXPORT] Imported vault {} (epoch {}) from {}",
+        vault_path.display(),
+        epoch,
+        archive_path.display()
+    );
+
+    Ok(ImportReport {
+        vault_path,
+        epoch,
+        device_header_count: payload.device_headers.len(),
+        replaced_existing,
+    })
+}
+
+/// Derive the archive key from a passphrase
+fn derive_archive_key(
+    passphrase: &str,
+    salt: &[u8],
+    config: Argon2idConfig,
+) -> Result<XChaCha20Key, StorageError> {
+    let kdf = Argon2idKDF::with_config(config)
+        .map_err(|e| StorageError::crypto(format!("Invalid archive KDF parameters: {}", e)))?;
+    let derived = kdf
+        .derive_key(passphrase.as_bytes(), salt)
+        .map_err(|e| StorageError::crypto(format!("Archive key derivation failed: {}", e)))?;
+    XChaCha20Key::from_bytes(derived.as_bytes())
+        .map_err(|e| StorageError::crypto(format!("Invalid archive key: {}", e)))
+}
+
+/// Read the device headers persisted next to `vault_path`
+///
+/// Returns an empty list if the vault has no header file.
+pub(crate) fn read_device_headers(vault_path: &Path) -> Result<Vec<DeviceHeader>, StorageError> {
+    let headers_path = device_headers_path(vault_path);
+    if !headers_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let bytes = std::fs::read(&headers_path).map_err(|e| {
+        StorageError::consistency_check(format!(
+            "Failed to read device headers {}: {}",
+            headers_path.display(),
+            e
+        ))
+    })?;
+    decode_header_list(&bytes).map_err(|e| {
+        StorageError::consistency_check(format!(
+            "Malformed device headers {}: {}",
+            headers_path.display(),
+            e
+        ))
+    })
+}
+
+/// Validate a raw vault file and return its epoch
+///
+/// Checks the header magic and checksum, that the blob deserializes and that the blob
+/// epoch matches the header epoch.
+pub(crate) fn validate_vault_file(vault_file: &[u8]) -> Result<u64, StorageError> {
+    let header = VaultHeader::from_bytes(vault_file)?;
+
+    let blob = VaultBlob::deserialize(&vault_file[VAULT_HEADER_LEN..])
+        .map_err(|e| StorageError::consistency_check(format!("Invalid vault blob: {}", e)))?;
+    blob.validate()
+        .map_err(|e| StorageError::consistency_check(format!("Invalid vault blob: {}", e)))?;
+
+    if blob.epoch.version != header.epoch_version {
+        return Err(StorageError::consistency_check(format!(
+            "Vault header epoch {} does not match blob epoch {}",
+            header.epoch_version, blob.epoch.version
+        )));
+    }
+
+    Ok(header.epoch_version)
+}
+
+/// Write `parts` to `path` via shadow write + atomic rename
+pub(crate) fn write_atomically(path: &Path, parts: &[&[u8]]) -> Result<(), StorageError> {
+    let (writer, shadow) = stage_atomically(path, parts)?;
+    writer.commit_shadow_write(shadow)
+}
+
+/// Write `parts` to a synced shadow file of `path`, ready to be committed
+fn stage_atomically(
+    path: &Path,
+    parts: &[&[u8]],
+) -> Result<(ShadowWriter, ShadowFile), StorageError> {
+    let writer = ShadowWriter::new(path);
+    let mut shadow = writer.begin_shadow_write()?;
+
+    for part in parts {
+        shadow.write_all(part).map_err(|e| {
+            StorageError::shadow_write(format!(
+                "Failed to write {}: {}",
+                shadow.path().display(),
+                e
+            ))
+        })?;
+    }
+
+    shadow.file().sync_all().map_err(|e| {
+        StorageError::fsync(format!(
+            "Failed to fsync {}: {}",
+            shadow.path().display(),
+            e
+        ))
+    })?;
+
+    Ok((writer, shadow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::kem::KyberKEM;
+    use crate::models::device::DeviceId;
+    use crate::models::epoch::{CryptoAlgorithm, CryptoEpoch};
+    use tempfile::TempDir;
+
+    fn test_config() -> Argon2idConfig {
+        Argon2idConfig::new(8192, 1, 1, 32)
+    }
+
+    fn write_vault(path: &Path, epoch: u64) {
+        let blob = VaultBlob::new(
+            VaultBlob::CURRENT_BLOB_VERSION,
+            CryptoEpoch::new(epoch, CryptoAlgorithm::V1),
+            vec![0xAB; 64],
+            [0x11; 16],
+            [0x22; 24],
+        );
+        let mut bytes = VaultHeader::new(&blob).to_bytes().to_vec();
+        bytes.extend_from_slice(&blob.serialize().unwrap());
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    fn write_device_headers(vault_path: &Path, epoch: u64, count: usize) {
+        let headers: Vec<DeviceHeader> = (0..count)
+            .map(|_| {
+                let keypair = KyberKEM::generate_keypair();
+                let (_ss, encrypted_dek) = KyberKEM::encapsulate(&keypair.public).unwrap();
+                DeviceHeader::new(
+                    DeviceId::generate(),
+                    CryptoEpoch::new(epoch, CryptoAlgorithm::V1),
+                    keypair.public,
+                    encrypted_dek,
+                )
+            })
+            .collect();
+        std::fs::write(
+            device_headers_path(vault_path),
+            bincode::serialize(&headers).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_export_import_roundtrip_preserves_epoch() {
+        let src = TempDir::new().unwrap();
+        let dest = TempDir::new().unwrap();
+        let vault_path = src.path().join("vault.db");
+        let archive_path = src.path().join("vault.aex");
+
+        write_vault(&vault_path, 7);
+        write_device_headers(&vault_path, 7, 2);
+
+        export_vault_with_config(&vault_path, "passphrase", &archive_path, test_config()).unwrap();
+
+        let archive = std::fs::read(&archive_path).unwrap();
+        assert_eq!(&archive[0..8], &EXPORT_MAGIC);
+
+        let report = import_vault(&archive_path, "passphrase", dest.path()).unwrap();
+        assert_eq!(report.epoch, 7);
+        assert_eq!(report.device_header_count, 2);
+        assert!(!report.replaced_existing);
+        assert_eq!(report.vault_path, dest.path().join("vault.db"));
+
+        assert_eq!(
+            std::fs::read(&report.vault_path).unwrap(),
+            std::fs::read(&vault_path).unwrap()
+        );
+        assert_eq!(read_vault_epoch(&report.vault_path).unwrap(), 7);
+        assert_eq!(
+            std::fs::read(device_headers_path(&report.vault_path)).unwrap(),
+            std::fs::read(device_headers_path(&vault_path)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_export_import_roundtrip_restores_sidecars() {
+        let src = TempDir::new().unwrap();
+        let dest = TempDir::new().unwrap();
+        let vault_path = src.path().join("vault.db");
+        let archive_path = src.path().join("vault.aex");
+
+        write_vault(&vault_path, 4);
+        std::fs::write(sidecar_path(&vault_path, ANCHOR_SEAL_SUFFIX), b"anchor").unwrap();
+        std::fs::write(sidecar_path(&vault_path, PASSWORD_WRAP_SUFFIX), b"vkwrap").unwrap();
+        export_vault_with_config(&vault_path, "passphrase", &archive_path, test_config()).unwrap();
+
+        // Stale files of an older copy the archive does not carry
+        let dest_vault = dest.path().join("vault.db");
+        write_vault(&dest_vault, 2);
+        write_device_headers(&dest_vault, 2, 1);
+        std::fs::write(sidecar_path(&dest_vault, RECOVERY_ATTEMPTS_SUFFIX), b"old").unwrap();
+
+        let report = import_vault(&archive_path, "passphrase", dest.path()).unwrap();
+        assert!(report.replaced_existing);
+        assert_eq!(report.device_header_count, 0);
+
+        assert_eq!(
+            std::fs::read(sidecar_path(&dest_vault, ANCHOR_SEAL_SUFFIX)).unwrap(),
+            b"anchor"
+        );
+        assert_eq!(
+            std::fs::read(sidecar_path(&dest_vault, PASSWORD_WRAP_SUFFIX)).unwrap(),
+            b"vkwrap"
+        );
+        assert!(!device_headers_path(&dest_vault).exists());
+        assert!(!sidecar_path(&dest_vault, RECOVERY_ATTEMPTS_SUFFIX).exists());
+
+        let metadata = SqliteMetadataStore::open(dest.path().join(METADATA_FILE_NAME)).unwrap();
+        assert_eq!(metadata.get_epoch().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_import_wrong_passphrase() {
+        let dir = TempDir::new().unwrap();
+        let dest = TempDir::new().unwrap();
+        let vault_path = dir.path().join("vault.db");
+        let archive_path = dir.path().join("vault.aex");
+
+        write_vault(&vault_path, 1);
+        export_vault_with_config(&vault_path, "right", &archive_path, test_config()).unwrap();
+
+        let result = import_vault(&archive_path, "wrong", dest.path());
+        assert!(matches!(result, Err(StorageError::AuthenticationFailed(_))));
+        assert!(!dest.path().join("vault.db").exists());
+    }
+
+    #[test]
+    fn test_import_truncated_archive() {
+        let dir = TempDir::new().unwrap();
+        let dest = TempDir::new().unwrap();
+        let vault_path = dir.path().join("vault.db");
+        let archive_path = dir.path().join("vault.aex");
+
+        write_vault(&vault_path, 1);
+        export_vault_with_config(&vault_path, "passphrase", &archive_path, test_config()).unwrap();
+        let archive = std::fs::read(&archive_path).unwrap();
+
+        // Truncated inside the header
+        std::fs::write(&archive_path, &archive[..ARCHIVE_HEADER_LEN - 1]).unwrap();
+        let result = import_vault(&archive_path, "passphrase", dest.path());
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+
+        // Truncated inside the ciphertext: the tag no longer verifies
+        std::fs::write(&archive_path, &archive[..archive.len() - 1]).unwrap();
+        let result = import_vault(&archive_path, "passphrase", dest.path());
+        assert!(matches!(result, Err(StorageError::AuthenticationFailed(_))));
+
+        assert!(!dest.path().join("vault.db").exists());
+    }
+
+    #[test]
+    fn test_import_rejects_excessive_kdf_params() {
+        let dir = TempDir::new().unwrap();
+        let dest = TempDir::new().unwrap();
+        let vault_path = dir.path().join("vault.db");
+        let archive_path = dir.path().join("vault.aex");
+
+        write_vault(&vault_path, 1);
+        export_vault_with_config(&vault_path, "passphrase", &archive_path, test_config()).unwrap();
+        let archive = std::fs::read(&archive_path).unwrap();
+
+        for (offset, value) in [
+            (8, MAX_ARCHIVE_M_COST + 1),
+            (12, MAX_ARCHIVE_T_COST + 1),
+            (16, MAX_ARCHIVE_P_COST + 1),
+        ] {
+            let mut crafted = archive.clone();
+            crafted[offset..offset + 4].copy_from_slice(&value.to_be_bytes());
+            std::fs::write(&archive_path, &crafted).unwrap();
+            let result = import_vault(&archive_path, "passphrase", dest.path());
+            assert!(matches!(
+                result,
+                Err(StorageError::ConsistencyCheckFailed(_))
+            ));
+        }
+
+        let too_costly = Argon2idConfig::new(MAX_ARCHIVE_M_COST + 1, 1, 1, 32);
+        assert!(matches!(
+            export_vault_with_config(&vault_path, "passphrase", &archive_path, too_costly),
+            Err(StorageError::CryptoFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_refuses_to_clobber_newer_epoch() {
+        let src = TempDir::new().unwrap();
+        let dest = TempDir::new().unwrap();
+        let vault_path = src.path().join("vault.db");
+        let archive_path = src.path().join("vault.aex");
+
+        write_vault(&vault_path, 3);
+        export_vault_with_config(&vault_path, "passphrase", &archive_path, test_config()).unwrap();
+
+        let existing = dest.path().join("vault.db");
+        write_vault(&existing, 5);
+        let before = std::fs::read(&existing).unwrap();
+
+        let result = import_vault(&archive_path, "passphrase", dest.path());
+        assert!(matches!(result, Err(StorageError::InvariantViolation(_))));
+        assert_eq!(std::fs::read(&existing).unwrap(), before);
+
+        // An older local vault may be replaced
+        write_vault(&existing, 2);
+        let report = import_vault(&archive_path, "passphrase", dest.path()).unwrap();
+        assert!(report.replaced_existing);
+        assert_eq!(read_vault_epoch(&existing).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_export_missing_vault() {
+        let dir = TempDir::new().unwrap();
+        let result = export_vault_with_config(
+            dir.path().join("missing.db"),
+            "passphrase",
+            dir.path().join("out.aex"),
+            test_config(),
+        );
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+        assert!(!dir.path().join("out.aex").exists());
+    }
+}
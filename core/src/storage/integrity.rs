@@ -14,6 +14,25 @@
 //! - `IntegrityAudit`: Vault integrity verifier
 //!   - `verify_vault_integrity()`: Verifies AEAD tag + BLAKE3 MAC
 //!   - `compute_vault_mac()`: Computes BLAKE3 hash of entire vault
+//!   - `compute_mac()` / `verify()`: Keyed BLAKE3 MAC over a vault file,
+//!     streamed in chunks
+//!   - `write_chunk_table()` / `audit_chunks()`: Per-chunk keyed MACs kept
+//!     in a `<vault>.chunks` sidecar, used to locate damaged regions
+//!   - `snapshot()` / `diff()`: Baseline of a vault directory
+//!     ([`IntegritySnapshot`]) and what changed since ([`IntegrityDiff`])
+//!
+//! ## Chunk-MAC Table
+//!
+//! The 32-byte `VaultHeader` only has 4 reserved bytes, which cannot hold
+//! one MAC per chunk, so the table lives next to the vault file:
+//!
+//! ```text
+//! magic "AETCMAC1" (8) | chunk_size u32 BE | file_len u64 BE | count u64 BE
+//! | count x 32-byte MAC
+//! ```
+//!
+//! Each entry is `BLAKE3-keyed(key, index_be64 || chunk)`; binding the index
+//! stops entries from being reordered without the key.
 //!
 //! ## Security Properties
 //!
@@ -38,9 +57,46 @@
 //! assert_eq!(mac.as_bytes().len(), 32);
 //! ```
 
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::crypto::ct::ct_eq;
 use crate::crypto::hash::HashOutput;
-use crate::crypto::hash::{hash, Blake3Hasher};
-use crate::storage::error::StorageError;
+use crate::crypto::hash::{hash, keyed_hash, Blake3Hasher, DeriveKey};
+use crate::storage::aug::{parse_vault_epoch, VAULT_FILE_NAME, VAULT_HEADER_LEN};
+use crate::storage::error::{FatalError, InvariantViolation, StorageError};
+use crate::storage::lock::VaultLock;
+use crate::storage::shadow::{reject_symlink, ShadowWriter};
+
+/// Read buffer size for streaming file MACs (64 KiB)
+const MAC_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Suffix of the chunk-MAC table stored next to a vault file
+pub const CHUNK_TABLE_SUFFIX: &str = ".chunks";
+
+/// Magic bytes of the chunk-MAC table
+const CHUNK_TABLE_MAGIC: [u8; 8] = *b"AETCMAC1";
+
+/// Fixed size of the chunk-MAC table header
+const CHUNK_TABLE_HEADER_LEN: usize = 8 + 4 + 8 + 8;
+
+/// Result of auditing a single chunk of a vault file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkStatus {
+    /// Chunk index
+    pub index: u64,
+    /// Byte offset of the chunk in the vault file
+    pub offset: u64,
+    /// Number of bytes in the chunk as currently on disk
+    pub len: usize,
+    /// Whether the chunk matches its recorded MAC
+    pub intact: bool,
+}
 
 /// Integrity audit for vault verification.
 ///
@@ -244,6 +300,675 @@ impl<'a> IntegrityAudit<'a> {
     }
 }
 
+// ============================================================================
+// Full-file MAC
+// ============================================================================
+
+impl IntegrityAudit<'_> {
+    /// Compute the keyed BLAKE3 MAC of an entire vault file.
+    ///
+    /// The file is streamed in 64 KiB chunks, so memory use does not grow
+    /// with the vault size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StorageError::ConsistencyCheckFailed` if the path is a
+    /// symlink or the file cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use aeternum_core::storage::integrity::IntegrityAudit;
+    ///
+    /// let key = [0u8; 32];
+    /// let mac = IntegrityAudit::compute_mac("vault.db", &key)?;
+    /// IntegrityAudit::verify("vault.db", &key, &mac)?;
+    /// # Ok::<(), aeternum_core::storage::StorageError>(())
+    /// ```
+    pub fn compute_mac(
+        vault_path: impl AsRef<Path>,
+        key: &[u8; 32],
+    ) -> Result<HashOutput, StorageError> {
+        let (_, mac) = stream_hash(vault_path.as_ref(), Blake3Hasher::new_keyed(key))?;
+        Ok(mac)
+    }
+
+    /// Verify a vault file against an expected keyed BLAKE3 MAC.
+    ///
+    /// The comparison is constant-time.
+    ///
+    /// # Errors
+    ///
+    /// - `StorageError::AuthenticationFailed` if the MAC does not match
+    ///   (file tampered with or wrong key)
+    /// - `StorageError::ConsistencyCheckFailed` if the file cannot be read
+    pub fn verify(
+        vault_path: impl AsRef<Path>,
+        key: &[u8; 32],
+        expected: &HashOutput,
+    ) -> Result<(), StorageError> {
+        let vault_path = vault_path.as_ref();
+        let actual = Self::compute_mac(vault_path, key)?;
+
+        if !ct_eq(actual.as_bytes(), expected.as_bytes()) {
+            return Err(StorageError::authentication(format!(
+                "Vault MAC mismatch for {}: file has been tampered with",
+                vault_path.display()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Chunk-level audit
+// ============================================================================
+
+/// Path of the chunk-MAC table belonging to `vault_path`
+pub fn chunk_table_path(vault_path: &Path) -> PathBuf {
+    let mut path = vault_path.as_os_str().to_owned();
+    path.push(CHUNK_TABLE_SUFFIX);
+    PathBuf::from(path)
+}
+
+impl IntegrityAudit<'_> {
+    /// Record per-chunk keyed MACs of a vault file in its `.chunks` sidecar.
+    ///
+    /// Must be called whenever the vault file is rewritten, so that a later
+    /// [`audit_chunks`](Self::audit_chunks) has a reference to compare with.
+    ///
+    /// # Errors
+    ///
+    /// - `StorageError::ConsistencyCheckFailed` if `chunk_size` is zero or
+    ///   the vault file cannot be read
+    /// - `StorageError::VaultLocked` if another writer holds the vault's lock
+    /// - Shadow write errors if the table cannot be written
+    pub fn write_chunk_table(
+        vault_path: impl AsRef<Path>,
+        key: &[u8; 32],
+        chunk_size: u32,
+    ) -> Result<(), StorageError> {
+        let vault_path = vault_path.as_ref();
+        let _lock = VaultLock::hold(vault_path)?;
+        let data = read_vault_file(vault_path)?;
+        let macs = chunk_macs(&data, key, chunk_size)?;
+
+        let mut table = Vec::with_capacity(CHUNK_TABLE_HEADER_LEN + macs.len() * 32);
+        table.extend_from_slice(&CHUNK_TABLE_MAGIC);
+        table.extend_from_slice(&chunk_size.to_be_bytes());
+        table.extend_from_slice(&(data.len() as u64).to_be_bytes());
+        table.extend_from_slice(&(macs.len() as u64).to_be_bytes());
+        for mac in &macs {
+            table.extend_from_slice(mac.as_bytes());
+        }
+
+        write_sidecar(&chunk_table_path(vault_path), &table, "chunk table")
+    }
+
+    /// Audit a vault file chunk by chunk against its recorded MAC table.
+    ///
+    /// Returns one [`ChunkStatus`] per chunk covered by either the table or
+    /// the current file. Chunks missing from the file (truncation) or not
+    /// covered by the table (appended data) are reported as not intact.
+    ///
+    /// # Errors
+    ///
+    /// - `StorageError::ConsistencyCheckFailed` if the table is missing or
+    ///   malformed, was recorded with a different `chunk_size`, or the vault
+    ///   file cannot be read
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use aeternum_core::storage::integrity::IntegrityAudit;
+    ///
+    /// let key = [0u8; 32];
+    /// IntegrityAudit::write_chunk_table("vault.db", &key, 4096)?;
+    /// let damaged: Vec<_> = IntegrityAudit::audit_chunks("vault.db", &key, 4096)?
+    ///     .into_iter()
+    ///     .filter(|c| !c.intact)
+    ///     .collect();
+    /// # Ok::<(), aeternum_core::storage::StorageError>(())
+    /// ```
+    pub fn audit_chunks(
+        vault_path: impl AsRef<Path>,
+        key: &[u8; 32],
+        chunk_size: u32,
+    ) -> Result<Vec<ChunkStatus>, StorageError> {
+        let vault_path = vault_path.as_ref();
+        let table_path = chunk_table_path(vault_path);
+        let expected = read_chunk_table(&table_path, chunk_size)?;
+
+        let data = read_vault_file(vault_path)?;
+        let actual = chunk_macs(&data, key, chunk_size)?;
+
+        let count = expected.len().max(actual.len());
+        let statuses = (0..count)
+            .map(|i| {
+                let offset = i as u64 * u64::from(chunk_size);
+                let len = data
+                    .len()
+                    .saturating_sub(offset as usize)
+                    .min(chunk_size as usize);
+                let intact = match (expected.get(i), actual.get(i)) {
+                    (Some(e), Some(a)) => ct_eq(e, a.as_bytes()),
+                    _ => false,
+                };
+                ChunkStatus {
+                    index: i as u64,
+                    offset,
+                    len,
+                    intact,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let damaged = statuses.iter().filter(|c| !c.intact).count();
+        if damaged > 0 {
+            eprintln!(
+                "[INTEGRITY] {} of {} chunks damaged in {}",
+                damaged,
+                statuses.len(),
+                vault_path.display()
+            );
+        }
+
+        Ok(statuses)
+    }
+}
+
+// ============================================================================
+// Baseline snapshots
+// ============================================================================
+
+/// File name of the baseline snapshot inside a vault directory
+pub const SNAPSHOT_FILE_NAME: &str = "integrity.snapshot";
+
+/// Magic bytes of a snapshot file
+const SNAPSHOT_MAGIC: [u8; 8] = *b"AETSNAP1";
+
+/// BLAKE3 context deriving the snapshot MAC key from the VK
+const SNAPSHOT_MAC_CONTEXT: &str = "Aeternum 2026 integrity snapshot MAC v1";
+
+/// Size and digest of one file in a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDigest {
+    /// File size in bytes
+    pub size: u64,
+    /// BLAKE3 digest of the file contents
+    pub digest: [u8; 32],
+}
+
+/// Baseline of a vault directory's on-disk state.
+///
+/// Taken with [`IntegrityAudit::snapshot`] and compared with
+/// [`IntegrityAudit::diff`]. Persisted as:
+///
+/// ```text
+/// magic "AETSNAP1" (8) | bincode body | BLAKE3-keyed(mac_key, magic || body) (32)
+/// ```
+///
+/// `mac_key` is derived from the VK, so the baseline cannot be rewritten
+/// by anyone who can only write to the filesystem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegritySnapshot {
+    /// Epoch version recorded in the vault header
+    pub epoch: u64,
+    /// BLAKE3 digest of the 32-byte vault header
+    pub header_digest: [u8; 32],
+    /// Every regular file, keyed by `/`-separated path relative to the
+    /// vault directory
+    pub files: BTreeMap<String, FileDigest>,
+}
+
+impl IntegritySnapshot {
+    /// Serialize and MAC the snapshot with a key derived from `vk`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StorageError::ConsistencyCheckFailed` if serialization fails.
+    pub fn to_bytes(&self, vk: &[u8]) -> Result<Vec<u8>, StorageError> {
+        let body = bincode::serialize(self).map_err(|e| {
+            StorageError::consistency_check(format!("Failed to serialize snapshot: {}", e))
+        })?;
+
+        let mut bytes = Vec::with_capacity(SNAPSHOT_MAGIC.len() + body.len() + 32);
+        bytes.extend_from_slice(&SNAPSHOT_MAGIC);
+        bytes.extend_from_slice(&body);
+        let mac = keyed_hash(&snapshot_mac_key(vk), &bytes);
+        bytes.extend_from_slice(mac.as_bytes());
+        Ok(bytes)
+    }
+
+    /// Verify and parse a snapshot produced by [`to_bytes`](Self::to_bytes).
+    ///
+    /// # Errors
+    ///
+    /// - `StorageError::AuthenticationFailed` if the MAC does not match
+    ///   (snapshot tampered with or wrong VK)
+    /// - `StorageError::ConsistencyCheckFailed` if the bytes are malformed
+    pub fn from_bytes(bytes: &[u8], vk: &[u8]) -> Result<Self, StorageError> {
+        if bytes.len() < SNAPSHOT_MAGIC.len() + 32 || bytes[..8] != SNAPSHOT_MAGIC {
+            return Err(StorageError::consistency_check(
+                "Malformed integrity snapshot".to_string(),
+            ));
+        }
+
+        let (signed, mac) = bytes.split_at(bytes.len() - 32);
+        let expected = keyed_hash(&snapshot_mac_key(vk), signed);
+        if !ct_eq(expected.as_bytes(), mac) {
+            return Err(StorageError::authentication(
+                "Integrity snapshot MAC mismatch: baseline has been tampered with".to_string(),
+            ));
+        }
+
+        bincode::deserialize(&signed[SNAPSHOT_MAGIC.len()..]).map_err(|e| {
+            StorageError::consistency_check(format!("Failed to deserialize snapshot: {}", e))
+        })
+    }
+
+    /// Write the snapshot to `path` atomically.
+    ///
+    /// # Errors
+    ///
+    /// Shadow write errors if the file cannot be written.
+    pub fn write(&self, path: impl AsRef<Path>, vk: &[u8]) -> Result<(), StorageError> {
+        write_sidecar(path.as_ref(), &self.to_bytes(vk)?, "integrity snapshot")
+    }
+
+    /// Read and verify a snapshot written by [`write`](Self::write).
+    ///
+    /// # Errors
+    ///
+    /// - `StorageError::AuthenticationFailed` if the file was tampered with
+    /// - `StorageError::ConsistencyCheckFailed` if the file cannot be read
+    ///   or is malformed
+    pub fn read(path: impl AsRef<Path>, vk: &[u8]) -> Result<Self, StorageError> {
+        let path = path.as_ref();
+        reject_symlink(path)?;
+        let bytes = std::fs::read(path).map_err(|e| {
+            StorageError::consistency_check(format!(
+                "Failed to read integrity snapshot {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_bytes(&bytes, vk)
+    }
+}
+
+/// Movement of the vault epoch between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpochMovement {
+    /// Same epoch in both snapshots
+    Unchanged,
+    /// Epoch advanced (expected after an upgrade)
+    Forward {
+        /// Epoch in the old snapshot
+        from: u64,
+        /// Epoch in the new snapshot
+        to: u64,
+    },
+    /// Epoch went back (Invariant #1 violation)
+    Backward {
+        /// Epoch in the old snapshot
+        from: u64,
+        /// Epoch in the new snapshot
+        to: u64,
+    },
+}
+
+/// A file present in both snapshots with different contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModifiedFile {
+    /// Path relative to the vault directory
+    pub path: String,
+    /// Entry in the old snapshot
+    pub old: FileDigest,
+    /// Entry in the new snapshot
+    pub new: FileDigest,
+}
+
+/// Differences between two integrity snapshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityDiff {
+    /// Files only in the new snapshot
+    pub added: Vec<String>,
+    /// Files only in the old snapshot
+    pub removed: Vec<String>,
+    /// Files whose size or digest changed
+    pub modified: Vec<ModifiedFile>,
+    /// How the vault epoch moved
+    pub epoch: EpochMovement,
+    /// Whether the vault header changed
+    pub header_changed: bool,
+}
+
+impl IntegrityDiff {
+    /// Whether the two snapshots are identical
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.epoch == EpochMovement::Unchanged
+            && !self.header_changed
+    }
+
+    /// Fatal classification of the diff, if any
+    ///
+    /// An epoch regression violates Invariant #1 and must trigger meltdown;
+    /// every other difference is left to the caller to judge.
+    pub fn fatal(&self) -> Option<FatalError> {
+        match self.epoch {
+            EpochMovement::Backward { from, to } => Some(
+                InvariantViolation::EpochMonotonicity {
+                    current: u32::try_from(from).unwrap_or(u32::MAX),
+                    new: u32::try_from(to).unwrap_or(u32::MAX),
+                }
+                .into(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl IntegrityAudit<'_> {
+    /// Take a baseline snapshot of a vault directory.
+    ///
+    /// Records the BLAKE3 digest and size of every regular file under
+    /// `vault_dir` (recursively; symlinks are not followed), plus the epoch
+    /// and header digest of the vault file. The snapshot file itself and
+    /// its shadow are excluded.
+    ///
+    /// # Errors
+    ///
+    /// - `StorageError::ConsistencyCheckFailed` if the directory or a file
+    ///   cannot be read, or the vault header is missing or invalid
+    /// - `StorageError::HeaderChecksumMismatch` if the vault header is corrupt
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use aeternum_core::storage::integrity::{IntegrityAudit, IntegritySnapshot};
+    ///
+    /// let vk = [0u8; 32];
+    /// let baseline = IntegritySnapshot::read("vault/integrity.snapshot", &vk)?;
+    /// let current = IntegrityAudit::snapshot("vault")?;
+    /// let diff = IntegrityAudit::diff(&baseline, &current);
+    /// if let Some(fatal) = diff.fatal() {
+    ///     fatal.trigger_meltdown();
+    /// }
+    /// # Ok::<(), aeternum_core::storage::StorageError>(())
+    /// ```
+    pub fn snapshot(vault_dir: impl AsRef<Path>) -> Result<IntegritySnapshot, StorageError> {
+        let vault_dir = vault_dir.as_ref();
+
+        let vault_path = vault_dir.join(VAULT_FILE_NAME);
+        reject_symlink(&vault_path)?;
+        let mut header = [0u8; VAULT_HEADER_LEN];
+        std::fs::File::open(&vault_path)
+            .and_then(|mut file| file.read_exact(&mut header))
+            .map_err(|e| {
+                StorageError::consistency_check(format!(
+                    "Failed to read vault header {}: {}",
+                    vault_path.display(),
+                    e
+                ))
+            })?;
+        let epoch = parse_vault_epoch(&header)?;
+
+        let mut files = BTreeMap::new();
+        collect_file_digests(vault_dir, vault_dir, &mut files)?;
+
+        Ok(IntegritySnapshot {
+            epoch,
+            header_digest: *hash(&header).as_bytes(),
+            files,
+        })
+    }
+
+    /// Compare two snapshots of the same vault directory.
+    pub fn diff(old: &IntegritySnapshot, new: &IntegritySnapshot) -> IntegrityDiff {
+        let added = new
+            .files
+            .keys()
+            .filter(|path| !old.files.contains_key(*path))
+            .cloned()
+            .collect();
+        let removed = old
+            .files
+            .keys()
+            .filter(|path| !new.files.contains_key(*path))
+            .cloned()
+            .collect();
+        let modified = old
+            .files
+            .iter()
+            .filter_map(|(path, old_entry)| {
+                let new_entry = new.files.get(path)?;
+                (old_entry != new_entry).then(|| ModifiedFile {
+                    path: path.clone(),
+                    old: *old_entry,
+                    new: *new_entry,
+                })
+            })
+            .collect();
+
+        let epoch = match new.epoch.cmp(&old.epoch) {
+            Ordering::Equal => EpochMovement::Unchanged,
+            Ordering::Greater => EpochMovement::Forward {
+                from: old.epoch,
+                to: new.epoch,
+            },
+            Ordering::Less => EpochMovement::Backward {
+                from: old.epoch,
+                to: new.epoch,
+            },
+        };
+
+        IntegrityDiff {
+            added,
+            removed,
+            modified,
+            epoch,
+            header_changed: old.header_digest != new.header_digest,
+        }
+    }
+}
+
+/// Derive the snapshot MAC key from the VK
+fn snapshot_mac_key(vk: &[u8]) -> Zeroizing<[u8; 32]> {
+    let derived = Zeroizing::new(DeriveKey::new(&[], SNAPSHOT_MAC_CONTEXT).derive(vk, 32));
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    key.copy_from_slice(&derived);
+    key
+}
+
+/// Record the digest of every regular file under `dir`.
+fn collect_file_digests(
+    root: &Path,
+    dir: &Path,
+    files: &mut BTreeMap<String, FileDigest>,
+) -> Result<(), StorageError> {
+    let read_failed = |path: &Path, e: std::io::Error| {
+        StorageError::consistency_check(format!(
+            "Failed to read vault directory {}: {}",
+            path.display(),
+            e
+        ))
+    };
+
+    for entry in std::fs::read_dir(dir).map_err(|e| read_failed(dir, e))? {
+        let entry = entry.map_err(|e| read_failed(dir, e))?;
+        let path = entry.path();
+        let file_type = entry.file_type().map_err(|e| read_failed(&path, e))?;
+
+        if file_type.is_dir() {
+            collect_file_digests(root, &path, files)?;
+        } else if file_type.is_file() {
+            let relative = path
+                .strip_prefix(root)
+                .expect("entry is under the vault directory")
+                .iter()
+                .map(|part| part.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            if relative.starts_with(SNAPSHOT_FILE_NAME) {
+                continue;
+            }
+
+            let (size, digest) = stream_hash(&path, Blake3Hasher::new())?;
+            files.insert(
+                relative,
+                FileDigest {
+                    size,
+                    digest: *digest.as_bytes(),
+                },
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a whole vault file, refusing symlinks.
+fn read_vault_file(vault_path: &Path) -> Result<Vec<u8>, StorageError> {
+    reject_symlink(vault_path)?;
+    std::fs::read(vault_path).map_err(|e| {
+        StorageError::consistency_check(format!(
+            "Failed to read vault file {}: {}",
+            vault_path.display(),
+            e
+        ))
+    })
+}
+
+/// Stream a file through `hasher` in 64 KiB chunks, refusing symlinks.
+///
+/// Returns the number of bytes hashed and the digest.
+fn stream_hash(path: &Path, mut hasher: Blake3Hasher) -> Result<(u64, HashOutput), StorageError> {
+    reject_symlink(path)?;
+
+    let mut file = std::fs::File::open(path).map_err(|e| {
+        StorageError::consistency_check(format!(
+            "Failed to open vault file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let mut buffer = vec![0u8; MAC_CHUNK_SIZE];
+    let mut size = 0u64;
+
+    loop {
+        let n = match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                return Err(StorageError::consistency_check(format!(
+                    "Failed to read vault file {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+        hasher.update(&buffer[..n]);
+        size += n as u64;
+    }
+
+    Ok((size, hasher.finalize()))
+}
+
+/// Write `bytes` to `path` through a fsynced shadow file.
+fn write_sidecar(path: &Path, bytes: &[u8], what: &str) -> Result<(), StorageError> {
+    let writer = ShadowWriter::new(path);
+    let mut shadow = writer.begin_shadow_write()?;
+    shadow.write_all(bytes).map_err(|e| {
+        StorageError::shadow_write(format!(
+            "Failed to write {} {}: {}",
+            what,
+            shadow.path().display(),
+            e
+        ))
+    })?;
+    shadow.file().sync_all().map_err(|e| {
+        StorageError::fsync(format!(
+            "Failed to fsync {} {}: {}",
+            what,
+            shadow.path().display(),
+            e
+        ))
+    })?;
+    writer.commit_shadow_write(shadow)
+}
+
+/// Compute `BLAKE3-keyed(key, index_be64 || chunk)` for every chunk.
+fn chunk_macs(
+    data: &[u8],
+    key: &[u8; 32],
+    chunk_size: u32,
+) -> Result<Vec<HashOutput>, StorageError> {
+    if chunk_size == 0 {
+        return Err(StorageError::consistency_check(
+            "Chunk size must be non-zero".to_string(),
+        ));
+    }
+
+    Ok(data
+        .chunks(chunk_size as usize)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut hasher = Blake3Hasher::new_keyed(key);
+            hasher.update(&(i as u64).to_be_bytes()).update(chunk);
+            hasher.finalize()
+        })
+        .collect())
+}
+
+/// Parse a chunk-MAC table, checking it was recorded with `chunk_size`.
+fn read_chunk_table(table_path: &Path, chunk_size: u32) -> Result<Vec<[u8; 32]>, StorageError> {
+    reject_symlink(table_path)?;
+    let bytes = std::fs::read(table_path).map_err(|e| {
+        StorageError::consistency_check(format!(
+            "Failed to read chunk table {}: {}",
+            table_path.display(),
+            e
+        ))
+    })?;
+
+    let malformed = || {
+        StorageError::consistency_check(format!("Malformed chunk table {}", table_path.display()))
+    };
+
+    if bytes.len() < CHUNK_TABLE_HEADER_LEN || bytes[0..8] != CHUNK_TABLE_MAGIC {
+        return Err(malformed());
+    }
+
+    let recorded_chunk_size = u32::from_be_bytes(bytes[8..12].try_into().unwrap());
+    if recorded_chunk_size != chunk_size {
+        return Err(StorageError::consistency_check(format!(
+            "Chunk table {} was recorded with chunk size {}, not {}",
+            table_path.display(),
+            recorded_chunk_size,
+            chunk_size
+        )));
+    }
+
+    let count = u64::from_be_bytes(bytes[20..28].try_into().unwrap());
+    let macs = &bytes[CHUNK_TABLE_HEADER_LEN..];
+    if macs.len() as u64 != count.saturating_mul(32) {
+        return Err(malformed());
+    }
+
+    Ok(macs
+        .chunks_exact(32)
+        .map(|mac| mac.try_into().unwrap())
+        .collect())
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -480,4 +1205,288 @@ mod tests {
         assert_eq!(hex.len(), 64);
         assert!(hex.chars().all(|c| c.is_ascii_hexdigit()));
     }
+
+    // ------------------------------------------------------------------------
+    // Full-file MAC Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_file_mac_untouched_file_verifies() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+
+        // Larger than one chunk, not a multiple of the chunk size
+        let data: Vec<u8> = (0..MAC_CHUNK_SIZE * 3 + 17).map(|i| i as u8).collect();
+        std::fs::write(&vault_path, &data).unwrap();
+
+        let key = [0x42u8; 32];
+        let mac = IntegrityAudit::compute_mac(&vault_path, &key).unwrap();
+
+        assert_eq!(mac, crate::crypto::hash::keyed_hash(&key, &data));
+        assert!(IntegrityAudit::verify(&vault_path, &key, &mac).is_ok());
+    }
+
+    #[test]
+    fn test_file_mac_detects_flipped_byte() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+
+        let mut data = vec![0xAAu8; MAC_CHUNK_SIZE + 100];
+        std::fs::write(&vault_path, &data).unwrap();
+
+        let key = [0x42u8; 32];
+        let mac = IntegrityAudit::compute_mac(&vault_path, &key).unwrap();
+
+        data[MAC_CHUNK_SIZE + 50] ^= 0x01;
+        std::fs::write(&vault_path, &data).unwrap();
+
+        let result = IntegrityAudit::verify(&vault_path, &key, &mac);
+        assert!(matches!(result, Err(StorageError::AuthenticationFailed(_))));
+    }
+
+    #[test]
+    fn test_file_mac_wrong_key_fails() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        std::fs::write(&vault_path, b"vault data").unwrap();
+
+        let mac = IntegrityAudit::compute_mac(&vault_path, &[1u8; 32]).unwrap();
+        let result = IntegrityAudit::verify(&vault_path, &[2u8; 32], &mac);
+        assert!(matches!(result, Err(StorageError::AuthenticationFailed(_))));
+    }
+
+    #[test]
+    fn test_file_mac_missing_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let result = IntegrityAudit::compute_mac(temp_dir.path().join("missing.db"), &[0u8; 32]);
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+    }
+
+    // ------------------------------------------------------------------------
+    // Chunk Audit Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_audit_chunks_untouched_file_all_intact() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        std::fs::write(&vault_path, vec![0x11u8; 4096 * 3 + 5]).unwrap();
+
+        let key = [0x42u8; 32];
+        IntegrityAudit::write_chunk_table(&vault_path, &key, 4096).unwrap();
+        let statuses = IntegrityAudit::audit_chunks(&vault_path, &key, 4096).unwrap();
+
+        assert_eq!(statuses.len(), 4);
+        assert!(statuses.iter().all(|c| c.intact));
+        assert_eq!(statuses[3].offset, 4096 * 3);
+        assert_eq!(statuses[3].len, 5);
+    }
+
+    #[test]
+    fn test_audit_chunks_flags_exactly_the_corrupted_chunk() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let chunk_size = 1024u32;
+        let data: Vec<u8> = (0..chunk_size as usize * 8).map(|i| i as u8).collect();
+        let key = [0x42u8; 32];
+
+        for n in [0usize, 1023, 1024, 4000, data.len() - 1] {
+            std::fs::write(&vault_path, &data).unwrap();
+            IntegrityAudit::write_chunk_table(&vault_path, &key, chunk_size).unwrap();
+
+            let mut corrupted = data.clone();
+            corrupted[n] ^= 0x80;
+            std::fs::write(&vault_path, &corrupted).unwrap();
+
+            let damaged: Vec<u64> = IntegrityAudit::audit_chunks(&vault_path, &key, chunk_size)
+                .unwrap()
+                .into_iter()
+                .filter(|c| !c.intact)
+                .map(|c| c.index)
+                .collect();
+            assert_eq!(
+                damaged,
+                vec![(n / chunk_size as usize) as u64],
+                "byte {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_audit_chunks_truncation_flags_missing_chunks() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let data = vec![0x22u8; 300];
+        std::fs::write(&vault_path, &data).unwrap();
+
+        let key = [0x42u8; 32];
+        IntegrityAudit::write_chunk_table(&vault_path, &key, 100).unwrap();
+        std::fs::write(&vault_path, &data[..150]).unwrap();
+
+        let statuses = IntegrityAudit::audit_chunks(&vault_path, &key, 100).unwrap();
+        let intact: Vec<bool> = statuses.iter().map(|c| c.intact).collect();
+        assert_eq!(intact, vec![true, false, false]);
+        assert_eq!(statuses[2].len, 0);
+    }
+
+    #[test]
+    fn test_audit_chunks_rejects_mismatched_chunk_size() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        std::fs::write(&vault_path, b"vault data").unwrap();
+
+        let key = [0x42u8; 32];
+        IntegrityAudit::write_chunk_table(&vault_path, &key, 4).unwrap();
+
+        assert!(matches!(
+            IntegrityAudit::audit_chunks(&vault_path, &key, 8),
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+        assert!(matches!(
+            IntegrityAudit::write_chunk_table(&vault_path, &key, 0),
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_audit_chunks_wrong_key_flags_every_chunk() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        std::fs::write(&vault_path, vec![0x33u8; 64]).unwrap();
+
+        IntegrityAudit::write_chunk_table(&vault_path, &[1u8; 32], 16).unwrap();
+        let statuses = IntegrityAudit::audit_chunks(&vault_path, &[2u8; 32], 16).unwrap();
+        assert!(statuses.iter().all(|c| !c.intact));
+    }
+
+    // ------------------------------------------------------------------------
+    // Snapshot Tests
+    // ------------------------------------------------------------------------
+
+    fn write_vault(vault_dir: &Path, version: u64) {
+        use crate::models::epoch::{CryptoAlgorithm, CryptoEpoch};
+        use crate::models::vault::{VaultBlob, VaultHeader};
+
+        let blob = VaultBlob::new(
+            VaultBlob::CURRENT_BLOB_VERSION,
+            CryptoEpoch::new(version, CryptoAlgorithm::V1),
+            vec![0xAB; 64],
+            [0x11; 16],
+            [0x22; 24],
+        );
+        let mut bytes = VaultHeader::new(&blob).to_bytes().to_vec();
+        bytes.extend_from_slice(&blob.serialize().unwrap());
+        std::fs::write(vault_dir.join(VAULT_FILE_NAME), bytes).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_no_change_diff_is_empty() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        write_vault(temp_dir.path(), 3);
+        std::fs::write(temp_dir.path().join("metadata.db"), b"metadata").unwrap();
+
+        let baseline = IntegrityAudit::snapshot(temp_dir.path()).unwrap();
+        baseline
+            .write(temp_dir.path().join(SNAPSHOT_FILE_NAME), &[7u8; 32])
+            .unwrap();
+        let current = IntegrityAudit::snapshot(temp_dir.path()).unwrap();
+
+        assert_eq!(baseline.epoch, 3);
+        assert_eq!(baseline.files.len(), 2);
+        let diff = IntegrityAudit::diff(&baseline, &current);
+        assert!(diff.is_empty());
+        assert!(diff.fatal().is_none());
+    }
+
+    #[test]
+    fn test_snapshot_diff_flags_modified_blob() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        write_vault(temp_dir.path(), 3);
+        std::fs::write(temp_dir.path().join("old.log"), b"log").unwrap();
+        let baseline = IntegrityAudit::snapshot(temp_dir.path()).unwrap();
+
+        let vault_path = temp_dir.path().join(VAULT_FILE_NAME);
+        let mut bytes = std::fs::read(&vault_path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 0x01;
+        std::fs::write(&vault_path, bytes).unwrap();
+        std::fs::remove_file(temp_dir.path().join("old.log")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("sub")).unwrap();
+        std::fs::write(temp_dir.path().join("sub/new.bin"), b"new").unwrap();
+
+        let diff = IntegrityAudit::diff(
+            &baseline,
+            &IntegrityAudit::snapshot(temp_dir.path()).unwrap(),
+        );
+
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].path, VAULT_FILE_NAME);
+        assert_ne!(diff.modified[0].old.digest, diff.modified[0].new.digest);
+        assert_eq!(diff.added, vec!["sub/new.bin".to_string()]);
+        assert_eq!(diff.removed, vec!["old.log".to_string()]);
+        // Only the blob changed, not the header or epoch
+        assert!(!diff.header_changed);
+        assert_eq!(diff.epoch, EpochMovement::Unchanged);
+        assert!(diff.fatal().is_none());
+    }
+
+    #[test]
+    fn test_snapshot_diff_epoch_regression_is_fatal() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        write_vault(temp_dir.path(), 5);
+        let at_five = IntegrityAudit::snapshot(temp_dir.path()).unwrap();
+        write_vault(temp_dir.path(), 6);
+        let at_six = IntegrityAudit::snapshot(temp_dir.path()).unwrap();
+
+        let forward = IntegrityAudit::diff(&at_five, &at_six);
+        assert_eq!(forward.epoch, EpochMovement::Forward { from: 5, to: 6 });
+        assert!(forward.header_changed);
+        assert!(forward.fatal().is_none());
+
+        let backward = IntegrityAudit::diff(&at_six, &at_five);
+        assert_eq!(backward.epoch, EpochMovement::Backward { from: 6, to: 5 });
+        assert!(matches!(
+            backward.fatal(),
+            Some(FatalError::InvariantViolationTriggered(_))
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_file_tampering_rejected() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        write_vault(temp_dir.path(), 3);
+        let snapshot_path = temp_dir.path().join(SNAPSHOT_FILE_NAME);
+        let vk = [7u8; 32];
+
+        let baseline = IntegrityAudit::snapshot(temp_dir.path()).unwrap();
+        baseline.write(&snapshot_path, &vk).unwrap();
+        assert_eq!(
+            IntegritySnapshot::read(&snapshot_path, &vk).unwrap(),
+            baseline
+        );
+
+        // Rewriting the baseline without the VK is detected
+        let mut forged = baseline.clone();
+        forged.epoch = 1;
+        forged.write(&snapshot_path, &[8u8; 32]).unwrap();
+        assert!(matches!(
+            IntegritySnapshot::read(&snapshot_path, &vk),
+            Err(StorageError::AuthenticationFailed(_))
+        ));
+
+        let mut bytes = baseline.to_bytes(&vk).unwrap();
+        bytes[10] ^= 0x01;
+        assert!(matches!(
+            IntegritySnapshot::from_bytes(&bytes, &vk),
+            Err(StorageError::AuthenticationFailed(_))
+        ));
+        assert!(matches!(
+            IntegritySnapshot::from_bytes(b"short", &vk),
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+    }
 }
@@ -25,6 +25,9 @@
 //! 3. **快速失败**: 任何违规立即返回错误，不继续执行
 //! 4. **详细上下文**: 错误消息包含足够信息用于调试和审计
 //!
+//! 启动诊断场景下可使用 [`InvariantValidator::audit_all`]，它不会在第一个
+//! 违规处返回，而是收集全部违规形成报告。
+//!
 //! ## Example
 //!
 //! ```no_run
@@ -62,14 +65,35 @@ use std::time::SystemTime;
 
 use crate::models::device::{DeviceHeader, DeviceId, Operation, Role};
 use crate::models::epoch::CryptoEpoch;
+use crate::protocol::time::{SystemTimeSource, TimeSource};
 
-use super::error::StorageError;
+use super::audit_log::{AuditEvent, AuditEventType, AuditSink};
+use super::error::{InvariantViolation, StorageError};
 
 /// 时间窗口：48 小时（以毫秒为单位）
 ///
 /// 用于 Invariant #4: 否决权优先
 const VETO_WINDOW_MS: u64 = 48 * 60 * 60 * 1000;
 
+/// 进行中的恢复流程快照（用于 [`InvariantValidator::audit_all`]）
+///
+/// 描述 Invariant #3 与 #4 所需的恢复上下文。没有进行中的恢复时，
+/// 向 `audit_all` 传入 `None` 即可。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VetoState {
+    /// 当前活跃的否决信号数量
+    pub veto_count: usize,
+
+    /// 恢复流程开始时间（Unix 毫秒）
+    pub recovery_start_ms: u64,
+
+    /// 发起恢复的设备角色
+    pub initiator_role: Role,
+
+    /// 发起者请求执行的管理操作（如有）
+    pub requested_operation: Option<Operation>,
+}
+
 /// 不变量检查器（无状态，纯函数）
 ///
 /// 提供四大数学不变量的验证方法。所有方法都是纯函数，
@@ -129,6 +153,44 @@ impl InvariantValidator {
     pub fn check_epoch_monotonicity(
         current_epoch: &CryptoEpoch,
         new_epoch: &CryptoEpoch,
+    ) -> Result<(), StorageError> {
+        Self::check_epoch_monotonicity_with_max_step(current_epoch, new_epoch, 1)
+    }
+
+    /// 验证纪元严格递增，允许一次最多前进 `max_step` 个版本
+    ///
+    /// 用于批量恢复 / 重同步场景：设备离线期间错过了多次轮换，需要一次性
+    /// 追上多个纪元。严格递增规则（禁止回滚与重复）保持不变。
+    ///
+    /// # Security
+    ///
+    /// 放宽步长会削弱“线性升级”保证：攻击者若能注入一个更高版本的纪元，
+    /// 可以让设备直接跳过中间纪元，从而绕过这些纪元上的否决窗口与 Header
+    /// 完备性检查。仅应在来源已认证的重同步路径中使用大于 1 的步长，并保持
+    /// 上限尽可能小。`max_step = 0` 会拒绝一切纪元变更。
+    ///
+    /// # Errors
+    ///
+    /// 返回 `StorageError::InvariantViolation` 如果：
+    /// - `new_epoch.version <= current_epoch.version`（回滚或重复）
+    /// - `new_epoch.version - current_epoch.version > max_step`（跳跃过大）
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::storage::invariant::InvariantValidator;
+    /// use aeternum_core::models::CryptoEpoch;
+    ///
+    /// let current = CryptoEpoch::initial();
+    /// let target = CryptoEpoch::new(current.version + 3, current.algorithm);
+    ///
+    /// assert!(InvariantValidator::check_epoch_monotonicity_with_max_step(&current, &target, 3).is_ok());
+    /// assert!(InvariantValidator::check_epoch_monotonicity_with_max_step(&current, &target, 2).is_err());
+    /// ```
+    pub fn check_epoch_monotonicity_with_max_step(
+        current_epoch: &CryptoEpoch,
+        new_epoch: &CryptoEpoch,
+        max_step: u64,
     ) -> Result<(), StorageError> {
         let current = current_epoch.version;
         let new = new_epoch.version;
@@ -141,11 +203,11 @@ impl InvariantValidator {
             )));
         }
 
-        // 纪元不能跳过版本（确保线性升级）
-        if new > current + 1 {
+        // 纪元跳跃不能超过允许的步长（max_step = 1 时确保线性升级）
+        if new - current > max_step {
             return Err(StorageError::invariant(format!(
-                "Invariant #1 violation: epoch jump detected (current={}, new={})",
-                current, new
+                "Invariant #1 violation: epoch jump detected (current={}, new={}, max_step={})",
+                current, new, max_step
             )));
         }
 
@@ -372,18 +434,70 @@ impl InvariantValidator {
     pub fn check_veto_supremacy(
         veto_count: usize,
         recovery_start_timestamp_ms: u64,
+    ) -> Result<(), StorageError> {
+        Self::check_veto_supremacy_with_source(
+            veto_count,
+            recovery_start_timestamp_ms,
+            &SystemTimeSource,
+        )
+    }
+
+    /// 验证否决权优先（从注入的时间源读取当前时间）
+    ///
+    /// 与 `check_veto_supremacy` 相同，但当前时间取自 `time`，测试中可使用
+    /// `MockTimeSource` 驱动 48h 窗口而无需等待。
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::storage::invariant::InvariantValidator;
+    /// use aeternum_core::protocol::time::MockTimeSource;
+    ///
+    /// let start = 1_000;
+    /// let time = MockTimeSource::new(start);
+    /// assert!(InvariantValidator::check_veto_supremacy_with_source(1, start, &time).is_err());
+    ///
+    /// time.advance(48 * 3_600_000);
+    /// assert!(InvariantValidator::check_veto_supremacy_with_source(1, start, &time).is_ok());
+    /// ```
+    pub fn check_veto_supremacy_with_source(
+        veto_count: usize,
+        recovery_start_timestamp_ms: u64,
+        time: &dyn TimeSource,
+    ) -> Result<(), StorageError> {
+        Self::check_veto_supremacy_at(veto_count, recovery_start_timestamp_ms, time.now_ms())
+    }
+
+    /// 验证否决权优先（由调用方提供当前时间）
+    ///
+    /// 与 `check_veto_supremacy` 相同，但不读取系统时钟，便于在测试中使用
+    /// 确定的时间。
+    ///
+    /// # Arguments
+    ///
+    /// - `veto_count`: 当前活跃的否决信号数量
+    /// - `recovery_start_timestamp_ms`: 恢复流程开始时间（Unix 毫秒）
+    /// - `now_ms`: 当前时间（Unix 毫秒）
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::storage::invariant::InvariantValidator;
+    ///
+    /// let start = 1_000;
+    /// assert!(InvariantValidator::check_veto_supremacy_at(1, start, start + 1).is_err());
+    /// assert!(InvariantValidator::check_veto_supremacy_at(1, start, start + 48 * 3_600_000).is_ok());
+    /// ```
+    pub fn check_veto_supremacy_at(
+        veto_count: usize,
+        recovery_start_timestamp_ms: u64,
+        now_ms: u64,
     ) -> Result<(), StorageError> {
         // 如果没有否决信号，通过验证
         if veto_count == 0 {
             return Ok(());
         }
 
-        // 计算当前时间
-        let now_ms = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .map(|d| d.as_millis() as u64)
-            .unwrap_or(0);
-
         // 检查是否在 48h 窗口内
         let elapsed_ms = now_ms.saturating_sub(recovery_start_timestamp_ms);
 
@@ -430,6 +544,147 @@ impl InvariantValidator {
 
         Self::check_veto_supremacy(veto_count, recovery_start_ms)
     }
+
+    // ========================================================================
+    // 批量审计 (Batch Audit)
+    // ========================================================================
+
+    /// 审计全部四大不变量并收集所有违规
+    ///
+    /// 与上面的快速失败检查不同，本方法会遍历整个设备集合，把每一处违规都
+    /// 记录下来，用于启动诊断。强制执行路径仍应使用各个 `check_*` 方法。
+    ///
+    /// 检查内容：
+    /// - **#1**: 任何 Header 的纪元高于当前纪元（本地纪元发生回滚）
+    /// - **#2**: 每个活跃设备在当前纪元有且仅有一个 Header
+    /// - **#3**: 恢复发起者的角色允许其请求的管理操作
+    /// - **#4**: 48h 窗口内不存在活跃否决
+    ///
+    /// # Arguments
+    ///
+    /// - `headers`: 所有设备的 Header 列表
+    /// - `epoch`: 当前纪元
+    /// - `veto_state`: 进行中的恢复流程，没有时为 `None`
+    ///
+    /// # Returns
+    ///
+    /// 按不变量编号排序的违规列表；为空表示全部通过。
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::storage::invariant::InvariantValidator;
+    /// use aeternum_core::models::CryptoEpoch;
+    ///
+    /// let report = InvariantValidator::audit_all(&[], &CryptoEpoch::initial(), None);
+    /// assert!(report.is_empty());
+    /// ```
+    pub fn audit_all(
+        headers: &[DeviceHeader],
+        epoch: &CryptoEpoch,
+        veto_state: Option<&VetoState>,
+    ) -> Vec<InvariantViolation> {
+        let mut violations = Vec::new();
+
+        // Invariant #1: 设备 Header 不能领先于当前纪元
+        for header in headers {
+            if header.epoch.version > epoch.version {
+                violations.push(InvariantViolation::EpochMonotonicity {
+                    current: u32::try_from(epoch.version).unwrap_or(u32::MAX),
+                    new: u32::try_from(header.epoch.version).unwrap_or(u32::MAX),
+                });
+            }
+        }
+
+        // Invariant #2: 每个活跃设备（按首次出现顺序）
+        let mut seen = HashSet::new();
+        for header in headers {
+            if header.status != crate::models::DeviceStatus::Active
+                || !seen.insert(header.device_id)
+            {
+                continue;
+            }
+            if Self::check_header_completeness(headers, &header.device_id, epoch).is_err() {
+                violations.push(InvariantViolation::HeaderIncomplete {
+                    device: format!("{:?}", header.device_id),
+                });
+            }
+        }
+
+        if let Some(state) = veto_state {
+            // Invariant #3
+            if let Some(operation) = state.requested_operation {
+                if Self::check_causal_barrier(&state.initiator_role, &operation).is_err() {
+                    violations.push(InvariantViolation::CausalBarrier {
+                        role: state.initiator_role.as_str().to_string(),
+                        op: operation.as_str().to_string(),
+                    });
+                }
+            }
+
+            // Invariant #4
+            if Self::check_veto_supremacy(state.veto_count, state.recovery_start_ms).is_err() {
+                violations.push(InvariantViolation::VetoSupremacy {
+                    count: state.veto_count,
+                });
+            }
+        }
+
+        violations
+    }
+
+    // ========================================================================
+    // 审计上报 (Audit Reporting)
+    // ========================================================================
+
+    /// 将检查失败上报审计日志，并原样返回检查结果
+    ///
+    /// 检查器本身保持无状态：调用方把任意 `check_*` 的结果连同 [`AuditSink`]
+    /// 传入，失败时记录一条 `InvariantViolation` 事件（subject 为违反的不变量
+    /// 编号，detail 为错误消息）。`sink` 为 `None` 时不做任何记录。
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use aeternum_core::storage::audit_log::AuditLog;
+    /// use aeternum_core::storage::invariant::InvariantValidator;
+    /// use aeternum_core::models::CryptoEpoch;
+    ///
+    /// let log = AuditLog::open("audit.log")?;
+    /// let current = CryptoEpoch::initial();
+    /// let result = InvariantValidator::check_epoch_monotonicity(&current, &current);
+    ///
+    /// // 违规被写入审计日志后仍返回原错误
+    /// assert!(InvariantValidator::audited(result, Some(&log), current.version).is_err());
+    /// # Ok::<(), aeternum_core::storage::error::StorageError>(())
+    /// ```
+    pub fn audited(
+        result: Result<(), StorageError>,
+        sink: Option<&dyn AuditSink>,
+        epoch: u64,
+    ) -> Result<(), StorageError> {
+        if let (Err(e), Some(sink)) = (&result, sink) {
+            let message = e.to_string();
+            // 错误消息形如 "... Invariant #N violation: ..."
+            let subject = message
+                .find("Invariant #")
+                .map(|start| {
+                    message[start..]
+                        .split(' ')
+                        .take(2)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_else(|| "Invariant".to_string());
+            sink.record(AuditEvent::new(
+                AuditEventType::InvariantViolation,
+                epoch,
+                subject,
+                message,
+            ));
+        }
+        result
+    }
 }
 
 // ============================================================================
@@ -441,6 +696,7 @@ mod tests {
     use super::*;
     use crate::crypto::kem::KyberKEM;
     use crate::models::DeviceStatus;
+    use crate::protocol::time::MockTimeSource;
     use std::time::{Duration, SystemTime};
 
     // ------------------------------------------------------------------------
@@ -488,6 +744,77 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("jump detected"));
     }
 
+    #[test]
+    fn test_epoch_monotonicity_max_step_allows_jumps_within_limit() {
+        let current = CryptoEpoch::new(10, CryptoEpoch::initial().algorithm);
+
+        for max_step in [1u64, 2, 5, 100] {
+            for step in 1..=max_step.min(10) {
+                let target = CryptoEpoch::new(current.version + step, current.algorithm);
+                assert!(
+                    InvariantValidator::check_epoch_monotonicity_with_max_step(
+                        &current, &target, max_step
+                    )
+                    .is_ok(),
+                    "step {} should pass with max_step {}",
+                    step,
+                    max_step
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_epoch_monotonicity_max_step_rejects_jumps_over_limit() {
+        let current = CryptoEpoch::new(10, CryptoEpoch::initial().algorithm);
+
+        for max_step in [1u64, 2, 5] {
+            let target = CryptoEpoch::new(current.version + max_step + 1, current.algorithm);
+            let result = InvariantValidator::check_epoch_monotonicity_with_max_step(
+                &current, &target, max_step,
+            );
+            assert!(result.unwrap_err().to_string().contains("jump detected"));
+        }
+    }
+
+    #[test]
+    fn test_epoch_monotonicity_max_step_keeps_strict_increase() {
+        let current = CryptoEpoch::new(10, CryptoEpoch::initial().algorithm);
+        let same = current;
+        let older = CryptoEpoch::new(9, current.algorithm);
+
+        for max_step in [0u64, 1, u64::MAX] {
+            assert!(InvariantValidator::check_epoch_monotonicity_with_max_step(
+                &current, &same, max_step
+            )
+            .is_err());
+            assert!(InvariantValidator::check_epoch_monotonicity_with_max_step(
+                &current, &older, max_step
+            )
+            .is_err());
+        }
+
+        // max_step = 0 rejects every change
+        let next = current.next();
+        assert!(
+            InvariantValidator::check_epoch_monotonicity_with_max_step(&current, &next, 0).is_err()
+        );
+    }
+
+    #[test]
+    fn test_epoch_monotonicity_max_step_no_overflow() {
+        let current = CryptoEpoch::new(0, CryptoEpoch::initial().algorithm);
+        let target = CryptoEpoch::new(u64::MAX, current.algorithm);
+
+        assert!(InvariantValidator::check_epoch_monotonicity_with_max_step(
+            &current,
+            &target,
+            u64::MAX
+        )
+        .is_ok());
+        assert!(InvariantValidator::check_epoch_monotonicity(&current, &target).is_err());
+    }
+
     // ------------------------------------------------------------------------
     // Invariant #2: Header Completeness Tests
     // ------------------------------------------------------------------------
@@ -550,8 +877,11 @@ mod tests {
             epoch: epoch.clone(),
             public_key: keypair.public,
             encrypted_dek,
+            dek_nonce: [0u8; 24],
+            dek_ciphertext: Vec::new(),
             status: DeviceStatus::Active,
             created_at: 0,
+            degraded_info: None,
         };
         let header2 = header1.clone();
         let headers = vec![header1, header2];
@@ -669,72 +999,187 @@ mod tests {
     // Invariant #4: Veto Supremacy Tests
     // ------------------------------------------------------------------------
 
+    /// 恢复开始时间（模拟时钟起点）
+    const RECOVERY_START_MS: u64 = 1_700_000_000_000;
+
+    /// 在恢复开始 `elapsed_ms` 毫秒后检查否决权优先
+    fn check_veto_after(veto_count: usize, elapsed_ms: u64) -> Result<(), StorageError> {
+        let time = MockTimeSource::new(RECOVERY_START_MS);
+        time.advance(elapsed_ms);
+        InvariantValidator::check_veto_supremacy_with_source(veto_count, RECOVERY_START_MS, &time)
+    }
+
     #[test]
     fn test_veto_supremacy_pass_no_veto() {
-        let veto_count = 0;
-        let recovery_start = SystemTime::now();
-
         // 通过：没有否决信号
-        assert!(
-            InvariantValidator::check_veto_supremacy_with_time(veto_count, &recovery_start).is_ok()
-        );
+        assert!(check_veto_after(0, 0).is_ok());
     }
 
     #[test]
     fn test_veto_supremacy_pass_expired_window() {
-        let veto_count = 5;
-        let recovery_start = SystemTime::now() - Duration::from_millis(48 * 60 * 60 * 1000 + 1000);
-
         // 通过：48h 窗口已过期
-        assert!(
-            InvariantValidator::check_veto_supremacy_with_time(veto_count, &recovery_start).is_ok()
-        );
+        assert!(check_veto_after(5, VETO_WINDOW_MS + 1000).is_ok());
     }
 
     #[test]
     fn test_veto_supremacy_fail_veto_in_window() {
-        let veto_count = 1;
-        let recovery_start = SystemTime::now();
-
         // 违规：48h 窗口内有否决
-        let result =
-            InvariantValidator::check_veto_supremacy_with_time(veto_count, &recovery_start);
+        let result = check_veto_after(1, 0);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Invariant #4"));
     }
 
     #[test]
     fn test_veto_supremacy_fail_multiple_vetoes() {
-        let veto_count = 3;
-        let recovery_start = SystemTime::now() - Duration::from_millis(1000); // 1 秒前
-
-        // 违规：48h 窗口内有多个否决
-        let result =
-            InvariantValidator::check_veto_supremacy_with_time(veto_count, &recovery_start);
+        // 违规：48h 窗口内有多个否决（1 秒后）
+        let result = check_veto_after(3, 1000);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("vetoes=3"));
     }
 
     #[test]
     fn test_veto_window_exactly_48_hours() {
-        let veto_count = 1;
-        let recovery_start = SystemTime::now() - Duration::from_millis(48 * 60 * 60 * 1000);
-
         // 边界情况：正好 48h，应该通过
+        assert!(check_veto_after(1, VETO_WINDOW_MS).is_ok());
+    }
+
+    #[test]
+    fn test_veto_window_one_ms_before() {
+        // 边界情况：48h 窗口内 1ms，应该失败
+        assert!(check_veto_after(1, VETO_WINDOW_MS - 1).is_err());
+    }
+
+    #[test]
+    fn test_veto_supremacy_with_time_uses_system_clock() {
+        // 兼容接口：SystemTime 起点，48h 前开始的恢复应该通过
+        let recovery_start = SystemTime::now() - Duration::from_millis(VETO_WINDOW_MS + 1000);
+        assert!(InvariantValidator::check_veto_supremacy_with_time(1, &recovery_start).is_ok());
+        assert!(InvariantValidator::check_veto_supremacy_with_time(1, &SystemTime::now()).is_err());
+    }
+
+    #[test]
+    fn test_veto_supremacy_at_fixed_time() {
+        let start = 1_700_000_000_000;
+
+        assert!(InvariantValidator::check_veto_supremacy_at(0, start, start).is_ok());
+        assert!(InvariantValidator::check_veto_supremacy_at(1, start, start).is_err());
+        assert!(
+            InvariantValidator::check_veto_supremacy_at(1, start, start + VETO_WINDOW_MS - 1)
+                .is_err()
+        );
         assert!(
-            InvariantValidator::check_veto_supremacy_with_time(veto_count, &recovery_start).is_ok()
+            InvariantValidator::check_veto_supremacy_at(1, start, start + VETO_WINDOW_MS).is_ok()
         );
     }
 
+    // ------------------------------------------------------------------------
+    // Batch Audit Tests
+    // ------------------------------------------------------------------------
+
+    fn now_ms() -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64
+    }
+
     #[test]
-    fn test_veto_window_one_ms_before() {
-        let veto_count = 1;
-        let recovery_start = SystemTime::now() - Duration::from_millis(48 * 60 * 60 * 1000 - 1);
+    fn test_audit_all_clean_state() {
+        let epoch = CryptoEpoch::initial();
+        let headers = vec![
+            create_test_header(DeviceId::generate(), epoch),
+            create_test_header(DeviceId::generate(), epoch),
+        ];
+        let veto_state = VetoState {
+            veto_count: 0,
+            recovery_start_ms: now_ms(),
+            initiator_role: Role::Recovery,
+            requested_operation: None,
+        };
 
-        // 边界情况：48h 窗口内 1ms，应该失败
-        let result =
-            InvariantValidator::check_veto_supremacy_with_time(veto_count, &recovery_start);
-        assert!(result.is_err());
+        assert!(InvariantValidator::audit_all(&headers, &epoch, Some(&veto_state)).is_empty());
+    }
+
+    #[test]
+    fn test_audit_all_reports_every_violation() {
+        let epoch = CryptoEpoch::initial().next();
+        let stale_device = DeviceId::generate();
+        let headers = vec![
+            create_test_header(DeviceId::generate(), epoch),
+            // 违规 #2：Header 仍停留在旧纪元
+            create_test_header(stale_device, CryptoEpoch::initial()),
+        ];
+        // 违规 #4：48h 窗口内有否决
+        let veto_state = VetoState {
+            veto_count: 2,
+            recovery_start_ms: now_ms(),
+            initiator_role: Role::Recovery,
+            requested_operation: None,
+        };
+
+        let report = InvariantValidator::audit_all(&headers, &epoch, Some(&veto_state));
+
+        assert_eq!(
+            report,
+            vec![
+                InvariantViolation::HeaderIncomplete {
+                    device: format!("{:?}", stale_device),
+                },
+                InvariantViolation::VetoSupremacy { count: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_audit_all_epoch_and_causal_barrier() {
+        let epoch = CryptoEpoch::initial();
+        let ahead = CryptoEpoch::new(epoch.version + 3, epoch.algorithm);
+        let mut revoked = create_test_header(DeviceId::generate(), ahead);
+        revoked.status = DeviceStatus::Revoked;
+        let veto_state = VetoState {
+            veto_count: 0,
+            recovery_start_ms: now_ms(),
+            initiator_role: Role::Recovery,
+            requested_operation: Some(Operation::SigmaRotate),
+        };
+
+        let report = InvariantValidator::audit_all(&[revoked], &epoch, Some(&veto_state));
+
+        assert_eq!(report.len(), 2);
+        assert_eq!(
+            report[0],
+            InvariantViolation::EpochMonotonicity {
+                current: epoch.version as u32,
+                new: ahead.version as u32,
+            }
+        );
+        assert!(matches!(
+            report[1],
+            InvariantViolation::CausalBarrier { .. }
+        ));
+    }
+
+    #[test]
+    fn test_audited_records_only_failures() {
+        use crate::storage::audit_log::AuditLog;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let log = AuditLog::open(dir.path().join("audit.log")).unwrap();
+        let current = CryptoEpoch::initial();
+
+        let ok = InvariantValidator::check_epoch_monotonicity(&current, &current.next());
+        assert!(InvariantValidator::audited(ok, Some(&log), current.version).is_ok());
+        let err = InvariantValidator::check_epoch_monotonicity(&current, &current);
+        assert!(InvariantValidator::audited(err, Some(&log), current.version).is_err());
+
+        let records = log.records().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            records[0].event.event_type,
+            AuditEventType::InvariantViolation
+        );
+        assert_eq!(records[0].event.subject, "Invariant #1");
+        assert_eq!(log.verify_chain().unwrap(), 1);
     }
 }
 
@@ -746,6 +1191,7 @@ mod tests {
 mod proptest_tests {
     use super::*;
     use crate::models::epoch::CryptoEpoch;
+    use crate::protocol::time::MockTimeSource;
     use proptest::prelude::*;
 
     // PropTest strategy for epoch version (1..1000)
@@ -790,9 +1236,13 @@ mod proptest_tests {
             // 属性：48h 窗口外的否决被忽略
             let veto_count = 1;
             let hours_ago_ms = hours_ago * 60 * 60 * 1000;
-            let recovery_start = SystemTime::now() - std::time::Duration::from_millis(hours_ago_ms);
 
-            let result = InvariantValidator::check_veto_supremacy_with_time(veto_count, &recovery_start);
+            let recovery_start = 1_700_000_000_000;
+            let time = MockTimeSource::new(recovery_start);
+            time.advance(hours_ago_ms);
+
+            let result =
+                InvariantValidator::check_veto_supremacy_with_source(veto_count, recovery_start, &time);
 
             // 48+ 小时前开始的恢复应该通过
             prop_assert_eq!(result.is_ok(), hours_ago >= 48);
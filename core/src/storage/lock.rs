@@ -0,0 +1,295 @@
+//! # Vault Write Lock
+//!
+//! Exclusive advisory lock that keeps two processes (or a backgrounded and a
+//! foregrounded app instance) from running the AUP on the same vault at the
+//! same time and interleaving their renames.
+//!
+//! ## Mechanism
+//!
+//! The lock is taken on a sidecar file `<vault>.lock` through `fs2`
+//! (`flock` on Unix, `LockFileEx` on Windows). Locking fails closed: if the
+//! platform or filesystem cannot lock, acquisition returns an error rather
+//! than letting the writer proceed unexcluded.
+//!
+//! Acquisition never blocks indefinitely: [`VaultLock::try_acquire`] fails
+//! at once if another writer holds the lock, and
+//! [`VaultLock::acquire_timeout`] retries until a deadline. The lock is
+//! released when the [`VaultLock`] is dropped, and by the OS if the process
+//! dies. The lock file itself is left in place; deleting it would let a
+//! third writer lock a fresh file while the second still holds the old one.
+//!
+//! Every function that writes a vault or one of its side files takes the
+//! lock through [`VaultLock::hold`], which nests inside a lock the calling
+//! thread already holds. A multi-phase operation therefore keeps other
+//! writers out for its whole duration, while a standalone write still runs
+//! locked.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use aeternum_core::storage::lock::VaultLock;
+//!
+//! let lock = VaultLock::try_acquire("/data/vault.db")?;
+//! // ... AUP phases 1-3 ...
+//! drop(lock);
+//! # Ok::<(), aeternum_core::storage::StorageError>(())
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+use std::time::{Duration, Instant};
+
+use fs2::FileExt;
+
+use super::StorageError;
+
+/// Suffix appended to the vault file name for its lock file
+const LOCK_SUFFIX: &str = ".lock";
+
+/// Delay between attempts in [`VaultLock::acquire_timeout`]
+const RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Lock files held by this process and the thread holding each
+static HELD: Mutex<Vec<(PathBuf, ThreadId)>> = Mutex::new(Vec::new());
+
+/// Held exclusive lock on a vault
+///
+/// Released on drop.
+#[derive(Debug)]
+pub struct VaultLock {
+    /// Open lock file; closing it releases the lock
+    file: File,
+    /// Path of the lock file
+    path: PathBuf,
+}
+
+impl VaultLock {
+    /// Lock file path for `vault_path` (`<vault>.lock`)
+    pub fn lock_path(vault_path: impl AsRef<Path>) -> PathBuf {
+        let mut path = vault_path.as_ref().as_os_str().to_owned();
+        path.push(LOCK_SUFFIX);
+        PathBuf::from(path)
+    }
+
+    /// Acquire the lock for `vault_path` without waiting
+    ///
+    /// # Errors
+    ///
+    /// - `StorageError::VaultLocked` if another writer holds the lock, or if
+    ///   locking is unsupported here
+    /// - `StorageError::ShadowWriteFailed` if the lock file cannot be opened
+    pub fn try_acquire(vault_path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let path = Self::lock_path(vault_path);
+        let file = open_lock_file(&path)?;
+
+        if !try_lock_exclusive(&file, &path)? {
+            return Err(StorageError::vault_locked(format!(
+                "{} is held by another writer",
+                path.display()
+            )));
+        }
+
+        Ok(Self::held(file, path))
+    }
+
+    /// Lock `vault_path` for one write unless this thread already holds it
+    ///
+    /// Every vault writer calls this, so a write runs under the lock both
+    /// when invoked on its own and from inside an operation (such as an
+    /// epoch upgrade) that holds the lock across several phases. Returns
+    /// `None` if the calling thread already holds the lock.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`try_acquire`](Self::try_acquire).
+    pub fn hold(vault_path: impl AsRef<Path>) -> Result<Option<Self>, StorageError> {
+        let path = Self::lock_path(&vault_path);
+        let current = thread::current().id();
+        let held_here = HELD
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .any(|(held, owner)| *held == path && *owner == current);
+        if held_here {
+            return Ok(None);
+        }
+        Self::try_acquire(vault_path).map(Some)
+    }
+
+    /// Acquire the lock for `vault_path`, retrying until `timeout` elapses
+    ///
+    /// # Errors
+    ///
+    /// Same as [`try_acquire`](Self::try_acquire); `VaultLocked` is returned
+    /// once `timeout` has elapsed.
+    pub fn acquire_timeout(
+        vault_path: impl AsRef<Path>,
+        timeout: Duration,
+    ) -> Result<Self, StorageError> {
+        let path = Self::lock_path(vault_path);
+        let file = open_lock_file(&path)?;
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if try_lock_exclusive(&file, &path)? {
+                return Ok(Self::held(file, path));
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(StorageError::vault_locked(format!(
+                    "{} still held by another writer after {} ms",
+                    path.display(),
+                    timeout.as_millis()
+                )));
+            }
+            std::thread::sleep(RETRY_INTERVAL.min(deadline - now));
+        }
+    }
+
+    /// Path of the held lock file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record a lock just taken on `file` as held by the calling thread
+    fn held(file: File, path: PathBuf) -> Self {
+        HELD.lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((path.clone(), thread::current().id()));
+        Self { file, path }
+    }
+}
+
+impl Drop for VaultLock {
+    fn drop(&mut self) {
+        let mut held = HELD.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(index) = held.iter().position(|(path, _)| *path == self.path) {
+            held.swap_remove(index);
+        }
+        drop(held);
+        // Closing the file also releases the lock; ignore unlock failures
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Open (creating if needed) the lock file at `path`
+fn open_lock_file(path: &Path) -> Result<File, StorageError> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(|e| {
+            StorageError::shadow_write(format!(
+                "Failed to open lock file {}: {}",
+                path.display(),
+                e
+            ))
+        })
+}
+
+/// Take an exclusive lock on `file` without waiting
+///
+/// Returns `Ok(false)` if another writer holds the lock. Any other failure,
+/// including a platform or filesystem without lock support, is an error:
+/// the AUP must not run unexcluded.
+fn try_lock_exclusive(file: &File, path: &Path) -> Result<bool, StorageError> {
+    match FileExt::try_lock_exclusive(file) {
+        Ok(()) => Ok(true),
+        Err(e) if e.raw_os_error() == fs2::lock_contended_error().raw_os_error() => Ok(false),
+        Err(e) => Err(StorageError::vault_locked(format!(
+            "Cannot lock {}: {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lock_path_appends_suffix() {
+        assert_eq!(
+            VaultLock::lock_path("/data/vault.db"),
+            PathBuf::from("/data/vault.db.lock")
+        );
+    }
+
+    #[test]
+    fn test_second_lock_fails_while_held() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+
+        let lock = VaultLock::try_acquire(&vault_path).unwrap();
+        assert!(lock.path().exists());
+
+        assert!(matches!(
+            VaultLock::try_acquire(&vault_path),
+            Err(StorageError::VaultLocked(_))
+        ));
+
+        let start = Instant::now();
+        assert!(matches!(
+            VaultLock::acquire_timeout(&vault_path, Duration::from_millis(50)),
+            Err(StorageError::VaultLocked(_))
+        ));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        // Released on drop
+        drop(lock);
+        let relocked = VaultLock::try_acquire(&vault_path).unwrap();
+        drop(relocked);
+    }
+
+    #[test]
+    fn test_acquire_timeout_waits_for_release() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+
+        let lock = VaultLock::try_acquire(&vault_path).unwrap();
+        let holder = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(30));
+            drop(lock);
+        });
+
+        VaultLock::acquire_timeout(&vault_path, Duration::from_secs(5)).unwrap();
+        holder.join().unwrap();
+    }
+
+    #[test]
+    fn test_hold_reuses_lock_held_by_this_thread() {
+        let temp_dir = TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+
+        // Not held: hold() takes the lock itself
+        let own = VaultLock::hold(&vault_path).unwrap().unwrap();
+        assert!(matches!(
+            VaultLock::try_acquire(&vault_path),
+            Err(StorageError::VaultLocked(_))
+        ));
+        drop(own);
+
+        // Held by this thread: hold() nests inside it
+        let lock = VaultLock::try_acquire(&vault_path).unwrap();
+        assert!(VaultLock::hold(&vault_path).unwrap().is_none());
+
+        // Held by another thread: hold() is refused
+        let path = vault_path.clone();
+        let other = std::thread::spawn(move || VaultLock::hold(&path).map(|l| l.is_some()));
+        assert!(matches!(
+            other.join().unwrap(),
+            Err(StorageError::VaultLocked(_))
+        ));
+        drop(lock);
+    }
+}
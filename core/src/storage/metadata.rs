@@ -0,0 +1,420 @@
+//! # Metadata Store
+//!
+//! Persistent vault metadata, most importantly `Local_Epoch`: the epoch of
+//! the last vault blob this device committed.
+//!
+//! ## Role in AUP
+//!
+//! [`aup_atomic_commit`](super::aug::aup_atomic_commit) renames the shadow
+//! file into place and then calls [`MetadataStore::set_local_epoch`]. A crash
+//! between the two leaves the blob one epoch ahead of `Local_Epoch`
+//! (`BlobAhead`), which [`CrashRecovery::recover`](super::recovery::CrashRecovery::recover)
+//! heals on the next startup.
+//!
+//! ## Backends
+//!
+//! - [`SqliteMetadataStore`] - rusqlite-backed store. Built against plain
+//!   SQLite by default; the `sqlcipher` feature links SQLCipher and enables
+//!   [`SqliteMetadataStore::open_encrypted`]
+//! - [`InMemoryMetadataStore`] - non-persistent store for tests and for
+//!   callers that track metadata elsewhere
+//!
+//! ## Schema
+//!
+//! The schema version lives in `PRAGMA user_version`. Opening a database
+//! applies every pending migration in one transaction; a database written
+//! by a newer schema is refused.
+
+use std::path::Path;
+
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::error::StorageError;
+use super::recovery::MetadataSource;
+
+/// Schema migrations, indexed by target version minus one
+const MIGRATIONS: &[&str] = &[
+    // v1: key/value metadata table
+    "CREATE TABLE metadata (
+         key   TEXT PRIMARY KEY NOT NULL,
+         value INTEGER NOT NULL
+     );
+     INSERT INTO metadata (key, value) VALUES ('local_epoch', 0), ('device_count', 0);",
+];
+
+/// Current schema version
+pub const METADATA_SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Metadata database file name inside a vault directory
+pub const METADATA_FILE_NAME: &str = "metadata.db";
+
+const LOCAL_EPOCH_KEY: &str = "local_epoch";
+const DEVICE_COUNT_KEY: &str = "device_count";
+
+// ============================================================================
+// MetadataStore
+// ============================================================================
+
+/// Persistent vault metadata
+pub trait MetadataStore {
+    /// `Local_Epoch`: epoch of the last committed vault blob (0 if none)
+    fn get_local_epoch(&self) -> Result<u64, StorageError>;
+
+    /// Set `Local_Epoch` in a single transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns `StorageError::InvariantViolation` if `epoch` is lower than
+    /// the stored epoch (Invariant #1), or `StorageError::MetadataFailed` if
+    /// the write fails.
+    fn set_local_epoch(&mut self, epoch: u64) -> Result<(), StorageError>;
+
+    /// Number of devices recorded for this vault
+    fn get_device_count(&self) -> Result<u32, StorageError>;
+
+    /// Record the number of devices for this vault
+    fn set_device_count(&mut self, count: u32) -> Result<(), StorageError>;
+}
+
+// ============================================================================
+// SqliteMetadataStore
+// ============================================================================
+
+/// rusqlite-backed metadata store
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::storage::metadata::{MetadataStore, SqliteMetadataStore};
+///
+/// let mut store = SqliteMetadataStore::open_in_memory()?;
+/// assert_eq!(store.get_local_epoch()?, 0);
+///
+/// store.set_local_epoch(3)?;
+/// assert_eq!(store.get_local_epoch()?, 3);
+/// # Ok::<(), aeternum_core::storage::StorageError>(())
+/// ```
+pub struct SqliteMetadataStore {
+    /// Database connection (`Connection` is not `Sync`)
+    conn: Mutex<Connection>,
+}
+
+impl SqliteMetadataStore {
+    /// Open or create the metadata database at `path` and migrate it
+    ///
+    /// # Errors
+    ///
+    /// Returns `StorageError::MetadataFailed` if the database cannot be
+    /// opened or migrated.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let conn = Connection::open(path.as_ref()).map_err(|e| {
+            StorageError::metadata(format!(
+                "Failed to open metadata database {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        Self::from_connection(conn)
+    }
+
+    /// Open a SQLCipher-encrypted metadata database
+    ///
+    /// `key` is passed to SQLCipher as a raw 256-bit key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StorageError::MetadataFailed` if the database cannot be
+    /// opened, the key is wrong, or migration fails.
+    #[cfg(feature = "sqlcipher")]
+    pub fn open_encrypted(path: impl AsRef<Path>, key: &[u8; 32]) -> Result<Self, StorageError> {
+        let conn = Connection::open(path.as_ref()).map_err(|e| {
+            StorageError::metadata(format!(
+                "Failed to open metadata database {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        let key_pragma = zeroize::Zeroizing::new(format!("x'{}'", hex::encode(key)));
+        conn.pragma_update(None, "key", key_pragma.as_str())
+            .map_err(|e| StorageError::metadata(format!("Failed to set database key: {}", e)))?;
+        Self::from_connection(conn)
+    }
+
+    /// Open a fresh in-memory database
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        let conn = Connection::open_in_memory().map_err(|e| {
+            StorageError::metadata(format!("Failed to open in-memory database: {}", e))
+        })?;
+        Self::from_connection(conn)
+    }
+
+    /// Schema version of the open database
+    pub fn schema_version(&self) -> Result<u32, StorageError> {
+        read_schema_version(&self.conn.lock())
+    }
+
+    fn from_connection(mut conn: Connection) -> Result<Self, StorageError> {
+        migrate(&mut conn)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn get_value(&self, key: &str) -> Result<i64, StorageError> {
+        self.conn
+            .lock()
+            .query_row(
+                "SELECT value FROM metadata WHERE key = ?1",
+                params![key],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()
+            .map_err(|e| StorageError::metadata(format!("Failed to read {}: {}", key, e)))?
+            .ok_or_else(|| StorageError::metadata(format!("Missing metadata key {}", key)))
+    }
+
+    fn set_value(&self, key: &str, value: i64) -> Result<(), StorageError> {
+        self.conn
+            .lock()
+            .execute(
+                "UPDATE metadata SET value = ?2 WHERE key = ?1",
+                params![key, value],
+            )
+            .map_err(|e| StorageError::metadata(format!("Failed to write {}: {}", key, e)))?;
+        Ok(())
+    }
+
+    /// Monotonic `Local_Epoch` update shared by both metadata traits
+    fn store_local_epoch(&self, epoch: u64) -> Result<(), StorageError> {
+        let new_value = i64::try_from(epoch).map_err(|_| {
+            StorageError::metadata(format!("Epoch {} exceeds metadata range", epoch))
+        })?;
+
+        let mut conn = self.conn.lock();
+        let tx = conn
+            .transaction()
+            .map_err(|e| StorageError::metadata(format!("Failed to begin transaction: {}", e)))?;
+
+        let current: i64 = tx
+            .query_row(
+                "SELECT value FROM metadata WHERE key = ?1",
+                params![LOCAL_EPOCH_KEY],
+                |row| row.get(0),
+            )
+            .map_err(|e| StorageError::metadata(format!("Failed to read local epoch: {}", e)))?;
+        if new_value < current {
+            return Err(StorageError::invariant(format!(
+                "Local_Epoch rollback rejected: {} -> {}",
+                current, new_value
+            )));
+        }
+
+        tx.execute(
+            "UPDATE metadata SET value = ?2 WHERE key = ?1",
+            params![LOCAL_EPOCH_KEY, new_value],
+        )
+        .map_err(|e| StorageError::metadata(format!("Failed to write local epoch: {}", e)))?;
+        tx.commit()
+            .map_err(|e| StorageError::metadata(format!("Failed to commit local epoch: {}", e)))
+    }
+}
+
+impl std::fmt::Debug for SqliteMetadataStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqliteMetadataStore")
+            .finish_non_exhaustive()
+    }
+}
+
+impl MetadataStore for SqliteMetadataStore {
+    fn get_local_epoch(&self) -> Result<u64, StorageError> {
+        Ok(self.get_value(LOCAL_EPOCH_KEY)? as u64)
+    }
+
+    fn set_local_epoch(&mut self, epoch: u64) -> Result<(), StorageError> {
+        self.store_local_epoch(epoch)
+    }
+
+    fn get_device_count(&self) -> Result<u32, StorageError> {
+        Ok(self.get_value(DEVICE_COUNT_KEY)? as u32)
+    }
+
+    fn set_device_count(&mut self, count: u32) -> Result<(), StorageError> {
+        self.set_value(DEVICE_COUNT_KEY, i64::from(count))
+    }
+}
+
+/// Lets [`CrashRecovery`](super::recovery::CrashRecovery) read and heal
+/// `Local_Epoch` directly
+impl MetadataSource for SqliteMetadataStore {
+    fn get_epoch(&self) -> Result<u32, StorageError> {
+        let epoch = self.get_local_epoch()?;
+        u32::try_from(epoch).map_err(|_| {
+            StorageError::consistency_check(format!(
+                "Local_Epoch {} exceeds metadata epoch range",
+                epoch
+            ))
+        })
+    }
+
+    fn update_epoch(&self, new_epoch: u32) -> Result<(), StorageError> {
+        self.store_local_epoch(u64::from(new_epoch))
+    }
+}
+
+/// Read `PRAGMA user_version`
+fn read_schema_version(conn: &Connection) -> Result<u32, StorageError> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get::<_, u32>(0))
+        .map_err(|e| StorageError::metadata(format!("Failed to read schema version: {}", e)))
+}
+
+/// Apply every pending migration in one transaction
+fn migrate(conn: &mut Connection) -> Result<(), StorageError> {
+    let version = read_schema_version(conn)?;
+    if version > METADATA_SCHEMA_VERSION {
+        return Err(StorageError::metadata(format!(
+            "Metadata schema version {} is newer than supported version {}",
+            version, METADATA_SCHEMA_VERSION
+        )));
+    }
+    if version == METADATA_SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| StorageError::metadata(format!("Failed to begin migration: {}", e)))?;
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        tx.execute_batch(migration).map_err(|e| {
+            StorageError::metadata(format!(
+                "Metadata migration to v{} failed: {}",
+                index + 1,
+                e
+            ))
+        })?;
+    }
+    tx.pragma_update(None, "user_version", METADATA_SCHEMA_VERSION)
+        .map_err(|e| StorageError::metadata(format!("Failed to set schema version: {}", e)))?;
+    tx.commit()
+        .map_err(|e| StorageError::metadata(format!("Failed to commit migration: {}", e)))?;
+
+    eprintln!(
+        "[Metadata] Migrated schema v{} -> v{}",
+        version, METADATA_SCHEMA_VERSION
+    );
+    Ok(())
+}
+
+// ============================================================================
+// InMemoryMetadataStore
+// ============================================================================
+
+/// Non-persistent metadata store
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InMemoryMetadataStore {
+    /// `Local_Epoch`
+    pub local_epoch: u64,
+
+    /// Recorded device count
+    pub device_count: u32,
+}
+
+impl MetadataStore for InMemoryMetadataStore {
+    fn get_local_epoch(&self) -> Result<u64, StorageError> {
+        Ok(self.local_epoch)
+    }
+
+    fn set_local_epoch(&mut self, epoch: u64) -> Result<(), StorageError> {
+        if epoch < self.local_epoch {
+            return Err(StorageError::invariant(format!(
+                "Local_Epoch rollback rejected: {} -> {}",
+                self.local_epoch, epoch
+            )));
+        }
+        self.local_epoch = epoch;
+        Ok(())
+    }
+
+    fn get_device_count(&self) -> Result<u32, StorageError> {
+        Ok(self.device_count)
+    }
+
+    fn set_device_count(&mut self, count: u32) -> Result<(), StorageError> {
+        self.device_count = count;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_migration_from_empty_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("metadata.db");
+
+        // An empty database file, as left by a fresh install
+        Connection::open(&db_path).unwrap();
+
+        let store = SqliteMetadataStore::open(&db_path).unwrap();
+        assert_eq!(store.schema_version().unwrap(), METADATA_SCHEMA_VERSION);
+        assert_eq!(store.get_local_epoch().unwrap(), 0);
+        assert_eq!(store.get_device_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_reopen_keeps_values_and_skips_migration() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("metadata.db");
+
+        {
+            let mut store = SqliteMetadataStore::open(&db_path).unwrap();
+            store.set_local_epoch(7).unwrap();
+            store.set_device_count(3).unwrap();
+        }
+
+        let store = SqliteMetadataStore::open(&db_path).unwrap();
+        assert_eq!(store.get_local_epoch().unwrap(), 7);
+        assert_eq!(store.get_device_count().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_newer_schema_is_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("metadata.db");
+        Connection::open(&db_path)
+            .unwrap()
+            .pragma_update(None, "user_version", METADATA_SCHEMA_VERSION + 1)
+            .unwrap();
+
+        let result = SqliteMetadataStore::open(&db_path);
+        assert!(matches!(result, Err(StorageError::MetadataFailed(_))));
+    }
+
+    #[test]
+    fn test_local_epoch_rollback_rejected() {
+        let mut store = SqliteMetadataStore::open_in_memory().unwrap();
+        store.set_local_epoch(5).unwrap();
+        store.set_local_epoch(5).unwrap();
+
+        let result = store.set_local_epoch(4);
+        assert!(matches!(result, Err(StorageError::InvariantViolation(_))));
+        assert_eq!(store.get_local_epoch().unwrap(), 5);
+
+        let mut memory = InMemoryMetadataStore::default();
+        memory.set_local_epoch(5).unwrap();
+        assert!(memory.set_local_epoch(4).is_err());
+    }
+
+    #[test]
+    fn test_metadata_source_view() {
+        let store = SqliteMetadataStore::open_in_memory().unwrap();
+        store.update_epoch(9).unwrap();
+
+        assert_eq!(store.get_epoch().unwrap(), 9);
+        assert_eq!(store.get_local_epoch().unwrap(), 9);
+    }
+}
@@ -19,6 +19,13 @@
 //! - `invariant` - Mathematical invariant validation
 //! - `integrity` - Vault integrity verification
 //! - `aug` - Atomic Epoch Upgrade Protocol (AUP) implementation
+//! - `backend` - Pluggable vault file stores (filesystem, in-memory)
+//! - `lock` - Exclusive lock file against concurrent vault writers
+//! - `export` - Passphrase-encrypted vault export/import archives
+//! - `compact` - Vault compaction and revoked header pruning
+//! - `audit_log` - Tamper-evident, hash-chained audit log of protocol events
+//! - `metadata` - SQLite/SQLCipher metadata store (`Local_Epoch`)
+//! - `device_secret` - Local device secret key at rest, optionally hardware-wrapped
 //!
 //! ## Safety Guarantees
 //!
@@ -29,18 +36,50 @@
 
 // Re-export common types
 pub use error::{FatalError, InvariantViolation, StorageError};
-pub use integrity::IntegrityAudit;
-pub use invariant::InvariantValidator;
-pub use recovery::{ConsistencyState, CrashRecovery, MetadataSource, VaultStorage};
+pub use integrity::{
+    ChunkStatus, EpochMovement, FileDigest, IntegrityAudit, IntegrityDiff, IntegritySnapshot,
+    ModifiedFile,
+};
+pub use invariant::{InvariantValidator, VetoState};
+pub use lock::VaultLock;
+pub use metadata::{InMemoryMetadataStore, MetadataStore, SqliteMetadataStore};
+pub use recovery::{ConsistencyState, CrashRecovery, MetadataSource, VaultFile, VaultStorage};
 pub use shadow::{ShadowFile, ShadowWriter};
 
 // Re-export AUP types
-pub use aug::{aup_atomic_commit, aup_prepare, aup_shadow_write, read_vault_epoch, AupPreparation};
+pub use aug::{
+    aup_atomic_commit, aup_atomic_commit_to, aup_prepare, aup_prepare_with_nonce, aup_shadow_write,
+    aup_shadow_write_to, aup_shadow_write_with_progress, parse_vault_epoch, parse_vault_key,
+    read_vault_blob, read_vault_epoch, read_vault_key, AupPreparation, StoredVaultKey,
+    AUP_PROGRESS_INTERVAL, VAULT_FILE_NAME,
+};
+
+// Re-export storage backends
+pub use backend::{FileBackend, InMemoryBackend, InMemoryShadow, VaultBackend};
+
+// Re-export export/import types
+pub use export::{export_vault, import_vault, ImportReport};
+
+// Re-export device secret storage types
+pub use device_secret::{device_secret_path, DeviceSecretFormat};
+
+// Re-export compaction types
+pub use compact::{compact_vault, CompactionReport};
+
+// Re-export audit log types
+pub use audit_log::{AuditEvent, AuditEventType, AuditLog, AuditRecord, AuditSink};
 
 // Public submodules for documentation examples
+pub mod audit_log;
 pub mod aug;
+pub mod backend;
+pub mod compact;
+pub mod device_secret;
 pub mod error;
+pub mod export;
 pub mod integrity;
 pub mod invariant;
+pub mod lock;
+pub mod metadata;
 pub mod recovery;
 pub mod shadow;
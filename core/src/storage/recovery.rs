@@ -7,7 +7,9 @@
 //!
 //! - **State A (Consistent)**: `metadata_epoch == blob_epoch` → Normal startup
 //! - **State B (BlobAhead)**: `blob_epoch > metadata_epoch` → Auto-heal (DB aligns to Blob)
-//! - **State C (MetadataAhead)**: `blob_epoch < metadata_epoch` → Meltdown (illegal state)
+//! - **State C (MetadataAhead)**: `blob_epoch < metadata_epoch` → Meltdown (illegal state),
+//!   unless the pending shadow file proves the rename was interrupted
+//!   (see [`CrashRecovery::heal`])
 //!
 //! ## Design Principles
 //!
@@ -18,18 +20,34 @@
 //! ## Example
 //!
 //! ```no_run
-//! use aeternum_core::storage::recovery::{CrashRecovery, ConsistencyState};
-//!
-//! // Note: Concrete implementations of MetadataSource and VaultStorage traits
-//! // are required (e.g., SQLCipher for metadata, encrypted file for vault)
-//! // This is a simplified example demonstrating the API usage.
+//! use aeternum_core::storage::metadata::SqliteMetadataStore;
+//! use aeternum_core::storage::recovery::{ConsistencyState, CrashRecovery, VaultFile};
 //!
+//! // On startup: align Local_Epoch and the vault file after any crash
+//! let metadata = SqliteMetadataStore::open("metadata.db")?;
+//! let recovery = CrashRecovery::new(metadata, VaultFile::new("vault.db"));
+//! let state = recovery.recover("vault.db")?;
+//! if state.needs_healing() {
+//!     println!("Healed: {}", state);
+//! }
 //! # Ok::<(), aeternum_core::storage::StorageError>(())
 //! ```
 
 use std::fmt;
+use std::path::{Path, PathBuf};
+
+use zeroize::Zeroize;
 
+use super::aug::read_vault_epoch;
+use super::device_secret::{
+    decode_device_secret, device_secret_path, encode_device_secret, DeviceSecretFormat,
+};
 use super::error::{FatalError, StorageError};
+use super::export::write_atomically;
+use super::lock::VaultLock;
+use super::shadow::ShadowWriter;
+use crate::crypto::kem::KyberSecretKeyBytes;
+use crate::crypto::wrap::KeyWrapper;
 
 /// Consistency check result
 ///
@@ -143,6 +161,101 @@ pub trait VaultStorage: Send + Sync {
     /// This should read the epoch from the encrypted vault file's header.
     /// The implementation must verify AEAD before returning the epoch.
     fn get_blob_epoch(&self) -> Result<u32, StorageError>;
+
+    /// Persist this device's Kyber secret key, wrapped by `wrapper`
+    ///
+    /// See [`device_secret`](super::device_secret) for the stored format.
+    /// The default implementation does not persist device secrets.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StorageError::CryptoFailed` if wrapping fails or the
+    /// storage does not persist device secrets, or a write error.
+    fn store_device_secret(
+        &self,
+        _secret: &KyberSecretKeyBytes,
+        _wrapper: &dyn KeyWrapper,
+    ) -> Result<(), StorageError> {
+        Err(StorageError::crypto(
+            "This vault storage does not persist device secrets",
+        ))
+    }
+
+    /// Load this device's Kyber secret key, unwrapping it with `wrapper`
+    ///
+    /// A legacy plaintext-at-rest secret is returned as well, and should be
+    /// rewritten wrapped before returning so the plaintext copy is gone
+    /// after the first load.
+    ///
+    /// # Errors
+    ///
+    /// - `StorageError::AuthenticationFailed` if `wrapper` cannot unwrap it
+    /// - `StorageError::CryptoFailed` if the stored secret is malformed or
+    ///   the storage does not persist device secrets
+    fn load_device_secret(
+        &self,
+        _wrapper: &dyn KeyWrapper,
+    ) -> Result<KyberSecretKeyBytes, StorageError> {
+        Err(StorageError::crypto(
+            "This vault storage does not persist device secrets",
+        ))
+    }
+}
+
+/// Vault storage backed by a vault file on disk
+///
+/// Reads the epoch from the vault header via [`read_vault_epoch`].
+#[derive(Debug, Clone)]
+pub struct VaultFile {
+    path: PathBuf,
+}
+
+impl VaultFile {
+    /// Vault file at `path`
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl VaultStorage for VaultFile {
+    fn get_blob_epoch(&self) -> Result<u32, StorageError> {
+        read_epoch_u32(&self.path)
+    }
+
+    fn store_device_secret(
+        &self,
+        secret: &KyberSecretKeyBytes,
+        wrapper: &dyn KeyWrapper,
+    ) -> Result<(), StorageError> {
+        let blob = encode_device_secret(secret, wrapper)?;
+        let _lock = VaultLock::hold(&self.path)?;
+        write_atomically(&device_secret_path(&self.path), &[&blob])
+    }
+
+    fn load_device_secret(
+        &self,
+        wrapper: &dyn KeyWrapper,
+    ) -> Result<KyberSecretKeyBytes, StorageError> {
+        let path = device_secret_path(&self.path);
+        let mut blob = std::fs::read(&path).map_err(|e| {
+            StorageError::crypto(format!(
+                "Failed to read device secret {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let decoded = decode_device_secret(&blob, wrapper);
+        blob.zeroize();
+
+        let (secret, format) = decoded?;
+        if format == DeviceSecretFormat::Plaintext {
+            // Migrate: replace the plaintext copy with a wrapped one
+            self.store_device_secret(&secret, wrapper)?;
+        }
+        Ok(secret)
+    }
 }
 
 /// Crash recovery engine
@@ -465,6 +578,154 @@ where
             }
         }
     }
+
+    /// Reconcile the on-disk vault file against a known metadata epoch
+    ///
+    /// File-based counterpart of [`check_consistency`](Self::check_consistency):
+    /// reads the epoch from the vault header at `vault_path` via
+    /// [`read_vault_epoch`] and compares it with `metadata_epoch`.
+    ///
+    /// # Returns
+    ///
+    /// - `Consistent` if epochs match
+    /// - `BlobAhead` if the vault file is ahead of metadata
+    ///   (rename succeeded, metadata update lost)
+    /// - `MetadataAhead` if metadata is ahead of the vault file
+    ///   (metadata updated, rename lost)
+    ///
+    /// # Errors
+    ///
+    /// Returns `StorageError::ConsistencyCheckFailed` if the vault header
+    /// cannot be read or the epoch does not fit into `u32`.
+    pub fn reconcile(
+        &self,
+        vault_path: impl AsRef<Path>,
+        metadata_epoch: u32,
+    ) -> Result<ConsistencyState, StorageError> {
+        let blob_epoch = read_epoch_u32(vault_path.as_ref())?;
+
+        Ok(match blob_epoch.cmp(&metadata_epoch) {
+            std::cmp::Ordering::Equal => ConsistencyState::Consistent,
+            std::cmp::Ordering::Greater => ConsistencyState::BlobAhead {
+                blob_epoch,
+                metadata_epoch,
+            },
+            std::cmp::Ordering::Less => ConsistencyState::MetadataAhead {
+                blob_epoch,
+                metadata_epoch,
+            },
+        })
+    }
+
+    /// Startup recovery: reconcile the vault file with the metadata store
+    /// and heal the result
+    ///
+    /// Reads `Local_Epoch` from the metadata source, compares it with the
+    /// vault file via [`reconcile`](Self::reconcile) and applies
+    /// [`heal`](Self::heal). A crash between the AUP rename and the
+    /// metadata update shows up as `BlobAhead` and is healed here.
+    ///
+    /// # Returns
+    ///
+    /// The state found before healing.
+    ///
+    /// # Panics
+    ///
+    /// Panics (meltdown) on an unexplained `MetadataAhead` state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either epoch cannot be read or healing fails.
+    pub fn recover(&self, vault_path: impl AsRef<Path>) -> Result<ConsistencyState, StorageError> {
+        let vault_path = vault_path.as_ref();
+        let metadata_epoch = self.metadata.get_epoch().map_err(|e| {
+            StorageError::consistency_check(format!("Failed to read metadata epoch: {}", e))
+        })?;
+
+        let state = self.reconcile(vault_path, metadata_epoch)?;
+        self.heal(vault_path, &state)?;
+        Ok(state)
+    }
+
+    /// Heal a state returned by [`reconcile`](Self::reconcile)
+    ///
+    /// - `Consistent`: no-op
+    /// - `BlobAhead`: metadata is aligned to the blob via
+    ///   [`heal_blob_ahead`](Self::heal_blob_ahead)
+    /// - `MetadataAhead`: if the shadow file (`<vault>.tmp`) is still present
+    ///   and carries exactly `metadata_epoch`, the interrupted rename is
+    ///   re-run. Otherwise the divergence cannot be explained by a crash and
+    ///   meltdown is triggered.
+    ///
+    /// # Panics
+    ///
+    /// Panics (meltdown) on a `MetadataAhead` state without a matching
+    /// shadow file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metadata update or the rename fails, or
+    /// `StorageError::VaultLocked` if another writer holds the vault's lock
+    /// when the rename must be re-run.
+    pub fn heal(
+        &self,
+        vault_path: impl AsRef<Path>,
+        state: &ConsistencyState,
+    ) -> Result<(), StorageError> {
+        let vault_path = vault_path.as_ref();
+
+        match *state {
+            ConsistencyState::Consistent => Ok(()),
+            ConsistencyState::BlobAhead { blob_epoch, .. } => self.heal_blob_ahead(blob_epoch),
+            ConsistencyState::MetadataAhead {
+                blob_epoch,
+                metadata_epoch,
+            } => {
+                let _lock = VaultLock::hold(vault_path)?;
+                let writer = ShadowWriter::new(vault_path);
+                let temp_path = writer.temp_path();
+
+                let shadow_epoch = if temp_path.exists() {
+                    read_epoch_u32(&temp_path).ok()
+                } else {
+                    None
+                };
+
+                if shadow_epoch != Some(metadata_epoch) {
+                    FatalError::StorageInconsistency(format!(
+                        "MetadataAhead without matching shadow file: blob_epoch={}, \
+                         metadata_epoch={}, shadow_epoch={:?}. Possible causes: \
+                         rollback attack, filesystem corruption, or tampering.",
+                        blob_epoch, metadata_epoch, shadow_epoch
+                    ))
+                    .trigger_meltdown()
+                }
+
+                eprintln!(
+                    "[RECOVERY] Auto-healing MetadataAhead state: re-running rename of {}",
+                    temp_path.display()
+                );
+
+                writer.commit_pending()?;
+
+                eprintln!("[RECOVERY] Successfully healed MetadataAhead state");
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Read the vault header epoch and narrow it to the `u32` used by metadata
+fn read_epoch_u32(path: &Path) -> Result<u32, StorageError> {
+    let epoch = read_vault_epoch(path)?;
+    u32::try_from(epoch).map_err(|_| {
+        StorageError::consistency_check(format!(
+            "Vault epoch {} in {} exceeds metadata epoch range",
+            epoch,
+            path.display()
+        ))
+    })
 }
 
 // ============================================================================
@@ -769,6 +1030,198 @@ mod tests {
         recovery.check_and_heal().unwrap();
     }
 
+    // ------------------------------------------------------------------------
+    // File-based reconcile / heal Tests
+    // ------------------------------------------------------------------------
+
+    /// Write a minimal vault file whose header carries `epoch`
+    fn write_vault_file(path: &Path, epoch: u64) {
+        let mut bytes = [0u8; 32];
+        bytes[0..8].copy_from_slice(&crate::models::vault::VAULT_MAGIC);
+        bytes[12..20].copy_from_slice(&epoch.to_be_bytes());
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    #[test]
+    fn test_reconcile_consistent() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        write_vault_file(&vault_path, 5);
+
+        let recovery = CrashRecovery::new(MockMetadata::new(5), MockVault::new(5));
+        let state = recovery.reconcile(&vault_path, 5).unwrap();
+        assert_eq!(state, ConsistencyState::Consistent);
+
+        recovery.heal(&vault_path, &state).unwrap();
+    }
+
+    #[test]
+    fn test_reconcile_and_heal_blob_ahead() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        write_vault_file(&vault_path, 5);
+
+        let metadata = MockMetadata::new(4);
+        let recovery = CrashRecovery::new(metadata.clone(), MockVault::new(5));
+        let state = recovery.reconcile(&vault_path, 4).unwrap();
+        assert_eq!(
+            state,
+            ConsistencyState::BlobAhead {
+                blob_epoch: 5,
+                metadata_epoch: 4
+            }
+        );
+
+        recovery.heal(&vault_path, &state).unwrap();
+        assert_eq!(metadata.get_epoch().unwrap(), 5);
+    }
+
+    #[test]
+    fn test_reconcile_and_heal_metadata_ahead_with_shadow() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let temp_path = temp_dir.path().join("vault.db.tmp");
+        write_vault_file(&vault_path, 4);
+        write_vault_file(&temp_path, 5);
+
+        let recovery = CrashRecovery::new(MockMetadata::new(5), MockVault::new(4));
+        let state = recovery.reconcile(&vault_path, 5).unwrap();
+        assert_eq!(
+            state,
+            ConsistencyState::MetadataAhead {
+                blob_epoch: 4,
+                metadata_epoch: 5
+            }
+        );
+
+        recovery.heal(&vault_path, &state).unwrap();
+
+        assert!(!temp_path.exists());
+        assert_eq!(read_vault_epoch(&vault_path).unwrap(), 5);
+        assert_eq!(
+            recovery.reconcile(&vault_path, 5).unwrap(),
+            ConsistencyState::Consistent
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "AETERNUM MELTDOWN")]
+    fn test_heal_metadata_ahead_without_shadow_triggers_meltdown() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        write_vault_file(&vault_path, 4);
+
+        let recovery = CrashRecovery::new(MockMetadata::new(5), MockVault::new(4));
+        let state = recovery.reconcile(&vault_path, 5).unwrap();
+
+        recovery.heal(&vault_path, &state).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "AETERNUM MELTDOWN")]
+    fn test_heal_metadata_ahead_with_stale_shadow_triggers_meltdown() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        write_vault_file(&vault_path, 4);
+        write_vault_file(&temp_dir.path().join("vault.db.tmp"), 3);
+
+        let recovery = CrashRecovery::new(MockMetadata::new(5), MockVault::new(4));
+        let state = recovery.reconcile(&vault_path, 5).unwrap();
+
+        recovery.heal(&vault_path, &state).unwrap();
+    }
+
+    #[test]
+    fn test_reconcile_missing_vault_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("missing.db");
+
+        let recovery = CrashRecovery::new(MockMetadata::new(5), MockVault::new(5));
+        let result = recovery.reconcile(&vault_path, 5);
+        assert!(matches!(
+            result.unwrap_err(),
+            StorageError::ConsistencyCheckFailed(_)
+        ));
+    }
+
+    // ------------------------------------------------------------------------
+    // Device Secret Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_vault_file_device_secret_roundtrip() {
+        use crate::crypto::kem::KyberKEM;
+        use crate::crypto::wrap::AeadKeyWrapper;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault = VaultFile::new(temp_dir.path().join("vault.db"));
+        let wrapper = AeadKeyWrapper::generate();
+        let keypair = KyberKEM::generate_keypair();
+
+        vault
+            .store_device_secret(&keypair.secret, &wrapper)
+            .unwrap();
+        let loaded = vault.load_device_secret(&wrapper).unwrap();
+        assert_eq!(loaded.as_bytes(), keypair.secret.as_bytes());
+    }
+
+    #[test]
+    fn test_vault_file_migrates_legacy_device_secret_on_first_load() {
+        use super::super::device_secret::{DEVICE_SECRET_PLAINTEXT, DEVICE_SECRET_WRAPPED};
+        use crate::crypto::kem::KyberKEM;
+        use crate::crypto::wrap::AeadKeyWrapper;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault_path = temp_dir.path().join("vault.db");
+        let secret_path = device_secret_path(&vault_path);
+        let keypair = KyberKEM::generate_keypair();
+        let mut legacy = vec![DEVICE_SECRET_PLAINTEXT];
+        legacy.extend_from_slice(keypair.secret.as_bytes());
+        std::fs::write(&secret_path, &legacy).unwrap();
+
+        let vault = VaultFile::new(&vault_path);
+        let wrapper = AeadKeyWrapper::generate();
+        let loaded = vault.load_device_secret(&wrapper).unwrap();
+        assert_eq!(loaded.as_bytes(), keypair.secret.as_bytes());
+
+        // Rewritten wrapped; a second load goes through the wrapper
+        let stored = std::fs::read(&secret_path).unwrap();
+        assert_eq!(stored[0], DEVICE_SECRET_WRAPPED);
+        assert_ne!(stored, legacy);
+        let again = vault.load_device_secret(&wrapper).unwrap();
+        assert_eq!(again.as_bytes(), keypair.secret.as_bytes());
+    }
+
+    #[test]
+    fn test_vault_file_device_secret_wrong_wrapper_is_error() {
+        use crate::crypto::kem::KyberKEM;
+        use crate::crypto::wrap::AeadKeyWrapper;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let vault = VaultFile::new(temp_dir.path().join("vault.db"));
+        let keypair = KyberKEM::generate_keypair();
+        vault
+            .store_device_secret(&keypair.secret, &AeadKeyWrapper::generate())
+            .unwrap();
+
+        let result = vault.load_device_secret(&AeadKeyWrapper::generate());
+        assert!(matches!(result, Err(StorageError::AuthenticationFailed(_))));
+    }
+
+    #[test]
+    fn test_device_secret_unsupported_by_default() {
+        use crate::crypto::kem::KyberKEM;
+        use crate::crypto::wrap::AeadKeyWrapper;
+
+        let wrapper = AeadKeyWrapper::generate();
+        let vault = MockVault::new(1);
+        let keypair = KyberKEM::generate_keypair();
+        assert!(vault
+            .store_device_secret(&keypair.secret, &wrapper)
+            .is_err());
+        assert!(vault.load_device_secret(&wrapper).is_err());
+    }
+
     #[test]
     fn test_recovery_cloned_is_independent() {
         let metadata = MockMetadata::new(5);
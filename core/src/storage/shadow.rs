@@ -5,6 +5,7 @@
 //! 2. Write data to the temporary file
 //! 3. Sync to disk (fsync)
 //! 4. Atomically rename to the target path
+//! 5. Sync the parent directory so the rename itself is durable (Unix)
 //!
 //! This ensures crash consistency: at any point, either the old file or the new file
 //! exists, never a corrupted intermediate state.
@@ -12,8 +13,13 @@
 //! ## Safety Guarantees
 //!
 //! - POSIX `rename()` is atomic on Linux/Android
+//! - On Windows, `MoveFileExW` with `MOVEFILE_REPLACE_EXISTING |
+//!   MOVEFILE_WRITE_THROUGH` replaces the target atomically
 //! - All writes are synced to disk before commit
+//! - The parent directory entry is synced after rename on Unix
 //! - Temporary files are automatically cleaned up on drop
+//! - Files are created with mode `0600` on Unix
+//! - Symlinked target or temporary paths are refused
 //!
 //! ## Example
 //!
@@ -37,6 +43,10 @@ use super::StorageError;
 /// Default suffix for temporary files
 const DEFAULT_TEMP_SUFFIX: &str = ".tmp";
 
+/// Permissions for vault files on Unix (owner read/write only)
+#[cfg(unix)]
+pub const VAULT_FILE_MODE: u32 = 0o600;
+
 /// Shadow writer for atomic file updates
 ///
 /// Creates and manages temporary files for atomic write operations.
@@ -109,6 +119,7 @@ impl ShadowWriter {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - The target or temporary path is a symlink
     /// - The parent directory doesn't exist
     /// - Permission denied
     /// - I/O error creating the temporary file
@@ -126,6 +137,10 @@ impl ShadowWriter {
     pub fn begin_shadow_write(&self) -> Result<ShadowFile, StorageError> {
         let temp_path = self.temp_path();
 
+        // Never write through a symlink planted at the target or temp path
+        reject_symlink(&self.base_path)?;
+        reject_symlink(&temp_path)?;
+
         // Ensure parent directory exists
         if let Some(parent) = temp_path.parent() {
             if !parent.exists() {
@@ -137,19 +152,41 @@ impl ShadowWriter {
         }
 
         // Create the temporary file with restrictive permissions
-        // Note: On Unix, tempfile crate creates files with 0600 permissions
-        let file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(&temp_path)
-            .map_err(|e| {
-                StorageError::shadow_write(format!(
-                    "Failed to create temporary file {}: {}",
-                    temp_path.display(),
-                    e
-                ))
-            })?;
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(VAULT_FILE_MODE);
+        }
+
+        let file = options.open(&temp_path).map_err(|e| {
+            StorageError::shadow_write(format!(
+                "Failed to create temporary file {}: {}",
+                temp_path.display(),
+                e
+            ))
+        })?;
+
+        // `mode` only applies on creation; a residual temp file keeps its old
+        // permissions, so enforce them explicitly. The rename carries the
+        // mode over to the target.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(std::fs::Permissions::from_mode(VAULT_FILE_MODE))
+                .map_err(|e| {
+                    StorageError::shadow_write(format!(
+                        "Failed to restrict permissions on {}: {}",
+                        temp_path.display(),
+                        e
+                    ))
+                })?;
+        }
+
+        // On Windows no ACL is applied: the file inherits the ACL of its
+        // parent directory, which is expected to be the app's private
+        // data directory.
 
         Ok(ShadowFile {
             file,
@@ -173,10 +210,12 @@ impl ShadowWriter {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - The target path is a symlink
     /// - The temporary file doesn't exist
     /// - Cross-device rename (not atomic)
     /// - Permission denied
     /// - I/O error during rename
+    /// - The parent directory cannot be synced (Unix)
     ///
     /// # Example
     ///
@@ -202,21 +241,31 @@ impl ShadowWriter {
         // Close the file handle first
         drop(shadow_file);
 
-        // Atomic rename
-        std::fs::rename(&temp_path, &target_path).map_err(|e| {
+        if let Err(e) = replace_target(&temp_path, &target_path) {
             // Try to clean up the temporary file on failure
             let _ = std::fs::remove_file(&temp_path);
-            StorageError::atomic_rename(format!(
-                "Failed to rename {} to {}: {}",
-                temp_path.display(),
-                target_path.display(),
-                e
-            ))
-        })?;
+            return Err(e);
+        }
 
         Ok(())
     }
 
+    /// Commit a shadow file left at [`temp_path`](Self::temp_path) by an
+    /// interrupted write
+    ///
+    /// Crash recovery uses this to re-run a rename that was lost after the
+    /// shadow file had been synced. The replacement is the same as in
+    /// [`commit_shadow_write`](Self::commit_shadow_write), but the shadow
+    /// file is kept if it fails, since it may hold the only copy of the
+    /// newer data.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`commit_shadow_write`](Self::commit_shadow_write).
+    pub(crate) fn commit_pending(self) -> Result<(), StorageError> {
+        replace_target(&self.temp_path(), &self.base_path)
+    }
+
     /// Clean up any residual temporary files
     ///
     /// This should be called at startup to remove any leftover `.tmp` files
@@ -384,6 +433,124 @@ impl io::Write for ShadowFile {
 // Tests
 // ============================================================================
 
+/// Reject a path whose final component is a symlink
+///
+/// Uses `symlink_metadata` so the link itself is inspected rather than its
+/// target. A missing path is accepted.
+///
+/// # Errors
+///
+/// Returns `StorageError::ConsistencyCheckFailed` if `path` is a symlink.
+pub(crate) fn reject_symlink(path: &Path) -> Result<(), StorageError> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => Err(StorageError::consistency_check(
+            format!("Refusing to use symlinked vault path: {}", path.display()),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Replace `target` with the synced file at `source` and make it durable
+///
+/// Refuses a symlinked target, which may have been swapped in since the
+/// shadow write began.
+fn replace_target(source: &Path, target: &Path) -> Result<(), StorageError> {
+    reject_symlink(target)?;
+
+    atomic_replace(source, target).map_err(|e| {
+        StorageError::atomic_rename(format!(
+            "Failed to rename {} to {}: {}",
+            source.display(),
+            target.display(),
+            e
+        ))
+    })?;
+
+    // POSIX only guarantees the rename survives a crash once the
+    // directory entry itself has been flushed
+    #[cfg(unix)]
+    sync_parent_dir(target)?;
+
+    Ok(())
+}
+
+/// Atomically replace `target` with `source`
+///
+/// POSIX `rename()` replaces an existing target atomically.
+#[cfg(unix)]
+fn atomic_replace(source: &Path, target: &Path) -> io::Result<()> {
+    std::fs::rename(source, target)
+}
+
+/// Atomically replace `target` with `source`
+///
+/// `rename` semantics differ on Windows, so the replacement goes through
+/// `MoveFileExW` with `MOVEFILE_REPLACE_EXISTING | MOVEFILE_WRITE_THROUGH`,
+/// which also flushes the move to disk before returning.
+#[cfg(windows)]
+fn atomic_replace(source: &Path, target: &Path) -> io::Result<()> {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+
+    const MOVEFILE_REPLACE_EXISTING: u32 = 0x1;
+    const MOVEFILE_WRITE_THROUGH: u32 = 0x8;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn MoveFileExW(existing: *const u16, new: *const u16, flags: u32) -> i32;
+    }
+
+    fn to_wide(s: &OsStr) -> Vec<u16> {
+        s.encode_wide().chain(std::iter::once(0)).collect()
+    }
+
+    let source = to_wide(source.as_os_str());
+    let target = to_wide(target.as_os_str());
+
+    // SAFETY: both buffers are NUL-terminated UTF-16 strings that outlive the call
+    let ok = unsafe {
+        MoveFileExW(
+            source.as_ptr(),
+            target.as_ptr(),
+            MOVEFILE_REPLACE_EXISTING | MOVEFILE_WRITE_THROUGH,
+        )
+    };
+
+    if ok == 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+/// Atomically replace `target` with `source`
+#[cfg(not(any(unix, windows)))]
+fn atomic_replace(source: &Path, target: &Path) -> io::Result<()> {
+    std::fs::rename(source, target)
+}
+
+/// Sync the directory containing `path` to disk
+///
+/// Opens the parent directory read-only and calls `sync_all` on the handle.
+/// A bare file name is resolved against the current directory.
+#[cfg(unix)]
+pub(crate) fn sync_parent_dir(path: &Path) -> Result<(), StorageError> {
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+
+    File::open(parent)
+        .and_then(|dir| dir.sync_all())
+        .map_err(|e| {
+            StorageError::fsync(format!(
+                "Failed to sync directory {}: {}",
+                parent.display(),
+                e
+            ))
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -488,6 +655,134 @@ mod tests {
         assert_eq!(content, b"new content");
     }
 
+    #[test]
+    fn test_commit_replaces_existing_file_repeatedly() {
+        let temp_dir = TempDir::new().unwrap();
+        let target_path = temp_dir.path().join("vault.db");
+        fs::write(&target_path, b"generation 0").unwrap();
+
+        for generation in 1..=3 {
+            let content = format!("generation {}", generation);
+            let writer = ShadowWriter::new(&target_path);
+            let mut shadow = writer.begin_shadow_write().unwrap();
+            shadow.write_and_sync(content.as_bytes()).unwrap();
+            writer.commit_shadow_write(shadow).unwrap();
+
+            assert_eq!(fs::read(&target_path).unwrap(), content.as_bytes());
+            assert!(!ShadowWriter::new(&target_path).temp_path().exists());
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_committed_file_has_mode_0600() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let target_path = temp_dir.path().join("vault.db");
+
+        // A residual temp file with loose permissions must not leak its mode
+        let writer = ShadowWriter::new(&target_path);
+        fs::write(writer.temp_path(), b"stale").unwrap();
+        fs::set_permissions(writer.temp_path(), fs::Permissions::from_mode(0o644)).unwrap();
+
+        let mut shadow = writer.begin_shadow_write().unwrap();
+        shadow.write_and_sync(b"secret").unwrap();
+        writer.commit_shadow_write(shadow).unwrap();
+
+        let mode = fs::metadata(&target_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, VAULT_FILE_MODE);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlinked_target_is_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let victim = temp_dir.path().join("victim");
+        let target_path = temp_dir.path().join("vault.db");
+        fs::write(&victim, b"do not touch").unwrap();
+        std::os::unix::fs::symlink(&victim, &target_path).unwrap();
+
+        let writer = ShadowWriter::new(&target_path);
+        let result = writer.begin_shadow_write();
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+        assert_eq!(fs::read(&victim).unwrap(), b"do not touch");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlinked_temp_path_is_refused() {
+        let temp_dir = TempDir::new().unwrap();
+        let victim = temp_dir.path().join("victim");
+        let target_path = temp_dir.path().join("vault.db");
+        fs::write(&victim, b"do not touch").unwrap();
+
+        let writer = ShadowWriter::new(&target_path);
+        std::os::unix::fs::symlink(&victim, writer.temp_path()).unwrap();
+
+        let result = writer.begin_shadow_write();
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+        assert_eq!(fs::read(&victim).unwrap(), b"do not touch");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_target_swapped_for_symlink_before_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let victim = temp_dir.path().join("victim");
+        let target_path = temp_dir.path().join("vault.db");
+        fs::write(&victim, b"do not touch").unwrap();
+
+        let writer = ShadowWriter::new(&target_path);
+        let mut shadow = writer.begin_shadow_write().unwrap();
+        shadow.write_and_sync(b"data").unwrap();
+
+        std::os::unix::fs::symlink(&victim, &target_path).unwrap();
+
+        let temp_path = writer.temp_path();
+        let result = writer.commit_shadow_write(shadow);
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+        assert!(!temp_path.exists());
+        assert_eq!(fs::read(&victim).unwrap(), b"do not touch");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_commit_pending_keeps_shadow_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let victim = temp_dir.path().join("victim");
+        let target_path = temp_dir.path().join("vault.db");
+        fs::write(&victim, b"do not touch").unwrap();
+
+        // A shadow file left behind by an interrupted write
+        let writer = ShadowWriter::new(&target_path);
+        let temp_path = writer.temp_path();
+        fs::write(&temp_path, b"pending").unwrap();
+
+        std::os::unix::fs::symlink(&victim, &target_path).unwrap();
+        let result = ShadowWriter::new(&target_path).commit_pending();
+        assert!(matches!(
+            result,
+            Err(StorageError::ConsistencyCheckFailed(_))
+        ));
+        assert_eq!(fs::read(&temp_path).unwrap(), b"pending");
+        assert_eq!(fs::read(&victim).unwrap(), b"do not touch");
+
+        fs::remove_file(&target_path).unwrap();
+        writer.commit_pending().unwrap();
+        assert!(!temp_path.exists());
+        assert_eq!(fs::read(&target_path).unwrap(), b"pending");
+    }
+
     #[test]
     fn test_write_large_data() {
         let temp_dir = TempDir::new().unwrap();
@@ -530,6 +825,36 @@ mod tests {
         assert_eq!(content, b"streamed data");
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_commit_syncs_parent_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let target_path = temp_dir.path().join("vault.db");
+
+        let writer = ShadowWriter::new(&target_path);
+        let mut shadow = writer.begin_shadow_write().unwrap();
+        shadow.write_and_sync(b"durable").unwrap();
+        writer.commit_shadow_write(shadow).unwrap();
+
+        assert_eq!(fs::read(&target_path).unwrap(), b"durable");
+
+        // The directory sync step works on the target's parent
+        assert!(sync_parent_dir(&target_path).is_ok());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_sync_parent_dir_errors() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // Bare file names resolve to the current directory
+        assert!(sync_parent_dir(Path::new("vault.db")).is_ok());
+
+        let missing = temp_dir.path().join("missing").join("vault.db");
+        let result = sync_parent_dir(&missing);
+        assert!(matches!(result, Err(StorageError::FsyncFailed(_))));
+    }
+
     #[test]
     fn test_cleanup_residual() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,438 @@
+//! # Veto Broadcast
+//!
+//! Fan-out of a signed veto to every known peer, with per-peer delivery
+//! acknowledgement.
+//!
+//! ## Delivery
+//!
+//! [`WireProtocol::broadcast_veto`] seals the veto into a `Veto` frame on
+//! each peer's session and sends it over the peer's [`FrameTransport`]. A
+//! peer confirms delivery with a `VetoAck` frame carrying the request ID and
+//! the BLAKE3 digest of the veto it received ([`VetoAck`]). Peers that have
+//! not acknowledged are retried with exponential backoff until the policy
+//! deadline; an ack whose request ID or digest does not match is ignored.
+//!
+//! ## Fail-Safe
+//!
+//! Invariant #4 is enforced locally: the veto takes effect on this device
+//! as soon as it is issued, before and regardless of any broadcast outcome.
+//! Acknowledgements only tell the user which peers are known to have it.
+
+use crate::crypto::hash::hash;
+use crate::models::device::DeviceId;
+use crate::protocol::recovery::SignedVetoMessage;
+use crate::sync::codec::{Message, PayloadType};
+use crate::sync::wire::WireProtocol;
+use crate::sync::{Result, WireError};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Frame transport to one peer
+///
+/// Carries serialized frames without interpreting them; sealing and
+/// opening are done by the peer's [`WireProtocol`] session.
+pub trait FrameTransport {
+    /// Send one frame to the peer
+    fn send(&mut self, frame: &[u8]) -> std::io::Result<()>;
+
+    /// Receive the next frame from the peer, if one has arrived
+    ///
+    /// Must not block.
+    fn try_recv(&mut self) -> std::io::Result<Option<Vec<u8>>>;
+}
+
+/// A known peer: its device ID, session and transport
+pub struct PeerHandle {
+    /// Device ID of the peer
+    pub device_id: DeviceId,
+    /// Established session with the peer
+    pub session: WireProtocol,
+    /// Transport carrying the session's frames
+    pub transport: Box<dyn FrameTransport + Send>,
+}
+
+impl PeerHandle {
+    /// Create a handle for a peer with an established session
+    pub fn new(
+        device_id: DeviceId,
+        session: WireProtocol,
+        transport: Box<dyn FrameTransport + Send>,
+    ) -> Self {
+        Self {
+            device_id,
+            session,
+            transport,
+        }
+    }
+}
+
+/// Acknowledgement of a received veto
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VetoAck {
+    /// Recovery request the acknowledged veto targets
+    pub request_id: String,
+    /// BLAKE3 digest of the acknowledged veto's encoding
+    pub veto_digest: [u8; 32],
+}
+
+impl VetoAck {
+    /// Acknowledgement for `veto`
+    pub fn for_veto(veto: &SignedVetoMessage) -> Self {
+        Self {
+            request_id: veto.request_id.clone(),
+            veto_digest: veto_digest(veto),
+        }
+    }
+
+    /// Whether this acknowledges exactly `veto`
+    pub fn matches(&self, veto: &SignedVetoMessage) -> bool {
+        self.request_id == veto.request_id && self.veto_digest == veto_digest(veto)
+    }
+}
+
+impl Message for VetoAck {
+    fn payload_type() -> PayloadType {
+        PayloadType::VetoAck
+    }
+}
+
+/// BLAKE3 digest of a veto's transmitted encoding
+fn veto_digest(veto: &SignedVetoMessage) -> [u8; 32] {
+    *hash(&veto.to_bytes()).as_bytes()
+}
+
+/// Retry schedule of a veto broadcast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VetoBroadcastPolicy {
+    /// Wait after the first send before checking for acks
+    pub initial_backoff: Duration,
+    /// Upper bound for the doubling backoff
+    pub max_backoff: Duration,
+    /// Total time after which unacknowledged peers are given up on
+    pub deadline: Duration,
+}
+
+impl Default for VetoBroadcastPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            deadline: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// Delivery outcome for one peer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerDelivery {
+    /// Device ID of the peer
+    pub device_id: DeviceId,
+    /// Whether the peer acknowledged the veto
+    pub acknowledged: bool,
+    /// Number of times the veto was sent to the peer
+    pub attempts: u32,
+    /// Most recent send or receive error, if any
+    pub last_error: Option<String>,
+}
+
+/// Outcome of a veto broadcast
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VetoBroadcastStatus {
+    /// Recovery request the veto targets
+    pub request_id: String,
+    /// Per-peer outcome, in the order the peers were given
+    pub peers: Vec<PeerDelivery>,
+}
+
+impl VetoBroadcastStatus {
+    /// Peers that acknowledged the veto
+    pub fn confirmed(&self) -> Vec<DeviceId> {
+        self.filter(true)
+    }
+
+    /// Peers that did not acknowledge the veto before the deadline
+    pub fn unconfirmed(&self) -> Vec<DeviceId> {
+        self.filter(false)
+    }
+
+    /// Whether every peer acknowledged the veto
+    pub fn all_acknowledged(&self) -> bool {
+        self.peers.iter().all(|peer| peer.acknowledged)
+    }
+
+    fn filter(&self, acknowledged: bool) -> Vec<DeviceId> {
+        self.peers
+            .iter()
+            .filter(|peer| peer.acknowledged == acknowledged)
+            .map(|peer| peer.device_id)
+            .collect()
+    }
+}
+
+impl WireProtocol {
+    /// Broadcast a veto to every peer and collect acknowledgements
+    ///
+    /// Sends the veto to all peers, then repeatedly waits the current
+    /// backoff, collects `VetoAck` frames and resends to peers that have
+    /// not acknowledged, doubling the backoff up to
+    /// `policy.max_backoff`, until every peer acknowledged or
+    /// `policy.deadline` has passed. Transport errors are recorded per peer
+    /// and never abort the broadcast.
+    ///
+    /// The veto is already in effect locally (see the module docs); the
+    /// returned status only reports delivery.
+    pub fn broadcast_veto(
+        veto: &SignedVetoMessage,
+        peers: &mut [PeerHandle],
+        policy: &VetoBroadcastPolicy,
+    ) -> VetoBroadcastStatus {
+        let started = Instant::now();
+        let body = veto.to_bytes();
+        let mut deliveries: Vec<PeerDelivery> = peers
+            .iter()
+            .map(|peer| PeerDelivery {
+                device_id: peer.device_id,
+                acknowledged: false,
+                attempts: 0,
+                last_error: None,
+            })
+            .collect();
+
+        let mut backoff = policy.initial_backoff;
+        loop {
+            for (peer, delivery) in peers.iter_mut().zip(&mut deliveries) {
+                if !delivery.acknowledged {
+                    delivery.attempts += 1;
+                    if let Err(e) = send_veto(peer, &body) {
+                        delivery.last_error = Some(e.to_string());
+                    }
+                }
+            }
+
+            std::thread::sleep(backoff.min(policy.deadline.saturating_sub(started.elapsed())));
+
+            for (peer, delivery) in peers.iter_mut().zip(&mut deliveries) {
+                if !delivery.acknowledged {
+                    match collect_ack(peer, veto) {
+                        Ok(acknowledged) => delivery.acknowledged = acknowledged,
+                        Err(e) => delivery.last_error = Some(e.to_string()),
+                    }
+                }
+            }
+
+            if deliveries.iter().all(|d| d.acknowledged) || started.elapsed() >= policy.deadline {
+                break;
+            }
+            backoff = (backoff * 2).min(policy.max_backoff);
+        }
+
+        VetoBroadcastStatus {
+            request_id: veto.request_id.clone(),
+            peers: deliveries,
+        }
+    }
+
+    /// Acknowledge a veto received on this session
+    ///
+    /// Returns the `VetoAck` frame to send back, and the decoded veto for
+    /// the caller to verify and apply.
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::DeserializationFailed` if `veto_body` is not a signed veto
+    pub fn acknowledge_veto(&mut self, veto_body: &[u8]) -> Result<(Vec<u8>, SignedVetoMessage)> {
+        let veto = SignedVetoMessage::from_bytes(veto_body)
+            .map_err(|e| WireError::DeserializationFailed(e.to_string()))?;
+        let ack = VetoAck::for_veto(&veto).serialize_message()?;
+        let frame = self.send_message(PayloadType::VetoAck, ack, self.current_epoch())?;
+        Ok((frame, veto))
+    }
+}
+
+/// Seal the veto on the peer's session and send it
+fn send_veto(peer: &mut PeerHandle, body: &[u8]) -> Result<()> {
+    let epoch = peer.session.current_epoch();
+    let frame = peer
+        .session
+        .send_message(PayloadType::Veto, body.to_vec(), epoch)?;
+    peer.transport.send(&frame)?;
+    Ok(())
+}
+
+/// Drain the peer's received frames; `true` if one acknowledges `veto`
+///
+/// Frames other than a matching `VetoAck` are dropped.
+fn collect_ack(peer: &mut PeerHandle, veto: &SignedVetoMessage) -> Result<bool> {
+    let mut acknowledged = false;
+    while let Some(frame) = peer.transport.try_recv()? {
+        let Ok(Some((PayloadType::VetoAck, body))) = peer.session.receive(&frame) else {
+            continue;
+        };
+        if let Ok(ack) = VetoAck::deserialize_message(&body) {
+            acknowledged |= ack.matches(veto);
+        }
+    }
+    Ok(acknowledged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::aead::XChaCha20Key;
+    use crate::protocol::recovery::{VetoMessage, VetoSigningKey};
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    /// How the simulated remote peer answers vetoes
+    #[derive(Clone, Copy)]
+    enum Remote {
+        /// Acknowledge every veto
+        Ack,
+        /// Drop the first `n` vetoes, then acknowledge
+        DropFirst(u32),
+        /// Acknowledge with a digest of a different veto
+        WrongDigest,
+        /// Never answer
+        Silent,
+    }
+
+    /// Loopback transport to a simulated remote session
+    struct Loopback {
+        remote: WireProtocol,
+        behavior: Remote,
+        received: Arc<Mutex<u32>>,
+        inbox: VecDeque<Vec<u8>>,
+    }
+
+    impl FrameTransport for Loopback {
+        fn send(&mut self, frame: &[u8]) -> std::io::Result<()> {
+            let (payload_type, body) = self.remote.receive(frame).unwrap().unwrap();
+            assert_eq!(payload_type, PayloadType::Veto);
+
+            let mut received = self.received.lock().unwrap();
+            *received += 1;
+            let ack = match self.behavior {
+                Remote::Ack => Some(self.remote.acknowledge_veto(&body).unwrap().0),
+                Remote::DropFirst(n) if *received > n => {
+                    Some(self.remote.acknowledge_veto(&body).unwrap().0)
+                }
+                Remote::WrongDigest => {
+                    let veto = SignedVetoMessage::from_bytes(&body).unwrap();
+                    let ack = VetoAck {
+                        request_id: veto.request_id,
+                        veto_digest: [0xEE; 32],
+                    };
+                    let frame = self
+                        .remote
+                        .send_message(PayloadType::VetoAck, ack.serialize_message().unwrap(), 0)
+                        .unwrap();
+                    Some(frame)
+                }
+                _ => None,
+            };
+            self.inbox.extend(ack);
+            Ok(())
+        }
+
+        fn try_recv(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+            Ok(self.inbox.pop_front())
+        }
+    }
+
+    fn peer(behavior: Remote) -> (PeerHandle, Arc<Mutex<u32>>) {
+        let key = XChaCha20Key::generate();
+        let received = Arc::new(Mutex::new(0));
+        let transport = Loopback {
+            remote: WireProtocol::new(key.clone()),
+            behavior,
+            received: received.clone(),
+            inbox: VecDeque::new(),
+        };
+        let handle = PeerHandle::new(
+            DeviceId::generate(),
+            WireProtocol::new(key),
+            Box::new(transport),
+        );
+        (handle, received)
+    }
+
+    fn signed_veto() -> SignedVetoMessage {
+        VetoMessage::new(DeviceId::generate(), Some("not me".to_string()))
+            .to_signed(&VetoSigningKey::from_bytes(&[7u8; 32]), "rec_1")
+    }
+
+    fn fast_policy() -> VetoBroadcastPolicy {
+        VetoBroadcastPolicy {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+            deadline: Duration::from_millis(200),
+        }
+    }
+
+    #[test]
+    fn test_broadcast_all_acknowledged() {
+        let (a, a_received) = peer(Remote::Ack);
+        let (b, b_received) = peer(Remote::Ack);
+        let mut peers = vec![a, b];
+        let ids: Vec<DeviceId> = peers.iter().map(|p| p.device_id).collect();
+
+        let status = WireProtocol::broadcast_veto(&signed_veto(), &mut peers, &fast_policy());
+
+        assert!(status.all_acknowledged());
+        assert_eq!(status.request_id, "rec_1");
+        assert_eq!(status.confirmed(), ids);
+        assert!(status.peers.iter().all(|p| p.attempts == 1));
+        assert_eq!(*a_received.lock().unwrap(), 1);
+        assert_eq!(*b_received.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_broadcast_retries_until_acknowledged() {
+        let (flaky, flaky_received) = peer(Remote::DropFirst(2));
+        let (silent, _) = peer(Remote::Silent);
+        let (good, _) = peer(Remote::Ack);
+        let mut peers = vec![flaky, silent, good];
+        let silent_id = peers[1].device_id;
+
+        let status = WireProtocol::broadcast_veto(&signed_veto(), &mut peers, &fast_policy());
+
+        assert!(!status.all_acknowledged());
+        assert_eq!(status.unconfirmed(), vec![silent_id]);
+
+        // The flaky peer acked the third copy and was not sent more
+        assert!(status.peers[0].acknowledged);
+        assert_eq!(status.peers[0].attempts, 3);
+        assert_eq!(*flaky_received.lock().unwrap(), 3);
+
+        // The silent peer was retried until the deadline
+        assert!(status.peers[1].attempts > 3);
+        assert_eq!(status.peers[2].attempts, 1);
+    }
+
+    #[test]
+    fn test_broadcast_ignores_ack_with_wrong_digest() {
+        let (forger, forger_received) = peer(Remote::WrongDigest);
+        let mut peers = vec![forger];
+
+        let status = WireProtocol::broadcast_veto(&signed_veto(), &mut peers, &fast_policy());
+
+        assert!(!status.peers[0].acknowledged);
+        assert!(status.peers[0].attempts > 1);
+        assert_eq!(*forger_received.lock().unwrap(), status.peers[0].attempts);
+    }
+
+    #[test]
+    fn test_veto_ack_matches_only_its_veto() {
+        let veto = signed_veto();
+        let ack = VetoAck::for_veto(&veto);
+        assert!(ack.matches(&veto));
+
+        let mut other = veto.clone();
+        other.veto.reason = Some("changed".to_string());
+        assert!(!ack.matches(&other));
+
+        let mut other_request = veto.clone();
+        other_request.request_id = "rec_2".to_string();
+        assert!(!ack.matches(&other_request));
+    }
+}
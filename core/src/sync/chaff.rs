@@ -4,13 +4,37 @@
 //! - **Padding Generation** - Ensures all frames are exactly 8192 bytes
 //! - **Chaff Sync** - Generates decoy synchronization messages
 //! - **Timing Jitter** - Random delays to prevent timing attacks
+//! - **Adaptive Rate** - [`AdaptiveChaffScheduler`] tops real traffic up to a
+//!   constant total rate
+//! - **Idle Schedule** - [`ChaffGenerator::idle_schedule`] decides when a device
+//!   with no real traffic emits chaff
 //!
 //! ## Security Properties
 //!
 //! - **Statistical Indistinguishability** - Chaff frames are identical to real frames
+//! - **Active-Probe Resistance** - Chaff from [`ChaffGenerator::generate_frame`] is
+//!   sealed with the real session key, so its auth tag verifies; the chaff marker
+//!   is bound into the AEAD associated data ([`CHAFF_AAD_LABEL`]) and never
+//!   appears in the cleartext header
 //! - **Timing Obfuscation** - Random delays prevent correlation attacks
 //! - **Entropy Maximization** - CSPRNG ensures maximum entropy in padding
 //!
+//! ## Deterministic Mode
+//!
+//! [`ChaffGenerator::from_seed`] drives every random choice (chaff bodies,
+//! nonces, auth tags and jitter) from a seeded CSPRNG, so integration tests
+//! can reproduce a chaff sequence exactly. Production code uses
+//! [`ChaffGenerator::new`], which seeds from the OS.
+//!
+//! ## Idle Schedule
+//!
+//! An idle device emits chaff at the arrival times of a Poisson process with
+//! mean interval [`IDLE_MEAN_INTERVAL_MS`] (configurable per generator).
+//! Arrivals are derived per [`IDLE_WINDOW_MS`] window from a keyed BLAKE3
+//! hash of the window bounds, so a schedule is reproducible from its seed but
+//! unpredictable without it, and devices with different seeds emit at
+//! unrelated times.
+//!
 //! ## Protocol Compliance
 //!
 //! Implements [AET-WIRE-SPEC-004 §4](../../../docs/protocols/Sync-Wire-Protocol.md):
@@ -18,14 +42,20 @@
 //! - Chaff Sync (decoy epoch upgrades)
 //! - Timing Obfuscation (50ms-200ms jitter)
 
+use crate::crypto::aead::{AeadCipher, XChaCha20Key};
+use crate::crypto::hash::Blake3Hasher;
 use crate::sync::{
-    codec::PayloadType, frame::WireFrame, Result, WireError, AUTH_TAG_SIZE, FRAME_SIZE,
-    MAX_BODY_SIZE, NONCE_SIZE,
+    codec::PayloadType,
+    frame::WireFrame,
+    version::{CapabilityFlags, NegotiationOutcome, ProtocolVersion},
+    wire::WireProtocol,
+    FrameProfile, Result, WireError, AUTH_TAG_SIZE, MAX_BODY_SIZE, NONCE_SIZE, PROFILE_DEFAULT,
 };
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::time::{Duration, Instant};
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 /// Minimum timing jitter in milliseconds
 pub const JITTER_MIN_MS: u64 = 50;
@@ -33,6 +63,31 @@ pub const JITTER_MIN_MS: u64 = 50;
 /// Maximum timing jitter in milliseconds
 pub const JITTER_MAX_MS: u64 = 200;
 
+/// Magic bytes following the `PayloadType::Chaff` marker inside a chaff body
+///
+/// Only visible after AEAD decryption. Receivers only rely on it for
+/// sessions that did not negotiate `CapabilityFlags::CHAFF_AAD`.
+pub const CHAFF_BODY_MAGIC: [u8; 8] = *b"AETCHAFF";
+
+/// Label appended to a chaff frame's AEAD associated data
+///
+/// Sent when the session negotiated `CapabilityFlags::CHAFF_AAD`. A frame
+/// only authenticates as chaff if it was sealed as chaff, so a real body
+/// that happens to start with [`CHAFF_BODY_MAGIC`] is still delivered.
+pub const CHAFF_AAD_LABEL: &[u8] = b"Aeternum_Chaff_v1";
+
+/// Default mean interval between idle chaff emissions in milliseconds
+pub const IDLE_MEAN_INTERVAL_MS: u64 = 30_000;
+
+/// Length of the aligned windows used by [`ChaffGenerator::next_emission`]
+pub const IDLE_WINDOW_MS: u64 = 300_000;
+
+/// Upper bound on the idle mean interval (one day)
+const IDLE_MEAN_INTERVAL_MAX_MS: u64 = 86_400_000;
+
+/// Domain separator for idle schedule derivation
+const IDLE_SCHEDULE_CONTEXT: &[u8] = b"Aeternum_Chaff_Idle_v1";
+
 /// Chaff synchronization message
 ///
 /// This struct represents a decoy sync message that is indistinguishable
@@ -90,10 +145,24 @@ impl ChaffSyncMessage {
 ///
 /// Generates padding, chaff messages, and timing jitter to prevent
 /// traffic analysis attacks.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ChaffGenerator {
     /// CSPRNG for generating random padding
     rng: StdRng,
+
+    /// Key for this generator's idle schedule
+    idle_key: Zeroizing<[u8; 32]>,
+
+    /// Mean interval between idle chaff emissions
+    idle_mean_interval_ms: u64,
+}
+
+impl fmt::Debug for ChaffGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChaffGenerator")
+            .field("idle_mean_interval_ms", &self.idle_mean_interval_ms)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for ChaffGenerator {
@@ -103,26 +172,131 @@ impl Default for ChaffGenerator {
 }
 
 impl ChaffGenerator {
-    /// Create a new chaff generator with a fresh CSPRNG
+    /// Create a new chaff generator with a CSPRNG seeded from `OsRng`
     pub fn new() -> Self {
+        let mut rng = StdRng::from_entropy();
+        let idle_key = Zeroizing::new(rng.gen());
         Self {
-            rng: StdRng::from_entropy(),
+            rng,
+            idle_key,
+            idle_mean_interval_ms: IDLE_MEAN_INTERVAL_MS,
         }
     }
 
-    /// Create a new chaff generator with a specific seed
+    /// Create a deterministic chaff generator from a seed
+    ///
+    /// Two generators created from the same seed emit the same chaff frames
+    /// and jitter values when called in the same order.
     ///
     /// # Arguments
     ///
-    /// * `seed` - Seed for the CSPRNG (useful for deterministic testing)
+    /// * `seed` - Seed for the CSPRNG
     ///
     /// # Note
     ///
-    /// In production, always use `ChaffGenerator::new()` to get
-    /// cryptographically secure randomness.
-    pub fn with_seed(seed: [u8; 32]) -> Self {
+    /// For tests only. A seeded generator also picks the AEAD nonces of
+    /// [`generate_frame`](Self::generate_frame), so reusing a seed under the
+    /// same session key reuses nonces. In production, always use
+    /// `ChaffGenerator::new()`.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
         Self {
             rng: StdRng::from_seed(seed),
+            idle_key: Zeroizing::new(seed),
+            idle_mean_interval_ms: IDLE_MEAN_INTERVAL_MS,
+        }
+    }
+
+    /// Create a new chaff generator with a specific seed
+    #[deprecated(note = "renamed to `ChaffGenerator::from_seed`")]
+    pub fn with_seed(seed: [u8; 32]) -> Self {
+        Self::from_seed(seed)
+    }
+
+    /// Set the mean interval between idle chaff emissions
+    ///
+    /// Clamped to 1 ms .. 1 day.
+    pub fn with_idle_mean_interval_ms(mut self, mean_interval_ms: u64) -> Self {
+        self.idle_mean_interval_ms = mean_interval_ms.clamp(1, IDLE_MEAN_INTERVAL_MAX_MS);
+        self
+    }
+
+    /// Mean interval between idle chaff emissions in milliseconds
+    pub fn idle_mean_interval_ms(&self) -> u64 {
+        self.idle_mean_interval_ms
+    }
+
+    /// Idle chaff emission times inside a window
+    ///
+    /// Returns the arrival times of a Poisson process with this generator's
+    /// mean interval over `window_start_ms..window_start_ms + window_len_ms`,
+    /// in ascending order. The arrivals are derived from a keyed BLAKE3 hash
+    /// of `seed` and the window bounds: the same inputs always give the same
+    /// schedule, and the schedule cannot be predicted without `seed`.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Per-device secret seed
+    /// * `window_start_ms` - Start of the window (Unix milliseconds)
+    /// * `window_len_ms` - Length of the window in milliseconds
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::sync::ChaffGenerator;
+    ///
+    /// let generator = ChaffGenerator::new().with_idle_mean_interval_ms(1_000);
+    /// let schedule = generator.idle_schedule(&[7u8; 32], 10_000, 60_000);
+    ///
+    /// assert!(schedule.iter().all(|t| (10_000..70_000).contains(t)));
+    /// assert_eq!(schedule, generator.idle_schedule(&[7u8; 32], 10_000, 60_000));
+    /// ```
+    pub fn idle_schedule(
+        &self,
+        seed: &[u8; 32],
+        window_start_ms: u64,
+        window_len_ms: u64,
+    ) -> Vec<u64> {
+        let mut hasher = Blake3Hasher::new_keyed(seed);
+        hasher.update(IDLE_SCHEDULE_CONTEXT);
+        hasher.update(&window_start_ms.to_le_bytes());
+        hasher.update(&window_len_ms.to_le_bytes());
+        let mut rng = StdRng::from_seed(*hasher.finalize().as_bytes());
+
+        let mean = self.idle_mean_interval_ms as f64;
+        let window_len = window_len_ms as f64;
+        let mut schedule = Vec::new();
+        let mut offset = 0.0;
+        loop {
+            // Exponential inter-arrival time by inverse transform sampling
+            let u: f64 = rng.gen();
+            offset += -mean * (1.0 - u).ln();
+            if offset >= window_len {
+                break;
+            }
+            let at = window_start_ms + offset as u64;
+            if schedule.last() != Some(&at) {
+                schedule.push(at);
+            }
+        }
+        schedule
+    }
+
+    /// Next idle chaff emission strictly after `now_ms`
+    ///
+    /// Walks this generator's idle schedule over consecutive
+    /// [`IDLE_WINDOW_MS`]-aligned windows, so repeated calls with the
+    /// returned time enumerate a single continuous schedule.
+    pub fn next_emission(&self, now_ms: u64) -> u64 {
+        let mut window_start = now_ms - now_ms % IDLE_WINDOW_MS;
+        loop {
+            if let Some(&at) = self
+                .idle_schedule(&self.idle_key, window_start, IDLE_WINDOW_MS)
+                .iter()
+                .find(|&&at| at > now_ms)
+            {
+                return at;
+            }
+            window_start = window_start.saturating_add(IDLE_WINDOW_MS);
         }
     }
 
@@ -148,11 +322,25 @@ impl ChaffGenerator {
     /// assert_eq!(padding.len(), FRAME_SIZE - 100);
     /// ```
     pub fn generate_padding(&mut self, current_size: usize) -> Result<Vec<u8>> {
-        if current_size > FRAME_SIZE {
+        self.generate_padding_with_profile(current_size, PROFILE_DEFAULT)
+    }
+
+    /// Generate random padding up to `profile.frame_size`
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidFrameSize` if `current_size` exceeds
+    /// `profile.frame_size`.
+    pub fn generate_padding_with_profile(
+        &mut self,
+        current_size: usize,
+        profile: FrameProfile,
+    ) -> Result<Vec<u8>> {
+        if current_size > profile.frame_size {
             return Err(WireError::InvalidFrameSize(current_size));
         }
 
-        let padding_size = FRAME_SIZE - current_size;
+        let padding_size = profile.frame_size - current_size;
         let mut padding = vec![0u8; padding_size];
 
         // Fill with cryptographically secure random data
@@ -164,7 +352,8 @@ impl ChaffGenerator {
     /// Generate padding for an existing frame
     ///
     /// This method recalculates and replaces the padding in a frame
-    /// to ensure it contains random data rather than zeros.
+    /// to ensure it contains random data rather than zeros. The frame keeps
+    /// the size of its profile.
     ///
     /// # Arguments
     ///
@@ -178,8 +367,9 @@ impl ChaffGenerator {
         let size_without_padding =
             NONCE_SIZE + 4 + 1 + 2 + frame.encrypted_body.len() + AUTH_TAG_SIZE;
 
-        // Generate new random padding
-        frame.padding = self.generate_padding(size_without_padding)?;
+        // Generate new random padding, keeping the frame's profile
+        let profile = FrameProfile::for_frame_size(frame.frame_size()).unwrap_or(PROFILE_DEFAULT);
+        frame.padding = self.generate_padding_with_profile(size_without_padding, profile)?;
 
         Ok(frame)
     }
@@ -204,7 +394,7 @@ impl ChaffGenerator {
     /// properly formatted to prevent statistical analysis.
     pub fn create_chaff_sync(&mut self, epoch: u32) -> Result<WireFrame> {
         // Create a chaff sync message
-        let chaff_msg = ChaffSyncMessage::new();
+        let chaff_msg = self.chaff_message();
 
         // Serialize the chaff message
         let serialized = bincode::serialize(&chaff_msg)
@@ -225,12 +415,138 @@ impl ChaffGenerator {
         WireFrame::new(
             nonce,
             epoch,
-            PayloadType::Sync.to_byte(),
+            PayloadType::EpochSync.to_byte(),
             encrypted_body,
             auth_tag,
         )
     }
 
+    /// Generate a fully valid, encrypted chaff frame
+    ///
+    /// Unlike [`create_chaff_sync`](Self::create_chaff_sync), the frame is
+    /// sealed with the real session key through the same path as
+    /// `WireProtocol::send_message`, so an active prober cannot distinguish
+    /// it by a failing auth tag. The plaintext type byte is `EpochSync`; use
+    /// [`generate_frame_imitating`](Self::generate_frame_imitating) to match
+    /// other traffic. The frame's associated data carries [`CHAFF_AAD_LABEL`]
+    /// and the decrypted body starts with the `PayloadType::Chaff` marker
+    /// followed by [`CHAFF_BODY_MAGIC`]; `WireProtocol::receive` silently
+    /// drops it.
+    ///
+    /// Returns the serialized frame, laid out for [`ProtocolVersion::current`].
+    ///
+    /// # Arguments
+    ///
+    /// * `session_key` - Session key shared with the receiving peer
+    /// * `epoch` - The current epoch
+    pub fn generate_frame(&mut self, session_key: &XChaCha20Key, epoch: u32) -> Result<Vec<u8>> {
+        self.generate_frame_with_profile(session_key, epoch, PROFILE_DEFAULT)
+    }
+
+    /// Generate a valid, encrypted chaff frame sized for `profile`
+    ///
+    /// Chaff must use the session's profile (`WireProtocol::frame_profile`)
+    /// so that it stays the same size as real frames.
+    ///
+    /// The frame is sealed for key generation 0, i.e. directly under the
+    /// session key. Once the session has ratcheted, use
+    /// `WireProtocol::send_chaff`, which seals with the current key.
+    pub fn generate_frame_with_profile(
+        &mut self,
+        session_key: &XChaCha20Key,
+        epoch: u32,
+        profile: FrameProfile,
+    ) -> Result<Vec<u8>> {
+        self.generate_frame_imitating(session_key, epoch, profile, PayloadType::EpochSync)
+    }
+
+    /// Generate a valid, encrypted chaff frame carrying `payload_type`
+    ///
+    /// The cleartext type byte is visible to observers, so chaff should
+    /// carry the type of the real traffic it hides among.
+    /// `WireProtocol::send_chaff` picks it automatically.
+    pub fn generate_frame_imitating(
+        &mut self,
+        session_key: &XChaCha20Key,
+        epoch: u32,
+        profile: FrameProfile,
+        payload_type: PayloadType,
+    ) -> Result<Vec<u8>> {
+        let session = NegotiationOutcome {
+            version: ProtocolVersion::current(),
+            capabilities: CapabilityFlags::default(),
+            frame_profile: profile,
+        };
+        self.seal_chaff(
+            &AeadCipher::new(session_key),
+            0,
+            epoch,
+            &session,
+            payload_type,
+        )
+        .map(|(frame, _)| frame)
+    }
+
+    /// Seal a chaff body under `cipher` for key `generation`, labelled `payload_type`
+    ///
+    /// The associated data carries [`CHAFF_AAD_LABEL`] when the session
+    /// negotiated `CapabilityFlags::CHAFF_AAD`; the body keeps the legacy
+    /// marker for peers that did not.
+    ///
+    /// Returns the serialized frame and the plaintext body length, which
+    /// counts towards the sender's ratchet like a real message.
+    pub(crate) fn seal_chaff(
+        &mut self,
+        cipher: &AeadCipher,
+        generation: u32,
+        epoch: u32,
+        session: &NegotiationOutcome,
+        payload_type: PayloadType,
+    ) -> Result<(Vec<u8>, usize)> {
+        let chaff_msg = self.chaff_message();
+
+        let serialized = bincode::serialize(&chaff_msg)
+            .map_err(|e| WireError::DeserializationFailed(e.to_string()))?;
+
+        let mut body = Vec::with_capacity(1 + CHAFF_BODY_MAGIC.len() + serialized.len());
+        body.push(PayloadType::Chaff.to_byte());
+        body.extend_from_slice(&CHAFF_BODY_MAGIC);
+        body.extend_from_slice(&serialized);
+
+        let frame = WireProtocol::seal_frame_with_rng(
+            cipher,
+            &WireProtocol::chaff_frame_aad(session, generation),
+            &mut self.rng,
+            payload_type,
+            &body,
+            epoch,
+            session,
+        )?;
+        Ok((frame, body.len()))
+    }
+
+    /// Check whether a decrypted body is a chaff body
+    ///
+    /// Returns `true` if the plaintext starts with the `PayloadType::Chaff`
+    /// marker followed by [`CHAFF_BODY_MAGIC`]. A real message may start the
+    /// same way, so this only identifies chaff from peers that did not
+    /// negotiate `CapabilityFlags::CHAFF_AAD`.
+    pub fn is_chaff_body(plaintext: &[u8]) -> bool {
+        plaintext.len() > CHAFF_BODY_MAGIC.len()
+            && plaintext[0] == PayloadType::Chaff.to_byte()
+            && plaintext[1..=CHAFF_BODY_MAGIC.len()] == CHAFF_BODY_MAGIC
+    }
+
+    /// Draw a random chaff sync message from this generator's CSPRNG
+    fn chaff_message(&mut self) -> ChaffSyncMessage {
+        ChaffSyncMessage {
+            fake_epoch: self.rng.gen(),
+            device_count: self.rng.gen_range(2..=10),
+            timestamp: self.rng.gen(),
+            checksum: self.rng.gen(),
+        }
+    }
+
     /// Generate random encrypted body for chaff
     ///
     /// This creates ciphertext-sized random data that matches the
@@ -267,6 +583,14 @@ impl ChaffGenerator {
         Duration::from_millis(jitter_ms)
     }
 
+    /// Draw a timing jitter value and record it with its expected range
+    ///
+    /// Consumes the same CSPRNG output as [`timing_jitter`](Self::timing_jitter).
+    pub fn timing_metadata(&mut self) -> TimingMetadata {
+        let jitter_ms = self.timing_jitter().as_millis() as u64;
+        TimingMetadata::new(jitter_ms, JITTER_MIN_MS, JITTER_MAX_MS)
+    }
+
     /// Apply timing jitter and measure the delay
     ///
     /// This is a convenience method that sleeps for the jitter duration
@@ -374,9 +698,131 @@ impl TimingMetadata {
     }
 }
 
+/// Chaff scheduler that keeps the total (real + chaff) message rate constant
+///
+/// The scheduler runs a sequence of slots at `target_rate` per second. Each
+/// slot carries chaff with probability `(target_rate - real_rate) /
+/// target_rate`, so chaff fills the gap left by real traffic: quiet periods
+/// get more chaff and bursts get less, while an observer sees the same
+/// message rate either way.
+///
+/// Slot delays are a fixed base plus a jitter drawn from
+/// `JITTER_MIN_MS..=JITTER_MAX_MS`, with the base chosen so the mean delay
+/// is `1000 / target_rate` ms. Targets above `1000 / ((JITTER_MIN_MS +
+/// JITTER_MAX_MS) / 2)` per second saturate at that rate.
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::sync::AdaptiveChaffScheduler;
+///
+/// let mut scheduler = AdaptiveChaffScheduler::new(2.0);
+///
+/// // Real traffic already exceeds the target: slots carry no chaff
+/// let delay_ms = scheduler.next_delay_ms(5.0);
+/// assert!(delay_ms > 0);
+/// assert!(scheduler.take_chaff().is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdaptiveChaffScheduler {
+    /// Target total message rate (messages per second)
+    target_rate: f64,
+
+    /// Source of chaff messages and jitter
+    generator: ChaffGenerator,
+
+    /// Whether the slot scheduled by the last `next_delay_ms` carries chaff
+    chaff_due: bool,
+}
+
+impl AdaptiveChaffScheduler {
+    /// Create a scheduler targeting `target_rate` messages per second
+    ///
+    /// A non-positive or non-finite target disables chaff.
+    pub fn new(target_rate: f64) -> Self {
+        Self::with_generator(target_rate, ChaffGenerator::new())
+    }
+
+    /// Create a deterministic scheduler from a seed (see
+    /// [`ChaffGenerator::from_seed`])
+    pub fn from_seed(target_rate: f64, seed: [u8; 32]) -> Self {
+        Self::with_generator(target_rate, ChaffGenerator::from_seed(seed))
+    }
+
+    fn with_generator(target_rate: f64, generator: ChaffGenerator) -> Self {
+        let target_rate = if target_rate.is_finite() && target_rate > 0.0 {
+            target_rate
+        } else {
+            0.0
+        };
+        Self {
+            target_rate,
+            generator,
+            chaff_due: false,
+        }
+    }
+
+    /// Target total message rate (messages per second)
+    pub fn target_rate(&self) -> f64 {
+        self.target_rate
+    }
+
+    /// Chaff rate needed to top `real_rate` up to the target
+    pub fn chaff_rate(&self, real_rate: f64) -> f64 {
+        let real_rate = if real_rate.is_finite() {
+            real_rate.max(0.0)
+        } else {
+            0.0
+        };
+        (self.target_rate - real_rate).max(0.0)
+    }
+
+    /// Schedule the next slot and return the delay until it fires
+    ///
+    /// # Arguments
+    ///
+    /// * `real_rate` - Recently observed rate of real messages (per second)
+    ///
+    /// When the slot fires, call [`take_chaff`](Self::take_chaff) to get
+    /// the chaff message to send, if any.
+    pub fn next_delay_ms(&mut self, real_rate: f64) -> u64 {
+        let chaff_probability = if self.target_rate > 0.0 {
+            (self.chaff_rate(real_rate) / self.target_rate).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.chaff_due = self.generator.rng.gen_bool(chaff_probability);
+
+        self.base_delay_ms() + self.generator.timing_jitter().as_millis() as u64
+    }
+
+    /// Chaff message for the slot that just fired
+    ///
+    /// Returns `None` if the slot carries no chaff or was already taken.
+    pub fn take_chaff(&mut self) -> Option<ChaffSyncMessage> {
+        if std::mem::take(&mut self.chaff_due) {
+            Some(self.generator.chaff_message())
+        } else {
+            None
+        }
+    }
+
+    /// Fixed part of the slot delay, before jitter
+    fn base_delay_ms(&self) -> u64 {
+        if self.target_rate <= 0.0 {
+            return 0;
+        }
+        let mean_jitter_ms = (JITTER_MIN_MS + JITTER_MAX_MS) as f64 / 2.0;
+        (1000.0 / self.target_rate - mean_jitter_ms)
+            .max(0.0)
+            .round() as u64
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sync::FRAME_SIZE;
 
     #[test]
     fn test_chaff_generator_new() {
@@ -386,10 +832,10 @@ mod tests {
     }
 
     #[test]
-    fn test_chaff_generator_with_seed() {
+    fn test_chaff_generator_from_seed() {
         let seed = [42u8; 32];
-        let mut gen1 = ChaffGenerator::with_seed(seed);
-        let mut gen2 = ChaffGenerator::with_seed(seed);
+        let mut gen1 = ChaffGenerator::from_seed(seed);
+        let mut gen2 = ChaffGenerator::from_seed(seed);
 
         // Same seed should produce same first value
         let val1 = gen1.rng.gen::<u32>();
@@ -397,6 +843,52 @@ mod tests {
         assert_eq!(val1, val2);
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_with_seed_forwards_to_from_seed() {
+        let seed = [42u8; 32];
+        let mut old = ChaffGenerator::with_seed(seed);
+        let mut new = ChaffGenerator::from_seed(seed);
+        assert_eq!(old.rng.gen::<u32>(), new.rng.gen::<u32>());
+    }
+
+    #[test]
+    fn test_seeded_generators_emit_identical_chaff_and_timings() {
+        let seed = [7u8; 32];
+        let session_key = XChaCha20Key::from_bytes(&[9u8; 32]).unwrap();
+        let mut gen1 = ChaffGenerator::from_seed(seed);
+        let mut gen2 = ChaffGenerator::from_seed(seed);
+
+        for epoch in 1..=4 {
+            assert_eq!(
+                gen1.create_chaff_sync(epoch).unwrap().serialize().unwrap(),
+                gen2.create_chaff_sync(epoch).unwrap().serialize().unwrap()
+            );
+            assert_eq!(
+                gen1.generate_frame(&session_key, epoch).unwrap(),
+                gen2.generate_frame(&session_key, epoch).unwrap()
+            );
+
+            let timing1 = gen1.timing_metadata();
+            let timing2 = gen2.timing_metadata();
+            assert_eq!(timing1.actual_delay_ms, timing2.actual_delay_ms);
+            assert!(timing1.is_within_range());
+            assert_eq!(timing1.expected_min_ms, JITTER_MIN_MS);
+            assert_eq!(timing1.expected_max_ms, JITTER_MAX_MS);
+        }
+
+        // A different seed diverges
+        let mut gen3 = ChaffGenerator::from_seed([8u8; 32]);
+        assert_ne!(
+            ChaffGenerator::from_seed(seed)
+                .create_chaff_sync(1)
+                .unwrap()
+                .serialize()
+                .unwrap(),
+            gen3.create_chaff_sync(1).unwrap().serialize().unwrap()
+        );
+    }
+
     #[test]
     fn test_generate_padding_correct_size() {
         let mut generator = ChaffGenerator::new();
@@ -470,7 +962,7 @@ mod tests {
 
         // Verify frame properties
         assert_eq!(chaff_frame.epoch, 5);
-        assert_eq!(chaff_frame.payload_type, PayloadType::Sync.to_byte());
+        assert_eq!(chaff_frame.payload_type, PayloadType::EpochSync.to_byte());
         assert_eq!(chaff_frame.nonce.len(), NONCE_SIZE);
         assert_eq!(chaff_frame.auth_tag.len(), AUTH_TAG_SIZE);
     }
@@ -545,7 +1037,7 @@ mod tests {
 
         for frame in &batch {
             assert_eq!(frame.epoch, 10);
-            assert_eq!(frame.payload_type, PayloadType::Sync.to_byte());
+            assert_eq!(frame.payload_type, PayloadType::EpochSync.to_byte());
         }
     }
 
@@ -566,7 +1058,7 @@ mod tests {
         let real_frame = WireFrame::new(
             [1u8; NONCE_SIZE],
             5,
-            PayloadType::Sync.to_byte(),
+            PayloadType::EpochSync.to_byte(),
             vec![2, 3, 4],
             [5u8; AUTH_TAG_SIZE],
         )
@@ -585,12 +1077,12 @@ mod tests {
 
     #[test]
     fn test_chaff_statistical_independence() {
-        let mut generator = ChaffGenerator::with_seed([1u8; 32]);
+        let mut generator = ChaffGenerator::from_seed([1u8; 32]);
 
         // Generate two batches with different seeds
         let batch1 = generator.generate_chaff_batch(10, 1).unwrap();
 
-        generator = ChaffGenerator::with_seed([2u8; 32]);
+        generator = ChaffGenerator::from_seed([2u8; 32]);
         let batch2 = generator.generate_chaff_batch(10, 1).unwrap();
 
         // Verify that different seeds produce different results
@@ -629,6 +1121,105 @@ mod tests {
         assert_eq!(metadata_clone.expected_max_ms, 0);
     }
 
+    #[test]
+    fn test_generate_frame_decrypts_to_chaff_marker() {
+        use crate::sync::frame::SealedFrame;
+
+        let key = XChaCha20Key::generate();
+        let mut generator = ChaffGenerator::new();
+        let bytes = generator.generate_frame(&key, 7).unwrap();
+        assert_eq!(bytes.len(), FRAME_SIZE);
+
+        let frame = SealedFrame::parse(&bytes, PROFILE_DEFAULT).unwrap();
+        assert_eq!(frame.epoch(), 7);
+        assert_eq!(frame.payload_type().unwrap(), PayloadType::EpochSync);
+
+        // The auth tag is valid under the session key
+        let mut aad = 0u32.to_be_bytes().to_vec();
+        aad.extend_from_slice(CHAFF_AAD_LABEL);
+        let padded = frame
+            .decrypt(&AeadCipher::new(&key), &aad)
+            .expect("Chaff frame must carry a valid auth tag");
+        assert!(frame
+            .decrypt(&AeadCipher::new(&key), &0u32.to_be_bytes())
+            .is_err());
+        let plaintext = SealedFrame::unpad(padded).unwrap();
+
+        assert!(ChaffGenerator::is_chaff_body(&plaintext));
+        let msg: ChaffSyncMessage =
+            bincode::deserialize(&plaintext[1 + CHAFF_BODY_MAGIC.len()..]).unwrap();
+        assert!(msg.device_count >= 2 && msg.device_count <= 10);
+    }
+
+    #[test]
+    fn test_is_chaff_body() {
+        let mut body = vec![PayloadType::Chaff.to_byte()];
+        body.extend_from_slice(&CHAFF_BODY_MAGIC);
+        body.push(0);
+        assert!(ChaffGenerator::is_chaff_body(&body));
+
+        assert!(!ChaffGenerator::is_chaff_body(&[]));
+        assert!(!ChaffGenerator::is_chaff_body(
+            &body[..CHAFF_BODY_MAGIC.len()]
+        ));
+        assert!(!ChaffGenerator::is_chaff_body(b"ordinary sync payload"));
+
+        body[0] = PayloadType::EpochSync.to_byte();
+        assert!(!ChaffGenerator::is_chaff_body(&body));
+    }
+
+    #[test]
+    fn test_chaff_byte_histogram_matches_real_frames() {
+        const SAMPLES: usize = 1000;
+
+        let key = XChaCha20Key::generate();
+        let mut generator = ChaffGenerator::new();
+        let mut sender = WireProtocol::new(key.clone());
+
+        // Real payloads have the same length as a chaff body
+        let body_len = 1 + CHAFF_BODY_MAGIC.len() + 17;
+
+        let mut real_hist = [0u64; 256];
+        let mut chaff_hist = [0u64; 256];
+
+        for _ in 0..SAMPLES {
+            let mut payload = vec![0u8; body_len];
+            generator.rng.fill(&mut payload[..]);
+            let real = sender
+                .send_message(PayloadType::EpochSync, payload, 1)
+                .unwrap();
+            for &b in &real {
+                real_hist[b as usize] += 1;
+            }
+
+            let chaff = generator.generate_frame(&key, 1).unwrap();
+            for &b in &chaff {
+                chaff_hist[b as usize] += 1;
+            }
+        }
+
+        // Two-sample chi-squared statistic (equal totals)
+        let mut chi_squared = 0.0;
+        let mut bins = 0usize;
+        for i in 0..256 {
+            let r = real_hist[i] as f64;
+            let c = chaff_hist[i] as f64;
+            if r + c > 0.0 {
+                chi_squared += (r - c).powi(2) / (r + c);
+                bins += 1;
+            }
+        }
+
+        // Expected value is (bins - 1) degrees of freedom; allow a wide margin
+        let bound = 2.0 * bins as f64;
+        assert!(
+            chi_squared < bound,
+            "Chaff byte histogram deviates from real frames: chi2={} (bound {})",
+            chi_squared,
+            bound
+        );
+    }
+
     // Property test: Verify padding always produces correct size
     #[test]
     fn test_property_padding_size() {
@@ -652,4 +1243,175 @@ mod tests {
             assert_eq!(serialized.len(), FRAME_SIZE);
         }
     }
+
+    // ------------------------------------------------------------------------
+    // Idle Schedule Tests
+    // ------------------------------------------------------------------------
+
+    #[test]
+    fn test_idle_schedule_deterministic() {
+        let generator = ChaffGenerator::new().with_idle_mean_interval_ms(2_000);
+        let seed = [9u8; 32];
+
+        let first = generator.idle_schedule(&seed, 1_000_000, 60_000);
+        let second = ChaffGenerator::from_seed([1u8; 32])
+            .with_idle_mean_interval_ms(2_000)
+            .idle_schedule(&seed, 1_000_000, 60_000);
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+
+        // Sorted and inside the window
+        assert!(first.windows(2).all(|w| w[0] < w[1]));
+        assert!(first.iter().all(|t| (1_000_000..1_060_000).contains(t)));
+
+        // Other seeds and windows give unrelated schedules
+        assert_ne!(
+            first,
+            generator.idle_schedule(&[10u8; 32], 1_000_000, 60_000)
+        );
+        assert_ne!(first, generator.idle_schedule(&seed, 1_060_000, 60_000));
+    }
+
+    #[test]
+    fn test_idle_schedule_mean_interval() {
+        let mean_ms = 2_000;
+        let generator = ChaffGenerator::new().with_idle_mean_interval_ms(mean_ms);
+        let seed = [4u8; 32];
+
+        let mut gaps = Vec::new();
+        for window in 0..200u64 {
+            let schedule = generator.idle_schedule(&seed, window * 60_000, 60_000);
+            gaps.extend(schedule.windows(2).map(|w| (w[1] - w[0]) as f64));
+        }
+
+        let observed = gaps.iter().sum::<f64>() / gaps.len() as f64;
+        assert!(
+            (observed - mean_ms as f64).abs() < mean_ms as f64 * 0.1,
+            "observed mean interval {}",
+            observed
+        );
+    }
+
+    #[test]
+    fn test_next_emission_follows_schedule() {
+        let generator = ChaffGenerator::from_seed([8u8; 32]).with_idle_mean_interval_ms(20_000);
+        let start = 5 * IDLE_WINDOW_MS;
+        let expected: Vec<u64> = (0..3)
+            .flat_map(|i| {
+                generator.idle_schedule(&[8u8; 32], start + i * IDLE_WINDOW_MS, IDLE_WINDOW_MS)
+            })
+            .collect();
+
+        let mut now = start - 1;
+        let mut emitted = Vec::new();
+        while emitted.len() < expected.len() {
+            now = generator.next_emission(now);
+            emitted.push(now);
+        }
+        assert_eq!(emitted, expected);
+    }
+
+    #[test]
+    fn test_idle_mean_interval_clamped() {
+        assert_eq!(
+            ChaffGenerator::new()
+                .with_idle_mean_interval_ms(0)
+                .idle_mean_interval_ms(),
+            1
+        );
+        assert_eq!(
+            ChaffGenerator::new().idle_mean_interval_ms(),
+            IDLE_MEAN_INTERVAL_MS
+        );
+    }
+
+    // ------------------------------------------------------------------------
+    // AdaptiveChaffScheduler Tests
+    // ------------------------------------------------------------------------
+
+    /// Run `slots` slots and return (chaff count, elapsed seconds)
+    fn run_scheduler(
+        scheduler: &mut AdaptiveChaffScheduler,
+        real_rate: f64,
+        slots: usize,
+    ) -> (usize, f64) {
+        let mut chaff = 0;
+        let mut elapsed_ms = 0;
+        for _ in 0..slots {
+            elapsed_ms += scheduler.next_delay_ms(real_rate);
+            if scheduler.take_chaff().is_some() {
+                chaff += 1;
+            }
+        }
+        (chaff, elapsed_ms as f64 / 1000.0)
+    }
+
+    #[test]
+    fn test_adaptive_chaff_decreases_with_real_traffic() {
+        let seed = [3u8; 32];
+        let (quiet, _) = run_scheduler(
+            &mut AdaptiveChaffScheduler::from_seed(4.0, seed),
+            0.5,
+            2_000,
+        );
+        let (busy, _) = run_scheduler(
+            &mut AdaptiveChaffScheduler::from_seed(4.0, seed),
+            3.0,
+            2_000,
+        );
+        let (saturated, _) = run_scheduler(
+            &mut AdaptiveChaffScheduler::from_seed(4.0, seed),
+            10.0,
+            2_000,
+        );
+
+        assert!(busy < quiet);
+        assert_eq!(saturated, 0);
+    }
+
+    #[test]
+    fn test_adaptive_chaff_combined_rate_near_target() {
+        let target = 2.0;
+        for real_rate in [0.0, 0.5, 1.5] {
+            let mut scheduler = AdaptiveChaffScheduler::from_seed(target, [5u8; 32]);
+            let (chaff, seconds) = run_scheduler(&mut scheduler, real_rate, 5_000);
+
+            let combined = chaff as f64 / seconds + real_rate;
+            assert!(
+                (combined - target).abs() < 0.1,
+                "real_rate={} combined={}",
+                real_rate,
+                combined
+            );
+        }
+    }
+
+    #[test]
+    fn test_adaptive_chaff_delay_within_jitter_envelope() {
+        let mut scheduler = AdaptiveChaffScheduler::from_seed(2.0, [6u8; 32]);
+        let base = scheduler.base_delay_ms();
+        assert_eq!(base, 375);
+
+        for _ in 0..500 {
+            let delay = scheduler.next_delay_ms(1.0);
+            assert!((base + JITTER_MIN_MS..=base + JITTER_MAX_MS).contains(&delay));
+        }
+
+        // High targets saturate at pure jitter
+        let mut fast = AdaptiveChaffScheduler::from_seed(100.0, [6u8; 32]);
+        for _ in 0..500 {
+            let delay = fast.next_delay_ms(0.0);
+            assert!((JITTER_MIN_MS..=JITTER_MAX_MS).contains(&delay));
+        }
+    }
+
+    #[test]
+    fn test_adaptive_chaff_invalid_target_disables_chaff() {
+        for target in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let mut scheduler = AdaptiveChaffScheduler::from_seed(target, [1u8; 32]);
+            assert_eq!(scheduler.target_rate(), 0.0);
+            let (chaff, _) = run_scheduler(&mut scheduler, f64::NAN, 100);
+            assert_eq!(chaff, 0);
+        }
+    }
 }
@@ -4,10 +4,22 @@
 //!
 //! ## Payload Types
 //!
-//! - `Handshake` - Device pairing protocol
-//! - `Sync` - Global epoch synchronization
-//! - `Veto` - Recovery veto signal (highest priority)
-//! - `Recovery` - Cold anchor recovery flow
+//! Every frame carries a 1-byte type discriminator. The byte values are
+//! fixed; any other byte is rejected with `WireError::InvalidPayloadType`
+//! rather than mapped to a catch-all, so a frame can never be processed as
+//! a type its sender did not intend.
+//!
+//! | Byte | Type | Purpose |
+//! |------|------|---------|
+//! | 0x01 | `Pairing` | Device pairing protocol |
+//! | 0x02 | `EpochSync` | Global epoch synchronization |
+//! | 0x03 | `Veto` | Recovery veto signal (highest priority) |
+//! | 0x04 | `Recovery` | Cold anchor recovery flow |
+//! | 0x05 | `VersionNegotiation` | Protocol version negotiation |
+//! | 0x06 | `Chaff` | Decoy traffic marker (only ever appears inside the encrypted body) |
+//! | 0x07 | `VetoAck` | Veto delivery acknowledgement |
+//! | 0x08 | `Data` | Application data (vault sync payloads) |
+//! | 0x09 | `Error` | Connection error or close notification ([`ErrorFrame`](crate::sync::connection::ErrorFrame)) |
 //!
 //! ## Security
 //!
@@ -25,10 +37,10 @@ use serde::{Deserialize, Serialize};
 #[repr(u8)]
 pub enum PayloadType {
     /// Device pairing handshake (new device onboarding)
-    Handshake = 0x01,
+    Pairing = 0x01,
 
     /// Global epoch synchronization (PQRR coordination)
-    Sync = 0x02,
+    EpochSync = 0x02,
 
     /// Veto signal (48h window recovery interception)
     ///
@@ -41,21 +53,60 @@ pub enum PayloadType {
     /// Protocol version negotiation
     VersionNegotiation = 0x05,
 
-    /// Unknown/invalid payload type
-    #[serde(other)]
-    Unknown = 0xFF,
+    /// Chaff (decoy) traffic
+    ///
+    /// Never used as the plaintext frame type: chaff frames carry a real
+    /// payload type on the wire and this marker inside the encrypted body,
+    /// so only the receiving peer can tell them apart.
+    Chaff = 0x06,
+
+    /// Veto delivery acknowledgement
+    ///
+    /// Confirms receipt of a specific veto (request ID + veto digest).
+    VetoAck = 0x07,
+
+    /// Application data (vault sync payloads)
+    Data = 0x08,
+
+    /// Connection error or graceful close notification
+    ///
+    /// Carries a coarse error code only; see
+    /// [`ErrorFrame`](crate::sync::connection::ErrorFrame).
+    Error = 0x09,
 }
 
 impl PayloadType {
+    /// Every payload type, in byte order
+    pub const ALL: [PayloadType; 9] = [
+        PayloadType::Pairing,
+        PayloadType::EpochSync,
+        PayloadType::Veto,
+        PayloadType::Recovery,
+        PayloadType::VersionNegotiation,
+        PayloadType::Chaff,
+        PayloadType::VetoAck,
+        PayloadType::Data,
+        PayloadType::Error,
+    ];
+
     /// Convert from byte representation
-    pub fn from_byte(value: u8) -> Self {
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidPayloadType` if `value` is not assigned
+    /// to a payload type.
+    pub fn from_byte(value: u8) -> Result<Self> {
         match value {
-            0x01 => PayloadType::Handshake,
-            0x02 => PayloadType::Sync,
-            0x03 => PayloadType::Veto,
-            0x04 => PayloadType::Recovery,
-            0x05 => PayloadType::VersionNegotiation,
-            _ => PayloadType::Unknown,
+            0x01 => Ok(PayloadType::Pairing),
+            0x02 => Ok(PayloadType::EpochSync),
+            0x03 => Ok(PayloadType::Veto),
+            0x04 => Ok(PayloadType::Recovery),
+            0x05 => Ok(PayloadType::VersionNegotiation),
+            0x06 => Ok(PayloadType::Chaff),
+            0x07 => Ok(PayloadType::VetoAck),
+            0x08 => Ok(PayloadType::Data),
+            0x09 => Ok(PayloadType::Error),
+            _ => Err(WireError::InvalidPayloadType(value)),
         }
     }
 
@@ -73,7 +124,18 @@ impl PayloadType {
 
     /// Check if this payload type can be processed in degraded mode
     pub fn allowed_in_degraded_mode(self) -> bool {
-        matches!(self, PayloadType::Veto | PayloadType::Recovery)
+        matches!(
+            self,
+            PayloadType::Veto | PayloadType::VetoAck | PayloadType::Recovery
+        )
+    }
+}
+
+impl TryFrom<u8> for PayloadType {
+    type Error = WireError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        Self::from_byte(value)
     }
 }
 
@@ -100,7 +162,9 @@ impl MessageCodec {
     /// # Note
     ///
     /// This method assumes encryption has been performed externally.
-    /// The `encrypted_body` parameter should already be ciphertext.
+    /// The `encrypted_body` parameter should already be ciphertext. Only
+    /// the fixed [`PayloadType`] bytes can be written; there is no way to
+    /// encode an unassigned type byte.
     pub fn encode(
         payload_type: PayloadType,
         encrypted_body: Vec<u8>,
@@ -117,7 +181,7 @@ impl MessageCodec {
         )
     }
 
-    /// Decode a Wire Frame and extract payload type
+    /// Decode a Wire Frame into its payload type and encrypted body
     ///
     /// # Arguments
     ///
@@ -127,17 +191,26 @@ impl MessageCodec {
     ///
     /// Returns the `PayloadType` and encrypted body.
     ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidPayloadType` if the frame's type byte is
+    /// not a known payload type.
+    ///
     /// # Note
     ///
     /// Decryption must be performed externally after extracting the body.
-    pub fn decode_payload_type(frame: &WireFrame) -> Result<PayloadType> {
-        let payload_type = PayloadType::from_byte(frame.payload_type);
-
-        if matches!(payload_type, PayloadType::Unknown) {
-            return Err(WireError::InvalidPayloadType(frame.payload_type));
-        }
+    pub fn decode(frame: &WireFrame) -> Result<(PayloadType, Vec<u8>)> {
+        Ok((Self::decode_payload_type(frame)?, Self::extract_body(frame)))
+    }
 
-        Ok(payload_type)
+    /// Decode the payload type of a Wire Frame
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidPayloadType` if the frame's type byte is
+    /// not a known payload type.
+    pub fn decode_payload_type(frame: &WireFrame) -> Result<PayloadType> {
+        PayloadType::from_byte(frame.payload_type)
     }
 
     /// Extract encrypted body from frame
@@ -198,38 +271,52 @@ mod tests {
 
     impl Message for TestMessage {
         fn payload_type() -> PayloadType {
-            PayloadType::Sync
+            PayloadType::EpochSync
         }
     }
 
     #[test]
     fn test_payload_type_conversion() {
-        assert_eq!(PayloadType::from_byte(0x01), PayloadType::Handshake);
-        assert_eq!(PayloadType::from_byte(0x02), PayloadType::Sync);
-        assert_eq!(PayloadType::from_byte(0x03), PayloadType::Veto);
-        assert_eq!(PayloadType::from_byte(0x04), PayloadType::Recovery);
+        assert_eq!(PayloadType::from_byte(0x01).unwrap(), PayloadType::Pairing);
+        assert_eq!(
+            PayloadType::from_byte(0x02).unwrap(),
+            PayloadType::EpochSync
+        );
+        assert_eq!(PayloadType::from_byte(0x03).unwrap(), PayloadType::Veto);
+        assert_eq!(PayloadType::from_byte(0x04).unwrap(), PayloadType::Recovery);
         assert_eq!(
-            PayloadType::from_byte(0x05),
+            PayloadType::from_byte(0x05).unwrap(),
             PayloadType::VersionNegotiation
         );
-        assert_eq!(PayloadType::from_byte(0xFF), PayloadType::Unknown);
+        assert_eq!(PayloadType::from_byte(0x06).unwrap(), PayloadType::Chaff);
+        assert_eq!(PayloadType::from_byte(0x07).unwrap(), PayloadType::VetoAck);
+        assert_eq!(PayloadType::from_byte(0x08).unwrap(), PayloadType::Data);
+        assert_eq!(PayloadType::from_byte(0x09).unwrap(), PayloadType::Error);
+        assert!(matches!(
+            PayloadType::from_byte(0xFF),
+            Err(WireError::InvalidPayloadType(0xFF))
+        ));
     }
 
     #[test]
     fn test_payload_type_to_byte() {
-        assert_eq!(PayloadType::Handshake.to_byte(), 0x01);
-        assert_eq!(PayloadType::Sync.to_byte(), 0x02);
+        assert_eq!(PayloadType::Pairing.to_byte(), 0x01);
+        assert_eq!(PayloadType::EpochSync.to_byte(), 0x02);
         assert_eq!(PayloadType::Veto.to_byte(), 0x03);
         assert_eq!(PayloadType::Recovery.to_byte(), 0x04);
         assert_eq!(PayloadType::VersionNegotiation.to_byte(), 0x05);
+        assert_eq!(PayloadType::Chaff.to_byte(), 0x06);
+        assert_eq!(PayloadType::VetoAck.to_byte(), 0x07);
+        assert_eq!(PayloadType::Data.to_byte(), 0x08);
+        assert_eq!(PayloadType::Error.to_byte(), 0x09);
     }
 
     #[test]
     fn test_payload_type_immediate_processing() {
         // Only Veto requires immediate processing
         assert!(PayloadType::Veto.requires_immediate_processing());
-        assert!(!PayloadType::Sync.requires_immediate_processing());
-        assert!(!PayloadType::Handshake.requires_immediate_processing());
+        assert!(!PayloadType::EpochSync.requires_immediate_processing());
+        assert!(!PayloadType::Pairing.requires_immediate_processing());
     }
 
     #[test]
@@ -237,8 +324,9 @@ mod tests {
         // Veto and Recovery are allowed in degraded mode
         assert!(PayloadType::Veto.allowed_in_degraded_mode());
         assert!(PayloadType::Recovery.allowed_in_degraded_mode());
-        assert!(!PayloadType::Sync.allowed_in_degraded_mode());
-        assert!(!PayloadType::Handshake.allowed_in_degraded_mode());
+        assert!(PayloadType::VetoAck.allowed_in_degraded_mode());
+        assert!(!PayloadType::EpochSync.allowed_in_degraded_mode());
+        assert!(!PayloadType::Pairing.allowed_in_degraded_mode());
     }
 
     #[test]
@@ -248,7 +336,7 @@ mod tests {
         let encrypted_body = vec![3, 4, 5];
 
         let frame = MessageCodec::encode(
-            PayloadType::Sync,
+            PayloadType::EpochSync,
             encrypted_body.clone(),
             1,
             nonce,
@@ -294,6 +382,49 @@ mod tests {
         assert!(matches!(result, Err(WireError::InvalidPayloadType(0xFF))));
     }
 
+    #[test]
+    fn test_every_payload_type_round_trips() {
+        for payload_type in PayloadType::ALL {
+            let frame = MessageCodec::encode(
+                payload_type,
+                vec![payload_type.to_byte(); 4],
+                9,
+                [5u8; NONCE_SIZE],
+                [6u8; crate::sync::AUTH_TAG_SIZE],
+            )
+            .unwrap();
+
+            let decoded = WireFrame::deserialize(&frame.serialize().unwrap()).unwrap();
+            assert_eq!(
+                MessageCodec::decode(&decoded).unwrap(),
+                (payload_type, vec![payload_type.to_byte(); 4])
+            );
+            assert_eq!(
+                PayloadType::try_from(payload_type.to_byte()).unwrap(),
+                payload_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_unassigned_payload_bytes_rejected() {
+        let assigned: Vec<u8> = PayloadType::ALL.iter().map(|t| t.to_byte()).collect();
+        for byte in (0..=u8::MAX).filter(|b| !assigned.contains(b)) {
+            let frame = WireFrame::new(
+                [0u8; NONCE_SIZE],
+                1,
+                byte,
+                vec![1, 2, 3],
+                [0u8; crate::sync::AUTH_TAG_SIZE],
+            )
+            .unwrap();
+            assert!(matches!(
+                MessageCodec::decode(&frame),
+                Err(WireError::InvalidPayloadType(b)) if b == byte
+            ));
+        }
+    }
+
     #[test]
     fn test_message_serialization() {
         let msg = TestMessage {
@@ -0,0 +1,734 @@
+//! # Connection Lifecycle
+//!
+//! Connection state machine layered on a [`WireProtocol`] session, driven
+//! entirely by the caller: every entry point takes the current time in
+//! milliseconds and nothing runs in the background.
+//!
+//! ## States
+//!
+//! ```text
+//! Handshaking ──negotiated──▶ Established ──close / error──▶ Closing ──▶ Closed
+//!      │                                                        ▲
+//!      └──────────────── timeout / error / peer close ──────────┘
+//! ```
+//!
+//! | State | Accepted frames | Timeout |
+//! |-------|-----------------|---------|
+//! | `Handshaking` | `VersionNegotiation`, `Error` | [`ConnectionTimeouts::handshake_ms`] since start |
+//! | `Established` | everything but `VersionNegotiation` | [`ConnectionTimeouts::idle_ms`] since the last frame |
+//! | `Closing` | `Error` (others are dropped) | [`ConnectionTimeouts::closing_ms`] since entering |
+//! | `Closed` | none | — |
+//!
+//! A frame type that is not accepted in the current state, a failed
+//! negotiation or a frame that fails to open moves the connection to
+//! `Closing` with an [`ErrorFrame`] queued for the peer. A timeout does the
+//! same with [`ErrorCode::Timeout`].
+//!
+//! ## Error Frames
+//!
+//! `Error` frames are sealed like any other frame and carry one coarse
+//! [`ErrorCode`], enough for the peer to tell "version mismatch" from
+//! "authentication failure" without learning which check failed.
+//! [`ErrorCode::Normal`] is the graceful close: the side closing sends it,
+//! the peer answers with its own `Normal` frame and closes, and the
+//! initiator closes on receiving the answer.
+//!
+//! ## Transport Loop
+//!
+//! The transport feeds received frames to [`WireProtocol::handle_frame`]
+//! and calls [`WireProtocol::tick`] until it returns
+//! [`ConnectionAction::None`], executing each action it gets:
+//!
+//! ```no_run
+//! use aeternum_core::crypto::aead::XChaCha20Key;
+//! use aeternum_core::sync::connection::ConnectionAction;
+//! use aeternum_core::sync::version::{ProtocolVersion, VersionNegotiationMessage};
+//! use aeternum_core::sync::wire::WireProtocol;
+//!
+//! let local = VersionNegotiationMessage::default_with_version(ProtocolVersion::current());
+//! let mut session = WireProtocol::new(XChaCha20Key::generate());
+//! session.start_connection(local, true, 0).unwrap();
+//!
+//! loop {
+//!     match session.tick(0) {
+//!         ConnectionAction::SendFrame(frame) => { /* write `frame` to the link */ }
+//!         ConnectionAction::CloseConnection => { /* drop the link */ break; }
+//!         ConnectionAction::None => break,
+//!     }
+//! }
+//! ```
+
+use crate::sync::codec::{Message, PayloadType};
+use crate::sync::frame::SealedFrame;
+use crate::sync::version::VersionNegotiationMessage;
+use crate::sync::wire::WireProtocol;
+use crate::sync::{FrameProfile, Result, WireError};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Default time allowed for version negotiation (10 seconds)
+pub const DEFAULT_HANDSHAKE_TIMEOUT_MS: u64 = 10_000;
+
+/// Default idle time after which an established connection is closed (5 minutes)
+pub const DEFAULT_IDLE_TIMEOUT_MS: u64 = 5 * 60 * 1000;
+
+/// Default time allowed for the peer to answer a close (5 seconds)
+pub const DEFAULT_CLOSING_TIMEOUT_MS: u64 = 5_000;
+
+/// Lifecycle state of a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Version negotiation in progress
+    Handshaking,
+    /// Negotiated; application frames flow
+    Established,
+    /// Close sent, waiting for the peer's answer
+    Closing,
+    /// Done; the link should be dropped
+    Closed,
+}
+
+impl ConnectionState {
+    /// Whether a frame of `payload_type` is valid in this state
+    pub fn accepts(self, payload_type: PayloadType) -> bool {
+        match self {
+            ConnectionState::Handshaking => matches!(
+                payload_type,
+                PayloadType::VersionNegotiation | PayloadType::Error
+            ),
+            ConnectionState::Established => payload_type != PayloadType::VersionNegotiation,
+            ConnectionState::Closing => payload_type == PayloadType::Error,
+            ConnectionState::Closed => false,
+        }
+    }
+}
+
+/// Per-state timeouts, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionTimeouts {
+    /// Time from start until negotiation must be complete
+    pub handshake_ms: u64,
+    /// Time without a received frame after which an established connection closes
+    pub idle_ms: u64,
+    /// Time to wait for the peer to answer a close
+    pub closing_ms: u64,
+}
+
+impl Default for ConnectionTimeouts {
+    fn default() -> Self {
+        Self {
+            handshake_ms: DEFAULT_HANDSHAKE_TIMEOUT_MS,
+            idle_ms: DEFAULT_IDLE_TIMEOUT_MS,
+            closing_ms: DEFAULT_CLOSING_TIMEOUT_MS,
+        }
+    }
+}
+
+/// Coarse reason carried by an [`ErrorFrame`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum ErrorCode {
+    /// Graceful close, no error
+    Normal = 0x00,
+    /// No common protocol version or frame profile
+    VersionMismatch = 0x01,
+    /// A frame failed authentication
+    AuthFailure = 0x02,
+    /// A frame was malformed, replayed or not valid in the current state
+    ProtocolViolation = 0x03,
+    /// The peer did not make progress in time
+    Timeout = 0x04,
+}
+
+impl ErrorCode {
+    /// Code reported to the peer for a local protocol error
+    pub fn for_error(error: &WireError) -> Self {
+        match error {
+            WireError::VersionNegotiationFailed { .. }
+            | WireError::FrameProfileMismatch { .. }
+            | WireError::UnsupportedFrameProfile(_) => ErrorCode::VersionMismatch,
+            WireError::AuthenticationFailed | WireError::Crypto(_) => ErrorCode::AuthFailure,
+            _ => ErrorCode::ProtocolViolation,
+        }
+    }
+}
+
+/// Payload of an `Error` frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorFrame {
+    /// Why the sender is closing
+    pub code: ErrorCode,
+}
+
+impl Message for ErrorFrame {
+    fn payload_type() -> PayloadType {
+        PayloadType::Error
+    }
+}
+
+/// What the transport loop should do next
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionAction {
+    /// Write this frame to the link
+    SendFrame(Vec<u8>),
+    /// Drop the link; the connection is closed
+    CloseConnection,
+    /// Nothing to do until the next frame or tick
+    None,
+}
+
+/// Connection lifecycle of a [`WireProtocol`] session
+#[derive(Debug)]
+pub(crate) struct Connection {
+    /// Current state
+    state: ConnectionState,
+    /// Timeouts per state
+    timeouts: ConnectionTimeouts,
+    /// Time the current state was entered
+    entered_ms: u64,
+    /// Time the last frame was accepted
+    last_activity_ms: u64,
+    /// Latest time seen by any entry point
+    now_ms: u64,
+    /// Local negotiation message and whether this side initiates
+    negotiation: Option<(VersionNegotiationMessage, bool)>,
+    /// Sealed frames waiting to be handed to the transport
+    outbox: VecDeque<Vec<u8>>,
+    /// `CloseConnection` has been returned
+    close_reported: bool,
+    /// Code of the `Error` frame received from the peer
+    peer_error: Option<ErrorCode>,
+}
+
+impl Connection {
+    /// Connection that has not been started
+    pub(crate) fn new() -> Self {
+        Self {
+            state: ConnectionState::Handshaking,
+            timeouts: ConnectionTimeouts::default(),
+            entered_ms: 0,
+            last_activity_ms: 0,
+            now_ms: 0,
+            negotiation: None,
+            outbox: VecDeque::new(),
+            close_reported: false,
+            peer_error: None,
+        }
+    }
+
+    /// Record `now_ms`, ignoring a clock that went backwards
+    fn observe(&mut self, now_ms: u64) {
+        self.now_ms = self.now_ms.max(now_ms);
+    }
+
+    /// Enter `state` at the latest observed time
+    fn enter(&mut self, state: ConnectionState) {
+        self.state = state;
+        self.entered_ms = self.now_ms;
+        self.last_activity_ms = self.now_ms;
+    }
+
+    /// Deadline of the current state, if it has one
+    fn deadline(&self) -> Option<u64> {
+        match self.state {
+            ConnectionState::Handshaking => Some(self.entered_ms + self.timeouts.handshake_ms),
+            ConnectionState::Established => Some(self.last_activity_ms + self.timeouts.idle_ms),
+            ConnectionState::Closing => Some(self.entered_ms + self.timeouts.closing_ms),
+            ConnectionState::Closed => None,
+        }
+    }
+}
+
+impl WireProtocol {
+    /// Use `timeouts` for the connection lifecycle
+    pub fn with_connection_timeouts(mut self, timeouts: ConnectionTimeouts) -> Self {
+        self.connection.timeouts = timeouts;
+        self
+    }
+
+    /// Start the connection lifecycle in `Handshaking` at `now_ms`
+    ///
+    /// `local` is this side's negotiation message. The initiator queues its
+    /// offer immediately; the responder waits for the peer's offer.
+    ///
+    /// # Errors
+    ///
+    /// Errors from building the initiator's offer frame.
+    pub fn start_connection(
+        &mut self,
+        local: VersionNegotiationMessage,
+        initiator: bool,
+        now_ms: u64,
+    ) -> Result<()> {
+        self.connection.observe(now_ms);
+        self.connection.enter(ConnectionState::Handshaking);
+        if initiator {
+            let offer = self.offer_negotiation(&local)?;
+            self.connection.outbox.push_back(offer);
+        }
+        self.connection.negotiation = Some((local, initiator));
+        Ok(())
+    }
+
+    /// Current lifecycle state
+    pub fn connection_state(&self) -> ConnectionState {
+        self.connection.state
+    }
+
+    /// Type byte for the next chaff frame
+    ///
+    /// Chaff carries the type of the last application frame sent, so it
+    /// blends into the traffic around it. A connection that was started
+    /// only accepts negotiation and `Error` frames while handshaking and
+    /// closing, and chaff cannot pass as either, so none is sent then.
+    /// Sessions that never started the lifecycle are always eligible.
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::UnexpectedFrame` while handshaking or closing
+    /// - `WireError::ConnectionClosed` once closed
+    pub(crate) fn chaff_payload_type(&self) -> Result<PayloadType> {
+        let state = self.connection.state;
+        let started = self.connection.negotiation.is_some();
+        match state {
+            ConnectionState::Established => Ok(self.last_sent_type),
+            ConnectionState::Handshaking if !started => Ok(self.last_sent_type),
+            ConnectionState::Closed => Err(WireError::ConnectionClosed),
+            _ => Err(WireError::UnexpectedFrame {
+                payload_type: PayloadType::Chaff.to_byte(),
+                state,
+            }),
+        }
+    }
+
+    /// Code of the `Error` frame the peer closed with, if any
+    pub fn peer_error(&self) -> Option<ErrorCode> {
+        self.connection.peer_error
+    }
+
+    /// Begin a graceful close at `now_ms`
+    ///
+    /// Queues an `Error` frame with [`ErrorCode::Normal`] and moves to
+    /// `Closing`. Does nothing if the connection is already closing.
+    pub fn close(&mut self, now_ms: u64) {
+        self.connection.observe(now_ms);
+        if matches!(
+            self.connection.state,
+            ConnectionState::Handshaking | ConnectionState::Established
+        ) {
+            self.fail(ErrorCode::Normal);
+        }
+    }
+
+    /// Process one received frame at `now_ms`
+    ///
+    /// Returns the decrypted application payload, or `None` for frames the
+    /// lifecycle consumes itself (negotiation, `Error`, chaff, frames
+    /// dropped while closing).
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::ConnectionClosed` if the connection is closed
+    /// - `WireError::UnexpectedFrame` if the frame type is not valid in the
+    ///   current state
+    /// - Any error from opening the frame or negotiating
+    ///
+    /// On every error except `ConnectionClosed` the connection has moved to
+    /// `Closing` with an [`ErrorFrame`] queued.
+    pub fn handle_frame(
+        &mut self,
+        frame_bytes: &[u8],
+        now_ms: u64,
+    ) -> Result<Option<(PayloadType, Vec<u8>)>> {
+        self.connection.observe(now_ms);
+        let state = self.connection.state;
+        if state == ConnectionState::Closed {
+            return Err(WireError::ConnectionClosed);
+        }
+
+        let result = self.dispatch_frame(state, frame_bytes);
+        match &result {
+            Ok(_) => self.connection.last_activity_ms = self.connection.now_ms,
+            // Nothing more to tell a peer we are already closing on
+            Err(_) if state == ConnectionState::Closing => {}
+            Err(error) => self.fail(ErrorCode::for_error(error)),
+        }
+        result
+    }
+
+    /// Advance timeouts to `now_ms` and return the next action
+    ///
+    /// Call until it returns [`ConnectionAction::None`]. Queued frames come
+    /// out first, then `CloseConnection` once the connection is closed.
+    pub fn tick(&mut self, now_ms: u64) -> ConnectionAction {
+        self.connection.observe(now_ms);
+        if self
+            .connection
+            .deadline()
+            .is_some_and(|deadline| self.connection.now_ms >= deadline)
+        {
+            match self.connection.state {
+                ConnectionState::Closing => self.connection.enter(ConnectionState::Closed),
+                _ => self.fail(ErrorCode::Timeout),
+            }
+        }
+
+        if let Some(frame) = self.connection.outbox.pop_front() {
+            return ConnectionAction::SendFrame(frame);
+        }
+        if self.connection.state == ConnectionState::Closed && !self.connection.close_reported {
+            self.connection.close_reported = true;
+            return ConnectionAction::CloseConnection;
+        }
+        ConnectionAction::None
+    }
+
+    /// Check the frame type against `state` and process the frame
+    fn dispatch_frame(
+        &mut self,
+        state: ConnectionState,
+        frame_bytes: &[u8],
+    ) -> Result<Option<(PayloadType, Vec<u8>)>> {
+        let payload_type = Self::peek_payload_type(frame_bytes)?;
+        if !state.accepts(payload_type) {
+            if state == ConnectionState::Closing {
+                return Ok(None);
+            }
+            return Err(WireError::UnexpectedFrame {
+                payload_type: payload_type.to_byte(),
+                state,
+            });
+        }
+
+        if payload_type == PayloadType::VersionNegotiation {
+            self.negotiate(frame_bytes)?;
+            return Ok(None);
+        }
+
+        match self.receive(frame_bytes)? {
+            Some((PayloadType::Error, body)) => {
+                let error = ErrorFrame::deserialize_message(&body)?;
+                self.on_peer_error(error.code);
+                Ok(None)
+            }
+            other => Ok(other),
+        }
+    }
+
+    /// Payload type from the plaintext frame header
+    ///
+    /// Both frame layouts start with nonce, epoch and payload type, so this
+    /// works before the session version is known.
+    fn peek_payload_type(frame_bytes: &[u8]) -> Result<PayloadType> {
+        let profile = FrameProfile::for_frame_size(frame_bytes.len())
+            .ok_or(WireError::InvalidFrameSize(frame_bytes.len()))?;
+        SealedFrame::parse(frame_bytes, profile)?.payload_type()
+    }
+
+    /// Run this side's half of the negotiation and enter `Established`
+    fn negotiate(&mut self, frame_bytes: &[u8]) -> Result<()> {
+        let (local, initiator) =
+            self.connection
+                .negotiation
+                .clone()
+                .ok_or(WireError::UnexpectedFrame {
+                    payload_type: PayloadType::VersionNegotiation.to_byte(),
+                    state: ConnectionState::Handshaking,
+                })?;
+
+        if initiator {
+            self.complete_negotiation(frame_bytes, &local)?;
+        } else {
+            let reply = self.respond_to_negotiation(frame_bytes, &local)?;
+            self.connection.outbox.push_back(reply);
+        }
+        self.connection.enter(ConnectionState::Established);
+        Ok(())
+    }
+
+    /// The peer sent an `Error` frame
+    fn on_peer_error(&mut self, code: ErrorCode) {
+        self.connection.peer_error = Some(code);
+        if code == ErrorCode::Normal && self.connection.state != ConnectionState::Closing {
+            // Answer a graceful close so the peer can finish its own
+            self.queue_error_frame(ErrorCode::Normal);
+        }
+        self.connection.enter(ConnectionState::Closed);
+    }
+
+    /// Move to `Closing` with an `Error` frame carrying `code` queued
+    fn fail(&mut self, code: ErrorCode) {
+        self.queue_error_frame(code);
+        self.connection.enter(ConnectionState::Closing);
+    }
+
+    /// Seal an `Error` frame into the outbox
+    ///
+    /// If sealing fails the peer learns nothing; the local state change
+    /// still happens and the closing timeout tears the link down.
+    fn queue_error_frame(&mut self, code: ErrorCode) {
+        let frame = ErrorFrame { code }
+            .serialize_message()
+            .and_then(|body| self.send_message(PayloadType::Error, body, self.current_epoch()));
+        if let Ok(frame) = frame {
+            self.connection.outbox.push_back(frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::aead::XChaCha20Key;
+    use crate::sync::chaff::ChaffGenerator;
+    use crate::sync::version::ProtocolVersion;
+
+    const TIMEOUTS: ConnectionTimeouts = ConnectionTimeouts {
+        handshake_ms: 1_000,
+        idle_ms: 10_000,
+        closing_ms: 500,
+    };
+
+    fn local() -> VersionNegotiationMessage {
+        VersionNegotiationMessage::default_with_version(ProtocolVersion::current())
+    }
+
+    fn pair() -> (WireProtocol, WireProtocol) {
+        let key = XChaCha20Key::generate();
+        (
+            WireProtocol::new(key.clone()).with_connection_timeouts(TIMEOUTS),
+            WireProtocol::new(key).with_connection_timeouts(TIMEOUTS),
+        )
+    }
+
+    /// Drain `from`'s actions at `now_ms`, delivering frames to `to`
+    fn pump(from: &mut WireProtocol, to: &mut WireProtocol, now_ms: u64) -> Vec<String> {
+        let mut log = Vec::new();
+        loop {
+            match from.tick(now_ms) {
+                ConnectionAction::SendFrame(frame) => {
+                    log.push("send".to_string());
+                    let _ = to.handle_frame(&frame, now_ms);
+                }
+                ConnectionAction::CloseConnection => log.push("close".to_string()),
+                ConnectionAction::None => return log,
+            }
+        }
+    }
+
+    fn established_pair() -> (WireProtocol, WireProtocol) {
+        let (mut alice, mut bob) = pair();
+        let local = local();
+        alice.start_connection(local.clone(), true, 0).unwrap();
+        bob.start_connection(local, false, 0).unwrap();
+        pump(&mut alice, &mut bob, 10);
+        pump(&mut bob, &mut alice, 20);
+        (alice, bob)
+    }
+
+    #[test]
+    fn test_negotiation_establishes_both_sides() {
+        let (alice, bob) = established_pair();
+        assert_eq!(alice.connection_state(), ConnectionState::Established);
+        assert_eq!(bob.connection_state(), ConnectionState::Established);
+    }
+
+    #[test]
+    fn test_handshake_timeout() {
+        let (mut alice, _) = pair();
+        alice.start_connection(local(), true, 100).unwrap();
+        assert!(matches!(alice.tick(100), ConnectionAction::SendFrame(_)));
+        assert_eq!(alice.tick(1_099), ConnectionAction::None);
+        assert_eq!(alice.connection_state(), ConnectionState::Handshaking);
+
+        // Deadline reached: error frame out, then closing times out too
+        assert!(matches!(alice.tick(1_100), ConnectionAction::SendFrame(_)));
+        assert_eq!(alice.connection_state(), ConnectionState::Closing);
+        assert_eq!(alice.tick(1_599), ConnectionAction::None);
+        assert_eq!(alice.tick(1_600), ConnectionAction::CloseConnection);
+        assert_eq!(alice.connection_state(), ConnectionState::Closed);
+        assert_eq!(alice.tick(5_000), ConnectionAction::None);
+    }
+
+    #[test]
+    fn test_idle_timeout_sends_timeout_code() {
+        let (mut alice, mut bob) = established_pair();
+        let frame = alice
+            .send_message(PayloadType::Data, b"x".to_vec(), 0)
+            .unwrap();
+        bob.handle_frame(&frame, 5_000).unwrap();
+
+        assert_eq!(bob.tick(14_999), ConnectionAction::None);
+        let ConnectionAction::SendFrame(error) = bob.tick(15_000) else {
+            panic!("expected a timeout error frame");
+        };
+        alice.handle_frame(&error, 15_000).unwrap();
+        assert_eq!(alice.peer_error(), Some(ErrorCode::Timeout));
+        assert_eq!(alice.connection_state(), ConnectionState::Closed);
+    }
+
+    #[test]
+    fn test_invalid_frame_in_established() {
+        let (mut alice, mut bob) = established_pair();
+        let renegotiate = alice.offer_negotiation(&local()).unwrap();
+
+        let result = bob.handle_frame(&renegotiate, 30);
+        assert!(matches!(
+            result,
+            Err(WireError::UnexpectedFrame {
+                state: ConnectionState::Established,
+                ..
+            })
+        ));
+        assert_eq!(bob.connection_state(), ConnectionState::Closing);
+
+        let ConnectionAction::SendFrame(error) = bob.tick(30) else {
+            panic!("expected an error frame");
+        };
+        alice.handle_frame(&error, 30).unwrap();
+        assert_eq!(alice.peer_error(), Some(ErrorCode::ProtocolViolation));
+        assert_eq!(alice.connection_state(), ConnectionState::Closed);
+    }
+
+    #[test]
+    fn test_application_frame_during_handshake_is_rejected() {
+        let (mut alice, mut bob) = pair();
+        bob.start_connection(local(), false, 0).unwrap();
+        let frame = alice
+            .send_message(PayloadType::Data, b"early".to_vec(), 0)
+            .unwrap();
+        assert!(bob.handle_frame(&frame, 1).is_err());
+        assert_eq!(bob.connection_state(), ConnectionState::Closing);
+    }
+
+    #[test]
+    fn test_graceful_close_handshake() {
+        let (mut alice, mut bob) = established_pair();
+        alice.close(100);
+        assert_eq!(alice.connection_state(), ConnectionState::Closing);
+
+        // Alice's close reaches Bob: Bob answers and closes
+        assert_eq!(pump(&mut alice, &mut bob, 100), ["send"]);
+        assert_eq!(bob.connection_state(), ConnectionState::Closed);
+        assert_eq!(bob.peer_error(), Some(ErrorCode::Normal));
+
+        // Bob's answer goes out and Bob drops the link; Alice closes on
+        // receiving the answer without answering again
+        assert_eq!(pump(&mut bob, &mut alice, 110), ["send", "close"]);
+        assert_eq!(alice.connection_state(), ConnectionState::Closed);
+        assert_eq!(pump(&mut alice, &mut bob, 120), ["close"]);
+
+        let late = bob.send_message(PayloadType::Data, vec![1], 0).unwrap();
+        assert!(matches!(
+            alice.handle_frame(&late, 130),
+            Err(WireError::ConnectionClosed)
+        ));
+    }
+
+    #[test]
+    fn test_frames_other_than_error_dropped_while_closing() {
+        let (mut alice, mut bob) = established_pair();
+        alice.close(100);
+        let data = bob.send_message(PayloadType::Data, vec![1], 0).unwrap();
+        assert!(alice.handle_frame(&data, 110).unwrap().is_none());
+        assert_eq!(alice.connection_state(), ConnectionState::Closing);
+    }
+
+    #[test]
+    fn test_chaff_imitates_last_application_type() {
+        let (mut alice, mut bob) = established_pair();
+        let mut generator = ChaffGenerator::new();
+
+        let chaff = alice.send_chaff(&mut generator).unwrap();
+        assert_eq!(
+            WireProtocol::peek_payload_type(&chaff).unwrap(),
+            PayloadType::EpochSync
+        );
+
+        let data = alice.send_message(PayloadType::Data, vec![1], 0).unwrap();
+        bob.handle_frame(&data, 30).unwrap();
+        let chaff = alice.send_chaff(&mut generator).unwrap();
+        assert_eq!(
+            WireProtocol::peek_payload_type(&chaff).unwrap(),
+            PayloadType::Data
+        );
+        assert!(bob.handle_frame(&chaff, 40).unwrap().is_none());
+        assert_eq!(bob.connection_state(), ConnectionState::Established);
+    }
+
+    #[test]
+    fn test_no_chaff_while_handshaking_or_closed() {
+        let (mut alice, mut bob) = pair();
+        let mut generator = ChaffGenerator::new();
+        alice.start_connection(local(), true, 0).unwrap();
+        assert!(matches!(
+            alice.send_chaff(&mut generator),
+            Err(WireError::UnexpectedFrame {
+                state: ConnectionState::Handshaking,
+                ..
+            })
+        ));
+
+        bob.start_connection(local(), false, 0).unwrap();
+        pump(&mut alice, &mut bob, 10);
+        pump(&mut bob, &mut alice, 20);
+        alice.close(30);
+        pump(&mut alice, &mut bob, 30);
+        pump(&mut bob, &mut alice, 40);
+        assert_eq!(alice.connection_state(), ConnectionState::Closed);
+        assert!(matches!(
+            alice.send_chaff(&mut generator),
+            Err(WireError::ConnectionClosed)
+        ));
+    }
+
+    #[test]
+    fn test_scripted_sequence_is_deterministic() {
+        fn run() -> Vec<String> {
+            let (mut alice, mut bob) = pair();
+            let local = local();
+            alice.start_connection(local.clone(), true, 0).unwrap();
+            bob.start_connection(local, false, 0).unwrap();
+
+            let mut log = Vec::new();
+            for now in [0, 200, 400, 5_000, 10_400, 10_450, 11_000] {
+                if now == 5_000 {
+                    alice.close(now);
+                }
+                for entry in pump(&mut alice, &mut bob, now) {
+                    log.push(format!("{now} alice {entry}"));
+                }
+                for entry in pump(&mut bob, &mut alice, now) {
+                    log.push(format!("{now} bob {entry}"));
+                }
+                log.push(format!(
+                    "{now} {:?} {:?}",
+                    alice.connection_state(),
+                    bob.connection_state()
+                ));
+            }
+            log
+        }
+
+        let first = run();
+        assert_eq!(first, run());
+        assert_eq!(
+            first,
+            [
+                "0 alice send",
+                "0 bob send",
+                "0 Established Established",
+                "200 Established Established",
+                "400 Established Established",
+                "5000 alice send",
+                "5000 bob send",
+                "5000 bob close",
+                "5000 Closed Closed",
+                "10400 alice close",
+                "10400 Closed Closed",
+                "10450 Closed Closed",
+                "11000 Closed Closed",
+            ]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! # Epoch Sync
+//!
+//! Network-level driver for Invariant #1 coordination: each device
+//! announces its current epoch and the Merkle root of its header set in an
+//! `EpochSync` frame ([`EpochSyncMessage`]), and the receiver compares the
+//! announcement against its own state with
+//! [`WireProtocol::handle_epoch_sync`].
+//!
+//! ## Outcomes
+//!
+//! | Peer epoch | Header root | Result |
+//! |------------|-------------|--------|
+//! | lower | any | `WireError::EpochRegression` |
+//! | equal | equal | [`EpochSyncAction::UpToDate`] |
+//! | equal | different | [`EpochSyncAction::Diverged`] |
+//! | higher | any | [`EpochSyncAction::NeedsUpgrade`] |
+//!
+//! `NeedsUpgrade` is the trigger for fetching and applying the peer's
+//! rekey; `Diverged` means both devices are on the same epoch but disagree
+//! about its header set, so the caller should diff the trees
+//! ([`MerkleTree::diff`]) before trusting either side. Handling an
+//! announcement never moves the session epoch; only completing the
+//! upgrade does.
+
+use crate::crypto::hash::HashOutput;
+use crate::models::device::{DeviceHeader, DeviceId};
+use crate::models::merkle::MerkleTree;
+use crate::sync::codec::{Message, PayloadType};
+use crate::sync::wire::WireProtocol;
+use crate::sync::{Result, WireError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// "Here is my current epoch" announcement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochSyncMessage {
+    /// Announcing device
+    pub device_id: DeviceId,
+    /// Epoch version the device is on
+    pub epoch_version: u32,
+    /// Merkle root of the device's header set at that epoch
+    pub header_root: [u8; 32],
+}
+
+impl EpochSyncMessage {
+    /// Announcement of `epoch_version` with the root of `tree`
+    pub fn new(device_id: DeviceId, epoch_version: u32, tree: &MerkleTree) -> Self {
+        Self {
+            device_id,
+            epoch_version,
+            header_root: *tree.root().as_bytes(),
+        }
+    }
+
+    /// Announcement of `epoch_version` with the root of `headers`
+    pub fn for_headers(
+        device_id: DeviceId,
+        epoch_version: u32,
+        headers: &HashMap<DeviceId, DeviceHeader>,
+    ) -> Self {
+        Self::new(device_id, epoch_version, &MerkleTree::from_headers(headers))
+    }
+
+    /// Header root as a hash
+    pub fn header_root(&self) -> HashOutput {
+        HashOutput::from_bytes(self.header_root)
+    }
+}
+
+impl Message for EpochSyncMessage {
+    fn payload_type() -> PayloadType {
+        PayloadType::EpochSync
+    }
+}
+
+/// What the receiver of an [`EpochSyncMessage`] should do next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpochSyncAction {
+    /// Same epoch and header set; nothing to do
+    UpToDate,
+    /// Peer is on a later epoch; run the upgrade to it
+    NeedsUpgrade {
+        /// Epoch the peer announced
+        target_epoch: u32,
+    },
+    /// Same epoch but a different header set; reconcile before proceeding
+    Diverged,
+}
+
+impl WireProtocol {
+    /// Seal `message` into an `EpochSync` frame at the session epoch
+    pub fn send_epoch_sync(&mut self, message: &EpochSyncMessage) -> Result<Vec<u8>> {
+        let body = message.serialize_message()?;
+        let epoch = self.current_epoch();
+        self.send_message(PayloadType::EpochSync, body, epoch)
+    }
+
+    /// Compare a peer's epoch announcement against the local state
+    ///
+    /// `local_root` is the Merkle root of this device's header set at the
+    /// session epoch.
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::EpochRegression` if the peer announces an epoch older
+    ///   than the session epoch (Invariant #1)
+    pub fn handle_epoch_sync(
+        &self,
+        message: &EpochSyncMessage,
+        local_root: &HashOutput,
+    ) -> Result<EpochSyncAction> {
+        let current = self.current_epoch();
+        if message.epoch_version < current {
+            return Err(WireError::EpochRegression {
+                current,
+                attempted: message.epoch_version,
+            });
+        }
+        if message.epoch_version > current {
+            return Ok(EpochSyncAction::NeedsUpgrade {
+                target_epoch: message.epoch_version,
+            });
+        }
+        if message.header_root == *local_root.as_bytes() {
+            Ok(EpochSyncAction::UpToDate)
+        } else {
+            Ok(EpochSyncAction::Diverged)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::aead::XChaCha20Key;
+    use crate::crypto::hash::hash;
+
+    fn session_pair(epoch: u32) -> (WireProtocol, WireProtocol) {
+        let key = XChaCha20Key::generate();
+        let mut a = WireProtocol::new(key.clone());
+        let mut b = WireProtocol::new(key);
+        a.accept_frame(epoch).unwrap();
+        b.accept_frame(epoch).unwrap();
+        (a, b)
+    }
+
+    fn announcement(epoch_version: u32, root: &HashOutput) -> EpochSyncMessage {
+        EpochSyncMessage {
+            device_id: DeviceId([7u8; 16]),
+            epoch_version,
+            header_root: *root.as_bytes(),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_through_frame() {
+        let (mut alice, mut bob) = session_pair(3);
+        let message = EpochSyncMessage::for_headers(DeviceId([1u8; 16]), 3, &HashMap::new());
+        assert_eq!(
+            message.header_root(),
+            MerkleTree::from_headers(&HashMap::new()).root()
+        );
+
+        let frame = alice.send_epoch_sync(&message).unwrap();
+        let (payload_type, body) = bob.receive_message(&frame).unwrap();
+        assert_eq!(payload_type, PayloadType::EpochSync);
+        assert_eq!(
+            EpochSyncMessage::deserialize_message(&body).unwrap(),
+            message
+        );
+    }
+
+    #[test]
+    fn test_same_epoch_same_root_is_up_to_date() {
+        let (session, _) = session_pair(5);
+        let root = hash(b"headers");
+        let action = session
+            .handle_epoch_sync(&announcement(5, &root), &root)
+            .unwrap();
+        assert_eq!(action, EpochSyncAction::UpToDate);
+    }
+
+    #[test]
+    fn test_later_epoch_needs_upgrade() {
+        let (session, _) = session_pair(5);
+        let action = session
+            .handle_epoch_sync(&announcement(7, &hash(b"theirs")), &hash(b"ours"))
+            .unwrap();
+        assert_eq!(action, EpochSyncAction::NeedsUpgrade { target_epoch: 7 });
+        assert_eq!(session.current_epoch(), 5);
+    }
+
+    #[test]
+    fn test_same_epoch_different_root_diverged() {
+        let (session, _) = session_pair(5);
+        let action = session
+            .handle_epoch_sync(&announcement(5, &hash(b"theirs")), &hash(b"ours"))
+            .unwrap();
+        assert_eq!(action, EpochSyncAction::Diverged);
+    }
+
+    #[test]
+    fn test_earlier_epoch_is_regression() {
+        let (session, _) = session_pair(5);
+        let root = hash(b"headers");
+        let result = session.handle_epoch_sync(&announcement(4, &root), &root);
+        assert!(matches!(
+            result,
+            Err(WireError::EpochRegression {
+                current: 5,
+                attempted: 4
+            })
+        ));
+    }
+}
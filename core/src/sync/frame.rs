@@ -1,6 +1,7 @@
 //! # Aeternum Wire Frame
 //!
-//! Fixed-size (8192-byte) frame format for network transmission.
+//! Fixed-size frame format for network transmission. Frames are 8192 bytes
+//! in the default profile and 512 bytes in the BLE profile.
 //!
 //! ## Frame Format
 //!
@@ -12,7 +13,7 @@
 //! +---------------------------------------------------+
 //! | Padding (Variable)              | Auth Tag (16 B)   |
 //! +-------------------------------+-------------------+
-//! Total: 8192 bytes (fixed)
+//! Total: FrameProfile::frame_size bytes (fixed per session)
 //! ```
 //!
 //! ## Security Properties
@@ -21,18 +22,68 @@
 //! - **AEAD encryption** provides confidentiality + integrity
 //! - **Epoch in clear** enables routing without decryption
 //!
+//! ## Sealing
+//!
+//! [`WireFrame::seal`] / [`WireFrame::open`] produce and consume complete
+//! default-profile frames, and `WireProtocol` seals every frame of a session
+//! negotiated at [`SEALED_FRAME_VERSION`] or later the same way. The body is
+//! encrypted with XChaCha20-Poly1305 under a random nonce; the cleartext
+//! header (nonce, epoch, payload type) is bound as associated data, so
+//! rerouting a frame to another epoch or relabelling its type breaks the
+//! tag. The body length travels inside the ciphertext together with `OsRng`
+//! padding, so nothing in clear reveals how long the body is:
+//!
+//! ```text
+//! +--------------+---------------+-----------------------------------+
+//! | Nonce (24 B) | Epoch ID (4 B)| Payload Type (1 B)                |
+//! +--------------+---------------+-----------------------------------+
+//! | AEAD( Body Len (2 B) || Body || Padding )  | Auth Tag (16 B)     |
+//! +--------------------------------------------+---------------------+
+//! ```
+//!
+//! Sessions negotiated at 1.0 keep the [`WireFrame`] layout above, with the
+//! padding filled from the same CSPRNG.
+//!
+//! [`padding_entropy_ok`] checks in tests that a frame's length-hiding
+//! region is indistinguishable from random bytes.
+//!
+//! ## Fragmentation
+//!
+//! BLE characteristic writes carry 20-512 bytes, so a sealed frame is sent
+//! as numbered fragments. [`FrameFragmenter`] prefixes each with
+//!
+//! ```text
+//! +------------------+--------------+--------------+------------------+
+//! | Frame ID (4 B)   | Index (2 B)  | Total (2 B)  | Frame bytes      |
+//! +------------------+--------------+--------------+------------------+
+//! ```
+//!
+//! and [`FrameReassembler`] rebuilds the frame from fragments arriving in
+//! any order, dropping duplicates and frames that stay incomplete past a
+//! timeout. Fragment headers are not authenticated; the reassembled frame
+//! is, by [`WireFrame::open`].
+//!
 //! ## Invariant Compliance
 //!
 //! - **Invariant #1**: Epoch field is validated for monotonicity
 //! - **Invariant #4**: Veto messages use highest priority routing
 
-use crate::sync::{Result, WireError, AUTH_TAG_SIZE, FRAME_SIZE, MAX_BODY_SIZE, NONCE_SIZE};
+use crate::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce};
+use crate::sync::codec::PayloadType;
+use crate::sync::version::ProtocolVersion;
+use crate::sync::{
+    FrameProfile, Result, WireError, AUTH_TAG_SIZE, FRAME_SIZE, NONCE_SIZE, PROFILE_DEFAULT,
+};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
-/// Aeternum Wire Frame - fixed 8192-byte network packet
+/// Aeternum Wire Frame - fixed-size network packet
 ///
 /// All network traffic must use this format to prevent traffic fingerprinting.
+/// The size is set by the session's [`FrameProfile`] (8192 bytes by default).
 #[derive(Debug, Clone, Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct WireFrame {
     /// XChaCha20-Poly1305 nonce (must be unique per message)
@@ -58,7 +109,7 @@ pub struct WireFrame {
 }
 
 impl WireFrame {
-    /// Create a new Wire Frame with automatic padding
+    /// Create a new Wire Frame with automatic padding (default profile)
     ///
     /// # Arguments
     ///
@@ -82,9 +133,33 @@ impl WireFrame {
         payload_type: u8,
         encrypted_body: Vec<u8>,
         auth_tag: [u8; AUTH_TAG_SIZE],
+    ) -> Result<Self> {
+        Self::new_with_profile(
+            nonce,
+            epoch,
+            payload_type,
+            encrypted_body,
+            auth_tag,
+            PROFILE_DEFAULT,
+        )
+    }
+
+    /// Create a new Wire Frame padded to the size of `profile`
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidFrameSize` if the body exceeds
+    /// `profile.max_body_size`.
+    pub fn new_with_profile(
+        nonce: [u8; NONCE_SIZE],
+        epoch: u32,
+        payload_type: u8,
+        encrypted_body: Vec<u8>,
+        auth_tag: [u8; AUTH_TAG_SIZE],
+        profile: FrameProfile,
     ) -> Result<Self> {
         // Validate body size
-        if encrypted_body.len() > MAX_BODY_SIZE {
+        if encrypted_body.len() > profile.max_body_size {
             return Err(WireError::InvalidFrameSize(
                 NONCE_SIZE + 4 + 1 + 2 + encrypted_body.len() + AUTH_TAG_SIZE,
             ));
@@ -94,7 +169,7 @@ impl WireFrame {
 
         // Calculate required padding
         let current_size = NONCE_SIZE + 4 + 1 + 2 + encrypted_body.len() + AUTH_TAG_SIZE;
-        let padding_size = FRAME_SIZE - current_size;
+        let padding_size = profile.frame_size - current_size;
 
         Ok(Self {
             nonce,
@@ -111,13 +186,14 @@ impl WireFrame {
     ///
     /// # Returns
     ///
-    /// Returns exactly `FRAME_SIZE` (8192) bytes.
+    /// Returns exactly `frame_size` bytes of the frame's profile.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics if internal size calculation is incorrect (should never happen).
+    /// Returns `WireError::InvalidFrameSize` if the frame does not match
+    /// any supported profile.
     pub fn serialize(&self) -> Result<Vec<u8>> {
-        let mut buffer = Vec::with_capacity(FRAME_SIZE);
+        let mut buffer = Vec::with_capacity(self.frame_size());
 
         // Write fixed fields
         buffer.extend_from_slice(&self.nonce);
@@ -128,15 +204,15 @@ impl WireFrame {
         buffer.extend_from_slice(&self.padding);
         buffer.extend_from_slice(&self.auth_tag);
 
-        // Validate size is exactly FRAME_SIZE
-        if buffer.len() != FRAME_SIZE {
+        // Validate size is exactly one profile's frame size
+        if FrameProfile::for_frame_size(buffer.len()).is_none() {
             return Err(WireError::InvalidFrameSize(buffer.len()));
         }
 
         Ok(buffer)
     }
 
-    /// Deserialize frame from bytes
+    /// Deserialize frame from bytes (default profile)
     ///
     /// # Arguments
     ///
@@ -150,7 +226,32 @@ impl WireFrame {
     ///
     /// Returns `WireError::InvalidFrameSize` if data is not exactly 8192 bytes.
     pub fn deserialize(data: &[u8]) -> Result<Self> {
-        if data.len() != FRAME_SIZE {
+        Self::deserialize_with_profile(data, PROFILE_DEFAULT)
+    }
+
+    /// Deserialize a frame that must belong to `profile`
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::FrameProfileMismatch` if data is a frame of another
+    ///   supported profile
+    /// - `WireError::InvalidFrameSize` if data has any other wrong size, or
+    ///   the body length field exceeds `profile.max_body_size`
+    /// - `WireError::UnsupportedFrameProfile` if `profile` is not one of the
+    ///   supported profiles
+    pub fn deserialize_with_profile(data: &[u8], profile: FrameProfile) -> Result<Self> {
+        // A forged profile could place the body or padding past the frame end
+        if !profile.is_supported() {
+            return Err(WireError::UnsupportedFrameProfile(profile.frame_size));
+        }
+
+        if data.len() != profile.frame_size {
+            if FrameProfile::for_frame_size(data.len()).is_some() {
+                return Err(WireError::FrameProfileMismatch {
+                    expected: profile.frame_size,
+                    actual: data.len(),
+                });
+            }
             return Err(WireError::InvalidFrameSize(data.len()));
         }
 
@@ -175,6 +276,12 @@ impl WireFrame {
         let body_len = u16::from_be_bytes(body_len_bytes.try_into().unwrap());
         pos += 2;
 
+        if body_len as usize > profile.max_body_size {
+            return Err(WireError::InvalidFrameSize(
+                NONCE_SIZE + 4 + 1 + 2 + body_len as usize + AUTH_TAG_SIZE,
+            ));
+        }
+
         // Parse encrypted body using body_len
         let body_end = pos + body_len as usize;
         let encrypted_body = data[pos..body_end].to_vec();
@@ -182,7 +289,7 @@ impl WireFrame {
 
         // Calculate padding size
         let padding_start = pos;
-        let padding_end = FRAME_SIZE - AUTH_TAG_SIZE;
+        let padding_end = profile.frame_size - AUTH_TAG_SIZE;
         let padding = data[padding_start..padding_end].to_vec();
         pos = padding_end;
 
@@ -201,7 +308,7 @@ impl WireFrame {
         })
     }
 
-    /// Validate frame integrity
+    /// Validate frame integrity (default profile)
     ///
     /// Checks:
     /// - Size is exactly 8192 bytes
@@ -211,9 +318,19 @@ impl WireFrame {
     ///
     /// Returns `WireError::InvalidFrameSize` if validation fails.
     pub fn validate(&self) -> Result<()> {
+        self.validate_with_profile(PROFILE_DEFAULT)
+    }
+
+    /// Validate frame integrity against `profile`
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidFrameSize` if the padding does not bring
+    /// the frame to `profile.frame_size` or `body_len` is inconsistent.
+    pub fn validate_with_profile(&self, profile: FrameProfile) -> Result<()> {
         // Recalculate expected padding size
         let current_size = NONCE_SIZE + 4 + 1 + 2 + self.encrypted_body.len() + AUTH_TAG_SIZE;
-        let expected_padding = FRAME_SIZE - current_size;
+        let expected_padding = profile.frame_size.saturating_sub(current_size);
 
         if self.padding.len() != expected_padding {
             return Err(WireError::InvalidFrameSize(
@@ -231,6 +348,11 @@ impl WireFrame {
         Ok(())
     }
 
+    /// Total serialized size of this frame in bytes
+    pub fn frame_size(&self) -> usize {
+        NONCE_SIZE + 4 + 1 + 2 + self.encrypted_body.len() + self.padding.len() + AUTH_TAG_SIZE
+    }
+
     /// Get the nonce value
     pub fn nonce(&self) -> &[u8; NONCE_SIZE] {
         &self.nonce
@@ -245,11 +367,633 @@ impl WireFrame {
     pub fn payload_type(&self) -> u8 {
         self.payload_type
     }
+
+    /// Encrypt `body` into a complete default-profile frame
+    ///
+    /// Uses a random nonce, authenticates the cleartext header
+    /// (nonce, epoch, payload type) as associated data, and encrypts the
+    /// body length, body and `OsRng` padding together, so the ciphertext
+    /// reveals nothing about how much of the frame is body.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Session key
+    /// * `epoch` - Current logical epoch (sent in clear)
+    /// * `payload_type` - Message type (sent in clear, authenticated)
+    /// * `body` - Plaintext body (at most `MAX_BODY_SIZE` bytes)
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::BodyTooLarge` if the body exceeds `MAX_BODY_SIZE`
+    /// - `WireError::Crypto` if encryption fails
+    pub fn seal(
+        key: &XChaCha20Key,
+        epoch: u32,
+        payload_type: PayloadType,
+        body: &[u8],
+    ) -> Result<[u8; FRAME_SIZE]> {
+        let sealed = Self::seal_padded(
+            &AeadCipher::new(key),
+            &mut OsRng,
+            epoch,
+            payload_type,
+            body,
+            PROFILE_DEFAULT,
+            &[],
+        )?;
+
+        let mut frame = [0u8; FRAME_SIZE];
+        frame.copy_from_slice(&sealed);
+        Ok(frame)
+    }
+
+    /// Encrypt `body` into a sealed-layout frame of `profile`
+    ///
+    /// `rng` supplies the nonce and the padding. `extra_aad` is
+    /// authenticated after the cleartext header. [`seal`](Self::seal) is
+    /// this with `OsRng`, the default profile and no extra AAD;
+    /// `WireProtocol` passes the key generation as `extra_aad`.
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::BodyTooLarge` if the body exceeds `profile.max_body_size`
+    /// - `WireError::Crypto` if encryption fails
+    pub(crate) fn seal_padded(
+        cipher: &AeadCipher,
+        rng: &mut (impl RngCore + CryptoRng),
+        epoch: u32,
+        payload_type: PayloadType,
+        body: &[u8],
+        profile: FrameProfile,
+        extra_aad: &[u8],
+    ) -> Result<Vec<u8>> {
+        // Checked before encrypting; an oversized body would underflow the padding
+        if body.len() > profile.max_body_size {
+            return Err(WireError::BodyTooLarge {
+                size: body.len(),
+                max: profile.max_body_size,
+            });
+        }
+
+        let mut nonce = [0u8; NONCE_SIZE];
+        rng.fill_bytes(&mut nonce);
+        let header = Self::header_aad(&nonce, epoch, payload_type.to_byte());
+        let aad = [&header[..], extra_aad].concat();
+
+        // body_len (2 B BE) || body || padding
+        let mut plaintext = vec![0u8; 2 + profile.max_body_size];
+        plaintext[..2].copy_from_slice(&(body.len() as u16).to_be_bytes());
+        plaintext[2..2 + body.len()].copy_from_slice(body);
+        rng.fill_bytes(&mut plaintext[2 + body.len()..]);
+
+        let ciphertext = cipher.encrypt(&XChaCha20Nonce::from_bytes(nonce), &plaintext, Some(&aad));
+        plaintext.zeroize();
+
+        let mut frame = Vec::with_capacity(profile.frame_size);
+        frame.extend_from_slice(&header);
+        frame.extend_from_slice(&ciphertext?);
+        Ok(frame)
+    }
+
+    /// Decrypt a frame produced by [`seal`](Self::seal)
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Session key
+    /// * `data` - Exactly `FRAME_SIZE` bytes from the network
+    ///
+    /// # Returns
+    ///
+    /// The payload type and the decrypted body.
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::InvalidFrameSize` if data is not exactly `FRAME_SIZE`
+    ///   bytes or its encrypted body length is out of range
+    /// - `WireError::AuthenticationFailed` if the key is wrong or any byte
+    ///   of the frame was modified
+    /// - `WireError::InvalidPayloadType` if the authenticated type byte is
+    ///   not a known payload type
+    pub fn open(key: &XChaCha20Key, data: &[u8]) -> Result<(PayloadType, Vec<u8>)> {
+        if data.len() != FRAME_SIZE {
+            return Err(WireError::InvalidFrameSize(data.len()));
+        }
+
+        let sealed = SealedFrame::parse(data, PROFILE_DEFAULT)?;
+        let plaintext = sealed
+            .decrypt(&AeadCipher::new(key), &[])
+            .map_err(|_| WireError::AuthenticationFailed)?;
+        let body = SealedFrame::unpad(plaintext)?;
+
+        Ok((sealed.payload_type()?, body))
+    }
+
+    /// Associated data for a sealed frame: nonce || epoch (BE) || payload type
+    fn header_aad(
+        nonce: &[u8; NONCE_SIZE],
+        epoch: u32,
+        payload_type: u8,
+    ) -> [u8; SEALED_HEADER_SIZE] {
+        let mut aad = [0u8; SEALED_HEADER_SIZE];
+        aad[..NONCE_SIZE].copy_from_slice(nonce);
+        aad[NONCE_SIZE..NONCE_SIZE + 4].copy_from_slice(&epoch.to_be_bytes());
+        aad[NONCE_SIZE + 4] = payload_type;
+        aad
+    }
+}
+
+/// A received frame in the sealed layout, split at the cleartext header
+///
+/// Parsing only checks the size; the header can be read for replay and
+/// epoch checks before [`decrypt`](Self::decrypt) authenticates it.
+pub(crate) struct SealedFrame<'a> {
+    header: &'a [u8],
+    ciphertext: &'a [u8],
+}
+
+impl<'a> SealedFrame<'a> {
+    /// Split `data`, which must be a frame of `profile`
+    ///
+    /// # Errors
+    ///
+    /// Same as [`WireFrame::deserialize_with_profile`].
+    pub(crate) fn parse(data: &'a [u8], profile: FrameProfile) -> Result<Self> {
+        if !profile.is_supported() {
+            return Err(WireError::UnsupportedFrameProfile(profile.frame_size));
+        }
+
+        if data.len() != profile.frame_size {
+            if FrameProfile::for_frame_size(data.len()).is_some() {
+                return Err(WireError::FrameProfileMismatch {
+                    expected: profile.frame_size,
+                    actual: data.len(),
+                });
+            }
+            return Err(WireError::InvalidFrameSize(data.len()));
+        }
+
+        let (header, ciphertext) = data.split_at(SEALED_HEADER_SIZE);
+        Ok(Self { header, ciphertext })
+    }
+
+    /// Cleartext nonce
+    pub(crate) fn nonce(&self) -> [u8; NONCE_SIZE] {
+        let mut nonce = [0u8; NONCE_SIZE];
+        nonce.copy_from_slice(&self.header[..NONCE_SIZE]);
+        nonce
+    }
+
+    /// Cleartext epoch
+    pub(crate) fn epoch(&self) -> u32 {
+        let mut epoch = [0u8; 4];
+        epoch.copy_from_slice(&self.header[NONCE_SIZE..NONCE_SIZE + 4]);
+        u32::from_be_bytes(epoch)
+    }
+
+    /// Cleartext payload type
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidPayloadType` for an unknown type byte.
+    pub(crate) fn payload_type(&self) -> Result<PayloadType> {
+        PayloadType::from_byte(self.header[NONCE_SIZE + 4])
+    }
+
+    /// Authenticate the header plus `extra_aad` and decrypt the padded body
+    ///
+    /// Pass the result to [`unpad`](Self::unpad).
+    pub(crate) fn decrypt(
+        &self,
+        cipher: &AeadCipher,
+        extra_aad: &[u8],
+    ) -> crate::crypto::error::Result<Vec<u8>> {
+        let aad = [self.header, extra_aad].concat();
+        cipher.decrypt(
+            &XChaCha20Nonce::from_bytes(self.nonce()),
+            self.ciphertext,
+            Some(&aad),
+        )
+    }
+
+    /// Strip the length prefix and padding from a decrypted frame
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidFrameSize` if the encrypted body length
+    /// is out of range.
+    pub(crate) fn unpad(mut plaintext: Vec<u8>) -> Result<Vec<u8>> {
+        let body_len = match plaintext.get(..2) {
+            Some(len) => u16::from_be_bytes([len[0], len[1]]) as usize,
+            None => {
+                return Err(WireError::InvalidFrameSize(
+                    SEALED_HEADER_SIZE + AUTH_TAG_SIZE,
+                ))
+            }
+        };
+        let body = plaintext.get(2..2 + body_len).map(<[u8]>::to_vec);
+        plaintext.zeroize();
+        body.ok_or(WireError::InvalidFrameSize(
+            SEALED_HEADER_SIZE + 2 + body_len + AUTH_TAG_SIZE,
+        ))
+    }
+}
+
+/// First protocol version whose sessions send sealed-layout frames
+///
+/// Sessions negotiated at an older version keep the layout with a
+/// cleartext body length, so 1.0 peers can still read them.
+pub const SEALED_FRAME_VERSION: ProtocolVersion = ProtocolVersion::new(1, 1);
+
+/// Cleartext header of a sealed frame: nonce || epoch (4 B) || payload type (1 B)
+const SEALED_HEADER_SIZE: usize = NONCE_SIZE + 5;
+
+/// Chi-square bound over 256 byte values (255 degrees of freedom) above
+/// which a byte distribution is not accepted as uniform
+///
+/// Roughly six standard deviations above the mean, so CSPRNG output fails
+/// with negligible probability while zero or repeating padding always does.
+const PADDING_CHI_SQUARE_LIMIT: f64 = 400.0;
+
+/// Whether the length-hiding region of `frame` looks uniformly random
+///
+/// Checks everything between the cleartext header and the auth tag, which
+/// covers the padding. For a [`WireFrame::seal`]ed frame that region is
+/// ciphertext and always passes; a frame padded with zeros or another
+/// predictable filler fails, since its body length could be estimated from
+/// how well the frame compresses. Both the byte values and the differences
+/// between neighbouring bytes must be uniform, so counters and other
+/// sequences with a flat histogram fail too. Frames of an unsupported size
+/// fail.
+///
+/// Intended for tests and diagnostics, not as a per-frame runtime check.
+pub fn padding_entropy_ok(frame: &[u8]) -> bool {
+    if FrameProfile::for_frame_size(frame.len()).is_none() {
+        return false;
+    }
+    let region = &frame[SEALED_HEADER_SIZE..frame.len() - AUTH_TAG_SIZE];
+    let deltas = region.windows(2).map(|pair| pair[1].wrapping_sub(pair[0]));
+
+    chi_square(region.iter().copied()) < PADDING_CHI_SQUARE_LIMIT
+        && chi_square(deltas) < PADDING_CHI_SQUARE_LIMIT
+}
+
+/// Pearson chi-square statistic of `bytes` against the uniform distribution
+fn chi_square(bytes: impl Iterator<Item = u8>) -> f64 {
+    let mut counts = [0usize; 256];
+    let mut total = 0usize;
+    for byte in bytes {
+        counts[byte as usize] += 1;
+        total += 1;
+    }
+    let expected = total as f64 / 256.0;
+    counts
+        .iter()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum()
+}
+
+/// Size of the header prepended to every fragment:
+/// frame_id (4 B) || index (2 B) || total (2 B), all big-endian
+pub const FRAGMENT_HEADER_SIZE: usize = 8;
+
+/// Smallest MTU a [`FrameFragmenter`] accepts (BLE 4.0 ATT payload)
+pub const MIN_FRAGMENT_MTU: usize = 20;
+
+/// Default time an incomplete frame is buffered before it is dropped
+pub const DEFAULT_REASSEMBLY_TIMEOUT_MS: u64 = 10_000;
+
+/// Default number of incomplete frames buffered at once
+pub const DEFAULT_MAX_PENDING_FRAMES: usize = 8;
+
+/// Header of one fragment of a sealed frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FragmentHeader {
+    /// Identifies the frame the fragment belongs to
+    pub frame_id: u32,
+    /// Position of the fragment, starting at 0
+    pub index: u16,
+    /// Number of fragments the frame was split into
+    pub total: u16,
+}
+
+impl FragmentHeader {
+    /// Encode the header as it is sent on the wire
+    pub fn to_bytes(&self) -> [u8; FRAGMENT_HEADER_SIZE] {
+        let mut bytes = [0u8; FRAGMENT_HEADER_SIZE];
+        bytes[..4].copy_from_slice(&self.frame_id.to_be_bytes());
+        bytes[4..6].copy_from_slice(&self.index.to_be_bytes());
+        bytes[6..].copy_from_slice(&self.total.to_be_bytes());
+        bytes
+    }
+
+    /// Split a received fragment into its header and payload
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidFragment` if the fragment is shorter than
+    /// the header, carries no payload, or has `total == 0` or
+    /// `index >= total`.
+    pub fn parse(fragment: &[u8]) -> Result<(Self, &[u8])> {
+        if fragment.len() <= FRAGMENT_HEADER_SIZE {
+            return Err(WireError::InvalidFragment(format!(
+                "fragment of {} bytes has no payload",
+                fragment.len()
+            )));
+        }
+
+        let (header, payload) = fragment.split_at(FRAGMENT_HEADER_SIZE);
+        let header = Self {
+            frame_id: u32::from_be_bytes(header[..4].try_into().unwrap()),
+            index: u16::from_be_bytes(header[4..6].try_into().unwrap()),
+            total: u16::from_be_bytes(header[6..].try_into().unwrap()),
+        };
+        if header.index >= header.total {
+            return Err(WireError::InvalidFragment(format!(
+                "index {} out of range for {} fragments",
+                header.index, header.total
+            )));
+        }
+        Ok((header, payload))
+    }
+}
+
+/// Splits sealed frames into MTU-sized fragments for BLE writes
+///
+/// A BLE characteristic write carries 20-512 bytes, far below the
+/// 8192-byte default frame. Each frame is cut into numbered fragments of at
+/// most `mtu` bytes, header included; [`FrameReassembler`] on the other
+/// side puts them back together in any delivery order.
+///
+/// Fragments are not authenticated on their own. A corrupted or forged
+/// fragment yields a frame that fails [`WireFrame::open`].
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::sync::frame::{FrameFragmenter, FrameReassembler};
+///
+/// let mut fragmenter = FrameFragmenter::new(185).unwrap();
+/// let mut reassembler = FrameReassembler::new();
+///
+/// let frame = vec![0xAB; 8192];
+/// let mut complete = None;
+/// for fragment in fragmenter.fragment(&frame).unwrap().iter().rev() {
+///     complete = reassembler.push(fragment, 0).unwrap();
+/// }
+/// assert_eq!(complete, Some(frame));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameFragmenter {
+    /// Maximum fragment size, header included
+    mtu: usize,
+    /// ID given to the next frame
+    next_frame_id: u32,
+}
+
+impl FrameFragmenter {
+    /// Create a fragmenter for links carrying `mtu` bytes per write
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidFragment` if `mtu` is below
+    /// [`MIN_FRAGMENT_MTU`].
+    pub fn new(mtu: usize) -> Result<Self> {
+        if mtu < MIN_FRAGMENT_MTU {
+            return Err(WireError::InvalidFragment(format!(
+                "MTU {} below minimum {}",
+                mtu, MIN_FRAGMENT_MTU
+            )));
+        }
+        Ok(Self {
+            mtu,
+            next_frame_id: rand::random(),
+        })
+    }
+
+    /// Maximum payload carried by one fragment
+    pub fn payload_size(&self) -> usize {
+        self.mtu - FRAGMENT_HEADER_SIZE
+    }
+
+    /// Split `frame` into fragments, in index order
+    ///
+    /// Each call uses a fresh frame ID, so fragments of consecutive frames
+    /// can be interleaved on the link.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidFragment` if `frame` is empty or needs
+    /// more than `u16::MAX` fragments.
+    pub fn fragment(&mut self, frame: &[u8]) -> Result<Vec<Vec<u8>>> {
+        if frame.is_empty() {
+            return Err(WireError::InvalidFragment("empty frame".to_string()));
+        }
+        let total = u16::try_from(frame.len().div_ceil(self.payload_size())).map_err(|_| {
+            WireError::InvalidFragment(format!(
+                "frame of {} bytes needs too many fragments",
+                frame.len()
+            ))
+        })?;
+
+        let frame_id = self.next_frame_id;
+        self.next_frame_id = self.next_frame_id.wrapping_add(1);
+
+        Ok(frame
+            .chunks(self.payload_size())
+            .enumerate()
+            .map(|(index, chunk)| {
+                let header = FragmentHeader {
+                    frame_id,
+                    index: index as u16,
+                    total,
+                };
+                let mut fragment = Vec::with_capacity(FRAGMENT_HEADER_SIZE + chunk.len());
+                fragment.extend_from_slice(&header.to_bytes());
+                fragment.extend_from_slice(chunk);
+                fragment
+            })
+            .collect())
+    }
+}
+
+/// Fragments received so far for one frame
+#[derive(Debug)]
+struct PendingFrame {
+    /// Fragment payloads by index
+    fragments: Vec<Option<Vec<u8>>>,
+    /// Number of fragments received
+    received: usize,
+    /// Payload bytes received
+    bytes: usize,
+    /// When the first fragment arrived (Unix milliseconds)
+    first_seen_ms: u64,
+}
+
+/// Buffers fragments from a [`FrameFragmenter`] and yields complete frames
+///
+/// Fragments may arrive in any order and more than once; duplicates are
+/// ignored. A frame still incomplete [`timeout_ms`](Self::with_limits)
+/// after its first fragment is dropped. Memory is bounded: a frame may not
+/// exceed [`FRAME_SIZE`] bytes, and once `max_pending` frames are
+/// incomplete the oldest is evicted to make room.
+#[derive(Debug)]
+pub struct FrameReassembler {
+    /// Incomplete frames by frame ID
+    pending: HashMap<u32, PendingFrame>,
+    /// How long an incomplete frame is kept
+    timeout_ms: u64,
+    /// Maximum number of incomplete frames
+    max_pending: usize,
+}
+
+impl Default for FrameReassembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameReassembler {
+    /// Create a reassembler with the default timeout and buffer limit
+    pub fn new() -> Self {
+        Self::with_limits(DEFAULT_REASSEMBLY_TIMEOUT_MS, DEFAULT_MAX_PENDING_FRAMES)
+    }
+
+    /// Create a reassembler with a custom timeout and buffer limit
+    pub fn with_limits(timeout_ms: u64, max_pending: usize) -> Self {
+        Self {
+            pending: HashMap::new(),
+            timeout_ms,
+            max_pending: max_pending.max(1),
+        }
+    }
+
+    /// Add a received fragment
+    ///
+    /// Expires timed-out frames first. Returns the complete frame once its
+    /// last missing fragment arrives.
+    ///
+    /// # Arguments
+    ///
+    /// * `fragment` - Fragment as received, header included
+    /// * `now_ms` - Current time (Unix milliseconds)
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::InvalidFragment` if the fragment is malformed,
+    /// disagrees with earlier fragments of its frame on `total`, or would
+    /// grow the frame past `FRAME_SIZE` bytes. The frame's other fragments
+    /// stay buffered.
+    pub fn push(&mut self, fragment: &[u8], now_ms: u64) -> Result<Option<Vec<u8>>> {
+        let (header, payload) = FragmentHeader::parse(fragment)?;
+        self.expire(now_ms);
+
+        if !self.pending.contains_key(&header.frame_id) {
+            if header.total as usize > FRAME_SIZE {
+                return Err(WireError::InvalidFragment(format!(
+                    "{} fragments exceed the frame size",
+                    header.total
+                )));
+            }
+            if self.pending.len() >= self.max_pending {
+                self.evict_oldest();
+            }
+        }
+
+        let pending = self
+            .pending
+            .entry(header.frame_id)
+            .or_insert_with(|| PendingFrame {
+                fragments: vec![None; header.total as usize],
+                received: 0,
+                bytes: 0,
+                first_seen_ms: now_ms,
+            });
+
+        if pending.fragments.len() != header.total as usize {
+            return Err(WireError::InvalidFragment(format!(
+                "frame {} announced {} fragments, now {}",
+                header.frame_id,
+                pending.fragments.len(),
+                header.total
+            )));
+        }
+        let slot = &mut pending.fragments[header.index as usize];
+        if slot.is_some() {
+            return Ok(None);
+        }
+        if pending.bytes + payload.len() > FRAME_SIZE {
+            return Err(WireError::InvalidFragment(format!(
+                "frame {} exceeds {} bytes",
+                header.frame_id, FRAME_SIZE
+            )));
+        }
+
+        *slot = Some(payload.to_vec());
+        pending.received += 1;
+        pending.bytes += payload.len();
+        if pending.received < pending.fragments.len() {
+            return Ok(None);
+        }
+
+        let pending = self.pending.remove(&header.frame_id).unwrap();
+        Ok(Some(
+            pending.fragments.into_iter().flatten().flatten().collect(),
+        ))
+    }
+
+    /// Indices still missing for `frame_id`, or `None` if the frame is not
+    /// being buffered
+    pub fn missing(&self, frame_id: u32) -> Option<Vec<u16>> {
+        self.pending.get(&frame_id).map(|pending| {
+            pending
+                .fragments
+                .iter()
+                .enumerate()
+                .filter(|(_, fragment)| fragment.is_none())
+                .map(|(index, _)| index as u16)
+                .collect()
+        })
+    }
+
+    /// Drop frames incomplete for longer than the timeout
+    ///
+    /// Returns the IDs of the dropped frames.
+    pub fn expire(&mut self, now_ms: u64) -> Vec<u32> {
+        let timeout_ms = self.timeout_ms;
+        let expired: Vec<u32> = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| now_ms.saturating_sub(pending.first_seen_ms) >= timeout_ms)
+            .map(|(frame_id, _)| *frame_id)
+            .collect();
+        for frame_id in &expired {
+            self.pending.remove(frame_id);
+        }
+        expired
+    }
+
+    /// Number of incomplete frames buffered
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Drop the incomplete frame whose first fragment arrived earliest
+    fn evict_oldest(&mut self) {
+        if let Some(oldest) = self
+            .pending
+            .iter()
+            .min_by_key(|(_, pending)| pending.first_seen_ms)
+            .map(|(frame_id, _)| *frame_id)
+        {
+            self.pending.remove(&oldest);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sync::{BLE_FRAME_SIZE, MAX_BODY_SIZE, PROFILE_BLE};
 
     #[test]
     fn test_wire_frame_creation() {
@@ -374,4 +1118,369 @@ mod tests {
         assert_eq!(frame_clone.nonce, [0u8; NONCE_SIZE]);
         assert_eq!(frame_clone.auth_tag, [0u8; AUTH_TAG_SIZE]);
     }
+
+    #[test]
+    fn test_wire_frame_ble_profile_roundtrip() {
+        let body = vec![7u8; PROFILE_BLE.max_body_size];
+        let frame = WireFrame::new_with_profile(
+            [3u8; NONCE_SIZE],
+            9,
+            0x02,
+            body.clone(),
+            [4u8; AUTH_TAG_SIZE],
+            PROFILE_BLE,
+        )
+        .expect("Failed to create BLE frame");
+        assert!(frame.validate_with_profile(PROFILE_BLE).is_ok());
+
+        let serialized = frame.serialize().unwrap();
+        assert_eq!(serialized.len(), 512);
+
+        let deserialized = WireFrame::deserialize_with_profile(&serialized, PROFILE_BLE).unwrap();
+        assert_eq!(deserialized.epoch, 9);
+        assert_eq!(deserialized.encrypted_body, body);
+        assert_eq!(deserialized.auth_tag, [4u8; AUTH_TAG_SIZE]);
+
+        // Too large for BLE although it fits the default profile
+        let result = WireFrame::new_with_profile(
+            [0u8; NONCE_SIZE],
+            1,
+            0,
+            vec![0u8; PROFILE_BLE.max_body_size + 1],
+            [0u8; AUTH_TAG_SIZE],
+            PROFILE_BLE,
+        );
+        assert!(matches!(result, Err(WireError::InvalidFrameSize(_))));
+    }
+
+    #[test]
+    fn test_wire_frame_profile_mixing_rejected() {
+        let default_frame =
+            WireFrame::new([0u8; NONCE_SIZE], 1, 0, vec![1, 2, 3], [0u8; AUTH_TAG_SIZE])
+                .unwrap()
+                .serialize()
+                .unwrap();
+        assert_eq!(default_frame.len(), FRAME_SIZE);
+
+        let result = WireFrame::deserialize_with_profile(&default_frame, PROFILE_BLE);
+        assert!(matches!(
+            result,
+            Err(WireError::FrameProfileMismatch {
+                expected: 512,
+                actual: 8192
+            })
+        ));
+
+        let ble_frame = WireFrame::new_with_profile(
+            [0u8; NONCE_SIZE],
+            1,
+            0,
+            vec![1, 2, 3],
+            [0u8; AUTH_TAG_SIZE],
+            PROFILE_BLE,
+        )
+        .unwrap();
+        assert!(ble_frame.validate().is_err());
+        assert!(matches!(
+            WireFrame::deserialize(&ble_frame.serialize().unwrap()),
+            Err(WireError::FrameProfileMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_wire_frame_body_len_beyond_profile_rejected() {
+        let mut data = WireFrame::new_with_profile(
+            [0u8; NONCE_SIZE],
+            1,
+            0,
+            vec![],
+            [0u8; AUTH_TAG_SIZE],
+            PROFILE_BLE,
+        )
+        .unwrap()
+        .serialize()
+        .unwrap();
+        // Forge a body length that only fits the default profile
+        data[NONCE_SIZE + 5..NONCE_SIZE + 7].copy_from_slice(&(MAX_BODY_SIZE as u16).to_be_bytes());
+
+        assert!(matches!(
+            WireFrame::deserialize_with_profile(&data, PROFILE_BLE),
+            Err(WireError::InvalidFrameSize(_))
+        ));
+    }
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let key = XChaCha20Key::generate();
+        let body = b"sync payload";
+
+        let sealed = WireFrame::seal(&key, 7, PayloadType::EpochSync, body).unwrap();
+        assert_eq!(sealed.len(), FRAME_SIZE);
+        assert_eq!(&sealed[NONCE_SIZE..NONCE_SIZE + 4], &7u32.to_be_bytes());
+
+        let (payload_type, opened) = WireFrame::open(&key, &sealed).unwrap();
+        assert_eq!(payload_type, PayloadType::EpochSync);
+        assert_eq!(opened, body);
+    }
+
+    #[test]
+    fn test_seal_body_at_limit() {
+        // A maximum-size body fills the frame exactly
+        let key = XChaCha20Key::generate();
+        let full = vec![0x42u8; MAX_BODY_SIZE];
+        let sealed = WireFrame::seal(&key, 7, PayloadType::Veto, &full).unwrap();
+        assert_eq!(WireFrame::open(&key, &sealed).unwrap().1, full);
+    }
+
+    #[test]
+    fn test_seal_body_one_over_limit() {
+        let key = XChaCha20Key::generate();
+        let result = WireFrame::seal(&key, 7, PayloadType::Veto, &[0u8; MAX_BODY_SIZE + 1]);
+        assert!(matches!(
+            result,
+            Err(WireError::BodyTooLarge { size, max: MAX_BODY_SIZE }) if size == MAX_BODY_SIZE + 1
+        ));
+    }
+
+    #[test]
+    fn test_open_with_wrong_key_fails() {
+        let sealed =
+            WireFrame::seal(&XChaCha20Key::generate(), 1, PayloadType::EpochSync, b"x").unwrap();
+
+        assert!(matches!(
+            WireFrame::open(&XChaCha20Key::generate(), &sealed),
+            Err(WireError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_open_rejects_modified_header_and_wrong_size() {
+        let key = XChaCha20Key::generate();
+        let sealed = WireFrame::seal(&key, 1, PayloadType::EpochSync, b"body").unwrap();
+
+        // Epoch and payload type are authenticated
+        let mut rerouted = sealed;
+        rerouted[NONCE_SIZE + 3] ^= 1;
+        assert!(matches!(
+            WireFrame::open(&key, &rerouted),
+            Err(WireError::AuthenticationFailed)
+        ));
+
+        let mut relabelled = sealed;
+        relabelled[NONCE_SIZE + 4] = PayloadType::Veto.to_byte();
+        assert!(matches!(
+            WireFrame::open(&key, &relabelled),
+            Err(WireError::AuthenticationFailed)
+        ));
+
+        assert!(matches!(
+            WireFrame::open(&key, &sealed[..FRAME_SIZE - 1]),
+            Err(WireError::InvalidFrameSize(len)) if len == FRAME_SIZE - 1
+        ));
+    }
+
+    #[test]
+    fn test_seal_padding_is_random() {
+        let key = XChaCha20Key::generate();
+        let a = WireFrame::seal(&key, 1, PayloadType::EpochSync, b"same").unwrap();
+        let b = WireFrame::seal(&key, 1, PayloadType::EpochSync, b"same").unwrap();
+
+        // No 16-byte block of the padding repeats between the two frames
+        let padding = SEALED_HEADER_SIZE + 2 + 4..FRAME_SIZE - AUTH_TAG_SIZE;
+        assert!(a[padding.clone()]
+            .chunks(16)
+            .zip(b[padding].chunks(16))
+            .all(|(x, y)| x != y));
+        assert!(padding_entropy_ok(&a));
+        assert!(padding_entropy_ok(&b));
+    }
+
+    #[test]
+    fn test_seal_hides_body_length() {
+        let key = XChaCha20Key::generate();
+        for body in [&[][..], b"short", &[0u8; MAX_BODY_SIZE]] {
+            let sealed = WireFrame::seal(&key, 1, PayloadType::EpochSync, body).unwrap();
+            assert!(padding_entropy_ok(&sealed));
+            assert_eq!(WireFrame::open(&key, &sealed).unwrap().1, body);
+        }
+    }
+
+    #[test]
+    fn test_padding_entropy_rejects_predictable_padding() {
+        let zero_padded = WireFrame::new(
+            [0u8; NONCE_SIZE],
+            1,
+            0,
+            vec![0xAB; 64],
+            [0u8; AUTH_TAG_SIZE],
+        )
+        .unwrap();
+        assert!(!padding_entropy_ok(&zero_padded.serialize().unwrap()));
+
+        let repeating: Vec<u8> = (0..FRAME_SIZE).map(|i| (i % 256) as u8).collect();
+        assert!(!padding_entropy_ok(&repeating));
+        assert!(!padding_entropy_ok(&[0u8; 100]));
+    }
+
+    #[test]
+    fn test_wire_frame_forged_profile_rejected() {
+        let forged = FrameProfile {
+            frame_size: BLE_FRAME_SIZE,
+            max_body_size: MAX_BODY_SIZE,
+        };
+        let mut data = vec![0u8; BLE_FRAME_SIZE];
+        data[NONCE_SIZE + 5..NONCE_SIZE + 7].copy_from_slice(&(MAX_BODY_SIZE as u16).to_be_bytes());
+
+        assert!(matches!(
+            WireFrame::deserialize_with_profile(&data, forged),
+            Err(WireError::UnsupportedFrameProfile(BLE_FRAME_SIZE))
+        ));
+    }
+
+    fn fragments_of(frame: &[u8], mtu: usize) -> Vec<Vec<u8>> {
+        FrameFragmenter::new(mtu).unwrap().fragment(frame).unwrap()
+    }
+
+    #[test]
+    fn test_fragment_reassemble_in_order() {
+        let key = XChaCha20Key::generate();
+        let frame = WireFrame::seal(&key, 3, PayloadType::EpochSync, b"epoch sync").unwrap();
+        let fragments = fragments_of(&frame, 185);
+        assert_eq!(
+            fragments.len(),
+            FRAME_SIZE.div_ceil(185 - FRAGMENT_HEADER_SIZE)
+        );
+        assert!(fragments.iter().all(|fragment| fragment.len() <= 185));
+
+        let mut reassembler = FrameReassembler::new();
+        let (last, rest) = fragments.split_last().unwrap();
+        for fragment in rest {
+            assert_eq!(reassembler.push(fragment, 0).unwrap(), None);
+        }
+        let complete = reassembler.push(last, 0).unwrap().unwrap();
+        assert_eq!(complete, frame);
+        assert_eq!(reassembler.pending_count(), 0);
+
+        let (payload_type, body) = WireFrame::open(&key, &complete).unwrap();
+        assert_eq!(payload_type, PayloadType::EpochSync);
+        assert_eq!(body, b"epoch sync");
+    }
+
+    #[test]
+    fn test_fragment_reassemble_out_of_order_and_interleaved() {
+        let mut fragmenter = FrameFragmenter::new(MIN_FRAGMENT_MTU).unwrap();
+        let a: Vec<u8> = (0..500u16).map(|i| i as u8).collect();
+        let b = vec![0xBB; 300];
+        let fragments_a = fragmenter.fragment(&a).unwrap();
+        let fragments_b = fragmenter.fragment(&b).unwrap();
+
+        // Reverse each frame and interleave the two
+        let mut reassembler = FrameReassembler::new();
+        let mut complete = Vec::new();
+        for (x, y) in fragments_a.iter().rev().zip(fragments_b.iter().rev()) {
+            complete.extend(reassembler.push(x, 0).unwrap());
+            complete.extend(reassembler.push(y, 0).unwrap());
+        }
+        for x in fragments_a.iter().rev().skip(fragments_b.len()) {
+            complete.extend(reassembler.push(x, 0).unwrap());
+        }
+        assert_eq!(complete, vec![b, a]);
+    }
+
+    #[test]
+    fn test_duplicate_fragments_ignored() {
+        let frame = vec![0x5A; 1_000];
+        let fragments = fragments_of(&frame, 100);
+
+        let mut reassembler = FrameReassembler::new();
+        for fragment in &fragments[..fragments.len() - 1] {
+            assert_eq!(reassembler.push(fragment, 0).unwrap(), None);
+            assert_eq!(reassembler.push(fragment, 0).unwrap(), None);
+        }
+        assert_eq!(
+            reassembler.push(fragments.last().unwrap(), 0).unwrap(),
+            Some(frame)
+        );
+    }
+
+    #[test]
+    fn test_missing_fragment_reported_and_expired() {
+        let fragments = fragments_of(&[0x11; 1_000], 100);
+        let (header, _) = FragmentHeader::parse(&fragments[0]).unwrap();
+
+        let mut reassembler = FrameReassembler::with_limits(5_000, 4);
+        for (i, fragment) in fragments.iter().enumerate() {
+            if i != 3 && i != 7 {
+                assert_eq!(reassembler.push(fragment, 1_000).unwrap(), None);
+            }
+        }
+        assert_eq!(reassembler.missing(header.frame_id), Some(vec![3, 7]));
+
+        assert!(reassembler.expire(5_999).is_empty());
+        assert_eq!(reassembler.expire(6_000), vec![header.frame_id]);
+        assert_eq!(reassembler.missing(header.frame_id), None);
+
+        // A late fragment starts over rather than completing a stale frame
+        assert_eq!(reassembler.push(&fragments[3], 6_001).unwrap(), None);
+        assert_eq!(
+            reassembler.missing(header.frame_id).unwrap().len(),
+            fragments.len() - 1
+        );
+    }
+
+    #[test]
+    fn test_malformed_fragments_rejected() {
+        let fragments = fragments_of(&[0x22; 300], 100);
+        let mut reassembler = FrameReassembler::with_limits(5_000, 2);
+
+        // Header only, no payload
+        assert!(matches!(
+            reassembler.push(&fragments[0][..FRAGMENT_HEADER_SIZE], 0),
+            Err(WireError::InvalidFragment(_))
+        ));
+
+        // index >= total
+        let mut bad_index = fragments[0].clone();
+        bad_index[4..6].copy_from_slice(&9u16.to_be_bytes());
+        assert!(reassembler.push(&bad_index, 0).is_err());
+
+        // total disagreeing with earlier fragments of the frame
+        reassembler.push(&fragments[0], 0).unwrap();
+        let mut bad_total = fragments[1].clone();
+        bad_total[6..8].copy_from_slice(&5u16.to_be_bytes());
+        assert!(reassembler.push(&bad_total, 0).is_err());
+
+        // A frame can never exceed FRAME_SIZE fragments
+        let oversized = FragmentHeader {
+            frame_id: 7,
+            index: 0,
+            total: u16::MAX,
+        };
+        let mut fragment = oversized.to_bytes().to_vec();
+        fragment.push(0);
+        assert!(reassembler.push(&fragment, 0).is_err());
+
+        assert!(FrameFragmenter::new(MIN_FRAGMENT_MTU - 1).is_err());
+        assert!(FrameFragmenter::new(100).unwrap().fragment(&[]).is_err());
+    }
+
+    #[test]
+    fn test_reassembler_evicts_oldest_when_full() {
+        let mut fragmenter = FrameFragmenter::new(100).unwrap();
+        let mut reassembler = FrameReassembler::with_limits(60_000, 2);
+        let frames: Vec<Vec<Vec<u8>>> = (0..3)
+            .map(|_| fragmenter.fragment(&[0x33; 200]).unwrap())
+            .collect();
+        let ids: Vec<u32> = frames
+            .iter()
+            .map(|fragments| FragmentHeader::parse(&fragments[0]).unwrap().0.frame_id)
+            .collect();
+
+        for (t, fragments) in frames.iter().enumerate() {
+            reassembler.push(&fragments[0], t as u64).unwrap();
+        }
+        assert_eq!(reassembler.pending_count(), 2);
+        assert_eq!(reassembler.missing(ids[0]), None);
+        assert!(reassembler.missing(ids[2]).is_some());
+    }
 }
@@ -31,15 +31,70 @@
 //! 2. Decapsulate Kyber shared secret: `ss_KEM = Decapsulate(sk_KEM_A, ct_KEM)`
 //! 3. Derive session key: `K_session = HKDF-SHA256(ss_X25519 || ss_KEM || context_id)`
 //!
+//! ## Three-Message Handshake
+//!
+//! [`Handshake`] wraps the exchange above in a byte-level, three-message
+//! protocol with key confirmation:
+//!
+//! ```text
+//! 1. I -> R  Hello     = version || caps_A || context_id || pk_A || pk_KEM_A
+//! 2. R -> I  Response  = version || caps_B || context_id || pk_B || ct_KEM || confirm_R
+//! 3. I -> R  Finished  = confirm_I
+//! ```
+//!
+//! Both sides feed every handshake message, in order, into a [`Transcript`]
+//! (message 2 without `confirm_R`). They combine the shared secrets with
+//! [`HybridKeyExchange::combine`] and derive directional session keys with
+//! the transcript hash `TH` as the derivation salt, so the keys are
+//! domain-separated per transcript. `confirm_R` and `confirm_I` are keyed
+//! BLAKE3 MACs over `TH`: swapping a version or capability bit, or any
+//! other handshake byte, makes confirmation fail on both sides.
+//!
+//! ## Session Resumption
+//!
+//! A completed handshake also yields a resumption secret. Both sides turn
+//! it into a [`ResumptionTicket`] with [`SessionKeys::resumption_ticket`];
+//! the ticket ID is derived from the secret, so the two copies match
+//! without the secret ever being sent. Reconnecting with a ticket takes a
+//! single round trip:
+//!
+//! ```text
+//! 1. I -> R  Resume    = ticket_id || salt_I || nonce || AEAD(K_chal, challenge)
+//! 2. R -> I  Resumed   = salt_R || nonce || AEAD(K_confirm, challenge, aad = TH)
+//! ```
+//!
+//! `K_chal` is derived from the resumption secret, so a responder that can
+//! open message 1 knows the initiator holds the ticket. The new session
+//! keys are derived from the resumption secret with the transcript hash
+//! over both messages (and so both fresh salts) as salt: no traffic key of
+//! the original session is reused. The responder answers with the challenge
+//! sealed under the new confirmation key, proving it holds the same secret.
+//!
+//! Tickets are single-use: the responder's [`ResumptionTicketStore`]
+//! removes a ticket when it is redeemed and rejects it afterwards. Expired
+//! tickets are refused on both sides; the initiator then falls back to a
+//! full [`Handshake`].
+//!
 //! ## Protocol Version
 //!
 //! This implementation follows [AET-WIRE-SPEC-004](../../../docs/protocols/Sync-Wire-Protocol.md)
 //! Section 2.1: Hybrid Handshake.
 
-use crate::crypto::ecdh::{HybridKeyExchange, X25519KeyPair, X25519PublicKeyBytes, X25519ECDH};
-use crate::crypto::hash::DeriveKey;
+use crate::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce, NONCE_SIZE, TAG_SIZE};
+use crate::crypto::ct::ct_eq;
+use crate::crypto::ecdh::{
+    HybridKeyExchange, HybridSharedSecret, KexTranscript, X25519KeyPair, X25519PublicKeyBytes,
+    X25519ECDH,
+};
+use crate::crypto::hash::{keyed_hash, Blake3Hasher, DeriveKey, HashOutput};
 use crate::crypto::kem::{KyberCipherText, KyberKEM, KyberKeyPair, KyberPublicKeyBytes};
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use crate::crypto::secret::SecretBytes;
+use crate::sync::version::{CapabilityFlags, ProtocolVersion, VersionNegotiation};
+use crate::sync::WireError;
+use rand::RngCore;
+use std::collections::HashMap;
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
 /// Combined public key: X25519 (32 bytes) || Kyber-1024 (1568 bytes) = 1600 bytes
 #[derive(Clone, PartialEq, Eq)]
@@ -102,6 +157,32 @@ pub struct InitiatorHello {
     pub context_id: [u8; 32],
 }
 
+impl InitiatorHello {
+    /// Encoded size: context ID (32) || combined public key (1600)
+    pub const SIZE: usize = 32 + CombinedPublicKey::SIZE;
+
+    /// Serialize to bytes (context ID || X25519 || Kyber)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.context_id);
+        bytes.extend_from_slice(&self.public_key.to_bytes());
+        bytes
+    }
+
+    /// Deserialize from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::SIZE {
+            return None;
+        }
+        let context_id = bytes[..32].try_into().ok()?;
+        let public_key = CombinedPublicKey::from_bytes(&bytes[32..])?;
+        Some(Self {
+            public_key,
+            context_id,
+        })
+    }
+}
+
 /// Responder's handshake response
 pub struct ResponderResponse {
     /// Responder's X25519 public key
@@ -112,6 +193,35 @@ pub struct ResponderResponse {
     pub context_id: [u8; 32],
 }
 
+impl ResponderResponse {
+    /// Encoded size: context ID (32) || X25519 (32) || Kyber ciphertext (1568)
+    pub const SIZE: usize = 32 + 32 + 1568;
+
+    /// Serialize to bytes (context ID || X25519 || Kyber ciphertext)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&self.context_id);
+        bytes.extend_from_slice(self.x25519_pk.as_bytes());
+        bytes.extend_from_slice(self.kyber_ct.as_bytes());
+        bytes
+    }
+
+    /// Deserialize from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::SIZE {
+            return None;
+        }
+        let context_id = bytes[..32].try_into().ok()?;
+        let x25519_pk = X25519PublicKeyBytes::from_bytes(&bytes[32..64]).ok()?;
+        let kyber_ct = KyberCipherText::from_bytes(&bytes[64..]).ok()?;
+        Some(Self {
+            x25519_pk,
+            kyber_ct,
+            context_id,
+        })
+    }
+}
+
 impl Zeroize for ResponderResponse {
     fn zeroize(&mut self) {
         self.context_id.zeroize();
@@ -131,6 +241,34 @@ pub struct SessionKey {
     pub key: [u8; 32],
 }
 
+/// Directional session keys produced by [`Handshake::finalize`]
+pub struct SessionKeys {
+    /// Key for frames this side sends
+    pub send: SessionKey,
+    /// Key for frames this side receives
+    pub recv: SessionKey,
+    /// Capabilities both sides support, authenticated by the transcript
+    pub capabilities: CapabilityFlags,
+    /// Secret for resuming this session, see [`Self::resumption_ticket`]
+    resumption: SecretBytes<32>,
+}
+
+impl SessionKeys {
+    /// Resumption ticket for reconnecting to the same peer
+    ///
+    /// Both sides of a session derive the same ticket ID and secret; only
+    /// the expiry depends on the local clock. The responder keeps its
+    /// ticket in a [`ResumptionTicketStore`], the initiator passes its
+    /// ticket to [`Handshake::resume`].
+    pub fn resumption_ticket(&self, now_ms: u64) -> ResumptionTicket {
+        ResumptionTicket::new(
+            self.resumption.clone(),
+            now_ms.saturating_add(TICKET_LIFETIME_MS),
+            self.capabilities,
+        )
+    }
+}
+
 /// Handshake state machine
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HandshakeState {
@@ -298,6 +436,845 @@ impl HybridHandshake {
     }
 }
 
+// ============================================================================
+// Three-Message Handshake
+// ============================================================================
+
+/// Size of a key confirmation MAC
+const CONFIRM_SIZE: usize = 32;
+
+/// Size of the version and capability prefix of messages 1 and 2
+const PARAMS_SIZE: usize = 3;
+
+/// Running hash of a handshake transcript
+///
+/// Every handshake message is appended in order, each prefixed with its
+/// length so that message boundaries are part of the hash.
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::sync::handshake::Transcript;
+///
+/// let mut a = Transcript::new();
+/// a.append(b"hello").append(b"response");
+///
+/// let mut b = Transcript::new();
+/// b.append(b"hellor").append(b"esponse");
+///
+/// assert_ne!(a.hash(), b.hash());
+/// ```
+#[derive(Clone)]
+pub struct Transcript {
+    /// BLAKE3 state over the messages appended so far
+    hasher: Blake3Hasher,
+}
+
+impl Default for Transcript {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transcript {
+    /// Domain separator hashed before the first message
+    const DOMAIN: &'static [u8] = b"aeternum v5 handshake transcript";
+
+    /// Create an empty transcript
+    pub fn new() -> Self {
+        let mut hasher = Blake3Hasher::new();
+        hasher.update(Self::DOMAIN);
+        Self { hasher }
+    }
+
+    /// Append the next handshake message
+    pub fn append(&mut self, message: &[u8]) -> &mut Self {
+        self.hasher.update(&(message.len() as u64).to_le_bytes());
+        self.hasher.update(message);
+        self
+    }
+
+    /// Hash of the messages appended so far
+    pub fn hash(&self) -> HashOutput {
+        self.hasher.clone().finalize()
+    }
+}
+
+/// Three-message hybrid handshake with key confirmation
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::sync::handshake::Handshake;
+/// use aeternum_core::sync::CapabilityFlags;
+///
+/// let caps = CapabilityFlags::default();
+/// let (initiator, hello) = Handshake::initiate([7u8; 32], caps);
+/// let (responder, response) = Handshake::respond(&hello, caps)?;
+///
+/// let (initiator_keys, finished) = initiator.finalize(&response)?;
+/// let (responder_keys, _) = responder.finalize(&finished.unwrap())?;
+///
+/// assert_eq!(initiator_keys.send.key, responder_keys.recv.key);
+/// assert_eq!(initiator_keys.recv.key, responder_keys.send.key);
+/// # Ok::<(), aeternum_core::sync::WireError>(())
+/// ```
+pub struct Handshake {
+    /// `InitiatorWaiting` or `ResponderResponding`
+    state: HandshakeState,
+
+    /// Context ID from the hello
+    context_id: [u8; 32],
+
+    /// Capabilities this side advertised
+    capabilities: CapabilityFlags,
+
+    /// Initiator: ephemeral keypair
+    keypair: Option<InitiatorKeyPair>,
+
+    /// Messages exchanged so far
+    transcript: Transcript,
+
+    /// Responder: derived keys awaiting the initiator's confirmation
+    derived: Option<DerivedKeys>,
+}
+
+/// Keys derived from a handshake transcript
+struct DerivedKeys {
+    /// Initiator -> responder session key
+    initiator_to_responder: [u8; 32],
+    /// Responder -> initiator session key
+    responder_to_initiator: [u8; 32],
+    /// Key confirmation MAC key
+    confirm_key: [u8; 32],
+    /// Secret for a later resumption of this session
+    resumption: [u8; 32],
+    /// Transcript hash
+    transcript_hash: HashOutput,
+    /// Capabilities both sides advertised
+    capabilities: CapabilityFlags,
+}
+
+impl Zeroize for DerivedKeys {
+    fn zeroize(&mut self) {
+        self.initiator_to_responder.zeroize();
+        self.responder_to_initiator.zeroize();
+        self.confirm_key.zeroize();
+        self.resumption.zeroize();
+    }
+}
+
+impl Drop for DerivedKeys {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl DerivedKeys {
+    /// Domain separation context for handshake key derivation
+    const KDF_CONTEXT: &'static str = "aeternum v5 handshake session-keys";
+
+    /// Domain separation context for resumed-session key derivation
+    const RESUME_KDF_CONTEXT: &'static str = "aeternum v5 resumption session-keys";
+
+    /// Derive session and confirmation keys bound to the transcript hash
+    fn derive(
+        shared: &HybridSharedSecret,
+        transcript_hash: HashOutput,
+        capabilities: CapabilityFlags,
+    ) -> Self {
+        Self::derive_from(
+            &shared.combined,
+            Self::KDF_CONTEXT,
+            transcript_hash,
+            capabilities,
+        )
+    }
+
+    /// Derive keys for a resumed session from the ticket's secret
+    fn derive_resumed(ticket: &ResumptionTicket, transcript_hash: HashOutput) -> Self {
+        Self::derive_from(
+            ticket.resumption_secret.as_bytes(),
+            Self::RESUME_KDF_CONTEXT,
+            transcript_hash,
+            ticket.capabilities,
+        )
+    }
+
+    fn derive_from(
+        ikm: &[u8],
+        context: &'static str,
+        transcript_hash: HashOutput,
+        capabilities: CapabilityFlags,
+    ) -> Self {
+        let okm =
+            Zeroizing::new(DeriveKey::new(transcript_hash.as_bytes(), context).derive(ikm, 128));
+
+        let mut keys = Self {
+            initiator_to_responder: [0u8; 32],
+            responder_to_initiator: [0u8; 32],
+            confirm_key: [0u8; 32],
+            resumption: [0u8; 32],
+            transcript_hash,
+            capabilities,
+        };
+        keys.initiator_to_responder.copy_from_slice(&okm[..32]);
+        keys.responder_to_initiator.copy_from_slice(&okm[32..64]);
+        keys.confirm_key.copy_from_slice(&okm[64..96]);
+        keys.resumption.copy_from_slice(&okm[96..]);
+        keys
+    }
+
+    /// Key confirmation MAC for `label`
+    fn confirm(&self, label: &[u8]) -> HashOutput {
+        let mut input = Vec::with_capacity(label.len() + 32);
+        input.extend_from_slice(label);
+        input.extend_from_slice(self.transcript_hash.as_bytes());
+        keyed_hash(&self.confirm_key, &input)
+    }
+
+    /// Session keys from the point of view of the initiator or responder
+    fn session_keys(&self, initiator: bool) -> SessionKeys {
+        let (send, recv) = if initiator {
+            (self.initiator_to_responder, self.responder_to_initiator)
+        } else {
+            (self.responder_to_initiator, self.initiator_to_responder)
+        };
+        SessionKeys {
+            send: SessionKey { key: send },
+            recv: SessionKey { key: recv },
+            capabilities: self.capabilities,
+            resumption: SecretBytes::new(self.resumption),
+        }
+    }
+}
+
+impl Handshake {
+    /// Confirmation label for the responder's MAC
+    const RESPONDER_CONFIRM: &'static [u8] = b"responder confirm";
+
+    /// Confirmation label for the initiator's MAC
+    const INITIATOR_CONFIRM: &'static [u8] = b"initiator confirm";
+
+    /// Initiator: start a handshake and return message 1 (Hello)
+    ///
+    /// # Arguments
+    ///
+    /// - `context_id`: Unique 32-byte identifier for this handshake
+    /// - `capabilities`: Capabilities this device advertises
+    pub fn initiate(context_id: [u8; 32], capabilities: CapabilityFlags) -> (Self, Vec<u8>) {
+        Self::initiate_with_keypair(
+            context_id,
+            capabilities,
+            HybridHandshake::generate_initiator_keypair(),
+        )
+    }
+
+    /// Initiator: start a handshake with a keypair generated in advance
+    ///
+    /// Used when the public keys were committed to out of band, e.g. in a
+    /// pairing QR code, so the responder can check the hello against them.
+    pub fn initiate_with_keypair(
+        context_id: [u8; 32],
+        capabilities: CapabilityFlags,
+        keypair: InitiatorKeyPair,
+    ) -> (Self, Vec<u8>) {
+        let mut hello = encode_params(capabilities);
+        hello.extend_from_slice(&HybridHandshake::initiate(&keypair, context_id).to_bytes());
+
+        let mut transcript = Transcript::new();
+        transcript.append(&hello);
+
+        let handshake = Self {
+            state: HandshakeState::InitiatorWaiting,
+            context_id,
+            capabilities,
+            keypair: Some(keypair),
+            transcript,
+            derived: None,
+        };
+        (handshake, hello)
+    }
+
+    /// Parse the initiator's public key and context ID from message 1 (Hello)
+    ///
+    /// Lets the responder check the hello against keys committed to out of
+    /// band before answering it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::DeserializationFailed` if `hello` is malformed.
+    pub fn parse_hello(hello: &[u8]) -> Result<InitiatorHello, WireError> {
+        if hello.len() != PARAMS_SIZE + InitiatorHello::SIZE {
+            return Err(WireError::DeserializationFailed(
+                "Malformed handshake hello".to_string(),
+            ));
+        }
+        InitiatorHello::from_bytes(&hello[PARAMS_SIZE..]).ok_or_else(|| {
+            WireError::DeserializationFailed("Malformed handshake hello".to_string())
+        })
+    }
+
+    /// Responder: answer message 1 and return message 2 (Response)
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::DeserializationFailed` if `hello` is malformed,
+    /// `WireError::VersionNegotiationFailed` if the initiator speaks another
+    /// major version, or `WireError::AuthenticationFailed` if key agreement
+    /// fails.
+    pub fn respond(
+        hello: &[u8],
+        capabilities: CapabilityFlags,
+    ) -> Result<(Self, Vec<u8>), WireError> {
+        if hello.len() != PARAMS_SIZE + InitiatorHello::SIZE {
+            return Err(WireError::DeserializationFailed(
+                "Malformed handshake hello".to_string(),
+            ));
+        }
+        let (params, body) = hello.split_at(PARAMS_SIZE);
+        let peer_capabilities = decode_params(params)?;
+        let parsed = InitiatorHello::from_bytes(body).ok_or_else(|| {
+            WireError::DeserializationFailed("Malformed handshake hello".to_string())
+        })?;
+
+        let keypair = X25519ECDH::generate_keypair();
+        let (kyber_ss, kyber_ct) = KyberKEM::encapsulate(&parsed.public_key.kyber_pk)
+            .map_err(|_| WireError::AuthenticationFailed)?;
+        let x25519_ss = X25519ECDH::diffie_hellman(&keypair.secret, &parsed.public_key.x25519_pk)
+            .map_err(|_| WireError::AuthenticationFailed)?;
+
+        let response = ResponderResponse {
+            x25519_pk: keypair.public,
+            kyber_ct,
+            context_id: parsed.context_id,
+        };
+        let mut message = encode_params(capabilities);
+        message.extend_from_slice(&response.to_bytes());
+
+        let mut transcript = Transcript::new();
+        transcript.append(hello).append(&message);
+
+        let kex_transcript = KexTranscript::new(
+            parsed.public_key.x25519_pk,
+            keypair.public,
+            parsed.public_key.kyber_pk.clone(),
+            response.kyber_ct.clone(),
+        );
+        let shared = HybridKeyExchange::combine(kyber_ss, x25519_ss, &kex_transcript);
+        let derived = DerivedKeys::derive(
+            &shared,
+            transcript.hash(),
+            capabilities.intersect(peer_capabilities),
+        );
+        message.extend_from_slice(derived.confirm(Self::RESPONDER_CONFIRM).as_bytes());
+
+        let handshake = Self {
+            state: HandshakeState::ResponderResponding,
+            context_id: parsed.context_id,
+            capabilities,
+            keypair: None,
+            transcript,
+            derived: Some(derived),
+        };
+        Ok((handshake, message))
+    }
+
+    /// Finish the handshake with the peer's last message
+    ///
+    /// - **Initiator**: `message` is message 2 (Response). Verifies the
+    ///   responder's confirmation and returns the session keys together with
+    ///   message 3 (Finished) to send.
+    /// - **Responder**: `message` is message 3 (Finished). Verifies the
+    ///   initiator's confirmation and returns the session keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::DeserializationFailed` if `message` is malformed,
+    /// `WireError::VersionNegotiationFailed` if the responder speaks another
+    /// major version, or `WireError::AuthenticationFailed` if the context ID
+    /// or a confirmation MAC does not match (e.g. a tampered transcript).
+    pub fn finalize(mut self, message: &[u8]) -> Result<(SessionKeys, Option<Vec<u8>>), WireError> {
+        match self.state {
+            HandshakeState::InitiatorWaiting => {
+                let (derived, confirm) = self.initiator_keys(message)?;
+                let expected = derived.confirm(Self::RESPONDER_CONFIRM);
+                if !ct_eq(expected.as_bytes(), &confirm) {
+                    self.state = HandshakeState::Failed;
+                    return Err(WireError::AuthenticationFailed);
+                }
+
+                self.state = HandshakeState::Completed;
+                let finished = derived.confirm(Self::INITIATOR_CONFIRM).as_bytes().to_vec();
+                Ok((derived.session_keys(true), Some(finished)))
+            }
+            HandshakeState::ResponderResponding => {
+                let derived = self.derived.take().ok_or(WireError::AuthenticationFailed)?;
+                let confirm: [u8; CONFIRM_SIZE] = message.try_into().map_err(|_| {
+                    WireError::DeserializationFailed("Malformed handshake finished".to_string())
+                })?;
+                if !ct_eq(
+                    derived.confirm(Self::INITIATOR_CONFIRM).as_bytes(),
+                    &confirm,
+                ) {
+                    self.state = HandshakeState::Failed;
+                    return Err(WireError::AuthenticationFailed);
+                }
+
+                self.state = HandshakeState::Completed;
+                Ok((derived.session_keys(false), None))
+            }
+            _ => Err(WireError::AuthenticationFailed),
+        }
+    }
+
+    /// Current handshake state
+    pub fn state(&self) -> HandshakeState {
+        self.state
+    }
+
+    /// Context ID of this handshake
+    pub fn context_id(&self) -> &[u8; 32] {
+        &self.context_id
+    }
+
+    /// Capabilities this side advertised
+    pub fn capabilities(&self) -> CapabilityFlags {
+        self.capabilities
+    }
+
+    /// Initiator: derive keys from message 2, returning them with the
+    /// responder's (unverified) confirmation MAC
+    fn initiator_keys(
+        &mut self,
+        message: &[u8],
+    ) -> Result<(DerivedKeys, [u8; CONFIRM_SIZE]), WireError> {
+        if message.len() != PARAMS_SIZE + ResponderResponse::SIZE + CONFIRM_SIZE {
+            return Err(WireError::DeserializationFailed(
+                "Malformed handshake response".to_string(),
+            ));
+        }
+        let (signed, confirm) = message.split_at(message.len() - CONFIRM_SIZE);
+        let (params, body) = signed.split_at(PARAMS_SIZE);
+        let peer_capabilities = decode_params(params)?;
+        let response = ResponderResponse::from_bytes(body).ok_or_else(|| {
+            WireError::DeserializationFailed("Malformed handshake response".to_string())
+        })?;
+        if !ct_eq(&response.context_id, &self.context_id) {
+            return Err(WireError::AuthenticationFailed);
+        }
+
+        let keypair = self.keypair.take().ok_or(WireError::AuthenticationFailed)?;
+        let kyber_ss = KyberKEM::decapsulate(&keypair.kyber.secret, &response.kyber_ct)
+            .map_err(|_| WireError::AuthenticationFailed)?;
+        let x25519_ss = X25519ECDH::diffie_hellman(&keypair.x25519.secret, &response.x25519_pk)
+            .map_err(|_| WireError::AuthenticationFailed)?;
+
+        self.transcript.append(signed);
+
+        let kex_transcript = KexTranscript::new(
+            keypair.x25519.public,
+            response.x25519_pk,
+            keypair.kyber.public.clone(),
+            response.kyber_ct.clone(),
+        );
+        let shared = HybridKeyExchange::combine(kyber_ss, x25519_ss, &kex_transcript);
+        let derived = DerivedKeys::derive(
+            &shared,
+            self.transcript.hash(),
+            self.capabilities.intersect(peer_capabilities),
+        );
+        Ok((derived, confirm.try_into().unwrap()))
+    }
+}
+
+// ============================================================================
+// Session Resumption
+// ============================================================================
+
+/// Lifetime of a resumption ticket (24 hours)
+pub const TICKET_LIFETIME_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// Size of a resumption ticket ID
+const TICKET_ID_SIZE: usize = 16;
+
+/// Size of the per-resumption salts
+const RESUME_SALT_SIZE: usize = 32;
+
+/// Size of the initiator's resumption challenge
+const CHALLENGE_SIZE: usize = 32;
+
+/// Size of a sealed challenge: nonce || ciphertext || tag
+const SEALED_CHALLENGE_SIZE: usize = NONCE_SIZE + CHALLENGE_SIZE + TAG_SIZE;
+
+/// Size of resumption message 1 (Resume)
+const RESUME_SIZE: usize = TICKET_ID_SIZE + RESUME_SALT_SIZE + SEALED_CHALLENGE_SIZE;
+
+/// Size of resumption message 2 (Resumed)
+const RESUMED_SIZE: usize = RESUME_SALT_SIZE + SEALED_CHALLENGE_SIZE;
+
+/// Ticket for resuming a session without a full hybrid handshake
+///
+/// Obtained from [`SessionKeys::resumption_ticket`]. The resumption secret
+/// is never serialized or exposed: it is zeroized on drop and redacted from
+/// `Debug`.
+#[derive(Clone)]
+pub struct ResumptionTicket {
+    /// Ticket ID, derived from the resumption secret
+    ticket_id: [u8; TICKET_ID_SIZE],
+    /// Expiry (Unix milliseconds)
+    expiry_ms: u64,
+    /// Secret the resumed session keys are derived from
+    resumption_secret: SecretBytes<32>,
+    /// Capabilities negotiated by the original session
+    capabilities: CapabilityFlags,
+}
+
+impl ResumptionTicket {
+    /// Domain separator for the ticket ID
+    const ID_CONTEXT: &'static [u8] = b"aeternum v5 resumption ticket-id";
+
+    /// Domain separation context for the challenge key
+    const CHALLENGE_CONTEXT: &'static str = "aeternum v5 resumption challenge";
+
+    fn new(
+        resumption_secret: SecretBytes<32>,
+        expiry_ms: u64,
+        capabilities: CapabilityFlags,
+    ) -> Self {
+        let id = keyed_hash(resumption_secret.as_bytes(), Self::ID_CONTEXT);
+        let mut ticket_id = [0u8; TICKET_ID_SIZE];
+        ticket_id.copy_from_slice(&id.as_bytes()[..TICKET_ID_SIZE]);
+        Self {
+            ticket_id,
+            expiry_ms,
+            resumption_secret,
+            capabilities,
+        }
+    }
+
+    /// Ticket ID, identical on both sides of the original session
+    pub fn ticket_id(&self) -> &[u8; TICKET_ID_SIZE] {
+        &self.ticket_id
+    }
+
+    /// Expiry (Unix milliseconds)
+    pub fn expiry_ms(&self) -> u64 {
+        self.expiry_ms
+    }
+
+    /// Capabilities a resumed session inherits
+    pub fn capabilities(&self) -> CapabilityFlags {
+        self.capabilities
+    }
+
+    /// Whether the ticket has expired at `now_ms`
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        now_ms >= self.expiry_ms
+    }
+
+    fn check_expiry(&self, now_ms: u64) -> Result<(), WireError> {
+        if self.is_expired(now_ms) {
+            return Err(WireError::TicketExpired {
+                expiry_ms: self.expiry_ms,
+                now_ms,
+            });
+        }
+        Ok(())
+    }
+
+    /// Key sealing the initiator's challenge, bound to message 1's header
+    fn challenge_key(&self, header: &[u8]) -> Result<XChaCha20Key, WireError> {
+        let okm = Zeroizing::new(
+            DeriveKey::new(header, Self::CHALLENGE_CONTEXT)
+                .derive(self.resumption_secret.as_bytes(), 32),
+        );
+        Ok(XChaCha20Key::from_bytes(&okm)?)
+    }
+}
+
+impl fmt::Debug for ResumptionTicket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResumptionTicket")
+            .field("ticket_id", &hex::encode(self.ticket_id))
+            .field("expiry_ms", &self.expiry_ms)
+            .field("resumption_secret", &self.resumption_secret)
+            .field("capabilities", &self.capabilities)
+            .finish()
+    }
+}
+
+/// Responder-side store of issued resumption tickets
+///
+/// A ticket is removed when it is redeemed and its ID is remembered until
+/// the ticket would have expired, so a replayed resumption is rejected with
+/// `WireError::TicketReplay` rather than served twice.
+#[derive(Debug, Default)]
+pub struct ResumptionTicketStore {
+    /// Tickets that can still be redeemed, by ID
+    tickets: HashMap<[u8; TICKET_ID_SIZE], ResumptionTicket>,
+    /// Redeemed ticket IDs with their expiry
+    redeemed: HashMap<[u8; TICKET_ID_SIZE], u64>,
+}
+
+impl ResumptionTicketStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a ticket issued at the end of a handshake
+    ///
+    /// A ticket that was already redeemed is not stored again.
+    pub fn insert(&mut self, ticket: ResumptionTicket) {
+        if !self.redeemed.contains_key(&ticket.ticket_id) {
+            self.tickets.insert(ticket.ticket_id, ticket);
+        }
+    }
+
+    /// Drop expired tickets and redeemed IDs past their expiry
+    pub fn prune(&mut self, now_ms: u64) {
+        self.tickets.retain(|_, ticket| !ticket.is_expired(now_ms));
+        self.redeemed.retain(|_, expiry_ms| now_ms < *expiry_ms);
+    }
+
+    /// Number of tickets that can still be redeemed
+    pub fn len(&self) -> usize {
+        self.tickets.len()
+    }
+
+    /// Whether no ticket can be redeemed
+    pub fn is_empty(&self) -> bool {
+        self.tickets.is_empty()
+    }
+
+    /// Redeem a ticket if `verify` accepts it
+    ///
+    /// The ticket is consumed only once `verify` succeeds, so a forged
+    /// message naming an observed ticket ID cannot burn the ticket.
+    fn redeem<T>(
+        &mut self,
+        ticket_id: &[u8; TICKET_ID_SIZE],
+        now_ms: u64,
+        verify: impl FnOnce(&ResumptionTicket) -> Result<T, WireError>,
+    ) -> Result<T, WireError> {
+        if self.redeemed.contains_key(ticket_id) {
+            return Err(WireError::TicketReplay(*ticket_id));
+        }
+        let ticket = self
+            .tickets
+            .get(ticket_id)
+            .ok_or(WireError::UnknownTicket)?;
+        if let Err(e) = ticket.check_expiry(now_ms) {
+            self.tickets.remove(ticket_id);
+            return Err(e);
+        }
+
+        let accepted = verify(ticket)?;
+        if let Some(ticket) = self.tickets.remove(ticket_id) {
+            self.redeemed.insert(*ticket_id, ticket.expiry_ms);
+        }
+        Ok(accepted)
+    }
+}
+
+/// Initiator side of a resumption in progress
+///
+/// Created by [`Handshake::resume`]; finish it with the responder's reply.
+pub struct ResumptionHandshake {
+    /// Ticket being redeemed
+    ticket: ResumptionTicket,
+    /// Challenge sealed into message 1
+    challenge: Zeroizing<[u8; CHALLENGE_SIZE]>,
+    /// Messages exchanged so far
+    transcript: Transcript,
+}
+
+impl ResumptionHandshake {
+    /// Finish the resumption with message 2 (Resumed)
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::DeserializationFailed` if `message` is malformed
+    /// or `WireError::AuthenticationFailed` if the responder did not prove
+    /// possession of the resumption secret.
+    pub fn finalize(mut self, message: &[u8]) -> Result<SessionKeys, WireError> {
+        if message.len() != RESUMED_SIZE {
+            return Err(WireError::DeserializationFailed(
+                "Malformed resumption response".to_string(),
+            ));
+        }
+        let (salt, sealed) = message.split_at(RESUME_SALT_SIZE);
+        self.transcript.append(salt);
+
+        let derived = DerivedKeys::derive_resumed(&self.ticket, self.transcript.hash());
+        let confirm_key = XChaCha20Key::from_bytes(&derived.confirm_key)?;
+        let challenge = open_challenge(&confirm_key, sealed, derived.transcript_hash.as_bytes())?;
+        if !ct_eq(challenge.as_slice(), self.challenge.as_slice()) {
+            return Err(WireError::AuthenticationFailed);
+        }
+        Ok(derived.session_keys(true))
+    }
+}
+
+impl Handshake {
+    /// Initiator: start resuming a session and return message 1 (Resume)
+    ///
+    /// # Errors
+    ///
+    /// Returns `WireError::TicketExpired` if the ticket has expired; start
+    /// a full handshake with [`Handshake::initiate`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::sync::handshake::{Handshake, ResumptionTicketStore};
+    /// use aeternum_core::sync::CapabilityFlags;
+    ///
+    /// let caps = CapabilityFlags::default();
+    /// let (initiator, hello) = Handshake::initiate([7u8; 32], caps);
+    /// let (responder, response) = Handshake::respond(&hello, caps)?;
+    /// let (initiator_keys, finished) = initiator.finalize(&response)?;
+    /// let (responder_keys, _) = responder.finalize(&finished.unwrap())?;
+    ///
+    /// let now_ms = 1_700_000_000_000;
+    /// let mut store = ResumptionTicketStore::new();
+    /// store.insert(responder_keys.resumption_ticket(now_ms));
+    /// let ticket = initiator_keys.resumption_ticket(now_ms);
+    ///
+    /// let (resuming, resume) = Handshake::resume(&ticket, now_ms + 1_000)?;
+    /// let (responder_keys, resumed) = Handshake::respond_resume(&resume, &mut store, now_ms + 1_000)?;
+    /// let initiator_keys = resuming.finalize(&resumed)?;
+    ///
+    /// assert_eq!(initiator_keys.send.key, responder_keys.recv.key);
+    /// # Ok::<(), aeternum_core::sync::WireError>(())
+    /// ```
+    pub fn resume(
+        ticket: &ResumptionTicket,
+        now_ms: u64,
+    ) -> Result<(ResumptionHandshake, Vec<u8>), WireError> {
+        ticket.check_expiry(now_ms)?;
+
+        let mut message = Vec::with_capacity(RESUME_SIZE);
+        message.extend_from_slice(&ticket.ticket_id);
+        let mut salt = [0u8; RESUME_SALT_SIZE];
+        rand::rngs::OsRng.fill_bytes(&mut salt);
+        message.extend_from_slice(&salt);
+
+        let mut challenge = Zeroizing::new([0u8; CHALLENGE_SIZE]);
+        rand::rngs::OsRng.fill_bytes(challenge.as_mut());
+        let key = ticket.challenge_key(&message)?;
+        let sealed = seal_challenge(&key, &challenge, &message)?;
+        message.extend_from_slice(&sealed);
+
+        let mut transcript = Transcript::new();
+        transcript.append(&message);
+
+        let handshake = ResumptionHandshake {
+            ticket: ticket.clone(),
+            challenge,
+            transcript,
+        };
+        Ok((handshake, message))
+    }
+
+    /// Responder: redeem message 1 (Resume) and return the resumed session
+    /// keys together with message 2 (Resumed)
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::DeserializationFailed` if `message` is malformed
+    /// - `WireError::UnknownTicket` if `store` does not hold the ticket
+    /// - `WireError::TicketReplay` if the ticket was already redeemed
+    /// - `WireError::TicketExpired` if the ticket has expired
+    /// - `WireError::AuthenticationFailed` if the initiator did not prove
+    ///   possession of the resumption secret; the ticket stays redeemable
+    pub fn respond_resume(
+        message: &[u8],
+        store: &mut ResumptionTicketStore,
+        now_ms: u64,
+    ) -> Result<(SessionKeys, Vec<u8>), WireError> {
+        if message.len() != RESUME_SIZE {
+            return Err(WireError::DeserializationFailed(
+                "Malformed resumption request".to_string(),
+            ));
+        }
+        let (header, sealed) = message.split_at(TICKET_ID_SIZE + RESUME_SALT_SIZE);
+        let ticket_id: [u8; TICKET_ID_SIZE] = header[..TICKET_ID_SIZE].try_into().unwrap();
+
+        store.redeem(&ticket_id, now_ms, |ticket| {
+            let challenge = open_challenge(&ticket.challenge_key(header)?, sealed, header)?;
+
+            let mut salt = [0u8; RESUME_SALT_SIZE];
+            rand::rngs::OsRng.fill_bytes(&mut salt);
+            let mut transcript = Transcript::new();
+            transcript.append(message).append(&salt);
+
+            let derived = DerivedKeys::derive_resumed(ticket, transcript.hash());
+            let confirm_key = XChaCha20Key::from_bytes(&derived.confirm_key)?;
+            let mut reply = Vec::with_capacity(RESUMED_SIZE);
+            reply.extend_from_slice(&salt);
+            reply.extend_from_slice(&seal_challenge(
+                &confirm_key,
+                &challenge,
+                derived.transcript_hash.as_bytes(),
+            )?);
+            Ok((derived.session_keys(false), reply))
+        })
+    }
+}
+
+/// Seal a resumption challenge under a fresh random nonce
+fn seal_challenge(
+    key: &XChaCha20Key,
+    challenge: &[u8; CHALLENGE_SIZE],
+    aad: &[u8],
+) -> Result<Vec<u8>, WireError> {
+    let nonce = XChaCha20Nonce::random();
+    let mut sealed = Vec::with_capacity(SEALED_CHALLENGE_SIZE);
+    sealed.extend_from_slice(nonce.as_bytes());
+    sealed.extend_from_slice(&AeadCipher::new(key).encrypt(&nonce, challenge, Some(aad))?);
+    Ok(sealed)
+}
+
+/// Open a challenge sealed by [`seal_challenge`]
+fn open_challenge(
+    key: &XChaCha20Key,
+    sealed: &[u8],
+    aad: &[u8],
+) -> Result<Zeroizing<[u8; CHALLENGE_SIZE]>, WireError> {
+    let (nonce, ciphertext) = sealed.split_at(NONCE_SIZE);
+    let nonce = XChaCha20Nonce::try_from_slice(nonce)?;
+    let plaintext = Zeroizing::new(
+        AeadCipher::new(key)
+            .decrypt(&nonce, ciphertext, Some(aad))
+            .map_err(|_| WireError::AuthenticationFailed)?,
+    );
+    let mut challenge = Zeroizing::new([0u8; CHALLENGE_SIZE]);
+    if plaintext.len() != CHALLENGE_SIZE {
+        return Err(WireError::AuthenticationFailed);
+    }
+    challenge.copy_from_slice(&plaintext);
+    Ok(challenge)
+}
+
+/// Encode the version and capability prefix of messages 1 and 2
+fn encode_params(capabilities: CapabilityFlags) -> Vec<u8> {
+    let mut params = Vec::with_capacity(PARAMS_SIZE);
+    params.extend_from_slice(&ProtocolVersion::current().to_bytes());
+    params.push(capabilities.as_u8());
+    params
+}
+
+/// Decode the peer's version and capability prefix
+///
+/// Peers must share our major version.
+fn decode_params(params: &[u8]) -> Result<CapabilityFlags, WireError> {
+    let theirs = ProtocolVersion::from_bytes(&params[..2])?;
+    VersionNegotiation::negotiate(ProtocolVersion::current(), theirs)?;
+    Ok(CapabilityFlags::new(params[2]))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -327,6 +1304,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_seeded_x25519_transcript_is_reproducible() {
+        // Kyber keygen/encapsulation cannot be seeded (see KyberKEM), so
+        // only the X25519 half of the transcript is reproducible.
+        let run = || {
+            let initiator = InitiatorKeyPair {
+                x25519: X25519ECDH::keypair_from_seed(&[0x11; 32]),
+                kyber: KyberKEM::generate_keypair(),
+            };
+            let responder = InitiatorKeyPair {
+                x25519: X25519ECDH::keypair_from_seed(&[0x22; 32]),
+                kyber: KyberKEM::generate_keypair(),
+            };
+
+            let hello = HybridHandshake::initiate(&initiator, [0x33; 32]);
+            let (response, responder_key) = HybridHandshake::respond(&hello, &responder);
+            let initiator_key = HybridHandshake::complete(&response, &initiator).unwrap();
+            assert_eq!(initiator_key.key, responder_key.key);
+
+            let x25519_ss =
+                X25519ECDH::diffie_hellman(&initiator.x25519.secret, &response.x25519_pk).unwrap();
+
+            (
+                hello.public_key.x25519_pk,
+                response.x25519_pk,
+                response.context_id,
+                *x25519_ss.as_bytes(),
+            )
+        };
+
+        assert_eq!(run(), run());
+    }
+
     #[test]
     fn test_session_key_length() {
         let initiator_kp = HybridHandshake::generate_initiator_keypair();
@@ -444,6 +1454,362 @@ mod tests {
         );
     }
 
+    // ─── Three-message handshake ───────────────────────────────────────────
+
+    /// Offset of the capability byte in messages 1 and 2
+    const CAPS_OFFSET: usize = 2;
+
+    #[test]
+    fn test_three_message_handshake_keys_match() {
+        let caps = CapabilityFlags::default();
+        let (initiator, hello) = Handshake::initiate([0x51u8; 32], caps);
+        assert_eq!(hello.len(), PARAMS_SIZE + InitiatorHello::SIZE);
+        assert_eq!(initiator.state(), HandshakeState::InitiatorWaiting);
+
+        let (responder, response) = Handshake::respond(&hello, caps).unwrap();
+        assert_eq!(
+            response.len(),
+            PARAMS_SIZE + ResponderResponse::SIZE + CONFIRM_SIZE
+        );
+        assert_eq!(responder.context_id(), &[0x51u8; 32]);
+
+        let (initiator_keys, finished) = initiator.finalize(&response).unwrap();
+        let finished = finished.unwrap();
+        assert_eq!(finished.len(), CONFIRM_SIZE);
+
+        let (responder_keys, reply) = responder.finalize(&finished).unwrap();
+        assert!(reply.is_none());
+
+        assert_eq!(initiator_keys.send.key, responder_keys.recv.key);
+        assert_eq!(initiator_keys.recv.key, responder_keys.send.key);
+        assert_ne!(initiator_keys.send.key, initiator_keys.recv.key);
+    }
+
+    #[test]
+    fn test_handshake_negotiates_common_capabilities() {
+        let initiator_caps = CapabilityFlags::new(CapabilityFlags::HYBRID_HANDSHAKE)
+            .with(CapabilityFlags::CHAFF_SYNC);
+        let responder_caps = CapabilityFlags::new(CapabilityFlags::HYBRID_HANDSHAKE)
+            .with(CapabilityFlags::VETO_SIGNALING);
+
+        let (initiator, hello) = Handshake::initiate([0x57u8; 32], initiator_caps);
+        let (responder, response) = Handshake::respond(&hello, responder_caps).unwrap();
+        let (initiator_keys, finished) = initiator.finalize(&response).unwrap();
+        let (responder_keys, _) = responder.finalize(&finished.unwrap()).unwrap();
+
+        let common = CapabilityFlags::new(CapabilityFlags::HYBRID_HANDSHAKE);
+        assert_eq!(initiator_keys.capabilities, common);
+        assert_eq!(responder_keys.capabilities, common);
+    }
+
+    #[test]
+    fn test_capability_downgrade_fails_on_both_sides() {
+        let caps = CapabilityFlags::default();
+        let (mut initiator, mut hello) = Handshake::initiate([0x52u8; 32], caps);
+
+        // Strip CHAFF_SYNC from the hello after the initiator hashed it
+        hello[CAPS_OFFSET] &= !CapabilityFlags::CHAFF_SYNC;
+        let (responder, response) = Handshake::respond(&hello, caps).unwrap();
+
+        // The initiator derives keys over the original hello, so the
+        // responder's confirmation does not verify...
+        let (derived, confirm) = initiator.initiator_keys(&response).unwrap();
+        let expected = derived.confirm(Handshake::RESPONDER_CONFIRM);
+        assert!(!ct_eq(expected.as_bytes(), &confirm));
+
+        // ...and a Finished computed over the initiator's view does not
+        // verify at the responder
+        let finished = derived.confirm(Handshake::INITIATOR_CONFIRM);
+        assert!(matches!(
+            responder.finalize(finished.as_bytes()),
+            Err(WireError::AuthenticationFailed)
+        ));
+
+        let (initiator, hello) = Handshake::initiate([0x52u8; 32], caps);
+        let (_, mut response) = Handshake::respond(&hello, caps).unwrap();
+        response[CAPS_OFFSET] &= !CapabilityFlags::CHAFF_SYNC;
+        assert!(matches!(
+            initiator.finalize(&response),
+            Err(WireError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_modified_hello_causes_mismatch() {
+        let caps = CapabilityFlags::default();
+        let (initiator, mut hello) = Handshake::initiate([0x52u8; 32], caps);
+        // Flip a byte of the initiator's Kyber public key in transit
+        let last = hello.len() - 1;
+        hello[last] ^= 0x01;
+
+        let (_, response) = Handshake::respond(&hello, caps).unwrap();
+        assert!(matches!(
+            initiator.finalize(&response),
+            Err(WireError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_modified_response_causes_mismatch() {
+        let caps = CapabilityFlags::default();
+        let (initiator, hello) = Handshake::initiate([0x53u8; 32], caps);
+        let (_, mut response) = Handshake::respond(&hello, caps).unwrap();
+        // Flip a byte of the responder's X25519 public key in transit
+        response[PARAMS_SIZE + 40] ^= 0x01;
+
+        assert!(matches!(
+            initiator.finalize(&response),
+            Err(WireError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_modified_finished_rejected() {
+        let caps = CapabilityFlags::default();
+        let (initiator, hello) = Handshake::initiate([0x54u8; 32], caps);
+        let (responder, response) = Handshake::respond(&hello, caps).unwrap();
+        let (_, finished) = initiator.finalize(&response).unwrap();
+
+        let mut finished = finished.unwrap();
+        finished[0] ^= 0x01;
+        assert!(matches!(
+            responder.finalize(&finished),
+            Err(WireError::AuthenticationFailed)
+        ));
+    }
+
+    #[test]
+    fn test_handshake_rejects_other_major_version() {
+        let caps = CapabilityFlags::default();
+        let (_, mut hello) = Handshake::initiate([0x58u8; 32], caps);
+        hello[0] = hello[0].wrapping_add(1);
+
+        assert!(matches!(
+            Handshake::respond(&hello, caps),
+            Err(WireError::VersionNegotiationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_handshake_rejects_malformed_messages() {
+        let caps = CapabilityFlags::default();
+        assert!(matches!(
+            Handshake::respond(&[0u8; 10], caps),
+            Err(WireError::DeserializationFailed(_))
+        ));
+
+        let (initiator, _) = Handshake::initiate([0x55u8; 32], caps);
+        assert!(matches!(
+            initiator.finalize(&[0u8; 10]),
+            Err(WireError::DeserializationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_transcript_order_and_boundaries() {
+        let mut ab = Transcript::new();
+        ab.append(b"a").append(b"b");
+        let mut ba = Transcript::new();
+        ba.append(b"b").append(b"a");
+        let mut joined = Transcript::new();
+        joined.append(b"ab");
+
+        assert_ne!(ab.hash(), ba.hash());
+        assert_ne!(ab.hash(), joined.hash());
+
+        // Hashing does not consume the transcript
+        let before = ab.hash();
+        assert_eq!(ab.hash(), before);
+        ab.append(b"c");
+        assert_ne!(ab.hash(), before);
+    }
+
+    #[test]
+    fn test_hello_roundtrip() {
+        let kp = HybridHandshake::generate_initiator_keypair();
+        let hello = HybridHandshake::initiate(&kp, [0x56u8; 32]);
+        let restored = InitiatorHello::from_bytes(&hello.to_bytes()).unwrap();
+
+        assert_eq!(restored.context_id, hello.context_id);
+        assert!(restored.public_key == hello.public_key);
+    }
+
+    // ─── Session resumption ────────────────────────────────────────────────
+
+    const NOW_MS: u64 = 1_700_000_000_000;
+
+    /// Run a full handshake, returning (initiator, responder) keys
+    fn full_session(caps: CapabilityFlags) -> (SessionKeys, SessionKeys) {
+        let (initiator, hello) = Handshake::initiate([0x61u8; 32], caps);
+        let (responder, response) = Handshake::respond(&hello, caps).unwrap();
+        let (initiator_keys, finished) = initiator.finalize(&response).unwrap();
+        let (responder_keys, _) = responder.finalize(&finished.unwrap()).unwrap();
+        (initiator_keys, responder_keys)
+    }
+
+    #[test]
+    fn test_resumption_roundtrip() {
+        let caps = CapabilityFlags::new(CapabilityFlags::HYBRID_HANDSHAKE)
+            .with(CapabilityFlags::VETO_SIGNALING);
+        let (initiator_keys, responder_keys) = full_session(caps);
+
+        let ticket = initiator_keys.resumption_ticket(NOW_MS);
+        let responder_ticket = responder_keys.resumption_ticket(NOW_MS);
+        assert_eq!(ticket.ticket_id(), responder_ticket.ticket_id());
+        assert_eq!(ticket.expiry_ms(), NOW_MS + TICKET_LIFETIME_MS);
+
+        let mut store = ResumptionTicketStore::new();
+        store.insert(responder_ticket);
+        assert_eq!(store.len(), 1);
+
+        let (resuming, resume) = Handshake::resume(&ticket, NOW_MS + 1).unwrap();
+        assert_eq!(resume.len(), RESUME_SIZE);
+        let (resumed_responder, resumed) =
+            Handshake::respond_resume(&resume, &mut store, NOW_MS + 1).unwrap();
+        assert_eq!(resumed.len(), RESUMED_SIZE);
+        assert!(store.is_empty());
+
+        let resumed_initiator = resuming.finalize(&resumed).unwrap();
+        assert_eq!(resumed_initiator.send.key, resumed_responder.recv.key);
+        assert_eq!(resumed_initiator.recv.key, resumed_responder.send.key);
+        assert_eq!(resumed_initiator.capabilities, caps);
+        assert_eq!(resumed_responder.capabilities, caps);
+
+        // The resumed session issues its own, different ticket
+        let next = resumed_initiator.resumption_ticket(NOW_MS);
+        assert_eq!(
+            next.ticket_id(),
+            resumed_responder.resumption_ticket(NOW_MS).ticket_id()
+        );
+        assert_ne!(next.ticket_id(), ticket.ticket_id());
+    }
+
+    #[test]
+    fn test_resumed_keys_differ_from_original() {
+        let caps = CapabilityFlags::default();
+        let (initiator_keys, responder_keys) = full_session(caps);
+        let ticket = initiator_keys.resumption_ticket(NOW_MS);
+
+        let mut store = ResumptionTicketStore::new();
+        store.insert(responder_keys.resumption_ticket(NOW_MS));
+        let (resuming, resume) = Handshake::resume(&ticket, NOW_MS).unwrap();
+        let (_, resumed) = Handshake::respond_resume(&resume, &mut store, NOW_MS).unwrap();
+        let resumed_keys = resuming.finalize(&resumed).unwrap();
+
+        for old in [&initiator_keys.send.key, &initiator_keys.recv.key] {
+            assert_ne!(&resumed_keys.send.key, old);
+            assert_ne!(&resumed_keys.recv.key, old);
+        }
+    }
+
+    #[test]
+    fn test_expired_ticket_falls_back_to_full_handshake() {
+        let caps = CapabilityFlags::default();
+        let (initiator_keys, responder_keys) = full_session(caps);
+        let ticket = initiator_keys.resumption_ticket(NOW_MS);
+        let expiry = ticket.expiry_ms();
+        assert!(!ticket.is_expired(expiry - 1));
+        assert!(ticket.is_expired(expiry));
+
+        // Initiator refuses to resume with an expired ticket ...
+        assert!(matches!(
+            Handshake::resume(&ticket, expiry),
+            Err(WireError::TicketExpired { expiry_ms, now_ms })
+                if expiry_ms == expiry && now_ms == expiry
+        ));
+
+        // ... and so does the responder, even for a message built in time
+        let mut store = ResumptionTicketStore::new();
+        store.insert(responder_keys.resumption_ticket(NOW_MS));
+        let (_, resume) = Handshake::resume(&ticket, expiry - 1).unwrap();
+        assert!(matches!(
+            Handshake::respond_resume(&resume, &mut store, expiry),
+            Err(WireError::TicketExpired { .. })
+        ));
+        assert!(store.is_empty());
+
+        // Falling back to a full handshake still works
+        let (fresh_initiator, fresh_responder) = full_session(caps);
+        assert_eq!(fresh_initiator.send.key, fresh_responder.recv.key);
+    }
+
+    #[test]
+    fn test_replayed_ticket_rejected() {
+        let caps = CapabilityFlags::default();
+        let (initiator_keys, responder_keys) = full_session(caps);
+        let ticket = initiator_keys.resumption_ticket(NOW_MS);
+        let responder_ticket = responder_keys.resumption_ticket(NOW_MS);
+
+        let mut store = ResumptionTicketStore::new();
+        store.insert(responder_ticket.clone());
+        let (_, resume) = Handshake::resume(&ticket, NOW_MS).unwrap();
+        Handshake::respond_resume(&resume, &mut store, NOW_MS).unwrap();
+
+        // Same message again
+        assert!(matches!(
+            Handshake::respond_resume(&resume, &mut store, NOW_MS),
+            Err(WireError::TicketReplay(id)) if &id == ticket.ticket_id()
+        ));
+
+        // A fresh message for the same ticket, even after re-inserting it
+        store.insert(responder_ticket);
+        assert!(store.is_empty());
+        let (_, again) = Handshake::resume(&ticket, NOW_MS).unwrap();
+        assert!(matches!(
+            Handshake::respond_resume(&again, &mut store, NOW_MS),
+            Err(WireError::TicketReplay(_))
+        ));
+
+        // Once the ticket would have expired, its ID is forgotten
+        store.prune(ticket.expiry_ms());
+        assert!(matches!(
+            Handshake::respond_resume(&again, &mut store, NOW_MS),
+            Err(WireError::UnknownTicket)
+        ));
+    }
+
+    #[test]
+    fn test_forged_resumption_does_not_burn_ticket() {
+        let caps = CapabilityFlags::default();
+        let (initiator_keys, responder_keys) = full_session(caps);
+        let ticket = initiator_keys.resumption_ticket(NOW_MS);
+
+        let mut store = ResumptionTicketStore::new();
+        store.insert(responder_keys.resumption_ticket(NOW_MS));
+
+        let (resuming, resume) = Handshake::resume(&ticket, NOW_MS).unwrap();
+        let mut forged = resume.clone();
+        forged[RESUME_SIZE - 1] ^= 0x01;
+        assert!(matches!(
+            Handshake::respond_resume(&forged, &mut store, NOW_MS),
+            Err(WireError::AuthenticationFailed)
+        ));
+        assert_eq!(store.len(), 1);
+
+        // A tampered reply is rejected by the initiator
+        let (_, mut resumed) = Handshake::respond_resume(&resume, &mut store, NOW_MS).unwrap();
+        resumed[0] ^= 0x01;
+        assert!(matches!(
+            resuming.finalize(&resumed),
+            Err(WireError::AuthenticationFailed)
+        ));
+
+        assert!(matches!(
+            Handshake::respond_resume(&resume[1..], &mut store, NOW_MS),
+            Err(WireError::DeserializationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_resumption_ticket_debug_redacts_secret() {
+        let (initiator_keys, _) = full_session(CapabilityFlags::default());
+        let ticket = initiator_keys.resumption_ticket(NOW_MS);
+        let debug = format!("{:?}", ticket);
+        assert!(debug.contains("[REDACTED]"));
+        assert!(debug.contains(&hex::encode(ticket.ticket_id())));
+        assert!(!debug.contains(&hex::encode(ticket.resumption_secret.as_bytes())));
+    }
+
     #[test]
     fn test_responder_response_components() {
         let initiator_kp = HybridHandshake::generate_initiator_keypair();
@@ -12,7 +12,8 @@
 //!
 //! ## Wire Frame Format
 //!
-//! All network messages are encapsulated in fixed-size **8192-byte** Aeternum-Frames:
+//! All network messages are encapsulated in fixed-size Aeternum-Frames. The
+//! default profile uses **8192-byte** frames:
 //!
 //! ```text
 //! +----------------+------------------+------------------+-------------------+
@@ -25,6 +26,14 @@
 //! Total: 8192 bytes (fixed)
 //! ```
 //!
+//! ## Frame Profiles
+//!
+//! BLE GATT links carry ~512 bytes per write, so a session over BLE can
+//! negotiate [`PROFILE_BLE`] (512-byte frames) instead of
+//! [`PROFILE_DEFAULT`]. The profile is agreed during version negotiation
+//! and fixed for the rest of the session: every frame in a session has the
+//! same size, and frames of another profile are rejected.
+//!
 //! ## Security Guarantees
 //!
 //! - **Fixed-size frames** prevent traffic fingerprinting attacks
@@ -38,34 +47,61 @@
 //! - `wire` - Wire protocol implementation
 //! - `codec` - Message encoding/decoding
 //! - `chaff` - Traffic obfuscation and chaff generation
-//! - `handshake` - Hybrid encryption handshake protocol
+//! - `handshake` - Hybrid encryption handshake protocol and session resumption
+//! - `ratchet` - Frame key ratchet for long-lived sessions
+//! - `connection` - Connection lifecycle, timeouts and error frames
+//! - `broadcast` - Veto fan-out with per-peer acknowledgement
+//! - `epoch_sync` - Epoch announcements and upgrade/divergence detection
 //!
 //! ## Protocol Versioning
 //!
 //! This implementation follows [AET-WIRE-SPEC-004](../../../docs/protocols/Sync-Wire-Protocol.md).
 
+use serde::{Deserialize, Serialize};
+
 // Public module exports
+pub mod broadcast;
 pub mod chaff;
 pub mod codec;
+pub mod connection;
+pub mod epoch_sync;
 pub mod frame;
 pub mod handshake;
+pub mod ratchet;
 pub mod version;
 pub mod wire;
 
 // Re-export common types
-pub use chaff::{ChaffGenerator, ChaffSyncMessage, TimingMetadata, JITTER_MAX_MS, JITTER_MIN_MS};
+pub use broadcast::{
+    FrameTransport, PeerDelivery, PeerHandle, VetoAck, VetoBroadcastPolicy, VetoBroadcastStatus,
+};
+pub use chaff::{
+    AdaptiveChaffScheduler, ChaffGenerator, ChaffSyncMessage, TimingMetadata, CHAFF_AAD_LABEL,
+    CHAFF_BODY_MAGIC, IDLE_MEAN_INTERVAL_MS, IDLE_WINDOW_MS, JITTER_MAX_MS, JITTER_MIN_MS,
+};
 pub use codec::{MessageCodec, PayloadType};
-pub use frame::WireFrame;
+pub use connection::{
+    ConnectionAction, ConnectionState, ConnectionTimeouts, ErrorCode, ErrorFrame,
+};
+pub use epoch_sync::{EpochSyncAction, EpochSyncMessage};
+pub use frame::{
+    FragmentHeader, FrameFragmenter, FrameReassembler, WireFrame, SEALED_FRAME_VERSION,
+};
+pub use ratchet::{RatchetConfig, DEFAULT_RATCHET_BYTES, DEFAULT_RATCHET_FRAMES};
 pub use version::{
     CapabilityFlags,
+    NegotiationOutcome,
     ProtocolVersion,
     VersionNegotiation, // Re-export for doctests
     VersionNegotiationMessage,
 };
-pub use wire::{VetoMessage, WireProtocol, VETO_WINDOW_SECONDS};
+pub use wire::{
+    EvictionPolicy, NonceCache, NonceCacheConfig, VetoMessage, WireProtocol,
+    DEFAULT_NONCE_CACHE_CAPACITY, VETO_WINDOW_SECONDS,
+};
 
 /// Current Wire protocol version
-pub const PROTOCOL_VERSION: (u8, u8) = (1, 0);
+pub const PROTOCOL_VERSION: (u8, u8) = (1, 1);
 
 /// Fixed frame size in bytes (prevents traffic fingerprinting)
 pub const FRAME_SIZE: usize = 8192;
@@ -79,6 +115,56 @@ pub const AUTH_TAG_SIZE: usize = 16;
 /// Maximum encrypted body size (excluding headers, body_len, padding, auth tag)
 pub const MAX_BODY_SIZE: usize = FRAME_SIZE - NONCE_SIZE - 4 - 1 - 2 - AUTH_TAG_SIZE;
 
+/// Frame size of the BLE profile (practical GATT MTU)
+pub const BLE_FRAME_SIZE: usize = 512;
+
+/// Default profile: 8192-byte frames
+pub const PROFILE_DEFAULT: FrameProfile = FrameProfile::with_frame_size(FRAME_SIZE);
+
+/// BLE profile: 512-byte frames
+pub const PROFILE_BLE: FrameProfile = FrameProfile::with_frame_size(BLE_FRAME_SIZE);
+
+/// Frame size profile of a session
+///
+/// Every frame of a session is exactly `frame_size` bytes, so the profile
+/// is negotiated once and never mixed. Only [`PROFILE_DEFAULT`] and
+/// [`PROFILE_BLE`] are supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameProfile {
+    /// Total serialized frame size in bytes
+    pub frame_size: usize,
+    /// Maximum encrypted body size for this frame size
+    pub max_body_size: usize,
+}
+
+impl FrameProfile {
+    /// Build a profile, deriving the body budget from the frame layout
+    const fn with_frame_size(frame_size: usize) -> Self {
+        Self {
+            frame_size,
+            max_body_size: frame_size - NONCE_SIZE - 4 - 1 - 2 - AUTH_TAG_SIZE,
+        }
+    }
+
+    /// Look up the supported profile with the given frame size
+    pub fn for_frame_size(frame_size: usize) -> Option<Self> {
+        [PROFILE_DEFAULT, PROFILE_BLE]
+            .into_iter()
+            .find(|profile| profile.frame_size == frame_size)
+    }
+
+    /// Whether this is one of the supported profiles
+    pub fn is_supported(&self) -> bool {
+        Self::for_frame_size(self.frame_size) == Some(*self)
+    }
+}
+
+impl Default for FrameProfile {
+    fn default() -> Self {
+        PROFILE_DEFAULT
+    }
+}
+
 /// Protocol-level error type
 #[derive(Debug, thiserror::Error)]
 pub enum WireError {
@@ -86,6 +172,15 @@ pub enum WireError {
     #[error("Invalid frame size: expected {FRAME_SIZE}, got {0}")]
     InvalidFrameSize(usize),
 
+    /// Plaintext body does not fit in a frame
+    #[error("Body too large: {size} bytes, maximum {max}")]
+    BodyTooLarge {
+        /// Length of the rejected body
+        size: usize,
+        /// Largest body the frame profile carries
+        max: usize,
+    },
+
     /// Authentication tag verification failed
     #[error("Authentication tag verification failed")]
     AuthenticationFailed,
@@ -94,6 +189,10 @@ pub enum WireError {
     #[error("Invalid payload type: {0}")]
     InvalidPayloadType(u8),
 
+    /// Malformed or inconsistent frame fragment
+    #[error("Invalid fragment: {0}")]
+    InvalidFragment(String),
+
     /// Frame deserialization failed
     #[error("Frame deserialization failed: {0}")]
     DeserializationFailed(String),
@@ -120,6 +219,19 @@ pub enum WireError {
         window_end: u64,
     },
 
+    /// Frame belongs to a different profile than the session uses
+    #[error("Frame profile mismatch: session uses {expected}-byte frames, got {actual}")]
+    FrameProfileMismatch {
+        /// Frame size of the session profile
+        expected: usize,
+        /// Size of the offending frame
+        actual: usize,
+    },
+
+    /// Peer requested a frame profile that is not supported
+    #[error("Unsupported frame profile: {0}-byte frames")]
+    UnsupportedFrameProfile(usize),
+
     /// Version negotiation failed
     #[error("Version negotiation failed: client {client:?}, server {server:?}")]
     VersionNegotiationFailed {
@@ -129,6 +241,36 @@ pub enum WireError {
         server: (u8, u8),
     },
 
+    /// Resumption ticket is past its expiry; run a full handshake instead
+    #[error("Resumption ticket expired at {expiry_ms} ms (now {now_ms} ms)")]
+    TicketExpired {
+        /// Ticket expiry (Unix milliseconds)
+        expiry_ms: u64,
+        /// Current time (Unix milliseconds)
+        now_ms: u64,
+    },
+
+    /// Resumption ticket was already redeemed (tickets are single-use)
+    #[error("Resumption ticket {0:?} already redeemed")]
+    TicketReplay([u8; 16]),
+
+    /// Resumption ticket is not known to the responder
+    #[error("Unknown resumption ticket")]
+    UnknownTicket,
+
+    /// Frame type not valid in the connection's current state
+    #[error("Unexpected payload type {payload_type:#04x} in state {state:?}")]
+    UnexpectedFrame {
+        /// Payload type byte of the offending frame
+        payload_type: u8,
+        /// Connection state the frame arrived in
+        state: connection::ConnectionState,
+    },
+
+    /// Connection is closed; no further frames are processed
+    #[error("Connection closed")]
+    ConnectionClosed,
+
     /// I/O error during frame processing
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
@@ -158,9 +300,32 @@ mod tests {
         assert_eq!(MAX_BODY_SIZE, 8145);
     }
 
+    #[test]
+    fn test_frame_profile_body_sizes() {
+        // FRAME_SIZE - NONCE - Epoch - Type - BodyLen - AuthTag
+        assert_eq!(PROFILE_DEFAULT.frame_size, 8192);
+        assert_eq!(PROFILE_DEFAULT.max_body_size, 8145);
+        assert_eq!(PROFILE_DEFAULT.max_body_size, MAX_BODY_SIZE);
+
+        // 512 - 24 - 4 - 1 - 2 - 16 = 465
+        assert_eq!(PROFILE_BLE.frame_size, 512);
+        assert_eq!(PROFILE_BLE.max_body_size, 465);
+
+        assert_eq!(FrameProfile::for_frame_size(512), Some(PROFILE_BLE));
+        assert_eq!(FrameProfile::for_frame_size(8192), Some(PROFILE_DEFAULT));
+        assert_eq!(FrameProfile::for_frame_size(1024), None);
+
+        let forged = FrameProfile {
+            frame_size: 512,
+            max_body_size: 8145,
+        };
+        assert!(!forged.is_supported());
+        assert!(PROFILE_BLE.is_supported());
+    }
+
     #[test]
     fn test_protocol_version() {
-        assert_eq!(PROTOCOL_VERSION, (1, 0));
+        assert_eq!(PROTOCOL_VERSION, (1, 1));
     }
 
     #[test]
@@ -176,5 +341,12 @@ mod tests {
         assert!(err.to_string().contains("regression"));
         assert!(err.to_string().contains("5"));
         assert!(err.to_string().contains("3"));
+
+        let err = WireError::TicketExpired {
+            expiry_ms: 1_000,
+            now_ms: 2_000,
+        };
+        assert!(err.to_string().contains("expired"));
+        assert!(err.to_string().contains("1000"));
     }
 }
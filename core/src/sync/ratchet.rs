@@ -0,0 +1,353 @@
+//! # Frame Key Ratchet
+//!
+//! Symmetric key ratchet for long-lived wire sessions.
+//!
+//! A pairing session can move hundreds of MB under one session key. The
+//! ratchet bounds how much traffic any single key protects and gives
+//! forward secrecy inside the session: after [`RatchetConfig::max_frames`]
+//! frames or [`RatchetConfig::max_bytes`] plaintext bytes, the sender
+//! derives the next key and zeroizes the old one.
+//!
+//! ```text
+//! key_{g+1} = BLAKE3-derive_key("aeternum wire ratchet v1", key_g)
+//! ```
+//!
+//! ## Generations
+//!
+//! Every key has a 4-byte generation counter `g` (the session key is
+//! generation 0). It is authenticated as AEAD associated data, so a frame
+//! only opens under the key of its own generation. The frame layout has
+//! no room for the counter, so the receiver finds the generation by trying
+//! at most three keys: its current generation, then `g + 1` (the sender
+//! has ratcheted; the receiver follows and drops its oldest key), then
+//! `g - 1` (a frame that was in flight when the sender ratcheted). Any
+//! other generation fails authentication.
+//!
+//! Both peers derive the same chain from the same session key, so the
+//! receiver never needs to be told when the sender ratcheted.
+//!
+//! ## Negotiation
+//!
+//! The ratchet runs only when both peers advertise
+//! [`CapabilityFlags::KEY_RATCHET`](crate::sync::version::CapabilityFlags::KEY_RATCHET).
+//! With a peer that does not, the whole session stays on the session key
+//! and frames carry no AAD, as before the ratchet existed. Version
+//! negotiation frames never carry the generation, since they are
+//! exchanged before either side knows the outcome.
+
+use crate::crypto::aead::{AeadCipher, XChaCha20Key, KEY_SIZE};
+use crate::crypto::error::Result;
+use crate::crypto::hash::DeriveKey;
+use zeroize::{Zeroize, Zeroizing};
+
+/// Domain separation context for the ratchet step
+const RATCHET_CONTEXT: &str = "aeternum wire ratchet v1";
+
+/// Default number of frames sent under one key
+pub const DEFAULT_RATCHET_FRAMES: u64 = 1024;
+
+/// Default number of plaintext bytes sent under one key (4 MiB)
+pub const DEFAULT_RATCHET_BYTES: u64 = 4 * 1024 * 1024;
+
+/// When the sending side ratchets
+///
+/// The key advances as soon as either limit is reached. A limit of 0 is
+/// treated as 1 (ratchet after every frame).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RatchetConfig {
+    /// Frames sent under one key
+    pub max_frames: u64,
+    /// Plaintext bytes sent under one key
+    pub max_bytes: u64,
+}
+
+impl Default for RatchetConfig {
+    fn default() -> Self {
+        Self {
+            max_frames: DEFAULT_RATCHET_FRAMES,
+            max_bytes: DEFAULT_RATCHET_BYTES,
+        }
+    }
+}
+
+/// Derive the key of the next generation
+///
+/// # Example
+///
+/// ```
+/// use aeternum_core::crypto::aead::XChaCha20Key;
+/// use aeternum_core::sync::ratchet::next_key;
+///
+/// let key = XChaCha20Key::from_bytes(&[7u8; 32]).unwrap();
+/// assert_eq!(next_key(&key).as_bytes(), next_key(&key).as_bytes());
+/// assert_ne!(next_key(&key).as_bytes(), key.as_bytes());
+/// ```
+pub fn next_key(key: &XChaCha20Key) -> XChaCha20Key {
+    let bytes =
+        Zeroizing::new(DeriveKey::new(&[], RATCHET_CONTEXT).derive(key.as_bytes(), KEY_SIZE));
+    // SAFETY: derive() always returns exactly KEY_SIZE bytes
+    XChaCha20Key::from_bytes(&bytes).unwrap()
+}
+
+/// AAD binding a frame to its key generation
+pub(crate) fn generation_aad(generation: u32) -> [u8; 4] {
+    generation.to_be_bytes()
+}
+
+/// One generation's key and the cipher built from it
+struct RatchetKey {
+    key: XChaCha20Key,
+    cipher: AeadCipher,
+    generation: u32,
+}
+
+impl RatchetKey {
+    fn new(key: XChaCha20Key, generation: u32) -> Self {
+        Self {
+            cipher: AeadCipher::new(&key),
+            key,
+            generation,
+        }
+    }
+
+    /// Key of the following generation
+    fn next(&self) -> Self {
+        Self::new(next_key(&self.key), self.generation.saturating_add(1))
+    }
+
+    /// Zeroize the key bytes; the cipher zeroizes its copy when dropped
+    fn retire(mut self) -> [u8; KEY_SIZE] {
+        self.key.zeroize();
+        *self.key.as_bytes()
+    }
+}
+
+/// Sending half of the ratchet
+pub(crate) struct SendRatchet {
+    current: RatchetKey,
+    config: RatchetConfig,
+    frames: u64,
+    bytes: u64,
+    /// Bytes of the last retired key after zeroization (test hook)
+    #[cfg(test)]
+    pub(crate) last_retired: Option<[u8; KEY_SIZE]>,
+}
+
+impl SendRatchet {
+    /// Start at generation 0 with the session key
+    pub(crate) fn new(session_key: XChaCha20Key, config: RatchetConfig) -> Self {
+        Self {
+            current: RatchetKey::new(session_key, 0),
+            config,
+            frames: 0,
+            bytes: 0,
+            #[cfg(test)]
+            last_retired: None,
+        }
+    }
+
+    pub(crate) fn set_config(&mut self, config: RatchetConfig) {
+        self.config = config;
+    }
+
+    /// Cipher and generation for the next frame
+    pub(crate) fn cipher(&self) -> (&AeadCipher, u32) {
+        (&self.current.cipher, self.current.generation)
+    }
+
+    pub(crate) fn generation(&self) -> u32 {
+        self.current.generation
+    }
+
+    /// Count one sent frame; ratchet if a limit was reached
+    pub(crate) fn record(&mut self, plaintext_len: usize) {
+        self.frames += 1;
+        self.bytes = self.bytes.saturating_add(plaintext_len as u64);
+
+        if self.frames >= self.config.max_frames.max(1)
+            || self.bytes >= self.config.max_bytes.max(1)
+        {
+            self.advance();
+        }
+    }
+
+    fn advance(&mut self) {
+        // The counter is 4 bytes on the wire; stay on the last key rather
+        // than wrap around to generation 0
+        if self.current.generation == u32::MAX {
+            return;
+        }
+
+        let next = self.current.next();
+        let _retired = std::mem::replace(&mut self.current, next).retire();
+        #[cfg(test)]
+        {
+            self.last_retired = Some(_retired);
+        }
+        self.frames = 0;
+        self.bytes = 0;
+    }
+}
+
+/// Receiving half of the ratchet
+pub(crate) struct RecvRatchet {
+    current: RatchetKey,
+    /// Key of `current.generation - 1`, kept for in-flight frames
+    previous: Option<RatchetKey>,
+    /// Bytes of the last retired key after zeroization (test hook)
+    #[cfg(test)]
+    pub(crate) last_retired: Option<[u8; KEY_SIZE]>,
+}
+
+impl RecvRatchet {
+    /// Start at generation 0 with the session key
+    pub(crate) fn new(session_key: XChaCha20Key) -> Self {
+        Self {
+            current: RatchetKey::new(session_key, 0),
+            previous: None,
+            #[cfg(test)]
+            last_retired: None,
+        }
+    }
+
+    pub(crate) fn generation(&self) -> u32 {
+        self.current.generation
+    }
+
+    /// Cipher and generation of the current receiving key
+    pub(crate) fn cipher(&self) -> (&AeadCipher, u32) {
+        (&self.current.cipher, self.current.generation)
+    }
+
+    /// Open a frame with the key of generation `g`, `g + 1` or `g - 1`
+    ///
+    /// `open` is called with each candidate cipher and its generation
+    /// until one succeeds. Success under `g + 1` advances the ratchet:
+    /// the `g - 1` key is zeroized and `g` becomes the previous key.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from the current generation if no candidate
+    /// opens the frame; the ratchet is unchanged.
+    pub(crate) fn open<T>(&mut self, open: impl Fn(&AeadCipher, u32) -> Result<T>) -> Result<T> {
+        let err = match open(&self.current.cipher, self.current.generation) {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+
+        if self.current.generation < u32::MAX {
+            let next = self.current.next();
+            if let Ok(value) = open(&next.cipher, next.generation) {
+                let current = std::mem::replace(&mut self.current, next);
+                if let Some(_retired) = self.previous.replace(current).map(RatchetKey::retire) {
+                    #[cfg(test)]
+                    {
+                        self.last_retired = Some(_retired);
+                    }
+                }
+                return Ok(value);
+            }
+        }
+
+        if let Some(previous) = &self.previous {
+            if let Ok(value) = open(&previous.cipher, previous.generation) {
+                return Ok(value);
+            }
+        }
+
+        Err(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::aead::XChaCha20Nonce;
+
+    fn seal(ratchet: &SendRatchet, nonce: &XChaCha20Nonce, msg: &[u8]) -> Vec<u8> {
+        let (cipher, generation) = ratchet.cipher();
+        cipher
+            .encrypt(nonce, msg, Some(&generation_aad(generation)))
+            .unwrap()
+    }
+
+    fn open(ratchet: &mut RecvRatchet, nonce: &XChaCha20Nonce, ct: &[u8]) -> Result<Vec<u8>> {
+        ratchet
+            .open(|cipher, generation| cipher.decrypt(nonce, ct, Some(&generation_aad(generation))))
+    }
+
+    #[test]
+    fn test_ratchet_context_stability() {
+        assert_eq!(RATCHET_CONTEXT, "aeternum wire ratchet v1");
+        let key = XChaCha20Key::from_bytes(&[0u8; 32]).unwrap();
+        let expected = DeriveKey::new(&[], "aeternum wire ratchet v1").derive(&[0u8; 32], 32);
+        assert_eq!(&next_key(&key).as_bytes()[..], &expected[..]);
+    }
+
+    #[test]
+    fn test_send_ratchet_advances_on_frame_and_byte_limits() {
+        let key = XChaCha20Key::generate();
+        let mut by_frames = SendRatchet::new(
+            key.clone(),
+            RatchetConfig {
+                max_frames: 3,
+                max_bytes: u64::MAX,
+            },
+        );
+        for _ in 0..2 {
+            by_frames.record(10);
+        }
+        assert_eq!(by_frames.generation(), 0);
+        by_frames.record(10);
+        assert_eq!(by_frames.generation(), 1);
+
+        let mut by_bytes = SendRatchet::new(
+            key,
+            RatchetConfig {
+                max_frames: u64::MAX,
+                max_bytes: 100,
+            },
+        );
+        by_bytes.record(60);
+        assert_eq!(by_bytes.generation(), 0);
+        by_bytes.record(60);
+        assert_eq!(by_bytes.generation(), 1);
+    }
+
+    #[test]
+    fn test_recv_ratchet_window() {
+        let key = XChaCha20Key::generate();
+        let config = RatchetConfig {
+            max_frames: 1,
+            max_bytes: u64::MAX,
+        };
+        let mut sender = SendRatchet::new(key.clone(), config);
+        let mut receiver = RecvRatchet::new(key);
+        let nonce = XChaCha20Nonce::random();
+
+        let mut frames = Vec::new();
+        for i in 0..4u8 {
+            frames.push(seal(&sender, &nonce, &[i]));
+            sender.record(1);
+        }
+
+        // g0, then g1 advances the receiver
+        assert_eq!(open(&mut receiver, &nonce, &frames[0]).unwrap(), [0]);
+        assert_eq!(open(&mut receiver, &nonce, &frames[1]).unwrap(), [1]);
+        assert_eq!(receiver.generation(), 1);
+
+        // g + 2 is outside the window
+        assert!(open(&mut receiver, &nonce, &frames[3]).is_err());
+        assert_eq!(receiver.generation(), 1);
+
+        // g + 1 then g - 1 (in flight) both open
+        assert_eq!(open(&mut receiver, &nonce, &frames[2]).unwrap(), [2]);
+        assert_eq!(receiver.generation(), 2);
+        assert_eq!(open(&mut receiver, &nonce, &frames[1]).unwrap(), [1]);
+
+        // g - 2 is rejected, and its key was zeroized when it was retired
+        assert!(open(&mut receiver, &nonce, &frames[0]).is_err());
+        assert_eq!(receiver.last_retired, Some([0u8; KEY_SIZE]));
+        assert_eq!(sender.last_retired, Some([0u8; KEY_SIZE]));
+    }
+}
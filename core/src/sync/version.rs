@@ -43,7 +43,8 @@
 //! }
 //! ```
 
-use crate::sync::{Result, WireError};
+use crate::sync::codec::{Message, PayloadType};
+use crate::sync::{FrameProfile, Result, WireError, PROFILE_DEFAULT};
 use serde::{Deserialize, Serialize};
 
 /// 协议版本号
@@ -229,6 +230,44 @@ pub enum VersionNegotiation {
 }
 
 impl VersionNegotiation {
+    /// 计算双方共同支持的最高版本
+    ///
+    /// 同一大版本内小版本向后兼容，因此较新的一方也支持较旧一方的版本：
+    /// 结果为 `(major, min(client.minor, server.minor))`。结果与参数顺序
+    /// 无关。
+    ///
+    /// # Errors
+    ///
+    /// 大版本不同时返回 `WireError::VersionNegotiationFailed`。
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::sync::version::{ProtocolVersion, VersionNegotiation};
+    ///
+    /// let version = VersionNegotiation::negotiate(
+    ///     ProtocolVersion::new(1, 0),
+    ///     ProtocolVersion::new(1, 2),
+    /// )
+    /// .unwrap();
+    /// assert_eq!(version, ProtocolVersion::new(1, 0));
+    ///
+    /// assert!(VersionNegotiation::negotiate(
+    ///     ProtocolVersion::new(1, 0),
+    ///     ProtocolVersion::new(2, 0),
+    /// )
+    /// .is_err());
+    /// ```
+    pub fn negotiate(client: ProtocolVersion, server: ProtocolVersion) -> Result<ProtocolVersion> {
+        if client.major != server.major {
+            return Err(WireError::VersionNegotiationFailed {
+                client: (client.major, client.minor),
+                server: (server.major, server.minor),
+            });
+        }
+        Ok(client.min(server))
+    }
+
     /// 检查是否需要升级
     #[must_use]
     pub fn requires_upgrade(&self) -> bool {
@@ -256,6 +295,8 @@ pub struct VersionNegotiationMessage {
     pub preferred_version: ProtocolVersion,
     /// 客户端能力标志
     pub capabilities: CapabilityFlags,
+    /// 本端传输所需的帧尺寸配置（BLE 链路使用 `PROFILE_BLE`）
+    pub frame_profile: FrameProfile,
 }
 
 /// 客户端能力标志
@@ -280,6 +321,18 @@ impl CapabilityFlags {
     /// 支持影子包装（Shadow Wrapping）
     pub const SHADOW_WRAPPING: u8 = 0b0000_1000;
 
+    /// 支持帧密钥棘轮（Key Ratchet）
+    ///
+    /// 双方均支持时帧密钥按 [`RatchetConfig`](crate::sync::ratchet::RatchetConfig)
+    /// 推进，且密钥代数写入帧 AAD；否则整个会话使用会话密钥、帧 AAD 为空。
+    pub const KEY_RATCHET: u8 = 0b0001_0000;
+
+    /// 诱饵帧在 AEAD AAD 中携带标记（Chaff AAD）
+    ///
+    /// 双方均支持时诱饵帧的 AAD 追加 [`CHAFF_AAD_LABEL`](crate::sync::chaff::CHAFF_AAD_LABEL)，
+    /// 接收方仅凭认证结果识别诱饵；否则按解密后的消息体前缀识别。
+    pub const CHAFF_AAD: u8 = 0b0010_0000;
+
     /// 创建新的能力标志
     #[must_use]
     pub const fn new(flags: u8) -> Self {
@@ -304,6 +357,12 @@ impl CapabilityFlags {
     pub const fn as_u8(self) -> u8 {
         self.0
     }
+
+    /// 取双方共同支持的能力（按位与）
+    #[must_use]
+    pub const fn intersect(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
 }
 
 impl Default for CapabilityFlags {
@@ -313,7 +372,9 @@ impl Default for CapabilityFlags {
             Self::HYBRID_HANDSHAKE
                 | Self::CHAFF_SYNC
                 | Self::VETO_SIGNALING
-                | Self::SHADOW_WRAPPING,
+                | Self::SHADOW_WRAPPING
+                | Self::KEY_RATCHET
+                | Self::CHAFF_AAD,
         )
     }
 }
@@ -336,6 +397,7 @@ impl VersionNegotiationMessage {
             supported_versions,
             preferred_version,
             capabilities,
+            frame_profile: PROFILE_DEFAULT,
         }
     }
 
@@ -346,9 +408,17 @@ impl VersionNegotiationMessage {
             supported_versions: vec![version],
             preferred_version: version,
             capabilities: CapabilityFlags::default(),
+            frame_profile: PROFILE_DEFAULT,
         }
     }
 
+    /// 指定本端请求的帧尺寸配置
+    #[must_use]
+    pub fn with_frame_profile(mut self, frame_profile: FrameProfile) -> Self {
+        self.frame_profile = frame_profile;
+        self
+    }
+
     /// 选择最佳匹配版本
     ///
     /// 根据服务器支持的版本和客户端首选版本，选择最佳匹配。
@@ -385,6 +455,104 @@ impl VersionNegotiationMessage {
             .iter()
             .any(|v| server_versions.contains(v))
     }
+
+    /// 根据双方的协商消息计算会话参数
+    ///
+    /// 同一大版本内小版本向后兼容：支持 1.1 的一方也能使用 1.0。
+    /// 因此共同版本取所有同 major 组合中 `(major, min(minor))` 的最大值；
+    /// 能力标志取双方交集；帧尺寸取双方请求中较小的一个（任一端为 BLE
+    /// 链路时整个会话使用 BLE 帧）。结果与调用方是哪一端无关，双方独立
+    /// 计算可得到相同结论。
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - 发起方的协商消息
+    /// * `server` - 响应方的协商消息
+    ///
+    /// # Errors
+    ///
+    /// 没有共同的大版本时返回 `WireError::VersionNegotiationFailed`，
+    /// 其中 `client` / `server` 为双方的首选版本。
+    ///
+    /// 任一端请求不受支持的帧尺寸时返回 `WireError::UnsupportedFrameProfile`。
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use aeternum_core::sync::version::{CapabilityFlags, ProtocolVersion, VersionNegotiationMessage};
+    ///
+    /// let client = VersionNegotiationMessage::default_with_version(ProtocolVersion::new(1, 1));
+    /// let server = VersionNegotiationMessage::default_with_version(ProtocolVersion::new(1, 0));
+    ///
+    /// let outcome = VersionNegotiationMessage::negotiate(&client, &server).unwrap();
+    /// assert_eq!(outcome.version, ProtocolVersion::new(1, 0));
+    /// ```
+    pub fn negotiate(client: &Self, server: &Self) -> Result<NegotiationOutcome> {
+        for profile in [client.frame_profile, server.frame_profile] {
+            if !profile.is_supported() {
+                return Err(WireError::UnsupportedFrameProfile(profile.frame_size));
+            }
+        }
+
+        let mut best: Option<ProtocolVersion> = None;
+
+        for c in client.all_versions() {
+            for s in server.all_versions() {
+                if let Ok(common) = VersionNegotiation::negotiate(*c, *s) {
+                    best = best.max(Some(common));
+                }
+            }
+        }
+
+        let version = best.ok_or(WireError::VersionNegotiationFailed {
+            client: (
+                client.preferred_version.major,
+                client.preferred_version.minor,
+            ),
+            server: (
+                server.preferred_version.major,
+                server.preferred_version.minor,
+            ),
+        })?;
+
+        let frame_profile = if client.frame_profile.frame_size <= server.frame_profile.frame_size {
+            client.frame_profile
+        } else {
+            server.frame_profile
+        };
+
+        Ok(NegotiationOutcome {
+            version,
+            capabilities: client.capabilities.intersect(server.capabilities),
+            frame_profile,
+        })
+    }
+
+    /// 支持的版本（包含首选版本）
+    fn all_versions(&self) -> impl Iterator<Item = &ProtocolVersion> {
+        self.supported_versions
+            .iter()
+            .chain(std::iter::once(&self.preferred_version))
+    }
+}
+
+impl Message for VersionNegotiationMessage {
+    fn payload_type() -> PayloadType {
+        PayloadType::VersionNegotiation
+    }
+}
+
+/// 版本协商结果
+///
+/// 协商完成后保存在会话（`WireProtocol`）中，编解码行为据此分支。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NegotiationOutcome {
+    /// 双方共同支持的最高版本
+    pub version: ProtocolVersion,
+    /// 双方共同支持的能力
+    pub capabilities: CapabilityFlags,
+    /// 会话使用的帧尺寸配置（整个会话不变）
+    pub frame_profile: FrameProfile,
 }
 
 #[cfg(test)]
@@ -401,7 +569,7 @@ mod tests {
     #[test]
     fn test_protocol_version_current() {
         let version = ProtocolVersion::current();
-        assert_eq!(version, ProtocolVersion::new(1, 0));
+        assert_eq!(version, ProtocolVersion::new(1, 1));
     }
 
     #[test]
@@ -497,6 +665,8 @@ mod tests {
         assert!(flags.has(CapabilityFlags::CHAFF_SYNC));
         assert!(flags.has(CapabilityFlags::VETO_SIGNALING));
         assert!(flags.has(CapabilityFlags::SHADOW_WRAPPING));
+        assert!(flags.has(CapabilityFlags::KEY_RATCHET));
+        assert!(flags.has(CapabilityFlags::CHAFF_AAD));
     }
 
     #[test]
@@ -576,6 +746,130 @@ mod tests {
         assert!(!client_msg.has_common_version(&server_versions_v2));
     }
 
+    #[test]
+    fn test_capability_flags_intersect() {
+        let a =
+            CapabilityFlags::new(CapabilityFlags::HYBRID_HANDSHAKE | CapabilityFlags::CHAFF_SYNC);
+        let b = CapabilityFlags::new(
+            CapabilityFlags::HYBRID_HANDSHAKE | CapabilityFlags::VETO_SIGNALING,
+        );
+
+        let common = a.intersect(b);
+        assert_eq!(common.as_u8(), CapabilityFlags::HYBRID_HANDSHAKE);
+        assert_eq!(common, b.intersect(a));
+    }
+
+    #[test]
+    fn test_negotiate_equal_versions() {
+        let v1_1 = ProtocolVersion::new(1, 1);
+        assert_eq!(VersionNegotiation::negotiate(v1_1, v1_1).unwrap(), v1_1);
+    }
+
+    #[test]
+    fn test_negotiate_server_newer() {
+        let client = ProtocolVersion::new(1, 0);
+        let server = ProtocolVersion::new(1, 3);
+        assert_eq!(
+            VersionNegotiation::negotiate(client, server).unwrap(),
+            ProtocolVersion::new(1, 0)
+        );
+    }
+
+    #[test]
+    fn test_negotiate_client_newer() {
+        let client = ProtocolVersion::new(1, 3);
+        let server = ProtocolVersion::new(1, 1);
+        assert_eq!(
+            VersionNegotiation::negotiate(client, server).unwrap(),
+            ProtocolVersion::new(1, 1)
+        );
+    }
+
+    #[test]
+    fn test_negotiate_incompatible_majors() {
+        let client = ProtocolVersion::new(1, 5);
+        let server = ProtocolVersion::new(2, 0);
+        assert!(matches!(
+            VersionNegotiation::negotiate(client, server),
+            Err(WireError::VersionNegotiationFailed {
+                client: (1, 5),
+                server: (2, 0),
+            })
+        ));
+    }
+
+    #[test]
+    fn test_negotiate_keeps_only_common_capabilities() {
+        let client = VersionNegotiationMessage::new(
+            vec![ProtocolVersion::new(1, 0)],
+            ProtocolVersion::new(1, 0),
+            CapabilityFlags::new(CapabilityFlags::HYBRID_HANDSHAKE | CapabilityFlags::CHAFF_SYNC),
+        );
+        let server = VersionNegotiationMessage::new(
+            vec![ProtocolVersion::new(1, 1)],
+            ProtocolVersion::new(1, 1),
+            CapabilityFlags::new(
+                CapabilityFlags::HYBRID_HANDSHAKE | CapabilityFlags::SHADOW_WRAPPING,
+            ),
+        );
+
+        let outcome = VersionNegotiationMessage::negotiate(&client, &server).unwrap();
+        assert_eq!(outcome.version, ProtocolVersion::new(1, 0));
+        assert_eq!(
+            outcome.capabilities,
+            CapabilityFlags::new(CapabilityFlags::HYBRID_HANDSHAKE)
+        );
+    }
+
+    #[test]
+    fn test_negotiate_picks_highest_common_version() {
+        let client = VersionNegotiationMessage::new(
+            vec![ProtocolVersion::new(1, 3), ProtocolVersion::new(2, 0)],
+            ProtocolVersion::new(2, 0),
+            CapabilityFlags::default(),
+        );
+        let server = VersionNegotiationMessage::new(
+            vec![ProtocolVersion::new(1, 2), ProtocolVersion::new(3, 0)],
+            ProtocolVersion::new(3, 0),
+            CapabilityFlags::default(),
+        );
+
+        // 唯一共同大版本为 1，小版本取较低者
+        let outcome = VersionNegotiationMessage::negotiate(&client, &server).unwrap();
+        assert_eq!(outcome.version, ProtocolVersion::new(1, 2));
+        assert_eq!(
+            VersionNegotiationMessage::negotiate(&server, &client).unwrap(),
+            outcome
+        );
+    }
+
+    #[test]
+    fn test_negotiate_frame_profile() {
+        use crate::sync::{PROFILE_BLE, PROFILE_DEFAULT};
+
+        let wifi = VersionNegotiationMessage::default_with_version(ProtocolVersion::current());
+        let ble = wifi.clone().with_frame_profile(PROFILE_BLE);
+
+        // 任一端为 BLE 时会话使用 BLE 帧，且与调用顺序无关
+        let outcome = VersionNegotiationMessage::negotiate(&ble, &wifi).unwrap();
+        assert_eq!(outcome.frame_profile, PROFILE_BLE);
+        assert_eq!(
+            VersionNegotiationMessage::negotiate(&wifi, &ble).unwrap(),
+            outcome
+        );
+
+        let outcome = VersionNegotiationMessage::negotiate(&wifi, &wifi).unwrap();
+        assert_eq!(outcome.frame_profile, PROFILE_DEFAULT);
+
+        // 不受支持的帧尺寸
+        let mut forged = wifi.clone();
+        forged.frame_profile.frame_size = 64;
+        assert!(matches!(
+            VersionNegotiationMessage::negotiate(&forged, &wifi),
+            Err(WireError::UnsupportedFrameProfile(64))
+        ));
+    }
+
     #[test]
     fn test_upgrade_negotiation_requires_upgrade() {
         let client = ProtocolVersion::new(1, 0);
@@ -6,19 +6,27 @@
 //!
 //! - **消息加密传输**: 使用 XChaCha20-Poly1305 AEAD 加密所有消息
 //! - **否决信号处理**: 实现 Invariant #4（否决权优先）
-//! - **重放攻击防护**: Nonce 记忆机制检测重复指令
+//! - **重放攻击防护**: 有界的 [`NonceCache`] 记忆近期 nonce，检测重复指令
 //! - **纪元单调性**: 强制执行 Invariant #1（禁止 epoch 回滚）
+//! - **版本协商**: 会话开始前交换 `VersionNegotiationMessage`，协商结果保存在会话中
+//! - **帧尺寸配置**: 协商确定会话的 `FrameProfile`，此后所有帧尺寸一致，混用即拒绝
+//! - **密钥棘轮**: 每发送 N 帧或 M 字节后派生下一代帧密钥并清零旧密钥
+//!   （见 [`ratchet`](crate::sync::ratchet)），密钥代数写入帧 AAD；
+//!   仅在双方协商 `KEY_RATCHET` 能力时启用，否则沿用旧的帧格式
+//! - **诱饵识别**: 诱饵帧的 AAD 追加 [`CHAFF_AAD_LABEL`]，接收方凭认证结果静默丢弃；
+//!   仅在双方协商 `CHAFF_AAD` 能力时启用，否则按解密后的消息体前缀识别
 //!
 //! ## 架构
 //!
 //! ```text
 //! ┌─────────────────────────────────────────────────────┐
-//! │  WireProtocol (session_key, nonce_memory)           │
+//! │  WireProtocol (send/recv ratchet, nonce_cache)      │
 //! ├─────────────────────────────────────────────────────┤
 //! │  send_message()   → 构建 Frame → AEAD 加密        │
 //! │  receive_message() → AEAD 解密 → 解析 Frame      │
 //! │  handle_veto()    → 验证签名 → 检查 48h 窗口   │
 //! │  nonce_memo()     → 检测重复 → 防重放攻击      │
+//! │  *_negotiation()  → 明文 + MAC → 协商版本/能力  │
 //! └─────────────────────────────────────────────────────┘
 //! ```
 //!
@@ -38,7 +46,7 @@
 //!
 //! // 发送消息
 //! let frame = protocol.send_message(
-//!     PayloadType::Sync,
+//!     PayloadType::EpochSync,
 //!     b"hello".to_vec(),
 //!     1, // epoch
 //! ).unwrap();
@@ -48,11 +56,20 @@
 //! ```
 
 use crate::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce};
-use crate::sync::codec::{MessageCodec, PayloadType};
-use crate::sync::frame::WireFrame;
-use crate::sync::{Result, WireError, AUTH_TAG_SIZE, NONCE_SIZE};
+use crate::sync::chaff::{ChaffGenerator, CHAFF_AAD_LABEL};
+use crate::sync::codec::{Message, MessageCodec, PayloadType};
+use crate::sync::connection::Connection;
+use crate::sync::frame::{SealedFrame, WireFrame, SEALED_FRAME_VERSION};
+use crate::sync::ratchet::{generation_aad, RatchetConfig, RecvRatchet, SendRatchet};
+use crate::sync::version::{
+    CapabilityFlags, NegotiationOutcome, ProtocolVersion, VersionNegotiationMessage,
+};
+use crate::sync::{FrameProfile, Result, WireError, AUTH_TAG_SIZE, NONCE_SIZE, PROFILE_DEFAULT};
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// 48小时否决窗口（秒）
@@ -71,16 +88,167 @@ pub struct VetoMessage {
     pub timestamp: u64,
 }
 
+/// Nonce 缓存默认容量
+pub const DEFAULT_NONCE_CACHE_CAPACITY: usize = 65_536;
+
+/// Nonce 缓存淘汰策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvictionPolicy {
+    /// 淘汰最久未见的 nonce（重复命中会刷新其位置）
+    #[default]
+    Lru,
+    /// 淘汰最早插入的 nonce（重复命中不影响顺序）
+    Fifo,
+}
+
+/// Nonce 缓存配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonceCacheConfig {
+    /// 最多记忆的 nonce 数量（至少为 1）
+    pub capacity: usize,
+    /// 超出容量时的淘汰策略
+    pub policy: EvictionPolicy,
+}
+
+impl Default for NonceCacheConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_NONCE_CACHE_CAPACITY,
+            policy: EvictionPolicy::Lru,
+        }
+    }
+}
+
+/// 重放防护 nonce 缓存
+///
+/// 每个对端会话持有一个缓存，记忆最近见过的 24 字节 nonce。容量有界，
+/// 超出后按 [`EvictionPolicy`] 淘汰；被淘汰的 nonce 不再被识别为重放，
+/// 因此容量应覆盖会话密钥生命周期内的预期消息量。
+///
+/// 内部加锁，`Send + Sync`，可在异步处理器间共享。
+#[derive(Debug)]
+pub struct NonceCache {
+    /// 缓存配置
+    config: NonceCacheConfig,
+    /// 受锁保护的缓存状态
+    inner: Mutex<NonceCacheInner>,
+}
+
+/// Nonce 缓存状态：nonce → 序号，以及按序号排序的淘汰队列
+#[derive(Debug, Default)]
+struct NonceCacheInner {
+    /// nonce 到其当前序号的映射
+    seen: HashMap<[u8; NONCE_SIZE], u64>,
+    /// 序号到 nonce 的映射（最小序号最先淘汰）
+    order: BTreeMap<u64, [u8; NONCE_SIZE]>,
+    /// 下一个序号
+    next_seq: u64,
+}
+
+impl NonceCache {
+    /// 使用默认配置创建缓存
+    pub fn new() -> Self {
+        Self::with_config(NonceCacheConfig::default())
+    }
+
+    /// 使用指定配置创建缓存
+    ///
+    /// 容量为 0 时按 1 处理。
+    pub fn with_config(config: NonceCacheConfig) -> Self {
+        Self {
+            config: NonceCacheConfig {
+                capacity: config.capacity.max(1),
+                ..config
+            },
+            inner: Mutex::new(NonceCacheInner::default()),
+        }
+    }
+
+    /// 缓存配置
+    pub fn config(&self) -> NonceCacheConfig {
+        self.config
+    }
+
+    /// 检查 nonce 是否已见过，未见过则记录
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::ReplayAttack`: 如果 nonce 仍在缓存中（重放攻击）
+    pub fn check_and_insert(&self, nonce: &[u8; NONCE_SIZE]) -> Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+        let seq = inner.next_seq;
+        inner.next_seq += 1;
+
+        if let Some(&previous) = inner.seen.get(nonce) {
+            if self.config.policy == EvictionPolicy::Lru {
+                inner.order.remove(&previous);
+                inner.order.insert(seq, *nonce);
+                inner.seen.insert(*nonce, seq);
+            }
+            return Err(WireError::ReplayAttack(*nonce));
+        }
+
+        if inner.seen.len() >= self.config.capacity {
+            if let Some((_, evicted)) = inner.order.pop_first() {
+                inner.seen.remove(&evicted);
+            }
+        }
+        inner.seen.insert(*nonce, seq);
+        inner.order.insert(seq, *nonce);
+        Ok(())
+    }
+
+    /// nonce 是否仍在缓存中（不影响淘汰顺序）
+    pub fn contains(&self, nonce: &[u8; NONCE_SIZE]) -> bool {
+        self.inner.lock().unwrap().seen.contains_key(nonce)
+    }
+
+    /// 当前记忆的 nonce 数量
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().seen.len()
+    }
+
+    /// 缓存是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清空缓存并释放内存
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.seen = HashMap::new();
+        inner.order.clear();
+    }
+}
+
+impl Default for NonceCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Wire 协议核心
 ///
-/// 维护会话密钥和 nonce 记忆，提供完整的消息发送/接收功能。
+/// 维护会话密钥和 nonce 缓存，提供完整的消息发送/接收功能。
+///
+/// 会话密钥是棘轮的第 0 代。发送方按 [`RatchetConfig`] 推进发送密钥；
+/// 接收方在当前代及其前后各一代内尝试解密，跟随发送方推进。
+/// 对端未协商 [`CapabilityFlags::KEY_RATCHET`] 时两个方向都停留在会话密钥。
 pub struct WireProtocol {
-    /// 会话密钥（XChaCha20-Poly1305）
-    session_key: XChaCha20Key,
-    /// Nonce 记忆（已使用的 nonce 集合）
-    nonce_memory: HashSet<[u8; NONCE_SIZE]>,
+    /// 发送方向的密钥棘轮
+    send: SendRatchet,
+    /// 接收方向的密钥棘轮
+    recv: RecvRatchet,
+    /// 重放防护 nonce 缓存（对端已使用的 nonce）
+    nonce_cache: NonceCache,
     /// 当前 epoch（用于单调性检查）
     current_epoch: u32,
+    /// 版本协商结果（协商完成前为 None）
+    negotiated: Option<NegotiationOutcome>,
+    /// 连接生命周期状态机（见 [`connection`](crate::sync::connection)）
+    pub(super) connection: Connection,
+    /// 最近发送的应用消息类型（诱饵帧模仿该类型）
+    pub(super) last_sent_type: PayloadType,
 }
 
 impl WireProtocol {
@@ -101,12 +269,38 @@ impl WireProtocol {
     /// ```
     pub fn new(session_key: XChaCha20Key) -> Self {
         Self {
-            session_key,
-            nonce_memory: HashSet::new(),
+            send: SendRatchet::new(session_key.clone(), RatchetConfig::default()),
+            recv: RecvRatchet::new(session_key),
+            nonce_cache: NonceCache::new(),
             current_epoch: 0,
+            negotiated: None,
+            connection: Connection::new(),
+            last_sent_type: PayloadType::EpochSync,
         }
     }
 
+    /// 使用指定的 nonce 缓存配置
+    pub fn with_nonce_cache(mut self, config: NonceCacheConfig) -> Self {
+        self.nonce_cache = NonceCache::with_config(config);
+        self
+    }
+
+    /// 使用指定的密钥棘轮配置（仅影响发送方向）
+    pub fn with_ratchet(mut self, config: RatchetConfig) -> Self {
+        self.send.set_config(config);
+        self
+    }
+
+    /// 发送密钥的当前代数
+    pub fn send_generation(&self) -> u32 {
+        self.send.generation()
+    }
+
+    /// 接收密钥的当前代数（对端最近一次推进到的代数）
+    pub fn recv_generation(&self) -> u32 {
+        self.recv.generation()
+    }
+
     /// 发送消息
     ///
     /// 构建 WireFrame、应用 Padding、AEAD 加密、添加认证标签。
@@ -119,7 +313,7 @@ impl WireProtocol {
     ///
     /// # Returns
     ///
-    /// 返回序列化后的 WireFrame（8192 字节）。
+    /// 返回序列化后的帧（尺寸由会话帧配置决定，默认 8192 字节；布局由会话版本决定）。
     ///
     /// # Errors
     ///
@@ -132,22 +326,105 @@ impl WireProtocol {
         epoch: u32,
     ) -> Result<Vec<u8>> {
         // INVARIANT #1: Epoch Monotonicity - 禁止回滚
-        if epoch < self.current_epoch {
-            return Err(WireError::EpochRegression {
-                current: self.current_epoch,
-                attempted: epoch,
-            });
+        self.check_epoch(epoch)?;
+
+        // 构建并加密帧（自动填充到会话帧尺寸）
+        let session = self.session();
+        let (cipher, generation) = self.send.cipher();
+        let frame = Self::seal_frame(
+            cipher,
+            generation,
+            payload_type,
+            &plaintext,
+            epoch,
+            &session,
+        )?;
+
+        // 更新当前 epoch，计数后按需推进发送密钥
+        self.current_epoch = epoch;
+        if self.ratchet_enabled() {
+            self.send.record(plaintext.len());
+        }
+        if !matches!(
+            payload_type,
+            PayloadType::VersionNegotiation | PayloadType::Error
+        ) {
+            self.last_sent_type = payload_type;
         }
 
-        // 生成随机 nonce
-        let nonce = XChaCha20Nonce::random();
-        let nonce_bytes = *nonce.as_bytes();
+        // 注意：不在发送时记录 nonce
+        // nonce 记忆应该在接收消息时使用，防止重放攻击
 
-        // 创建 AEAD cipher
-        let cipher = AeadCipher::new(&self.session_key);
+        Ok(frame)
+    }
 
-        // AEAD 加密（认证标签自动附加到密文）
-        let ciphertext_with_tag = cipher.encrypt(&nonce, &plaintext, None)?;
+    /// 使用会话 cipher 加密明文并封装为帧
+    ///
+    /// nonce 与填充取自 `OsRng`，会话启用密钥棘轮时 AEAD 的 AAD 绑定密钥代数 `generation`，
+    /// 帧填充到 `session.frame_profile.frame_size`。
+    /// 真实消息与诱饵（chaff）消息共用此路径，保证两者在字节层面不可区分。
+    pub(crate) fn seal_frame(
+        cipher: &AeadCipher,
+        generation: u32,
+        payload_type: PayloadType,
+        plaintext: &[u8],
+        epoch: u32,
+        session: &NegotiationOutcome,
+    ) -> Result<Vec<u8>> {
+        Self::seal_frame_with_rng(
+            cipher,
+            &Self::frame_aad(session, generation),
+            &mut OsRng,
+            payload_type,
+            plaintext,
+            epoch,
+            session,
+        )
+    }
+
+    /// 使用调用方提供的 CSPRNG 生成 nonce 与填充并封装帧
+    ///
+    /// 会话版本不低于 [`SEALED_FRAME_VERSION`] 时使用密封布局
+    /// （[`WireFrame::seal_padded`]：明文头作 AAD，长度与填充一并加密）；
+    /// 1.0 会话保持明文长度的 [`WireFrame`] 布局以兼容旧对端，填充同样取自 `rng`。
+    /// AEAD 关联数据 `aad` 由调用方给出：真实帧为 [`frame_aad`](Self::frame_aad)，
+    /// 诱饵帧为 [`chaff_frame_aad`](Self::chaff_frame_aad)。
+    ///
+    /// 供 [`ChaffGenerator`] 使用自身 CSPRNG，使种子模式下的诱饵帧可复现。
+    pub(crate) fn seal_frame_with_rng(
+        cipher: &AeadCipher,
+        aad: &[u8],
+        rng: &mut (impl RngCore + CryptoRng),
+        payload_type: PayloadType,
+        plaintext: &[u8],
+        epoch: u32,
+        session: &NegotiationOutcome,
+    ) -> Result<Vec<u8>> {
+        let profile = session.frame_profile;
+        if plaintext.len() > profile.max_body_size {
+            return Err(WireError::InvalidFrameSize(
+                NONCE_SIZE + 4 + 1 + 2 + plaintext.len() + AUTH_TAG_SIZE,
+            ));
+        }
+
+        if session.version >= SEALED_FRAME_VERSION {
+            return WireFrame::seal_padded(
+                cipher,
+                rng,
+                epoch,
+                payload_type,
+                plaintext,
+                profile,
+                aad,
+            );
+        }
+
+        let mut nonce = [0u8; NONCE_SIZE];
+        rng.fill_bytes(&mut nonce);
+
+        // AEAD 加密（认证标签自动附加到密文，密钥代数作为 AAD）
+        let ciphertext_with_tag =
+            cipher.encrypt(&XChaCha20Nonce::from_bytes(nonce), plaintext, Some(aad))?;
 
         // 提取认证标签（最后 16 字节）
         let ciphertext_len = ciphertext_with_tag.len() - AUTH_TAG_SIZE;
@@ -159,22 +436,16 @@ impl WireProtocol {
             tag
         };
 
-        // 构建 WireFrame（自动填充到 8192 字节）
-        let frame = WireFrame::new(
-            nonce_bytes,
+        // 构建 WireFrame（自动填充到会话帧尺寸），填充不参与认证，以随机字节覆盖
+        let mut frame = WireFrame::new_with_profile(
+            nonce,
             epoch,
             payload_type.to_byte(),
             encrypted_body,
             auth_tag,
+            profile,
         )?;
-
-        // 更新当前 epoch
-        self.current_epoch = epoch;
-
-        // 注意：不在发送时记录 nonce
-        // nonce 记忆应该在接收消息时使用，防止重放攻击
-
-        // 序列化 Frame
+        rng.fill_bytes(&mut frame.padding);
         frame.serialize()
     }
 
@@ -182,9 +453,12 @@ impl WireProtocol {
     ///
     /// 验证认证标签、AEAD 解密、移除 Padding、解析 Payload。
     ///
+    /// 注意：此方法返回原始解密结果，诱饵（chaff）消息也会被返回。
+    /// 应用层应使用 [`receive`](Self::receive)，它会静默丢弃诱饵消息。
+    ///
     /// # Arguments
     ///
-    /// * `frame_bytes` - 接收到的 8192 字节 Frame
+    /// * `frame_bytes` - 接收到的 Frame（尺寸必须符合会话帧配置）
     ///
     /// # Returns
     ///
@@ -192,32 +466,41 @@ impl WireProtocol {
     ///
     /// # Errors
     ///
+    /// - `WireError::FrameProfileMismatch`: 如果帧属于其他帧尺寸配置
     /// - `WireError::ReplayAttack`: 如果 nonce 已被使用（重放攻击）
     /// - `WireError::AuthenticationFailed`: 如果认证标签验证失败
+    /// - `WireError::Crypto`: 如果帧在当前密钥代及前后各一代下均无法解密
+    ///   （篡改、错误密钥或超出代数窗口）
     /// - `WireError::EpochRegression`: 如果 epoch 回滚（违反 Invariant #1）
     pub fn receive_message(&mut self, frame_bytes: &[u8]) -> Result<(PayloadType, Vec<u8>)> {
-        // 反序列化 WireFrame
-        let frame = WireFrame::deserialize(frame_bytes)?;
+        self.receive_frame(frame_bytes)
+            .map(|(payload_type, plaintext, _)| (payload_type, plaintext))
+    }
+
+    /// 接收帧，并报告其是否以诱饵 AAD（[`chaff_frame_aad`](Self::chaff_frame_aad)）认证
+    fn receive_frame(&mut self, frame_bytes: &[u8]) -> Result<(PayloadType, Vec<u8>, bool)> {
+        if self.protocol_version() >= SEALED_FRAME_VERSION {
+            return self.receive_sealed(frame_bytes);
+        }
+
+        // 反序列化 WireFrame（拒绝其他帧尺寸配置）
+        let profile = self.frame_profile();
+        let frame = WireFrame::deserialize_with_profile(frame_bytes, profile)?;
 
         // 验证 Frame 完整性
-        frame.validate()?;
+        frame.validate_with_profile(profile)?;
 
         // 提取 nonce
         let nonce_bytes = frame.nonce();
 
         // 检测重放攻击
-        if self.nonce_memory.contains(nonce_bytes) {
+        if self.nonce_cache.contains(nonce_bytes) {
             return Err(WireError::ReplayAttack(*nonce_bytes));
         }
 
-        // INVARIANT #1: 检查 epoch 单调性
+        // INVARIANT #1: 检查 epoch 单调性（解密前快速拒绝）
         let frame_epoch = frame.epoch();
-        if frame_epoch < self.current_epoch {
-            return Err(WireError::EpochRegression {
-                current: self.current_epoch,
-                attempted: frame_epoch,
-            });
-        }
+        self.check_epoch(frame_epoch)?;
 
         // 提取 payload type
         let payload_type = MessageCodec::decode_payload_type(&frame)?;
@@ -231,17 +514,100 @@ impl WireProtocol {
         let mut ciphertext_with_tag = encrypted_body;
         ciphertext_with_tag.extend_from_slice(&auth_tag);
 
-        // AEAD 解密
-        let cipher = AeadCipher::new(&self.session_key);
-        let plaintext = cipher.decrypt(&nonce, &ciphertext_with_tag, None)?;
+        // AEAD 解密（在 g、g+1、g-1 代密钥下尝试；g+1 成功时接收方随之推进）
+        let (plaintext, chaff) =
+            self.open_frame(|cipher, aad| cipher.decrypt(&nonce, &ciphertext_with_tag, Some(aad)))?;
 
         // 记录 nonce（防止重放）
-        self.nonce_memory.insert(*nonce_bytes);
+        self.nonce_cache.check_and_insert(nonce_bytes)?;
 
-        // 更新当前 epoch
-        self.current_epoch = frame_epoch;
+        // 接受帧 epoch，推进当前 epoch
+        self.accept_frame(frame_epoch)?;
+
+        Ok((payload_type, plaintext, chaff))
+    }
+
+    /// 接收密封布局的帧（会话版本不低于 [`SEALED_FRAME_VERSION`]）
+    ///
+    /// 检查顺序与 1.0 布局相同；明文头在解密时一并认证。
+    fn receive_sealed(&mut self, frame_bytes: &[u8]) -> Result<(PayloadType, Vec<u8>, bool)> {
+        let frame = SealedFrame::parse(frame_bytes, self.frame_profile())?;
+
+        // 检测重放攻击
+        let nonce_bytes = frame.nonce();
+        if self.nonce_cache.contains(&nonce_bytes) {
+            return Err(WireError::ReplayAttack(nonce_bytes));
+        }
+
+        // INVARIANT #1: 检查 epoch 单调性（解密前快速拒绝）
+        let frame_epoch = frame.epoch();
+        self.check_epoch(frame_epoch)?;
+
+        let payload_type = frame.payload_type()?;
+
+        // AEAD 解密（在 g、g+1、g-1 代密钥下尝试；g+1 成功时接收方随之推进）
+        let (padded, chaff) = self.open_frame(|cipher, aad| frame.decrypt(cipher, aad))?;
+        let plaintext = SealedFrame::unpad(padded)?;
+
+        // 记录 nonce（防止重放）
+        self.nonce_cache.check_and_insert(&nonce_bytes)?;
+
+        // 接受帧 epoch，推进当前 epoch
+        self.accept_frame(frame_epoch)?;
+
+        Ok((payload_type, plaintext, chaff))
+    }
+
+    /// 接收消息并静默丢弃诱饵（chaff）消息
+    ///
+    /// 与 [`receive_message`](Self::receive_message) 执行相同的验证
+    /// （重放检测、epoch 单调性、AEAD 认证），但帧以诱饵 AAD 认证时返回 `Ok(None)`，
+    /// 不向上层暴露任何事件。会话未协商 [`CapabilityFlags::CHAFF_AAD`] 时，
+    /// 改为按消息体的 `PayloadType::Chaff` 标记识别诱饵。
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some((PayloadType, 明文)))`: 真实消息
+    /// - `Ok(None)`: 诱饵消息（已丢弃）
+    pub fn receive(&mut self, frame_bytes: &[u8]) -> Result<Option<(PayloadType, Vec<u8>)>> {
+        let (payload_type, plaintext, chaff) = self.receive_frame(frame_bytes)?;
+
+        let chaff = if self.capabilities().has(CapabilityFlags::CHAFF_AAD) {
+            chaff
+        } else {
+            ChaffGenerator::is_chaff_body(&plaintext)
+        };
+        if chaff {
+            return Ok(None);
+        }
 
-        Ok((payload_type, plaintext))
+        Ok(Some((payload_type, plaintext)))
+    }
+
+    /// 使用当前发送密钥生成一帧诱饵（chaff）消息
+    ///
+    /// 诱饵帧与真实帧同样计入密钥棘轮，使其始终可被对端的接收棘轮解密。
+    /// 明文类型字节模仿最近发送的应用消息类型（默认 `EpochSync`）。
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::UnexpectedFrame`: 连接处于握手或关闭阶段（此时没有可模仿的流量）
+    /// - `WireError::ConnectionClosed`: 连接已关闭
+    pub fn send_chaff(&mut self, generator: &mut ChaffGenerator) -> Result<Vec<u8>> {
+        let payload_type = self.chaff_payload_type()?;
+        let session = self.session();
+        let (cipher, generation) = self.send.cipher();
+        let (frame, body_len) = generator.seal_chaff(
+            cipher,
+            generation,
+            self.current_epoch,
+            &session,
+            payload_type,
+        )?;
+        if self.ratchet_enabled() {
+            self.send.record(body_len);
+        }
+        Ok(frame)
     }
 
     /// 处理否决信号（Invariant #4）
@@ -318,7 +684,7 @@ impl WireProtocol {
     /// assert!(!protocol.nonce_memo(&nonce));
     /// ```
     pub fn nonce_memo(&self, nonce: &XChaCha20Nonce) -> bool {
-        self.nonce_memory.contains(nonce.as_bytes())
+        self.nonce_cache.contains(nonce.as_bytes())
     }
 
     /// 获取当前 epoch
@@ -326,12 +692,283 @@ impl WireProtocol {
         self.current_epoch
     }
 
+    /// 接受入站帧的 epoch（网络边界上的 Invariant #1）
+    ///
+    /// 帧 epoch 不小于当前 epoch 时接受，并将当前 epoch 推进到帧 epoch。
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::EpochRegression`: 如果 `frame_epoch < current_epoch`，当前 epoch 不变
+    pub fn accept_frame(&mut self, frame_epoch: u32) -> Result<()> {
+        self.check_epoch(frame_epoch)?;
+        self.current_epoch = frame_epoch;
+        Ok(())
+    }
+
+    /// 检查 epoch 不早于当前 epoch（Invariant #1）
+    fn check_epoch(&self, epoch: u32) -> Result<()> {
+        if epoch < self.current_epoch {
+            return Err(WireError::EpochRegression {
+                current: self.current_epoch,
+                attempted: epoch,
+            });
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------------
+    // 版本协商 (Version Negotiation)
+    // ------------------------------------------------------------------------
+
+    /// 发起版本协商：构建携带本端协商消息的帧
+    ///
+    /// 协商完成前使用本端请求的帧尺寸（`local.frame_profile`），
+    /// 协商完成后使用会话帧尺寸。
+    ///
+    /// # Arguments
+    ///
+    /// * `local` - 本端支持的版本、能力与帧尺寸
+    ///
+    /// # Returns
+    ///
+    /// 返回序列化后的协商帧。
+    pub fn offer_negotiation(&self, local: &VersionNegotiationMessage) -> Result<Vec<u8>> {
+        let profile = self
+            .negotiated
+            .map_or(local.frame_profile, |outcome| outcome.frame_profile);
+        let body = local.serialize_message()?;
+        let epoch = self.current_epoch;
+        let nonce = XChaCha20Nonce::random();
+
+        // 空明文 + 关联数据 = 仅计算 MAC
+        let (cipher, _) = self.send.cipher();
+        let aad = Self::negotiation_aad(epoch, &body);
+        let tag_bytes = cipher.encrypt(&nonce, &[], Some(&aad))?;
+        let mut auth_tag = [0u8; AUTH_TAG_SIZE];
+        auth_tag.copy_from_slice(&tag_bytes);
+
+        WireFrame::new_with_profile(
+            *nonce.as_bytes(),
+            epoch,
+            PayloadType::VersionNegotiation.to_byte(),
+            body,
+            auth_tag,
+            profile,
+        )?
+        .serialize()
+    }
+
+    /// 响应版本协商
+    ///
+    /// 验证发起方的协商帧、计算协商结果并保存到会话，然后返回本端的协商帧。
+    /// 返回的协商帧已使用协商后的帧尺寸。
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::AuthenticationFailed`: 帧 MAC 校验失败
+    /// - `WireError::VersionNegotiationFailed`: 双方没有共同大版本
+    /// - `WireError::FrameProfileMismatch`: 协商帧尺寸与发起方声明的不符，
+    ///   或会话已使用其他帧尺寸
+    pub fn respond_to_negotiation(
+        &mut self,
+        client_frame: &[u8],
+        local: &VersionNegotiationMessage,
+    ) -> Result<Vec<u8>> {
+        let client = self.open_negotiation(client_frame)?;
+        Self::check_frame_profile(client.frame_profile, client_frame.len())?;
+        let outcome = VersionNegotiationMessage::negotiate(&client, local)?;
+        self.set_negotiated(outcome)?;
+        self.offer_negotiation(local)
+    }
+
+    /// 完成版本协商（发起方）
+    ///
+    /// 验证响应方的协商帧，并独立计算与响应方相同的协商结果。
+    ///
+    /// # Errors
+    ///
+    /// - `WireError::AuthenticationFailed`: 帧 MAC 校验失败
+    /// - `WireError::VersionNegotiationFailed`: 双方没有共同大版本
+    /// - `WireError::FrameProfileMismatch`: 响应帧未使用协商后的帧尺寸，
+    ///   或会话已使用其他帧尺寸
+    pub fn complete_negotiation(
+        &mut self,
+        server_frame: &[u8],
+        local: &VersionNegotiationMessage,
+    ) -> Result<NegotiationOutcome> {
+        let server = self.open_negotiation(server_frame)?;
+        let outcome = VersionNegotiationMessage::negotiate(local, &server)?;
+        Self::check_frame_profile(outcome.frame_profile, server_frame.len())?;
+        self.set_negotiated(outcome)?;
+        Ok(outcome)
+    }
+
+    /// 保存协商结果；会话帧尺寸一经确定不可更改
+    fn set_negotiated(&mut self, outcome: NegotiationOutcome) -> Result<()> {
+        if let Some(current) = self.negotiated {
+            Self::check_frame_profile(current.frame_profile, outcome.frame_profile.frame_size)?;
+        }
+        self.negotiated = Some(outcome);
+        Ok(())
+    }
+
+    /// 检查帧尺寸是否符合期望的帧配置
+    fn check_frame_profile(expected: FrameProfile, frame_size: usize) -> Result<()> {
+        if frame_size != expected.frame_size {
+            return Err(WireError::FrameProfileMismatch {
+                expected: expected.frame_size,
+                actual: frame_size,
+            });
+        }
+        Ok(())
+    }
+
+    /// 获取版本协商结果
+    pub fn negotiated(&self) -> Option<&NegotiationOutcome> {
+        self.negotiated.as_ref()
+    }
+
+    /// 会话协商结果；未协商时为当前版本、默认能力与 `PROFILE_DEFAULT`
+    pub(crate) fn session(&self) -> NegotiationOutcome {
+        NegotiationOutcome {
+            version: self.protocol_version(),
+            capabilities: self.capabilities(),
+            frame_profile: self.frame_profile(),
+        }
+    }
+
+    /// 会话使用的协议版本（未协商时为 `PROTOCOL_VERSION`）
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.negotiated
+            .map_or_else(ProtocolVersion::current, |outcome| outcome.version)
+    }
+
+    /// 会话启用的能力（未协商时为默认能力）
+    pub fn capabilities(&self) -> CapabilityFlags {
+        self.negotiated
+            .map_or_else(CapabilityFlags::default, |outcome| outcome.capabilities)
+    }
+
+    /// 会话使用的帧尺寸配置（未协商时为 `PROFILE_DEFAULT`）
+    pub fn frame_profile(&self) -> FrameProfile {
+        self.negotiated
+            .map_or(PROFILE_DEFAULT, |outcome| outcome.frame_profile)
+    }
+
+    /// 会话是否启用帧密钥棘轮（未协商时按默认能力启用）
+    pub fn ratchet_enabled(&self) -> bool {
+        self.capabilities().has(CapabilityFlags::KEY_RATCHET)
+    }
+
+    /// 对端是否支持诱饵流量（决定是否调度 chaff 帧）
+    pub fn chaff_enabled(&self) -> bool {
+        self.capabilities().has(CapabilityFlags::CHAFF_SYNC)
+    }
+
+    /// 验证协商帧并解析对端的协商消息
+    ///
+    /// 协商帧可以是任一受支持的帧尺寸；调用方负责检查尺寸与协商结果一致。
+    fn open_negotiation(&mut self, frame_bytes: &[u8]) -> Result<VersionNegotiationMessage> {
+        let profile = FrameProfile::for_frame_size(frame_bytes.len())
+            .ok_or(WireError::InvalidFrameSize(frame_bytes.len()))?;
+        let frame = WireFrame::deserialize_with_profile(frame_bytes, profile)?;
+        frame.validate_with_profile(profile)?;
+
+        let payload_type = MessageCodec::decode_payload_type(&frame)?;
+        if payload_type != PayloadType::VersionNegotiation {
+            return Err(WireError::InvalidPayloadType(frame.payload_type));
+        }
+
+        let nonce_bytes = frame.nonce();
+        if self.nonce_cache.contains(nonce_bytes) {
+            return Err(WireError::ReplayAttack(*nonce_bytes));
+        }
+
+        let nonce = XChaCha20Nonce::from_bytes(*nonce_bytes);
+        let aad = Self::negotiation_aad(frame.epoch(), &frame.encrypted_body);
+        self.open_frame(|cipher, _| cipher.decrypt(&nonce, &frame.auth_tag, Some(&aad)))
+            .map_err(|_| WireError::AuthenticationFailed)?;
+
+        self.nonce_cache.check_and_insert(nonce_bytes)?;
+
+        VersionNegotiationMessage::deserialize_message(&frame.encrypted_body)
+    }
+
+    /// 协商帧的 MAC 关联数据：帧头（类型 + epoch）与明文消息体
+    ///
+    /// 协商帧在双方得知协商结果之前交换，因此不含密钥代数。
+    fn negotiation_aad(epoch: u32, body: &[u8]) -> Vec<u8> {
+        let mut aad = Vec::with_capacity(1 + 4 + body.len());
+        aad.push(PayloadType::VersionNegotiation.to_byte());
+        aad.extend_from_slice(&epoch.to_be_bytes());
+        aad.extend_from_slice(body);
+        aad
+    }
+
+    /// 帧的 AEAD 关联数据
+    ///
+    /// 会话启用 [`CapabilityFlags::KEY_RATCHET`] 时为密钥代数；否则为空，
+    /// 与不支持密钥棘轮的对端一致。
+    fn frame_aad(session: &NegotiationOutcome, generation: u32) -> Vec<u8> {
+        if session.capabilities.has(CapabilityFlags::KEY_RATCHET) {
+            generation_aad(generation).to_vec()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// 诱饵帧的 AEAD 关联数据
+    ///
+    /// 会话启用 [`CapabilityFlags::CHAFF_AAD`] 时在 [`frame_aad`](Self::frame_aad)
+    /// 之后追加 [`CHAFF_AAD_LABEL`]；否则与真实帧相同。
+    pub(crate) fn chaff_frame_aad(session: &NegotiationOutcome, generation: u32) -> Vec<u8> {
+        let mut aad = Self::frame_aad(session, generation);
+        if session.capabilities.has(CapabilityFlags::CHAFF_AAD) {
+            aad.extend_from_slice(CHAFF_AAD_LABEL);
+        }
+        aad
+    }
+
+    /// 使用接收密钥解密帧
+    ///
+    /// 启用密钥棘轮时在 g、g+1、g-1 代密钥下尝试（`open` 收到对应代数的 AAD）；
+    /// 否则只使用会话密钥，AAD 为空。启用 [`CapabilityFlags::CHAFF_AAD`] 时，
+    /// 每代密钥再以追加 [`CHAFF_AAD_LABEL`] 的 AAD 尝试一次，返回值的 `bool`
+    /// 表示帧是否以诱饵 AAD 认证。
+    fn open_frame<T>(
+        &mut self,
+        open: impl Fn(&AeadCipher, &[u8]) -> crate::crypto::error::Result<T>,
+    ) -> crate::crypto::error::Result<(T, bool)> {
+        let chaff_aad = self.capabilities().has(CapabilityFlags::CHAFF_AAD);
+        let open_either = |cipher: &AeadCipher, aad: &[u8]| {
+            open(cipher, aad)
+                .map(|value| (value, false))
+                .or_else(|err| {
+                    if !chaff_aad {
+                        return Err(err);
+                    }
+                    let chaff = [aad, CHAFF_AAD_LABEL].concat();
+                    open(cipher, &chaff)
+                        .map(|value| (value, true))
+                        .map_err(|_| err)
+                })
+        };
+
+        if self.ratchet_enabled() {
+            return self
+                .recv
+                .open(|cipher, generation| open_either(cipher, &generation_aad(generation)));
+        }
+
+        let (cipher, _) = self.recv.cipher();
+        open_either(cipher, &[])
+    }
+
     /// 清空 nonce 记忆
     ///
     /// 警告：仅在确定不会有旧消息重放时使用（例如密钥轮换后）。
     pub fn clear_nonce_memory(&mut self) {
-        self.nonce_memory.clear();
-        self.nonce_memory.shrink_to_fit();
+        self.nonce_cache.clear();
     }
 }
 
@@ -339,6 +976,8 @@ impl WireProtocol {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sync::frame::padding_entropy_ok;
+    use crate::sync::ratchet::DEFAULT_RATCHET_FRAMES;
     use crate::sync::FRAME_SIZE;
 
     #[test]
@@ -361,7 +1000,7 @@ mod tests {
 
         // 发送消息
         let frame_bytes = sender
-            .send_message(PayloadType::Sync, plaintext.clone(), epoch)
+            .send_message(PayloadType::EpochSync, plaintext.clone(), epoch)
             .expect("Failed to send message");
 
         // 验证 Frame 大小
@@ -372,7 +1011,7 @@ mod tests {
             .receive_message(&frame_bytes)
             .expect("Failed to receive message");
 
-        assert_eq!(payload_type, PayloadType::Sync);
+        assert_eq!(payload_type, PayloadType::EpochSync);
         assert_eq!(decrypted, plaintext);
     }
 
@@ -382,10 +1021,10 @@ mod tests {
         let mut protocol = WireProtocol::new(key);
 
         // 发送 epoch = 1
-        let _ = protocol.send_message(PayloadType::Sync, vec![1, 2, 3], 1);
+        let _ = protocol.send_message(PayloadType::EpochSync, vec![1, 2, 3], 1);
 
         // 尝试发送 epoch = 0（回滚）
-        let result = protocol.send_message(PayloadType::Sync, vec![4, 5, 6], 0);
+        let result = protocol.send_message(PayloadType::EpochSync, vec![4, 5, 6], 0);
         assert!(matches!(result, Err(WireError::EpochRegression { .. })));
     }
 
@@ -398,7 +1037,7 @@ mod tests {
 
         // 发送消息
         let frame_bytes = sender
-            .send_message(PayloadType::Sync, vec![1, 2, 3], 1)
+            .send_message(PayloadType::EpochSync, vec![1, 2, 3], 1)
             .expect("Failed to send message");
 
         // 第一次接收应该成功
@@ -428,10 +1067,10 @@ mod tests {
         assert!(!receiver.nonce_memo(&nonce2));
 
         // 发送并接收消息会记录 nonce 到 receiver
-        let _ = sender.send_message(PayloadType::Sync, vec![1, 2, 3], 1);
+        let _ = sender.send_message(PayloadType::EpochSync, vec![1, 2, 3], 1);
 
         let frame_bytes = sender
-            .send_message(PayloadType::Sync, vec![4, 5, 6], 2)
+            .send_message(PayloadType::EpochSync, vec![4, 5, 6], 2)
             .expect("Failed to send");
 
         let _ = receiver
@@ -501,14 +1140,14 @@ mod tests {
 
         // 空消息
         let frame_bytes = sender
-            .send_message(PayloadType::Sync, vec![], 1)
+            .send_message(PayloadType::EpochSync, vec![], 1)
             .expect("Failed to send empty message");
 
         let (payload_type, decrypted) = receiver
             .receive_message(&frame_bytes)
             .expect("Failed to receive empty message");
 
-        assert_eq!(payload_type, PayloadType::Sync);
+        assert_eq!(payload_type, PayloadType::EpochSync);
         assert!(decrypted.is_empty());
     }
 
@@ -522,14 +1161,14 @@ mod tests {
         // 最大尺寸消息
         let max_plaintext = vec![0xAB; crate::sync::MAX_BODY_SIZE];
         let frame_bytes = sender
-            .send_message(PayloadType::Sync, max_plaintext.clone(), 1)
+            .send_message(PayloadType::EpochSync, max_plaintext.clone(), 1)
             .expect("Failed to send max size message");
 
         let (payload_type, decrypted) = receiver
             .receive_message(&frame_bytes)
             .expect("Failed to receive max size message");
 
-        assert_eq!(payload_type, PayloadType::Sync);
+        assert_eq!(payload_type, PayloadType::EpochSync);
         assert_eq!(decrypted, max_plaintext);
     }
 
@@ -540,7 +1179,7 @@ mod tests {
 
         // 超过最大尺寸的消息
         let too_large = vec![0; crate::sync::MAX_BODY_SIZE + 1];
-        let result = protocol.send_message(PayloadType::Sync, too_large, 1);
+        let result = protocol.send_message(PayloadType::EpochSync, too_large, 1);
 
         assert!(matches!(result, Err(WireError::InvalidFrameSize(_))));
     }
@@ -556,14 +1195,14 @@ mod tests {
         for epoch in 1..=5 {
             let plaintext = format!("message {}", epoch).into_bytes();
             let frame_bytes = sender
-                .send_message(PayloadType::Sync, plaintext.clone(), epoch)
+                .send_message(PayloadType::EpochSync, plaintext.clone(), epoch)
                 .expect("Failed to send message");
 
             let (payload_type, decrypted) = receiver
                 .receive_message(&frame_bytes)
                 .expect("Failed to receive message");
 
-            assert_eq!(payload_type, PayloadType::Sync);
+            assert_eq!(payload_type, PayloadType::EpochSync);
             assert_eq!(decrypted, plaintext);
         }
     }
@@ -576,7 +1215,7 @@ mod tests {
         let mut receiver = WireProtocol::new(key);
 
         let frame_bytes = sender
-            .send_message(PayloadType::Sync, vec![1, 2, 3], 1)
+            .send_message(PayloadType::EpochSync, vec![1, 2, 3], 1)
             .expect("Failed to send message");
 
         // 篡改 Frame（修改认证标签，最后一个字节）
@@ -588,6 +1227,151 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_receive_discards_chaff() {
+        let key = XChaCha20Key::generate();
+
+        let mut sender = WireProtocol::new(key.clone());
+        let mut receiver = WireProtocol::new(key.clone());
+        let mut generator = ChaffGenerator::new();
+
+        let chaff_bytes = generator.generate_frame(&key, 1).unwrap();
+
+        // Chaff decrypts with a valid tag and carries the marker
+        let (payload_type, body) = WireProtocol::new(key.clone())
+            .receive_message(&chaff_bytes)
+            .expect("Chaff must decrypt");
+        assert_eq!(payload_type, PayloadType::EpochSync);
+        assert!(ChaffGenerator::is_chaff_body(&body));
+
+        // The receive path drops it silently
+        assert!(receiver.receive(&chaff_bytes).unwrap().is_none());
+
+        // Real messages still come through
+        let real_bytes = sender
+            .send_message(PayloadType::EpochSync, b"real".to_vec(), 1)
+            .unwrap();
+        let (payload_type, body) = receiver.receive(&real_bytes).unwrap().unwrap();
+        assert_eq!(payload_type, PayloadType::EpochSync);
+        assert_eq!(body, b"real");
+
+        // Chaff nonces are remembered like any other frame
+        let result = receiver.receive(&chaff_bytes);
+        assert!(matches!(result, Err(WireError::ReplayAttack(_))));
+    }
+
+    #[test]
+    fn test_receive_delivers_real_body_with_chaff_prefix() {
+        use crate::sync::chaff::CHAFF_BODY_MAGIC;
+
+        let key = XChaCha20Key::generate();
+        let mut sender = WireProtocol::new(key.clone());
+        let mut receiver = WireProtocol::new(key);
+
+        let mut body = vec![PayloadType::Chaff.to_byte()];
+        body.extend_from_slice(&CHAFF_BODY_MAGIC);
+        body.extend_from_slice(b"real");
+        assert!(ChaffGenerator::is_chaff_body(&body));
+
+        // Only the authenticated chaff label marks a frame as chaff
+        let frame = sender
+            .send_message(PayloadType::EpochSync, body.clone(), 1)
+            .unwrap();
+        let (payload_type, received) = receiver.receive(&frame).unwrap().unwrap();
+        assert_eq!(payload_type, PayloadType::EpochSync);
+        assert_eq!(received, body);
+
+        let chaff = sender.send_chaff(&mut ChaffGenerator::new()).unwrap();
+        assert!(receiver.receive(&chaff).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_receive_discards_legacy_chaff_without_chaff_aad() {
+        let key = XChaCha20Key::generate();
+        let mut client = WireProtocol::new(key.clone());
+        let mut server = WireProtocol::new(key.clone());
+
+        // 对端（旧版本）不声明 CHAFF_AAD
+        let client_msg =
+            VersionNegotiationMessage::default_with_version(ProtocolVersion::current());
+        let server_msg = VersionNegotiationMessage::new(
+            vec![ProtocolVersion::current()],
+            ProtocolVersion::current(),
+            CapabilityFlags::new(CapabilityFlags::HYBRID_HANDSHAKE | CapabilityFlags::CHAFF_SYNC),
+        );
+        let offer = client.offer_negotiation(&client_msg).unwrap();
+        let reply = server.respond_to_negotiation(&offer, &server_msg).unwrap();
+        client.complete_negotiation(&reply, &client_msg).unwrap();
+
+        // 诱饵以旧格式发送（AAD 不含标记），接收方按消息体前缀丢弃
+        let chaff = client.send_chaff(&mut ChaffGenerator::new()).unwrap();
+        let padded = SealedFrame::parse(&chaff, client.frame_profile())
+            .unwrap()
+            .decrypt(&AeadCipher::new(&key), &[])
+            .unwrap();
+        assert!(ChaffGenerator::is_chaff_body(
+            &SealedFrame::unpad(padded).unwrap()
+        ));
+        assert!(server.receive(&chaff).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_receive_rejects_chaff_under_wrong_key() {
+        let mut receiver = WireProtocol::new(XChaCha20Key::generate());
+        let mut generator = ChaffGenerator::new();
+
+        let chaff_bytes = generator
+            .generate_frame(&XChaCha20Key::generate(), 1)
+            .unwrap();
+
+        assert!(receiver.receive(&chaff_bytes).is_err());
+    }
+
+    #[test]
+    fn test_send_message_padding_is_random() {
+        let key = XChaCha20Key::generate();
+        let mut sender = WireProtocol::new(key.clone());
+        let mut receiver = WireProtocol::new(key);
+
+        let frame = sender
+            .send_message(PayloadType::EpochSync, b"short".to_vec(), 1)
+            .unwrap();
+
+        // 长度与填充在密文内，明文头之后的区域与随机字节不可区分
+        assert_eq!(frame.len(), FRAME_SIZE);
+        assert!(padding_entropy_ok(&frame));
+
+        let (payload_type, body) = receiver.receive_message(&frame).unwrap();
+        assert_eq!(payload_type, PayloadType::EpochSync);
+        assert_eq!(body, b"short");
+    }
+
+    #[test]
+    fn test_legacy_session_keeps_wire_frame_layout() {
+        let legacy = VersionNegotiationMessage::default_with_version(ProtocolVersion::new(1, 0));
+        let current = VersionNegotiationMessage::default_with_version(ProtocolVersion::current());
+        let key = XChaCha20Key::generate();
+        let mut client = WireProtocol::new(key.clone());
+        let mut server = WireProtocol::new(key);
+
+        let offer = client.offer_negotiation(&legacy).unwrap();
+        let reply = server.respond_to_negotiation(&offer, &current).unwrap();
+        client.complete_negotiation(&reply, &legacy).unwrap();
+        assert_eq!(server.protocol_version(), ProtocolVersion::new(1, 0));
+
+        let frame = server
+            .send_message(PayloadType::EpochSync, b"short".to_vec(), 1)
+            .unwrap();
+
+        // 1.0 对端仍按 WireFrame 布局解析；未认证的填充同样是随机字节
+        let parsed = WireFrame::deserialize(&frame).unwrap();
+        assert_eq!(parsed.body_len, 5);
+        assert!(padding_entropy_ok(&frame));
+
+        let (_, body) = client.receive_message(&frame).unwrap();
+        assert_eq!(body, b"short");
+    }
+
     #[test]
     fn test_clear_nonce_memory() {
         let key = XChaCha20Key::generate();
@@ -597,7 +1381,7 @@ mod tests {
 
         // 发送消息
         let frame_bytes = sender
-            .send_message(PayloadType::Sync, vec![1, 2, 3], 1)
+            .send_message(PayloadType::EpochSync, vec![1, 2, 3], 1)
             .expect("Failed to send message");
 
         // 第一次接收应该成功
@@ -605,7 +1389,7 @@ mod tests {
             .receive_message(&frame_bytes)
             .expect("Failed to receive message");
 
-        assert_eq!(payload_type, PayloadType::Sync);
+        assert_eq!(payload_type, PayloadType::EpochSync);
         assert_eq!(decrypted, vec![1, 2, 3]);
 
         // 重放应该被检测到
@@ -620,7 +1404,530 @@ mod tests {
             .receive_message(&frame_bytes)
             .expect("Failed to receive message after clearing nonce memory");
 
-        assert_eq!(payload_type, PayloadType::Sync);
+        assert_eq!(payload_type, PayloadType::EpochSync);
         assert_eq!(decrypted, vec![1, 2, 3]);
     }
+
+    // ------------------------------------------------------------------------
+    // 版本协商测试
+    // ------------------------------------------------------------------------
+
+    /// 执行完整的协商往返，返回（发起方结果，响应方结果）
+    fn run_negotiation(
+        client_msg: &VersionNegotiationMessage,
+        server_msg: &VersionNegotiationMessage,
+    ) -> Result<(NegotiationOutcome, NegotiationOutcome)> {
+        let key = XChaCha20Key::generate();
+        let mut client = WireProtocol::new(key.clone());
+        let mut server = WireProtocol::new(key);
+
+        let offer = client.offer_negotiation(client_msg)?;
+        let reply = server.respond_to_negotiation(&offer, server_msg)?;
+        let client_outcome = client.complete_negotiation(&reply, client_msg)?;
+
+        assert_eq!(client.negotiated(), Some(&client_outcome));
+        Ok((client_outcome, *server.negotiated().unwrap()))
+    }
+
+    #[test]
+    fn test_negotiation_equal_versions() {
+        let msg = VersionNegotiationMessage::default_with_version(ProtocolVersion::current());
+
+        let (client, server) = run_negotiation(&msg, &msg).unwrap();
+
+        assert_eq!(client, server);
+        assert_eq!(client.version, ProtocolVersion::current());
+        assert_eq!(client.capabilities, CapabilityFlags::default());
+    }
+
+    #[test]
+    fn test_negotiation_client_newer_minor() {
+        let client_msg = VersionNegotiationMessage::new(
+            vec![ProtocolVersion::new(1, 0), ProtocolVersion::new(1, 1)],
+            ProtocolVersion::new(1, 1),
+            CapabilityFlags::default(),
+        );
+        let server_msg =
+            VersionNegotiationMessage::default_with_version(ProtocolVersion::new(1, 0));
+
+        let (client, server) = run_negotiation(&client_msg, &server_msg).unwrap();
+
+        assert_eq!(client, server);
+        assert_eq!(client.version, ProtocolVersion::new(1, 0));
+    }
+
+    #[test]
+    fn test_negotiation_disjoint_versions_fail() {
+        let client_msg =
+            VersionNegotiationMessage::default_with_version(ProtocolVersion::new(1, 2));
+        let server_msg =
+            VersionNegotiationMessage::default_with_version(ProtocolVersion::new(2, 0));
+
+        let key = XChaCha20Key::generate();
+        let client = WireProtocol::new(key.clone());
+        let mut server = WireProtocol::new(key);
+
+        let offer = client.offer_negotiation(&client_msg).unwrap();
+        let result = server.respond_to_negotiation(&offer, &server_msg);
+
+        assert!(matches!(
+            result,
+            Err(WireError::VersionNegotiationFailed {
+                client: (1, 2),
+                server: (2, 0),
+            })
+        ));
+        assert!(server.negotiated().is_none());
+        assert_eq!(server.protocol_version(), ProtocolVersion::current());
+    }
+
+    #[test]
+    fn test_negotiation_capability_intersection() {
+        let client_msg = VersionNegotiationMessage::new(
+            vec![ProtocolVersion::current()],
+            ProtocolVersion::current(),
+            CapabilityFlags::default(),
+        );
+        // 服务器不支持诱饵流量
+        let server_msg = VersionNegotiationMessage::new(
+            vec![ProtocolVersion::current()],
+            ProtocolVersion::current(),
+            CapabilityFlags::new(
+                CapabilityFlags::HYBRID_HANDSHAKE
+                    | CapabilityFlags::VETO_SIGNALING
+                    | CapabilityFlags::SHADOW_WRAPPING,
+            ),
+        );
+
+        let key = XChaCha20Key::generate();
+        let mut client = WireProtocol::new(key.clone());
+        let mut server = WireProtocol::new(key);
+        assert!(client.chaff_enabled());
+
+        let offer = client.offer_negotiation(&client_msg).unwrap();
+        let reply = server.respond_to_negotiation(&offer, &server_msg).unwrap();
+        let outcome = client.complete_negotiation(&reply, &client_msg).unwrap();
+
+        assert!(!outcome.capabilities.has(CapabilityFlags::CHAFF_SYNC));
+        assert!(outcome.capabilities.has(CapabilityFlags::VETO_SIGNALING));
+        assert!(!client.chaff_enabled());
+        assert!(!server.chaff_enabled());
+        assert_eq!(client.capabilities(), server.capabilities());
+    }
+
+    #[test]
+    fn test_negotiation_frame_tampering_detected() {
+        let msg = VersionNegotiationMessage::default_with_version(ProtocolVersion::current());
+        let key = XChaCha20Key::generate();
+        let client = WireProtocol::new(key.clone());
+        let mut server = WireProtocol::new(key);
+
+        let mut offer = client.offer_negotiation(&msg).unwrap();
+        assert_eq!(offer.len(), FRAME_SIZE);
+
+        // 明文消息体可读，但修改会破坏 MAC
+        let mut frame = WireFrame::deserialize(&offer).unwrap();
+        frame.encrypted_body[0] ^= 0x01;
+        offer = frame.serialize().unwrap();
+
+        let result = server.respond_to_negotiation(&offer, &msg);
+        assert!(matches!(result, Err(WireError::AuthenticationFailed)));
+        assert!(server.negotiated().is_none());
+    }
+
+    /// 完成一次协商：客户端请求 BLE 帧，服务器使用默认帧
+    fn ble_session() -> (WireProtocol, WireProtocol, XChaCha20Key) {
+        use crate::sync::PROFILE_BLE;
+
+        let client_msg =
+            VersionNegotiationMessage::default_with_version(ProtocolVersion::current())
+                .with_frame_profile(PROFILE_BLE);
+        let server_msg =
+            VersionNegotiationMessage::default_with_version(ProtocolVersion::current());
+
+        let key = XChaCha20Key::generate();
+        let mut client = WireProtocol::new(key.clone());
+        let mut server = WireProtocol::new(key.clone());
+
+        let offer = client.offer_negotiation(&client_msg).unwrap();
+        assert_eq!(offer.len(), 512);
+        let reply = server.respond_to_negotiation(&offer, &server_msg).unwrap();
+        assert_eq!(reply.len(), 512);
+        client.complete_negotiation(&reply, &client_msg).unwrap();
+
+        (client, server, key)
+    }
+
+    #[test]
+    fn test_ble_profile_session_roundtrip() {
+        use crate::sync::PROFILE_BLE;
+
+        let (mut client, mut server, key) = ble_session();
+        assert_eq!(client.frame_profile(), PROFILE_BLE);
+        assert_eq!(server.frame_profile(), PROFILE_BLE);
+
+        let frame = client
+            .send_message(PayloadType::EpochSync, b"pairing".to_vec(), 1)
+            .unwrap();
+        assert_eq!(frame.len(), 512);
+        let (payload_type, plaintext) = server.receive_message(&frame).unwrap();
+        assert_eq!(payload_type, PayloadType::EpochSync);
+        assert_eq!(plaintext, b"pairing");
+
+        // 诱饵帧与真实帧尺寸相同
+        let chaff = ChaffGenerator::new()
+            .generate_frame_with_profile(&key, 1, server.frame_profile())
+            .unwrap();
+        assert_eq!(chaff.len(), frame.len());
+        assert!(client.receive(&chaff).unwrap().is_none());
+
+        // BLE 帧容量之外的消息被拒绝
+        let too_large = vec![0; PROFILE_BLE.max_body_size + 1];
+        assert!(matches!(
+            client.send_message(PayloadType::EpochSync, too_large, 1),
+            Err(WireError::InvalidFrameSize(_))
+        ));
+    }
+
+    #[test]
+    fn test_ble_session_rejects_default_frame() {
+        let (_client, mut server, key) = ble_session();
+
+        // 使用默认帧尺寸的对端（8192 字节）
+        let mut wifi_peer = WireProtocol::new(key);
+        let frame = wifi_peer
+            .send_message(PayloadType::EpochSync, b"hello".to_vec(), 1)
+            .unwrap();
+        assert_eq!(frame.len(), FRAME_SIZE);
+
+        assert!(matches!(
+            server.receive_message(&frame),
+            Err(WireError::FrameProfileMismatch {
+                expected: 512,
+                actual: 8192,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_renegotiation_cannot_switch_profile() {
+        let (_client, mut server, key) = ble_session();
+
+        // 会话已确定 BLE 帧，再次协商默认帧被拒绝
+        let wifi_msg = VersionNegotiationMessage::default_with_version(ProtocolVersion::current());
+        let offer = WireProtocol::new(key).offer_negotiation(&wifi_msg).unwrap();
+        assert_eq!(offer.len(), FRAME_SIZE);
+
+        assert!(matches!(
+            server.respond_to_negotiation(&offer, &wifi_msg),
+            Err(WireError::FrameProfileMismatch {
+                expected: 512,
+                actual: 8192,
+            })
+        ));
+        assert_eq!(server.frame_profile(), crate::sync::PROFILE_BLE);
+    }
+
+    #[test]
+    fn test_nonce_cache_accepts_once_rejects_duplicate() {
+        let cache = NonceCache::new();
+        let nonce = [7u8; NONCE_SIZE];
+
+        assert!(cache.check_and_insert(&nonce).is_ok());
+        assert!(matches!(
+            cache.check_and_insert(&nonce),
+            Err(WireError::ReplayAttack(n)) if n == nonce
+        ));
+        assert!(cache.check_and_insert(&[8u8; NONCE_SIZE]).is_ok());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_nonce_cache_evicts_after_capacity() {
+        for policy in [EvictionPolicy::Lru, EvictionPolicy::Fifo] {
+            let cache = NonceCache::with_config(NonceCacheConfig {
+                capacity: 2,
+                policy,
+            });
+            cache.check_and_insert(&[1u8; NONCE_SIZE]).unwrap();
+            cache.check_and_insert(&[2u8; NONCE_SIZE]).unwrap();
+            cache.check_and_insert(&[3u8; NONCE_SIZE]).unwrap();
+
+            // 最早的 nonce 被淘汰，不再识别为重放
+            assert_eq!(cache.len(), 2);
+            assert!(!cache.contains(&[1u8; NONCE_SIZE]));
+            assert!(cache.check_and_insert(&[1u8; NONCE_SIZE]).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_nonce_cache_lru_refreshes_on_duplicate() {
+        let make = |policy| {
+            let cache = NonceCache::with_config(NonceCacheConfig {
+                capacity: 2,
+                policy,
+            });
+            cache.check_and_insert(&[1u8; NONCE_SIZE]).unwrap();
+            cache.check_and_insert(&[2u8; NONCE_SIZE]).unwrap();
+            // 重放 nonce 1，然后插入新的 nonce 3
+            assert!(cache.check_and_insert(&[1u8; NONCE_SIZE]).is_err());
+            cache.check_and_insert(&[3u8; NONCE_SIZE]).unwrap();
+            cache
+        };
+
+        let lru = make(EvictionPolicy::Lru);
+        assert!(lru.contains(&[1u8; NONCE_SIZE]));
+        assert!(!lru.contains(&[2u8; NONCE_SIZE]));
+
+        let fifo = make(EvictionPolicy::Fifo);
+        assert!(!fifo.contains(&[1u8; NONCE_SIZE]));
+        assert!(fifo.contains(&[2u8; NONCE_SIZE]));
+    }
+
+    #[test]
+    fn test_nonce_cache_shared_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<NonceCache>();
+
+        let cache = std::sync::Arc::new(NonceCache::new());
+        let accepted: usize = (0..4)
+            .map(|_| {
+                let cache = cache.clone();
+                std::thread::spawn(move || cache.check_and_insert(&[9u8; NONCE_SIZE]).is_ok())
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap() as usize)
+            .sum();
+
+        // 同一 nonce 在并发下也只被接受一次
+        assert_eq!(accepted, 1);
+    }
+
+    #[test]
+    fn test_nonce_cache_config_bounds_protocol_memory() {
+        let key = XChaCha20Key::generate();
+        let mut sender = WireProtocol::new(key.clone());
+        let mut receiver = WireProtocol::new(key).with_nonce_cache(NonceCacheConfig {
+            capacity: 1,
+            policy: EvictionPolicy::Fifo,
+        });
+
+        let first = sender
+            .send_message(PayloadType::EpochSync, b"one".to_vec(), 1)
+            .unwrap();
+        let second = sender
+            .send_message(PayloadType::EpochSync, b"two".to_vec(), 1)
+            .unwrap();
+        receiver.receive_message(&first).unwrap();
+        assert!(matches!(
+            receiver.receive_message(&first),
+            Err(WireError::ReplayAttack(_))
+        ));
+        receiver.receive_message(&second).unwrap();
+        assert!(matches!(
+            receiver.receive_message(&second),
+            Err(WireError::ReplayAttack(_))
+        ));
+    }
+
+    #[test]
+    fn test_accept_frame_equal_epoch() {
+        let mut protocol = WireProtocol::new(XChaCha20Key::generate());
+        protocol.accept_frame(5).unwrap();
+
+        assert!(protocol.accept_frame(5).is_ok());
+        assert_eq!(protocol.current_epoch(), 5);
+    }
+
+    #[test]
+    fn test_accept_frame_newer_epoch_advances() {
+        let mut protocol = WireProtocol::new(XChaCha20Key::generate());
+        protocol.accept_frame(5).unwrap();
+
+        assert!(protocol.accept_frame(6).is_ok());
+        assert_eq!(protocol.current_epoch(), 6);
+    }
+
+    #[test]
+    fn test_accept_frame_older_epoch_rejected() {
+        let mut protocol = WireProtocol::new(XChaCha20Key::generate());
+        protocol.accept_frame(5).unwrap();
+
+        assert!(matches!(
+            protocol.accept_frame(4),
+            Err(WireError::EpochRegression {
+                current: 5,
+                attempted: 4,
+            })
+        ));
+        // 拒绝后当前 epoch 不变
+        assert_eq!(protocol.current_epoch(), 5);
+    }
+
+    // ------------------------------------------------------------------------
+    // 密钥棘轮测试
+    // ------------------------------------------------------------------------
+
+    /// 每 `max_frames` 帧推进一次的会话两端
+    fn ratchet_session(max_frames: u64) -> (WireProtocol, WireProtocol) {
+        let key = XChaCha20Key::generate();
+        let config = RatchetConfig {
+            max_frames,
+            max_bytes: u64::MAX,
+        };
+        (
+            WireProtocol::new(key.clone()).with_ratchet(config),
+            WireProtocol::new(key).with_ratchet(config),
+        )
+    }
+
+    #[test]
+    fn test_ratchet_multi_generation_conversation() {
+        let (mut alice, mut bob) = ratchet_session(3);
+
+        for i in 0..20u8 {
+            let to_bob = alice
+                .send_message(PayloadType::EpochSync, vec![i], 1)
+                .unwrap();
+            assert_eq!(bob.receive_message(&to_bob).unwrap().1, vec![i]);
+
+            let to_alice = bob
+                .send_message(PayloadType::EpochSync, vec![i, i], 1)
+                .unwrap();
+            assert_eq!(alice.receive_message(&to_alice).unwrap().1, vec![i, i]);
+        }
+
+        // 20 帧、每 3 帧推进一次：双方发送与接收的代数一致
+        assert_eq!(alice.send_generation(), 6);
+        assert_eq!(bob.recv_generation(), 6);
+        assert_eq!(bob.send_generation(), 6);
+        assert_eq!(alice.recv_generation(), 6);
+    }
+
+    #[test]
+    fn test_ratchet_is_deterministic_across_peers() {
+        let key = XChaCha20Key::generate();
+        let mut a = SendRatchet::new(key.clone(), RatchetConfig::default());
+        let mut b = SendRatchet::new(key, RatchetConfig::default());
+
+        for _ in 0..(3 * DEFAULT_RATCHET_FRAMES) {
+            a.record(1);
+            b.record(1);
+        }
+
+        let nonce = XChaCha20Nonce::random();
+        let (cipher_a, generation_a) = a.cipher();
+        let (cipher_b, generation_b) = b.cipher();
+        assert_eq!((generation_a, generation_b), (3, 3));
+        assert_eq!(
+            cipher_a.encrypt(&nonce, b"x", None).unwrap(),
+            cipher_b.encrypt(&nonce, b"x", None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ratchet_tolerates_in_flight_previous_generation() {
+        let (mut sender, mut receiver) = ratchet_session(1);
+
+        let g0 = sender
+            .send_message(PayloadType::EpochSync, b"g0".to_vec(), 1)
+            .unwrap();
+        let g1 = sender
+            .send_message(PayloadType::EpochSync, b"g1".to_vec(), 1)
+            .unwrap();
+
+        // g1 先到达：接收方推进到第 1 代，g0 仍在 ±1 窗口内
+        assert_eq!(receiver.receive_message(&g1).unwrap().1, b"g1");
+        assert_eq!(receiver.recv_generation(), 1);
+        assert_eq!(receiver.receive_message(&g0).unwrap().1, b"g0");
+    }
+
+    #[test]
+    fn test_ratchet_rejects_out_of_window_generations() {
+        let (mut sender, mut receiver) = ratchet_session(1);
+
+        let frames: Vec<_> = (0..4u8)
+            .map(|i| {
+                sender
+                    .send_message(PayloadType::EpochSync, vec![i], 1)
+                    .unwrap()
+            })
+            .collect();
+
+        // g+2：超前两代，拒绝且接收方不推进
+        assert!(receiver.receive_message(&frames[2]).is_err());
+        assert_eq!(receiver.recv_generation(), 0);
+
+        receiver.receive_message(&frames[1]).unwrap();
+        receiver.receive_message(&frames[3]).unwrap_err();
+        assert_eq!(receiver.receive_message(&frames[2]).unwrap().1, vec![2]);
+        assert_eq!(receiver.recv_generation(), 2);
+
+        // g-2：旧代密钥已清零，拒绝
+        assert!(receiver.receive_message(&frames[0]).is_err());
+        assert_eq!(receiver.recv.last_retired, Some([0u8; 32]));
+        assert_eq!(sender.send.last_retired, Some([0u8; 32]));
+    }
+
+    #[test]
+    fn test_ratchet_off_when_peer_lacks_capability() {
+        let key = XChaCha20Key::generate();
+        let config = RatchetConfig {
+            max_frames: 1,
+            max_bytes: u64::MAX,
+        };
+        let mut client = WireProtocol::new(key.clone()).with_ratchet(config);
+        let mut server = WireProtocol::new(key.clone()).with_ratchet(config);
+
+        // 对端（旧版本）不声明 KEY_RATCHET
+        let client_msg =
+            VersionNegotiationMessage::default_with_version(ProtocolVersion::current());
+        let server_msg = VersionNegotiationMessage::new(
+            vec![ProtocolVersion::current()],
+            ProtocolVersion::current(),
+            CapabilityFlags::new(CapabilityFlags::HYBRID_HANDSHAKE),
+        );
+        let offer = client.offer_negotiation(&client_msg).unwrap();
+        let reply = server.respond_to_negotiation(&offer, &server_msg).unwrap();
+        client.complete_negotiation(&reply, &client_msg).unwrap();
+        assert!(!client.ratchet_enabled());
+        assert!(!server.ratchet_enabled());
+
+        for i in 0..4u8 {
+            let frame = client
+                .send_message(PayloadType::EpochSync, vec![i], 1)
+                .unwrap();
+
+            // 旧格式：会话密钥、空 AAD
+            let padded = SealedFrame::parse(&frame, client.frame_profile())
+                .unwrap()
+                .decrypt(&AeadCipher::new(&key), &[])
+                .unwrap();
+            assert_eq!(SealedFrame::unpad(padded).unwrap(), vec![i]);
+
+            assert_eq!(server.receive_message(&frame).unwrap().1, vec![i]);
+        }
+        assert_eq!(client.send_generation(), 0);
+        assert_eq!(server.recv_generation(), 0);
+    }
+
+    #[test]
+    fn test_ratchet_chaff_follows_send_generation() {
+        let (mut sender, mut receiver) = ratchet_session(2);
+        let mut generator = ChaffGenerator::new();
+
+        for i in 0..5u8 {
+            let real = sender
+                .send_message(PayloadType::EpochSync, vec![i], 1)
+                .unwrap();
+            assert_eq!(receiver.receive(&real).unwrap().unwrap().1, vec![i]);
+
+            let chaff = sender.send_chaff(&mut generator).unwrap();
+            assert!(receiver.receive(&chaff).unwrap().is_none());
+        }
+        // 第 10 帧之后发送方已推进到第 5 代，但尚无该代的帧，接收方仍在第 4 代
+        assert_eq!(sender.send_generation(), 5);
+        assert_eq!(receiver.recv_generation(), 4);
+    }
 }
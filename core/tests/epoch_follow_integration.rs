@@ -0,0 +1,253 @@
+//! # 跨设备纪元同步集成测试
+//!
+//! 在同一进程内模拟发起方与跟随方两台设备，各自拥有独立的 vault 目录。
+//!
+//! ## 测试覆盖
+//!
+//! - 发起方提交纪元 n+1 并广播 `EpochAnnounce`
+//! - 跟随方经 `EpochFollower` 验证并应用，双方纪元与 Header 摘要一致
+//! - 承诺不符时跟随方的 vault 与状态机保持不变
+
+use aeternum_core::crypto::aead::{AeadCipher, XChaCha20Key, XChaCha20Nonce};
+use aeternum_core::crypto::kem::{KyberKEM, KyberKeyPair};
+use aeternum_core::models::device::{DeviceHeader, DeviceId};
+use aeternum_core::models::epoch::{CryptoAlgorithm, CryptoEpoch};
+use aeternum_core::models::key_hierarchy::DataEncryptionKey;
+use aeternum_core::protocol::{
+    EpochAnnounce, EpochFollower, FollowRejection, FollowState, PqrrError, PqrrStateMachine,
+};
+use aeternum_core::storage::aug::{
+    aup_atomic_commit, aup_prepare_with_algorithm, aup_prepare_with_nonce, aup_shadow_write,
+    open_vault, read_vault_blob, read_vault_epoch, read_vault_key, LEGACY_VK_NONCE,
+};
+use aeternum_core::storage::InMemoryMetadataStore;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// 模拟设备：独立的 vault 目录、Kyber 密钥对、当前 DEK 与状态机
+struct Device {
+    _dir: TempDir,
+    vault_path: PathBuf,
+    id: DeviceId,
+    keypair: KyberKeyPair,
+    dek: XChaCha20Key,
+    state_machine: PqrrStateMachine,
+}
+
+/// 以共享的 VK 创建纪元 1 的设备
+///
+/// 纪元 0 仅用于引导：两台设备从同一 VK 派生出相同的纪元 DEK。
+fn bootstrap_device(vk: &[u8; 32], data: &[u8]) -> Device {
+    let dir = TempDir::new().unwrap();
+    let vault_path = dir.path().join("vault.db");
+
+    let bootstrap_dek = XChaCha20Key::from_bytes(&[9u8; 32]).unwrap();
+    let nonce = XChaCha20Nonce::from_bytes(LEGACY_VK_NONCE);
+    let encrypted_vk = AeadCipher::new(&bootstrap_dek)
+        .encrypt(&nonce, vk, None)
+        .unwrap();
+    let prep = aup_prepare_with_algorithm(
+        &CryptoEpoch::new(0, CryptoAlgorithm::V1),
+        CryptoAlgorithm::V2,
+        &encrypted_vk,
+        &LEGACY_VK_NONCE,
+        &bootstrap_dek,
+        data,
+    )
+    .unwrap();
+    let shadow = aup_shadow_write(&vault_path, &prep).unwrap();
+    aup_atomic_commit(
+        &vault_path,
+        shadow,
+        &prep.new_epoch,
+        &mut InMemoryMetadataStore::default(),
+    )
+    .unwrap();
+
+    Device {
+        _dir: dir,
+        vault_path,
+        id: DeviceId::generate(),
+        keypair: KyberKEM::generate_keypair(),
+        dek: prep.new_dek,
+        state_machine: PqrrStateMachine::create(prep.new_epoch, HashMap::new()),
+    }
+}
+
+/// 为 `devices` 中每台设备生成持有 `dek` 的 `epoch` Header
+fn headers_for(devices: &[&Device], epoch: CryptoEpoch, dek: &XChaCha20Key) -> Vec<DeviceHeader> {
+    let dek = DataEncryptionKey::from_bytes(*dek.as_bytes());
+    devices
+        .iter()
+        .map(|device| {
+            DeviceHeader::with_wrapped_dek(
+                device.id,
+                epoch,
+                device.keypair.public.clone(),
+                dek.wrap_for_device(&device.keypair.public).unwrap(),
+            )
+        })
+        .collect()
+}
+
+/// 在双方均处于纪元 1 时安装纪元 1 的 Header 集合
+fn install_initial_headers(initiator: &mut Device, follower: &mut Device) {
+    let epoch = initiator.state_machine.current_epoch();
+    let headers: HashMap<DeviceId, DeviceHeader> =
+        headers_for(&[initiator, follower], epoch, &initiator.dek)
+            .into_iter()
+            .map(|header| (header.device_id, header))
+            .collect();
+    *initiator.state_machine.device_headers_mut() = headers.clone();
+    *follower.state_machine.device_headers_mut() = headers;
+}
+
+/// 发起方执行纪元升级（AUP 三阶段）并返回广播内容
+fn initiator_upgrade(initiator: &mut Device, follower: &Device) -> EpochAnnounce {
+    let blob = read_vault_blob(&initiator.vault_path).unwrap();
+    let key = read_vault_key(&initiator.vault_path).unwrap().unwrap();
+    let data = open_vault(&blob, &key, &initiator.dek).unwrap();
+    let prep = aup_prepare_with_nonce(
+        &blob.epoch,
+        &key.encrypted_vk,
+        &key.vk_nonce,
+        &initiator.dek,
+        &data,
+    )
+    .unwrap();
+
+    let headers = headers_for(&[initiator, follower], prep.new_epoch, &prep.new_dek);
+    let announce = EpochAnnounce::new(prep.new_epoch, &prep.new_dek, headers);
+
+    let shadow = aup_shadow_write(&initiator.vault_path, &prep).unwrap();
+    aup_atomic_commit(
+        &initiator.vault_path,
+        shadow,
+        &prep.new_epoch,
+        &mut InMemoryMetadataStore::default(),
+    )
+    .unwrap();
+    initiator
+        .state_machine
+        .apply_epoch_upgrade_internal(prep.new_epoch)
+        .unwrap();
+    *initiator.state_machine.device_headers_mut() = announce.header_map();
+    initiator.dek = prep.new_dek;
+
+    announce
+}
+
+/// 从广播中取出某台设备的 Header（模拟单独下发）
+fn own_header(announce: &EpochAnnounce, device: &Device) -> DeviceHeader {
+    announce
+        .headers
+        .iter()
+        .find(|header| header.device_id == device.id)
+        .cloned()
+        .unwrap()
+}
+
+// ===== 集成测试：发起方与跟随方收敛 =====
+
+#[test]
+fn test_initiator_and_follower_converge() {
+    let vk = [0x42u8; 32];
+    let data = br#"{"site":"example.com","user":"alice"}"#;
+    let mut initiator = bootstrap_device(&vk, data);
+    let mut follower = bootstrap_device(&vk, data);
+    install_initial_headers(&mut initiator, &mut follower);
+    assert_eq!(
+        initiator.state_machine.headers_digest(),
+        follower.state_machine.headers_digest()
+    );
+
+    // 连续两轮升级：纪元 1 → 2 → 3
+    for expected in [2, 3] {
+        let announce = initiator_upgrade(&mut initiator, &follower);
+        let header = own_header(&announce, &follower);
+
+        let mut session = EpochFollower::new(&mut follower.state_machine, follower.id);
+        let new_dek = session
+            .follow(
+                announce,
+                header,
+                &follower.vault_path,
+                &follower.keypair.secret,
+                &follower.dek,
+                &mut InMemoryMetadataStore::default(),
+            )
+            .unwrap();
+        assert_eq!(session.state(), FollowState::Done);
+        follower.dek = new_dek;
+
+        // 双方纪元与 Header 摘要一致
+        assert_eq!(initiator.state_machine.current_epoch().version, expected);
+        assert_eq!(follower.state_machine.current_epoch().version, expected);
+        assert_eq!(read_vault_epoch(&initiator.vault_path).unwrap(), expected);
+        assert_eq!(read_vault_epoch(&follower.vault_path).unwrap(), expected);
+        assert_eq!(
+            initiator.state_machine.headers_digest(),
+            follower.state_machine.headers_digest()
+        );
+        assert_eq!(initiator.dek.as_bytes(), follower.dek.as_bytes());
+    }
+
+    // 跟随方的 vault 可用新 DEK 打开
+    let blob = read_vault_blob(&follower.vault_path).unwrap();
+    let key = read_vault_key(&follower.vault_path).unwrap().unwrap();
+    assert_eq!(open_vault(&blob, &key, &follower.dek).unwrap(), data);
+}
+
+// ===== 集成测试：验证失败不改变本地状态 =====
+
+#[test]
+fn test_follower_rejects_tampered_announce() {
+    let vk = [0x24u8; 32];
+    let mut initiator = bootstrap_device(&vk, b"vault data");
+    let mut follower = bootstrap_device(&vk, b"vault data");
+    install_initial_headers(&mut initiator, &mut follower);
+
+    let mut announce = initiator_upgrade(&mut initiator, &follower);
+    announce.dek_commitment[31] ^= 0x80;
+    let header = own_header(&announce, &follower);
+
+    let vault_before = std::fs::read(&follower.vault_path).unwrap();
+    let digest_before = follower.state_machine.headers_digest();
+
+    let mut session = EpochFollower::new(&mut follower.state_machine, follower.id);
+    let err = session
+        .follow(
+            announce,
+            header,
+            &follower.vault_path,
+            &follower.keypair.secret,
+            &follower.dek,
+            &mut InMemoryMetadataStore::default(),
+        )
+        .unwrap_err();
+
+    // 结构化错误：代码可回传给发起方
+    match err {
+        PqrrError::EpochFollowRejected { rejection, .. } => {
+            assert_eq!(rejection, FollowRejection::CommitmentMismatch);
+            assert_eq!(
+                FollowRejection::from_code(rejection.code()),
+                Some(rejection)
+            );
+        }
+        other => panic!("意外的错误: {:?}", other),
+    }
+    assert_eq!(
+        session.state(),
+        FollowState::Failed(FollowRejection::CommitmentMismatch)
+    );
+
+    assert_eq!(std::fs::read(&follower.vault_path).unwrap(), vault_before);
+    assert_eq!(follower.state_machine.current_epoch().version, 1);
+    assert_eq!(follower.state_machine.headers_digest(), digest_before);
+    assert_ne!(
+        initiator.state_machine.headers_digest(),
+        follower.state_machine.headers_digest()
+    );
+}
@@ -0,0 +1,87 @@
+//! # Fuzz 语料回放测试
+//!
+//! 将 `fuzz/corpus/<target>/` 下提交的种子输入逐个送入对应解析器，
+//! 使未运行 cargo-fuzz 的 CI 也能覆盖这些输入。
+//!
+//! ## 检查项（与 `fuzz/fuzz_targets/` 保持一致）
+//!
+//! - 解析器对任意输入不 panic
+//! - 解析成功的输入可以无损地重新序列化
+
+use aeternum_core::models::{DeviceHeader, VaultBlob, VaultHeader};
+use aeternum_core::sync::frame::WireFrame;
+use aeternum_core::sync::{PROFILE_BLE, PROFILE_DEFAULT};
+use std::fs;
+use std::path::PathBuf;
+
+/// 读取某个 fuzz 目标的全部语料（按文件名排序）
+fn corpus(target: &str) -> Vec<(String, Vec<u8>)> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fuzz/corpus")
+        .join(target);
+
+    let mut entries: Vec<(String, Vec<u8>)> = fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("无法读取语料目录 {}: {}", dir.display(), e))
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (name, fs::read(&path).unwrap())
+        })
+        .collect();
+    entries.sort();
+
+    assert!(!entries.is_empty(), "语料目录 {} 为空", dir.display());
+    entries
+}
+
+#[test]
+fn test_replay_wire_frame_corpus() {
+    let mut accepted = 0;
+
+    for (name, data) in corpus("wire_frame") {
+        for profile in [PROFILE_DEFAULT, PROFILE_BLE] {
+            if let Ok(frame) = WireFrame::deserialize_with_profile(&data, profile) {
+                let _ = frame.validate_with_profile(profile);
+                assert_eq!(frame.serialize().unwrap(), data, "{}", name);
+                accepted += 1;
+            }
+        }
+    }
+
+    // 语料中至少包含一个合法帧
+    assert!(accepted > 0);
+}
+
+#[test]
+fn test_replay_vault_blob_corpus() {
+    let mut accepted = 0;
+
+    for (name, data) in corpus("vault_blob") {
+        let _ = VaultHeader::from_bytes(&data);
+
+        if let Ok(blob) = VaultBlob::deserialize(&data) {
+            let _ = blob.validate();
+            let bytes = blob.serialize().unwrap();
+            let reparsed = VaultBlob::deserialize(&bytes).unwrap();
+            assert_eq!(reparsed.serialize().unwrap(), bytes, "{}", name);
+            accepted += 1;
+        }
+    }
+
+    assert!(accepted > 0);
+}
+
+#[test]
+fn test_replay_device_header_corpus() {
+    let mut accepted = 0;
+
+    for (name, data) in corpus("device_header") {
+        if let Ok(header) = DeviceHeader::deserialize(&data) {
+            let reparsed = DeviceHeader::deserialize(&header.serialize()).unwrap();
+            assert_eq!(reparsed, header, "{}", name);
+            accepted += 1;
+        }
+    }
+
+    assert!(accepted > 0);
+}
@@ -59,7 +59,7 @@ fn test_models_sync_integration() {
 
     // 创建 WireFrame 并关联 Epoch
     let nonce = [1u8; NONCE_SIZE];
-    let payload_type = PayloadType::Sync.to_byte();
+    let payload_type = PayloadType::EpochSync.to_byte();
     let encrypted_body = vec![2, 3, 4];
     let auth_tag = [5u8; 16];
 
@@ -102,7 +102,7 @@ fn test_wire_frame_with_aead() {
     let frame = WireFrame::new(
         nonce_bytes,
         1,
-        PayloadType::Sync.to_byte(),
+        PayloadType::EpochSync.to_byte(),
         encrypted_body,
         auth_tag,
     )
@@ -161,8 +161,8 @@ fn test_wire_protocol_end_to_end() {
 
     // 发送不同类型的消息
     let test_cases = vec![
-        (PayloadType::Handshake, b"Handshake data".to_vec()),
-        (PayloadType::Sync, b"Sync data".to_vec()),
+        (PayloadType::Pairing, b"Handshake data".to_vec()),
+        (PayloadType::EpochSync, b"Sync data".to_vec()),
         (PayloadType::Veto, b"Veto data".to_vec()),
         (PayloadType::Recovery, b"Recovery data".to_vec()),
     ];
@@ -221,8 +221,14 @@ fn test_chaff_indistinguishability() {
     let real_body = vec![2, 3, 4];
     let auth_tag = [5u8; 16];
 
-    let real_frame = WireFrame::new(nonce, 1, PayloadType::Sync.to_byte(), real_body, auth_tag)
-        .expect("创建真实 Frame 失败");
+    let real_frame = WireFrame::new(
+        nonce,
+        1,
+        PayloadType::EpochSync.to_byte(),
+        real_body,
+        auth_tag,
+    )
+    .expect("创建真实 Frame 失败");
 
     // 验证两者长度相同
     let real_serialized = real_frame.serialize().expect("序列化失败");
@@ -312,16 +318,16 @@ fn test_epoch_monotonicity_enforcement() {
 
     // 发送 epoch = 1
     let _ = protocol
-        .send_message(PayloadType::Sync, vec![1, 2, 3], 1)
+        .send_message(PayloadType::EpochSync, vec![1, 2, 3], 1)
         .expect("发送 epoch 1 失败");
 
     // 发送 epoch = 2
     let _ = protocol
-        .send_message(PayloadType::Sync, vec![4, 5, 6], 2)
+        .send_message(PayloadType::EpochSync, vec![4, 5, 6], 2)
         .expect("发送 epoch 2 失败");
 
     // 尝试发送 epoch = 1（回滚，应该失败）
-    let result = protocol.send_message(PayloadType::Sync, vec![7, 8, 9], 1);
+    let result = protocol.send_message(PayloadType::EpochSync, vec![7, 8, 9], 1);
 
     assert!(result.is_err());
     assert!(matches!(
@@ -342,7 +348,7 @@ fn test_replay_attack_protection() {
 
     // 发送消息
     let frame_bytes = sender
-        .send_message(PayloadType::Sync, vec![1, 2, 3], 1)
+        .send_message(PayloadType::EpochSync, vec![1, 2, 3], 1)
         .expect("发送消息失败");
 
     // 第一次接收应该成功
@@ -368,15 +374,15 @@ fn test_message_codec_integration() {
 
     // 编码不同类型的消息
     let test_cases = vec![
-        PayloadType::Handshake,
-        PayloadType::Sync,
+        PayloadType::Pairing,
+        PayloadType::EpochSync,
         PayloadType::Veto,
         PayloadType::Recovery,
     ];
 
     for payload_type in test_cases {
         let byte_value = payload_type.to_byte();
-        let decoded = PayloadType::from_byte(byte_value);
+        let decoded = PayloadType::from_byte(byte_value).unwrap();
 
         assert_eq!(decoded, payload_type);
     }
@@ -404,14 +410,14 @@ fn test_large_message_handling() {
     for size in sizes {
         let plaintext = vec![0xAB; size];
         let frame_bytes = sender
-            .send_message(PayloadType::Sync, plaintext.clone(), 1)
+            .send_message(PayloadType::EpochSync, plaintext.clone(), 1)
             .unwrap_or_else(|_| panic!("发送 {} 字节消息失败", size));
 
         let (payload_type, decrypted) = receiver
             .receive_message(&frame_bytes)
             .unwrap_or_else(|_| panic!("接收 {} 字节消息失败", size));
 
-        assert_eq!(payload_type, PayloadType::Sync);
+        assert_eq!(payload_type, PayloadType::EpochSync);
         assert_eq!(decrypted, plaintext);
     }
 }
@@ -481,7 +487,7 @@ fn test_inv_2_header_completeness_with_wire_protocol() {
     // 发送消息（epoch 1）应该成功
     let frame_bytes = protocol
         .send_message(
-            PayloadType::Sync,
+            PayloadType::EpochSync,
             b"test message".to_vec(),
             epoch_v1.version as u32,
         )
@@ -535,7 +541,7 @@ fn test_inv_2_header_completeness_with_wire_protocol() {
     // WireProtocol 应该接受新纪元的消息
     let frame_bytes_v2 = protocol
         .send_message(
-            PayloadType::Sync,
+            PayloadType::EpochSync,
             b"epoch 2 message".to_vec(),
             epoch_v2.version as u32,
         )
@@ -563,7 +569,7 @@ fn test_inv_2_header_completeness_with_wire_protocol() {
     }
 
     // INV_2: 验证纪元单调性（禁止回滚）
-    let result = protocol.send_message(PayloadType::Sync, b"rollback attempt".to_vec(), 1);
+    let result = protocol.send_message(PayloadType::EpochSync, b"rollback attempt".to_vec(), 1);
     assert!(result.is_err(), "INV_1 违规: epoch 回滚应该被拒绝");
     assert!(matches!(
         result,
@@ -594,13 +600,21 @@ fn test_inv_2_device_header_epoch_consistency() {
     let frame_epoch = epoch.version as u32;
 
     let frame_bytes = protocol
-        .send_message(PayloadType::Sync, b"consistency test".to_vec(), frame_epoch)
+        .send_message(
+            PayloadType::EpochSync,
+            b"consistency test".to_vec(),
+            frame_epoch,
+        )
         .expect("发送消息失败");
 
-    // 验证 epoch 匹配
-    let received_frame = WireFrame::deserialize(&frame_bytes).expect("反序列化失败");
-    assert_eq!(received_frame.epoch(), frame_epoch);
-    assert_eq!(u64::from(received_frame.epoch()), epoch.version);
+    // 验证 epoch 匹配（epoch 以明文紧随 nonce）
+    let received_epoch = u32::from_be_bytes(
+        frame_bytes[NONCE_SIZE..NONCE_SIZE + 4]
+            .try_into()
+            .expect("帧头过短"),
+    );
+    assert_eq!(received_epoch, frame_epoch);
+    assert_eq!(u64::from(received_epoch), epoch.version);
 
     // 验证 DeviceHeader 的 epoch 与 Frame 的 epoch 一致
     assert!(header.belongs_to_epoch(&epoch));
@@ -722,7 +736,7 @@ fn test_inv_2_epoch_upgrade_header_migration() {
     // 发送纪元 1 消息
     let _ = protocol
         .send_message(
-            PayloadType::Sync,
+            PayloadType::EpochSync,
             b"epoch 1".to_vec(),
             epoch_v1.version as u32,
         )
@@ -731,7 +745,7 @@ fn test_inv_2_epoch_upgrade_header_migration() {
     // 发送纪元 2 消息
     let _ = protocol
         .send_message(
-            PayloadType::Sync,
+            PayloadType::EpochSync,
             b"epoch 2".to_vec(),
             epoch_v2.version as u32,
         )